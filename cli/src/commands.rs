@@ -0,0 +1,252 @@
+// Copyright (c) 2025 Robert Schiwon
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use sciformats::{
+    api::{ExportFormat, Node, Parameter, Reader, Scanner},
+    common::{BufSeekRead, ScannerRepository, SfError, SfErrorKind, SharedSeekRead},
+};
+use std::{
+    error::Error,
+    fmt,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+/// The scanner repository used by all commands.
+pub(crate) type Repository = ScannerRepository<SharedSeekRead<BufSeekRead<File>>>;
+
+/// An error when executing a command.
+#[derive(Debug)]
+pub(crate) enum CliError {
+    Io(io::Error),
+    Sf(SfError),
+}
+
+impl CliError {
+    /// The process exit code for the error.
+    pub(crate) fn exit_code(&self) -> u8 {
+        match self {
+            CliError::Io(_) => 6,
+            CliError::Sf(error) => match error.kind() {
+                SfErrorKind::UnsupportedFormat => 3,
+                SfErrorKind::IllegalNodePath | SfErrorKind::IllegalDataRange => 4,
+                SfErrorKind::CorruptData => 5,
+                SfErrorKind::Io => 6,
+                _ => 1,
+            },
+        }
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let error: &dyn Error = match self {
+            CliError::Io(error) => error,
+            CliError::Sf(error) => error,
+        };
+        write!(f, "{}", error)?;
+        let mut source = error.source();
+        while let Some(e) = source {
+            write!(f, ": {}", e)?;
+            source = e.source();
+        }
+        Ok(())
+    }
+}
+
+impl From<io::Error> for CliError {
+    fn from(value: io::Error) -> Self {
+        CliError::Io(value)
+    }
+}
+
+impl From<SfError> for CliError {
+    fn from(value: SfError) -> Self {
+        CliError::Sf(value)
+    }
+}
+
+/// Prints the detected format, the candidate scanners, and the size of a data set.
+pub(crate) fn info(repo: &Repository, file: &Path, out: &mut dyn Write) -> Result<(), CliError> {
+    let (path, mut input) = open(file)?;
+    let size = file.metadata()?.len();
+    let candidates = repo.get_candidates(&path, &mut input)?;
+    let (format, reader) = repo.get_named_reader(&path, input)?;
+    let root = reader.read("/")?;
+
+    writeln!(out, "File: {}", path)?;
+    writeln!(out, "Size: {} bytes", size)?;
+    writeln!(out, "Format: {}", format)?;
+    let candidates: Vec<String> = candidates
+        .iter()
+        .map(|c| format!("{} ({:?})", c.name, c.confidence))
+        .collect();
+    writeln!(out, "Candidates: {}", candidates.join(", "))?;
+    writeln!(out, "Name: {}", root.name)?;
+    writeln!(out, "Child nodes: {}", root.child_node_names.len())?;
+    write_warnings(reader.as_ref(), out)?;
+    Ok(())
+}
+
+/// Prints the node hierarchy of a data set.
+pub(crate) fn tree(repo: &Repository, file: &Path, out: &mut dyn Write) -> Result<(), CliError> {
+    let (path, input) = open(file)?;
+    let reader = repo.get_reader(&path, input)?;
+    write_tree(reader.as_ref(), "/", 0, out)
+}
+
+/// Prints the parameters, metadata, data, and table of a node.
+pub(crate) fn cat(
+    repo: &Repository,
+    file: &Path,
+    node_path: &str,
+    out: &mut dyn Write,
+) -> Result<(), CliError> {
+    let (path, input) = open(file)?;
+    let reader = repo.get_reader(&path, input)?;
+    let node = reader.read(node_path)?;
+    write_node(&node, out)
+}
+
+/// Exports a data set to a file or, if no output file is given, to the output.
+pub(crate) fn export(
+    repo: &Repository,
+    file: &Path,
+    format: ExportFormat,
+    output: Option<&Path>,
+    out: &mut dyn Write,
+) -> Result<(), CliError> {
+    let (path, input) = open(file)?;
+    let reader = repo.get_reader(&path, input)?;
+    match output {
+        Some(output) => {
+            let mut writer = BufWriter::new(File::create(output)?);
+            reader.export(format, &mut writer)?;
+            writer.flush()?;
+        }
+        None => reader.export(format, out)?,
+    }
+    Ok(())
+}
+
+fn open(file: &Path) -> Result<(String, SharedSeekRead<BufSeekRead<File>>), CliError> {
+    let path = file.to_string_lossy().into_owned();
+    let input = File::open(file)
+        .map_err(|e| io::Error::new(e.kind(), format!("Error opening {}: {}", path, e)))?;
+    Ok((path, SharedSeekRead::new(BufSeekRead::new(input))))
+}
+
+fn write_warnings(reader: &dyn Reader, out: &mut dyn Write) -> Result<(), CliError> {
+    let warnings = reader.get_warnings();
+    writeln!(out, "Warnings: {}", warnings.len())?;
+    for warning in warnings {
+        match &warning.location {
+            Some(location) => writeln!(out, "  {} ({:?})", warning.message, location)?,
+            None => writeln!(out, "  {}", warning.message)?,
+        }
+    }
+    Ok(())
+}
+
+fn write_tree(
+    reader: &dyn Reader,
+    node_path: &str,
+    depth: usize,
+    out: &mut dyn Write,
+) -> Result<(), CliError> {
+    let node = reader.read(node_path)?;
+    writeln!(out, "{}{} {}", "  ".repeat(depth), node_path, node.name)?;
+    for index in 0..node.child_node_names.len() {
+        let child_path = match node_path {
+            "/" => format!("/{}", index),
+            _ => format!("{}/{}", node_path, index),
+        };
+        write_tree(reader, &child_path, depth + 1, out)?;
+    }
+    Ok(())
+}
+
+fn write_node(node: &Node, out: &mut dyn Write) -> Result<(), CliError> {
+    writeln!(out, "Name: {}", node.name)?;
+    if !node.parameters.is_empty() {
+        writeln!(out, "Parameters:")?;
+        for parameter in &node.parameters {
+            match parameter {
+                Parameter::KeyValue(key, value) => writeln!(out, "  {}: {}", key, value)?,
+                Parameter::Value(value) => writeln!(out, "  {}", value)?,
+            }
+        }
+    }
+    if !node.metadata.is_empty() {
+        writeln!(out, "Metadata:")?;
+        for (key, value) in &node.metadata {
+            writeln!(out, "  {}: {}", key, value)?;
+        }
+    }
+    if !node.data.is_empty() {
+        writeln!(out, "Data:")?;
+        for point in &node.data {
+            writeln!(out, "  {}\t{}", point.x, point.y)?;
+        }
+    }
+    if let Some(table) = &node.table {
+        writeln!(out, "Table:")?;
+        let names: Vec<&str> = table.column_names.iter().map(|c| c.name.as_str()).collect();
+        writeln!(out, "  {}", names.join("\t"))?;
+        for row in &table.rows {
+            let cells: Vec<String> = table
+                .column_names
+                .iter()
+                .map(|c| row.get(&c.key).map(|v| v.to_string()).unwrap_or_default())
+                .collect();
+            writeln!(out, "  {}", cells.join("\t"))?;
+        }
+    }
+    if let Some(nd_array) = &node.nd_array {
+        let shape: Vec<String> = nd_array
+            .axes
+            .iter()
+            .map(|axis| format!("{} ({})", axis.name, axis.values.len()))
+            .collect();
+        writeln!(out, "Array: {} [{}]", nd_array.name, shape.join(" x "))?;
+    }
+    if let Some(complex_data) = &node.complex_data {
+        writeln!(
+            out,
+            "Complex data: {} [{} ({})]",
+            complex_data.name,
+            complex_data.axis.name,
+            complex_data.axis.values.len()
+        )?;
+    }
+    if !node.links.is_empty() {
+        writeln!(out, "Links:")?;
+        for link in &node.links {
+            writeln!(out, "  {}: {}", link.relation, link.path)?;
+        }
+    }
+    if !node.child_node_names.is_empty() {
+        writeln!(out, "Child nodes:")?;
+        for (index, name) in node.child_node_names.iter().enumerate() {
+            writeln!(out, "  {}: {}", index, name)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,168 @@
+// Copyright (c) 2025 Robert Schiwon
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! A command-line tool for inspecting and converting scientific data sets.
+
+mod commands;
+
+use clap::{Parser, Subcommand, ValueEnum};
+use commands::CliError;
+use sciformats::{
+    api::{ExportFormat, ParseOptions},
+    common::ScannerRepository,
+};
+use std::{
+    io::{self, BufWriter, Write},
+    path::PathBuf,
+    process::ExitCode,
+};
+
+const EXIT_CODES_HELP: &str = "\
+Exit codes:
+  0  Success
+  1  Other error
+  2  Illegal command line arguments
+  3  Unsupported format
+  4  Illegal node path or data range
+  5  Corrupt data
+  6  I/O error";
+
+#[derive(Parser)]
+#[command(name = "sciformats", version, about, after_help = EXIT_CODES_HELP)]
+struct Cli {
+    /// Only recognize data sets whose extension matches their format.
+    #[arg(long, global = true)]
+    strict_extensions: bool,
+
+    /// Fail on deviations from the format specification instead of warning about them.
+    #[arg(long, global = true)]
+    strict_parsing: bool,
+
+    /// Fail on data sets whose integrity checksum does not match their content or cannot be verified.
+    #[arg(long, global = true)]
+    strict_integrity: bool,
+
+    /// Only index large data sets when opening them and parse their parts when read.
+    #[arg(long, global = true)]
+    on_demand: bool,
+
+    /// Read data sets stored as directory, e.g., Bruker experiment directories, from the file system for paths pointing to a file in them.
+    #[arg(long, global = true)]
+    read_directories: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Show the detected format, the candidate scanners, and the size of a data set.
+    Info {
+        /// The data set.
+        file: PathBuf,
+    },
+    /// Print the node hierarchy of a data set.
+    Tree {
+        /// The data set.
+        file: PathBuf,
+    },
+    /// Print the parameters, metadata, data, and table of a node.
+    Cat {
+        /// The data set.
+        file: PathBuf,
+        /// The node path, e.g., "/0/1".
+        #[arg(default_value = "/")]
+        node_path: String,
+    },
+    /// Export a data set.
+    Export {
+        /// The data set.
+        file: PathBuf,
+        /// The export format.
+        #[arg(short, long, value_enum, default_value_t = Format::Json)]
+        format: Format,
+        /// The output file. Standard output if omitted.
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Json,
+    /// Comma separated values, one set of files per node bundled in a ZIP archive.
+    Csv,
+    /// Tab separated values, one set of files per node bundled in a ZIP archive.
+    Tsv,
+    /// JCAMP-DX, one block per node.
+    Jdx,
+    /// GAML, one experiment per node holding data.
+    Gaml,
+    /// MDL Molfile, the chemical structure of a data set, e.g., of a JCAMP-CS block.
+    Molfile,
+}
+
+impl From<Format> for ExportFormat {
+    fn from(value: Format) -> Self {
+        match value {
+            Format::Json => ExportFormat::Json,
+            Format::Csv => ExportFormat::Csv,
+            Format::Tsv => ExportFormat::Tsv,
+            Format::Jdx => ExportFormat::Jdx,
+            Format::Gaml => ExportFormat::Gaml,
+            Format::Molfile => ExportFormat::Molfile,
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let mut repo = ScannerRepository::init_all();
+    repo.set_strict_extensions(cli.strict_extensions);
+    let mut options = match cli.strict_parsing {
+        true => ParseOptions::strict(),
+        false => ParseOptions::default(),
+    };
+    options.strict_integrity = cli.strict_integrity;
+    options.on_demand = cli.on_demand;
+    options.read_directories = cli.read_directories;
+    repo.set_parse_options(&options);
+
+    let mut out = BufWriter::new(io::stdout().lock());
+    let result = match &cli.command {
+        Command::Info { file } => commands::info(&repo, file, &mut out),
+        Command::Tree { file } => commands::tree(&repo, file, &mut out),
+        Command::Cat { file, node_path } => commands::cat(&repo, file, node_path, &mut out),
+        Command::Export {
+            file,
+            format,
+            output,
+        } => commands::export(&repo, file, (*format).into(), output.as_deref(), &mut out),
+    }
+    .and_then(|()| out.flush().map_err(CliError::from));
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("Error: {error}");
+            ExitCode::from(error.exit_code())
+        }
+    }
+}
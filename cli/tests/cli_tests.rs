@@ -0,0 +1,211 @@
+// Copyright (c) 2025 Robert Schiwon
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use std::{
+    path::PathBuf,
+    process::{Command, Output},
+};
+
+fn resource(name: &str) -> PathBuf {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("../examples/_resources");
+    path.push(name);
+    path
+}
+
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_sciformats"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn info_prints_format_and_size() {
+    let file = resource("CompoundFile.jdx");
+    let output = run(&["info", file.to_str().unwrap()]);
+
+    assert!(output.status.success());
+    let out = stdout(&output);
+    assert!(out.contains("Format: JCAMP-DX"));
+    assert!(out.contains("Size: 3981 bytes"));
+    assert!(out.contains("Child nodes: 9"));
+}
+
+#[test]
+fn tree_prints_node_hierarchy() {
+    let file = resource("CompoundFile.jdx");
+    let output = run(&["tree", file.to_str().unwrap()]);
+
+    assert!(output.status.success());
+    let out = stdout(&output);
+    assert!(out.starts_with("/ CompoundFile.jdx\n"));
+    assert!(out.contains("\n  /3 NTUPLES Block\n"));
+    assert!(out.contains("\n      /3/0/1 N=2 - SPECTRUM/IMAG\n"));
+}
+
+#[test]
+fn cat_prints_node() {
+    let file = resource("andi_chrom_valid.cdf");
+    let output = run(&["cat", file.to_str().unwrap(), "/0"]);
+
+    assert!(output.status.success());
+    let out = stdout(&output);
+    assert!(out.starts_with("Name: Admin Data\n"));
+    assert!(out.contains("Parameters:\n"));
+    assert!(out.contains("  Dataset Completeness: C1+C2\n"));
+}
+
+#[test]
+fn cat_prints_node_by_block_id() {
+    let mut file = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    file.push("../lib-rs/tests/jdx/resources/StructureLink.jdx");
+    let output = run(&["cat", file.to_str().unwrap(), "/#2"]);
+
+    assert!(output.status.success());
+    assert!(stdout(&output).starts_with("Name: Acetic acid IR spectrum\n"));
+}
+
+#[test]
+fn cat_fails_for_illegal_node_path() {
+    let file = resource("CompoundFile.jdx");
+    let output = run(&["cat", file.to_str().unwrap(), "/9"]);
+
+    assert_eq!(Some(4), output.status.code());
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("Error: "));
+}
+
+#[test]
+fn export_writes_json() {
+    let file = resource("andi_chrom_valid.cdf");
+    let output = run(&["export", "--format", "json", file.to_str().unwrap()]);
+
+    assert!(output.status.success());
+    assert!(stdout(&output).starts_with("{\"format\":\"sciformats\""));
+}
+
+#[test]
+fn export_writes_jdx() {
+    let file = resource("andi_chrom_valid.cdf");
+    let output = run(&["export", "--format", "jdx", file.to_str().unwrap()]);
+
+    assert!(output.status.success());
+    let export = stdout(&output);
+    assert!(export.starts_with("##TITLE= andi_chrom_valid.cdf\r\n"));
+    assert!(export.contains("##DATA TYPE= LINK\r\n"));
+    assert!(export.contains("##XYDATA= (X++(Y..Y))\r\n"));
+}
+
+#[test]
+fn export_writes_gaml() {
+    let file = resource("andi_chrom_valid.cdf");
+    let output = run(&["export", "--format", "gaml", file.to_str().unwrap()]);
+
+    assert!(output.status.success());
+    let export = stdout(&output);
+    assert!(export.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n"));
+    assert!(export.contains("<GAML version=\"1.20\" name=\"andi_chrom_valid.cdf\">"));
+    assert!(export.contains("<Xdata units=\"SECONDS\""));
+}
+
+#[test]
+fn export_writes_molfile() {
+    let mut file = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    file.push("../lib-rs/tests/jdx/resources/StructureLink.jdx");
+    let output = run(&["export", file.to_str().unwrap(), "--format", "molfile"]);
+
+    assert!(output.status.success());
+    let out = stdout(&output);
+    assert!(out.starts_with("Acetic acid structure\n"));
+    assert!(out.contains("  4  3  0  0  0  0  0  0  0  0999 V2000\n"));
+    assert!(out.ends_with("M  END\n"));
+
+    let file = resource("CompoundFile.jdx");
+    let output = run(&["export", file.to_str().unwrap(), "--format", "molfile"]);
+    assert_eq!(Some(3), output.status.code());
+}
+
+#[test]
+fn export_writes_csv_bundle_to_file() {
+    let file = resource("andi_chrom_valid.cdf");
+    let output_file = std::env::temp_dir().join("sciformats_cli_export_test.zip");
+    let output = run(&[
+        "export",
+        "--format",
+        "csv",
+        "--output",
+        output_file.to_str().unwrap(),
+        file.to_str().unwrap(),
+    ]);
+
+    assert!(output.status.success());
+    let export = std::fs::read(&output_file).unwrap();
+    std::fs::remove_file(&output_file).unwrap();
+    // ZIP local file header signature
+    assert!(export.starts_with(b"PK\x03\x04"));
+}
+
+#[test]
+fn tree_reads_directory_only_if_enabled() {
+    let directory =
+        std::env::temp_dir().join(format!("sciformats_cli_bruker_{}", std::process::id()));
+    let experiment = directory.join("1");
+    std::fs::create_dir_all(&experiment).unwrap();
+    std::fs::write(
+        experiment.join("acqus"),
+        "##$TD= 2\n##$SW_h= 1000\n##$NC= 0\n##END=\n",
+    )
+    .unwrap();
+    std::fs::write(experiment.join("fid"), [0u8; 8]).unwrap();
+    let acqus = experiment.join("acqus");
+
+    let by_default = run(&["tree", acqus.to_str().unwrap()]);
+    let enabled = run(&["tree", "--read-directories", acqus.to_str().unwrap()]);
+    std::fs::remove_dir_all(&directory).unwrap();
+
+    assert_eq!(Some(3), by_default.status.code());
+    assert!(enabled.status.success());
+    assert!(stdout(&enabled).starts_with("/ 1\n"));
+}
+
+#[test]
+fn unsupported_format_fails() {
+    let file = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml");
+    let output = run(&["info", file.to_str().unwrap()]);
+
+    assert_eq!(Some(3), output.status.code());
+}
+
+#[test]
+fn missing_file_fails() {
+    let output = run(&["info", "does_not_exist.jdx"]);
+
+    assert_eq!(Some(6), output.status.code());
+}
+
+#[test]
+fn illegal_arguments_fail() {
+    let output = run(&["unknown"]);
+
+    assert_eq!(Some(2), output.status.code());
+}
@@ -18,14 +18,14 @@
 // CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
 use sciformats::api::{Node, Scanner};
-use sciformats::common::ScannerRepository;
+use sciformats::common::{ScannerRepository, SharedSeekRead};
 use std::fs::File;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Open file.
     let file_name = "CompoundFile.jdx";
     let file_path = format!("{}/../_resources/{}", env!("CARGO_MANIFEST_DIR"), file_name);
-    let mut file = File::open(&file_path)?;
+    let mut file = SharedSeekRead::new(File::open(&file_path)?);
 
     // Initialize scanner repository with all supported data types.
     let repo = ScannerRepository::init_all();
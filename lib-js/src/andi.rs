@@ -17,9 +17,12 @@
 // IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
 // CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
-use super::{BlobSeekRead, JsNode, JsReader, create_js_reader, create_js_scanner, map_to_js_err};
+use super::{
+    BlobSeekRead, JsNode, JsReader, create_js_reader, create_js_scanner, map_to_js_err,
+    map_to_parse_options,
+};
 use sciformats::{andi::andi_scanner::AndiScanner, api::Scanner};
-use wasm_bindgen::{JsError, prelude::wasm_bindgen};
+use wasm_bindgen::{JsError, JsValue, prelude::wasm_bindgen};
 use web_sys::Blob;
 
 create_js_scanner!(AndiScanner, JsAndiScanner);
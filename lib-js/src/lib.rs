@@ -27,8 +27,11 @@ use js_sys::{Array, Number, Object, Uint8Array};
 #[cfg(not(feature = "nodejs"))]
 use js_sys::{Array, Uint8Array};
 use sciformats::{
-    api::{ExportFormat, Node, Parameter, Reader, Scanner, SeekRead, Value},
-    common::{BufSeekRead, ScannerRepository},
+    api::{
+        Axis, ExportFormat, Node, Parameter, ParseOptions, ParseWarning, PointXy, Reader, Scanner,
+        SeekRead, Value,
+    },
+    common::{BufSeekRead, ScannerRepository, SfError, SfErrorLocation, SharedSeekRead},
 };
 use std::{
     error::Error,
@@ -71,6 +74,40 @@ fn map_to_jsvalue(value: &Value) -> JsValue {
 // API
 // -------------------------------------------------
 
+fn map_to_js_data(data: &[PointXy]) -> Vec<JsValue> {
+    let mut vec: Vec<JsValue> = vec![];
+    for xy in data {
+        let x = JsValue::from_f64(xy.x);
+        let y = JsValue::from_f64(xy.y);
+        let js_xy = js_sys::Object::new();
+        let set_x_ret = js_sys::Reflect::set(&js_xy, &JsValue::from("x"), &x).unwrap();
+        let set_y_ret = js_sys::Reflect::set(&js_xy, &JsValue::from("y"), &y).unwrap();
+        if !set_x_ret || !set_y_ret {
+            panic!("Could not convert data point to JS Object.");
+        }
+        vec.push(js_xy.into());
+    }
+    vec
+}
+
+fn map_to_js_axis(axis: &Axis) -> js_sys::Object {
+    let js_axis = js_sys::Object::new();
+    let set_name_ret =
+        js_sys::Reflect::set(&js_axis, &JsValue::from("name"), &JsValue::from(&axis.name)).unwrap();
+    let set_unit_ret = js_sys::Reflect::set(
+        &js_axis,
+        &JsValue::from("unit"),
+        &JsValue::from(axis.unit.as_ref()),
+    )
+    .unwrap();
+    let values = js_sys::Float64Array::from(axis.values.as_slice());
+    let set_values_ret = js_sys::Reflect::set(&js_axis, &JsValue::from("values"), &values).unwrap();
+    if !set_name_ret || !set_unit_ret || !set_values_ret {
+        panic!("Could not convert array axis to JS Object.");
+    }
+    js_axis
+}
+
 #[wasm_bindgen(js_name = Node)]
 pub struct JsNode {
     node: Node,
@@ -122,19 +159,7 @@ impl JsNode {
 
     #[wasm_bindgen(getter)]
     pub fn data(&self) -> Vec<JsValue> {
-        let mut vec: Vec<JsValue> = vec![];
-        for xy in &self.node.data {
-            let x = JsValue::from_f64(xy.x);
-            let y = JsValue::from_f64(xy.y);
-            let js_xy = js_sys::Object::new();
-            let set_x_ret = js_sys::Reflect::set(&js_xy, &JsValue::from("x"), &x).unwrap();
-            let set_y_ret = js_sys::Reflect::set(&js_xy, &JsValue::from("y"), &y).unwrap();
-            if !set_x_ret || !set_y_ret {
-                panic!("Could not convert data point to JS Object.");
-            }
-            vec.push(js_xy.into());
-        }
-        vec
+        map_to_js_data(&self.node.data)
     }
 
     #[wasm_bindgen(getter)]
@@ -200,6 +225,103 @@ impl JsNode {
         js_table
     }
 
+    #[wasm_bindgen(getter, js_name = ndArray)]
+    pub fn nd_array(&self) -> Option<js_sys::Object> {
+        let nd_array = self.node.nd_array.as_ref()?;
+
+        let js_axes: js_sys::Array = js_sys::Array::new();
+        for axis in &nd_array.axes {
+            js_axes.push(&map_to_js_axis(axis));
+        }
+
+        let js_nd_array = js_sys::Object::new();
+        let set_name_ret = js_sys::Reflect::set(
+            &js_nd_array,
+            &JsValue::from("name"),
+            &JsValue::from(&nd_array.name),
+        )
+        .unwrap();
+        let set_unit_ret = js_sys::Reflect::set(
+            &js_nd_array,
+            &JsValue::from("unit"),
+            &JsValue::from(nd_array.unit.as_ref()),
+        )
+        .unwrap();
+        let set_axes_ret =
+            js_sys::Reflect::set(&js_nd_array, &JsValue::from("axes"), &js_axes).unwrap();
+        let values = js_sys::Float64Array::from(nd_array.values.as_slice());
+        let set_values_ret =
+            js_sys::Reflect::set(&js_nd_array, &JsValue::from("values"), &values).unwrap();
+        if !set_name_ret || !set_unit_ret || !set_axes_ret || !set_values_ret {
+            panic!("Could not populate array JS Object.");
+        }
+
+        Some(js_nd_array)
+    }
+
+    #[wasm_bindgen(getter, js_name = complexData)]
+    pub fn complex_data(&self) -> Option<js_sys::Object> {
+        let complex_data = self.node.complex_data.as_ref()?;
+
+        let js_complex_data = js_sys::Object::new();
+        let set_name_ret = js_sys::Reflect::set(
+            &js_complex_data,
+            &JsValue::from("name"),
+            &JsValue::from(&complex_data.name),
+        )
+        .unwrap();
+        let set_unit_ret = js_sys::Reflect::set(
+            &js_complex_data,
+            &JsValue::from("unit"),
+            &JsValue::from(complex_data.unit.as_ref()),
+        )
+        .unwrap();
+        let set_axis_ret = js_sys::Reflect::set(
+            &js_complex_data,
+            &JsValue::from("axis"),
+            &map_to_js_axis(&complex_data.axis),
+        )
+        .unwrap();
+        let mut set_values_ret = true;
+        for (key, values) in [
+            ("real", complex_data.real.as_slice()),
+            ("imaginary", complex_data.imaginary.as_slice()),
+            ("magnitude", complex_data.get_magnitude().as_slice()),
+            ("phase", complex_data.get_phase().as_slice()),
+        ] {
+            let js_values = js_sys::Float64Array::from(values);
+            set_values_ret &=
+                js_sys::Reflect::set(&js_complex_data, &JsValue::from(key), &js_values).unwrap();
+        }
+        if !set_name_ret || !set_unit_ret || !set_axis_ret || !set_values_ret {
+            panic!("Could not populate complex data JS Object.");
+        }
+
+        Some(js_complex_data)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn links(&self) -> Vec<js_sys::Object> {
+        let mut vec: Vec<js_sys::Object> = vec![];
+        for link in &self.node.links {
+            let js_link = js_sys::Object::new();
+            let set_relation_ret = js_sys::Reflect::set(
+                &js_link,
+                &JsValue::from("relation"),
+                &JsValue::from(&link.relation),
+            )
+            .unwrap();
+            let set_path_ret =
+                js_sys::Reflect::set(&js_link, &JsValue::from("path"), &JsValue::from(&link.path))
+                    .unwrap();
+            if !set_relation_ret || !set_path_ret {
+                panic!("Could not populate link JS Object.");
+            }
+            vec.push(js_link);
+        }
+        vec
+    }
+
     #[wasm_bindgen(getter, js_name = childNodeNames)]
     pub fn child_node_names(&self) -> Vec<JsValue> {
         let mut vec: Vec<JsValue> = vec![];
@@ -231,6 +353,30 @@ impl JsReader {
         }
     }
 
+    #[wasm_bindgen(js_name = readDataLength)]
+    pub fn read_data_len(&self, path: &str) -> Result<usize, JsError> {
+        self.reader
+            .read_data_len(path)
+            .map_err(|e| map_to_js_err(&e))
+    }
+
+    #[wasm_bindgen(js_name = readData)]
+    pub fn read_data(&self, path: &str, start: usize, end: usize) -> Result<Vec<JsValue>, JsError> {
+        match self.reader.read_data(path, start..end) {
+            Ok(data) => Ok(map_to_js_data(&data)),
+            Err(error) => Err(map_to_js_err(&error)),
+        }
+    }
+
+    #[wasm_bindgen(js_name = getWarnings)]
+    pub fn get_warnings(&self) -> Vec<JsValue> {
+        self.reader
+            .get_warnings()
+            .iter()
+            .map(map_to_js_warning)
+            .collect()
+    }
+
     #[wasm_bindgen(js_name = getExportFormats)]
     pub fn get_export_formats(&self) -> Vec<String> {
         let mut str_formats = vec![];
@@ -238,6 +384,11 @@ impl JsReader {
         for format in formats {
             match format {
                 ExportFormat::Json => str_formats.push("Json".to_owned()),
+                ExportFormat::Csv => str_formats.push("Csv".to_owned()),
+                ExportFormat::Tsv => str_formats.push("Tsv".to_owned()),
+                ExportFormat::Jdx => str_formats.push("Jdx".to_owned()),
+                ExportFormat::Gaml => str_formats.push("Gaml".to_owned()),
+                ExportFormat::Molfile => str_formats.push("Molfile".to_owned()),
             }
         }
         str_formats
@@ -269,14 +420,18 @@ impl JsReader {
     }
 
     fn export(&self, format: &str, mut writer: &mut impl Write) -> Result<(), JsError> {
-        match format {
-            "Json" => self
-                .reader
-                .export(ExportFormat::Json, &mut writer)
-                .map_err(|e| map_to_js_err(&e)),
-            _ => Err(JsError::new(&format!("Unknown export format: {}", format))),
-        }?;
-        Ok(())
+        let export_format = match format {
+            "Json" => ExportFormat::Json,
+            "Csv" => ExportFormat::Csv,
+            "Tsv" => ExportFormat::Tsv,
+            "Jdx" => ExportFormat::Jdx,
+            "Gaml" => ExportFormat::Gaml,
+            "Molfile" => ExportFormat::Molfile,
+            _ => return Err(JsError::new(&format!("Unknown export format: {}", format))),
+        };
+        self.reader
+            .export(export_format, &mut writer)
+            .map_err(|e| map_to_js_err(&e))
     }
 }
 
@@ -612,7 +767,7 @@ impl Read for FdSeekRead {
 
 #[wasm_bindgen(js_name = ScannerRepository)]
 pub struct JsScannerRepository {
-    repo: ScannerRepository<Box<dyn SeekRead>>,
+    repo: ScannerRepository<SharedSeekRead<Box<dyn SeekRead>>>,
 }
 
 #[wasm_bindgen(js_class = ScannerRepository)]
@@ -623,6 +778,17 @@ impl JsScannerRepository {
         Self { repo }
     }
 
+    #[wasm_bindgen(js_name = setStrictExtensions)]
+    pub fn js_set_strict_extensions(&mut self, strict_extensions: bool) {
+        self.repo.set_strict_extensions(strict_extensions);
+    }
+
+    #[wasm_bindgen(js_name = setStrictParsing)]
+    pub fn js_set_strict_parsing(&mut self, strict_parsing: bool) {
+        self.repo
+            .set_parse_options(&map_to_parse_options(strict_parsing));
+    }
+
     #[wasm_bindgen(js_name = isRecognized)]
     pub fn js_is_recognized(&self, path: &str, input: &JsValue) -> bool {
         // use web_sys::console;
@@ -630,15 +796,18 @@ impl JsScannerRepository {
         let seek_read_res = map_js_input_to_seekread(input);
         match seek_read_res {
             Err(_err) => false,
-            Ok(mut seek_read) => self.repo.is_recognized(path, &mut seek_read),
+            Ok(seek_read) => {
+                let mut input = SharedSeekRead::new(seek_read);
+                self.repo.is_recognized(path, &mut input)
+            }
         }
     }
 
     #[wasm_bindgen(js_name = getReader)]
     pub fn js_get_reader(&self, path: &str, input: &JsValue) -> Result<JsReader, JsError> {
         let seek_read = map_js_input_to_seekread(input)?;
-        let input = BufSeekRead::new(seek_read);
-        let reader_result = self.repo.get_reader(path, Box::new(input));
+        let input: Box<dyn SeekRead> = Box::new(BufSeekRead::new(seek_read));
+        let reader_result = self.repo.get_reader(path, SharedSeekRead::new(input));
         match reader_result {
             Ok(reader) => Ok(JsReader::from(reader)),
             Err(error) => Err(map_to_js_err(&error)),
@@ -753,6 +922,14 @@ macro_rules! create_js_scanner {
                 }
             }
 
+            #[wasm_bindgen(js_name = setStrictParsing)]
+            pub fn js_set_strict_parsing(&mut self, strict_parsing: bool) {
+                Scanner::<BlobSeekRead>::set_parse_options(
+                    &mut self.scanner,
+                    &map_to_parse_options(strict_parsing),
+                );
+            }
+
             #[wasm_bindgen(js_name = isRecognized)]
             pub fn js_is_recognized(&self, path: &str, input: &Blob) -> bool {
                 let mut blob = BlobSeekRead::new(input.clone());
@@ -796,6 +973,11 @@ macro_rules! create_js_reader {
                 self.reader.read(path)
             }
 
+            #[wasm_bindgen(js_name = getWarnings)]
+            pub fn get_warnings(&self) -> Vec<JsValue> {
+                self.reader.get_warnings()
+            }
+
             #[wasm_bindgen(js_name = getExportFormats)]
             pub fn get_export_formats(&self) -> Vec<String> {
                 self.reader.get_export_formats()
@@ -816,7 +998,7 @@ macro_rules! create_js_reader {
 }
 pub(crate) use create_js_reader;
 
-pub(crate) fn map_to_js_err(error: &dyn Error) -> JsError {
+pub(crate) fn map_to_js_err(error: &(dyn Error + 'static)) -> JsError {
     let mut err_str = error.to_string();
     let mut source = error.source();
     while let Some(nested_err) = source {
@@ -824,7 +1006,71 @@ pub(crate) fn map_to_js_err(error: &dyn Error) -> JsError {
         err_str += nested_err.to_string().as_str();
         source = nested_err.source();
     }
-    JsError::new(&err_str)
+    let js_error = JsError::new(&err_str);
+    if let Some(sf_error) = error.downcast_ref::<SfError>() {
+        // expose kind and location as properties of the JS Error
+        let js_value = JsValue::from(js_error.clone());
+        set_js_property(
+            &js_value,
+            "kind",
+            &JsValue::from(sf_error.kind().to_string()),
+        );
+        if let Some(location) = sf_error.location() {
+            set_js_property(&js_value, "location", &map_to_js_location(location));
+        }
+    }
+    js_error
+}
+
+fn map_to_js_location(location: &SfErrorLocation) -> JsValue {
+    let js_location: JsValue = js_sys::Object::new().into();
+    match location {
+        SfErrorLocation::ByteOffset(byte_offset) => {
+            set_js_property(
+                &js_location,
+                "byteOffset",
+                &JsValue::from_f64(*byte_offset as f64),
+            );
+        }
+        SfErrorLocation::Line { line, byte_offset } => {
+            set_js_property(&js_location, "line", &JsValue::from_f64(*line as f64));
+            set_js_property(
+                &js_location,
+                "byteOffset",
+                &JsValue::from_f64(*byte_offset as f64),
+            );
+        }
+        SfErrorLocation::ElementPath(path) => {
+            set_js_property(&js_location, "elementPath", &JsValue::from(path));
+        }
+        SfErrorLocation::Variable(name) => {
+            set_js_property(&js_location, "variable", &JsValue::from(name));
+        }
+    }
+    js_location
+}
+
+fn map_to_js_warning(warning: &ParseWarning) -> JsValue {
+    let js_warning: JsValue = js_sys::Object::new().into();
+    set_js_property(&js_warning, "message", &JsValue::from(&warning.message));
+    if let Some(location) = &warning.location {
+        set_js_property(&js_warning, "location", &map_to_js_location(location));
+    }
+    js_warning
+}
+
+pub(crate) fn map_to_parse_options(strict_parsing: bool) -> ParseOptions {
+    if strict_parsing {
+        ParseOptions::strict()
+    } else {
+        ParseOptions::default()
+    }
+}
+
+fn set_js_property(target: &JsValue, key: &str, value: &JsValue) {
+    // setting a property on a plain object or Error cannot fail, and failing to
+    // add error details should not mask the original error
+    let _ = js_sys::Reflect::set(target, &JsValue::from(key), value);
 }
 
 #[cfg(feature = "nodejs")]
@@ -939,6 +1185,9 @@ mod tests {
                     ],
                 }),
                 // child_node_names: vec![],
+                nd_array: None,
+                complex_data: None,
+                links: vec![],
                 child_node_names: vec![
                     "child node name 0".to_owned(),
                     "child node name 1".to_owned(),
@@ -950,6 +1199,9 @@ mod tests {
                 data: vec![],
                 metadata: vec![],
                 table: None,
+                nd_array: None,
+                complex_data: None,
+                links: vec![],
                 child_node_names: vec![],
             };
             let child1 = Node {
@@ -958,6 +1210,9 @@ mod tests {
                 data: vec![],
                 metadata: vec![],
                 table: None,
+                nd_array: None,
+                complex_data: None,
+                links: vec![],
                 child_node_names: vec![],
             };
 
@@ -965,7 +1220,7 @@ mod tests {
                 "" | "/" => Ok(root),
                 "/0" => Ok(child0),
                 "/1" => Ok(child1),
-                _ => Err(SfError::new(&format!("Illegal path: {}", path)).into()),
+                _ => Err(SfError::new(&format!("Illegal path: {}", path))),
             }
         }
     }
@@ -981,6 +1236,9 @@ mod tests {
             data: vec![],
             metadata: vec![],
             table: None,
+            nd_array: None,
+            complex_data: None,
+            links: vec![],
             child_node_names: vec![],
         };
 
@@ -1093,7 +1351,7 @@ mod tests {
             .unwrap()
             .as_bool()
             .unwrap();
-        assert_eq!(true, value_1);
+        assert!(value_1);
         let key_2 = js_sys::Reflect::get(&params[2], &JsValue::from("key"))
             .unwrap()
             .as_string()
@@ -1177,19 +1435,19 @@ mod tests {
         assert_eq!(4.0, y_1);
 
         let metadata = &node.metadata();
-        let metadata_value0 = js_sys::Reflect::get(&metadata, &JsValue::from("mk0"))
+        let metadata_value0 = js_sys::Reflect::get(metadata, &JsValue::from("mk0"))
             .unwrap()
             .as_string()
             .unwrap();
         assert_eq!("mv0", metadata_value0);
-        let metadata_value1 = js_sys::Reflect::get(&metadata, &JsValue::from("mk1"))
+        let metadata_value1 = js_sys::Reflect::get(metadata, &JsValue::from("mk1"))
             .unwrap()
             .as_string()
             .unwrap();
         assert_eq!("mv1", metadata_value1);
 
         let table = &node.table();
-        let column_names = js_sys::Reflect::get(&table, &JsValue::from("columnNames")).unwrap();
+        let column_names = js_sys::Reflect::get(table, &JsValue::from("columnNames")).unwrap();
         let columns = js_sys::Array::from(&column_names);
         assert_eq!(1, columns.length());
         let column_0 = columns.get(0);
@@ -1203,7 +1461,7 @@ mod tests {
             .as_string()
             .unwrap();
         assert_eq!("col name", column_name_0);
-        let table_rows = js_sys::Reflect::get(&table, &JsValue::from("rows")).unwrap();
+        let table_rows = js_sys::Reflect::get(table, &JsValue::from("rows")).unwrap();
         let rows = js_sys::Array::from(&table_rows);
         assert_eq!(8, rows.length());
         let row_0 = rows.get(0);
@@ -1217,7 +1475,7 @@ mod tests {
             .unwrap()
             .as_bool()
             .unwrap();
-        assert_eq!(true, cell_value_1);
+        assert!(cell_value_1);
         let row_2 = rows.get(2);
         let cell_value_2 = js_sys::Reflect::get(&row_2, &JsValue::from("col key"))
             .unwrap()
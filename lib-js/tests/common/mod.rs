@@ -19,8 +19,8 @@
 
 use super::open_files;
 use sciformats::{
-    api::{Scanner, SeekRead},
-    common::{BufSeekRead, ScannerRepository},
+    api::Scanner,
+    common::{BufSeekRead, ScannerRepository, SharedSeekRead},
 };
 use wasm_bindgen_test::wasm_bindgen_test;
 
@@ -39,7 +39,7 @@ open_files!(
 fn scanner_repository_recognizes_valid_file() {
     let repo = ScannerRepository::init_all();
     let (path, file) = open_file(ANDI_CHROM_VALID_FILE_PATH);
-    let mut input: Box<dyn SeekRead> = Box::new(file);
+    let mut input = SharedSeekRead::new(file);
     assert!(repo.is_recognized(&path, &mut input));
 }
 
@@ -48,7 +48,7 @@ fn scanner_repository_recognizes_valid_file() {
 fn scanner_repository_rejects_invalid_file() {
     let repo = ScannerRepository::init_all();
     let (path, file) = open_file(ANDI_INVALID_FILE_PATH);
-    let mut input: Box<dyn SeekRead> = Box::new(file);
+    let mut input = SharedSeekRead::new(file);
     assert!(!repo.is_recognized(&path, &mut input));
 }
 
@@ -57,7 +57,7 @@ fn scanner_repository_rejects_invalid_file() {
 fn scanner_repository_returns_reader_for_valid_file() {
     let repo = ScannerRepository::init_all();
     let (path, file) = open_file(ANDI_CHROM_VALID_FILE_PATH);
-    let input: Box<dyn SeekRead> = Box::new(file);
+    let input = SharedSeekRead::new(file);
     assert!(repo.get_reader(&path, input).is_ok());
 }
 
@@ -66,7 +66,7 @@ fn scanner_repository_returns_reader_for_valid_file() {
 fn scanner_repository_returns_error_for_valid_file() {
     let repo = ScannerRepository::init_all();
     let (path, file) = open_file(ANDI_INVALID_FILE_PATH);
-    let input: Box<dyn SeekRead> = Box::new(file);
+    let input = SharedSeekRead::new(file);
     assert!(repo.get_reader(&path, input).is_err());
 }
 
@@ -76,7 +76,7 @@ fn buf_seek_read_allows_valid_file_reading() {
     let repo = ScannerRepository::init_all();
     let (path, file) = open_file(ANDI_CHROM_VALID_FILE_PATH);
     let buf_seek_read = BufSeekRead::new(file);
-    let input: Box<dyn SeekRead> = Box::new(buf_seek_read);
+    let input = SharedSeekRead::new(buf_seek_read);
     let reader = repo.get_reader(&path, input).unwrap();
     assert!(reader.read("/").is_ok());
 }
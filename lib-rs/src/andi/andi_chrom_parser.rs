@@ -18,40 +18,69 @@
 // CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
 use super::andi_utils::{
+    check_global_attr_name, check_global_attr_zero_terminated, check_var_attr_zero_terminated,
     read_global_attr_str, read_index_from_slice, read_index_from_var_2d_string,
     read_index_from_var_f32, read_multi_string_var, read_optional_var, trim_zeros_in_place,
+    var_location,
 };
 use super::{
     AndiDatasetCompleteness,
-    andi_utils::{read_optional_var_or_attr_f32, read_scalar_var_f32},
-};
-use crate::api::Parser;
-use crate::common::SfError;
-use std::{
-    cell::RefCell,
-    error::Error,
-    io::{Read, Seek},
-    rc::Rc,
-    str::FromStr,
+    andi_utils::{CdfInput, read_optional_var_or_attr_f32, read_scalar_var_f32},
 };
+use crate::api::{ParseOptions, ParseWarning, Parser, SeekRead};
+use crate::common::{SfError, SfErrorKind, WarningCollector};
+use std::{error::Error, str::FromStr};
 
 pub struct AndiChromParser {}
 
 impl AndiChromParser {
-    pub(crate) fn parse_cdf(reader: netcdf3::FileReader) -> Result<AndiChromFile, SfError> {
-        AndiChromFile::new(reader)
+    /// Parses AnDI data, checking the data against the specification as set in the options.
+    pub fn parse_with_options<T: SeekRead + 'static>(
+        name: &str,
+        input: T,
+        options: &ParseOptions,
+    ) -> Result<AndiChromFile, SfError> {
+        let input = CdfInput::new(name, Box::new(input));
+        let reader = input.open()?;
+        Self::parse_cdf(reader, input, options)
+    }
+
+    pub(crate) fn parse_cdf(
+        reader: netcdf3::FileReader,
+        input: CdfInput,
+        options: &ParseOptions,
+    ) -> Result<AndiChromFile, SfError> {
+        let mut collector = WarningCollector::new(options);
+        check_global_attr_zero_terminated(&reader, "dataset_completeness", &mut collector);
+        check_var_attr_zero_terminated(
+            &reader,
+            "ordinate_values",
+            "uniform_sampling_flag",
+            &mut collector,
+        );
+        check_global_attr_zero_terminated(&reader, "uniform_sampling_flag", &mut collector);
+        check_global_attr_name(&reader, "detector_unit", "detector_units", &mut collector);
+        check_global_attr_name(&reader, "retention_unit", "retention_units", &mut collector);
+        check_global_attr_name(
+            &reader,
+            "peak_amount_unit",
+            "peak_amount_units",
+            &mut collector,
+        );
+        let warnings = collector.into_warnings()?;
+        let mut file =
+            AndiChromFile::new(reader, input).map_err(|e| e.or_kind(SfErrorKind::CorruptData))?;
+        file.warnings = warnings;
+        Ok(file)
     }
 }
 
-impl<T: Seek + Read + 'static> Parser<T> for AndiChromParser {
+impl<T: SeekRead + 'static> Parser<T> for AndiChromParser {
     type R = AndiChromFile;
     type E = SfError;
 
     fn parse(name: &str, input: T) -> Result<Self::R, Self::E> {
-        let input_seek_read = Box::new(input);
-        let reader = netcdf3::FileReader::open_seek_read(name, input_seek_read)
-            .map_err(|e| SfError::from_source(e, "AnDI Error. Error parsing netCDF."))?;
-        Self::parse_cdf(reader)
+        Self::parse_with_options(name, input, &ParseOptions::default())
     }
 }
 
@@ -62,10 +91,11 @@ pub struct AndiChromFile {
     pub detection_method: AndiChromDetectionMethod,
     pub raw_data: AndiChromRawData,
     pub peak_processing_results: AndiChromPeakProcessingResults,
+    pub warnings: Vec<ParseWarning>,
 }
 
 impl AndiChromFile {
-    pub fn new(mut reader: netcdf3::FileReader) -> Result<Self, SfError> {
+    pub(crate) fn new(mut reader: netcdf3::FileReader, input: CdfInput) -> Result<Self, SfError> {
         let admin_data = AndiChromAdminData::new(&mut reader)
             .map_err(|e| SfError::from_source(e, "Error parsing AnDI Chrom admin data."))?;
         let sample_description = AndiChromSampleDescription::new(&mut reader)
@@ -73,12 +103,11 @@ impl AndiChromFile {
         let detection_method = AndiChromDetectionMethod::new(&mut reader)
             .map_err(|e| SfError::from_source(e, "Error parsing AnDI Chrom detection method."))?;
 
-        let reader_ref: Rc<RefCell<netcdf3::FileReader>> = Rc::new(RefCell::new(reader));
-
-        let raw_data = AndiChromRawData::new(Rc::clone(&reader_ref))
+        let raw_data = AndiChromRawData::new(&mut reader, input.clone())
             .map_err(|e| SfError::from_source(e, "Error parsing AnDI Chrom raw data."))?;
         let peak_processing_results = AndiChromPeakProcessingResults::new(
-            reader_ref,
+            &reader,
+            input,
             &raw_data.retention_unit,
             detection_method.detector_unit.as_deref(),
         )
@@ -92,6 +121,7 @@ impl AndiChromFile {
             detection_method,
             raw_data,
             peak_processing_results,
+            warnings: vec![],
         })
     }
 }
@@ -119,7 +149,7 @@ pub struct AndiChromAdminData {
 }
 
 impl AndiChromAdminData {
-    pub fn new(reader: &mut netcdf3::FileReader) -> Result<Self, Box<dyn Error>> {
+    pub fn new(reader: &mut netcdf3::FileReader) -> Result<Self, Box<dyn Error + Send + Sync>> {
         let dataset_completeness_attr = read_global_attr_str(reader, "dataset_completeness")
             .ok_or(SfError::new("Missing dataset_completeness attribute."))?;
         let dataset_completeness = AndiDatasetCompleteness::from_str(&dataset_completeness_attr)?;
@@ -181,7 +211,7 @@ pub struct AndiChromSampleDescription {
 }
 
 impl AndiChromSampleDescription {
-    pub fn new(reader: &mut netcdf3::FileReader) -> Result<Self, Box<dyn Error>> {
+    pub fn new(reader: &mut netcdf3::FileReader) -> Result<Self, Box<dyn Error + Send + Sync>> {
         let sample_id_comments = read_global_attr_str(reader, "sample_id_comments");
         let sample_id = read_global_attr_str(reader, "sample_id");
         let sample_name = read_global_attr_str(reader, "sample_name");
@@ -245,7 +275,7 @@ impl AndiChromDetectionMethod {
 
 #[derive(Debug)]
 pub struct AndiChromRawData {
-    reader_ref: Rc<RefCell<netcdf3::FileReader>>,
+    input: CdfInput,
 
     pub point_number: i32, // required
     pub raw_data_table_name: Option<String>,
@@ -262,30 +292,30 @@ pub struct AndiChromRawData {
 }
 
 impl AndiChromRawData {
-    pub fn new(reader_ref: Rc<RefCell<netcdf3::FileReader>>) -> Result<Self, Box<dyn Error>> {
-        let mut reader = reader_ref.borrow_mut();
-
+    pub(crate) fn new(
+        reader: &mut netcdf3::FileReader,
+        input: CdfInput,
+    ) -> Result<Self, Box<dyn Error + Send + Sync>> {
         let point_number_dim = reader
             .data_set()
             .get_dim("point_number")
             .ok_or(SfError::new("Missing dataset_completeness dimension."))?;
         // usize?
         let point_number = point_number_dim.size() as i32;
-        let raw_data_table_name = read_global_attr_str(&reader, "raw_data_table_name");
-        let retention_unit = match read_global_attr_str(&reader, "retention_unit") {
+        let raw_data_table_name = read_global_attr_str(reader, "raw_data_table_name");
+        let retention_unit = match read_global_attr_str(reader, "retention_unit") {
             Some(unit) => unit,
             None => {
                 // quirk: accomodate different naming found in some data
-                read_global_attr_str(&reader, "retention_units")
+                read_global_attr_str(reader, "retention_units")
                     .ok_or(SfError::new("Missing retention_unit attribute."))?
             }
         };
-        let actual_run_time_length = read_scalar_var_f32(&mut reader, "actual_run_time_length")?
+        let actual_run_time_length = read_scalar_var_f32(reader, "actual_run_time_length")?
             .ok_or(SfError::new("Missing actual_run_time_length variable."))?;
-        let actual_sampling_interval =
-            read_scalar_var_f32(&mut reader, "actual_sampling_interval")?
-                .ok_or(SfError::new("Missing actual_sampling_interval variable."))?;
-        let actual_delay_time = read_scalar_var_f32(&mut reader, "actual_delay_time")?
+        let actual_sampling_interval = read_scalar_var_f32(reader, "actual_sampling_interval")?
+            .ok_or(SfError::new("Missing actual_sampling_interval variable."))?;
+        let actual_delay_time = read_scalar_var_f32(reader, "actual_delay_time")?
             .ok_or(SfError::new("Missing actual_delay_time variable."))?;
         // ordinate_values are lazily accessed through a method
         let mut uniform_sampling_flag_attr = reader
@@ -317,7 +347,7 @@ impl AndiChromRawData {
         };
 
         Ok(Self {
-            reader_ref: Rc::clone(&reader_ref),
+            input,
 
             point_number,
             raw_data_table_name,
@@ -331,35 +361,44 @@ impl AndiChromRawData {
     }
 
     pub fn get_ordinate_values(&self) -> Result<Vec<f32>, SfError> {
-        let mut reader = self.reader_ref.borrow_mut();
+        let mut reader = self.input.open()?;
         let ordinate_values = reader
             .read_var("ordinate_values")
             .map_err(|e| {
                 SfError::from_source(
-                    Box::new(e),
+                    SfError::from(e),
                     "AnDI error. Error parsing AnDI ordinate values.",
                 )
+                .or_location(|| Some(var_location("ordinate_values")))
             })?
             .get_f32()
-            .ok_or(SfError::new("Missing ordinate_values variable."))?
+            .ok_or(
+                SfError::new("Missing ordinate_values variable.")
+                    .with_location(var_location("ordinate_values")),
+            )?
             .to_owned();
         Ok(ordinate_values)
     }
 
     pub fn get_raw_data_retention(&self) -> Result<Option<Vec<f32>>, SfError> {
-        let mut reader = self.reader_ref.borrow_mut();
         let raw_data_retention = match self.uniform_sampling_flag {
             true => None,
             false => Some(
-                reader
+                self.input
+                    .open()?
                     .read_var("raw_data_retention")
                     .map_err(|e| {
-                        SfError::from_source(e, "Error parsing AnDI raw datat retention.")
+                        SfError::from_source(
+                            SfError::from(e),
+                            "Error parsing AnDI raw datat retention.",
+                        )
+                        .or_location(|| Some(var_location("raw_data_retention")))
                     })?
                     .get_f32()
-                    .ok_or(SfError::new(
-                        "AnDI error. Missing raw_data_retention variable.",
-                    ))?
+                    .ok_or(
+                        SfError::new("AnDI error. Missing raw_data_retention variable.")
+                            .with_location(var_location("raw_data_retention")),
+                    )?
                     .to_owned(),
             ),
         };
@@ -369,7 +408,7 @@ impl AndiChromRawData {
 
 #[derive(Debug)]
 pub struct AndiChromPeakProcessingResults {
-    reader_ref: Rc<RefCell<netcdf3::FileReader>>,
+    input: CdfInput,
     peak_retention_unit: String,
     detector_unit: Option<String>,
 
@@ -384,13 +423,12 @@ pub struct AndiChromPeakProcessingResults {
 }
 
 impl AndiChromPeakProcessingResults {
-    pub fn new(
-        reader_ref: Rc<RefCell<netcdf3::FileReader>>,
+    pub(crate) fn new(
+        reader: &netcdf3::FileReader,
+        input: CdfInput,
         peak_retention_unit: &str,
         detector_unit: Option<&str>,
-    ) -> Result<Self, Box<dyn Error>> {
-        let reader = reader_ref.borrow_mut();
-
+    ) -> Result<Self, Box<dyn Error + Send + Sync>> {
         let peak_number_dim = reader.data_set().get_dim("peak_number");
         let peak_number = match peak_number_dim {
             // usize?
@@ -398,23 +436,21 @@ impl AndiChromPeakProcessingResults {
             None => 0,
         };
         let peak_processing_results_table_name =
-            read_global_attr_str(&reader, "peak_processing_results_table_name");
+            read_global_attr_str(reader, "peak_processing_results_table_name");
         let peak_processing_results_comments =
-            read_global_attr_str(&reader, "peak_processing_results_comments");
+            read_global_attr_str(reader, "peak_processing_results_comments");
         let peak_processing_method_name =
-            read_global_attr_str(&reader, "peak_processing_method_name");
+            read_global_attr_str(reader, "peak_processing_method_name");
         let peak_processing_date_time_stamp =
-            read_global_attr_str(&reader, "peak_processing_date_time_stamp");
-        let mut peak_amount_unit = read_global_attr_str(&reader, "peak_amount_unit");
+            read_global_attr_str(reader, "peak_processing_date_time_stamp");
+        let mut peak_amount_unit = read_global_attr_str(reader, "peak_amount_unit");
         if peak_amount_unit.is_none() {
             // quirk: accomodate different naming found in some data
-            peak_amount_unit = read_global_attr_str(&reader, "peak_amount_units");
+            peak_amount_unit = read_global_attr_str(reader, "peak_amount_units");
         }
 
-        drop(reader);
-
         Ok(Self {
-            reader_ref,
+            input,
             peak_retention_unit: peak_retention_unit.to_owned(),
             detector_unit: detector_unit.map(|s| s.to_owned()),
 
@@ -428,13 +464,13 @@ impl AndiChromPeakProcessingResults {
     }
 
     pub fn get_peaks(&self) -> Result<Option<Vec<AndiChromPeak>>, SfError> {
-        let reader = &mut self.reader_ref.borrow_mut();
-
         let peak_number = self.peak_number;
         if peak_number <= 0 {
             return Ok(None);
         }
 
+        let reader = &mut self.input.open()?;
+
         let peak_retention_unit = &self.peak_retention_unit[..];
         let peak_amount_unit = self.peak_amount_unit.as_deref();
         let detector_unit = self.detector_unit.as_deref();
@@ -19,11 +19,11 @@
 
 use super::andi_chrom_parser::AndiChromFile;
 use crate::{
-    api::{Column, Node, Parameter, PointXy, Reader, Table, Value},
-    common::SfError,
-    utils::convert_path_to_node_indices,
+    api::{Column, Node, Parameter, ParseWarning, PointXy, Reader, Table, Value},
+    common::{SfError, SfErrorKind},
+    utils::{check_data_range, convert_path_to_node_indices, extract_data_range},
 };
-use std::{collections::HashMap, path::Path};
+use std::{collections::HashMap, ops::Range, path::Path};
 
 pub struct AndiChromReader {
     path: String,
@@ -32,6 +32,34 @@ pub struct AndiChromReader {
 
 impl Reader for AndiChromReader {
     fn read(&self, path: &str) -> Result<Node, SfError> {
+        self.read_node(path)
+            .map_err(|e| e.or_kind(SfErrorKind::CorruptData))
+    }
+
+    fn read_data_len(&self, path: &str) -> Result<usize, SfError> {
+        self.read_node_data_len(path)
+            .map_err(|e| e.or_kind(SfErrorKind::CorruptData))
+    }
+
+    fn read_data(&self, path: &str, range: Range<usize>) -> Result<Vec<PointXy>, SfError> {
+        self.read_node_data(path, range)
+            .map_err(|e| e.or_kind(SfErrorKind::CorruptData))
+    }
+
+    fn get_warnings(&self) -> &[ParseWarning] {
+        &self.file.warnings
+    }
+}
+
+impl AndiChromReader {
+    pub fn new(path: &str, file: AndiChromFile) -> Self {
+        AndiChromReader {
+            path: path.to_owned(),
+            file,
+        }
+    }
+
+    fn read_node(&self, path: &str) -> Result<Node, SfError> {
         let path_indices = convert_path_to_node_indices(path)?;
         match path_indices[..] {
             [] => self.read_root(), // "", "/"
@@ -41,16 +69,24 @@ impl Reader for AndiChromReader {
             [3] => self.read_raw_data(),
             [4] => self.read_peak_processing_results(),
             [0, 0] => self.read_error_log(),
-            _ => Err(SfError::new(&format!("Illegal node path: {}", path))),
+            _ => Err(SfError::new(&format!("Illegal node path: {}", path))
+                .with_kind(SfErrorKind::IllegalNodePath)),
         }
     }
-}
 
-impl AndiChromReader {
-    pub fn new(path: &str, file: AndiChromFile) -> Self {
-        AndiChromReader {
-            path: path.to_owned(),
-            file,
+    fn read_node_data_len(&self, path: &str) -> Result<usize, SfError> {
+        let path_indices = convert_path_to_node_indices(path)?;
+        match path_indices[..] {
+            [3] => Ok(self.file.raw_data.point_number as usize),
+            _ => Ok(self.read(path)?.data.len()),
+        }
+    }
+
+    fn read_node_data(&self, path: &str, range: Range<usize>) -> Result<Vec<PointXy>, SfError> {
+        let path_indices = convert_path_to_node_indices(path)?;
+        match path_indices[..] {
+            [3] => self.read_raw_data_points(Some(range)),
+            _ => extract_data_range(self.read(path)?.data, &range),
         }
     }
 
@@ -63,6 +99,9 @@ impl AndiChromReader {
             data: Vec::new(),
             metadata: Vec::new(),
             table: None,
+            nd_array: None,
+            complex_data: None,
+            links: vec![],
             child_node_names: vec![
                 "Admin Data".to_owned(),
                 "Sample Description".to_owned(),
@@ -171,6 +210,9 @@ impl AndiChromReader {
             data: Vec::new(),
             metadata: Vec::new(),
             table: None,
+            nd_array: None,
+            complex_data: None,
+            links: vec![],
             child_node_names,
         })
     }
@@ -212,6 +254,9 @@ impl AndiChromReader {
             data: Vec::new(),
             metadata: Vec::new(),
             table: None,
+            nd_array: None,
+            complex_data: None,
+            links: vec![],
             child_node_names: Vec::new(),
         })
     }
@@ -262,6 +307,9 @@ impl AndiChromReader {
             data: Vec::new(),
             metadata: Vec::new(),
             table: None,
+            nd_array: None,
+            complex_data: None,
+            links: vec![],
             child_node_names: Vec::new(),
         })
     }
@@ -305,59 +353,66 @@ impl AndiChromReader {
             &mut parameters,
         );
 
+        let data = self.read_raw_data_points(None)?;
+
+        let mut metadata: Vec<(String, String)> = vec![];
+        metadata.push(("x.unit".to_owned(), raw_data.retention_unit.to_owned()));
+        let y_unit = &self.file.detection_method.detector_unit;
+        if let Some(y_unit) = y_unit {
+            metadata.push(("y.unit".to_owned(), y_unit.to_owned()));
+        }
+
+        Ok(Node {
+            name: "Raw Data".to_owned(),
+            parameters,
+            data,
+            metadata,
+            table: None,
+            nd_array: None,
+            complex_data: None,
+            links: vec![],
+            child_node_names: Vec::new(),
+        })
+    }
+
+    /// Reads all raw data points or only those within a range.
+    fn read_raw_data_points(&self, range: Option<Range<usize>>) -> Result<Vec<PointXy>, SfError> {
+        let raw_data = &self.file.raw_data;
+
+        // netCDF variables can only be read as a whole, so only the mapping is limited to the range
+        let y_values = raw_data.get_ordinate_values()?;
+        let range = range.unwrap_or(0..y_values.len());
+        check_data_range(&range, y_values.len())?;
+
         // map to xy pairs
         let raw_data_retention = raw_data.get_raw_data_retention()?;
         let data = match &raw_data_retention {
             Some(x_values) => {
                 // x values present
-                let y_values = &raw_data.get_ordinate_values()?;
                 if x_values.len() != y_values.len() {
                     return Err(SfError::new(
                         "Numbers of ordinate and retention values do not match.",
                     ));
                 }
-                let xy_values: Vec<PointXy> = x_values
-                    .iter()
-                    .enumerate()
-                    .map(|(i, &x)| {
-                        PointXy::new(x as f64, y_values.get(i).unwrap().to_owned() as f64)
-                    })
-                    .collect();
-                xy_values
+                range
+                    .map(|i| PointXy::new(x_values[i] as f64, y_values[i] as f64))
+                    .collect()
             }
             None => {
                 // x values need to be calculated
                 let actual_delay_time = raw_data.actual_delay_time as f64;
                 let actual_sampling_interval = raw_data.actual_sampling_interval as f64;
-                let y_values = &raw_data.get_ordinate_values()?;
-                let xy_values: Vec<PointXy> = y_values
-                    .iter()
-                    .enumerate()
-                    .map(|(i, &y)| {
+                range
+                    .map(|i| {
                         // spec is ambigious, could be i or (i+1)
                         let x = actual_delay_time + i as f64 * actual_sampling_interval;
-                        PointXy::new(x, y as f64)
+                        PointXy::new(x, y_values[i] as f64)
                     })
-                    .collect();
-                xy_values
+                    .collect()
             }
         };
 
-        let mut metadata: Vec<(String, String)> = vec![];
-        metadata.push(("x.unit".to_owned(), raw_data.retention_unit.to_owned()));
-        let y_unit = &self.file.detection_method.detector_unit;
-        if let Some(y_unit) = y_unit {
-            metadata.push(("y.unit".to_owned(), y_unit.to_owned()));
-        }
-
-        Ok(Node {
-            name: "Raw Data".to_owned(),
-            parameters,
-            data,
-            metadata,
-            table: None,
-            child_node_names: Vec::new(),
-        })
+        Ok(data)
     }
 
     fn read_peak_processing_results(&self) -> Result<Node, SfError> {
@@ -405,6 +460,9 @@ impl AndiChromReader {
             data: Vec::new(),
             metadata: Vec::new(),
             table,
+            nd_array: None,
+            complex_data: None,
+            links: vec![],
             child_node_names: Vec::new(),
         })
     }
@@ -607,7 +665,8 @@ impl AndiChromReader {
             .collect();
 
         if rows.is_empty() {
-            return Err(SfError::new("Illegal path. No error log found."));
+            return Err(SfError::new("Illegal path. No error log found.")
+                .with_kind(SfErrorKind::IllegalNodePath));
         }
 
         Ok(Node {
@@ -616,6 +675,9 @@ impl AndiChromReader {
             data: vec![],
             metadata: vec![],
             table: Some(Table { column_names, rows }),
+            nd_array: None,
+            complex_data: None,
+            links: vec![],
             child_node_names: vec![],
         })
     }
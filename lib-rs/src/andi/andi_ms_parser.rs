@@ -24,42 +24,57 @@ use super::andi_enums::{
     AndiMsMassSpectrometerInlet, AndiMsResolutionType, AndiMsSampleState, AndiMsScanDirection,
     AndiMsScanFunction, AndiMsScanLaw, AndiMsSeparationMethod, AndiMsTimeAxisUnit,
 };
+use super::andi_utils::CdfInput;
 use super::andi_utils::{
-    check_var_is_2d, read_enum_from_global_attr_str, read_global_attr_f32, read_global_attr_f64,
-    read_global_attr_i16, read_global_attr_i32, read_global_attr_str,
-    read_index_from_var_2d_string, read_index_from_var_f32, read_index_from_var_f64,
-    read_index_from_var_i16, read_index_from_var_i32, read_multi_string_var, read_optional_var,
-    read_var_2d_slice_f64, trim_zeros_in_place,
+    check_global_attr_zero_terminated, check_var_is_2d, read_enum_from_global_attr_str,
+    read_global_attr_f32, read_global_attr_f64, read_global_attr_i16, read_global_attr_i32,
+    read_global_attr_str, read_index_from_var_2d_string, read_index_from_var_f32,
+    read_index_from_var_f64, read_index_from_var_i16, read_index_from_var_i32,
+    read_multi_string_var, read_optional_var, read_var_2d_slice_f64, trim_zeros_in_place,
+    var_location,
 };
-use crate::api::Parser;
-use crate::common::SfError;
+use crate::api::{ParseOptions, ParseWarning, Parser, SeekRead};
+use crate::common::{SfError, SfErrorKind, WarningCollector};
 use netcdf3::{DataVector, Variable};
-use std::cell::RefCell;
 use std::ops::Range;
-use std::rc::Rc;
-use std::{
-    error::Error,
-    io::{Read, Seek},
-    str::FromStr,
-};
+use std::sync::Arc;
+use std::{error::Error, str::FromStr};
 
 pub struct AndiMsParser {}
 
 impl AndiMsParser {
-    pub(crate) fn parse_cdf(reader: netcdf3::FileReader) -> Result<AndiMsFile, SfError> {
-        AndiMsFile::new(reader)
+    /// Parses AnDI data, checking the data against the specification as set in the options.
+    pub fn parse_with_options<T: SeekRead + 'static>(
+        name: &str,
+        input: T,
+        options: &ParseOptions,
+    ) -> Result<AndiMsFile, SfError> {
+        let input = CdfInput::new(name, Box::new(input));
+        let reader = input.open()?;
+        Self::parse_cdf(reader, input, options)
+    }
+
+    pub(crate) fn parse_cdf(
+        reader: netcdf3::FileReader,
+        input: CdfInput,
+        options: &ParseOptions,
+    ) -> Result<AndiMsFile, SfError> {
+        let mut collector = WarningCollector::new(options);
+        check_global_attr_zero_terminated(&reader, "dataset_completeness", &mut collector);
+        let warnings = collector.into_warnings()?;
+        let mut file =
+            AndiMsFile::new(reader, input).map_err(|e| e.or_kind(SfErrorKind::CorruptData))?;
+        file.warnings = warnings;
+        Ok(file)
     }
 }
 
-impl<T: Seek + Read + 'static> Parser<T> for AndiMsParser {
+impl<T: SeekRead + 'static> Parser<T> for AndiMsParser {
     type R = AndiMsFile;
     type E = SfError;
 
     fn parse(name: &str, input: T) -> Result<Self::R, Self::E> {
-        let input_seek_read = Box::new(input);
-        let reader = netcdf3::FileReader::open_seek_read(name, input_seek_read)
-            .map_err(|e| SfError::from_source(e, "AnDI Error. Error parsing netCDF."))?;
-        Self::parse_cdf(reader)
+        Self::parse_with_options(name, input, &ParseOptions::default())
     }
 }
 
@@ -70,14 +85,15 @@ pub struct AndiMsFile {
     pub instrument_data: AndiMsInstrumentData,
     pub sample_data: AndiMsSampleData,
     pub test_data: AndiMsTestData,
-    pub raw_data_global: Rc<AndiMsRawDataGlobal>,
+    pub raw_data_global: Arc<AndiMsRawDataGlobal>,
     pub raw_data_scans: AndiMsRawDataScans,
     pub library_data: Option<AndiMsLibraryData>,
     pub scan_groups: Option<AndiMsRawDataScanGroups>,
+    pub warnings: Vec<ParseWarning>,
 }
 
 impl AndiMsFile {
-    pub fn new(mut reader: netcdf3::FileReader) -> Result<Self, SfError> {
+    pub(crate) fn new(mut reader: netcdf3::FileReader, input: CdfInput) -> Result<Self, SfError> {
         let admin_data = AndiMsAdminData::new(&mut reader)
             .map_err(|e| SfError::from_source(e, "Error parsing AnDI MS admin data."))?;
         let instrument_data = AndiMsInstrumentData::new(&mut reader, admin_data.instrument_number)
@@ -86,7 +102,7 @@ impl AndiMsFile {
             .map_err(|e| SfError::from_source(e, "Error parsing AnDI MS sample data."))?;
         let test_data = AndiMsTestData::new(&reader)
             .map_err(|e| SfError::from_source(e, "Error parsing AnDI MS test data."))?;
-        let raw_data_global = Rc::new(
+        let raw_data_global = Arc::new(
             AndiMsRawDataGlobal::new(&reader)
                 .map_err(|e| SfError::from_source(e, "Error parsing AnDI MS raw data global."))?,
         );
@@ -97,16 +113,16 @@ impl AndiMsFile {
             ),
             _ => None,
         };
-        let reader_ref: Rc<RefCell<netcdf3::FileReader>> = Rc::new(RefCell::new(reader));
         let raw_data_scans = AndiMsRawDataScans::new(
-            Rc::clone(&reader_ref),
-            Rc::clone(&raw_data_global),
+            &mut reader,
+            input.clone(),
+            Arc::clone(&raw_data_global),
             test_data.resolution_type.clone(),
         )
         .map_err(|e| SfError::from_source(e, "Error parsing AnDI MS raw data scans."))?;
         let scan_groups = match &test_data.scan_function {
             AndiMsScanFunction::Sid => Some(
-                AndiMsRawDataScanGroups::new(reader_ref)
+                AndiMsRawDataScanGroups::new(&mut reader, input)
                     .map_err(|e| SfError::from_source(e, "Error parsing AnDI MS scan groups."))?,
             ),
             _ => None,
@@ -117,10 +133,11 @@ impl AndiMsFile {
             instrument_data,
             sample_data,
             test_data,
-            raw_data_global: Rc::clone(&raw_data_global),
+            raw_data_global: Arc::clone(&raw_data_global),
             raw_data_scans,
             library_data,
             scan_groups,
+            warnings: vec![],
         })
     }
 }
@@ -165,7 +182,7 @@ pub struct AndiMsAdminData {
 }
 
 impl AndiMsAdminData {
-    pub fn new(reader: &mut netcdf3::FileReader) -> Result<Self, Box<dyn Error>> {
+    pub fn new(reader: &mut netcdf3::FileReader) -> Result<Self, Box<dyn Error + Send + Sync>> {
         let dataset_completeness_attr = read_global_attr_str(reader, "dataset_completeness")
             .ok_or(SfError::new("Missing dataset_completeness attribute."))?;
         let dataset_completeness = AndiDatasetCompleteness::from_str(&dataset_completeness_attr)?;
@@ -289,7 +306,7 @@ impl AndiMsInstrumentData {
     pub fn new(
         reader: &mut netcdf3::FileReader,
         instrument_number: Option<i32>,
-    ) -> Result<Self, Box<dyn Error>> {
+    ) -> Result<Self, Box<dyn Error + Send + Sync>> {
         if instrument_number.is_none() {
             return Ok(Self {
                 instrument_components: vec![],
@@ -382,7 +399,7 @@ pub struct AndiMsSampleData {
 }
 
 impl AndiMsSampleData {
-    pub fn new(reader: &netcdf3::FileReader) -> Result<Self, Box<dyn Error>> {
+    pub fn new(reader: &netcdf3::FileReader) -> Result<Self, Box<dyn Error + Send + Sync>> {
         let sample_owner = read_global_attr_str(reader, "sample_owner");
         let sample_receipt_date_time_stamp =
             read_global_attr_str(reader, "sample_receipt_date_time_stamp");
@@ -468,7 +485,7 @@ pub struct AndiMsTestData {
 }
 
 impl AndiMsTestData {
-    pub fn new(reader: &netcdf3::FileReader) -> Result<Self, Box<dyn Error>> {
+    pub fn new(reader: &netcdf3::FileReader) -> Result<Self, Box<dyn Error + Send + Sync>> {
         let separation_experiment_type = read_enum_from_global_attr_str::<AndiMsSeparationMethod>(
             reader,
             "test_separation_type",
@@ -618,7 +635,7 @@ pub fn read_var_attr_str(var_opt: Option<&Variable>, attr_name: &str) -> Option<
 }
 
 impl AndiMsRawDataGlobal {
-    pub fn new(reader: &netcdf3::FileReader) -> Result<Self, Box<dyn Error>> {
+    pub fn new(reader: &netcdf3::FileReader) -> Result<Self, Box<dyn Error + Send + Sync>> {
         let scan_number_dim = reader
             .data_set()
             .get_dim("scan_number")
@@ -735,16 +752,19 @@ impl AndiMsRawDataGlobal {
 
 #[derive(Debug)]
 pub struct AndiMsRawDataScans {
+    input: CdfInput,
+    raw_data_global: Arc<AndiMsRawDataGlobal>,
+
     pub raw_data_per_scan_list: Vec<AndiMsRawDataPerScan>,
 }
 
 impl AndiMsRawDataScans {
-    pub fn new(
-        reader_ref: Rc<RefCell<netcdf3::FileReader>>,
-        raw_data_global: Rc<AndiMsRawDataGlobal>,
+    pub(crate) fn new(
+        reader: &mut netcdf3::FileReader,
+        input: CdfInput,
+        raw_data_global: Arc<AndiMsRawDataGlobal>,
         resolution_type: AndiMsResolutionType,
-    ) -> Result<Self, Box<dyn Error>> {
-        let reader = &mut reader_ref.borrow_mut();
+    ) -> Result<Self, Box<dyn Error + Send + Sync>> {
         let scan_index_var = read_optional_var(reader, "scan_index")?;
         let actual_scan_number_var = read_optional_var(reader, "actual_scan_number")?;
         let point_count_var = read_optional_var(reader, "point_count")?;
@@ -800,9 +820,9 @@ impl AndiMsRawDataScans {
             let resolution = read_index_from_var_f64(&resolution_var_var, i as usize)?;
 
             let ms_raw_data_per_scan = AndiMsRawDataPerScan {
-                reader_ref: Rc::clone(&reader_ref),
+                input: input.clone(),
                 scan_index,
-                raw_data_global: Rc::clone(&raw_data_global),
+                raw_data_global: Arc::clone(&raw_data_global),
                 resolution_type: resolution_type.clone(),
                 scan_number,
                 actual_scan_number,
@@ -824,17 +844,51 @@ impl AndiMsRawDataScans {
         }
 
         Ok(Self {
+            input,
+            raw_data_global,
             raw_data_per_scan_list,
         })
     }
+
+    /// The range of the values of all scans.
+    fn get_values_range(&self) -> Range<usize> {
+        let end = self
+            .raw_data_per_scan_list
+            .iter()
+            .map(|scan| scan.get_values_range().end)
+            .max()
+            .unwrap_or(0);
+        0..end
+    }
+
+    /// Reads the mass axis values of all scans at once.
+    ///
+    /// The values of a scan are found at its values range.
+    pub fn get_mass_axis_values(&self) -> Result<Option<Vec<f64>>, SfError> {
+        read_mass_axis_values(&self.input, &self.raw_data_global, &self.get_values_range())
+    }
+
+    /// Reads the time axis values of all scans at once.
+    ///
+    /// The values of a scan are found at its values range.
+    pub fn get_time_axis_values(&self) -> Result<Option<Vec<f64>>, SfError> {
+        read_time_axis_values(&self.input, &self.raw_data_global, &self.get_values_range())
+    }
+
+    /// Reads the intensity axis values of all scans at once.
+    ///
+    /// The values of a scan are found at its values range.
+    pub fn get_intensity_axis_values(&self) -> Result<Option<Vec<f64>>, SfError> {
+        read_intensity_axis_values(&self.input, &self.raw_data_global, &self.get_values_range())
+    }
 }
 
 #[derive(Debug)]
 pub struct AndiMsRawDataPerScan {
-    reader_ref: Rc<RefCell<netcdf3::FileReader>>,
+    input: CdfInput,
     // the offset into the variable array at which the scan starts
     scan_index: i32,
-    raw_data_global: Rc<AndiMsRawDataGlobal>,
+    raw_data_global: Arc<AndiMsRawDataGlobal>,
 
     pub resolution_type: AndiMsResolutionType,
     /// Which nth scan of all scans.
@@ -860,31 +914,148 @@ pub struct AndiMsRawDataPerScan {
     pub resolution: Option<f64>,
 }
 
-impl AndiMsRawDataPerScan {
-    fn extract_scan<T>(
-        var_values: Vec<T>,
-        var_name: &str,
-        range: &Range<usize>,
-        scale_factor: f64,
-        offset: f64,
-    ) -> Result<Vec<f64>, Box<dyn Error>>
-    where
-        f64: From<T>,
-        T: Copy,
-    {
-        let slice = var_values.get(range.clone()).ok_or(SfError::new(&format!(
-            "Illegal range for {}: {}..{}",
-            var_name, &range.start, &range.end
-        )))?;
-
-        let scaled_values: Vec<f64> = slice
-            .iter()
-            .map(|v| f64::from(*v) * scale_factor + offset)
-            .collect();
+fn extract_values<T>(
+    var_values: Vec<T>,
+    var_name: &str,
+    range: &Range<usize>,
+    scale_factor: f64,
+    offset: f64,
+) -> Result<Vec<f64>, Box<dyn Error + Send + Sync>>
+where
+    f64: From<T>,
+    T: Copy,
+{
+    let slice = var_values.get(range.clone()).ok_or(SfError::new(&format!(
+        "Illegal range for {}: {}..{}",
+        var_name, &range.start, &range.end
+    )))?;
+
+    let scaled_values: Vec<f64> = slice
+        .iter()
+        .map(|v| f64::from(*v) * scale_factor + offset)
+        .collect();
+
+    Ok(scaled_values)
+}
 
-        Ok(scaled_values)
+fn read_values(
+    input: &CdfInput,
+    var_name: &str,
+    data_format: &AndiMsDataFormat,
+    range: &Range<usize>,
+    scale_factor: f64,
+    offset: f64,
+) -> Result<Option<Vec<f64>>, Box<dyn Error + Send + Sync>> {
+    let mut reader = input.open()?;
+    if reader.data_set().get_var(var_name).is_none() {
+        return Ok(None);
     }
+    // reader currently does not provide an option to read only a part of the array
+    // this is inefficient as only a slice is processed
+    // inefficient but netCDF library has no method to read slice of data from variable
+    let res = match data_format {
+        AndiMsDataFormat::Short => extract_values(
+            reader
+                .read_var_i16(var_name)
+                .map_err(|e| SfError::from(e).or_location(|| Some(var_location(var_name))))?,
+            var_name,
+            range,
+            scale_factor,
+            offset,
+        )?,
+        AndiMsDataFormat::Long => extract_values(
+            reader
+                .read_var_i32(var_name)
+                .map_err(|e| SfError::from(e).or_location(|| Some(var_location(var_name))))?,
+            var_name,
+            range,
+            scale_factor,
+            offset,
+        )?,
+        AndiMsDataFormat::Float => extract_values(
+            reader
+                .read_var_f32(var_name)
+                .map_err(|e| SfError::from(e).or_location(|| Some(var_location(var_name))))?,
+            var_name,
+            range,
+            scale_factor,
+            offset,
+        )?,
+        AndiMsDataFormat::Double => extract_values(
+            reader
+                .read_var_f64(var_name)
+                .map_err(|e| SfError::from(e).or_location(|| Some(var_location(var_name))))?,
+            var_name,
+            range,
+            scale_factor,
+            offset,
+        )?,
+    };
+
+    Ok(Some(res))
+}
+
+fn read_mass_axis_values(
+    input: &CdfInput,
+    raw_data_global: &AndiMsRawDataGlobal,
+    range: &Range<usize>,
+) -> Result<Option<Vec<f64>>, SfError> {
+    let data_format = &raw_data_global.mass_axis_data_format;
+    let scale_factor = raw_data_global.mass_axis_scale_factor;
+    let offset = 0.0f64;
+
+    read_values(
+        input,
+        "mass_values",
+        data_format,
+        range,
+        scale_factor,
+        offset,
+    )
+    .map_err(|e| SfError::from_source(e, "Error parsing AnDI mass axis values."))
+}
+
+fn read_time_axis_values(
+    input: &CdfInput,
+    raw_data_global: &AndiMsRawDataGlobal,
+    range: &Range<usize>,
+) -> Result<Option<Vec<f64>>, SfError> {
+    let data_format = &raw_data_global.time_axis_data_format;
+    let scale_factor = raw_data_global.time_axis_scale_factor;
+    let offset = 0.0f64;
+
+    read_values(
+        input,
+        "time_values",
+        data_format,
+        range,
+        scale_factor,
+        offset,
+    )
+    .map_err(|e| SfError::from_source(e, "Error parsing AnDI time axis values."))
+}
+
+fn read_intensity_axis_values(
+    input: &CdfInput,
+    raw_data_global: &AndiMsRawDataGlobal,
+    range: &Range<usize>,
+) -> Result<Option<Vec<f64>>, SfError> {
+    let data_format = &raw_data_global.intensity_axis_data_format;
+    let scale_factor = raw_data_global.intensity_axis_scale_factor;
+    let offset = raw_data_global.intensity_axis_offset;
+
+    read_values(
+        input,
+        "intensity_values",
+        data_format,
+        range,
+        scale_factor,
+        offset,
+    )
+    .map_err(|e| SfError::from_source(e, "Error parsing AnDI intensity axis values."))
+}
 
+impl AndiMsRawDataPerScan {
     fn read_values(
         &self,
         var_name: &str,
@@ -892,82 +1063,32 @@ impl AndiMsRawDataPerScan {
         range: &Range<usize>,
         scale_factor: f64,
         offset: f64,
-    ) -> Result<Option<Vec<f64>>, Box<dyn Error>> {
-        let mut reader = self.reader_ref.borrow_mut();
-        if reader.data_set().get_var(var_name).is_none() {
-            return Ok(None);
-        }
-        // reader currently does not provide an option to read only a part of the array
-        // this is inefficient as only a slice is processed
-        // inefficient but netCDF library has no method to read slice of data from variable
-        let res = match data_format {
-            AndiMsDataFormat::Short => Self::extract_scan(
-                reader.read_var_i16(var_name)?,
-                var_name,
-                range,
-                scale_factor,
-                offset,
-            )?,
-            AndiMsDataFormat::Long => Self::extract_scan(
-                reader.read_var_i32(var_name)?,
-                var_name,
-                range,
-                scale_factor,
-                offset,
-            )?,
-            AndiMsDataFormat::Float => Self::extract_scan(
-                reader.read_var_f32(var_name)?,
-                var_name,
-                range,
-                scale_factor,
-                offset,
-            )?,
-            AndiMsDataFormat::Double => Self::extract_scan(
-                reader.read_var_f64(var_name)?,
-                var_name,
-                range,
-                scale_factor,
-                offset,
-            )?,
-        };
+    ) -> Result<Option<Vec<f64>>, Box<dyn Error + Send + Sync>> {
+        read_values(
+            &self.input,
+            var_name,
+            data_format,
+            range,
+            scale_factor,
+            offset,
+        )
+    }
 
-        Ok(Some(res))
+    /// The range of the scan's values within the values of all scans.
+    pub fn get_values_range(&self) -> Range<usize> {
+        self.scan_index as usize..(self.scan_index + self.number_of_points) as usize
     }
 
     pub fn get_mass_axis_values(&self) -> Result<Option<Vec<f64>>, SfError> {
-        let data_format = &self.raw_data_global.mass_axis_data_format;
-        let range = self.scan_index as usize..(self.scan_index + self.number_of_points) as usize;
-        let scale_factor = self.raw_data_global.mass_axis_scale_factor;
-        let offset = 0.0f64;
-
-        self.read_values("mass_values", data_format, &range, scale_factor, offset)
-            .map_err(|e| SfError::from_source(e, "Error parsing AnDI mass axis values."))
+        read_mass_axis_values(&self.input, &self.raw_data_global, &self.get_values_range())
     }
 
     pub fn get_time_axis_values(&self) -> Result<Option<Vec<f64>>, SfError> {
-        let data_format = &self.raw_data_global.time_axis_data_format;
-        let range = self.scan_index as usize..(self.scan_index + self.number_of_points) as usize;
-        let scale_factor = self.raw_data_global.time_axis_scale_factor;
-        let offset = 0.0f64;
-
-        self.read_values("time_values", data_format, &range, scale_factor, offset)
-            .map_err(|e| SfError::from_source(e, "Error parsing AnDI time axis values."))
+        read_time_axis_values(&self.input, &self.raw_data_global, &self.get_values_range())
     }
 
     pub fn get_intensity_axis_values(&self) -> Result<Option<Vec<f64>>, SfError> {
-        let data_format = &self.raw_data_global.intensity_axis_data_format;
-        let range = self.scan_index as usize..(self.scan_index + self.number_of_points) as usize;
-        let scale_factor = self.raw_data_global.intensity_axis_scale_factor;
-        let offset = self.raw_data_global.intensity_axis_offset;
-
-        self.read_values(
-            "intensity_values",
-            data_format,
-            &range,
-            scale_factor,
-            offset,
-        )
-        .map_err(|e| SfError::from_source(e, "Error parsing AnDI intensity axis values."))
+        read_intensity_axis_values(&self.input, &self.raw_data_global, &self.get_values_range())
     }
 
     // account for -9999 values?
@@ -1045,7 +1166,7 @@ impl AndiMsLibraryData {
     pub fn new(
         reader: &mut netcdf3::FileReader,
         number_of_scans: i32,
-    ) -> Result<Self, Box<dyn Error>> {
+    ) -> Result<Self, Box<dyn Error + Send + Sync>> {
         let entry_name_var = read_optional_var(reader, "entry_name")?;
         let entry_id_var = read_optional_var(reader, "entry_id")?;
         let entry_number_var = read_optional_var(reader, "entry_number")?;
@@ -1192,9 +1313,10 @@ pub struct AndiMsRawDataScanGroups {
 }
 
 impl AndiMsRawDataScanGroups {
-    pub fn new(reader_ref: Rc<RefCell<netcdf3::FileReader>>) -> Result<Self, Box<dyn Error>> {
-        let reader = &mut reader_ref.borrow_mut();
-
+    pub(crate) fn new(
+        reader: &mut netcdf3::FileReader,
+        input: CdfInput,
+    ) -> Result<Self, Box<dyn Error + Send + Sync>> {
         let number_of_groups = reader
             .data_set()
             .get_dim("group_number")
@@ -1222,7 +1344,7 @@ impl AndiMsRawDataScanGroups {
                 )?;
 
             raw_data_per_scan_groups.push(AndiMsRawDataPerScanGroup {
-                reader_ref: Rc::clone(&reader_ref),
+                input: input.clone(),
                 group_number: group_number as i32,
                 number_of_masses_in_group,
                 starting_scan_number,
@@ -1237,7 +1359,7 @@ impl AndiMsRawDataScanGroups {
 
 #[derive(Debug)]
 pub struct AndiMsRawDataPerScanGroup {
-    reader_ref: Rc<RefCell<netcdf3::FileReader>>,
+    input: CdfInput,
 
     // group_masses (required), sampling_times (optional), and delay_times (optional) are lazily loaded through getters
     pub group_number: i32,              // required
@@ -1264,8 +1386,7 @@ impl AndiMsRawDataPerScanGroup {
 
     pub fn get_group_masses(&self) -> Result<Vec<f64>, SfError> {
         let var_name = "group_masses";
-        let group_masses_var = match read_optional_var(&mut self.reader_ref.borrow_mut(), var_name)?
-        {
+        let group_masses_var = match read_optional_var(&mut self.input.open()?, var_name)? {
             Some(var) => var,
             None => Err(SfError::new(&format!(
                 "Could not find required variable: {}",
@@ -1278,11 +1399,10 @@ impl AndiMsRawDataPerScanGroup {
 
     pub fn get_group_sampling_times(&self) -> Result<Option<Vec<f64>>, SfError> {
         let var_name = "group_sampling_times";
-        let group_sampling_times_var =
-            match read_optional_var(&mut self.reader_ref.borrow_mut(), var_name)? {
-                Some(var) => var,
-                None => return Ok(None),
-            };
+        let group_sampling_times_var = match read_optional_var(&mut self.input.open()?, var_name)? {
+            Some(var) => var,
+            None => return Ok(None),
+        };
 
         Ok(Some(self.read_scan_group_var_slice_f64(
             &group_sampling_times_var,
@@ -1291,11 +1411,10 @@ impl AndiMsRawDataPerScanGroup {
 
     pub fn get_group_delay_times(&self) -> Result<Option<Vec<f64>>, SfError> {
         let var_name = "group_delay_times";
-        let group_delay_times_var =
-            match read_optional_var(&mut self.reader_ref.borrow_mut(), var_name)? {
-                Some(var) => var,
-                None => return Ok(None),
-            };
+        let group_delay_times_var = match read_optional_var(&mut self.input.open()?, var_name)? {
+            Some(var) => var,
+            None => return Ok(None),
+        };
 
         Ok(Some(
             self.read_scan_group_var_slice_f64(&group_delay_times_var)?,
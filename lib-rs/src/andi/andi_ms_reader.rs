@@ -22,11 +22,11 @@ use super::{
     andi_ms_parser::{AndiMsFile, AndiMsInstrumentComponent, AndiMsRawDataPerScan},
 };
 use crate::{
-    api::{Column, Node, Parameter, PointXy, Reader, Table, Value},
-    common::SfError,
-    utils::convert_path_to_node_indices,
+    api::{Axis, Column, NdArray, Node, Parameter, ParseWarning, PointXy, Reader, Table, Value},
+    common::{SfError, SfErrorKind},
+    utils::{check_data_range, convert_path_to_node_indices, extract_data_range},
 };
-use std::{collections::HashMap, path::Path};
+use std::{collections::HashMap, ops::Range, path::Path};
 
 pub struct AndiMsReader {
     path: String,
@@ -35,6 +35,38 @@ pub struct AndiMsReader {
 
 impl Reader for AndiMsReader {
     fn read(&self, path: &str) -> Result<Node, SfError> {
+        self.read_node(path)
+            .map_err(|e| e.or_kind(SfErrorKind::CorruptData))
+    }
+
+    fn read_data_len(&self, path: &str) -> Result<usize, SfError> {
+        self.read_node_data_len(path)
+            .map_err(|e| e.or_kind(SfErrorKind::CorruptData))
+    }
+
+    fn read_data(&self, path: &str, range: Range<usize>) -> Result<Vec<PointXy>, SfError> {
+        self.read_node_data(path, range)
+            .map_err(|e| e.or_kind(SfErrorKind::CorruptData))
+    }
+
+    fn get_warnings(&self) -> &[ParseWarning] {
+        &self.file.warnings
+    }
+}
+
+impl AndiMsReader {
+    /// The maximum number of values of the array of all scans. Larger runs are only provided
+    /// as scan child nodes to avoid holding all values in memory at once.
+    const MAX_ARRAY_LEN: usize = 1 << 24;
+
+    pub fn new(path: &str, file: AndiMsFile) -> Self {
+        AndiMsReader {
+            path: path.to_owned(),
+            file,
+        }
+    }
+
+    fn read_node(&self, path: &str) -> Result<Node, SfError> {
         let path_indices = convert_path_to_node_indices(path)?;
         match path_indices[..] {
             [] => self.read_root(), // "", "/"
@@ -50,16 +82,34 @@ impl Reader for AndiMsReader {
             [5, n, m] => self.read_raw_data_per_scan_child(n, m),
             [6] => self.read_scan_groups(),
             [6, n] => self.read_scan_group(n),
-            _ => Err(SfError::new(&format!("Illegal node path: {}", path))),
+            _ => Err(SfError::new(&format!("Illegal node path: {}", path))
+                .with_kind(SfErrorKind::IllegalNodePath)),
         }
     }
-}
 
-impl AndiMsReader {
-    pub fn new(path: &str, file: AndiMsFile) -> Self {
-        AndiMsReader {
-            path: path.to_owned(),
-            file,
+    fn read_node_data_len(&self, path: &str) -> Result<usize, SfError> {
+        let path_indices = convert_path_to_node_indices(path)?;
+        match self.find_scan_data_index(&path_indices) {
+            Some((index, _)) => Ok(self.get_scan(index)?.number_of_points as usize),
+            None => Ok(self.read(path)?.data.len()),
+        }
+    }
+
+    fn read_node_data(&self, path: &str, range: Range<usize>) -> Result<Vec<PointXy>, SfError> {
+        let path_indices = convert_path_to_node_indices(path)?;
+        match self.find_scan_data_index(&path_indices) {
+            Some((index, is_time_mass_data)) => {
+                let scan = self.get_scan(index)?;
+                check_data_range(&range, scan.number_of_points as usize)?;
+                let (x_values, y_values) = self.read_scan_values(scan, index, is_time_mass_data)?;
+                let data = x_values[range.clone()]
+                    .iter()
+                    .zip(y_values[range].iter())
+                    .map(|(x, y)| PointXy::new(*x, *y))
+                    .collect();
+                Ok(data)
+            }
+            None => extract_data_range(self.read(path)?.data, &range),
         }
     }
 
@@ -83,6 +133,9 @@ impl AndiMsReader {
             data: vec![],
             metadata: vec![],
             table: None,
+            nd_array: None,
+            complex_data: None,
+            links: vec![],
             child_node_names,
         })
     }
@@ -270,6 +323,9 @@ impl AndiMsReader {
             data: Vec::new(),
             metadata: Vec::new(),
             table: None,
+            nd_array: None,
+            complex_data: None,
+            links: vec![],
             child_node_names,
         })
     }
@@ -302,16 +358,22 @@ impl AndiMsReader {
             data: Vec::new(),
             metadata: Vec::new(),
             table: None,
+            nd_array: None,
+            complex_data: None,
+            links: vec![],
             child_node_names,
         })
     }
 
     fn read_instrument_component(&self, index: usize) -> Result<Node, SfError> {
         let components = &self.file.instrument_data.instrument_components;
-        let component = components.get(index).ok_or(SfError::new(&format!(
-            "Illegal path. Instrument component not found for index: {}",
-            index
-        )))?;
+        let component = components.get(index).ok_or(
+            SfError::new(&format!(
+                "Illegal path. Instrument component not found for index: {}",
+                index
+            ))
+            .with_kind(SfErrorKind::IllegalNodePath),
+        )?;
 
         let name = Self::generate_instrument_component_name(index, component);
         let mut parameters: Vec<Parameter> = vec![];
@@ -368,6 +430,9 @@ impl AndiMsReader {
             data: Vec::new(),
             metadata: Vec::new(),
             table: None,
+            nd_array: None,
+            complex_data: None,
+            links: vec![],
             child_node_names: Vec::new(),
         })
     }
@@ -449,6 +514,9 @@ impl AndiMsReader {
             data: Vec::new(),
             metadata: Vec::new(),
             table: None,
+            nd_array: None,
+            complex_data: None,
+            links: vec![],
             child_node_names: Vec::new(),
         })
     }
@@ -574,6 +642,9 @@ impl AndiMsReader {
             data: Vec::new(),
             metadata: Vec::new(),
             table: None,
+            nd_array: None,
+            complex_data: None,
+            links: vec![],
             child_node_names: Vec::new(),
         })
     }
@@ -720,6 +791,9 @@ impl AndiMsReader {
             data: Vec::new(),
             metadata: Vec::new(),
             table: None,
+            nd_array: None,
+            complex_data: None,
+            links: vec![],
             child_node_names: Vec::new(),
         })
     }
@@ -753,17 +827,122 @@ impl AndiMsReader {
     fn read_raw_data_scans(&self) -> Result<Node, SfError> {
         let scans = &self.file.raw_data_scans.raw_data_per_scan_list;
         let child_node_names: Vec<String> = scans.iter().map(Self::generate_scan_name).collect();
+        let num_values = scans
+            .iter()
+            .map(|scan| scan.number_of_points.max(0) as usize)
+            .fold(0usize, usize::saturating_add);
+        let (parameters, nd_array) = match Self::check_array_len(num_values) {
+            Some(note) => (vec![note], None),
+            None => (Vec::new(), self.read_raw_data_scans_array()?),
+        };
 
         Ok(Node {
             name: "Raw Data Scans".to_owned(),
-            parameters: Vec::new(),
+            parameters,
             data: Vec::new(),
             metadata: Vec::new(),
             table: None,
+            nd_array,
+            complex_data: None,
+            links: vec![],
             child_node_names,
         })
     }
 
+    /// Provides a note if the array of all scans would exceed the maximum number of values.
+    fn check_array_len(num_values: usize) -> Option<Parameter> {
+        (num_values > Self::MAX_ARRAY_LEN).then(|| {
+            Parameter::from_str_str(
+                "Array",
+                format!(
+                    "Not provided for {} values exceeding the maximum of {}, see the scans.",
+                    num_values,
+                    Self::MAX_ARRAY_LEN
+                ),
+            )
+        })
+    }
+
+    /// Maps the scans to a two dimensional array if all scans share the same x values.
+    fn read_raw_data_scans_array(&self) -> Result<Option<NdArray>, SfError> {
+        let scans = &self.file.raw_data_scans.raw_data_per_scan_list;
+        let Some(first_scan) = scans.first() else {
+            return Ok(None);
+        };
+        // cheap check before reading any values
+        if first_scan.number_of_points <= 0
+            || scans
+                .iter()
+                .any(|scan| scan.number_of_points != first_scan.number_of_points)
+        {
+            return Ok(None);
+        }
+
+        // read the values of all scans at once instead of rereading the variables for each scan
+        let raw_data_scans = &self.file.raw_data_scans;
+        let raw_data_global = &self.file.raw_data_global;
+        let all_x_values = match (raw_data_global.has_masses, raw_data_global.has_times) {
+            (true, _) => raw_data_scans
+                .get_mass_axis_values()?
+                .ok_or(SfError::new("Could not find m/z values for scans."))?,
+            (_, true) => raw_data_scans
+                .get_time_axis_values()?
+                .ok_or(SfError::new("Could not find time values for scans."))?,
+            _ => return Err(SfError::new("Could not find m/z or time values for scans.")),
+        };
+        let all_y_values = raw_data_scans
+            .get_intensity_axis_values()?
+            .ok_or(SfError::new("Could not find intensity values for scans."))?;
+
+        let x_values = Self::get_scan_slice(&all_x_values, first_scan, 0)?;
+        let mut values = Vec::<f64>::with_capacity(x_values.len() * scans.len());
+        for (index, scan) in scans.iter().enumerate() {
+            if Self::get_scan_slice(&all_x_values, scan, index)? != x_values {
+                return Ok(None);
+            }
+            values.extend_from_slice(Self::get_scan_slice(&all_y_values, scan, index)?);
+        }
+        let x_values = x_values.to_vec();
+
+        let scan_acquisition_times = scans
+            .iter()
+            .map(|scan| scan.scan_acquisition_time)
+            .collect::<Option<Vec<f64>>>();
+        let scan_axis = match scan_acquisition_times {
+            Some(times) => Axis::new("Scan Acquisition Time", None, times),
+            None => Axis::new(
+                "Scan Number",
+                None,
+                scans.iter().map(|scan| scan.scan_number as f64).collect(),
+            ),
+        };
+
+        let x_axis = if raw_data_global.has_masses {
+            Axis::new(
+                raw_data_global.mass_axis_label.as_deref().unwrap_or("Mass"),
+                Some(raw_data_global.mass_axis_units.to_string()),
+                x_values,
+            )
+        } else {
+            Axis::new(
+                raw_data_global.time_axis_label.as_deref().unwrap_or("Time"),
+                Some(raw_data_global.time_axis_units.to_string()),
+                x_values,
+            )
+        };
+
+        let array = NdArray::new(
+            raw_data_global
+                .intensity_axis_label
+                .as_deref()
+                .unwrap_or("Intensity"),
+            Some(raw_data_global.intensity_axis_units.to_string()),
+            vec![scan_axis, x_axis],
+            values,
+        )?;
+        Ok(Some(array))
+    }
+
     fn read_raw_data_per_scan_child(
         &self,
         scan_index: usize,
@@ -775,7 +954,7 @@ impl AndiMsReader {
         match (has_time_series_child, child_index) {
             (true, 0) | (false, 1) => self.read_time_mass_raw_data_per_scan(scan_index),
             (false, 0) => self.read_library_data_per_scan(scan_index),
-            _ => Err(SfError::new("Illegal node path.")),
+            _ => Err(SfError::new("Illegal node path.").with_kind(SfErrorKind::IllegalNodePath)),
         }
     }
 
@@ -880,33 +1059,84 @@ impl AndiMsReader {
         Ok(table)
     }
 
-    fn read_raw_data_per_scan(&self, index: usize) -> Result<Node, SfError> {
+    fn get_scan(&self, index: usize) -> Result<&AndiMsRawDataPerScan, SfError> {
         let scans = &self.file.raw_data_scans.raw_data_per_scan_list;
-        let scan = scans.get(index).ok_or(SfError::new(&format!(
-            "Illegal path. Raw data per scan not found for index: {}",
-            index
-        )))?;
+        scans.get(index).ok_or(
+            SfError::new(&format!(
+                "Illegal path. Raw data per scan not found for index: {}",
+                index
+            ))
+            .with_kind(SfErrorKind::IllegalNodePath),
+        )
+    }
 
-        let name = Self::generate_scan_name(scan);
+    /// Finds the scan index for a path to a node holding scan data
+    /// and whether the node holds the time-mass data of the scan.
+    fn find_scan_data_index(&self, path_indices: &[usize]) -> Option<(usize, bool)> {
+        let raw_data_global = &self.file.raw_data_global;
+        let has_time_series_child = raw_data_global.has_masses && raw_data_global.has_times;
 
-        let parameters = Self::read_parameters_for_scan(scan);
+        match path_indices {
+            [5, n] => Some((*n, false)),
+            [5, n, m] => match (has_time_series_child, m) {
+                (true, 0) | (false, 1) => Some((*n, true)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
 
+    /// Gets the values of a scan from the values of all scans.
+    fn get_scan_slice<'a>(
+        values: &'a [f64],
+        scan: &AndiMsRawDataPerScan,
+        index: usize,
+    ) -> Result<&'a [f64], SfError> {
+        let range = scan.get_values_range();
+        values.get(range.clone()).ok_or(SfError::new(&format!(
+            "Illegal range for scan at index {}: {}..{}",
+            index, range.start, range.end
+        )))
+    }
+
+    /// Reads the x and y values of a scan.
+    ///
+    /// The x values are the m/z values if present and the time values otherwise.
+    /// For time-mass data, the x values are the time values.
+    fn read_scan_values(
+        &self,
+        scan: &AndiMsRawDataPerScan,
+        index: usize,
+        is_time_mass_data: bool,
+    ) -> Result<(Vec<f64>, Vec<f64>), SfError> {
         let raw_data_global = &self.file.raw_data_global;
-        let x_values = match (raw_data_global.has_masses, raw_data_global.has_times) {
-            (true, _) => scan.get_mass_axis_values()?.ok_or(SfError::new(&format!(
+        let x_values = match (
+            is_time_mass_data,
+            raw_data_global.has_masses,
+            raw_data_global.has_times,
+        ) {
+            (false, true, _) => scan.get_mass_axis_values()?.ok_or(SfError::new(&format!(
                 "Could not find m/z values for scan at index: {}",
                 index
             )))?,
-            (_, true) => scan.get_time_axis_values()?.ok_or(SfError::new(&format!(
-                "Could not find time values for scan at index: {}",
-                index
-            )))?,
-            _ => {
+            (false, _, true) | (true, true, true) => {
+                scan.get_time_axis_values()?.ok_or(SfError::new(&format!(
+                    "Could not find time values for scan at index: {}",
+                    index
+                )))?
+            }
+            (false, _, _) => {
                 return Err(SfError::new(&format!(
                     "Could not find m/z or time values for scan at index: {}",
                     index
                 )))?;
             }
+            (true, _, _) => {
+                return Err(SfError::new(&format!(
+                    "Could not find time values for scan at index: {}",
+                    index
+                )))?;
+            }
         };
         let y_values = scan
             .get_intensity_axis_values()?
@@ -920,6 +1150,19 @@ impl AndiMsReader {
                 index
             )))?;
         }
+
+        Ok((x_values, y_values))
+    }
+
+    fn read_raw_data_per_scan(&self, index: usize) -> Result<Node, SfError> {
+        let scan = self.get_scan(index)?;
+
+        let name = Self::generate_scan_name(scan);
+
+        let parameters = Self::read_parameters_for_scan(scan);
+
+        let raw_data_global = &self.file.raw_data_global;
+        let (x_values, y_values) = self.read_scan_values(scan, index, false)?;
         let data: Vec<PointXy> = x_values
             .iter()
             .zip(y_values.iter())
@@ -973,46 +1216,22 @@ impl AndiMsReader {
             data,
             metadata,
             table,
+            nd_array: None,
+            complex_data: None,
+            links: vec![],
             child_node_names,
         })
     }
 
     fn read_time_mass_raw_data_per_scan(&self, index: usize) -> Result<Node, SfError> {
-        let scans = &self.file.raw_data_scans.raw_data_per_scan_list;
-        let scan = scans.get(index).ok_or(SfError::new(&format!(
-            "Illegal path. Raw data per scan not found for index: {}",
-            index
-        )))?;
+        let scan = self.get_scan(index)?;
 
         let name = "Time-Mass Data".to_owned();
 
         let parameters = Self::read_parameters_for_scan(scan);
 
         let raw_data_global = &self.file.raw_data_global;
-        let x_values = match (raw_data_global.has_masses, raw_data_global.has_times) {
-            (true, true) => scan.get_time_axis_values()?.ok_or(SfError::new(&format!(
-                "Could not find time values for scan at index: {}",
-                index
-            )))?,
-            _ => {
-                return Err(SfError::new(&format!(
-                    "Could not find time values for scan at index: {}",
-                    index
-                )))?;
-            }
-        };
-        let y_values = scan
-            .get_intensity_axis_values()?
-            .ok_or(SfError::new(&format!(
-                "Could not find intensity values for scan at index: {}",
-                index
-            )))?;
-        if x_values.len() != y_values.len() {
-            return Err(SfError::new(&format!(
-                "Mismatch of x and y value lengths for scan at index: {}",
-                index
-            )))?;
-        }
+        let (x_values, y_values) = self.read_scan_values(scan, index, true)?;
         let data: Vec<PointXy> = x_values
             .iter()
             .zip(y_values.iter())
@@ -1050,6 +1269,9 @@ impl AndiMsReader {
             data,
             metadata,
             table,
+            nd_array: None,
+            complex_data: None,
+            links: vec![],
             child_node_names,
         })
     }
@@ -1060,13 +1282,13 @@ impl AndiMsReader {
             .library_data
             .as_ref()
             .ok_or(SfError::new("No library data found."))?;
-        let scan_lib_data = library_data
-            .library_data_per_scan
-            .get(index)
-            .ok_or(SfError::new(&format!(
+        let scan_lib_data = library_data.library_data_per_scan.get(index).ok_or(
+            SfError::new(&format!(
                 "Illegal path. Library data per scan not found for index: {}",
                 index
-            )))?;
+            ))
+            .with_kind(SfErrorKind::IllegalNodePath),
+        )?;
 
         let name = "Library Data".to_owned();
 
@@ -1177,16 +1399,18 @@ impl AndiMsReader {
             data: vec![],
             metadata: vec![],
             table: None,
+            nd_array: None,
+            complex_data: None,
+            links: vec![],
             child_node_names: vec![],
         })
     }
 
     pub fn read_scan_groups(&self) -> Result<Node, SfError> {
-        let scan_groups = &self
-            .file
-            .scan_groups
-            .as_ref()
-            .ok_or(SfError::new("Illegal path. No scan groups found."))?;
+        let scan_groups = &self.file.scan_groups.as_ref().ok_or(
+            SfError::new("Illegal path. No scan groups found.")
+                .with_kind(SfErrorKind::IllegalNodePath),
+        )?;
 
         let child_node_names: Vec<String> = scan_groups
             .raw_data_per_scan_groups
@@ -1200,23 +1424,25 @@ impl AndiMsReader {
             data: vec![],
             metadata: vec![],
             table: None,
+            nd_array: None,
+            complex_data: None,
+            links: vec![],
             child_node_names,
         })
     }
 
     pub fn read_scan_group(&self, n: usize) -> Result<Node, SfError> {
-        let scan_groups = &self
-            .file
-            .scan_groups
-            .as_ref()
-            .ok_or(SfError::new("Illegal path. No scan groups found."))?;
-        let scan_group = &scan_groups
-            .raw_data_per_scan_groups
-            .get(n)
-            .ok_or(SfError::new(&format!(
+        let scan_groups = &self.file.scan_groups.as_ref().ok_or(
+            SfError::new("Illegal path. No scan groups found.")
+                .with_kind(SfErrorKind::IllegalNodePath),
+        )?;
+        let scan_group = &scan_groups.raw_data_per_scan_groups.get(n).ok_or(
+            SfError::new(&format!(
                 "Illegal path. No scan group found for index: {}",
                 n
-            )))?;
+            ))
+            .with_kind(SfErrorKind::IllegalNodePath),
+        )?;
 
         let name = format!("Scan Group {}", n);
         let parameters: Vec<Parameter> = vec![
@@ -1280,6 +1506,9 @@ impl AndiMsReader {
             data: vec![],
             metadata: vec![],
             table,
+            nd_array: None,
+            complex_data: None,
+            links: vec![],
             child_node_names: vec![],
         })
     }
@@ -1299,7 +1528,8 @@ impl AndiMsReader {
             .collect();
 
         if rows.is_empty() {
-            return Err(SfError::new("Illegal path. No error log found."));
+            return Err(SfError::new("Illegal path. No error log found.")
+                .with_kind(SfErrorKind::IllegalNodePath));
         }
 
         Ok(Node {
@@ -1308,7 +1538,32 @@ impl AndiMsReader {
             data: vec![],
             metadata: vec![],
             table: Some(Table { column_names, rows }),
+            nd_array: None,
+            complex_data: None,
+            links: vec![],
             child_node_names: vec![],
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notes_array_exceeding_maximum_length() {
+        assert_eq!(
+            None,
+            AndiMsReader::check_array_len(AndiMsReader::MAX_ARRAY_LEN)
+        );
+
+        let note = AndiMsReader::check_array_len(AndiMsReader::MAX_ARRAY_LEN + 1).unwrap();
+        assert_eq!(
+            Parameter::from_str_str(
+                "Array",
+                "Not provided for 16777217 values exceeding the maximum of 16777216, see the scans."
+            ),
+            note
+        );
+    }
+}
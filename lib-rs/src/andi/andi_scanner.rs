@@ -19,21 +19,23 @@
 
 use super::{
     andi_chrom_parser::AndiChromParser, andi_chrom_reader::AndiChromReader,
-    andi_ms_parser::AndiMsParser, andi_ms_reader::AndiMsReader,
+    andi_ms_parser::AndiMsParser, andi_ms_reader::AndiMsReader, andi_utils::CdfInput,
 };
 use crate::{
-    api::{Reader, Scanner},
-    common::SfError,
-    utils::is_recognized_extension,
+    api::{Confidence, ParseOptions, Reader, Scanner, SeekRead},
+    common::{SfError, SfErrorKind},
+    utils::{determine_confidence, is_recognized_extension},
 };
-use std::io::{Read, Seek};
 
 #[derive(Default)]
-pub struct AndiScanner {}
+pub struct AndiScanner {
+    options: ParseOptions,
+}
 
 impl AndiScanner {
     const ACCEPTED_EXTENSIONS: [&'static str; 2] = ["cdf", "nc"];
     const MAGIC_BYTES: [u8; 3] = [0x43, 0x44, 0x46]; // "CDF"
+    const VERSIONS: [u8; 2] = [0x01, 0x02]; // classic and 64-bit offset format
     const AIA_TEMPLATE_REVISION_ATTR: &'static str = "aia_template_revision";
     const MS_TEMPLATE_REVISION_ATTR: &'static str = "ms_template_revision";
 }
@@ -42,41 +44,57 @@ impl AndiScanner {
     pub fn new() -> Self {
         Self::default()
     }
-}
-
-impl<T: Seek + Read + 'static> Scanner<T> for AndiScanner {
-    fn is_recognized(&self, path: &str, input: &mut T) -> bool {
-        if !is_recognized_extension(path, &Self::ACCEPTED_EXTENSIONS) {
-            return false;
-        }
 
-        // recognized extension => check first few bytes ("magic bytes")
-        let mut buf = [0u8; 3];
+    /// Checks the first few bytes ("magic bytes") followed by the netCDF version.
+    fn is_recognized_content<T: SeekRead>(input: &mut T) -> bool {
+        let mut buf = [0u8; 4];
         let read_success = input.read_exact(&mut buf);
         if read_success.is_err() {
             return false;
         }
 
-        buf.as_slice() == Self::MAGIC_BYTES
+        buf[..3] == Self::MAGIC_BYTES && Self::VERSIONS.contains(&buf[3])
+    }
+}
+
+impl<T: SeekRead + 'static> Scanner<T> for AndiScanner {
+    fn is_recognized(&self, path: &str, input: &mut T) -> bool {
+        // recognized extension => check content
+        is_recognized_extension(path, &Self::ACCEPTED_EXTENSIONS)
+            && Self::is_recognized_content(input)
+    }
+
+    fn get_confidence(&self, path: &str, input: &mut T) -> Option<Confidence> {
+        determine_confidence(
+            is_recognized_extension(path, &Self::ACCEPTED_EXTENSIONS),
+            Self::is_recognized_content(input),
+        )
+    }
+
+    fn get_name(&self) -> &str {
+        "AnDI"
+    }
+
+    fn set_parse_options(&mut self, options: &ParseOptions) {
+        self.options = options.clone();
     }
 
     fn get_reader(&self, path: &str, input: T) -> Result<Box<dyn Reader>, SfError> {
-        let input_seek_read = Box::new(input);
-        let cdf_reader = netcdf3::FileReader::open_seek_read(path, input_seek_read)
-            .map_err(|e| SfError::from_source(e, "AnDI Error. Error parsing netCDF."))?;
+        let cdf_input = CdfInput::new(path, Box::new(input));
+        let cdf_reader = cdf_input.open()?;
 
         if cdf_reader
             .data_set()
             .has_global_attr(Self::AIA_TEMPLATE_REVISION_ATTR)
         {
-            let file = AndiChromParser::parse_cdf(cdf_reader)?;
+            let file = AndiChromParser::parse_cdf(cdf_reader, cdf_input, &self.options)?;
             return Ok(Box::new(AndiChromReader::new(path, file)));
         }
         if cdf_reader
             .data_set()
             .has_global_attr(Self::MS_TEMPLATE_REVISION_ATTR)
         {
-            let file = AndiMsParser::parse_cdf(cdf_reader)?;
+            let file = AndiMsParser::parse_cdf(cdf_reader, cdf_input, &self.options)?;
             return Ok(Box::new(AndiMsReader::new(path, file)));
         }
 
@@ -85,6 +103,7 @@ impl<T: Seek + Read + 'static> Scanner<T> for AndiScanner {
             path,
             Self::AIA_TEMPLATE_REVISION_ATTR,
             Self::MS_TEMPLATE_REVISION_ATTR
-        )))
+        ))
+        .with_kind(SfErrorKind::UnsupportedFormat))
     }
 }
@@ -17,9 +17,55 @@
 // IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
 // CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
-use crate::{common::SfError, utils::from_iso_8859_1_cstr};
+use crate::{
+    api::SeekRead,
+    common::{SfError, SfErrorLocation, SharedSeekRead, WarningCollector},
+    utils::from_iso_8859_1_cstr,
+};
 use netcdf3::{DataType, DataVector};
-use std::{ops::Range, str::FromStr};
+use std::{
+    fmt,
+    io::{Seek, SeekFrom},
+    ops::Range,
+    str::FromStr,
+};
+
+/// The netCDF input of an AnDI file, shared by its lazily loaded parts.
+///
+/// A netCDF reader cannot be shared between threads. Hence, instead of keeping
+/// one reader open, a new reader is opened for each lazy access. Each of these
+/// readers keeps its own position in the shared input.
+#[derive(Clone)]
+pub(crate) struct CdfInput {
+    name: String,
+    input: SharedSeekRead<Box<dyn SeekRead>>,
+}
+
+impl CdfInput {
+    pub fn new(name: &str, input: Box<dyn SeekRead>) -> Self {
+        Self {
+            name: name.to_owned(),
+            input: SharedSeekRead::new(input),
+        }
+    }
+
+    /// Opens a netCDF reader for the input and parses its header.
+    pub fn open(&self) -> Result<netcdf3::FileReader, SfError> {
+        let mut input = self.input.clone();
+        input.seek(SeekFrom::Start(0))?;
+        netcdf3::FileReader::open_seek_read(&self.name, Box::new(input)).map_err(|e| {
+            SfError::from_source(SfError::from(e), "AnDI Error. Error parsing netCDF.")
+        })
+    }
+}
+
+impl fmt::Debug for CdfInput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CdfInput")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
 
 fn read_index_from_var<T: Clone + Copy + From<i16> + PartialEq>(
     var: &Option<(&str, Vec<usize>, DataVector)>,
@@ -66,13 +112,19 @@ pub fn read_index_from_var_f64(
     read_index_from_var(var, slice, index)
 }
 
+/// The location of an error relating to a netCDF variable.
+pub(crate) fn var_location(var_name: &str) -> SfErrorLocation {
+    SfErrorLocation::Variable(var_name.to_owned())
+}
+
 pub fn check_var_is_2d(var_name: &str, dims: &[usize]) -> Result<(), SfError> {
     if dims.len() != 2 {
         return Err(SfError::new(&format!(
             "Unexpected number of dimensions for {}: {}",
             var_name,
             dims.len()
-        )));
+        ))
+        .with_location(var_location(var_name)));
     }
     Ok(())
 }
@@ -87,9 +139,10 @@ pub fn read_index_from_var_2d_string(
             check_var_is_2d(var_name, dims)?;
 
             let row_length = dims[1];
-            let bytes = data
-                .get_u8()
-                .ok_or(SfError::new(&format!("Failed to read {}", var_name)))?;
+            let bytes = data.get_u8().ok_or(
+                SfError::new(&format!("Failed to read {}", var_name))
+                    .with_location(var_location(var_name)),
+            )?;
             let start_index = index * row_length;
             let end_index = start_index + row_length;
             let string_bytes = &bytes[start_index..end_index];
@@ -108,16 +161,19 @@ pub fn read_var_2d_slice_f64(
     let values = var
         .2
         .get_f64()
-        .ok_or(SfError::new(&format!(
-            "Could not read values for variable: {}",
-            var.0
-        )))?
+        .ok_or(
+            SfError::new(&format!("Could not read values for variable: {}", var.0))
+                .with_location(var_location(var.0)),
+        )?
         .get(range.to_owned())
         .map(|v| v.to_owned())
-        .ok_or(SfError::new(&format!(
-            "Could not read range for variable {}: {}..{}",
-            var.0, range.start, range.end
-        )))?;
+        .ok_or(
+            SfError::new(&format!(
+                "Could not read range for variable {}: {}..{}",
+                var.0, range.start, range.end
+            ))
+            .with_location(var_location(var.0)),
+        )?;
     Ok(values)
 }
 
@@ -133,8 +189,10 @@ pub fn read_multi_string_var(
 
             let mut vec = vec![];
             for i in 0..dims[0] {
-                let value = read_index_from_var_2d_string(&var_opt, i)?
-                    .ok_or(SfError::new(&format!("Failed to read {}", var_name)))?;
+                let value = read_index_from_var_2d_string(&var_opt, i)?.ok_or(
+                    SfError::new(&format!("Failed to read {}", var_name))
+                        .with_location(var_location(var_name)),
+                )?;
                 vec.push(value);
             }
             Ok(vec)
@@ -150,10 +208,10 @@ pub fn read_index_from_slice<'a, T: 'a>(
     match slice {
         None => Ok(None),
         Some(sl) => match sl.get(index) {
-            None => Err(SfError::new(&format!(
-                "Index out of bounds for {}: {}",
-                var_name, index
-            ))),
+            None => Err(
+                SfError::new(&format!("Index out of bounds for {}: {}", var_name, index))
+                    .with_location(var_location(var_name)),
+            ),
             Some(val) => Ok(Some(val)),
         },
     }
@@ -169,9 +227,10 @@ pub fn read_optional_var<'a>(
     match var {
         Some(var) => {
             let dims: Vec<usize> = var.get_dims().iter().map(|dim| dim.size()).collect();
-            let vec = reader
-                .read_var(var_name)
-                .map_err(|e| SfError::from_source(e, "Error reading netCDF variable."))?;
+            let vec = reader.read_var(var_name).map_err(|e| {
+                SfError::from_source(SfError::from(e), "Error reading netCDF variable.")
+                    .or_location(|| Some(var_location(var_name)))
+            })?;
             Ok(Some((var_name, dims, vec)))
         }
         None => Ok(None),
@@ -186,14 +245,16 @@ pub fn read_scalar_var_f32(
     match var {
         Some(var) => {
             if var.len() != 1 {
-                return Err(SfError::new(&format!("{} not scalar", var_name)));
+                return Err(SfError::new(&format!("{} not scalar", var_name))
+                    .with_location(var_location(var_name)));
             }
             if var.data_type() != DataType::F32 {
                 return Err(SfError::new(&format!(
                     "{} unexpected data type: {}",
                     var_name,
                     var.data_type()
-                )));
+                ))
+                .with_location(var_location(var_name)));
             }
             let val = reader.read_var_f32(var_name).unwrap()[0];
             Ok(Some(val))
@@ -214,10 +275,10 @@ pub fn read_optional_var_or_attr_f32(
                 match val {
                     [single_val] => value = Some(single_val.to_owned()),
                     _ => {
-                        return Err(SfError::new(&format!(
-                            "Unexpected content for {}.",
-                            var_name
-                        )));
+                        return Err(
+                            SfError::new(&format!("Unexpected content for {}.", var_name))
+                                .with_location(var_location(var_name)),
+                        );
                     }
                 }
             } else if let Some(mut val) = attr.get_as_string() {
@@ -227,6 +288,7 @@ pub fn read_optional_var_or_attr_f32(
                 if !no_zero_bytes_val.is_empty() {
                     let v = val.parse::<f32>().map_err(|_e| {
                         SfError::new(&format!("Error parsing value as float: {}", val))
+                            .with_location(var_location(var_name))
                     })?;
                     value = Some(v);
                 }
@@ -301,6 +363,63 @@ pub fn read_enum_from_global_attr_str<T: Default + FromStr>(
     })
 }
 
+/// Reports a zero terminated global string attribute.
+pub(crate) fn check_global_attr_zero_terminated(
+    reader: &netcdf3::FileReader,
+    attr_name: &str,
+    collector: &mut WarningCollector,
+) {
+    if let Some(s) = reader.data_set().get_global_attr_as_string(attr_name)
+        && s.contains('\0')
+    {
+        collector.report(
+            &format!("Zero terminated value of global {} attribute.", attr_name),
+            None,
+        );
+    }
+}
+
+/// Reports a zero terminated string attribute of a variable.
+pub(crate) fn check_var_attr_zero_terminated(
+    reader: &netcdf3::FileReader,
+    var_name: &str,
+    attr_name: &str,
+    collector: &mut WarningCollector,
+) {
+    if let Some(s) = reader
+        .data_set()
+        .get_var_attr(var_name, attr_name)
+        .and_then(|attr| attr.get_as_string())
+        && s.contains('\0')
+    {
+        collector.report(
+            &format!("Zero terminated value of {} attribute.", attr_name),
+            Some(var_location(var_name)),
+        );
+    }
+}
+
+/// Reports a global attribute that is present under a non-standard name only.
+pub(crate) fn check_global_attr_name(
+    reader: &netcdf3::FileReader,
+    attr_name: &str,
+    non_standard_name: &str,
+    collector: &mut WarningCollector,
+) {
+    let data_set = reader.data_set();
+    if data_set.get_global_attr(attr_name).is_none()
+        && data_set.get_global_attr(non_standard_name).is_some()
+    {
+        collector.report(
+            &format!(
+                "Non-standard global attribute name {} instead of {}.",
+                non_standard_name, attr_name
+            ),
+            None,
+        );
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -337,4 +456,19 @@ mod tests {
         assert_eq!(expect, from_iso_8859_1_cstr(&iso8850_1_data_zt));
         assert_eq!(expect, from_iso_8859_1_cstr(&iso8850_1_data_zt_plus));
     }
+
+    #[test]
+    fn test_var_errors_provide_variable_name() {
+        let error = check_var_is_2d("ordinate_values", &[3]).unwrap_err();
+        assert_eq!(
+            Some(&SfErrorLocation::Variable("ordinate_values".to_owned())),
+            error.location()
+        );
+
+        let error = read_index_from_slice(Some(&[1, 2][..]), "ordinate_values", 2).unwrap_err();
+        assert_eq!(
+            Some(&SfErrorLocation::Variable("ordinate_values".to_owned())),
+            error.location()
+        );
+    }
 }
@@ -17,21 +17,51 @@
 // IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
 // CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
-use crate::{common::SfError, json_exporter::JsonExporter};
+use crate::{
+    common::{SfError, SfErrorKind, SfErrorLocation},
+    csv_exporter::CsvExporter,
+    gaml::gaml_exporter::GamlExporter,
+    jdx::jdx_exporter::JdxExporter,
+    json_exporter::JsonExporter,
+    utils::extract_data_range,
+};
 use std::{
     collections::HashMap,
     error::Error,
     fmt::Display,
     io::{BufRead, Read, Seek, Write},
+    ops::Range,
 };
 
+/// Marker for types that are `Send` if the "sync" feature is enabled.
+#[cfg(feature = "sync")]
+pub trait MaybeSend: Send {}
+#[cfg(feature = "sync")]
+impl<T: Send + ?Sized> MaybeSend for T {}
+/// Marker for types that are `Send` if the "sync" feature is enabled.
+#[cfg(not(feature = "sync"))]
+pub trait MaybeSend {}
+#[cfg(not(feature = "sync"))]
+impl<T: ?Sized> MaybeSend for T {}
+
+/// Marker for types that are `Send + Sync` if the "sync" feature is enabled.
+#[cfg(feature = "sync")]
+pub trait MaybeSendSync: Send + Sync {}
+#[cfg(feature = "sync")]
+impl<T: Send + Sync + ?Sized> MaybeSendSync for T {}
+/// Marker for types that are `Send + Sync` if the "sync" feature is enabled.
+#[cfg(not(feature = "sync"))]
+pub trait MaybeSendSync {}
+#[cfg(not(feature = "sync"))]
+impl<T: ?Sized> MaybeSendSync for T {}
+
 /// Abstraction for any kind of random access input.
-pub trait SeekRead: Seek + Read {}
-impl<T: Seek + Read> SeekRead for T {}
+pub trait SeekRead: Seek + Read + MaybeSend {}
+impl<T: Seek + Read + MaybeSend> SeekRead for T {}
 
 /// Abstraction for any kind of buffered text input with lines and random access.
-pub trait SeekBufRead: Seek + BufRead {}
-impl<T: Seek + BufRead> SeekBufRead for T {}
+pub trait SeekBufRead: Seek + BufRead + MaybeSend {}
+impl<T: Seek + BufRead + MaybeSend> SeekBufRead for T {}
 
 /// Parses a (readonly) data set.
 pub trait Parser<T: Read + Seek> {
@@ -42,7 +72,9 @@ pub trait Parser<T: Read + Seek> {
 }
 
 /// Scans a data set and provides a reader for recognized formats.
-pub trait Scanner<T: Read + Seek> {
+///
+/// With the "sync" feature enabled, scanners are required to be `Send + Sync`.
+pub trait Scanner<T: Read + Seek>: MaybeSendSync {
     /// Checks whether a data set is recognized. Shallow check.
     ///
     /// # Arguments
@@ -58,6 +90,33 @@ pub trait Scanner<T: Read + Seek> {
     /// The cursor in `input` is not guaranteed to be reset upon return.
     fn is_recognized(&self, path: &str, input: &mut T) -> bool;
 
+    /// Determines the confidence with which a data set is recognized. Shallow check.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the data set.
+    /// * `input` - The readonly raw data set.
+    ///
+    /// # Notes
+    ///
+    /// Returns `None` if the data set is not recognized at all. Unless overridden,
+    /// a data set recognized by `is_recognized()` is reported with high confidence.
+    ///
+    /// The cursor in `input` is not guaranteed to be reset upon return.
+    fn get_confidence(&self, path: &str, input: &mut T) -> Option<Confidence> {
+        self.is_recognized(path, input).then_some(Confidence::High)
+    }
+
+    /// Provides a human readable name for the scanner, e.g., the format it recognizes.
+    fn get_name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+
+    /// Sets the options for parsing data sets.
+    ///
+    /// Unless overridden, the options are ignored.
+    fn set_parse_options(&mut self, _options: &ParseOptions) {}
+
     /// Provides a reader for a recognized data set.
     ///
     /// # Arguments
@@ -74,8 +133,68 @@ pub trait Scanner<T: Read + Seek> {
     fn get_reader(&self, path: &str, input: T) -> Result<Box<dyn Reader>, SfError>;
 }
 
+/// The confidence with which a scanner recognizes a data set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Confidence {
+    /// The data set may be readable, e.g., the path matches but the content does not.
+    Low,
+    /// The data set is likely readable, e.g., the content matches but the path does not.
+    Medium,
+    /// The data set is readable, e.g., both path and content match.
+    High,
+}
+
+/// How strictly a data set is checked against its format specification.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ParseMode {
+    /// Known deviations from the specification are accepted and reported as warnings.
+    #[default]
+    Lenient,
+    /// Known deviations from the specification result in an error.
+    Strict,
+}
+
+/// Options for parsing data sets.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ParseOptions {
+    pub mode: ParseMode,
+    /// Whether data sets with an integrity checksum that does not match their content or
+    /// cannot be verified, e.g., due to an unsupported algorithm, result in an error.
+    pub strict_integrity: bool,
+    /// Whether only the positions of the parts of large data sets are recorded when parsing
+    /// and the parts are parsed when read, e.g., experiments and traces of GAML files. Reduces
+    /// the time to open and the memory held for large data sets. Errors in the parts are
+    /// reported when reading them.
+    pub on_demand: bool,
+    /// Whether scanners for data sets stored as directory, e.g., Bruker experiment
+    /// directories, read the files of the directory from the file system for paths pointing
+    /// to the directory or a file in it, ignoring the input. Otherwise, such data sets are only
+    /// recognized from the input, e.g., a ZIP archive holding the directory.
+    pub read_directories: bool,
+}
+
+impl ParseOptions {
+    /// Options for strict parsing.
+    pub fn strict() -> Self {
+        Self {
+            mode: ParseMode::Strict,
+            ..Default::default()
+        }
+    }
+}
+
+/// A deviation from the format specification accepted while parsing a data set.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseWarning {
+    pub message: String,
+    pub location: Option<SfErrorLocation>,
+}
+
 /// Provides a harmonized view for reading a scientifc data set.
-pub trait Reader {
+///
+/// With the "sync" feature enabled, readers are required to be `Send + Sync`
+/// and may be read from concurrently.
+pub trait Reader: MaybeSendSync {
     /// Reads a Node from the data set.
     ///
     /// # Arguments
@@ -83,12 +202,46 @@ pub trait Reader {
     /// * `path` - The path inside the data set identifying the Node.
     fn read(&self, path: &str) -> Result<Node, SfError>;
 
+    /// Reads the number of data points of a Node.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path inside the data set identifying the Node.
+    ///
+    /// The default implementation reads the whole Node. Specific readers
+    /// may override this method and avoid reading the data points.
+    fn read_data_len(&self, path: &str) -> Result<usize, SfError> {
+        Ok(self.read(path)?.data.len())
+    }
+
+    /// Reads a range of data points of a Node.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path inside the data set identifying the Node.
+    /// * `range` - The indices of the data points to read.
+    ///
+    /// Returns an error if the range exceeds the number of data points.
+    ///
+    /// The default implementation reads the whole Node. Specific readers
+    /// may override this method and avoid materializing all data points.
+    fn read_data(&self, path: &str, range: Range<usize>) -> Result<Vec<PointXy>, SfError> {
+        extract_data_range(self.read(path)?.data, &range)
+    }
+
     /// Provides a list of the supported export formats for the reader.
     ///
-    /// The canonical JSON format is provided for all readers. Specific readers
-    /// may override this method and provide additional export formats.
+    /// The canonical JSON format, CSV, TSV, JCAMP-DX, and GAML are provided for all readers.
+    /// Specific readers may override this method and provide additional export formats.
     fn get_export_formats(&self) -> &'static [ExportFormat] {
-        &[ExportFormat::Json]
+        &COMMON_EXPORT_FORMATS
+    }
+
+    /// Provides the deviations from the format specification accepted while parsing.
+    ///
+    /// Unless overridden, no warnings are provided.
+    fn get_warnings(&self) -> &[ParseWarning] {
+        &[]
     }
 
     /// Exports data.
@@ -100,17 +253,57 @@ pub trait Reader {
     ///
     /// Writes data in the export format to the writer. Returns an error in case of any issue.
     fn export(&self, format: ExportFormat, writer: &mut dyn Write) -> Result<(), SfError> {
-        match format {
-            ExportFormat::Json => {
-                let mut exporter = JsonExporter::new(self);
-                exporter.write(writer)
-            }
+        export_data_set(self, format, writer)
+    }
+}
+
+/// The export formats provided for all readers.
+pub(crate) const COMMON_EXPORT_FORMATS: [ExportFormat; 5] = [
+    ExportFormat::Json,
+    ExportFormat::Csv,
+    ExportFormat::Tsv,
+    ExportFormat::Jdx,
+    ExportFormat::Gaml,
+];
+
+/// Exports a data set in one of the formats provided for all readers.
+///
+/// Returns an error for other formats.
+pub(crate) fn export_data_set<R: Reader + ?Sized>(
+    reader: &R,
+    format: ExportFormat,
+    writer: &mut dyn Write,
+) -> Result<(), SfError> {
+    match format {
+        ExportFormat::Json => {
+            let mut exporter = JsonExporter::new(reader);
+            exporter.write(writer)
+        }
+        ExportFormat::Csv => {
+            let mut exporter = CsvExporter::csv(reader);
+            exporter.write(writer)
+        }
+        ExportFormat::Tsv => {
+            let mut exporter = CsvExporter::tsv(reader);
+            exporter.write(writer)
+        }
+        ExportFormat::Jdx => {
+            let mut exporter = JdxExporter::new(reader);
+            exporter.write(writer)
         }
+        ExportFormat::Gaml => {
+            let mut exporter = GamlExporter::new(reader);
+            exporter.write(writer)
+        }
+        ExportFormat::Molfile => Err(SfError::new(
+            "Molfile export is only supported for data sets holding a chemical structure.",
+        )
+        .with_kind(SfErrorKind::UnsupportedFormat)),
     }
 }
 
 /// A parameter value.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Value {
     String(String),
     Bool(bool),
@@ -137,7 +330,7 @@ impl Display for Value {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 /// A parameter.
 pub enum Parameter {
     KeyValue(String, Value),
@@ -212,7 +405,7 @@ impl Parameter {
 }
 
 /// A 2D data point.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct PointXy {
     pub x: f64,
     pub y: f64,
@@ -233,7 +426,7 @@ impl From<(f64, f64)> for PointXy {
 /// A table column.
 ///
 /// Note: This does not hold any data. It is used to indicate what columns a table consists of.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Column {
     /// A unique key for a table.
     pub key: String,
@@ -251,7 +444,7 @@ impl Column {
 }
 
 /// A data table.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Table {
     /// A list of column keys and corresponding column names.
     pub column_names: Vec<Column>,
@@ -265,23 +458,235 @@ pub struct Table {
     pub rows: Vec<HashMap<String, Value>>,
 }
 
+/// An axis of a multi-dimensional array.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Axis {
+    /// The name of the axis, e.g., "Time".
+    pub name: String,
+    /// The unit of the axis values, if known.
+    pub unit: Option<String>,
+    /// The axis values, one for each index along the axis.
+    pub values: Vec<f64>,
+}
+
+impl Axis {
+    pub fn new(name: impl Into<String>, unit: Option<String>, values: Vec<f64>) -> Axis {
+        Axis {
+            name: name.into(),
+            unit,
+            values,
+        }
+    }
+}
+
+/// A multi-dimensional array of values.
+///
+/// The values are stored in row-major order, i.e., the index of the last axis
+/// changes fastest. The number of values equals the product of the axes' lengths.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NdArray {
+    /// The name of the values, e.g., "Intensity".
+    pub name: String,
+    /// The unit of the values, if known.
+    pub unit: Option<String>,
+    /// One axis per dimension, from the outermost to the innermost dimension.
+    pub axes: Vec<Axis>,
+    /// The values in row-major order.
+    pub values: Vec<f64>,
+}
+
+impl NdArray {
+    /// Creates an array. Fails if the number of values does not match the axes' lengths.
+    pub fn new(
+        name: impl Into<String>,
+        unit: Option<String>,
+        axes: Vec<Axis>,
+        values: Vec<f64>,
+    ) -> Result<NdArray, SfError> {
+        let array = NdArray {
+            name: name.into(),
+            unit,
+            axes,
+            values,
+        };
+        let expected_len = array.get_shape().iter().product::<usize>();
+        if array.values.len() != expected_len {
+            return Err(SfError::new(&format!(
+                "Number of array values does not match shape {:?}: {}",
+                array.get_shape(),
+                array.values.len()
+            )));
+        }
+        Ok(array)
+    }
+
+    /// Provides the lengths of all axes.
+    pub fn get_shape(&self) -> Vec<usize> {
+        self.axes.iter().map(|axis| axis.values.len()).collect()
+    }
+
+    /// Provides the value at the given indices, one for each axis.
+    pub fn get(&self, indices: &[usize]) -> Option<f64> {
+        let shape = self.get_shape();
+        if indices.len() != shape.len() {
+            return None;
+        }
+        let mut offset = 0usize;
+        for (index, len) in indices.iter().zip(shape) {
+            if *index >= len {
+                return None;
+            }
+            offset = offset * len + index;
+        }
+        self.values.get(offset).copied()
+    }
+
+    /// Provides the array at an index along an axis, e.g., a column of a matrix for axis 1.
+    ///
+    /// The provided array has all axes except the selected one. Returns None for an illegal
+    /// axis or index.
+    pub fn select(&self, axis: usize, index: usize) -> Option<NdArray> {
+        let shape = self.get_shape();
+        let len = *shape.get(axis)?;
+        if index >= len {
+            return None;
+        }
+        let outer_len: usize = shape[..axis].iter().product();
+        let inner_len: usize = shape[axis + 1..].iter().product();
+        let mut values = Vec::with_capacity(outer_len * inner_len);
+        for outer_index in 0..outer_len {
+            let start = (outer_index * len + index) * inner_len;
+            values.extend_from_slice(&self.values[start..start + inner_len]);
+        }
+        let axes = self
+            .axes
+            .iter()
+            .enumerate()
+            .filter(|(i, _axis)| *i != axis)
+            .map(|(_i, axis)| axis.clone())
+            .collect();
+        Some(NdArray {
+            name: self.name.clone(),
+            unit: self.unit.clone(),
+            axes,
+            values,
+        })
+    }
+}
+
+/// A complex-valued signal, e.g., an NMR FID, with real and imaginary parts sharing an axis.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComplexData {
+    /// The name of the signal, e.g., "FID".
+    pub name: String,
+    /// The unit of the real and imaginary values, if known.
+    pub unit: Option<String>,
+    /// The axis shared by the real and imaginary values.
+    pub axis: Axis,
+    /// The real parts, one for each axis value.
+    pub real: Vec<f64>,
+    /// The imaginary parts, one for each axis value.
+    pub imaginary: Vec<f64>,
+}
+
+impl ComplexData {
+    /// Creates a complex signal. Fails if the number of real or imaginary values
+    /// does not match the axis' length.
+    pub fn new(
+        name: impl Into<String>,
+        unit: Option<String>,
+        axis: Axis,
+        real: Vec<f64>,
+        imaginary: Vec<f64>,
+    ) -> Result<ComplexData, SfError> {
+        if real.len() != axis.values.len() || imaginary.len() != axis.values.len() {
+            return Err(SfError::new(&format!(
+                "Number of real and imaginary values does not match axis length {}: {}, {}",
+                axis.values.len(),
+                real.len(),
+                imaginary.len()
+            )));
+        }
+        Ok(ComplexData {
+            name: name.into(),
+            unit,
+            axis,
+            real,
+            imaginary,
+        })
+    }
+
+    /// Provides the magnitudes, i.e., the absolute values.
+    pub fn get_magnitude(&self) -> Vec<f64> {
+        self.real
+            .iter()
+            .zip(&self.imaginary)
+            .map(|(re, im)| re.hypot(*im))
+            .collect()
+    }
+
+    /// Provides the phases in radians in the range [-π, π].
+    pub fn get_phase(&self) -> Vec<f64> {
+        self.real
+            .iter()
+            .zip(&self.imaginary)
+            .map(|(re, im)| im.atan2(*re))
+            .collect()
+    }
+}
+
 /// A tree node representing a section of data.
 /// An harmonized abstraction for a part of a data set.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Node {
     pub name: String,
     pub parameters: Vec<Parameter>,
     pub data: Vec<PointXy>,
     pub metadata: Vec<(String, String)>,
     pub table: Option<Table>,
+    /// Multi-dimensional data, e.g., for a series of spectra sharing an axis.
+    pub nd_array: Option<NdArray>,
+    /// Complex-valued data, e.g., for an NMR FID with real and imaginary parts.
+    pub complex_data: Option<ComplexData>,
+    /// Links to related nodes, e.g., the structure a spectrum refers to.
+    pub links: Vec<NodeLink>,
     pub child_node_names: Vec<String>,
 }
 
+/// A link from a node to a related node of the same reader.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeLink {
+    /// The kind of relation, e.g., "STRUCTURE" or "PEAK TABLE".
+    pub relation: String,
+    /// The path of the linked node, e.g., "/2".
+    pub path: String,
+}
+
+impl NodeLink {
+    pub fn new(relation: impl Into<String>, path: impl Into<String>) -> Self {
+        Self {
+            relation: relation.into(),
+            path: path.into(),
+        }
+    }
+}
+
 /// A parameter value.
 #[derive(Debug, PartialEq)]
 pub enum ExportFormat {
     /// Exporter to canonical JSON.
     Json,
+    /// Exporter to comma separated values, one set of files per node bundled in a ZIP archive.
+    Csv,
+    /// Exporter to tab separated values, one set of files per node bundled in a ZIP archive.
+    Tsv,
+    /// Exporter to JCAMP-DX, one block per node.
+    Jdx,
+    /// Exporter to GAML, one experiment per node holding data.
+    Gaml,
+    /// Exporter to MDL Molfile (V2000), the chemical structure of a data set, e.g., of the first
+    /// JCAMP-CS block holding one.
+    Molfile,
 }
 
 /// Exports data.
@@ -293,6 +698,7 @@ pub trait Exporter {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::f64::consts::{FRAC_PI_2, PI};
 
     #[test]
     fn table_value_displays_value() {
@@ -392,6 +798,9 @@ mod tests {
             data: vec![],
             metadata: vec![],
             table: None,
+            nd_array: None,
+            complex_data: None,
+            links: vec![],
             child_node_names: vec![],
         };
         assert!(format!("{:?}", node).contains("Node"));
@@ -400,6 +809,106 @@ mod tests {
         assert!(format!("{:?}", node).contains("data"));
         assert!(format!("{:?}", node).contains("metadata"));
         assert!(format!("{:?}", node).contains("table"));
+        assert!(format!("{:?}", node).contains("nd_array"));
+        assert!(format!("{:?}", node).contains("complex_data"));
         assert!(format!("{:?}", node).contains("child_node_names"));
     }
+
+    #[test]
+    fn nd_array_provides_values_by_indices() {
+        let nd_array = NdArray::new(
+            "values",
+            None,
+            vec![
+                Axis::new("axis 0", None, vec![0.0, 1.0]),
+                Axis::new("axis 1", Some("unit".to_owned()), vec![10.0, 20.0, 30.0]),
+            ],
+            vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0],
+        )
+        .unwrap();
+
+        assert_eq!(vec![2, 3], nd_array.get_shape());
+        assert_eq!(Some(1.0), nd_array.get(&[0, 0]));
+        assert_eq!(Some(3.0), nd_array.get(&[0, 2]));
+        assert_eq!(Some(4.0), nd_array.get(&[1, 0]));
+        assert_eq!(Some(6.0), nd_array.get(&[1, 2]));
+        assert_eq!(None, nd_array.get(&[2, 0]));
+        assert_eq!(None, nd_array.get(&[0, 3]));
+        assert_eq!(None, nd_array.get(&[0]));
+    }
+
+    #[test]
+    fn nd_array_provides_selected_sub_arrays() {
+        let nd_array = NdArray::new(
+            "values",
+            Some("unit".to_owned()),
+            vec![
+                Axis::new("axis 0", None, vec![0.0, 1.0]),
+                Axis::new("axis 1", None, vec![10.0, 20.0, 30.0]),
+                Axis::new("axis 2", None, vec![100.0, 200.0]),
+            ],
+            (1..=12).map(f64::from).collect(),
+        )
+        .unwrap();
+
+        let matrix = nd_array.select(1, 2).unwrap();
+        assert_eq!(vec![2, 2], matrix.get_shape());
+        assert_eq!("axis 0", matrix.axes[0].name);
+        assert_eq!("axis 2", matrix.axes[1].name);
+        assert_eq!(vec![5.0, 6.0, 11.0, 12.0], matrix.values);
+        assert_eq!(Some("unit".to_owned()), matrix.unit);
+
+        let column = matrix.select(1, 0).unwrap();
+        assert_eq!(vec![2], column.get_shape());
+        assert_eq!(vec![5.0, 11.0], column.values);
+        let row = matrix.select(0, 1).unwrap();
+        assert_eq!(vec![11.0, 12.0], row.values);
+
+        assert_eq!(None, nd_array.select(3, 0));
+        assert_eq!(None, nd_array.select(0, 2));
+    }
+
+    #[test]
+    fn nd_array_rejects_values_not_matching_shape() {
+        let result = NdArray::new(
+            "values",
+            None,
+            vec![
+                Axis::new("axis 0", None, vec![0.0, 1.0]),
+                Axis::new("axis 1", None, vec![10.0, 20.0]),
+            ],
+            vec![1.0, 2.0, 3.0],
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn complex_data_provides_magnitude_and_phase() {
+        let complex_data = ComplexData::new(
+            "FID",
+            None,
+            Axis::new("TIME", Some("SECONDS".to_owned()), vec![0.0, 1.0, 2.0]),
+            vec![3.0, 0.0, -1.0],
+            vec![4.0, 2.0, 0.0],
+        )
+        .unwrap();
+
+        assert_eq!(vec![5.0, 2.0, 1.0], complex_data.get_magnitude());
+        assert_eq!(
+            vec![4.0f64.atan2(3.0), FRAC_PI_2, PI],
+            complex_data.get_phase()
+        );
+    }
+
+    #[test]
+    fn complex_data_rejects_values_not_matching_axis() {
+        let result = ComplexData::new(
+            "FID",
+            None,
+            Axis::new("TIME", None, vec![0.0, 1.0]),
+            vec![1.0, 2.0],
+            vec![1.0],
+        );
+        assert!(result.is_err());
+    }
 }
@@ -0,0 +1,494 @@
+// Copyright (c) 2025 Robert Schiwon
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use crate::{
+    api::{Column, Parameter, Table, Value},
+    common::{SfError, WarningCollector},
+};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::LazyLock;
+
+/// An array index range in groups 1 and 2 followed by the values in group 3, e.g.,
+/// "(0..63)" and 64 values.
+static ARRAY_REGEX: LazyLock<regex::Regex> =
+    LazyLock::new(|| regex::Regex::new(r"(?s)^\(\s*(\d+)\s*\.\.\s*(\d+)\s*\)(.*)$").unwrap());
+
+/// A typed Bruker parameter value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BrukerValue {
+    /// An integer, e.g., "65536".
+    I64(i64),
+    /// A floating point number, e.g., "400.13".
+    F64(f64),
+    /// A string without the enclosing angle brackets, e.g., "1H" for "<1H>".
+    String(String),
+    /// An array with its index range, e.g., "(0..63)" followed by 64 values.
+    Array {
+        first_index: usize,
+        last_index: usize,
+        values: Vec<BrukerValue>,
+    },
+    /// Any other value, e.g., text without angle brackets or an array whose number of values
+    /// does not match its index range.
+    Text(String),
+}
+
+impl BrukerValue {
+    /// Parses a raw parameter value, including the lines following an array index range.
+    pub fn parse(raw_value: &str) -> BrukerValue {
+        let value = raw_value.trim();
+        if let Some(caps) = ARRAY_REGEX.captures(value)
+            && let (Ok(first_index), Ok(last_index)) =
+                (caps[1].parse::<usize>(), caps[2].parse::<usize>())
+            && first_index <= last_index
+        {
+            let values: Vec<BrukerValue> =
+                Self::tokenize(&caps[3]).map(Self::parse_scalar).collect();
+            if values.len() == last_index - first_index + 1 {
+                return BrukerValue::Array {
+                    first_index,
+                    last_index,
+                    values,
+                };
+            }
+            return BrukerValue::Text(value.to_owned());
+        }
+        let mut tokens = Self::tokenize(value);
+        match (tokens.next(), tokens.next()) {
+            (Some(token), None) => Self::parse_scalar(token),
+            _ => BrukerValue::Text(value.to_owned()),
+        }
+    }
+
+    /// Splits values at whitespace, keeping strings in angle brackets together.
+    fn tokenize(values: &str) -> impl Iterator<Item = &str> {
+        // a string in angle brackets or a sequence of non whitespace characters
+        const TOKEN_REGEX_PATTERN: &str = r"<[^>]*>|\S+";
+        static TOKEN_REGEX: LazyLock<regex::Regex> =
+            LazyLock::new(|| regex::Regex::new(TOKEN_REGEX_PATTERN).unwrap());
+
+        TOKEN_REGEX.find_iter(values).map(|m| m.as_str())
+    }
+
+    fn parse_scalar(token: &str) -> BrukerValue {
+        if let Some(string) = token
+            .strip_prefix('<')
+            .and_then(|token| token.strip_suffix('>'))
+        {
+            return BrukerValue::String(string.to_owned());
+        }
+        if let Ok(value) = token.parse::<i64>() {
+            return BrukerValue::I64(value);
+        }
+        match token.parse::<f64>() {
+            Ok(value) => BrukerValue::F64(value),
+            Err(_) => BrukerValue::Text(token.to_owned()),
+        }
+    }
+
+    /// The value as floating point number if numeric.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            BrukerValue::I64(value) => Some(*value as f64),
+            BrukerValue::F64(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// The value as integer if integral.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            BrukerValue::I64(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// The value as string if a string or text.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            BrukerValue::String(value) | BrukerValue::Text(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Maps the value to a parameter value, joining array values by blanks.
+    pub fn to_value(&self) -> Value {
+        match self {
+            BrukerValue::I64(value) => Value::I64(*value),
+            BrukerValue::F64(value) => Value::F64(*value),
+            _ => Value::String(self.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for BrukerValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BrukerValue::I64(value) => write!(f, "{}", value),
+            BrukerValue::F64(value) => write!(f, "{}", value),
+            BrukerValue::String(value) | BrukerValue::Text(value) => write!(f, "{}", value),
+            BrukerValue::Array { values, .. } => {
+                let values: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+                write!(f, "{}", values.join(" "))
+            }
+        }
+    }
+}
+
+/// A Bruker parameter, e.g., "##$TD= 65536".
+#[derive(Debug, Clone, PartialEq)]
+pub struct BrukerParameter {
+    /// The label without "##" and "=", e.g., "$TD".
+    pub name: String,
+    /// The typed value.
+    pub value: BrukerValue,
+}
+
+impl BrukerParameter {
+    /// Creates a parameter from a label and a raw value, e.g., "$TD" and "65536".
+    pub fn parse(name: impl Into<String>, raw_value: &str) -> BrukerParameter {
+        BrukerParameter {
+            name: name.into(),
+            value: BrukerValue::parse(raw_value),
+        }
+    }
+}
+
+/// Maps Bruker parameters to node parameters and a table holding the array elements.
+///
+/// Arrays are provided as parameters with their values joined by blanks and additionally as
+/// one table row per element with the parameter name, index, and value. The table is None if
+/// there are no arrays.
+pub fn map_parameters(parameters: &[BrukerParameter]) -> (Vec<Parameter>, Option<Table>) {
+    let node_parameters = parameters
+        .iter()
+        .map(|p| Parameter::KeyValue(p.name.clone(), p.value.to_value()))
+        .collect();
+
+    let mut rows = vec![];
+    for parameter in parameters {
+        let BrukerValue::Array {
+            first_index,
+            values,
+            ..
+        } = &parameter.value
+        else {
+            continue;
+        };
+        for (i, value) in values.iter().enumerate() {
+            rows.push(HashMap::from([
+                (
+                    "parameter".to_owned(),
+                    Value::String(parameter.name.clone()),
+                ),
+                ("index".to_owned(), Value::U64((first_index + i) as u64)),
+                ("value".to_owned(), value.to_value()),
+            ]));
+        }
+    }
+    let table = (!rows.is_empty()).then(|| Table {
+        column_names: vec![
+            Column::new("parameter", "Parameter"),
+            Column::new("index", "Index"),
+            Column::new("value", "Value"),
+        ],
+        rows,
+    });
+
+    (node_parameters, table)
+}
+
+/// Parses a Bruker parameter file such as "acqus" or "procs".
+///
+/// The file uses JCAMP-DX syntax. Lines starting with "$$" and trailing "$$" comments are
+/// ignored, parsing stops at "##END=".
+pub fn parse_parameter_file(content: &str) -> Result<Vec<BrukerParameter>, SfError> {
+    let mut parameters = Vec::<BrukerParameter>::new();
+    let mut current: Option<(String, String)> = None;
+    let mut push_current = |current: Option<(String, String)>| {
+        if let Some((name, raw_value)) = current {
+            parameters.push(BrukerParameter::parse(name, &raw_value));
+        }
+    };
+
+    for line in content.lines() {
+        let line = strip_comment(line);
+        if let Some(ldr) = line.strip_prefix("##") {
+            let (label, value) = ldr.split_once('=').ok_or(SfError::new(&format!(
+                "Malformed Bruker parameter: {}",
+                line
+            )))?;
+            push_current(current.take());
+            let label = label.trim();
+            if label == "END" {
+                break;
+            }
+            current = Some((label.to_owned(), value.trim().to_owned()));
+        } else if let Some((_, raw_value)) = &mut current
+            && !line.trim().is_empty()
+        {
+            // continuation line, e.g., array values
+            raw_value.push('\n');
+            raw_value.push_str(line.trim());
+        }
+    }
+    push_current(current.take());
+
+    Ok(parameters)
+}
+
+/// Reports the deviations of a parameter file from the JCAMP-DX syntax, i.e., a missing
+/// "##END=" and arrays whose number of values does not match their index range.
+pub(crate) fn check_parameter_file(
+    file_name: &str,
+    content: &str,
+    parameters: &[BrukerParameter],
+    collector: &mut WarningCollector,
+) {
+    let has_end = content.lines().any(|line| {
+        strip_comment(line)
+            .strip_prefix("##")
+            .and_then(|ldr| ldr.split_once('='))
+            .is_some_and(|(label, _)| label.trim() == "END")
+    });
+    if !has_end {
+        collector.report(
+            &format!("Missing \"##END=\" in Bruker parameter file: {}", file_name),
+            None,
+        );
+    }
+    for parameter in parameters {
+        if let BrukerValue::Text(text) = &parameter.value
+            && ARRAY_REGEX.is_match(text)
+        {
+            collector.report(
+                &format!(
+                    "Number of values not matching the index range of Bruker parameter {} in: {}",
+                    parameter.name, file_name
+                ),
+                None,
+            );
+        }
+    }
+}
+
+/// Finds the value of a parameter by its name, e.g., "$TD", ignoring case.
+pub fn find_parameter<'p>(
+    name: &str,
+    parameters: &'p [BrukerParameter],
+) -> Option<&'p BrukerValue> {
+    parameters
+        .iter()
+        .find(|parameter| parameter.name.eq_ignore_ascii_case(name))
+        .map(|parameter| &parameter.value)
+}
+
+/// Removes a "$$" comment from a line unless it is inside angle brackets.
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    let bytes = line.as_bytes();
+    for (i, c) in bytes.iter().enumerate() {
+        match c {
+            b'<' => in_string = true,
+            b'>' => in_string = false,
+            b'$' if !in_string && bytes.get(i + 1) == Some(&b'$') => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_typed_values() {
+        assert_eq!(BrukerValue::I64(65536), BrukerValue::parse("65536"));
+        assert_eq!(BrukerValue::F64(400.13), BrukerValue::parse(" 400.13 "));
+        assert_eq!(BrukerValue::F64(-1e-6), BrukerValue::parse("-1e-06"));
+        assert_eq!(
+            BrukerValue::String("1H".to_owned()),
+            BrukerValue::parse("<1H>")
+        );
+        assert_eq!(
+            BrukerValue::String("a b".to_owned()),
+            BrukerValue::parse("<a b>")
+        );
+        assert_eq!(BrukerValue::String("".to_owned()), BrukerValue::parse("<>"));
+        assert_eq!(
+            BrukerValue::Text("some text".to_owned()),
+            BrukerValue::parse("some text")
+        );
+        assert_eq!(
+            BrukerValue::Array {
+                first_index: 0,
+                last_index: 3,
+                values: vec![
+                    BrukerValue::I64(10),
+                    BrukerValue::F64(9.5),
+                    BrukerValue::I64(0),
+                    BrukerValue::I64(0)
+                ]
+            },
+            BrukerValue::parse("(0..3)\n10 9.5\n0 0")
+        );
+        assert_eq!(
+            BrukerValue::Array {
+                first_index: 1,
+                last_index: 2,
+                values: vec![
+                    BrukerValue::String("".to_owned()),
+                    BrukerValue::String("gauss 1".to_owned())
+                ]
+            },
+            BrukerValue::parse("(1..2)\n<> <gauss 1>")
+        );
+        // number of values not matching index range
+        assert_eq!(
+            BrukerValue::Text("(0..3)\n1 2".to_owned()),
+            BrukerValue::parse("(0..3)\n1 2")
+        );
+    }
+
+    #[test]
+    fn maps_values() {
+        assert_eq!(Some(3.0), BrukerValue::I64(3).as_f64());
+        assert_eq!(None, BrukerValue::F64(3.0).as_i64());
+        assert_eq!(Some("1H"), BrukerValue::String("1H".to_owned()).as_str());
+        assert_eq!(Value::F64(1.5), BrukerValue::F64(1.5).to_value());
+        assert_eq!(
+            Value::String("1 2.5".to_owned()),
+            BrukerValue::parse("(0..1) 1 2.5").to_value()
+        );
+    }
+
+    #[test]
+    fn maps_parameters_and_array_table() {
+        let parameters = vec![
+            BrukerParameter::parse("$TD", "8"),
+            BrukerParameter::parse("$P", "(1..2)\n10.5 <a b>"),
+        ];
+
+        let (node_parameters, table) = map_parameters(&parameters);
+
+        assert_eq!(
+            vec![
+                Parameter::from_str_i64("$TD", 8),
+                Parameter::from_str_str("$P", "10.5 a b"),
+            ],
+            node_parameters
+        );
+        let table = table.unwrap();
+        assert_eq!(3, table.column_names.len());
+        assert_eq!(
+            vec![
+                HashMap::from([
+                    ("parameter".to_owned(), Value::String("$P".to_owned())),
+                    ("index".to_owned(), Value::U64(1)),
+                    ("value".to_owned(), Value::F64(10.5)),
+                ]),
+                HashMap::from([
+                    ("parameter".to_owned(), Value::String("$P".to_owned())),
+                    ("index".to_owned(), Value::U64(2)),
+                    ("value".to_owned(), Value::String("a b".to_owned())),
+                ]),
+            ],
+            table.rows
+        );
+        assert!(map_parameters(&parameters[..1]).1.is_none());
+    }
+
+    #[test]
+    fn parses_parameter_file() {
+        let content = "##TITLE= Parameter file, TOPSPIN\t\tVersion 3.6.2\n\
+                       ##JCAMPDX= 5.0\n\
+                       ##DATATYPE= Parameter Values\n\
+                       ##NPOINTS= 4\t$$ modification sequence number\n\
+                       $$ 2020-01-01 12:00:00.000 +0100  user@host\n\
+                       ##$BYTORDA= 0\n\
+                       ##$D= (0..3)\n\
+                       0 1 0.03\n\
+                       0\n\
+                       ##$NUC1= <1H>\n\
+                       ##$SFO1= 400.132470966543\n\
+                       ##END=\n\
+                       ##$IGNORED= 1\n";
+
+        let parameters = parse_parameter_file(content).unwrap();
+
+        assert_eq!(8, parameters.len());
+        assert_eq!(
+            BrukerParameter {
+                name: "TITLE".to_owned(),
+                value: BrukerValue::Text("Parameter file, TOPSPIN\t\tVersion 3.6.2".to_owned())
+            },
+            parameters[0]
+        );
+        assert_eq!(
+            Some(&BrukerValue::I64(4)),
+            find_parameter("NPOINTS", &parameters)
+        );
+        assert_eq!(
+            Some(&BrukerValue::I64(0)),
+            find_parameter("$bytorda", &parameters)
+        );
+        assert_eq!(
+            Some(4),
+            match find_parameter("$D", &parameters) {
+                Some(BrukerValue::Array { values, .. }) => Some(values.len()),
+                _ => None,
+            }
+        );
+        assert_eq!(
+            Some("1H"),
+            find_parameter("$NUC1", &parameters).and_then(|v| v.as_str())
+        );
+        assert_eq!(None, find_parameter("$IGNORED", &parameters));
+    }
+
+    #[test]
+    fn fails_parsing_malformed_parameter_file() {
+        assert!(parse_parameter_file("##TITLE= a\n##$TD 1\n##END=").is_err());
+    }
+
+    #[test]
+    fn reports_parameter_file_deviations() {
+        let content = "##TITLE= a\n##$D= (0..3)\n1 2 3\n";
+        let parameters = parse_parameter_file(content).unwrap();
+        let mut collector = WarningCollector::new(&Default::default());
+
+        check_parameter_file("acqus", content, &parameters, &mut collector);
+
+        let messages: Vec<String> = collector
+            .into_warnings()
+            .unwrap()
+            .into_iter()
+            .map(|w| w.message)
+            .collect();
+        assert_eq!(
+            vec![
+                "Missing \"##END=\" in Bruker parameter file: acqus",
+                "Number of values not matching the index range of Bruker parameter $D in: acqus",
+            ],
+            messages
+        );
+    }
+}
@@ -0,0 +1,675 @@
+// Copyright (c) 2025 Robert Schiwon
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use super::bruker_parameters::{
+    BrukerParameter, check_parameter_file, find_parameter, parse_parameter_file,
+};
+use crate::{
+    api::{ParseOptions, ParseWarning, Parser, SeekRead},
+    common::{SfError, SfErrorKind, WarningCollector},
+    directory_source::DirectorySource,
+};
+use std::path::{Path, PathBuf};
+
+pub struct BrukerParser {}
+
+impl<T: SeekRead + 'static> Parser<T> for BrukerParser {
+    type R = BrukerDataSet;
+    type E = SfError;
+
+    /// Parses a ZIP archive holding a Bruker experiment directory.
+    fn parse(name: &str, input: T) -> Result<Self::R, Self::E> {
+        Self::parse_with_options(name, input, &ParseOptions::default())
+    }
+}
+
+impl BrukerParser {
+    /// Parses a ZIP archive holding a Bruker experiment directory with the given options.
+    ///
+    /// In strict mode, malformed parameter files and raw data not holding complete FIDs
+    /// result in an error.
+    pub fn parse_with_options<T: SeekRead + 'static>(
+        name: &str,
+        input: T,
+        options: &ParseOptions,
+    ) -> Result<BrukerDataSet, SfError> {
+        let input: Box<dyn SeekRead> = Box::new(input);
+        let source = DirectorySource::open_zip(input, BrukerDataSet::ACQUISITION_PARAMETERS)?
+            .ok_or(
+                SfError::new(&format!(
+                    "No Bruker experiment (acqus) found in ZIP archive: {}",
+                    name
+                ))
+                .with_kind(SfErrorKind::UnsupportedFormat),
+            )?;
+        let file_name = Path::new(name)
+            .file_name()
+            .and_then(|file_name| file_name.to_str())
+            .unwrap_or(name);
+        BrukerDataSet::new(file_name, source, options)
+    }
+
+    /// Parses a Bruker experiment directory in the file system.
+    ///
+    /// The path may also point to a file inside the experiment directory, e.g., "acqus", "fid",
+    /// or "pdata/1/1r".
+    pub fn parse_directory(path: &str, options: &ParseOptions) -> Result<BrukerDataSet, SfError> {
+        let directory = find_experiment_directory(Path::new(path)).ok_or(
+            SfError::new(&format!("No Bruker experiment directory found: {}", path))
+                .with_kind(SfErrorKind::UnsupportedFormat),
+        )?;
+        let name = directory
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or(path)
+            .to_owned();
+        BrukerDataSet::new(&name, DirectorySource::Directory(directory), options)
+    }
+}
+
+/// Finds the experiment directory, i.e., the directory holding "acqus", for a path pointing
+/// to the directory itself, to a raw data or acquisition parameter file in it, or to a
+/// processed data or processing parameter file in its "pdata" sub directories.
+pub(crate) fn find_experiment_directory(path: &Path) -> Option<PathBuf> {
+    const EXPERIMENT_FILES: [&str; 4] = ["fid", "ser", "acqus", "acqu2s"];
+    const PROCESSED_FILES: [&str; 4] = ["1r", "1i", "procs", "title"];
+
+    let file_name = path.file_name().and_then(|name| name.to_str());
+    let directory = match file_name {
+        _ if path.is_dir() => Some(path),
+        Some(name) if EXPERIMENT_FILES.contains(&name) => path.parent(),
+        Some(name) if PROCESSED_FILES.contains(&name) => path.ancestors().nth(3),
+        _ => None,
+    }?;
+    directory
+        .join(BrukerDataSet::ACQUISITION_PARAMETERS)
+        .is_file()
+        .then(|| directory.to_path_buf())
+}
+
+/// The kind of raw data of an experiment.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RawDataKind {
+    /// A single FID ("fid" file).
+    Fid,
+    /// A series of FIDs ("ser" file), e.g., of a 2D experiment.
+    Ser,
+}
+
+impl RawDataKind {
+    pub fn get_file_name(&self) -> &'static str {
+        match self {
+            RawDataKind::Fid => "fid",
+            RawDataKind::Ser => "ser",
+        }
+    }
+}
+
+/// The binary number format of Bruker data.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BrukerDataType {
+    /// 32 bit integers, DTYPA/DTYPP 0.
+    I32,
+    /// 32 bit floating point numbers, DTYPA/DTYPP 1.
+    F32,
+    /// 64 bit floating point numbers, DTYPA/DTYPP 2.
+    F64,
+}
+
+impl BrukerDataType {
+    fn from_parameter(name: &str, parameters: &[BrukerParameter]) -> Result<Self, SfError> {
+        match find_parameter(name, parameters).and_then(|v| v.as_i64()) {
+            None | Some(0) => Ok(BrukerDataType::I32),
+            Some(1) => Ok(BrukerDataType::F32),
+            Some(2) => Ok(BrukerDataType::F64),
+            Some(other) => Err(SfError::new(&format!(
+                "Unsupported Bruker data type {}: {}",
+                name, other
+            ))),
+        }
+    }
+
+    fn get_size(&self) -> usize {
+        match self {
+            BrukerDataType::I32 | BrukerDataType::F32 => 4,
+            BrukerDataType::F64 => 8,
+        }
+    }
+}
+
+/// The binary format of Bruker data, BYTORDA/BYTORDP 0 indicating little endian and 1 big
+/// endian byte order.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BinaryFormat {
+    pub data_type: BrukerDataType,
+    pub big_endian: bool,
+}
+
+impl BinaryFormat {
+    fn from_parameters(
+        data_type_name: &str,
+        byte_order_name: &str,
+        parameters: &[BrukerParameter],
+    ) -> Result<Self, SfError> {
+        let data_type = BrukerDataType::from_parameter(data_type_name, parameters)?;
+        let big_endian =
+            find_parameter(byte_order_name, parameters).and_then(|v| v.as_i64()) == Some(1);
+        Ok(Self {
+            data_type,
+            big_endian,
+        })
+    }
+
+    /// Decodes binary values, ignoring trailing bytes not forming a complete value.
+    pub fn decode(&self, bytes: &[u8]) -> Vec<f64> {
+        let size = self.data_type.get_size();
+        bytes
+            .chunks_exact(size)
+            .map(|chunk| match (self.data_type, self.big_endian) {
+                (BrukerDataType::I32, false) => {
+                    i32::from_le_bytes(chunk.try_into().unwrap()) as f64
+                }
+                (BrukerDataType::I32, true) => i32::from_be_bytes(chunk.try_into().unwrap()) as f64,
+                (BrukerDataType::F32, false) => {
+                    f32::from_le_bytes(chunk.try_into().unwrap()) as f64
+                }
+                (BrukerDataType::F32, true) => f32::from_be_bytes(chunk.try_into().unwrap()) as f64,
+                (BrukerDataType::F64, false) => f64::from_le_bytes(chunk.try_into().unwrap()),
+                (BrukerDataType::F64, true) => f64::from_be_bytes(chunk.try_into().unwrap()),
+            })
+            .collect()
+    }
+}
+
+/// The positions of the FIDs in a raw data file.
+struct FidLayout {
+    format: BinaryFormat,
+    /// The number of bytes of an FID.
+    fid_size: u64,
+    /// The number of bytes from the start of one FID to the next.
+    block_size: u64,
+    /// The number of bytes of the file holding complete values.
+    file_len: u64,
+    num_fids: usize,
+}
+
+/// A processed data directory, e.g., "pdata/1".
+pub struct ProcessedData {
+    /// The name of the directory, e.g., "1".
+    pub name: String,
+    /// The processing parameters from "procs".
+    pub parameters: Vec<BrukerParameter>,
+    /// The content of the "title" file if present.
+    pub title: Option<String>,
+}
+
+impl ProcessedData {
+    const PARAMETERS: &'static str = "procs";
+}
+
+/// A processed 1D spectrum.
+pub struct ProcessedSpectrum {
+    /// The real part from "1r".
+    pub real: Vec<f64>,
+    /// The imaginary part from "1i" if present.
+    pub imaginary: Option<Vec<f64>>,
+}
+
+/// A Bruker TopSpin experiment, i.e., an experiment directory such as "1" holding "acqus",
+/// "fid" or "ser", and processed data in "pdata".
+pub struct BrukerDataSet {
+    /// The name of the data set, e.g., the experiment directory or ZIP file name.
+    pub name: String,
+    /// The acquisition parameters from "acqus".
+    pub acquisition_parameters: Vec<BrukerParameter>,
+    /// The acquisition parameters of the second dimension from "acqu2s" if present.
+    pub acquisition_2_parameters: Option<Vec<BrukerParameter>>,
+    /// The kind of raw data if present.
+    pub raw_data: Option<RawDataKind>,
+    /// The processed data directories.
+    pub processed_data: Vec<ProcessedData>,
+    /// The deviations from the format accepted while parsing.
+    pub warnings: Vec<ParseWarning>,
+
+    source: DirectorySource,
+}
+
+impl BrukerDataSet {
+    pub(crate) const ACQUISITION_PARAMETERS: &'static str = "acqus";
+    const ACQUISITION_2_PARAMETERS: &'static str = "acqu2s";
+    /// Each FID in a ser file starts at a multiple of this block size in bytes.
+    const SER_BLOCK_SIZE: u64 = 1024;
+
+    fn new(name: &str, source: DirectorySource, options: &ParseOptions) -> Result<Self, SfError> {
+        let mut collector = WarningCollector::new(options);
+        let acquisition_parameters =
+            Self::read_parameters(&source, Self::ACQUISITION_PARAMETERS, &mut collector)?.ok_or(
+                SfError::new(&format!(
+                    "Missing \"{}\" in Bruker data set: {}",
+                    Self::ACQUISITION_PARAMETERS,
+                    name
+                ))
+                .with_kind(SfErrorKind::CorruptData),
+            )?;
+        let acquisition_2_parameters =
+            Self::read_parameters(&source, Self::ACQUISITION_2_PARAMETERS, &mut collector)?;
+        let raw_data = if source.is_file(RawDataKind::Fid.get_file_name()) {
+            Some(RawDataKind::Fid)
+        } else if source.is_file(RawDataKind::Ser.get_file_name()) {
+            Some(RawDataKind::Ser)
+        } else {
+            None
+        };
+
+        let mut processed_data = vec![];
+        for processed_name in source.list_sub_directories("pdata", ProcessedData::PARAMETERS)? {
+            let directory = format!("pdata/{}/", processed_name);
+            let parameters = Self::read_parameters(
+                &source,
+                &format!("{}{}", directory, ProcessedData::PARAMETERS),
+                &mut collector,
+            )?
+            .unwrap_or_default();
+            let title = source
+                .read_file(&format!("{}title", directory))?
+                .map(|bytes| String::from_utf8_lossy(&bytes).trim().to_owned())
+                .filter(|title| !title.is_empty());
+            processed_data.push(ProcessedData {
+                name: processed_name,
+                parameters,
+                title,
+            });
+        }
+
+        let mut data_set = Self {
+            name: name.to_owned(),
+            acquisition_parameters,
+            acquisition_2_parameters,
+            raw_data,
+            processed_data,
+            warnings: vec![],
+            source,
+        };
+        data_set.check_raw_data(&mut collector);
+        data_set.warnings = collector.into_warnings()?;
+        Ok(data_set)
+    }
+
+    fn read_parameters(
+        source: &DirectorySource,
+        relative_path: &str,
+        collector: &mut WarningCollector,
+    ) -> Result<Option<Vec<BrukerParameter>>, SfError> {
+        match source.read_file(relative_path)? {
+            None => Ok(None),
+            Some(bytes) => {
+                // parameter files are ISO 8859-1 encoded
+                let content: String = bytes.iter().map(|&b| b as char).collect();
+                let parameters = parse_parameter_file(&content)
+                    .map_err(|e| e.or_kind(SfErrorKind::CorruptData))?;
+                check_parameter_file(relative_path, &content, &parameters, collector);
+                Ok(Some(parameters))
+            }
+        }
+    }
+
+    /// Reports a missing or odd TD and raw data not holding complete FIDs.
+    ///
+    /// Raw data in an unsupported format is reported when reading it.
+    fn check_raw_data(&self, collector: &mut WarningCollector) {
+        let Some(raw_data) = self.raw_data else {
+            return;
+        };
+        match find_parameter("$TD", &self.acquisition_parameters).and_then(|v| v.as_i64()) {
+            Some(td) if td > 0 && td % 2 == 0 => {}
+            Some(td) => collector.report(
+                &format!("Illegal number of values per Bruker FID \"$TD\": {}", td),
+                None,
+            ),
+            None => collector.report("Missing Bruker acquisition parameter \"$TD\"", None),
+        }
+        let Ok(layout) = self.get_fid_layout(raw_data) else {
+            return;
+        };
+        let file_name = raw_data.get_file_name();
+        match raw_data {
+            RawDataKind::Fid if layout.file_len < layout.fid_size => collector.report(
+                &format!(
+                    "Bruker {} file too short for one FID of {} bytes: {} bytes",
+                    file_name, layout.fid_size, layout.file_len
+                ),
+                None,
+            ),
+            RawDataKind::Ser
+                if layout.block_size > 0
+                    && layout.file_len % layout.block_size != 0
+                    && layout.file_len % layout.block_size < layout.fid_size =>
+            {
+                collector.report(
+                    &format!(
+                        "Bruker {} file with an incomplete FID after {} FIDs of {} bytes",
+                        file_name, layout.num_fids, layout.fid_size
+                    ),
+                    None,
+                )
+            }
+            _ => {}
+        }
+    }
+
+    /// The number of FIDs in the raw data, i.e., one for a fid file and the number of complete
+    /// FIDs for a ser file.
+    pub fn get_num_fids(&self) -> Result<usize, SfError> {
+        match self.raw_data {
+            None => Ok(0),
+            Some(raw_data) => Ok(self.get_fid_layout(raw_data)?.num_fids),
+        }
+    }
+
+    /// Reads an FID of interleaved real and imaginary values by its index, scaled by 2^NC.
+    ///
+    /// Only the bytes of the FID are read from the raw data file.
+    pub fn read_fid(&self, index: usize) -> Result<Vec<f64>, SfError> {
+        let illegal_index_error = || SfError::new(&format!("Illegal Bruker FID index: {}", index));
+        let raw_data = self.raw_data.ok_or_else(illegal_index_error)?;
+        let layout = self.get_fid_layout(raw_data)?;
+        if index >= layout.num_fids {
+            return Err(illegal_index_error());
+        }
+        let offset = index as u64 * layout.block_size;
+        let end = (offset + layout.fid_size).min(layout.file_len);
+        let bytes = self
+            .source
+            .read_file_range(raw_data.get_file_name(), offset..end)?
+            .ok_or(
+                SfError::new(&format!(
+                    "Missing \"{}\" in Bruker data set: {}",
+                    raw_data.get_file_name(),
+                    self.name
+                ))
+                .with_kind(SfErrorKind::CorruptData),
+            )?;
+        let scale = Self::get_scale(layout.format, "$NC", &self.acquisition_parameters);
+        Ok(layout
+            .format
+            .decode(&bytes)
+            .into_iter()
+            .map(|v| v * scale)
+            .collect())
+    }
+
+    /// Determines the positions of the FIDs in the raw data file from its length and TD.
+    ///
+    /// A ser file holds one FID per block of TD values padded to a multiple of 1024 bytes.
+    fn get_fid_layout(&self, raw_data: RawDataKind) -> Result<FidLayout, SfError> {
+        let parameters = &self.acquisition_parameters;
+        let format = BinaryFormat::from_parameters("$DTYPA", "$BYTORDA", parameters)?;
+        let value_size = format.data_type.get_size() as u64;
+        let file_len = self
+            .source
+            .get_file_len(raw_data.get_file_name())?
+            .unwrap_or_default()
+            / value_size
+            * value_size;
+        let fid_size = find_parameter("$TD", parameters)
+            .and_then(|v| v.as_i64())
+            .filter(|td| *td > 0)
+            .map(|td| (td as u64).saturating_mul(value_size))
+            .unwrap_or(file_len);
+
+        Ok(match raw_data {
+            RawDataKind::Fid => FidLayout {
+                format,
+                fid_size,
+                block_size: 0,
+                file_len,
+                num_fids: 1,
+            },
+            RawDataKind::Ser => {
+                let block_size = fid_size
+                    .div_ceil(Self::SER_BLOCK_SIZE)
+                    .saturating_mul(Self::SER_BLOCK_SIZE);
+                let num_fids = match block_size {
+                    0 => 0,
+                    _ => file_len / block_size + u64::from(file_len % block_size >= fid_size),
+                };
+                FidLayout {
+                    format,
+                    fid_size,
+                    block_size,
+                    file_len,
+                    num_fids: num_fids as usize,
+                }
+            }
+        })
+    }
+
+    /// Reads the real ("1r") and imaginary ("1i") part of a processed 1D spectrum, scaled by
+    /// 2^NC_proc.
+    ///
+    /// Returns None for the imaginary part if missing.
+    pub fn read_processed_spectrum(
+        &self,
+        processed: &ProcessedData,
+    ) -> Result<Option<ProcessedSpectrum>, SfError> {
+        let parameters = &processed.parameters;
+        let format = BinaryFormat::from_parameters("$DTYPP", "$BYTORDP", parameters)?;
+        let scale = Self::get_scale(format, "$NC_proc", parameters);
+        let read_part = |file_name: &str| -> Result<Option<Vec<f64>>, SfError> {
+            let path = format!("pdata/{}/{}", processed.name, file_name);
+            Ok(self.source.read_file(&path)?.map(|bytes| {
+                format
+                    .decode(&bytes)
+                    .into_iter()
+                    .map(|v| v * scale)
+                    .collect()
+            }))
+        };
+        let Some(real) = read_part("1r")? else {
+            return Ok(None);
+        };
+        let imaginary = read_part("1i")?.filter(|imaginary| imaginary.len() == real.len());
+        Ok(Some(ProcessedSpectrum { real, imaginary }))
+    }
+
+    /// The scaling factor 2^NC of integer data.
+    fn get_scale(format: BinaryFormat, name: &str, parameters: &[BrukerParameter]) -> f64 {
+        match format.data_type {
+            BrukerDataType::I32 => find_parameter(name, parameters)
+                .and_then(|v| v.as_f64())
+                .map(|nc| 2f64.powf(nc))
+                .unwrap_or(1.0),
+            _ => 1.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::directory_source::create_zip;
+    use std::{fs, io::Cursor};
+
+    #[test]
+    fn decodes_binary_formats() {
+        let format = BinaryFormat {
+            data_type: BrukerDataType::I32,
+            big_endian: true,
+        };
+        assert_eq!(
+            vec![1.0, -2.0],
+            format.decode(&[0, 0, 0, 1, 0xFF, 0xFF, 0xFF, 0xFE, 0])
+        );
+
+        let format = BinaryFormat {
+            data_type: BrukerDataType::F64,
+            big_endian: false,
+        };
+        assert_eq!(vec![1.5], format.decode(&1.5f64.to_le_bytes()));
+    }
+
+    #[test]
+    fn reads_ser_fids_padded_to_blocks() {
+        let acqus = b"##$TD= 4\n##$DTYPA= 2\n##$BYTORDA= 1\n##END=\n";
+        // 4 values of 8 bytes padded to 1024 bytes per FID
+        let mut ser = vec![];
+        for fid in 0..2 {
+            let mut block: Vec<u8> = (0..4)
+                .flat_map(|i| ((fid * 10 + i) as f64).to_be_bytes())
+                .collect();
+            block.resize(BrukerDataSet::SER_BLOCK_SIZE as usize, 0);
+            ser.extend(block);
+        }
+        // incomplete trailing FID
+        ser.extend([0u8; 8]);
+        let zip = create_zip(&[
+            ("data/2/acqus", acqus),
+            ("data/2/acqu2s", b"##$TD= 2\n##END=\n"),
+            ("data/2/ser", &ser),
+        ]);
+
+        let data_set = BrukerParser::parse("data.zip", zip).unwrap();
+
+        assert_eq!("data.zip", data_set.name);
+        assert_eq!(Some(RawDataKind::Ser), data_set.raw_data);
+        assert!(data_set.acquisition_2_parameters.is_some());
+        assert_eq!(2, data_set.get_num_fids().unwrap());
+        assert_eq!(vec![0.0, 1.0, 2.0, 3.0], data_set.read_fid(0).unwrap());
+        assert_eq!(vec![10.0, 11.0, 12.0, 13.0], data_set.read_fid(1).unwrap());
+        assert!(data_set.read_fid(2).is_err());
+    }
+
+    #[test]
+    fn finds_shortest_experiment_prefix_and_processed_data_in_order() {
+        let zip = create_zip(&[
+            ("sample/1/pdata/10/procs", b"##END=\n"),
+            ("sample/1/pdata/2/procs", b"##END=\n"),
+            ("sample/1/pdata/2/title", b" Title \n"),
+            ("sample/1/pdata/3/1r", b""),
+            ("sample/1/acqus", b"##$TD= 2\n##END=\n"),
+            ("sample/1/other/acqus", b"##END=\n"),
+        ]);
+
+        let data_set = BrukerParser::parse("sample.zip", zip).unwrap();
+
+        assert_eq!(None, data_set.raw_data);
+        let names: Vec<&str> = data_set
+            .processed_data
+            .iter()
+            .map(|p| p.name.as_str())
+            .collect();
+        assert_eq!(vec!["2", "10"], names);
+        assert_eq!(Some("Title"), data_set.processed_data[0].title.as_deref());
+        assert_eq!(0, data_set.get_num_fids().unwrap());
+    }
+
+    #[test]
+    fn reports_incomplete_raw_data_in_lenient_mode_only() {
+        let acqus = b"##$TD= 4\n##$DTYPA= 2\n##END=\n".as_slice();
+        let fid = [0u8; 24];
+        let ser = [0u8; 1024 + 24];
+
+        let data_set =
+            BrukerParser::parse("fid.zip", create_zip(&[("acqus", acqus), ("fid", &fid)])).unwrap();
+        assert_eq!(
+            vec!["Bruker fid file too short for one FID of 32 bytes: 24 bytes"],
+            data_set
+                .warnings
+                .iter()
+                .map(|w| w.message.as_str())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(3, data_set.read_fid(0).unwrap().len());
+
+        let data_set =
+            BrukerParser::parse("ser.zip", create_zip(&[("acqus", acqus), ("ser", &ser)])).unwrap();
+        assert_eq!(1, data_set.warnings.len());
+        assert_eq!(1, data_set.get_num_fids().unwrap());
+
+        let error = BrukerParser::parse_with_options(
+            "ser.zip",
+            create_zip(&[("acqus", acqus), ("ser", &ser)]),
+            &ParseOptions::strict(),
+        )
+        .err()
+        .unwrap();
+        assert_eq!(SfErrorKind::CorruptData, error.kind());
+        assert!(error.to_string().contains("incomplete FID after 1 FIDs"));
+    }
+
+    #[test]
+    fn fails_parsing_malformed_parameter_file_in_strict_mode() {
+        let zip = || create_zip(&[("acqus", b"##$TD= 2\n##$D= (0..1)\n1\n".as_slice())]);
+
+        assert!(BrukerParser::parse("lenient.zip", zip()).is_ok());
+        let error = BrukerParser::parse_with_options("strict.zip", zip(), &ParseOptions::strict())
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains("Missing \"##END=\""));
+        assert!(error.to_string().contains("parameter $D"));
+    }
+
+    #[test]
+    fn fails_parsing_zip_without_experiment() {
+        let zip = create_zip(&[("readme.txt", b"no experiment")]);
+        let error = BrukerParser::parse("other.zip", zip).err().unwrap();
+        assert_eq!(SfErrorKind::UnsupportedFormat, error.kind());
+
+        let error = BrukerParser::parse("invalid.zip", Cursor::new(b"invalid"))
+            .err()
+            .unwrap();
+        assert_eq!(SfErrorKind::CorruptData, error.kind());
+    }
+
+    #[test]
+    fn parses_experiment_directory() {
+        let directory =
+            std::env::temp_dir().join(format!("sciformats_bruker_test_{}", std::process::id()));
+        let experiment = directory.join("1");
+        fs::create_dir_all(experiment.join("pdata/1")).unwrap();
+        fs::write(
+            experiment.join("acqus"),
+            "##$TD= 2\n##$DTYPA= 0\n##$NC= 2\n##END=\n",
+        )
+        .unwrap();
+        let fid: Vec<u8> = [3i32, -1].iter().flat_map(|v| v.to_le_bytes()).collect();
+        fs::write(experiment.join("fid"), fid).unwrap();
+        fs::write(experiment.join("pdata/1/procs"), "##$SI= 1\n##END=\n").unwrap();
+
+        let from_directory =
+            BrukerParser::parse_directory(experiment.to_str().unwrap(), &ParseOptions::default())
+                .unwrap();
+        let from_fid = BrukerParser::parse_directory(
+            experiment.join("fid").to_str().unwrap(),
+            &ParseOptions::default(),
+        )
+        .unwrap();
+        let from_procs = find_experiment_directory(&experiment.join("pdata/1/procs"));
+        let from_other = find_experiment_directory(&experiment.join("pdata"));
+        let fid = from_directory.read_fid(0).unwrap();
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!("1", from_directory.name);
+        assert_eq!(vec![12.0, -4.0], fid);
+        assert_eq!(1, from_directory.processed_data.len());
+        assert_eq!("1", from_fid.name);
+        assert_eq!(Some(experiment), from_procs);
+        assert_eq!(None, from_other);
+    }
+}
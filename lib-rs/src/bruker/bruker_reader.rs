@@ -0,0 +1,294 @@
+// Copyright (c) 2025 Robert Schiwon
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use super::{
+    bruker_parameters::{find_parameter, map_parameters},
+    bruker_parser::{BrukerDataSet, ProcessedData, ProcessedSpectrum, RawDataKind},
+};
+use crate::{
+    api::{Axis, ComplexData, Node, Parameter, ParseWarning, PointXy, Reader},
+    common::{SfError, SfErrorKind},
+    nmr_processing::map_interleaved_fid,
+    utils::convert_path_to_node_indices,
+};
+
+/// A reader for Bruker TopSpin experiments.
+///
+/// The root node holds the acquisition parameters and has the raw data ("fid" or "ser")
+/// followed by the processed data ("pdata/1", ...) as child nodes. A "ser" node has one
+/// child node per FID.
+pub struct BrukerReader {
+    path: String,
+    data_set: BrukerDataSet,
+}
+
+impl Reader for BrukerReader {
+    fn read(&self, path: &str) -> Result<Node, SfError> {
+        self.read_node(path)
+            .map_err(|e| e.or_kind(SfErrorKind::CorruptData))
+    }
+
+    fn get_warnings(&self) -> &[ParseWarning] {
+        &self.data_set.warnings
+    }
+}
+
+impl BrukerReader {
+    pub fn new(path: &str, data_set: BrukerDataSet) -> Self {
+        Self {
+            path: path.to_owned(),
+            data_set,
+        }
+    }
+
+    fn read_node(&self, path: &str) -> Result<Node, SfError> {
+        let node_indices = convert_path_to_node_indices(path)?;
+        let raw_data_len = usize::from(self.data_set.raw_data.is_some());
+        match (&node_indices[..], self.data_set.raw_data) {
+            ([], _) => Ok(self.map_root()),
+            ([0], Some(RawDataKind::Fid)) => self.map_fid("fid", &self.data_set.read_fid(0)?),
+            ([0], Some(RawDataKind::Ser)) => Ok(self.map_ser(self.data_set.get_num_fids()?)),
+            ([0, fid_index], Some(RawDataKind::Ser)) => {
+                if *fid_index >= self.data_set.get_num_fids()? {
+                    return Err(self.illegal_path_error(path));
+                }
+                let fid = self.data_set.read_fid(*fid_index)?;
+                self.map_fid(&format!("FID {}", fid_index + 1), &fid)
+            }
+            ([index], _) => {
+                let processed = self
+                    .data_set
+                    .processed_data
+                    .get(index - raw_data_len)
+                    .ok_or(self.illegal_path_error(path))?;
+                self.map_processed_data(processed)
+            }
+            _ => Err(self.illegal_path_error(path)),
+        }
+    }
+
+    fn illegal_path_error(&self, path: &str) -> SfError {
+        SfError::new(&format!("Illegal node path: {}", path))
+            .with_kind(SfErrorKind::IllegalNodePath)
+    }
+
+    fn map_root(&self) -> Node {
+        let mut child_node_names = vec![];
+        if let Some(raw_data) = self.data_set.raw_data {
+            child_node_names.push(raw_data.get_file_name().to_owned());
+        }
+        for processed in &self.data_set.processed_data {
+            child_node_names.push(Self::get_processed_data_name(processed));
+        }
+
+        let (parameters, table) = map_parameters(&self.data_set.acquisition_parameters);
+
+        Node {
+            name: self.data_set.name.clone(),
+            parameters,
+            data: vec![],
+            metadata: vec![],
+            table,
+            nd_array: None,
+            complex_data: None,
+            links: vec![],
+            child_node_names,
+        }
+    }
+
+    fn map_ser(&self, num_fids: usize) -> Node {
+        let (parameters, table) = self
+            .data_set
+            .acquisition_2_parameters
+            .as_deref()
+            .map(map_parameters)
+            .unwrap_or_default();
+
+        Node {
+            name: RawDataKind::Ser.get_file_name().to_owned(),
+            parameters,
+            data: vec![],
+            metadata: vec![],
+            table,
+            nd_array: None,
+            complex_data: None,
+            links: vec![],
+            child_node_names: (1..=num_fids).map(|i| format!("FID {}", i)).collect(),
+        }
+    }
+
+    /// Maps an FID of interleaved real and imaginary values to complex data with a time axis.
+    ///
+    /// The dwell time is derived from the spectral width $SW_h.
+    fn map_fid(&self, name: &str, fid: &[f64]) -> Result<Node, SfError> {
+        let parameters = &self.data_set.acquisition_parameters;
+        let dwell_time = find_parameter("$SW_h", parameters)
+            .and_then(|v| v.as_f64())
+            .filter(|sw_h| *sw_h > 0.0)
+            .map(|sw_h| 1.0 / sw_h)
+            .ok_or(SfError::new(&format!(
+                "Missing or illegal spectral width \"$SW_h\" for: {}",
+                self.path
+            )))?;
+        let mut node = map_interleaved_fid(name, fid, dwell_time)?;
+        if let Some(sfo1) = find_parameter("$SFO1", parameters).and_then(|v| v.as_f64()) {
+            node.parameters
+                .push(Parameter::from_str_f64(".OBSERVE FREQUENCY", sfo1));
+        }
+        Ok(node)
+    }
+
+    /// Maps a processed 1D spectrum to data with a chemical shift axis.
+    ///
+    /// The shift of point i is OFFSET - i * SW_p / SF / SI.
+    fn map_processed_data(&self, processed: &ProcessedData) -> Result<Node, SfError> {
+        let (mut parameters, table) = map_parameters(&processed.parameters);
+        if let Some(title) = &processed.title {
+            parameters.insert(0, Parameter::from_str_str("title", title));
+        }
+        let mut node = Node {
+            name: Self::get_processed_data_name(processed),
+            parameters,
+            data: vec![],
+            metadata: vec![],
+            table,
+            nd_array: None,
+            complex_data: None,
+            links: vec![],
+            child_node_names: vec![],
+        };
+
+        let Some(ProcessedSpectrum { real, imaginary }) =
+            self.data_set.read_processed_spectrum(processed)?
+        else {
+            return Ok(node);
+        };
+        let get_f64 = |name: &str| {
+            find_parameter(name, &processed.parameters)
+                .and_then(|v| v.as_f64())
+                .ok_or(SfError::new(&format!(
+                    "Missing processing parameter \"{}\" for: {}",
+                    name, node.name
+                )))
+        };
+        let offset = get_f64("$OFFSET")?;
+        let sw_p = get_f64("$SW_p")?;
+        let sf = get_f64("$SF")?;
+        if sf == 0.0 {
+            return Err(SfError::new(&format!(
+                "Illegal spectrometer frequency \"$SF\" for: {}",
+                node.name
+            )));
+        }
+        let increment = sw_p / sf / real.len().max(1) as f64;
+        let shifts: Vec<f64> = (0..real.len())
+            .map(|i| offset - i as f64 * increment)
+            .collect();
+
+        node.data = shifts
+            .iter()
+            .zip(&real)
+            .map(|(x, y)| PointXy::new(*x, *y))
+            .collect();
+        node.metadata = vec![
+            ("x.unit".to_owned(), "PPM".to_owned()),
+            ("x.label".to_owned(), "Chemical Shift".to_owned()),
+            ("x.reverse".to_owned(), "true".to_owned()),
+        ];
+        if let Some(imaginary) = imaginary {
+            node.complex_data = Some(ComplexData::new(
+                "SPECTRUM",
+                None,
+                Axis::new("CHEMICAL SHIFT", Some("PPM".to_owned()), shifts),
+                real,
+                imaginary,
+            )?);
+        }
+        Ok(node)
+    }
+
+    fn get_processed_data_name(processed: &ProcessedData) -> String {
+        format!("pdata/{}", processed.name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{api::Parser, bruker::bruker_parser::BrukerParser, directory_source::create_zip};
+
+    fn create_reader(entries: &[(&str, &[u8])]) -> BrukerReader {
+        let cursor = create_zip(entries);
+        let data_set = BrukerParser::parse("test.zip", cursor).unwrap();
+        BrukerReader::new("test.zip", data_set)
+    }
+
+    #[test]
+    fn maps_ser_fids_to_child_nodes() {
+        let ser: Vec<u8> = (0..512).flat_map(|i: i32| i.to_le_bytes()).collect();
+        let reader = create_reader(&[
+            ("1/acqus", b"##$TD= 4\n##$SW_h= 10\n##END=\n"),
+            ("1/acqu2s", b"##$TD= 2\n##END=\n"),
+            ("1/ser", &ser),
+        ]);
+
+        let ser_node = reader.read("/0").unwrap();
+        assert_eq!("ser", ser_node.name);
+        assert_eq!(vec!["FID 1", "FID 2"], ser_node.child_node_names);
+        assert_eq!(vec![Parameter::from_str_i64("$TD", 2)], ser_node.parameters);
+
+        let fid_2 = reader.read("/0/1").unwrap();
+        assert_eq!("FID 2", fid_2.name);
+        assert_eq!(
+            vec![PointXy::new(0.0, 256.0), PointXy::new(0.1, 258.0)],
+            fid_2.data
+        );
+        assert_eq!(vec![257.0, 259.0], fid_2.complex_data.unwrap().imaginary);
+        assert_eq!(
+            SfErrorKind::IllegalNodePath,
+            reader.read("/0/2").unwrap_err().kind()
+        );
+    }
+
+    #[test]
+    fn maps_processed_data_without_spectrum() {
+        let reader = create_reader(&[
+            ("1/acqus", b"##$TD= 4\n##END=\n"),
+            ("1/pdata/1/procs", b"##$SI= 4\n##END=\n"),
+        ]);
+
+        let root = reader.read("/").unwrap();
+        assert_eq!(vec!["pdata/1"], root.child_node_names);
+        let processed = reader.read("/0").unwrap();
+        assert_eq!(
+            vec![Parameter::from_str_i64("$SI", 4)],
+            processed.parameters
+        );
+        assert!(processed.data.is_empty());
+    }
+
+    #[test]
+    fn fails_reading_fid_without_spectral_width() {
+        let reader = create_reader(&[("1/acqus", b"##$TD= 2\n##END=\n"), ("1/fid", &[0; 8])]);
+
+        let error = reader.read("/0").unwrap_err();
+        assert_eq!(SfErrorKind::CorruptData, error.kind());
+        assert!(error.to_string().contains("$SW_h"));
+    }
+}
@@ -0,0 +1,178 @@
+// Copyright (c) 2025 Robert Schiwon
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use super::{
+    bruker_parser::{BrukerDataSet, BrukerParser, find_experiment_directory},
+    bruker_reader::BrukerReader,
+};
+use crate::{
+    api::{Confidence, ParseOptions, Reader, Scanner, SeekRead},
+    common::SfError,
+    directory_source::DirectoryScanner,
+};
+
+/// A scanner for Bruker TopSpin experiments.
+///
+/// Experiments are recognized as ZIP archives holding an experiment directory. With the
+/// `read_directories` parse option set, they are also recognized as paths in the file system
+/// pointing to an experiment directory or a file in it. For the latter, the input is ignored
+/// and the files are read from the experiment directory.
+#[derive(Default)]
+pub struct BrukerScanner {
+    options: ParseOptions,
+}
+
+impl BrukerScanner {
+    const DIRECTORY_SCANNER: DirectoryScanner = DirectoryScanner {
+        marker_file: BrukerDataSet::ACQUISITION_PARAMETERS,
+        find_directory: find_experiment_directory,
+        read_directory: |path, options| {
+            let data_set = BrukerParser::parse_directory(path, options)?;
+            Ok(Box::new(BrukerReader::new(path, data_set)))
+        },
+        read_zip: |path, input, options| {
+            let data_set = BrukerParser::parse_with_options(path, input, options)?;
+            Ok(Box::new(BrukerReader::new(path, data_set)))
+        },
+    };
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<T: SeekRead + 'static> Scanner<T> for BrukerScanner {
+    fn is_recognized(&self, path: &str, input: &mut T) -> bool {
+        Self::DIRECTORY_SCANNER.is_recognized(path, input, &self.options)
+    }
+
+    fn get_confidence(&self, path: &str, input: &mut T) -> Option<Confidence> {
+        Self::DIRECTORY_SCANNER.get_confidence(path, input, &self.options)
+    }
+
+    fn get_name(&self) -> &str {
+        "Bruker"
+    }
+
+    fn set_parse_options(&mut self, options: &ParseOptions) {
+        self.options = options.clone();
+    }
+
+    fn get_reader(&self, path: &str, input: T) -> Result<Box<dyn Reader>, SfError> {
+        Self::DIRECTORY_SCANNER.get_reader(path, input, &self.options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::directory_source::create_zip;
+    use std::io::Cursor;
+
+    #[test]
+    fn provides_confidence() {
+        let scanner = BrukerScanner::new();
+        let experiment = [("exp/1/acqus", b"##$TD= 2\n##END=\n".as_slice())];
+        let other = [("other.txt", b"other".as_slice())];
+
+        let mut input = create_zip(&experiment);
+        let confidence = scanner.get_confidence("valid.zip", &mut input);
+        assert_eq!(Some(Confidence::High), confidence);
+
+        let mut input = create_zip(&other);
+        let confidence = scanner.get_confidence("invalid.zip", &mut input);
+        assert_eq!(Some(Confidence::Low), confidence);
+
+        let mut input = create_zip(&experiment);
+        let confidence = scanner.get_confidence("valid.bin", &mut input);
+        assert_eq!(Some(Confidence::Medium), confidence);
+
+        let mut input = Cursor::new(b"##TITLE= not a ZIP archive".to_vec());
+        let confidence = scanner.get_confidence("invalid.bin", &mut input);
+        assert_eq!(None, confidence);
+    }
+
+    #[test]
+    fn provides_reader_for_valid_zip() {
+        let scanner = BrukerScanner::new();
+        let mut input = create_zip(&[("1/acqus", b"##$TD= 2\n##END=\n")]);
+
+        assert!(scanner.is_recognized("valid.zip", &mut input));
+        assert_eq!(0, input.position());
+        assert!(scanner.get_reader("valid.zip", input).is_ok());
+    }
+
+    #[test]
+    fn passes_parse_options_to_parser() {
+        // missing "##END="
+        let zip = || create_zip(&[("1/acqus", b"##$TD= 2\n".as_slice())]);
+
+        let reader = BrukerScanner::new()
+            .get_reader("lenient.zip", zip())
+            .unwrap();
+        assert_eq!(1, reader.get_warnings().len());
+        let scanner = BrukerScanner {
+            options: ParseOptions::strict(),
+        };
+        assert!(scanner.get_reader("strict.zip", zip()).is_err());
+    }
+
+    #[test]
+    fn rejects_path_outside_experiment_directory() {
+        let scanner = BrukerScanner::new();
+        let mut input = Cursor::new(b"##TITLE= test".to_vec());
+
+        assert!(!scanner.is_recognized("/nonexistent/1/acqus", &mut input));
+        assert!(!scanner.is_recognized("/nonexistent/1/pdata/1/1r", &mut input));
+    }
+
+    #[test]
+    fn reads_experiment_directory_only_if_enabled() {
+        let directory =
+            std::env::temp_dir().join(format!("sciformats_bruker_scanner_{}", std::process::id()));
+        let experiment = directory.join("1");
+        std::fs::create_dir_all(&experiment).unwrap();
+        std::fs::write(experiment.join("acqus"), "##$TD= 2\n##END=\n").unwrap();
+        let path = experiment.join("acqus");
+        let path = path.to_str().unwrap();
+        let mut input = Cursor::new(b"##TITLE= test".to_vec());
+
+        let scanner = BrukerScanner::new();
+        let recognized_by_default = scanner.is_recognized(path, &mut input);
+        let confidence_by_default = scanner.get_confidence(path, &mut input);
+        let reader_by_default = scanner.get_reader(path, input.clone());
+        let scanner = BrukerScanner {
+            options: ParseOptions {
+                read_directories: true,
+                ..Default::default()
+            },
+        };
+        let recognized = scanner.is_recognized(path, &mut input);
+        let confidence = scanner.get_confidence(path, &mut input);
+        let reader = scanner.get_reader(path, input);
+        std::fs::remove_dir_all(&directory).unwrap();
+
+        assert!(!recognized_by_default);
+        assert_eq!(None, confidence_by_default);
+        assert!(reader_by_default.is_err());
+        assert!(recognized);
+        assert_eq!(Some(Confidence::High), confidence);
+        assert!(reader.is_ok());
+    }
+}
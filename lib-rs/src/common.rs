@@ -18,45 +18,162 @@
 // CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
 use chrono::ParseError;
+use strum::Display;
 
 use crate::andi::andi_scanner::AndiScanner;
-use crate::api::{Reader, Scanner};
+use crate::api::{
+    Confidence, Node, ParseMode, ParseOptions, ParseWarning, Reader, Scanner, SeekRead,
+};
+use crate::bruker::bruker_scanner::BrukerScanner;
 use crate::gaml::gaml_scanner::GamlScanner;
 use crate::jdx::jdx_scanner::JdxScanner;
+use crate::jeol::jeol_scanner::JeolScanner;
 use crate::json::json_scanner::JsonScanner;
+use crate::utils::{convert_path_to_node_indices, lock_input};
+use crate::varian::varian_scanner::VarianScanner;
 use std::fmt;
 use std::io::{BufReader, ErrorKind, SeekFrom};
 use std::{
     error::Error,
     io::{Read, Seek},
+    sync::{Arc, Mutex},
 };
 
 /// A generic error.
 #[derive(Debug)]
 pub struct SfError {
     message: String,
-    source: Option<Box<dyn Error>>,
+    kind: SfErrorKind,
+    location: Option<SfErrorLocation>,
+    source: Option<Box<dyn Error + Send + Sync>>,
+}
+
+/// The kind of an error.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Display)]
+pub enum SfErrorKind {
+    /// The data set is not in a supported format.
+    UnsupportedFormat,
+    /// The data set is corrupt or violates its format specification.
+    CorruptData,
+    /// Reading or writing data failed.
+    Io,
+    /// The node path does not exist in the data set.
+    IllegalNodePath,
+    /// The data range exceeds the available data points.
+    IllegalDataRange,
+    /// Any other error.
+    #[default]
+    Other,
+}
+
+/// The location in a data set an error relates to.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SfErrorLocation {
+    /// The position of the input when the error occurred.
+    ByteOffset(u64),
+    /// The position of the input and the line (starting at 1) last read when the error occurred.
+    Line { line: u64, byte_offset: u64 },
+    /// The path of the XML element, e.g., "/GAML/experiment[0]/trace[1]".
+    ElementPath(String),
+    /// The name of the netCDF variable.
+    Variable(String),
 }
 
 impl SfError {
     pub fn new(msg: &str) -> Self {
         Self {
             message: msg.into(),
+            kind: SfErrorKind::Other,
+            location: None,
             source: None,
         }
     }
 
-    pub fn from_source(source: impl Into<Box<dyn Error>>, message: impl Into<String>) -> Self {
+    /// Creates an error from a source error.
+    ///
+    /// Kind and location are taken over from a source `SfError`. A source I/O error
+    /// results in kind `Io`.
+    pub fn from_source(
+        source: impl Into<Box<dyn Error + Send + Sync>>,
+        message: impl Into<String>,
+    ) -> Self {
+        let source = source.into();
+        let (kind, location) = if let Some(e) = source.downcast_ref::<SfError>() {
+            (e.kind, e.location.clone())
+        } else if source.is::<std::io::Error>() {
+            (SfErrorKind::Io, None)
+        } else {
+            (SfErrorKind::Other, None)
+        };
         Self {
             message: message.into(),
-            source: Some(source.into()),
+            kind,
+            location,
+            source: Some(source),
+        }
+    }
+
+    /// Sets the kind of the error.
+    pub fn with_kind(mut self, kind: SfErrorKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Sets the location in the data set the error relates to.
+    pub fn with_location(mut self, location: SfErrorLocation) -> Self {
+        self.location = Some(location);
+        self
+    }
+
+    /// The kind of the error.
+    pub fn kind(&self) -> SfErrorKind {
+        self.kind
+    }
+
+    /// The location in the data set the error relates to, if known.
+    pub fn location(&self) -> Option<&SfErrorLocation> {
+        self.location.as_ref()
+    }
+
+    /// Sets the kind of the error unless a specific kind has already been set.
+    pub(crate) fn or_kind(mut self, kind: SfErrorKind) -> Self {
+        if self.kind == SfErrorKind::Other {
+            self.kind = kind;
         }
+        self
+    }
+
+    /// Sets the location of the error unless a location has already been set.
+    pub(crate) fn or_location(
+        mut self,
+        location: impl FnOnce() -> Option<SfErrorLocation>,
+    ) -> Self {
+        if self.location.is_none() {
+            self.location = location();
+        }
+        self
+    }
+
+    /// Prepends an XML element to the element path of the error.
+    ///
+    /// Errors with a location other than an element path remain unchanged.
+    pub(crate) fn prepend_element_path(mut self, element: &str) -> Self {
+        self.location = match self.location {
+            None => Some(SfErrorLocation::ElementPath(format!("/{element}"))),
+            Some(SfErrorLocation::ElementPath(path)) => {
+                Some(SfErrorLocation::ElementPath(format!("/{element}{path}")))
+            }
+            location => location,
+        };
+        self
     }
 }
 
 impl Error for SfError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
-        self.source.as_ref().map(|b| b.as_ref())
+        self.source
+            .as_ref()
+            .map(|b| b.as_ref() as &(dyn Error + 'static))
     }
 }
 
@@ -74,73 +191,311 @@ impl From<std::io::Error> for SfError {
 
 impl From<ParseError> for SfError {
     fn from(value: ParseError) -> Self {
-        Self::from_source(value, "Parsing value error.")
+        Self::from_source(value, "Parsing value error.").with_kind(SfErrorKind::CorruptData)
     }
 }
 
+impl From<netcdf3::ReadError> for SfError {
+    fn from(value: netcdf3::ReadError) -> Self {
+        let kind = match value {
+            netcdf3::ReadError::IOErrorKind(_) => SfErrorKind::Io,
+            _ => SfErrorKind::CorruptData,
+        };
+        let location = match &value {
+            netcdf3::ReadError::VariableNotDefined(var_name)
+            | netcdf3::ReadError::VariableMismatchDataType { var_name, .. } => {
+                Some(SfErrorLocation::Variable(var_name.to_owned()))
+            }
+            _ => None,
+        };
+        // netCDF errors cannot be sent between threads, so only their message is retained
+        let error =
+            Self::from_source(Self::new(&value.to_string()), "netCDF error.").with_kind(kind);
+        match location {
+            Some(location) => error.with_location(location),
+            None => error,
+        }
+    }
+}
+
+/// A scanner that may be able to read a data set.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScannerCandidate {
+    /// The name of the scanner.
+    pub name: String,
+    /// The confidence with which the scanner recognizes the data set.
+    pub confidence: Confidence,
+}
+
 /// A repository for scanners.
-pub struct ScannerRepository<T: Seek + Read + 'static> {
+///
+/// Unless strict extension checking is enabled, data sets are also recognized by
+/// their content alone, e.g., if the path has a missing or wrong extension.
+///
+/// Providing a reader requires a cloneable input, e.g., a [`SharedSeekRead`], as all
+/// recognizing scanners are tried in turn with a rewound clone of the input.
+pub struct ScannerRepository<T: SeekRead + 'static> {
     scanners: Vec<Box<dyn Scanner<T>>>,
+    strict_extensions: bool,
 }
 
-impl<T: Seek + Read + 'static> Scanner<T> for ScannerRepository<T> {
+impl<T: SeekRead + Clone + 'static> Scanner<T> for ScannerRepository<T> {
     /// Checks whether a data set is recognized by any contained scanner. Shallow check.
     fn is_recognized(&self, path: &str, input: &mut T) -> bool {
-        self.scanners
-            .iter()
-            .any(|scanner| scanner.is_recognized(path, input))
+        self.scanners.iter().any(|scanner| {
+            input.seek(SeekFrom::Start(0)).is_ok()
+                && self.is_sufficient(scanner.get_confidence(path, input))
+        })
     }
 
     /// Provides a reader for a recognized data set.
     ///
-    /// If multiple scanners recognize a data set, any of them may be returned.
-    fn get_reader(&self, path: &str, mut input: T) -> Result<Box<dyn Reader>, SfError> {
+    /// See [`ScannerRepository::get_named_reader()`] for the scanners tried.
+    fn get_reader(&self, path: &str, input: T) -> Result<Box<dyn Reader>, SfError> {
+        self.get_named_reader(path, input).map(|(_, reader)| reader)
+    }
+}
+
+impl<T: SeekRead + Clone + 'static> ScannerRepository<T> {
+    /// Provides a reader for a recognized data set together with the name of the
+    /// scanner that initialized it.
+    ///
+    /// All scanners recognizing the data set are tried in turn, ordered by descending
+    /// confidence, and the first reader successfully initialized is returned. If all
+    /// of them fail, the error lists the failure of each scanner.
+    pub fn get_named_reader(
+        &self,
+        path: &str,
+        mut input: T,
+    ) -> Result<(&str, Box<dyn Reader>), SfError> {
+        let mut recognizing = vec![];
         for scanner in &self.scanners {
+            let scanner = scanner.as_ref();
             input.seek(SeekFrom::Start(0))?;
-            if scanner.is_recognized(path, &mut input) {
-                input.seek(SeekFrom::Start(0))?;
-                // TODO: find a way to still try other recognizing readers in case of error
-                let reader = scanner.get_reader(path, input)?;
-                return Ok(reader);
+            let confidence = scanner.get_confidence(path, &mut input);
+            if self.is_sufficient(confidence)
+                && let Some(confidence) = confidence
+            {
+                recognizing.push((confidence, scanner));
+            }
+        }
+        recognizing.sort_by_key(|(confidence, _)| std::cmp::Reverse(*confidence));
+
+        let mut failures = vec![];
+        let mut first_failure: Option<SfError> = None;
+        for (_, scanner) in recognizing {
+            let mut attempt_input = input.clone();
+            attempt_input.seek(SeekFrom::Start(0))?;
+            match scanner.get_reader(path, attempt_input) {
+                Ok(reader) => return Ok((scanner.get_name(), reader)),
+                Err(e) => {
+                    failures.push(format!(
+                        "{}: {}",
+                        scanner.get_name(),
+                        format_error_chain(&e)
+                    ));
+                    first_failure.get_or_insert(e);
+                }
             }
         }
 
-        Err(SfError::new(&format!(
-            "No reader can be initialized for file: {}",
+        let Some(first_failure) = first_failure else {
+            return Err(
+                SfError::new(&format!("No reader can be initialized for file: {}", path,))
+                    .with_kind(SfErrorKind::UnsupportedFormat),
+            );
+        };
+        // the most confident scanner's failure determines kind and location
+        let mut error = SfError::new(&format!(
+            "No reader can be initialized for file: {}. Recognizing scanners failed with: {}",
             path,
-        )))?
+            failures.join("; "),
+        ))
+        .with_kind(first_failure.kind);
+        error.location = first_failure.location;
+        Err(error)
     }
 }
 
-impl<T: Seek + Read + 'static> ScannerRepository<T> {
+impl<T: SeekRead + 'static> ScannerRepository<T> {
     /// Create a repository containing the passed scanners.
     pub fn new(scanners: Vec<Box<dyn Scanner<T>>>) -> ScannerRepository<T> {
-        ScannerRepository { scanners }
+        ScannerRepository {
+            scanners,
+            strict_extensions: false,
+        }
     }
 
     /// Create a repository containing all available scanners.
     pub fn init_all() -> ScannerRepository<T> {
         let andi_scanner: Box<dyn Scanner<T>> = Box::new(AndiScanner::new());
+        let bruker_scanner = Box::new(BrukerScanner::new());
         let gaml_scanner = Box::new(GamlScanner::new());
         let jdx_scanner = Box::new(JdxScanner::new());
+        let jeol_scanner = Box::new(JeolScanner::new());
         let json_scanner = Box::new(JsonScanner::new());
-        let scanners: Vec<Box<dyn Scanner<T>>> =
-            vec![andi_scanner, gaml_scanner, jdx_scanner, json_scanner];
-        ScannerRepository { scanners }
+        let varian_scanner = Box::new(VarianScanner::new());
+        let scanners: Vec<Box<dyn Scanner<T>>> = vec![
+            andi_scanner,
+            bruker_scanner,
+            gaml_scanner,
+            jdx_scanner,
+            jeol_scanner,
+            json_scanner,
+            varian_scanner,
+        ];
+        ScannerRepository::new(scanners)
     }
 
     /// Add a scanner to the repository.
     pub fn push(&mut self, scanner: Box<dyn Scanner<T>>) {
         self.scanners.push(scanner)
     }
+
+    /// Enable or disable strict extension checking.
+    ///
+    /// If enabled, data sets are only recognized if both path and content match a
+    /// format. Otherwise, a matching content suffices. Disabled by default.
+    pub fn set_strict_extensions(&mut self, strict_extensions: bool) {
+        self.strict_extensions = strict_extensions;
+    }
+
+    /// Sets the options for parsing data sets for all contained scanners.
+    pub fn set_parse_options(&mut self, options: &ParseOptions) {
+        for scanner in &mut self.scanners {
+            scanner.set_parse_options(options);
+        }
+    }
+
+    /// Checks whether a confidence suffices for recognizing a data set.
+    fn is_sufficient(&self, confidence: Option<Confidence>) -> bool {
+        let min_confidence = match self.strict_extensions {
+            true => Confidence::High,
+            false => Confidence::Medium,
+        };
+        confidence.is_some_and(|c| c >= min_confidence)
+    }
+
+    /// Lists all scanners that may be able to read a data set. Shallow check.
+    ///
+    /// The candidates are ordered by descending confidence. Candidates with equal
+    /// confidence retain the order of the scanners in the repository.
+    pub fn get_candidates(
+        &self,
+        path: &str,
+        input: &mut T,
+    ) -> Result<Vec<ScannerCandidate>, SfError> {
+        let mut candidates = vec![];
+        for scanner in &self.scanners {
+            let scanner = scanner.as_ref();
+            input.seek(SeekFrom::Start(0))?;
+            if let Some(confidence) = scanner.get_confidence(path, input) {
+                candidates.push(ScannerCandidate {
+                    name: scanner.get_name().to_owned(),
+                    confidence,
+                });
+            }
+        }
+        candidates.sort_by_key(|c| std::cmp::Reverse(c.confidence));
+
+        Ok(candidates)
+    }
 }
 
-impl<T: Seek + Read + 'static> Default for ScannerRepository<T> {
+impl<T: SeekRead + 'static> Default for ScannerRepository<T> {
     fn default() -> Self {
         ScannerRepository::new(vec![])
     }
 }
 
+/// Formats an error message followed by the messages of all its sources.
+fn format_error_chain(error: &dyn Error) -> String {
+    let mut message = error.to_string();
+    let mut source = error.source();
+    while let Some(e) = source {
+        message.push_str(": ");
+        message.push_str(&e.to_string());
+        source = e.source();
+    }
+    message
+}
+
+/// Collects the deviations from a format specification found while parsing a data set.
+#[derive(Debug)]
+pub(crate) struct WarningCollector {
+    mode: ParseMode,
+    warnings: Vec<ParseWarning>,
+}
+
+impl WarningCollector {
+    pub(crate) fn new(options: &ParseOptions) -> Self {
+        Self {
+            mode: options.mode,
+            warnings: vec![],
+        }
+    }
+
+    /// Reports a deviation from the format specification.
+    ///
+    /// The deviation is kept as a warning. In strict mode, all warnings result in an
+    /// error when parsing is finished.
+    pub(crate) fn report(&mut self, message: &str, location: Option<SfErrorLocation>) {
+        self.warnings.push(ParseWarning {
+            message: message.to_owned(),
+            location,
+        });
+    }
+
+    /// Provides the collected warnings.
+    ///
+    /// In strict mode, any warning results in an error listing all of them. Its
+    /// location is that of the first warning.
+    pub(crate) fn into_warnings(self) -> Result<Vec<ParseWarning>, SfError> {
+        if self.mode == ParseMode::Lenient || self.warnings.is_empty() {
+            return Ok(self.warnings);
+        }
+        let messages: Vec<String> = self
+            .warnings
+            .iter()
+            .map(|w| match &w.location {
+                Some(location) => format!("{} ({:?})", w.message, location),
+                None => w.message.clone(),
+            })
+            .collect();
+        let mut error = SfError::new(&format!(
+            "Deviations from the format specification: {}",
+            messages.join("; ")
+        ))
+        .with_kind(SfErrorKind::CorruptData);
+        error.location = self.warnings.into_iter().next().and_then(|w| w.location);
+        Err(error)
+    }
+}
+
+/// A reader providing a single node as root node, e.g., a processed spectrum.
+///
+/// Allows for exporting a node like any data set. Child nodes are not provided.
+pub struct NodeReader {
+    node: Node,
+}
+
+impl NodeReader {
+    pub fn new(mut node: Node) -> Self {
+        node.child_node_names.clear();
+        Self { node }
+    }
+}
+
+impl Reader for NodeReader {
+    fn read(&self, path: &str) -> Result<Node, SfError> {
+        if !convert_path_to_node_indices(path)?.is_empty() {
+            return Err(SfError::new(&format!("Illegal node path: {}", path))
+                .with_kind(SfErrorKind::IllegalNodePath));
+        }
+        Ok(self.node.clone())
+    }
+}
+
 /// A buffered implementation of the SeekRead trait.
 ///
 /// Unlike the std BufReader, this implementation tries to avoid clearing the buffer on seek.
@@ -216,11 +571,61 @@ impl<T: Seek + Read> Read for BufSeekRead<T> {
     }
 }
 
+/// A SeekRead implementation providing access to one input for multiple consumers.
+///
+/// Clones share the underlying input but each keeps track of its own position.
+pub struct SharedSeekRead<T: Seek + Read> {
+    input: Arc<Mutex<T>>,
+    pos: u64,
+}
+
+impl<T: Seek + Read> SharedSeekRead<T> {
+    pub fn new(input: T) -> Self {
+        Self {
+            input: Arc::new(Mutex::new(input)),
+            pos: 0,
+        }
+    }
+}
+
+impl<T: Seek + Read> Clone for SharedSeekRead<T> {
+    fn clone(&self) -> Self {
+        Self {
+            input: Arc::clone(&self.input),
+            pos: self.pos,
+        }
+    }
+}
+
+impl<T: Seek + Read> Seek for SharedSeekRead<T> {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        self.pos = match pos {
+            SeekFrom::Start(offset) => offset,
+            SeekFrom::Current(offset) => self
+                .pos
+                .checked_add_signed(offset)
+                .ok_or(std::io::Error::from(ErrorKind::InvalidInput))?,
+            SeekFrom::End(offset) => lock_input(&self.input).seek(SeekFrom::End(offset))?,
+        };
+        Ok(self.pos)
+    }
+}
+
+impl<T: Seek + Read> Read for SharedSeekRead<T> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let mut input = lock_input(&self.input);
+        input.seek(SeekFrom::Start(self.pos))?;
+        let num_read = input.read(buf)?;
+        self.pos += num_read as u64;
+        Ok(num_read)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
-        api::{Node, Reader, Scanner, SeekRead},
+        api::{Confidence, Node, Reader, Scanner},
         common::BufSeekRead,
     };
     use std::io::{Cursor, Read, Seek, SeekFrom};
@@ -238,6 +643,9 @@ mod tests {
                     data: vec![],
                     metadata: vec![],
                     table: None,
+                    nd_array: None,
+                    complex_data: None,
+                    links: vec![],
                     child_node_names: vec![],
                 }),
                 _ => Err(SfError::new("Error"))?,
@@ -282,6 +690,84 @@ mod tests {
         assert_eq!("Message", error.to_string());
     }
 
+    #[test]
+    fn sf_error_defaults_to_kind_other_without_location() {
+        let error = SfError::new("Message");
+        assert_eq!(SfErrorKind::Other, error.kind());
+        assert_eq!(None, error.location());
+    }
+
+    #[test]
+    fn sf_error_inherits_kind_and_location_from_source() {
+        let source = SfError::new("Source")
+            .with_kind(SfErrorKind::CorruptData)
+            .with_location(SfErrorLocation::ByteOffset(3));
+        let error = SfError::from_source(source, "Message");
+        assert_eq!(SfErrorKind::CorruptData, error.kind());
+        assert_eq!(Some(&SfErrorLocation::ByteOffset(3)), error.location());
+
+        let io_error = std::io::Error::other("Source");
+        assert_eq!(SfErrorKind::Io, SfError::from(io_error).kind());
+    }
+
+    #[test]
+    fn sf_error_keeps_specific_kind_and_location() {
+        let error = SfError::new("Message")
+            .with_kind(SfErrorKind::IllegalNodePath)
+            .or_kind(SfErrorKind::CorruptData)
+            .with_location(SfErrorLocation::Variable("var".to_owned()))
+            .or_location(|| Some(SfErrorLocation::ByteOffset(1)));
+        assert_eq!(SfErrorKind::IllegalNodePath, error.kind());
+        assert_eq!(
+            Some(&SfErrorLocation::Variable("var".to_owned())),
+            error.location()
+        );
+    }
+
+    #[test]
+    fn sf_error_prepends_element_path() {
+        let error = SfError::new("Message")
+            .prepend_element_path("trace[1]")
+            .prepend_element_path("GAML");
+        assert_eq!(
+            Some(&SfErrorLocation::ElementPath("/GAML/trace[1]".to_owned())),
+            error.location()
+        );
+
+        let error = SfError::new("Message")
+            .with_location(SfErrorLocation::ByteOffset(1))
+            .prepend_element_path("GAML");
+        assert_eq!(Some(&SfErrorLocation::ByteOffset(1)), error.location());
+    }
+
+    #[test]
+    fn warning_collector_keeps_warnings_in_lenient_mode() {
+        let mut collector = WarningCollector::new(&ParseOptions::default());
+        collector.report("Message", Some(SfErrorLocation::ByteOffset(1)));
+        assert_eq!(
+            vec![ParseWarning {
+                message: "Message".to_owned(),
+                location: Some(SfErrorLocation::ByteOffset(1)),
+            }],
+            collector.into_warnings().unwrap()
+        );
+    }
+
+    #[test]
+    fn warning_collector_fails_with_all_warnings_in_strict_mode() {
+        let mut collector = WarningCollector::new(&ParseOptions::strict());
+        collector.report("Message 0", Some(SfErrorLocation::ByteOffset(1)));
+        collector.report("Message 1", None);
+        let error = collector.into_warnings().unwrap_err();
+        assert_eq!(SfErrorKind::CorruptData, error.kind());
+        assert_eq!(Some(&SfErrorLocation::ByteOffset(1)), error.location());
+        assert!(error.to_string().contains("Message 0"));
+        assert!(error.to_string().contains("Message 1"));
+
+        let collector = WarningCollector::new(&ParseOptions::strict());
+        assert!(collector.into_warnings().unwrap().is_empty());
+    }
+
     #[test]
     fn buf_seek_read_mimicks_std_seek_read_behavior() {
         let arr: [u8; 3] = [1, 2, 3];
@@ -335,7 +821,7 @@ mod tests {
             recognized: false,
             reader_name: None,
         };
-        let mut input: Box<dyn SeekRead> = Box::new(Cursor::new("abc"));
+        let mut input = SharedSeekRead::new(Cursor::new("abc"));
         let mut repo = ScannerRepository::default();
         repo.push(Box::new(scanner_non_recognizing_0));
 
@@ -360,7 +846,7 @@ mod tests {
             recognized: true,
             reader_name: Some("2".to_owned()),
         });
-        let mut input: Box<dyn SeekRead> = Box::new(Cursor::new("abc"));
+        let mut input = SharedSeekRead::new(Cursor::new("abc"));
         let repo = ScannerRepository::new(vec![
             scanner_non_recognizing_0,
             scanner_recognizing_1,
@@ -372,12 +858,176 @@ mod tests {
         assert_eq!("1", reader_result.read("").unwrap().name);
     }
 
+    #[test]
+    fn scanner_repository_falls_back_to_next_recognizing_scanner() {
+        let scanner_failing_0 = Box::new(StubScanner {
+            recognized: true,
+            reader_name: None,
+        });
+        let scanner_recognizing_1 = Box::new(StubScanner {
+            recognized: true,
+            reader_name: Some("1".to_owned()),
+        });
+        let input = SharedSeekRead::new(Cursor::new("abc"));
+        let repo = ScannerRepository::new(vec![scanner_failing_0, scanner_recognizing_1]);
+
+        let reader_result = repo.get_reader("path", input).unwrap();
+        assert_eq!("1", reader_result.read("").unwrap().name);
+    }
+
+    #[test]
+    fn scanner_repository_returns_aggregated_error_if_all_recognizing_scanners_fail() {
+        let scanner_failing_0 = Box::new(StubScanner {
+            recognized: true,
+            reader_name: None,
+        });
+        let scanner_non_recognizing_1 = Box::new(StubScanner {
+            recognized: false,
+            reader_name: None,
+        });
+        let scanner_failing_2 = Box::new(StubScanner {
+            recognized: true,
+            reader_name: None,
+        });
+        let input = SharedSeekRead::new(Cursor::new("abc"));
+        let repo = ScannerRepository::new(vec![
+            scanner_failing_0,
+            scanner_non_recognizing_1,
+            scanner_failing_2,
+        ]);
+
+        let error = repo.get_reader("path", input).err().unwrap();
+        let message = error.to_string();
+        assert!(message.contains("path"));
+        assert_eq!(2, message.matches("StubScanner: Error").count());
+    }
+
+    #[test]
+    fn scanner_repository_names_scanner_providing_reader() {
+        let repo = ScannerRepository::init_all();
+
+        let input = SharedSeekRead::new(Cursor::new("##TITLE= abc\n##END="));
+        let (name, _) = repo.get_named_reader("upload.json", input).unwrap();
+        assert_eq!("JCAMP-DX", name);
+    }
+
+    #[test]
+    fn scanner_repository_lists_candidates_by_confidence() {
+        let repo = ScannerRepository::init_all();
+
+        let mut input = SharedSeekRead::new(Cursor::new("abc"));
+        let candidates = repo.get_candidates("file.jdx", &mut input).unwrap();
+        assert_eq!(
+            vec![ScannerCandidate {
+                name: "JCAMP-DX".to_owned(),
+                confidence: Confidence::Low
+            }],
+            candidates
+        );
+
+        let mut input = SharedSeekRead::new(Cursor::new("##TITLE= abc\n##END="));
+        let candidates = repo.get_candidates("file.jdx", &mut input).unwrap();
+        assert_eq!(1, candidates.len());
+        assert_eq!(Confidence::High, candidates[0].confidence);
+
+        let mut input = SharedSeekRead::new(Cursor::new("abc"));
+        let candidates = repo.get_candidates("file.abc", &mut input).unwrap();
+        assert!(candidates.is_empty());
+    }
+
+    #[test]
+    fn scanner_repository_recognizes_content_unless_strict() {
+        let jdx = "##TITLE= abc\n##END=";
+        let mut repo = ScannerRepository::init_all();
+
+        for path in ["upload.bin", "data.txt", ""] {
+            let mut input = SharedSeekRead::new(Cursor::new(jdx));
+            assert!(repo.is_recognized(path, &mut input));
+            let input = SharedSeekRead::new(Cursor::new(jdx));
+            assert!(repo.get_reader(path, input).is_ok());
+        }
+
+        repo.set_strict_extensions(true);
+        let mut input = SharedSeekRead::new(Cursor::new(jdx));
+        assert!(!repo.is_recognized("upload.bin", &mut input));
+        let input = SharedSeekRead::new(Cursor::new(jdx));
+        assert!(repo.get_reader("upload.bin", input).is_err());
+        let mut input = SharedSeekRead::new(Cursor::new(jdx));
+        assert!(repo.is_recognized("upload.jdx", &mut input));
+    }
+
+    #[test]
+    fn scanner_repository_prefers_matching_extension() {
+        let jdx = "##TITLE= abc\n##END=";
+        let repo = ScannerRepository::init_all();
+
+        let mut input = SharedSeekRead::new(Cursor::new(jdx));
+        let candidates = repo.get_candidates("upload.json", &mut input).unwrap();
+        assert_eq!(
+            vec![
+                ScannerCandidate {
+                    name: "JCAMP-DX".to_owned(),
+                    confidence: Confidence::Medium
+                },
+                ScannerCandidate {
+                    name: "JSON".to_owned(),
+                    confidence: Confidence::Low
+                },
+            ],
+            candidates
+        );
+    }
+
+    #[test]
+    fn shared_seek_read_clones_keep_own_positions() {
+        let mut buf = [0u8; 2];
+        let mut shared_0 = SharedSeekRead::new(Cursor::new([1u8, 2, 3, 4]));
+        shared_0.read_exact(&mut buf).unwrap();
+        assert_eq!([1, 2], buf);
+
+        let mut shared_1 = shared_0.clone();
+        shared_0.seek(SeekFrom::Start(0)).unwrap();
+        shared_1.read_exact(&mut buf).unwrap();
+        assert_eq!([3, 4], buf);
+        shared_0.read_exact(&mut buf).unwrap();
+        assert_eq!([1, 2], buf);
+
+        assert_eq!(4, shared_1.seek(SeekFrom::End(0)).unwrap());
+        assert_eq!(2, shared_0.stream_position().unwrap());
+    }
+
     #[test]
     fn scanner_repository_returns_error_if_no_applicable_scanner() {
-        let input: Box<dyn SeekRead> = Box::new(Cursor::new("abc"));
+        let input = SharedSeekRead::new(Cursor::new("abc"));
         let repo = ScannerRepository::new(vec![]);
 
-        let reader_result = repo.get_reader("path", input);
-        assert!(reader_result.is_err());
+        let error = repo.get_reader("path", input).err().unwrap();
+        assert_eq!(SfErrorKind::UnsupportedFormat, error.kind());
+    }
+
+    #[test]
+    fn node_reader_reads_single_node_without_children() {
+        let node = Node {
+            name: "Node".to_owned(),
+            parameters: vec![],
+            data: vec![],
+            metadata: vec![],
+            table: None,
+            nd_array: None,
+            complex_data: None,
+            links: vec![],
+            child_node_names: vec!["Child".to_owned()],
+        };
+
+        let reader = NodeReader::new(node);
+
+        let root = reader.read("/").unwrap();
+        assert_eq!("Node", root.name);
+        assert!(root.child_node_names.is_empty());
+        assert_eq!(root, reader.read("").unwrap());
+        assert_eq!(
+            SfErrorKind::IllegalNodePath,
+            reader.read("/0").unwrap_err().kind()
+        );
     }
 }
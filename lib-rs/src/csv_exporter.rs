@@ -0,0 +1,376 @@
+// Copyright (c) 2025 Robert Schiwon
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use crate::{
+    api::{Exporter, Node, Parameter, Reader},
+    common::SfError,
+};
+use std::io::{Seek, Write};
+use zip::{CompressionMethod, ZipWriter, write::SimpleFileOptions};
+
+/// Exports all nodes as delimiter separated values, bundled in a ZIP archive.
+///
+/// The archive contains a "nodes" file listing the path and name of each node.
+/// The files for a node are placed in a directory corresponding to its path,
+/// e.g., "0/1/" for the node "/0/1" and the archive root for the root node:
+/// - "parameters": The parameters as key and value columns.
+/// - "metadata": The metadata as key and value columns.
+/// - "data": The data as x and y columns.
+/// - "table": The table with the column keys as headers.
+/// - "array": The multi-dimensional array with one column per axis and a values column.
+///
+/// Files are only written for node contents that are present.
+pub struct CsvExporter<'a, R: Reader + ?Sized> {
+    reader: &'a R,
+    delimiter: char,
+}
+
+impl<'a, R: Reader + ?Sized> CsvExporter<'a, R> {
+    /// Creates an exporter for comma separated values.
+    pub fn csv(reader: &'a R) -> Self {
+        Self {
+            reader,
+            delimiter: ',',
+        }
+    }
+
+    /// Creates an exporter for tab separated values.
+    pub fn tsv(reader: &'a R) -> Self {
+        Self {
+            reader,
+            delimiter: '\t',
+        }
+    }
+
+    fn write_node<W: Write + Seek>(
+        &self,
+        path: &str,
+        zip: &mut ZipWriter<W>,
+        index: &mut Vec<(String, String)>,
+    ) -> Result<(), SfError> {
+        let node = self.reader.read(path)?;
+        let dir = match path.trim_start_matches('/') {
+            "" => String::new(),
+            p => format!("{}/", p),
+        };
+        index.push((
+            match path {
+                "" => "/".to_owned(),
+                p => p.to_owned(),
+            },
+            node.name.clone(),
+        ));
+        self.write_node_files(&node, &dir, zip)?;
+
+        for i in 0..node.child_node_names.len() {
+            let child_path = format!("{}/{}", path, i);
+            self.write_node(&child_path, zip, index)?;
+        }
+        Ok(())
+    }
+
+    fn write_node_files<W: Write + Seek>(
+        &self,
+        node: &Node,
+        dir: &str,
+        zip: &mut ZipWriter<W>,
+    ) -> Result<(), SfError> {
+        if !node.parameters.is_empty() {
+            self.start_file(zip, dir, "parameters")?;
+            self.write_record(zip, &["key", "value"])?;
+            for parameter in &node.parameters {
+                match parameter {
+                    Parameter::KeyValue(key, value) => {
+                        self.write_record(zip, &[key, &value.to_string()])?
+                    }
+                    Parameter::Value(value) => self.write_record(zip, &["", &value.to_string()])?,
+                }
+            }
+        }
+        if !node.metadata.is_empty() {
+            self.start_file(zip, dir, "metadata")?;
+            self.write_record(zip, &["key", "value"])?;
+            for (key, value) in &node.metadata {
+                self.write_record(zip, &[key, value])?;
+            }
+        }
+        if !node.data.is_empty() {
+            self.start_file(zip, dir, "data")?;
+            self.write_record(zip, &["x", "y"])?;
+            for point in &node.data {
+                self.write_record(zip, &[&point.x.to_string(), &point.y.to_string()])?;
+            }
+        }
+        if let Some(table) = &node.table {
+            self.start_file(zip, dir, "table")?;
+            let keys: Vec<&str> = table.column_names.iter().map(|c| c.key.as_str()).collect();
+            self.write_record(zip, &keys)?;
+            for row in &table.rows {
+                let cells: Vec<String> = keys
+                    .iter()
+                    .map(|key| row.get(*key).map(|v| v.to_string()).unwrap_or_default())
+                    .collect();
+                let cells: Vec<&str> = cells.iter().map(String::as_str).collect();
+                self.write_record(zip, &cells)?;
+            }
+        }
+        if let Some(nd_array) = &node.nd_array {
+            self.start_file(zip, dir, "array")?;
+            let mut header: Vec<&str> = nd_array.axes.iter().map(|a| a.name.as_str()).collect();
+            header.push(&nd_array.name);
+            self.write_record(zip, &header)?;
+            let mut indices = vec![0usize; nd_array.axes.len()];
+            for value in &nd_array.values {
+                let mut cells: Vec<String> = nd_array
+                    .axes
+                    .iter()
+                    .zip(&indices)
+                    .map(|(axis, &i)| axis.values[i].to_string())
+                    .collect();
+                cells.push(value.to_string());
+                let cells: Vec<&str> = cells.iter().map(String::as_str).collect();
+                self.write_record(zip, &cells)?;
+                // advance the indices in row-major order
+                for (dim, axis) in nd_array.axes.iter().enumerate().rev() {
+                    indices[dim] += 1;
+                    if indices[dim] < axis.values.len() {
+                        break;
+                    }
+                    indices[dim] = 0;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn start_file<W: Write + Seek>(
+        &self,
+        zip: &mut ZipWriter<W>,
+        dir: &str,
+        name: &str,
+    ) -> Result<(), SfError> {
+        let options = SimpleFileOptions::default()
+            .compression_method(CompressionMethod::Stored)
+            .large_file(true);
+        let extension = match self.delimiter {
+            '\t' => "tsv",
+            _ => "csv",
+        };
+        zip.start_file(format!("{}{}.{}", dir, name, extension), options)
+            .map_err(|e| SfError::from_source(e, "Error writing export."))
+    }
+
+    /// Writes a record, quoting fields that contain delimiters, quotes, or line breaks.
+    fn write_record(&self, writer: &mut dyn Write, fields: &[&str]) -> Result<(), SfError> {
+        let mut line = String::new();
+        for (i, field) in fields.iter().enumerate() {
+            if i > 0 {
+                line.push(self.delimiter);
+            }
+            if field.contains([self.delimiter, '"', '\r', '\n']) {
+                line.push('"');
+                line.push_str(&field.replace('"', "\"\""));
+                line.push('"');
+            } else {
+                line.push_str(field);
+            }
+        }
+        line.push_str("\r\n");
+        writer.write_all(line.as_bytes())?;
+        Ok(())
+    }
+}
+
+impl<R: Reader + ?Sized> Exporter for CsvExporter<'_, R> {
+    fn get_name(&self) -> &'static str {
+        match self.delimiter {
+            '\t' => "TSV Exporter",
+            _ => "CSV Exporter",
+        }
+    }
+
+    fn write(&mut self, writer: &mut dyn Write) -> Result<(), SfError> {
+        let mut zip = ZipWriter::new_stream(writer);
+        let mut index = vec![];
+        self.write_node("", &mut zip, &mut index)?;
+
+        self.start_file(&mut zip, "", "nodes")?;
+        self.write_record(&mut zip, &["path", "name"])?;
+        for (path, name) in &index {
+            self.write_record(&mut zip, &[path, name])?;
+        }
+        zip.finish()
+            .map_err(|e| SfError::from_source(e, "Error writing export."))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{Axis, Column, ExportFormat, NdArray, PointXy, Table, Value};
+    use std::{
+        collections::HashMap,
+        io::{Cursor, Read},
+    };
+    use zip::ZipArchive;
+
+    struct StubReader {}
+
+    impl Reader for StubReader {
+        fn read(&self, path: &str) -> Result<Node, SfError> {
+            let root = Node {
+                name: "root node name".to_owned(),
+                parameters: vec![
+                    Parameter::from_str_str("param String", "a,b"),
+                    Parameter::from_str_i32("param i32", -1),
+                    Parameter::from_f64(1.5),
+                ],
+                data: vec![PointXy::new(1.0, 2.0), PointXy::new(3.0, 4.5)],
+                metadata: vec![("mk0".to_owned(), "say \"hi\"".to_owned())],
+                table: Some(Table {
+                    column_names: vec![
+                        Column::new("key0", "Name 0"),
+                        Column::new("key1", "Name 1"),
+                    ],
+                    rows: vec![
+                        HashMap::from([
+                            ("key0".to_owned(), Value::String("value".to_owned())),
+                            ("key1".to_owned(), Value::Bool(true)),
+                        ]),
+                        HashMap::from([("key1".to_owned(), Value::U64(2))]),
+                    ],
+                }),
+                nd_array: None,
+                complex_data: None,
+                links: vec![],
+                child_node_names: vec!["child node name 0".to_owned()],
+            };
+            let child0 = Node {
+                name: "child node name 0".to_owned(),
+                parameters: vec![],
+                data: vec![],
+                metadata: vec![],
+                table: None,
+                nd_array: Some(
+                    NdArray::new(
+                        "values",
+                        None,
+                        vec![
+                            Axis::new("axis 0", None, vec![0.0, 1.0]),
+                            Axis::new("axis 1", None, vec![10.0, 20.0]),
+                        ],
+                        vec![1.0, 2.0, 3.0, 4.0],
+                    )
+                    .unwrap(),
+                ),
+                complex_data: None,
+                links: vec![],
+                child_node_names: vec![],
+            };
+
+            match path {
+                "" | "/" => Ok(root),
+                "/0" => Ok(child0),
+                _ => Err(SfError::new(&format!("Illegal path: {}", path))),
+            }
+        }
+    }
+
+    fn read_entries(export: Vec<u8>) -> HashMap<String, String> {
+        let mut archive = ZipArchive::new(Cursor::new(export)).unwrap();
+        let mut entries = HashMap::new();
+        for i in 0..archive.len() {
+            let mut file = archive.by_index(i).unwrap();
+            let mut content = String::new();
+            file.read_to_string(&mut content).unwrap();
+            entries.insert(file.name().to_owned(), content);
+        }
+        entries
+    }
+
+    #[test]
+    fn exports_node_tree_to_csv_bundle() {
+        let reader = StubReader {};
+        let mut export = vec![];
+        reader.export(ExportFormat::Csv, &mut export).unwrap();
+
+        let entries = read_entries(export);
+        let mut names: Vec<&str> = entries.keys().map(String::as_str).collect();
+        names.sort();
+        assert_eq!(
+            vec![
+                "0/array.csv",
+                "data.csv",
+                "metadata.csv",
+                "nodes.csv",
+                "parameters.csv",
+                "table.csv"
+            ],
+            names
+        );
+        assert_eq!(
+            "path,name\r\n/,root node name\r\n/0,child node name 0\r\n",
+            entries["nodes.csv"]
+        );
+        assert_eq!(
+            "key,value\r\nparam String,\"a,b\"\r\nparam i32,-1\r\n,1.5\r\n",
+            entries["parameters.csv"]
+        );
+        assert_eq!(
+            "key,value\r\nmk0,\"say \"\"hi\"\"\"\r\n",
+            entries["metadata.csv"]
+        );
+        assert_eq!("x,y\r\n1,2\r\n3,4.5\r\n", entries["data.csv"]);
+        assert_eq!("key0,key1\r\nvalue,true\r\n,2\r\n", entries["table.csv"]);
+        assert_eq!(
+            "axis 0,axis 1,values\r\n0,10,1\r\n0,20,2\r\n1,10,3\r\n1,20,4\r\n",
+            entries["0/array.csv"]
+        );
+    }
+
+    #[test]
+    fn exports_node_tree_to_tsv_bundle() {
+        let reader = StubReader {};
+        let mut export = vec![];
+        reader.export(ExportFormat::Tsv, &mut export).unwrap();
+
+        let entries = read_entries(export);
+        assert_eq!(6, entries.len());
+        assert_eq!(
+            "key\tvalue\r\nparam String\ta,b\r\nparam i32\t-1\r\n\t1.5\r\n",
+            entries["parameters.tsv"]
+        );
+        assert_eq!("x\ty\r\n1\t2\r\n3\t4.5\r\n", entries["data.tsv"]);
+    }
+
+    #[test]
+    fn export_fails_for_unreadable_node() {
+        struct FailingReader {}
+        impl Reader for FailingReader {
+            fn read(&self, _path: &str) -> Result<Node, SfError> {
+                Err(SfError::new("Error"))
+            }
+        }
+
+        let reader = FailingReader {};
+        let mut export = vec![];
+        assert!(reader.export(ExportFormat::Csv, &mut export).is_err());
+    }
+}
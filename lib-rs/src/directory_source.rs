@@ -0,0 +1,397 @@
+// Copyright (c) 2025 Robert Schiwon
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use crate::{
+    api::{Confidence, ParseOptions, Reader, SeekRead},
+    common::{SfError, SfErrorKind},
+    utils::{determine_confidence, is_recognized_extension, lock_input},
+};
+use std::{
+    fs,
+    io::{self, ErrorKind, Read, Seek, SeekFrom},
+    ops::Range,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+use zip::{ZipArchive, result::ZipError};
+
+/// The files of a data set stored as directory, e.g., a Bruker experiment directory, either
+/// in the file system or in a ZIP archive.
+pub(crate) enum DirectorySource {
+    /// A directory in the file system.
+    Directory(PathBuf),
+    /// A ZIP archive and the path prefix of the directory within it, e.g., "sample/1/".
+    Zip(Mutex<ZipArchive<Box<dyn SeekRead>>>, String),
+}
+
+impl DirectorySource {
+    /// Opens a ZIP archive holding the data set directory, i.e., the directory holding the
+    /// marker file with the shortest path.
+    ///
+    /// Returns None if the archive holds no marker file.
+    pub(crate) fn open_zip(
+        input: Box<dyn SeekRead>,
+        marker_file: &str,
+    ) -> Result<Option<Self>, SfError> {
+        let archive = ZipArchive::new(input).map_err(map_zip_error)?;
+        Ok(find_zip_prefix(&archive, marker_file)
+            .map(|prefix| DirectorySource::Zip(Mutex::new(archive), prefix)))
+    }
+
+    /// Reads a file by its path relative to the directory.
+    ///
+    /// Returns None if the file does not exist.
+    pub(crate) fn read_file(&self, relative_path: &str) -> Result<Option<Vec<u8>>, SfError> {
+        match self {
+            DirectorySource::Directory(directory) => {
+                match fs::read(directory.join(relative_path)) {
+                    Ok(bytes) => Ok(Some(bytes)),
+                    Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+                    Err(e) => Err(e.into()),
+                }
+            }
+            DirectorySource::Zip(archive, prefix) => {
+                let mut archive = lock_input(archive);
+                let mut file = match archive.by_name(&format!("{}{}", prefix, relative_path)) {
+                    Ok(file) => file,
+                    Err(ZipError::FileNotFound) => return Ok(None),
+                    Err(e) => return Err(map_zip_error(e)),
+                };
+                let mut bytes = Vec::with_capacity(file.size() as usize);
+                file.read_to_end(&mut bytes)?;
+                Ok(Some(bytes))
+            }
+        }
+    }
+
+    /// Reads a range of bytes of a file by its path relative to the directory.
+    ///
+    /// Returns None if the file does not exist and an error if the file ends before the end
+    /// of the range.
+    pub(crate) fn read_file_range(
+        &self,
+        relative_path: &str,
+        range: Range<u64>,
+    ) -> Result<Option<Vec<u8>>, SfError> {
+        let len = range.end.saturating_sub(range.start);
+        match self {
+            DirectorySource::Directory(directory) => {
+                let mut file = match fs::File::open(directory.join(relative_path)) {
+                    Ok(file) => file,
+                    Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+                    Err(e) => return Err(e.into()),
+                };
+                file.seek(SeekFrom::Start(range.start))?;
+                read_exact_len(file, len, relative_path).map(Some)
+            }
+            DirectorySource::Zip(archive, prefix) => {
+                let mut archive = lock_input(archive);
+                let mut file = match archive.by_name(&format!("{}{}", prefix, relative_path)) {
+                    Ok(file) => file,
+                    Err(ZipError::FileNotFound) => return Ok(None),
+                    Err(e) => return Err(map_zip_error(e)),
+                };
+                // compressed entries can only be read sequentially
+                io::copy(&mut (&mut file).take(range.start), &mut io::sink())?;
+                read_exact_len(file, len, relative_path).map(Some)
+            }
+        }
+    }
+
+    /// Provides the length of a file in bytes by its path relative to the directory.
+    ///
+    /// Returns None if the file does not exist.
+    pub(crate) fn get_file_len(&self, relative_path: &str) -> Result<Option<u64>, SfError> {
+        match self {
+            DirectorySource::Directory(directory) => {
+                match fs::metadata(directory.join(relative_path)) {
+                    Ok(metadata) => Ok(Some(metadata.len())),
+                    Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+                    Err(e) => Err(e.into()),
+                }
+            }
+            DirectorySource::Zip(archive, prefix) => {
+                let mut archive = lock_input(archive);
+                match archive.by_name(&format!("{}{}", prefix, relative_path)) {
+                    Ok(file) => Ok(Some(file.size())),
+                    Err(ZipError::FileNotFound) => Ok(None),
+                    Err(e) => Err(map_zip_error(e)),
+                }
+            }
+        }
+    }
+
+    /// Checks whether a file exists by its path relative to the directory.
+    pub(crate) fn is_file(&self, relative_path: &str) -> bool {
+        match self {
+            DirectorySource::Directory(directory) => directory.join(relative_path).is_file(),
+            DirectorySource::Zip(archive, prefix) => lock_input(archive)
+                .index_for_name(&format!("{}{}", prefix, relative_path))
+                .is_some(),
+        }
+    }
+
+    /// Lists the names of the sub directories of a directory holding a file, e.g., the
+    /// processed data directories in "pdata" holding "procs".
+    ///
+    /// Numeric names are sorted numerically, followed by other names in lexical order.
+    pub(crate) fn list_sub_directories(
+        &self,
+        relative_path: &str,
+        file_name: &str,
+    ) -> Result<Vec<String>, SfError> {
+        let mut names = match self {
+            DirectorySource::Directory(directory) => {
+                match fs::read_dir(directory.join(relative_path)) {
+                    Ok(entries) => entries
+                        .filter_map(|entry| entry.ok())
+                        .filter(|entry| entry.path().join(file_name).is_file())
+                        .filter_map(|entry| entry.file_name().to_str().map(str::to_owned))
+                        .collect(),
+                    Err(e) if e.kind() == ErrorKind::NotFound => vec![],
+                    Err(e) => return Err(e.into()),
+                }
+            }
+            DirectorySource::Zip(archive, prefix) => {
+                let archive = lock_input(archive);
+                let directory_prefix = format!("{}{}/", prefix, relative_path);
+                archive
+                    .file_names()
+                    .filter_map(|name| name.strip_prefix(&directory_prefix))
+                    .filter_map(|name| {
+                        let (directory, file) = name.split_once('/')?;
+                        (file == file_name).then(|| directory.to_owned())
+                    })
+                    .collect::<Vec<String>>()
+            }
+        };
+        names.sort_by_key(|name| (name.parse::<u64>().unwrap_or(u64::MAX), name.clone()));
+        Ok(names)
+    }
+}
+
+/// Reads a data set directory in the file system by its path with the given parse options.
+type ReadDirectoryFn = fn(&str, &ParseOptions) -> Result<Box<dyn Reader>, SfError>;
+
+/// Reads a ZIP archive holding a data set directory by its name with the given parse options.
+type ReadZipFn = fn(&str, Box<dyn SeekRead>, &ParseOptions) -> Result<Box<dyn Reader>, SfError>;
+
+/// Recognizes data sets stored as directory, e.g., Bruker experiment directories, and provides
+/// readers for them.
+///
+/// Data sets are recognized as ZIP archives holding the directory. With the `read_directories`
+/// parse option set, they are also recognized as paths in the file system pointing to the
+/// directory or a file in it. For the latter, the input is ignored and the files are read from
+/// the directory.
+pub(crate) struct DirectoryScanner {
+    /// The file identifying the data set directory, e.g., "acqus".
+    pub marker_file: &'static str,
+    /// Finds the data set directory for a path pointing to the directory or a file in it.
+    pub find_directory: fn(&Path) -> Option<PathBuf>,
+    /// Reads a data set directory in the file system by its path.
+    pub read_directory: ReadDirectoryFn,
+    /// Reads a ZIP archive holding a data set directory.
+    pub read_zip: ReadZipFn,
+}
+
+impl DirectoryScanner {
+    const ACCEPTED_ZIP_EXTENSIONS: [&'static str; 1] = ["zip"];
+
+    fn is_recognized_directory(&self, path: &str, options: &ParseOptions) -> bool {
+        options.read_directories && (self.find_directory)(Path::new(path)).is_some()
+    }
+
+    pub(crate) fn is_recognized<T: SeekRead>(
+        &self,
+        path: &str,
+        input: &mut T,
+        options: &ParseOptions,
+    ) -> bool {
+        self.is_recognized_directory(path, options)
+            || is_recognized_extension(path, &Self::ACCEPTED_ZIP_EXTENSIONS)
+                && is_zip_with_file(input, self.marker_file)
+    }
+
+    pub(crate) fn get_confidence<T: SeekRead>(
+        &self,
+        path: &str,
+        input: &mut T,
+        options: &ParseOptions,
+    ) -> Option<Confidence> {
+        if self.is_recognized_directory(path, options) {
+            return Some(Confidence::High);
+        }
+        determine_confidence(
+            is_recognized_extension(path, &Self::ACCEPTED_ZIP_EXTENSIONS),
+            is_zip_with_file(input, self.marker_file),
+        )
+    }
+
+    pub(crate) fn get_reader<T: SeekRead + 'static>(
+        &self,
+        path: &str,
+        input: T,
+        options: &ParseOptions,
+    ) -> Result<Box<dyn Reader>, SfError> {
+        if self.is_recognized_directory(path, options) {
+            (self.read_directory)(path, options)
+        } else {
+            (self.read_zip)(path, Box::new(input), options)
+        }
+    }
+}
+
+/// Finds the path prefix of the directory with the shortest path holding a marker file in a
+/// ZIP archive, e.g., "sample/1/" for an entry "sample/1/acqus".
+pub(crate) fn find_zip_prefix<R: Read + Seek>(
+    archive: &ZipArchive<R>,
+    marker_file: &str,
+) -> Option<String> {
+    archive
+        .file_names()
+        .filter_map(|name| {
+            let file_name = name.rsplit('/').next()?;
+            (file_name == marker_file).then(|| name[..name.len() - file_name.len()].to_owned())
+        })
+        .min_by_key(|prefix| (prefix.matches('/').count(), prefix.clone()))
+}
+
+/// Checks whether an input is a ZIP archive holding a marker file.
+///
+/// The input is rewound before and after the check.
+pub(crate) fn is_zip_with_file<T: SeekRead>(input: &mut T, marker_file: &str) -> bool {
+    if input.seek(std::io::SeekFrom::Start(0)).is_err() {
+        return false;
+    }
+    let recognized = match ZipArchive::new(&mut *input) {
+        Err(_) => false,
+        Ok(archive) => find_zip_prefix(&archive, marker_file).is_some(),
+    };
+    let _ = input.seek(std::io::SeekFrom::Start(0));
+    recognized
+}
+
+/// Reads a number of bytes, failing if the input ends before.
+///
+/// The bytes are not allocated up front so that lengths from untrusted headers cannot
+/// exhaust memory.
+fn read_exact_len(input: impl Read, len: u64, relative_path: &str) -> Result<Vec<u8>, SfError> {
+    let mut bytes = vec![];
+    input.take(len).read_to_end(&mut bytes)?;
+    if (bytes.len() as u64) < len {
+        return Err(SfError::new(&format!(
+            "Unexpected end of file reading {} bytes: {}",
+            len, relative_path
+        ))
+        .with_kind(SfErrorKind::CorruptData));
+    }
+    Ok(bytes)
+}
+
+fn map_zip_error(error: ZipError) -> SfError {
+    SfError::from_source(error, "Error reading ZIP archive.").with_kind(SfErrorKind::CorruptData)
+}
+
+/// Creates a ZIP archive holding the entries, rewound to the start.
+#[cfg(test)]
+pub(crate) fn create_zip(entries: &[(&str, &[u8])]) -> std::io::Cursor<Vec<u8>> {
+    use std::io::Write;
+    use zip::{ZipWriter, write::SimpleFileOptions};
+
+    let mut writer = ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    for (name, content) in entries {
+        writer
+            .start_file(*name, SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(content).unwrap();
+    }
+    let mut cursor = writer.finish().unwrap();
+    cursor.set_position(0);
+    cursor
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn reads_files_relative_to_shortest_marker_directory() {
+        let zip = create_zip(&[
+            ("a/b/c/marker", b"deep"),
+            ("a/b/marker", b"shallow"),
+            ("a/b/file", b"content"),
+            ("a/b/sub/2/file", b""),
+            ("a/b/sub/10/file", b""),
+            ("a/b/sub/x/file", b""),
+            ("a/b/sub/1/other", b""),
+        ]);
+
+        let source = DirectorySource::open_zip(Box::new(zip), "marker")
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            Some(b"shallow".to_vec()),
+            source.read_file("marker").unwrap()
+        );
+        assert_eq!(Some(b"content".to_vec()), source.read_file("file").unwrap());
+        assert_eq!(
+            Some(b"nte".to_vec()),
+            source.read_file_range("file", 2..5).unwrap()
+        );
+        assert_eq!(None, source.read_file_range("missing", 0..1).unwrap());
+        assert_eq!(
+            SfErrorKind::CorruptData,
+            source.read_file_range("file", 5..8).unwrap_err().kind()
+        );
+        assert_eq!(Some(7), source.get_file_len("file").unwrap());
+        assert_eq!(None, source.get_file_len("missing").unwrap());
+        assert_eq!(None, source.read_file("missing").unwrap());
+        assert!(source.is_file("file"));
+        assert!(!source.is_file("sub"));
+        assert_eq!(
+            vec!["2", "10", "x"],
+            source.list_sub_directories("sub", "file").unwrap()
+        );
+        assert!(
+            source
+                .list_sub_directories("missing", "file")
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn recognizes_zip_with_marker_file() {
+        let mut zip = create_zip(&[("a/marker", b"")]);
+        assert!(is_zip_with_file(&mut zip, "marker"));
+        assert_eq!(0, zip.position());
+        assert!(!is_zip_with_file(&mut zip, "other"));
+        assert!(!is_zip_with_file(&mut Cursor::new(b"no ZIP"), "marker"));
+
+        let zip = create_zip(&[("a/other", b"")]);
+        assert!(
+            DirectorySource::open_zip(Box::new(zip), "marker")
+                .unwrap()
+                .is_none()
+        );
+        assert!(DirectorySource::open_zip(Box::new(Cursor::new(b"no ZIP")), "marker").is_err());
+    }
+}
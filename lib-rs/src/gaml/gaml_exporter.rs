@@ -0,0 +1,703 @@
+// Copyright (c) 2025 Robert Schiwon
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use super::gaml_parser::{Byteorder, Format, Technique, Units, Version};
+use crate::{
+    api::{Exporter, Node, Parameter, Reader, Table, Value},
+    common::SfError,
+    utils::convert_value_to_f64,
+};
+use base64::prelude::*;
+use std::{
+    io::{BufWriter, Write},
+    str::FromStr,
+};
+
+const INDENT: &str = "  ";
+
+/// Exports all nodes as GAML 1.20.
+///
+/// The root node's name and parameters are written as the name and parameters of the GAML
+/// element. Each node holding xy data or a peak table is written in depth-first order as an
+/// experiment with a single trace named after the node and holding:
+/// - The node's parameters as experiment parameters.
+/// - The technique of the nearest "Technique" parameter of the node or its ancestors, UNKNOWN
+///   if there is none.
+/// - The data as Xdata and Ydata with FLOAT64 values in INTEL byte order and the units mapped
+///   from the "x.unit" and "y.unit" metadata.
+/// - The node's peak table and the peak tables of its child nodes without data as peaktables
+///   of the Ydata. Child nodes of these peak table nodes are not written.
+///
+/// Nodes holding only array or complex data are not written.
+pub struct GamlExporter<'a, R: Reader + ?Sized> {
+    reader: &'a R,
+}
+
+impl<'a, R: Reader + ?Sized> GamlExporter<'a, R> {
+    const VERSION: Version = Version::Version1_20;
+    /// Column keys of peak tables holding peak positions and intensities.
+    const PEAK_XY_KEYS: [(&'static str, &'static str); 3] = [
+        ("peak_x_value", "peak_y_value"),
+        ("x", "y"),
+        ("peak_retention_time", "peak_height"),
+    ];
+    /// Column keys of peak tables holding the baseline start and end points.
+    const BASELINE_KEYS: [&'static str; 4] = [
+        "baseline_start_x_value",
+        "baseline_start_y_value",
+        "baseline_end_x_value",
+        "baseline_end_y_value",
+    ];
+
+    pub fn new(reader: &'a R) -> Self {
+        Self { reader }
+    }
+
+    fn write_experiments(
+        &self,
+        writer: &mut dyn Write,
+        path: &str,
+        node: &Node,
+        technique: &str,
+        is_root: bool,
+    ) -> Result<(), SfError> {
+        let technique = Self::find_technique(node).unwrap_or_else(|| technique.to_owned());
+        let is_experiment = !node.data.is_empty() || Self::find_peak_xy_keys(node).is_some();
+        if !is_experiment {
+            for i in 0..node.child_node_names.len() {
+                let child_path = format!("{}/{}", path, i);
+                let child = self.reader.read(&child_path)?;
+                self.write_experiments(writer, &child_path, &child, &technique, false)?;
+            }
+            return Ok(());
+        }
+
+        // peak tables of child nodes are part of the experiment, so other child nodes
+        // are only read again, by path, after it has been written
+        let mut peaktable_nodes = vec![];
+        let mut other_child_paths = vec![];
+        for i in 0..node.child_node_names.len() {
+            let child_path = format!("{}/{}", path, i);
+            let child = self.reader.read(&child_path)?;
+            if child.data.is_empty() && Self::find_peak_xy_keys(&child).is_some() {
+                peaktable_nodes.push(child);
+            } else {
+                other_child_paths.push(child_path);
+            }
+        }
+        // the root node's parameters are written as GAML parameters
+        let parameters: &[Parameter] = match is_root {
+            true => &[],
+            false => &node.parameters,
+        };
+        Self::write_experiment(writer, node, parameters, &technique, &peaktable_nodes)?;
+        for child_path in &other_child_paths {
+            let child = self.reader.read(child_path)?;
+            self.write_experiments(writer, child_path, &child, &technique, false)?;
+        }
+        Ok(())
+    }
+
+    fn find_technique(node: &Node) -> Option<String> {
+        node.parameters
+            .iter()
+            .find_map(|parameter| match parameter {
+                Parameter::KeyValue(key, value) if key == "Technique" => {
+                    Technique::from_str(value.to_string().trim().to_uppercase().as_str())
+                        .ok()
+                        .map(|technique| technique.to_string())
+                }
+                _ => None,
+            })
+    }
+
+    fn find_metadata<'n>(node: &'n Node, key: &str) -> Option<&'n str> {
+        node.metadata
+            .iter()
+            .find(|(k, _v)| k == key)
+            .map(|(_k, v)| v.as_str())
+    }
+
+    fn find_peak_xy_keys(node: &Node) -> Option<(&'static str, &'static str)> {
+        let table = node.table.as_ref()?;
+        let has_key = |key: &str| table.column_names.iter().any(|c| c.key == key);
+        Self::PEAK_XY_KEYS
+            .into_iter()
+            .find(|(x_key, y_key)| has_key(x_key) && has_key(y_key))
+    }
+
+    /// Maps a unit to GAML units, UNKNOWN if there is no matching GAML unit.
+    fn map_units(unit: Option<&str>) -> Units {
+        let unit = match unit {
+            None => return Units::Unknown,
+            Some(unit) => unit.trim().to_uppercase(),
+        };
+        if let Ok(units) = Units::from_str(&unit) {
+            return units;
+        }
+        match unit.as_str() {
+            "1/CM" | "CM-1" | "CM^-1" => Units::Wavenumber,
+            "HZ" => Units::Hertz,
+            "KHZ" => Units::Kilohertz,
+            "MHZ" => Units::Megahertz,
+            "GHZ" => Units::Ghertz,
+            "S" | "SEC" => Units::Seconds,
+            "MS" => Units::Milliseconds,
+            "MIN" => Units::Minutes,
+            "NM" => Units::Nanometers,
+            "MICROMETERS" | "UM" => Units::Microns,
+            "M/Z" => Units::Masschargeratio,
+            "MAU" => Units::Milliabsorbance,
+            "MV" => Units::Millivolts,
+            "V" => Units::Volts,
+            "%" => Units::Percent,
+            _ => Units::Unknown,
+        }
+    }
+
+    fn write_experiment(
+        writer: &mut dyn Write,
+        node: &Node,
+        parameters: &[Parameter],
+        technique: &str,
+        peaktable_nodes: &[Node],
+    ) -> Result<(), SfError> {
+        write_line(
+            writer,
+            1,
+            &format!("<experiment name=\"{}\">", escape(&node.name)),
+        )?;
+        write_parameters(writer, 2, parameters)?;
+        write_line(
+            writer,
+            2,
+            &format!("<trace technique=\"{}\">", escape(technique)),
+        )?;
+
+        let x_units = Self::map_units(Self::find_metadata(node, "x.unit"));
+        let x_label = Self::find_metadata(node, "x.label");
+        write_line(
+            writer,
+            3,
+            &format!("<Xdata{}>", format_xy_attributes(&x_units, x_label)),
+        )?;
+        write_values(writer, 4, node.data.iter().map(|p| p.x))?;
+
+        let y_units = Self::map_units(Self::find_metadata(node, "y.unit"));
+        let y_label = Self::find_metadata(node, "y.label");
+        write_line(
+            writer,
+            4,
+            &format!("<Ydata{}>", format_xy_attributes(&y_units, y_label)),
+        )?;
+        write_values(writer, 5, node.data.iter().map(|p| p.y))?;
+        if let (Some(table), Some(keys)) = (&node.table, Self::find_peak_xy_keys(node)) {
+            Self::write_peaktable(writer, None, &[], table, keys)?;
+        }
+        for peaktable_node in peaktable_nodes {
+            if let (Some(table), Some(keys)) = (
+                &peaktable_node.table,
+                Self::find_peak_xy_keys(peaktable_node),
+            ) {
+                Self::write_peaktable(
+                    writer,
+                    Some(&peaktable_node.name),
+                    &peaktable_node.parameters,
+                    table,
+                    keys,
+                )?;
+            }
+        }
+        write_line(writer, 4, "</Ydata>")?;
+
+        write_line(writer, 3, "</Xdata>")?;
+        write_line(writer, 2, "</trace>")?;
+        write_line(writer, 1, "</experiment>")
+    }
+
+    fn write_peaktable(
+        writer: &mut dyn Write,
+        name: Option<&str>,
+        parameters: &[Parameter],
+        table: &Table,
+        (x_key, y_key): (&str, &str),
+    ) -> Result<(), SfError> {
+        match name {
+            None => write_line(writer, 5, "<peaktable>")?,
+            Some(name) => write_line(writer, 5, &format!("<peaktable name=\"{}\">", escape(name)))?,
+        }
+        write_parameters(writer, 6, parameters)?;
+        for (i, row) in table.rows.iter().enumerate() {
+            let get_f64 = |key: &str| row.get(key).and_then(convert_value_to_f64);
+            let (Some(x), Some(y)) = (get_f64(x_key), get_f64(y_key)) else {
+                // peak position and intensity are required
+                continue;
+            };
+            let number = get_f64("number")
+                .filter(|n| *n >= 1.0 && n.fract() == 0.0)
+                .map_or(i as u64 + 1, |n| n as u64);
+            let mut start_tag = format!("<peak number=\"{}\"", number);
+            for key in ["group", "name"] {
+                if let Some(Value::String(value)) = row.get(key) {
+                    start_tag += &format!(" {}=\"{}\"", key, escape(value));
+                }
+            }
+            start_tag += ">";
+            write_line(writer, 6, &start_tag)?;
+            write_element(writer, 7, "peakXvalue", &format_value(x))?;
+            write_element(writer, 7, "peakYvalue", &format_value(y))?;
+            let baseline: Option<Vec<f64>> =
+                Self::BASELINE_KEYS.iter().map(|key| get_f64(key)).collect();
+            if let Some(baseline) = baseline {
+                write_line(writer, 7, "<baseline>")?;
+                for (tag, value) in ["startXvalue", "startYvalue", "endXvalue", "endYvalue"]
+                    .iter()
+                    .zip(baseline)
+                {
+                    write_element(writer, 8, tag, &format_value(value))?;
+                }
+                write_line(writer, 7, "</baseline>")?;
+            }
+            write_line(writer, 6, "</peak>")?;
+        }
+        write_line(writer, 5, "</peaktable>")
+    }
+}
+
+impl<R: Reader + ?Sized> Exporter for GamlExporter<'_, R> {
+    fn get_name(&self) -> &'static str {
+        "GAML Exporter"
+    }
+
+    fn write(&mut self, writer: &mut dyn Write) -> Result<(), SfError> {
+        let mut writer = BufWriter::new(writer);
+        let root = self.reader.read("")?;
+
+        write_line(&mut writer, 0, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        write_line(
+            &mut writer,
+            0,
+            &format!(
+                "<GAML version=\"{}\" name=\"{}\">",
+                Self::VERSION,
+                escape(&root.name)
+            ),
+        )?;
+        write_parameters(&mut writer, 1, &root.parameters)?;
+        let technique = Technique::Unknown.to_string();
+        self.write_experiments(&mut writer, "", &root, &technique, true)?;
+        write_line(&mut writer, 0, "</GAML>")?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Writes parameters, using an empty name for parameters without key.
+fn write_parameters(
+    writer: &mut dyn Write,
+    indent: usize,
+    parameters: &[Parameter],
+) -> Result<(), SfError> {
+    for parameter in parameters {
+        let (name, value) = match parameter {
+            Parameter::KeyValue(key, value) => (key.as_str(), value),
+            Parameter::Value(value) => ("", value),
+        };
+        write_line(
+            writer,
+            indent,
+            &format!(
+                "<parameter name=\"{}\">{}</parameter>",
+                escape(name),
+                escape(&value.to_string())
+            ),
+        )?;
+    }
+    Ok(())
+}
+
+/// Writes values as base64 encoded FLOAT64 values in INTEL (little endian) byte order.
+fn write_values(
+    writer: &mut dyn Write,
+    indent: usize,
+    values: impl ExactSizeIterator<Item = f64>,
+) -> Result<(), SfError> {
+    let num_values = values.len();
+    let bytes: Vec<u8> = values.flat_map(|v| v.to_le_bytes()).collect();
+    write_line(
+        writer,
+        indent,
+        &format!(
+            "<values byteorder=\"{}\" format=\"{}\" numvalues=\"{}\">{}</values>",
+            Byteorder::Intel,
+            Format::Float64,
+            num_values,
+            BASE64_STANDARD.encode(bytes)
+        ),
+    )
+}
+
+fn write_element(
+    writer: &mut dyn Write,
+    indent: usize,
+    tag: &str,
+    value: &str,
+) -> Result<(), SfError> {
+    write_line(writer, indent, &format!("<{tag}>{value}</{tag}>"))
+}
+
+fn write_line(writer: &mut dyn Write, indent: usize, line: &str) -> Result<(), SfError> {
+    for _ in 0..indent {
+        writer.write_all(INDENT.as_bytes())?;
+    }
+    writer.write_all(line.as_bytes())?;
+    writer.write_all(b"\n")?;
+    Ok(())
+}
+
+fn format_xy_attributes(units: &Units, label: Option<&str>) -> String {
+    match label {
+        None => format!(" units=\"{}\"", units),
+        Some(label) => format!(" units=\"{}\" label=\"{}\"", units, escape(label)),
+    }
+}
+
+/// Formats a value as xs:double.
+fn format_value(value: f64) -> String {
+    match value {
+        v if v.is_nan() => "NaN".to_owned(),
+        f64::INFINITY => "INF".to_owned(),
+        f64::NEG_INFINITY => "-INF".to_owned(),
+        v => v.to_string(),
+    }
+}
+
+/// Escapes XML markup characters, removing characters not allowed in XML 1.0.
+fn escape(value: &str) -> String {
+    let value: String = value
+        .chars()
+        .filter(|c| !c.is_control() || ['\t', '\n', '\r'].contains(c))
+        .collect();
+    quick_xml::escape::escape(value.as_str()).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        api::{Axis, Column, ExportFormat, NdArray, Parser, PointXy},
+        gaml::{
+            gaml_parser::{Gaml, GamlParser},
+            gaml_reader::GamlReader,
+        },
+    };
+    use std::{collections::HashMap, io::Cursor};
+
+    struct StubReader {}
+
+    impl Reader for StubReader {
+        fn read(&self, path: &str) -> Result<Node, SfError> {
+            let empty_node = |name: &str| Node {
+                name: name.to_owned(),
+                parameters: vec![],
+                data: vec![],
+                metadata: vec![],
+                table: None,
+                nd_array: None,
+                complex_data: None,
+                links: vec![],
+                child_node_names: vec![],
+            };
+            match path {
+                "" | "/" => Ok(Node {
+                    parameters: vec![
+                        Parameter::from_str_str("Technique", "ir"),
+                        Parameter::from_str_str("Operator", "<A & B>"),
+                        Parameter::from_f64(1.5),
+                    ],
+                    child_node_names: vec![
+                        "spectrum".to_owned(),
+                        "group".to_owned(),
+                        "array".to_owned(),
+                    ],
+                    ..empty_node("root \"name\"")
+                }),
+                "/0" => Ok(Node {
+                    parameters: vec![Parameter::from_str_str("Resolution", "4")],
+                    data: vec![
+                        PointXy::new(4000.0, 0.25),
+                        PointXy::new(3000.0, f64::NAN),
+                        PointXy::new(2000.0, -1.5),
+                    ],
+                    metadata: vec![
+                        ("x.unit".to_owned(), "1/CM".to_owned()),
+                        ("x.label".to_owned(), "Wavenumber".to_owned()),
+                        ("y.unit".to_owned(), "absorbance".to_owned()),
+                    ],
+                    child_node_names: vec!["peaks".to_owned()],
+                    ..empty_node("spectrum")
+                }),
+                "/0/0" => Ok(Node {
+                    parameters: vec![Parameter::from_str_str("Threshold", "0.1")],
+                    table: Some(Table {
+                        column_names: vec![
+                            Column::new("x", "Peak Position"),
+                            Column::new("y", "Intensity"),
+                            Column::new("name", "Name"),
+                        ],
+                        rows: vec![
+                            HashMap::from([
+                                ("x".to_owned(), Value::F64(4000.0)),
+                                ("y".to_owned(), Value::F64(0.25)),
+                                ("name".to_owned(), Value::String("OH".to_owned())),
+                            ]),
+                            HashMap::from([("x".to_owned(), Value::F64(3000.0))]),
+                            HashMap::from([
+                                ("x".to_owned(), Value::F64(2000.0)),
+                                ("y".to_owned(), Value::String("-1.5".to_owned())),
+                            ]),
+                        ],
+                    }),
+                    child_node_names: vec!["not written".to_owned()],
+                    ..empty_node("peaks")
+                }),
+                "/1" => Ok(Node {
+                    parameters: vec![Parameter::from_str_str("Technique", "CHROM")],
+                    child_node_names: vec!["chromatogram".to_owned()],
+                    ..empty_node("group")
+                }),
+                "/1/0" => Ok(Node {
+                    data: vec![PointXy::new(0.0, 1.0), PointXy::new(0.5, 2.0)],
+                    metadata: vec![
+                        ("x.unit".to_owned(), "MIN".to_owned()),
+                        ("y.unit".to_owned(), "counts".to_owned()),
+                    ],
+                    ..empty_node("chromatogram")
+                }),
+                "/2" => Ok(Node {
+                    nd_array: Some(NdArray::new(
+                        "intensity",
+                        None,
+                        vec![Axis::new("x", None, vec![1.0, 2.0])],
+                        vec![1.0, 2.0],
+                    )?),
+                    ..empty_node("array")
+                }),
+                _ => Err(SfError::new(&format!("Illegal path: {}", path))),
+            }
+        }
+    }
+
+    fn export_and_parse(reader: &impl Reader) -> (String, Gaml) {
+        let mut export = vec![];
+        reader.export(ExportFormat::Gaml, &mut export).unwrap();
+        let gaml = GamlParser::parse("export.gaml", Cursor::new(export.clone())).unwrap();
+        (String::from_utf8(export).unwrap(), gaml)
+    }
+
+    #[test]
+    fn maps_units() {
+        type E<'a> = GamlExporter<'a, StubReader>;
+        assert_eq!(Units::Ppm, E::map_units(Some("PPM")));
+        assert_eq!(Units::Minutes, E::map_units(Some(" minutes ")));
+        assert_eq!(Units::Wavenumber, E::map_units(Some("1/CM")));
+        assert_eq!(Units::Hertz, E::map_units(Some("Hz")));
+        assert_eq!(Units::Masschargeratio, E::map_units(Some("M/Z")));
+        assert_eq!(Units::Unknown, E::map_units(Some("ARBITRARY UNITS")));
+        assert_eq!(Units::Unknown, E::map_units(None));
+    }
+
+    #[test]
+    fn exports_node_tree_as_experiments() {
+        let reader = StubReader {};
+        let (export, gaml) = export_and_parse(&reader);
+
+        assert!(export.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n"));
+        assert!(export.contains("<parameter name=\"Operator\">&lt;A &amp; B&gt;</parameter>"));
+        assert_eq!(Version::Version1_20, gaml.version);
+        assert_eq!(Some("root \"name\"".to_owned()), gaml.name);
+        assert!(gaml.integrity.is_none());
+        assert_eq!(3, gaml.parameters.len());
+        assert_eq!("Operator", gaml.parameters[1].name);
+        assert_eq!(Some("<A & B>".to_owned()), gaml.parameters[1].value);
+        assert_eq!("", gaml.parameters[2].name);
+        assert_eq!(Some("1.5".to_owned()), gaml.parameters[2].value);
+
+        // array node and peak table node are not written as experiments
+        assert_eq!(2, gaml.experiments.len());
+
+        let spectrum = &gaml.experiments[0];
+        assert_eq!(Some("spectrum".to_owned()), spectrum.name);
+        assert_eq!(1, spectrum.parameters.len());
+        assert_eq!("Resolution", spectrum.parameters[0].name);
+        assert_eq!(1, spectrum.traces.len());
+        let trace = &spectrum.traces[0];
+        assert_eq!(Technique::Ir, trace.technique);
+        assert_eq!(1, trace.x_data.len());
+        let x_data = &trace.x_data[0];
+        assert_eq!(Units::Wavenumber, x_data.units);
+        assert_eq!(Some("Wavenumber".to_owned()), x_data.label);
+        assert_eq!(Format::Float64, x_data.values.format);
+        assert_eq!(Byteorder::Intel, x_data.values.byteorder);
+        assert_eq!(Some(3), x_data.values.numvalues);
+        assert_eq!(
+            vec![4000.0, 3000.0, 2000.0],
+            x_data.values.get_data().unwrap()
+        );
+        assert_eq!(1, x_data.y_data.len());
+        let y_data = &x_data.y_data[0];
+        assert_eq!(Units::Absorbance, y_data.units);
+        assert_eq!(None, y_data.label);
+        let y_values = y_data.values.get_data().unwrap();
+        assert_eq!(0.25, y_values[0]);
+        assert!(y_values[1].is_nan());
+        assert_eq!(-1.5, y_values[2]);
+
+        assert_eq!(1, y_data.peaktables.len());
+        let peaktable = &y_data.peaktables[0];
+        assert_eq!(Some("peaks".to_owned()), peaktable.name);
+        assert_eq!("Threshold", peaktable.parameters[0].name);
+        // row without intensity is skipped
+        assert_eq!(2, peaktable.peaks.len());
+        assert_eq!(1, peaktable.peaks[0].number);
+        assert_eq!(Some("OH".to_owned()), peaktable.peaks[0].name);
+        assert_eq!(4000.0, peaktable.peaks[0].peak_x_value);
+        assert_eq!(0.25, peaktable.peaks[0].peak_y_value);
+        assert_eq!(3, peaktable.peaks[1].number);
+        assert_eq!(2000.0, peaktable.peaks[1].peak_x_value);
+        assert_eq!(-1.5, peaktable.peaks[1].peak_y_value);
+        assert!(peaktable.peaks[1].baseline.is_none());
+
+        let chromatogram = &gaml.experiments[1];
+        assert_eq!(Some("chromatogram".to_owned()), chromatogram.name);
+        assert!(chromatogram.parameters.is_empty());
+        let trace = &chromatogram.traces[0];
+        assert_eq!(Technique::Chrom, trace.technique);
+        assert_eq!(Units::Minutes, trace.x_data[0].units);
+        assert_eq!(Units::Unknown, trace.x_data[0].y_data[0].units);
+        assert_eq!(vec![0.0, 0.5], trace.x_data[0].values.get_data().unwrap());
+        assert_eq!(
+            vec![1.0, 2.0],
+            trace.x_data[0].y_data[0].values.get_data().unwrap()
+        );
+    }
+
+    #[test]
+    fn exports_root_node_with_data_as_experiment() {
+        struct SingleNodeReader {}
+        impl Reader for SingleNodeReader {
+            fn read(&self, _path: &str) -> Result<Node, SfError> {
+                Ok(Node {
+                    name: "single".to_owned(),
+                    parameters: vec![Parameter::from_str_str("key", "value")],
+                    data: vec![PointXy::new(1.0, 2.0)],
+                    metadata: vec![],
+                    table: None,
+                    nd_array: None,
+                    complex_data: None,
+                    links: vec![],
+                    child_node_names: vec![],
+                })
+            }
+        }
+
+        let reader = SingleNodeReader {};
+        let (_export, gaml) = export_and_parse(&reader);
+
+        assert_eq!(1, gaml.parameters.len());
+        assert_eq!(1, gaml.experiments.len());
+        let experiment = &gaml.experiments[0];
+        // root parameters are only written as GAML parameters
+        assert!(experiment.parameters.is_empty());
+        assert_eq!(Technique::Unknown, experiment.traces[0].technique);
+        assert_eq!(
+            vec![1.0],
+            experiment.traces[0].x_data[0].values.get_data().unwrap()
+        );
+    }
+
+    #[test]
+    fn round_trips_peaktable_with_baseline() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<GAML version="1.20" name="Peaks">
+  <experiment name="Experiment">
+    <trace name="Trace" technique="CHROM">
+      <Xdata units="SECONDS" label="Time">
+        <values byteorder="INTEL" format="FLOAT32" numvalues="2">AACAPwAAAEA=</values>
+        <Ydata units="MILLIVOLTS">
+          <values byteorder="INTEL" format="FLOAT32" numvalues="2">AABAQAAAgEA=</values>
+          <peaktable name="Peak table">
+            <peak number="7" group="A" name="Peak 7">
+              <peakXvalue>1.5</peakXvalue>
+              <peakYvalue>3.5</peakYvalue>
+              <baseline>
+                <startXvalue>1</startXvalue>
+                <startYvalue>0.1</startYvalue>
+                <endXvalue>2</endXvalue>
+                <endYvalue>INF</endYvalue>
+              </baseline>
+            </peak>
+          </peaktable>
+        </Ydata>
+      </Xdata>
+    </trace>
+  </experiment>
+</GAML>"#;
+        let gaml = GamlParser::parse("peaks.gaml", Cursor::new(xml.as_bytes().to_vec())).unwrap();
+        let reader = GamlReader::new("peaks.gaml", gaml);
+        let (_export, gaml) = export_and_parse(&reader);
+
+        assert_eq!(Some("peaks.gaml".to_owned()), gaml.name);
+        assert_eq!(1, gaml.experiments.len());
+        let trace = &gaml.experiments[0].traces[0];
+        assert_eq!(Technique::Chrom, trace.technique);
+        let x_data = &trace.x_data[0];
+        assert_eq!(Units::Seconds, x_data.units);
+        assert_eq!(Some("Time".to_owned()), x_data.label);
+        assert_eq!(vec![1.0, 2.0], x_data.values.get_data().unwrap());
+        let y_data = &x_data.y_data[0];
+        assert_eq!(Units::Millivolts, y_data.units);
+        assert_eq!(vec![3.0, 4.0], y_data.values.get_data().unwrap());
+        let peak = &y_data.peaktables[0].peaks[0];
+        assert_eq!(7, peak.number);
+        assert_eq!(Some("A".to_owned()), peak.group);
+        assert_eq!(Some("Peak 7".to_owned()), peak.name);
+        assert_eq!(1.5, peak.peak_x_value);
+        assert_eq!(3.5, peak.peak_y_value);
+        let baseline = peak.baseline.as_ref().unwrap();
+        assert_eq!(1.0, baseline.start_x_value);
+        assert_eq!(0.1, baseline.start_y_value);
+        assert_eq!(2.0, baseline.end_x_value);
+        assert_eq!(f64::INFINITY, baseline.end_y_value);
+    }
+
+    #[test]
+    fn export_fails_for_unreadable_node() {
+        struct FailingReader {}
+        impl Reader for FailingReader {
+            fn read(&self, _path: &str) -> Result<Node, SfError> {
+                Err(SfError::new("Error"))
+            }
+        }
+
+        let reader = FailingReader {};
+        let mut export = vec![];
+        assert!(reader.export(ExportFormat::Gaml, &mut export).is_err());
+    }
+}
@@ -17,35 +17,65 @@
 // IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
 // CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
-use crate::api::{Parser, SeekBufRead};
-use crate::common::SfError;
+use crate::api::{ParseMode, ParseOptions, ParseWarning, Parser, SeekBufRead, SeekRead};
+use crate::common::{SfError, SfErrorKind, SfErrorLocation, WarningCollector};
+use crate::utils::{check_data_range, lock_input};
 use crate::xml_utils::{
-    BufEvent, XmlTagStart, consume_end, consume_end_rc, next_non_whitespace, read_empty,
-    read_next_event, read_opt_elem, read_opt_elem_rc, read_req_elem_rc, read_req_elem_value_f64,
-    read_sequence, read_sequence_rc, read_start, read_start_or_empty, read_value, read_value_pos,
-    skip_whitespace, skip_xml_decl,
+    BufEvent, XmlTagStart, consume_end, consume_end_rc, get_opt_attr, next_non_whitespace,
+    read_empty, read_next_event, read_opt_elem, read_opt_elem_rc, read_req_elem_rc,
+    read_req_elem_value_f64, read_sequence, read_sequence_rc, read_start, read_start_or_empty,
+    read_value, read_value_pos, skip_whitespace, skip_xml_decl,
 };
 use base64::prelude::*;
 use chrono::{DateTime, SecondsFormat};
+use md5::Md5;
+use quick_xml::events::{BytesStart, Event};
 use quick_xml::reader::Reader;
-use std::cell::RefCell;
+use sha1::Sha1;
+use sha2::{Sha256, digest::DynDigest};
 use std::fmt::Debug;
-use std::io::{BufRead, BufReader, Cursor, Read, Seek, SeekFrom};
-use std::rc::Rc;
+use std::io::{self, BufRead, BufReader, Cursor, ErrorKind, Read, Seek, SeekFrom};
+use std::ops::Range;
 use std::str::{self, FromStr};
+use std::sync::{Arc, Mutex};
 use strum::{Display, EnumString};
 
 pub struct GamlParser {}
 
-impl<T: Seek + Read + 'static> Parser<T> for GamlParser {
+impl<T: SeekRead + 'static> Parser<T> for GamlParser {
     type R = Gaml;
     type E = SfError;
 
     fn parse(name: &str, input: T) -> Result<Self::R, Self::E> {
+        Self::parse_with_options(name, input, &ParseOptions::default())
+    }
+}
+
+impl GamlParser {
+    /// Parses GAML, checking the data against the specification as set in the options.
+    // the input is only Send with the "sync" feature enabled
+    #[allow(clippy::arc_with_non_send_sync)]
+    pub fn parse_with_options<T: SeekRead + 'static>(
+        name: &str,
+        input: T,
+        options: &ParseOptions,
+    ) -> Result<Gaml, SfError> {
         let buf_reader: Box<dyn SeekBufRead> = Box::new(BufReader::new(input));
         let reader = Reader::from_reader(buf_reader);
-        let reader_ref = Rc::new(RefCell::new(reader));
-        Self::R::new(name, reader_ref)
+        let reader_ref = Arc::new(Mutex::new(reader));
+        let mut gaml = Gaml::new(name, reader_ref, options.on_demand)
+            .map_err(|e| e.or_kind(SfErrorKind::CorruptData))?;
+        if options.strict_integrity {
+            gaml.check_integrity()?;
+        }
+        if let Some(index) = &mut gaml.index {
+            index.strict_conformance =
+                gaml.version == Version::Version1_00 && options.mode == ParseMode::Strict;
+        }
+        let mut collector = WarningCollector::new(options);
+        gaml.check_conformance(&mut collector);
+        gaml.warnings = collector.into_warnings()?;
+        Ok(gaml)
     }
 }
 
@@ -57,7 +87,12 @@ pub struct Gaml {
     // Elements
     pub integrity: Option<Integrity>,
     pub parameters: Vec<Parameter>,
+    // Empty if parsed on demand
     pub experiments: Vec<Experiment>,
+    // Positions of the experiments and traces if parsed on demand
+    pub index: Option<GamlIndex>,
+    // Deviations from the specification accepted while parsing
+    pub warnings: Vec<ParseWarning>,
 }
 
 impl Gaml {
@@ -65,9 +100,10 @@ impl Gaml {
 
     fn new(
         _name: &str,
-        reader_ref: Rc<RefCell<Reader<Box<dyn SeekBufRead>>>>,
+        reader_ref: Arc<Mutex<Reader<Box<dyn SeekBufRead>>>>,
+        on_demand: bool,
     ) -> Result<Self, SfError> {
-        let mut reader = reader_ref.borrow_mut();
+        let mut reader = lock_input(&reader_ref);
         let mut buf = Vec::new();
 
         // skip <?xml> element if present
@@ -75,30 +111,65 @@ impl Gaml {
 
         // attributes
         let start = read_start(Self::TAG, &reader, &next)?;
+        // errors inside the root element are located relative to it
+        let in_root = |e: SfError| e.prepend_element_path("GAML");
         // In GAML 1.10 version was turned into an enum. However, it was not updated for 1.20 even though 1.20 is used in multiple examples.
         // Versions 1.10 and 1.20 allow more flexibility. There should be no harm in also allowing this flexibility also in previous versions.
-        let version = start.parse_req_attr(
-            "version",
-            &Version::from_str,
-            str::from_utf8(Self::TAG).unwrap_or_default(),
-        )?;
+        let version = start
+            .parse_req_attr(
+                "version",
+                &Version::from_str,
+                str::from_utf8(Self::TAG).unwrap_or_default(),
+            )
+            .map_err(in_root)?;
         let name = start.get_opt_attr("name");
 
         // nested elements
-        let next = skip_whitespace(&mut reader, &mut buf)?;
-        let (integrity, next) = read_opt_elem(b"integrity", next, &mut reader, &Integrity::new)?;
-        let (parameters, next) = read_sequence(b"parameter", next, &mut reader, &Parameter::new)?;
+        let next = skip_whitespace(&mut reader, &mut buf).map_err(in_root)?;
+        let (integrity, next) = read_opt_elem(
+            b"integrity",
+            next,
+            &mut reader,
+            &Integrity::new_with_content_start,
+        )
+        .map_err(in_root)?;
+        let (parameters, next) =
+            read_sequence(b"parameter", next, &mut reader, &Parameter::new).map_err(in_root)?;
         drop(reader);
         // In GAML 1.00 experiments had to contain at least one item but here zero items are allowed for all versions.
-        let (experiments, next) = read_sequence_rc(
-            b"experiment",
-            next,
-            Rc::clone(&reader_ref),
-            &Experiment::new,
-        )?;
-        let mut reader = reader_ref.borrow_mut();
+        let (experiments, index, next) = match on_demand {
+            false => {
+                let (experiments, next) = read_sequence_rc(
+                    b"experiment",
+                    next,
+                    Arc::clone(&reader_ref),
+                    &Experiment::new,
+                )
+                .map_err(in_root)?;
+                (experiments, None, next)
+            }
+            true => {
+                let (index, next) =
+                    GamlIndex::new(next, Arc::clone(&reader_ref)).map_err(in_root)?;
+                (vec![], Some(index), next)
+            }
+        };
+        let mut reader = lock_input(&reader_ref);
 
-        let _next = consume_end(Self::TAG, &mut reader, next)?;
+        let next = next_non_whitespace(next, &mut reader).map_err(in_root)?;
+        let end_tag_end = reader.buffer_position();
+        let _next = consume_end(Self::TAG, &mut reader, next).map_err(in_root)?;
+
+        // the checksum is only verified when the status is requested
+        let integrity = integrity.map(|(algorithm, value, content_start)| {
+            Integrity::new(
+                algorithm,
+                value,
+                content_start..end_tag_end,
+                Arc::clone(&reader_ref),
+            )
+        });
+        drop(reader);
 
         Ok(Self {
             version,
@@ -106,8 +177,490 @@ impl Gaml {
             integrity,
             parameters,
             experiments,
+            index,
+            warnings: vec![],
         })
     }
+
+    /// Fails if the integrity checksum does not match the content or cannot be verified.
+    fn check_integrity(&self) -> Result<(), SfError> {
+        let Some(integrity) = &self.integrity else {
+            return Ok(());
+        };
+        let message = match integrity.get_status()? {
+            IntegrityStatus::Valid => return Ok(()),
+            IntegrityStatus::Invalid => format!(
+                "GAML integrity checksum does not match content: {}",
+                integrity.value
+            ),
+            IntegrityStatus::Unsupported => format!(
+                "Unsupported GAML integrity algorithm: {}",
+                integrity.algorithm
+            ),
+        };
+        Err(SfError::new(&message)
+            .with_kind(SfErrorKind::CorruptData)
+            .with_location(SfErrorLocation::ElementPath("/GAML/integrity".to_owned())))
+    }
+
+    /// Reports the deviations from the specification of the GAML version that are accepted when parsing.
+    ///
+    /// The traces of experiments parsed on demand are checked when reading them.
+    fn check_conformance(&self, collector: &mut WarningCollector) {
+        if self.version != Version::Version1_00 {
+            return;
+        }
+        let num_traces: Vec<usize> = match &self.index {
+            None => self.experiments.iter().map(|e| e.traces.len()).collect(),
+            Some(index) => index.experiments.iter().map(|e| e.traces.len()).collect(),
+        };
+        if num_traces.is_empty() {
+            report_empty_element(collector, "experiment", "/GAML".to_owned());
+        }
+        for (exp_index, num) in num_traces.into_iter().enumerate() {
+            if num == 0 {
+                let exp_path = format!("/GAML/experiment[{}]", exp_index);
+                report_empty_element(collector, "trace", exp_path);
+            }
+        }
+        for (exp_index, experiment) in self.experiments.iter().enumerate() {
+            for (trace_index, trace) in experiment.traces.iter().enumerate() {
+                let trace_path = format!("/GAML/experiment[{}]/trace[{}]", exp_index, trace_index);
+                trace.check_conformance(&trace_path, collector);
+            }
+        }
+    }
+}
+
+/// Reports a missing element required by GAML 1.00.
+fn report_empty_element(collector: &mut WarningCollector, elem: &str, path: String) {
+    collector.report(
+        &format!("No {} element in GAML 1.00 data.", elem),
+        Some(SfErrorLocation::ElementPath(path)),
+    );
+}
+
+/// The positions of the experiments and traces of GAML parsed on demand.
+///
+/// Only the XML structure is checked when recording the positions. The content of the
+/// experiments and traces is parsed and checked when reading them.
+pub struct GamlIndex {
+    pub experiments: Vec<ExperimentEntry>,
+    // XML declaration with the encoding of the input, prepended to the parsed elements
+    declaration: Vec<u8>,
+    // whether parsed traces are checked against the specification of GAML 1.00 strictly
+    strict_conformance: bool,
+    reader_ref: Arc<Mutex<Reader<Box<dyn SeekBufRead>>>>,
+}
+
+impl std::fmt::Debug for GamlIndex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GamlIndex")
+            .field("experiments", &self.experiments)
+            // skip reader_ref as quickxml::Reader does not implement Debug
+            .finish()
+    }
+}
+
+impl PartialEq for GamlIndex {
+    fn eq(&self, other: &Self) -> bool {
+        self.experiments == other.experiments
+    }
+}
+
+impl GamlIndex {
+    /// Records the positions of the experiments following the root parameters.
+    fn new(
+        next: BufEvent<'_>,
+        reader_ref: Arc<Mutex<Reader<Box<dyn SeekBufRead>>>>,
+    ) -> Result<(Self, BufEvent<'_>), SfError> {
+        let mut reader = lock_input(&reader_ref);
+        let declaration = format!(
+            "<?xml version=\"1.0\" encoding=\"{}\"?>",
+            reader.decoder().encoding().name()
+        )
+        .into_bytes();
+
+        let mut next = next_non_whitespace(next, &mut reader)?;
+        let mut experiments = vec![];
+        loop {
+            let (start_pos, name) = match &next.event {
+                Event::Start(bytes) if bytes.name().as_ref() == Experiment::TAG => (
+                    Self::get_start_pos(&reader, bytes),
+                    get_opt_attr(bytes, "name", &reader),
+                ),
+                Event::Empty(bytes) if bytes.name().as_ref() == Experiment::TAG => {
+                    return Err(Self::empty_tag_error(Experiment::TAG)
+                        .prepend_element_path(&format!("experiment[{}]", experiments.len())));
+                }
+                _ => break,
+            };
+            let traces = Self::scan_traces(&mut reader).map_err(|e| {
+                e.prepend_element_path(&format!("experiment[{}]", experiments.len()))
+            })?;
+            experiments.push(ExperimentEntry {
+                name,
+                range: start_pos..reader.buffer_position(),
+                traces,
+            });
+            next = skip_whitespace(&mut reader, next.buf)?;
+        }
+        drop(reader);
+
+        Ok((
+            Self {
+                experiments,
+                declaration,
+                strict_conformance: false,
+                reader_ref,
+            },
+            next,
+        ))
+    }
+
+    /// Records the positions of the traces of an experiment up to and including its end tag.
+    fn scan_traces<R: BufRead>(reader: &mut Reader<R>) -> Result<Vec<TraceEntry>, SfError> {
+        let mut buf = Vec::new();
+        let mut traces = vec![];
+        // depth of the elements nested in the experiment other than traces
+        let mut depth = 0usize;
+        loop {
+            match reader.read_event_into(&mut buf)? {
+                Event::Start(bytes) if depth == 0 && bytes.name().as_ref() == Trace::TAG => {
+                    let start_pos = Self::get_start_pos(reader, &bytes);
+                    let name = get_opt_attr(&bytes, "name", reader);
+                    let links = Self::scan_trace_links(reader)
+                        .map_err(|e| e.prepend_element_path(&format!("trace[{}]", traces.len())))?;
+                    traces.push(TraceEntry {
+                        name,
+                        range: start_pos..reader.buffer_position(),
+                        links,
+                    });
+                }
+                Event::Empty(bytes) if depth == 0 && bytes.name().as_ref() == Trace::TAG => {
+                    return Err(Self::empty_tag_error(Trace::TAG)
+                        .prepend_element_path(&format!("trace[{}]", traces.len())));
+                }
+                Event::Start(_) => depth += 1,
+                Event::End(_) if depth == 0 => return Ok(traces),
+                Event::End(_) => depth -= 1,
+                Event::Eof => return Err(SfError::new("Unexpected end of experiment.")),
+                _ => (),
+            }
+            buf.clear();
+        }
+    }
+
+    /// Records the linkid attributes and link elements of a trace up to and including its end
+    /// tag.
+    fn scan_trace_links<R: BufRead>(reader: &mut Reader<R>) -> Result<TraceLinks, SfError> {
+        let mut buf = Vec::new();
+        let mut skip_buf = Vec::new();
+        let mut links = TraceLinks::default();
+        // names of the open elements nested in the trace
+        let mut open_elements = Vec::<Vec<u8>>::new();
+        loop {
+            match reader.read_event_into(&mut buf)? {
+                Event::Start(bytes) if bytes.name().as_ref() == Values::TAG => {
+                    // skip the possibly large content
+                    reader.read_to_end_into(bytes.name(), &mut skip_buf)?;
+                    skip_buf.clear();
+                }
+                Event::Start(bytes) => {
+                    links.add_element(&open_elements, &bytes, reader);
+                    open_elements.push(bytes.name().as_ref().to_vec());
+                }
+                Event::Empty(bytes) => links.add_element(&open_elements, &bytes, reader),
+                Event::End(_) if open_elements.is_empty() => return Ok(links),
+                Event::End(_) => {
+                    open_elements.pop();
+                }
+                Event::Eof => return Err(SfError::new("Unexpected end of trace.")),
+                _ => (),
+            }
+            buf.clear();
+        }
+    }
+
+    /// Provides the position of the start tag that has just been read.
+    fn get_start_pos<R>(reader: &Reader<R>, bytes: &BytesStart<'_>) -> u64 {
+        // "<", the raw tag content, and ">"
+        reader.buffer_position() - bytes.len() as u64 - 2
+    }
+
+    fn empty_tag_error(tag: &[u8]) -> SfError {
+        SfError::new(&format!(
+            "Empty XML tag instead of start tag found for: {}",
+            str::from_utf8(tag).unwrap_or_default()
+        ))
+    }
+
+    /// Parses an experiment without its traces, which are parsed by [`GamlIndex::read_trace`].
+    pub fn read_experiment(&self, exp_index: usize) -> Result<Experiment, SfError> {
+        let entry = self.get_experiment_entry(exp_index)?;
+        let in_experiment = |e: SfError| {
+            e.prepend_element_path(&format!("experiment[{exp_index}]"))
+                .prepend_element_path("GAML")
+                .or_kind(SfErrorKind::CorruptData)
+        };
+
+        let reader_ref = self.open_element(&entry.range);
+        let mut reader = lock_input(&reader_ref);
+        let mut buf = Vec::new();
+        let next = skip_xml_decl(&mut reader, &mut buf).map_err(in_experiment)?;
+        let (experiment, _next) =
+            Experiment::new_without_traces(next, &mut reader).map_err(in_experiment)?;
+
+        Ok(experiment)
+    }
+
+    /// Parses a trace of an experiment.
+    pub fn read_trace(&self, exp_index: usize, trace_index: usize) -> Result<Trace, SfError> {
+        let entry = self
+            .get_experiment_entry(exp_index)?
+            .traces
+            .get(trace_index)
+            .ok_or(
+                SfError::new(&format!("Illegal trace index: {}", trace_index))
+                    .with_kind(SfErrorKind::IllegalNodePath),
+            )?;
+        let in_trace = |e: SfError| {
+            e.prepend_element_path(&format!("trace[{trace_index}]"))
+                .prepend_element_path(&format!("experiment[{exp_index}]"))
+                .prepend_element_path("GAML")
+                .or_kind(SfErrorKind::CorruptData)
+        };
+
+        let reader_ref = self.open_element(&entry.range);
+        let mut buf = Vec::new();
+        let next = skip_xml_decl(&mut lock_input(&reader_ref), &mut buf).map_err(in_trace)?;
+        let (trace, _next) = Trace::new(next, reader_ref).map_err(in_trace)?;
+
+        // deviations accepted when parsing leniently are only provided as warnings for
+        // experiments parsed when opening the file
+        if self.strict_conformance {
+            let mut collector = WarningCollector::new(&ParseOptions::strict());
+            let trace_path = format!("/GAML/experiment[{exp_index}]/trace[{trace_index}]");
+            trace.check_conformance(&trace_path, &mut collector);
+            collector.into_warnings()?;
+        }
+
+        Ok(trace)
+    }
+
+    fn get_experiment_entry(&self, exp_index: usize) -> Result<&ExperimentEntry, SfError> {
+        self.experiments.get(exp_index).ok_or(
+            SfError::new(&format!("Illegal experiment index: {}", exp_index))
+                .with_kind(SfErrorKind::IllegalNodePath),
+        )
+    }
+
+    /// Provides a reader for an element of the input, with the positions of values relative
+    /// to the element.
+    // the input is only Send with the "sync" feature enabled
+    #[allow(clippy::arc_with_non_send_sync)]
+    fn open_element(&self, range: &Range<u64>) -> Arc<Mutex<Reader<Box<dyn SeekBufRead>>>> {
+        let input = ElementInput {
+            reader_ref: Arc::clone(&self.reader_ref),
+            declaration: self.declaration.clone(),
+            range: range.clone(),
+            pos: 0,
+        };
+        let buf_reader: Box<dyn SeekBufRead> = Box::new(BufReader::new(input));
+        Arc::new(Mutex::new(Reader::from_reader(buf_reader)))
+    }
+}
+
+/// The position of an experiment element.
+#[derive(Debug, PartialEq)]
+pub struct ExperimentEntry {
+    pub name: Option<String>,
+    // From the start of the start tag to the end of the end tag
+    pub range: Range<u64>,
+    pub traces: Vec<TraceEntry>,
+}
+
+/// The position of a trace element.
+#[derive(Debug, PartialEq)]
+pub struct TraceEntry {
+    pub name: Option<String>,
+    // From the start of the start tag to the end of the end tag
+    pub range: Range<u64>,
+    pub links: TraceLinks,
+}
+
+/// The linkid attributes and link elements of the elements of a trace.
+#[derive(Debug, Default, PartialEq)]
+pub struct TraceLinks {
+    pub coordinates: Vec<ElementLinks>,
+    pub x_data: Vec<XdataLinks>,
+}
+
+impl TraceLinks {
+    /// Adds an element of a trace starting inside the open elements.
+    fn add_element<R>(
+        &mut self,
+        open_elements: &[Vec<u8>],
+        bytes: &BytesStart<'_>,
+        reader: &Reader<R>,
+    ) {
+        let parents: Vec<&[u8]> = open_elements.iter().map(Vec::as_slice).collect();
+        let linkid = || get_opt_attr(bytes, "linkid", reader);
+        match (parents.as_slice(), bytes.name().as_ref()) {
+            ([], b"coordinates") => self.coordinates.push(ElementLinks::new(linkid())),
+            ([], b"Xdata") => self.x_data.push(XdataLinks {
+                x_data: ElementLinks::new(linkid()),
+                ..Default::default()
+            }),
+            ([b"Xdata"], b"altXdata") => {
+                if let Some(x_data) = self.x_data.last_mut() {
+                    x_data.alt_x_data.push(ElementLinks::new(linkid()));
+                }
+            }
+            ([b"Xdata"], b"Ydata") => {
+                if let Some(x_data) = self.x_data.last_mut() {
+                    x_data.y_data.push(ElementLinks::new(linkid()));
+                }
+            }
+            (parents, b"link") => {
+                let linkref = get_opt_attr(bytes, "linkref", reader);
+                if let (Some(element), Some(linkref)) = (self.get_element_mut(parents), linkref) {
+                    element.links.push(Link { linkref });
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn get_element_mut(&mut self, path: &[&[u8]]) -> Option<&mut ElementLinks> {
+        match path {
+            [b"coordinates"] => self.coordinates.last_mut(),
+            [b"Xdata"] => self.x_data.last_mut().map(|x_data| &mut x_data.x_data),
+            [b"Xdata", b"altXdata"] => self
+                .x_data
+                .last_mut()
+                .and_then(|x_data| x_data.alt_x_data.last_mut()),
+            [b"Xdata", b"Ydata"] => self
+                .x_data
+                .last_mut()
+                .and_then(|x_data| x_data.y_data.last_mut()),
+            _ => None,
+        }
+    }
+}
+
+impl From<&Trace> for TraceLinks {
+    fn from(trace: &Trace) -> Self {
+        let element_links = |linkid: &Option<String>, links: &[Link]| ElementLinks {
+            linkid: linkid.clone(),
+            links: links.to_vec(),
+        };
+        Self {
+            coordinates: trace
+                .coordinates
+                .iter()
+                .map(|co| element_links(&co.linkid, &co.links))
+                .collect(),
+            x_data: trace
+                .x_data
+                .iter()
+                .map(|x_data| XdataLinks {
+                    x_data: element_links(&x_data.linkid, &x_data.links),
+                    alt_x_data: x_data
+                        .alt_x_data
+                        .iter()
+                        .map(|alt_x_data| element_links(&alt_x_data.linkid, &alt_x_data.links))
+                        .collect(),
+                    y_data: x_data
+                        .y_data
+                        .iter()
+                        .map(|y_data| element_links(&y_data.linkid, &y_data.links))
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// The linkid attributes and link elements of an Xdata element and its altXdata and Ydata.
+#[derive(Debug, Default, PartialEq)]
+pub struct XdataLinks {
+    pub x_data: ElementLinks,
+    pub alt_x_data: Vec<ElementLinks>,
+    pub y_data: Vec<ElementLinks>,
+}
+
+/// The linkid attribute and link elements of an element.
+#[derive(Debug, Default, PartialEq)]
+pub struct ElementLinks {
+    pub linkid: Option<String>,
+    pub links: Vec<Link>,
+}
+
+impl ElementLinks {
+    fn new(linkid: Option<String>) -> Self {
+        Self {
+            linkid,
+            links: vec![],
+        }
+    }
+}
+
+/// An element of the input preceded by an XML declaration.
+///
+/// Positions are relative to the start of the declaration.
+struct ElementInput {
+    reader_ref: Arc<Mutex<Reader<Box<dyn SeekBufRead>>>>,
+    declaration: Vec<u8>,
+    range: Range<u64>,
+    pos: u64,
+}
+
+impl ElementInput {
+    fn len(&self) -> u64 {
+        self.declaration.len() as u64 + (self.range.end - self.range.start)
+    }
+}
+
+impl Read for ElementInput {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let decl_len = self.declaration.len() as u64;
+        let remaining = self.len().saturating_sub(self.pos);
+        let len = buf
+            .len()
+            .min(usize::try_from(remaining).unwrap_or(usize::MAX));
+        if len == 0 {
+            return Ok(0);
+        }
+        let num_read = if self.pos < decl_len {
+            let decl = &self.declaration[self.pos as usize..];
+            let num_read = len.min(decl.len());
+            buf[..num_read].copy_from_slice(&decl[..num_read]);
+            num_read
+        } else {
+            let mut reader = lock_input(&self.reader_ref);
+            let input = reader.get_mut();
+            input.seek(SeekFrom::Start(self.range.start + self.pos - decl_len))?;
+            input.read(&mut buf[..len])?
+        };
+        self.pos += num_read as u64;
+        Ok(num_read)
+    }
+}
+
+impl Seek for ElementInput {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let new_pos = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::End(offset) => self.len().checked_add_signed(offset),
+            SeekFrom::Current(offset) => self.pos.checked_add_signed(offset),
+        };
+        self.pos = new_pos.ok_or(io::Error::new(
+            ErrorKind::InvalidInput,
+            "Illegal seek to a negative or overflowing position.",
+        ))?;
+        Ok(self.pos)
+    }
 }
 
 #[derive(EnumString, PartialEq, Debug, Display)]
@@ -120,21 +673,62 @@ pub enum Version {
     Version1_20,
 }
 
-#[derive(Debug, PartialEq)]
 pub struct Integrity {
     // Attributes
     pub algorithm: String,
     // Content
     pub value: String,
+
+    // Result of checking the value against the content is lazily determined
+    content: Range<u64>,
+    status: Mutex<Option<IntegrityStatus>>,
+    reader_ref: Arc<Mutex<Reader<Box<dyn SeekBufRead>>>>,
+}
+
+impl std::fmt::Debug for Integrity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Integrity")
+            .field("algorithm", &self.algorithm)
+            .field("value", &self.value)
+            .field("content", &self.content)
+            // skip reader_ref as quickxml::Reader does not implement Debug
+            .finish()
+    }
+}
+
+impl PartialEq for Integrity {
+    fn eq(&self, other: &Self) -> bool {
+        self.algorithm == other.algorithm
+            && self.value == other.value
+            && self.content == other.content
+            && self.get_status().ok() == other.get_status().ok()
+    }
+}
+
+/// The result of checking an integrity checksum.
+#[derive(Debug, Clone, Copy, PartialEq, Display)]
+pub enum IntegrityStatus {
+    /// The checksum matches the content.
+    #[strum(serialize = "valid")]
+    Valid,
+    /// The checksum does not match the content.
+    #[strum(serialize = "invalid")]
+    Invalid,
+    /// The checksum algorithm is not supported.
+    #[strum(serialize = "unsupported")]
+    Unsupported,
 }
 
 impl Integrity {
     const TAG: &'static [u8] = b"integrity";
+    const CHUNK_SIZE: usize = 64 * 1024;
 
-    fn new<'buf, R: BufRead>(
+    /// Reads the integrity element and the position of the checksummed content following its
+    /// end tag.
+    fn new_with_content_start<'buf, R: BufRead>(
         next: BufEvent<'buf>,
         reader: &mut Reader<R>,
-    ) -> Result<(Self, BufEvent<'buf>), SfError> {
+    ) -> Result<((String, String, u64), BufEvent<'buf>), SfError> {
         let start = read_start(Self::TAG, reader, &next)?;
 
         // attributes
@@ -142,10 +736,108 @@ impl Integrity {
 
         // value
         let (value, next) = read_value(reader, next.buf)?;
+        // the end tag has just been read
+        let content_start = reader.buffer_position();
 
         let next = consume_end(Self::TAG, reader, next)?;
 
-        Ok((Self { algorithm, value }, next))
+        Ok(((algorithm, value, content_start), next))
+    }
+
+    fn new(
+        algorithm: String,
+        value: String,
+        content: Range<u64>,
+        reader_ref: Arc<Mutex<Reader<Box<dyn SeekBufRead>>>>,
+    ) -> Self {
+        Self {
+            algorithm,
+            value,
+            content,
+            status: Mutex::new(None),
+            reader_ref,
+        }
+    }
+
+    /// Provides the result of checking the value against the checksum of the content.
+    ///
+    /// The checksum is calculated on first request.
+    pub fn get_status(&self) -> Result<IntegrityStatus, SfError> {
+        let mut status = lock_input(&self.status);
+        if let Some(status) = *status {
+            return Ok(status);
+        }
+        let mut reader = lock_input(&self.reader_ref);
+        let verified = self.verify(reader.get_mut())?;
+        *status = Some(verified);
+        Ok(verified)
+    }
+
+    fn create_hasher(algorithm: &str) -> Option<Box<dyn DynDigest>> {
+        match algorithm.to_ascii_uppercase().as_str() {
+            "MD5" => Some(Box::new(Md5::default())),
+            "SHA1" | "SHA-1" => Some(Box::new(Sha1::default())),
+            "SHA256" | "SHA-256" => Some(Box::new(Sha256::default())),
+            _ => None,
+        }
+    }
+
+    /// Checks the value against the checksum of the content between the end of the integrity
+    /// element and the start of the GAML end tag, ending at the end of the content range.
+    fn verify<R: Read + Seek>(&self, input: &mut R) -> Result<IntegrityStatus, SfError> {
+        let Some(mut hasher) = Self::create_hasher(&self.algorithm) else {
+            return Ok(IntegrityStatus::Unsupported);
+        };
+        let range = &self.content;
+
+        // the end tag is short, e.g., "</GAML>", possibly with trailing whitespace
+        let tail_start = range.end.saturating_sub(64).max(range.start);
+        let mut tail = vec![0u8; (range.end - tail_start) as usize];
+        input.seek(SeekFrom::Start(tail_start))?;
+        input.read_exact(&mut tail)?;
+        let end_tag_start = tail
+            .windows(2)
+            .rposition(|window| window == b"</")
+            .ok_or(SfError::new("No GAML end tag found for integrity check."))?;
+        let content_end = tail_start + end_tag_start as u64;
+
+        input.seek(SeekFrom::Start(range.start))?;
+        let mut content = input.take(content_end - range.start);
+        let mut chunk = vec![0u8; Self::CHUNK_SIZE];
+        loop {
+            let len = content.read(&mut chunk)?;
+            if len == 0 {
+                break;
+            }
+            hasher.update(&chunk[..len]);
+        }
+        let digest = hasher.finalize();
+
+        let value = self.value.trim();
+        let hex_digest: String = digest.iter().map(|b| format!("{:02x}", b)).collect();
+        let is_valid = value.eq_ignore_ascii_case(&hex_digest)
+            || BASE64_STANDARD
+                .decode(value)
+                .is_ok_and(|decoded| decoded[..] == digest[..]);
+        Ok(match is_valid {
+            true => IntegrityStatus::Valid,
+            false => IntegrityStatus::Invalid,
+        })
+    }
+
+    /// #[cfg(test)] and pub(super) to allow creating Integrity in unit tests
+    #[cfg(test)]
+    #[allow(clippy::arc_with_non_send_sync)]
+    pub(super) fn create_integrity_with(
+        algorithm: &str,
+        value: &str,
+        status: IntegrityStatus,
+    ) -> Integrity {
+        let buf_reader: Box<dyn SeekBufRead> = Box::new(BufReader::new(Cursor::new(vec![])));
+        let reader_ref = Arc::new(Mutex::new(quick_xml::Reader::from_reader(buf_reader)));
+        let integrity = Self::new(algorithm.to_owned(), value.to_owned(), 0..0, reader_ref);
+        *lock_input(&integrity.status) = Some(status);
+        integrity
     }
 }
 
@@ -216,17 +908,38 @@ impl Experiment {
 
     fn new(
         next: BufEvent<'_>,
-        reader_ref: Rc<RefCell<Reader<Box<dyn SeekBufRead>>>>,
+        reader_ref: Arc<Mutex<Reader<Box<dyn SeekBufRead>>>>,
     ) -> Result<(Self, BufEvent<'_>), SfError> {
-        let mut reader = reader_ref.borrow_mut();
+        let mut reader = lock_input(&reader_ref);
+        let (experiment, next) = Self::new_without_traces(next, &mut reader)?;
+        drop(reader);
+        // In GAML 1.00 traces had to contain at least one item but here zero items are allowed for all versions.
+        let (traces, next) =
+            read_sequence_rc(b"trace", next, Arc::clone(&reader_ref), &Trace::new)?;
 
+        let next = consume_end_rc(Self::TAG, Arc::clone(&reader_ref), next)?;
+
+        Ok((
+            Self {
+                traces,
+                ..experiment
+            },
+            next,
+        ))
+    }
+
+    /// Reads the attributes and the nested elements preceding the traces.
+    fn new_without_traces<'buf, R: BufRead>(
+        next: BufEvent<'buf>,
+        reader: &mut Reader<R>,
+    ) -> Result<(Self, BufEvent<'buf>), SfError> {
         // attributes
-        let start = read_start(Self::TAG, &reader, &next)?;
+        let start = read_start(Self::TAG, reader, &next)?;
         let name = start.get_opt_attr("name");
 
         // nested elements
-        let next = skip_whitespace(&mut reader, next.buf)?;
-        let (datetime, next) = read_opt_elem(b"collectdate", next, &mut reader, &Collectdate::new)?;
+        let next = skip_whitespace(reader, next.buf)?;
+        let (datetime, next) = read_opt_elem(b"collectdate", next, reader, &Collectdate::new)?;
         let collectdate = datetime
             .map(|dt| {
                 DateTime::parse_from_rfc3339(&dt.value)
@@ -234,19 +947,14 @@ impl Experiment {
             })
             .transpose()?
             .map(|d| d.to_rfc3339_opts(SecondsFormat::AutoSi, true));
-        let (parameters, next) = read_sequence(b"parameter", next, &mut reader, &Parameter::new)?;
-        drop(reader);
-        // In GAML 1.00 traces had to contain at least one item but here zero items are allowed for all versions.
-        let (traces, next) = read_sequence_rc(b"trace", next, Rc::clone(&reader_ref), &Trace::new)?;
-
-        let next = consume_end_rc(Self::TAG, Rc::clone(&reader_ref), next)?;
+        let (parameters, next) = read_sequence(b"parameter", next, reader, &Parameter::new)?;
 
         Ok((
             Self {
                 name,
                 collectdate,
                 parameters,
-                traces,
+                traces: vec![],
             },
             next,
         ))
@@ -331,9 +1039,9 @@ impl Trace {
 
     fn new(
         next: BufEvent<'_>,
-        reader_ref: Rc<RefCell<Reader<Box<dyn SeekBufRead>>>>,
+        reader_ref: Arc<Mutex<Reader<Box<dyn SeekBufRead>>>>,
     ) -> Result<(Self, BufEvent<'_>), SfError> {
-        let mut reader = reader_ref.borrow_mut();
+        let mut reader = lock_input(&reader_ref);
 
         // attributes
         let start = read_start(Self::TAG, &reader, &next)?;
@@ -351,12 +1059,13 @@ impl Trace {
         let (coordinates, next) = read_sequence_rc(
             b"coordinates",
             next,
-            Rc::clone(&reader_ref),
+            Arc::clone(&reader_ref),
             &Coordinates::new,
         )?;
-        let (x_data, next) = read_sequence_rc(b"Xdata", next, Rc::clone(&reader_ref), &Xdata::new)?;
+        let (x_data, next) =
+            read_sequence_rc(b"Xdata", next, Arc::clone(&reader_ref), &Xdata::new)?;
 
-        let next = consume_end_rc(Self::TAG, Rc::clone(&reader_ref), next)?;
+        let next = consume_end_rc(Self::TAG, Arc::clone(&reader_ref), next)?;
 
         Ok((
             Self {
@@ -369,6 +1078,23 @@ impl Trace {
             next,
         ))
     }
+
+    /// Reports the deviations from the specification of GAML 1.00 that are accepted when parsing.
+    fn check_conformance(&self, trace_path: &str, collector: &mut WarningCollector) {
+        for (x_index, x_data) in self.x_data.iter().enumerate() {
+            for (y_index, y_data) in x_data.y_data.iter().enumerate() {
+                for (pt_index, peaktable) in y_data.peaktables.iter().enumerate() {
+                    if peaktable.peaks.is_empty() {
+                        let path = format!(
+                            "{}/Xdata[{}]/Ydata[{}]/peaktable[{}]",
+                            trace_path, x_index, y_index, pt_index
+                        );
+                        report_empty_element(collector, "peak", path);
+                    }
+                }
+            }
+        }
+    }
 }
 
 #[derive(EnumString, PartialEq, Debug, Display)]
@@ -542,7 +1268,7 @@ impl Coordinates {
 
     fn new(
         next: BufEvent<'_>,
-        reader_ref: Rc<RefCell<Reader<Box<dyn SeekBufRead>>>>,
+        reader_ref: Arc<Mutex<Reader<Box<dyn SeekBufRead>>>>,
     ) -> Result<(Self, BufEvent<'_>), SfError> {
         // attributes
         let (
@@ -556,7 +1282,7 @@ impl Coordinates {
         ) = read_data_attributes(
             Self::TAG,
             str::from_utf8(Self::TAG).unwrap_or_default(),
-            Rc::clone(&reader_ref),
+            Arc::clone(&reader_ref),
             next,
         )?;
 
@@ -568,9 +1294,9 @@ impl Coordinates {
                 values,
             },
             next,
-        ) = read_data_elements(Rc::clone(&reader_ref), next)?;
+        ) = read_data_elements(Arc::clone(&reader_ref), next)?;
 
-        let next = consume_end_rc(Self::TAG, Rc::clone(&reader_ref), next)?;
+        let next = consume_end_rc(Self::TAG, Arc::clone(&reader_ref), next)?;
 
         Ok((
             Self {
@@ -587,7 +1313,7 @@ impl Coordinates {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Link {
     // Attributes
     pub linkref: String,
@@ -632,7 +1358,7 @@ pub struct Values {
     // Value is lazily read
     value_start_pos: u64,
     value_end_pos: u64,
-    reader_ref: Rc<RefCell<Reader<Box<dyn SeekBufRead>>>>,
+    reader_ref: Arc<Mutex<Reader<Box<dyn SeekBufRead>>>>,
 }
 
 impl std::fmt::Debug for Values {
@@ -665,9 +1391,9 @@ impl Values {
 
     fn new(
         next: BufEvent<'_>,
-        reader_ref: Rc<RefCell<Reader<Box<dyn SeekBufRead>>>>,
+        reader_ref: Arc<Mutex<Reader<Box<dyn SeekBufRead>>>>,
     ) -> Result<(Self, BufEvent<'_>), SfError> {
-        let mut reader = reader_ref.borrow_mut();
+        let mut reader = lock_input(&reader_ref);
 
         // attributes
         let start = read_start(Self::TAG, &reader, &next)?;
@@ -708,7 +1434,58 @@ impl Values {
     }
 
     pub fn get_data(&self) -> Result<Vec<f64>, SfError> {
-        let mut reader = self.reader_ref.borrow_mut();
+        let value = self.read_base64_value()?;
+        let raw_data = BASE64_STANDARD
+            .decode(value.as_bytes())
+            .map_err(|e| SfError::from_source(e, "Error decoding base64 data."))?;
+        self.check_num_bytes(raw_data.len())?;
+
+        Ok(self.decode_values(&raw_data))
+    }
+
+    /// Provides the number of values.
+    ///
+    /// Uses the numvalues attribute if present. Otherwise, determines the number
+    /// of values from the length of the base64 data without decoding it.
+    pub fn get_len(&self) -> Result<usize, SfError> {
+        if let Some(n) = self.numvalues {
+            return Ok(n as usize);
+        }
+        let value = self.read_base64_value()?;
+        let num_bytes = Self::get_decoded_len(&value)?;
+        self.check_num_bytes(num_bytes)?;
+
+        Ok(num_bytes / self.get_bytes_per_value())
+    }
+
+    /// Provides a range of the values.
+    ///
+    /// Only the base64 characters holding the values in the range are decoded.
+    pub fn get_data_range(&self, range: Range<usize>) -> Result<Vec<f64>, SfError> {
+        let value = self.read_base64_value()?;
+        let num_bytes = Self::get_decoded_len(&value)?;
+        self.check_num_bytes(num_bytes)?;
+        let bytes_per_value = self.get_bytes_per_value();
+        check_data_range(&range, num_bytes / bytes_per_value)?;
+        if range.is_empty() {
+            return Ok(vec![]);
+        }
+
+        // base64 encodes groups of 3 bytes as 4 characters
+        let start_byte = range.start * bytes_per_value;
+        let end_byte = range.end * bytes_per_value;
+        let start_char = start_byte / 3 * 4;
+        let end_char = (end_byte.div_ceil(3) * 4).min(value.len());
+        let raw_data = BASE64_STANDARD
+            .decode(&value.as_bytes()[start_char..end_char])
+            .map_err(|e| SfError::from_source(e, "Error decoding base64 data."))?;
+        let offset = start_byte % 3;
+
+        Ok(self.decode_values(&raw_data[offset..offset + end_byte - start_byte]))
+    }
+
+    fn read_base64_value(&self) -> Result<String, SfError> {
+        let mut reader = lock_input(&self.reader_ref);
 
         let start = self.value_start_pos;
         let end = self.value_end_pos;
@@ -724,30 +1501,53 @@ impl Values {
         let (mut value, _next) = read_value(&mut reader, &mut buf)?;
         value.retain(|c| !c.is_whitespace());
 
-        let raw_data = BASE64_STANDARD
-            .decode(value.as_bytes())
-            .map_err(|e| SfError::from_source(e, "Error decoding base64 data."))?;
+        Ok(value)
+    }
 
-        let multiple = match &self.format {
-            Format::Float32 => 4u64,
-            Format::Float64 => 8u64,
-        };
-        if !(raw_data.len() as u64).is_multiple_of(multiple) {
+    fn get_decoded_len(value: &str) -> Result<usize, SfError> {
+        if !value.len().is_multiple_of(4) {
+            return Err(SfError::new(&format!(
+                "Illegal number of base64 characters: {}",
+                value.len()
+            )));
+        }
+        let padding = value.bytes().rev().take_while(|c| *c == b'=').count();
+        if padding > 2 {
+            return Err(SfError::new("Illegal base64 padding."));
+        }
+
+        Ok(value.len() / 4 * 3 - padding)
+    }
+
+    fn get_bytes_per_value(&self) -> usize {
+        match &self.format {
+            Format::Float32 => 4,
+            Format::Float64 => 8,
+        }
+    }
+
+    fn check_num_bytes(&self, num_bytes: usize) -> Result<(), SfError> {
+        let multiple = self.get_bytes_per_value();
+        if !num_bytes.is_multiple_of(multiple) {
             return Err(SfError::new(&format!(
                 "Illegal number of data bytes: {}",
-                raw_data.len()
+                num_bytes
             )));
         }
         if let Some(n) = self.numvalues
-            && n != raw_data.len() as u64 / multiple
+            && n != (num_bytes / multiple) as u64
         {
             return Err(SfError::new(&format!(
                 "Number of data bytes does not correspond to numvalues and format attributes: {}",
-                raw_data.len()
+                num_bytes
             )));
         }
 
-        let data = if Format::Float32 == self.format {
+        Ok(())
+    }
+
+    fn decode_values(&self, raw_data: &[u8]) -> Vec<f64> {
+        if Format::Float32 == self.format {
             // see https://stackoverflow.com/a/77388975 for a more elegant solution in the future
             // f32
             raw_data
@@ -765,20 +1565,19 @@ impl Values {
                 .map(Result::unwrap)
                 .map(f64::from_le_bytes)
                 .collect()
-        };
-
-        Ok(data)
+        }
     }
 
     /// #[cfg(test)] and pub(super) to allow creating Values in unit tests
     #[cfg(test)]
+    #[allow(clippy::arc_with_non_send_sync)]
     pub(super) fn create_values_with(bytes: &[u8], format: Format, byteorder: Byteorder) -> Values {
         let base64 = BASE64_STANDARD.encode(bytes);
         let base64_len = base64.len();
         let input = Cursor::new(base64);
         let buf_reader: Box<dyn SeekBufRead> = Box::new(BufReader::new(input));
         let reader = quick_xml::Reader::from_reader(buf_reader);
-        let reader_ref = Rc::new(RefCell::new(reader));
+        let reader_ref = Arc::new(Mutex::new(reader));
 
         let numvalues = match &format {
             Format::Float32 => bytes.len() / 4,
@@ -816,7 +1615,7 @@ impl Xdata {
 
     fn new(
         next: BufEvent<'_>,
-        reader_ref: Rc<RefCell<Reader<Box<dyn SeekBufRead>>>>,
+        reader_ref: Arc<Mutex<Reader<Box<dyn SeekBufRead>>>>,
     ) -> Result<(Self, BufEvent<'_>), SfError> {
         // attributes
         let (
@@ -830,7 +1629,7 @@ impl Xdata {
         ) = read_data_attributes(
             Self::TAG,
             str::from_utf8(Self::TAG).unwrap_or_default(),
-            Rc::clone(&reader_ref),
+            Arc::clone(&reader_ref),
             next,
         )?;
 
@@ -842,15 +1641,16 @@ impl Xdata {
                 values,
             },
             next,
-        ) = read_data_elements(Rc::clone(&reader_ref), next)?;
+        ) = read_data_elements(Arc::clone(&reader_ref), next)?;
         let (alt_x_data, next) =
-            read_sequence_rc(b"altXdata", next, Rc::clone(&reader_ref), &AltXdata::new)?;
-        let (y_data, next) = read_sequence_rc(b"Ydata", next, Rc::clone(&reader_ref), &Ydata::new)?;
+            read_sequence_rc(b"altXdata", next, Arc::clone(&reader_ref), &AltXdata::new)?;
+        let (y_data, next) =
+            read_sequence_rc(b"Ydata", next, Arc::clone(&reader_ref), &Ydata::new)?;
         if y_data.is_empty() {
             return Err(SfError::new("No Ydata found for Xdata."));
         }
 
-        let next = consume_end_rc(Self::TAG, Rc::clone(&reader_ref), next)?;
+        let next = consume_end_rc(Self::TAG, Arc::clone(&reader_ref), next)?;
 
         Ok((
             Self {
@@ -887,7 +1687,7 @@ impl AltXdata {
 
     fn new(
         next: BufEvent<'_>,
-        reader_ref: Rc<RefCell<Reader<Box<dyn SeekBufRead>>>>,
+        reader_ref: Arc<Mutex<Reader<Box<dyn SeekBufRead>>>>,
     ) -> Result<(Self, BufEvent<'_>), SfError> {
         // attributes
         let (
@@ -901,7 +1701,7 @@ impl AltXdata {
         ) = read_data_attributes(
             Self::TAG,
             str::from_utf8(Self::TAG).unwrap_or_default(),
-            Rc::clone(&reader_ref),
+            Arc::clone(&reader_ref),
             next,
         )?;
 
@@ -913,9 +1713,9 @@ impl AltXdata {
                 values,
             },
             next,
-        ) = read_data_elements(Rc::clone(&reader_ref), next)?;
+        ) = read_data_elements(Arc::clone(&reader_ref), next)?;
 
-        let next = consume_end_rc(Self::TAG, Rc::clone(&reader_ref), next)?;
+        let next = consume_end_rc(Self::TAG, Arc::clone(&reader_ref), next)?;
 
         Ok((
             Self {
@@ -937,7 +1737,9 @@ pub struct Ydata {
     // Attributes
     pub units: Units,
     pub label: Option<String>,
+    pub linkid: Option<String>,
     // Elements
+    pub links: Vec<Link>,
     pub parameters: Vec<Parameter>,
     pub values: Values,
     pub peaktables: Vec<Peaktable>,
@@ -948,34 +1750,44 @@ impl Ydata {
 
     fn new(
         next: BufEvent<'_>,
-        reader_ref: Rc<RefCell<Reader<Box<dyn SeekBufRead>>>>,
+        reader_ref: Arc<Mutex<Reader<Box<dyn SeekBufRead>>>>,
     ) -> Result<(Self, BufEvent<'_>), SfError> {
         // attributes
-        let (DataAttributes { units, label, .. }, next) = read_data_attributes(
+        let (
+            DataAttributes {
+                units,
+                label,
+                linkid,
+                ..
+            },
+            next,
+        ) = read_data_attributes(
             Self::TAG,
             str::from_utf8(Self::TAG).unwrap_or_default(),
-            Rc::clone(&reader_ref),
+            Arc::clone(&reader_ref),
             next,
         )?;
 
         // nested elements
         let (
             DataElements {
-                links: _,
+                links,
                 parameters,
                 values,
             },
             next,
-        ) = read_data_elements(Rc::clone(&reader_ref), next)?;
+        ) = read_data_elements(Arc::clone(&reader_ref), next)?;
         let (peaktables, next) =
-            read_sequence_rc(b"peaktable", next, Rc::clone(&reader_ref), &Peaktable::new)?;
+            read_sequence_rc(b"peaktable", next, Arc::clone(&reader_ref), &Peaktable::new)?;
 
-        let next = consume_end_rc(Self::TAG, Rc::clone(&reader_ref), next)?;
+        let next = consume_end_rc(Self::TAG, Arc::clone(&reader_ref), next)?;
 
         Ok((
             Self {
                 units,
                 label,
+                linkid,
+                links,
                 parameters,
                 values,
                 peaktables,
@@ -999,9 +1811,9 @@ impl Peaktable {
 
     fn new(
         next: BufEvent<'_>,
-        reader_ref: Rc<RefCell<Reader<Box<dyn SeekBufRead>>>>,
+        reader_ref: Arc<Mutex<Reader<Box<dyn SeekBufRead>>>>,
     ) -> Result<(Self, BufEvent<'_>), SfError> {
-        let mut reader = reader_ref.borrow_mut();
+        let mut reader = lock_input(&reader_ref);
 
         // attributes
         let start = read_start(Self::TAG, &reader, &next)?;
@@ -1012,9 +1824,9 @@ impl Peaktable {
         let (parameters, next) = read_sequence(b"parameter", next, &mut reader, &Parameter::new)?;
         drop(reader);
         // In GAML 1.00 peaks had to contain at least one item but here zero items are allowed for all versions.
-        let (peaks, next) = read_sequence_rc(b"peak", next, Rc::clone(&reader_ref), &Peak::new)?;
+        let (peaks, next) = read_sequence_rc(b"peak", next, Arc::clone(&reader_ref), &Peak::new)?;
 
-        let next = consume_end_rc(Self::TAG, Rc::clone(&reader_ref), next)?;
+        let next = consume_end_rc(Self::TAG, Arc::clone(&reader_ref), next)?;
 
         Ok((
             Self {
@@ -1045,9 +1857,9 @@ impl Peak {
 
     fn new(
         next: BufEvent<'_>,
-        reader_ref: Rc<RefCell<Reader<Box<dyn SeekBufRead>>>>,
+        reader_ref: Arc<Mutex<Reader<Box<dyn SeekBufRead>>>>,
     ) -> Result<(Self, BufEvent<'_>), SfError> {
-        let mut reader = reader_ref.borrow_mut();
+        let mut reader = lock_input(&reader_ref);
 
         // attributes
         let start = read_start(Self::TAG, &reader, &next)?;
@@ -1070,9 +1882,9 @@ impl Peak {
         let (peak_y_value, next) = read_req_elem_value_f64(b"peakYvalue", next, &mut reader)?;
         drop(reader);
         let (baseline, next) =
-            read_opt_elem_rc(b"baseline", next, Rc::clone(&reader_ref), &Baseline::new)?;
+            read_opt_elem_rc(b"baseline", next, Arc::clone(&reader_ref), &Baseline::new)?;
 
-        let next = consume_end_rc(Self::TAG, Rc::clone(&reader_ref), next)?;
+        let next = consume_end_rc(Self::TAG, Arc::clone(&reader_ref), next)?;
 
         Ok((
             Self {
@@ -1105,9 +1917,9 @@ impl Baseline {
 
     fn new(
         next: BufEvent<'_>,
-        reader_ref: Rc<RefCell<Reader<Box<dyn SeekBufRead>>>>,
+        reader_ref: Arc<Mutex<Reader<Box<dyn SeekBufRead>>>>,
     ) -> Result<(Self, BufEvent<'_>), SfError> {
-        let mut reader = reader_ref.borrow_mut();
+        let mut reader = lock_input(&reader_ref);
 
         // attributes
         let _start = read_start(Self::TAG, &reader, &next)?;
@@ -1121,9 +1933,9 @@ impl Baseline {
         let (end_y_value, next) = read_req_elem_value_f64(b"endYvalue", next, &mut reader)?;
         drop(reader);
         let (basecurve, next) =
-            read_opt_elem_rc(b"basecurve", next, Rc::clone(&reader_ref), &Basecurve::new)?;
+            read_opt_elem_rc(b"basecurve", next, Arc::clone(&reader_ref), &Basecurve::new)?;
 
-        let next = consume_end_rc(Self::TAG, Rc::clone(&reader_ref), next)?;
+        let next = consume_end_rc(Self::TAG, Arc::clone(&reader_ref), next)?;
 
         Ok((
             Self {
@@ -1151,9 +1963,9 @@ impl Basecurve {
 
     fn new(
         next: BufEvent<'_>,
-        reader_ref: Rc<RefCell<Reader<Box<dyn SeekBufRead>>>>,
+        reader_ref: Arc<Mutex<Reader<Box<dyn SeekBufRead>>>>,
     ) -> Result<(Self, BufEvent<'_>), SfError> {
-        let mut reader = reader_ref.borrow_mut();
+        let mut reader = lock_input(&reader_ref);
 
         // attributes
         let _start = read_start(Self::TAG, &reader, &next)?;
@@ -1161,10 +1973,10 @@ impl Basecurve {
         // nested elements
         let next = skip_whitespace(&mut reader, next.buf)?;
         drop(reader);
-        let (base_x_data, next) = read_base_values(b"baseXdata", next, Rc::clone(&reader_ref))?;
-        let (base_y_data, next) = read_base_values(b"baseYdata", next, Rc::clone(&reader_ref))?;
+        let (base_x_data, next) = read_base_values(b"baseXdata", next, Arc::clone(&reader_ref))?;
+        let (base_y_data, next) = read_base_values(b"baseYdata", next, Arc::clone(&reader_ref))?;
 
-        let next = consume_end_rc(Self::TAG, Rc::clone(&reader_ref), next)?;
+        let next = consume_end_rc(Self::TAG, Arc::clone(&reader_ref), next)?;
 
         Ok((
             Self {
@@ -1201,17 +2013,17 @@ impl Basecurve {
 fn read_base_values<'buf>(
     tag_name: &[u8],
     next: BufEvent<'buf>,
-    reader_ref: Rc<RefCell<Reader<Box<dyn SeekBufRead>>>>,
+    reader_ref: Arc<Mutex<Reader<Box<dyn SeekBufRead>>>>,
 ) -> Result<(Vec<Values>, BufEvent<'buf>), SfError> {
-    let mut reader = reader_ref.borrow_mut();
+    let mut reader = lock_input(&reader_ref);
     let next = next_non_whitespace(next, &mut reader)?;
 
     let _start = read_start(tag_name, &reader, &next)?;
     let next = skip_whitespace(&mut reader, next.buf)?;
     drop(reader);
-    let (values, next) = read_sequence_rc(b"values", next, Rc::clone(&reader_ref), &Values::new)?;
+    let (values, next) = read_sequence_rc(b"values", next, Arc::clone(&reader_ref), &Values::new)?;
 
-    let next = consume_end_rc(tag_name, Rc::clone(&reader_ref), next)?;
+    let next = consume_end_rc(tag_name, Arc::clone(&reader_ref), next)?;
 
     Ok((values, next))
 }
@@ -1227,10 +2039,10 @@ struct DataAttributes {
 fn read_data_attributes<'buf>(
     tag_name: &[u8],
     display_name: &str,
-    reader_ref: Rc<RefCell<Reader<Box<dyn SeekBufRead>>>>,
+    reader_ref: Arc<Mutex<Reader<Box<dyn SeekBufRead>>>>,
     next: BufEvent<'buf>,
 ) -> Result<(DataAttributes, BufEvent<'buf>), SfError> {
-    let mut reader = reader_ref.borrow_mut();
+    let mut reader = lock_input(&reader_ref);
     // attributes
     let start = read_start(tag_name, &reader, &next)?;
     let units = start.parse_req_attr("units", &Units::from_str, display_name)?;
@@ -1259,15 +2071,15 @@ struct DataElements {
 }
 
 fn read_data_elements(
-    reader_ref: Rc<RefCell<Reader<Box<dyn SeekBufRead>>>>,
+    reader_ref: Arc<Mutex<Reader<Box<dyn SeekBufRead>>>>,
     next: BufEvent<'_>,
 ) -> Result<(DataElements, BufEvent<'_>), SfError> {
-    let mut reader = reader_ref.borrow_mut();
+    let mut reader = lock_input(&reader_ref);
 
     let (links, next) = read_sequence(b"link", next, &mut reader, &Link::new)?;
     let (parameters, next) = read_sequence(b"parameter", next, &mut reader, &Parameter::new)?;
     drop(reader);
-    let (values, next) = read_req_elem_rc(b"values", next, Rc::clone(&reader_ref), &Values::new)?;
+    let (values, next) = read_req_elem_rc(b"values", next, Arc::clone(&reader_ref), &Values::new)?;
 
     Ok((
         DataElements {
@@ -1282,6 +2094,7 @@ fn read_data_elements(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::SfErrorLocation;
     use chrono::{FixedOffset, NaiveDate, NaiveDateTime, NaiveTime};
     use std::{error::Error, io::Cursor};
 
@@ -1323,7 +2136,8 @@ mod tests {
                                                 <!-- A values comment -->
                                             </values>
                                         </altXdata>
-                                        <Ydata label=\"Ydata label\" units=\"MICRONS\">
+                                        <Ydata label=\"Ydata label\" units=\"MICRONS\" linkid=\"ydata-linkid\">
+                                            <link linkref=\"ydata-linkref\"/>
                                             <parameter name=\"ydata-parameter0\" label=\"Ydata parameter label 0\">Ydata parameter value 0</parameter>
                                             <values byteorder=\"INTEL\" format=\"FLOAT32\" numvalues=\"2\">
                                                 <!-- A values comment -->
@@ -1390,8 +2204,8 @@ mod tests {
 
         let experiments = &gaml.experiments;
         assert_eq!(1, experiments.len());
-        let date = NaiveDate::from_ymd_opt(2024, 03, 27).unwrap();
-        let time = NaiveTime::from_hms_opt(06, 46, 0).unwrap();
+        let date = NaiveDate::from_ymd_opt(2024, 3, 27).unwrap();
+        let time = NaiveTime::from_hms_opt(6, 46, 0).unwrap();
         assert_eq!(
             &DateTime::<FixedOffset>::from_naive_utc_and_offset(
                 NaiveDateTime::new(date, time),
@@ -1545,6 +2359,9 @@ mod tests {
         assert_eq!(1, y_data.len());
         assert_eq!(Some("Ydata label".into()), y_data[0].label);
         assert_eq!(Units::Microns, y_data[0].units);
+        assert_eq!(Some("ydata-linkid".into()), y_data[0].linkid);
+        assert_eq!(1, y_data[0].links.len());
+        assert_eq!("ydata-linkref", y_data[0].links[0].linkref);
 
         let y_data_parameters = &y_data[0].parameters;
         assert_eq!("ydata-parameter0", &y_data_parameters[0].name);
@@ -1616,6 +2433,113 @@ mod tests {
         assert_eq!(vec![1.0, 2.0, 1.0, 2.0], basecurve.get_y_data().unwrap());
     }
 
+    #[test]
+    fn reports_empty_elements_in_gaml_1_00_as_warnings_or_errors() {
+        let xml =
+            b"<GAML version=\"1.00\"><experiment name=\"Experiment name\"></experiment></GAML>";
+
+        let gaml = GamlParser::parse("test.gaml", Cursor::new(xml)).unwrap();
+        assert_eq!(
+            vec![Some(SfErrorLocation::ElementPath(
+                "/GAML/experiment[0]".to_owned()
+            )),],
+            gaml.warnings
+                .iter()
+                .map(|w| w.location.clone())
+                .collect::<Vec<_>>()
+        );
+
+        let on_demand = ParseOptions {
+            on_demand: true,
+            ..Default::default()
+        };
+        let on_demand_gaml =
+            GamlParser::parse_with_options("test.gaml", Cursor::new(xml), &on_demand).unwrap();
+        assert_eq!(gaml.warnings, on_demand_gaml.warnings);
+
+        let error =
+            GamlParser::parse_with_options("test.gaml", Cursor::new(xml), &ParseOptions::strict())
+                .unwrap_err();
+        assert_eq!(SfErrorKind::CorruptData, error.kind());
+
+        let xml_gaml_1_10 = b"<GAML version=\"1.10\"></GAML>";
+        let gaml = GamlParser::parse_with_options(
+            "test.gaml",
+            Cursor::new(xml_gaml_1_10),
+            &ParseOptions::strict(),
+        )
+        .unwrap();
+        assert!(gaml.warnings.is_empty());
+    }
+
+    fn parse_integrity(algorithm: &str, value: &str, content: &str) -> Integrity {
+        let xml = format!(
+            "<GAML version=\"1.20\"><integrity algorithm=\"{}\">{}</integrity>{}</GAML >\n",
+            algorithm, value, content
+        );
+        let gaml = GamlParser::parse("test.gaml", Cursor::new(xml)).unwrap();
+        gaml.integrity.unwrap()
+    }
+
+    #[test]
+    fn verifies_integrity_checksums() {
+        let content = "<parameter name=\"p\">v</parameter>\n";
+
+        let md5 = parse_integrity("MD5", "8cf84a000a9ba83199cffc10a5e47821", content);
+        // the checksum is only calculated on request
+        assert_eq!(None, *lock_input(&md5.status));
+        assert_eq!(IntegrityStatus::Valid, md5.get_status().unwrap());
+        assert_eq!(Some(IntegrityStatus::Valid), *lock_input(&md5.status));
+        let sha1 = parse_integrity(
+            "sha1",
+            " DD17CDBDE78CA77E332AEB6ADB867C48242D9826 ",
+            content,
+        );
+        assert_eq!(IntegrityStatus::Valid, sha1.get_status().unwrap());
+        let sha256 = parse_integrity(
+            "SHA-256",
+            "B3IDStrmTLXd06sKrKaNaH29/C9tDtRbVduMSrO+ALg=",
+            content,
+        );
+        assert_eq!(IntegrityStatus::Valid, sha256.get_status().unwrap());
+
+        let tampered = content.replace('v', "w");
+        let md5 = parse_integrity("MD5", "8cf84a000a9ba83199cffc10a5e47821", &tampered);
+        assert_eq!(IntegrityStatus::Invalid, md5.get_status().unwrap());
+
+        let unsupported = parse_integrity("CRC32", "8cf84a00", content);
+        assert_eq!(
+            IntegrityStatus::Unsupported,
+            unsupported.get_status().unwrap()
+        );
+    }
+
+    #[test]
+    fn rejects_unverified_integrity_in_strict_integrity_mode() {
+        let options = ParseOptions {
+            strict_integrity: true,
+            ..Default::default()
+        };
+        let xml = b"<GAML version=\"1.20\"><integrity algorithm=\"MD5\">0</integrity></GAML>";
+        let error = GamlParser::parse_with_options("test.gaml", Cursor::new(xml), &options)
+            .err()
+            .unwrap();
+        assert_eq!(SfErrorKind::CorruptData, error.kind());
+        assert_eq!(
+            Some(&SfErrorLocation::ElementPath("/GAML/integrity".to_owned())),
+            error.location()
+        );
+
+        let xml = b"<GAML version=\"1.20\"><integrity algorithm=\"CRC32\">0</integrity></GAML>";
+        let error = GamlParser::parse_with_options("test.gaml", Cursor::new(xml), &options)
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains("Unsupported"));
+
+        let xml = b"<GAML version=\"1.20\"></GAML>";
+        assert!(GamlParser::parse_with_options("test.gaml", Cursor::new(xml), &options).is_ok());
+    }
+
     #[test]
     fn parses_supported_gaml_versions() {
         let xml_gaml_1_00 = b"<GAML version=\"1.00\"></GAML>";
@@ -1738,6 +2662,174 @@ mod tests {
         assert!(source_err.is_some());
     }
 
+    #[test]
+    fn parsing_error_provides_element_path() {
+        let xml = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n
+                        <GAML version=\"1.20\" name=\"Gaml test file\">\n
+                            <experiment>
+                                <trace name=\"Trace 0\" technique=\"UNKNOWN\">
+                                </trace>
+                                <trace name=\"Trace 1\" technique=\"ILLEGAL_TECHNIQUE\">
+                                </trace>
+                            </experiment>
+                        </GAML>";
+        let cursor = Cursor::new(xml);
+
+        let gaml_err = GamlParser::parse("test.gaml", cursor).unwrap_err();
+        assert_eq!(SfErrorKind::CorruptData, gaml_err.kind());
+        assert_eq!(
+            Some(&SfErrorLocation::ElementPath(
+                "/GAML/experiment[0]/trace[1]".to_owned()
+            )),
+            gaml_err.location()
+        );
+    }
+
+    #[test]
+    fn parses_experiments_and_traces_on_demand() {
+        // experiment name="Experiment Ä", parameter value "ä", trace name="Trace Ö"
+        let xml = b"<?xml version=\"1.0\" encoding=\"iso-8859-1\"?>\n
+                        <GAML version=\"1.20\">\n
+                            <experiment name=\"Experiment \xc4\">
+                                <parameter name=\"param\">\xe4</parameter>
+                                <trace name=\"Trace \xd6\" technique=\"UNKNOWN\">
+                                    <Xdata units=\"UNKNOWN\" linkid=\"x0\">
+                                        <values format=\"FLOAT64\" byteorder=\"INTEL\">AAAAAAAA8D8=</values>
+                                        <Ydata units=\"UNKNOWN\">
+                                            <link linkref=\"x0\"/>
+                                            <values format=\"FLOAT64\" byteorder=\"INTEL\">AAAAAAAAAEA=</values>
+                                        </Ydata>
+                                    </Xdata>
+                                </trace>
+                                <trace name=\"Trace 1\" technique=\"ILLEGAL_TECHNIQUE\">
+                                </trace>
+                            </experiment>
+                        </GAML>";
+        let options = ParseOptions {
+            on_demand: true,
+            ..Default::default()
+        };
+
+        let gaml = GamlParser::parse_with_options("test.gaml", Cursor::new(xml), &options).unwrap();
+        assert!(gaml.experiments.is_empty());
+        let index = gaml.index.unwrap();
+        assert_eq!(1, index.experiments.len());
+        assert_eq!(Some("Experiment Ä".into()), index.experiments[0].name);
+        let traces = &index.experiments[0].traces;
+        assert_eq!(2, traces.len());
+        assert_eq!(Some("Trace Ö".into()), traces[0].name);
+        assert_eq!(
+            TraceLinks {
+                coordinates: vec![],
+                x_data: vec![XdataLinks {
+                    x_data: ElementLinks::new(Some("x0".into())),
+                    alt_x_data: vec![],
+                    y_data: vec![ElementLinks {
+                        linkid: None,
+                        links: vec![Link {
+                            linkref: "x0".into()
+                        }],
+                    }],
+                }],
+            },
+            traces[0].links
+        );
+        assert_eq!(Some("Trace 1".into()), traces[1].name);
+
+        let experiment = index.read_experiment(0).unwrap();
+        assert_eq!(Some("Experiment Ä".into()), experiment.name);
+        assert_eq!(Some("ä".into()), experiment.parameters[0].value);
+        assert!(experiment.traces.is_empty());
+
+        let trace = index.read_trace(0, 0).unwrap();
+        assert_eq!(Some("Trace Ö".into()), trace.name);
+        assert_eq!(vec![1.0], trace.x_data[0].values.get_data().unwrap());
+        assert_eq!(
+            vec![2.0],
+            trace.x_data[0].y_data[0].values.get_data().unwrap()
+        );
+
+        let error = index.read_trace(0, 1).unwrap_err();
+        assert_eq!(SfErrorKind::CorruptData, error.kind());
+        assert!(error.to_string().contains("ILLEGAL_TECHNIQUE"));
+        assert_eq!(
+            Some(&SfErrorLocation::ElementPath(
+                "/GAML/experiment[0]/trace[1]".to_owned()
+            )),
+            error.location()
+        );
+        let error = index.read_trace(0, 2).unwrap_err();
+        assert_eq!(SfErrorKind::IllegalNodePath, error.kind());
+        let error = index.read_experiment(1).unwrap_err();
+        assert_eq!(SfErrorKind::IllegalNodePath, error.kind());
+    }
+
+    #[test]
+    fn checks_traces_parsed_on_demand_strictly() {
+        let xml = "<GAML version=\"1.00\">
+                       <experiment>
+                           <trace technique=\"UNKNOWN\">
+                               <Xdata units=\"UNKNOWN\">
+                                   <values format=\"FLOAT64\" byteorder=\"INTEL\">AAAAAAAA8D8=</values>
+                                   <Ydata units=\"UNKNOWN\">
+                                       <values format=\"FLOAT64\" byteorder=\"INTEL\">AAAAAAAAAEA=</values>
+                                       <peaktable></peaktable>
+                                   </Ydata>
+                               </Xdata>
+                           </trace>
+                       </experiment>
+                   </GAML>";
+        let options = ParseOptions {
+            on_demand: true,
+            ..Default::default()
+        };
+        let gaml = GamlParser::parse_with_options("test.gaml", Cursor::new(xml), &options).unwrap();
+        assert!(gaml.index.unwrap().read_trace(0, 0).is_ok());
+
+        let options = ParseOptions {
+            on_demand: true,
+            ..ParseOptions::strict()
+        };
+        let gaml = GamlParser::parse_with_options("test.gaml", Cursor::new(xml), &options).unwrap();
+        let error = gaml.index.unwrap().read_trace(0, 0).unwrap_err();
+        assert_eq!(SfErrorKind::CorruptData, error.kind());
+        assert_eq!(
+            Some(&SfErrorLocation::ElementPath(
+                "/GAML/experiment[0]/trace[0]/Xdata[0]/Ydata[0]/peaktable[0]".to_owned()
+            )),
+            error.location()
+        );
+        let error =
+            GamlParser::parse_with_options("test.gaml", Cursor::new(xml), &ParseOptions::strict())
+                .unwrap_err();
+        assert_eq!(SfErrorKind::CorruptData, error.kind());
+    }
+
+    #[test]
+    fn fails_to_parse_illegal_xml_structure_on_demand() {
+        let xml = "<GAML version=\"1.20\">
+                       <experiment>
+                           <trace technique=\"UNKNOWN\">
+                               <Xdata>
+                           </trace>
+                       </experiment>
+                   </GAML>";
+        let options = ParseOptions {
+            on_demand: true,
+            ..Default::default()
+        };
+
+        let error =
+            GamlParser::parse_with_options("test.gaml", Cursor::new(xml), &options).unwrap_err();
+        assert_eq!(SfErrorKind::CorruptData, error.kind());
+        assert_eq!(
+            Some(&SfErrorLocation::ElementPath(
+                "/GAML/experiment[0]/trace[0]".to_owned()
+            )),
+            error.location()
+        );
+    }
+
     #[test]
     fn fails_to_parse_illegal_coordinates_unit_attribute() {
         let xml = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n
@@ -1888,4 +2980,32 @@ mod tests {
         assert!(gaml_err.to_string().contains("numvalues"));
         assert!(gaml_err.to_string().contains("values"));
     }
+
+    #[test]
+    fn provides_values_ranges() {
+        let f64_values: Vec<f64> = (0..10).map(|i| i as f64 * 1.5).collect();
+        let f64_bytes: Vec<u8> = f64_values.iter().flat_map(|v| v.to_le_bytes()).collect();
+        let f32_values: Vec<f32> = (0..11).map(|i| i as f32 * -0.5).collect();
+        let f32_bytes: Vec<u8> = f32_values.iter().flat_map(|v| v.to_le_bytes()).collect();
+        let f32_values: Vec<f64> = f32_values.into_iter().map(|v| v as f64).collect();
+
+        for (bytes, format, expected) in [
+            (f64_bytes, Format::Float64, f64_values),
+            (f32_bytes, Format::Float32, f32_values),
+        ] {
+            let mut values = Values::create_values_with(&bytes, format, Byteorder::Intel);
+            assert_eq!(expected.len(), values.get_len().unwrap());
+            values.numvalues = None;
+            assert_eq!(expected.len(), values.get_len().unwrap());
+            for start in 0..expected.len() {
+                for end in start..=expected.len() {
+                    assert_eq!(
+                        expected[start..end],
+                        values.get_data_range(start..end).unwrap()
+                    );
+                }
+            }
+            assert!(values.get_data_range(0..expected.len() + 1).is_err());
+        }
+    }
 }
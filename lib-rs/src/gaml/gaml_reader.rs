@@ -18,15 +18,24 @@
 // CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
 use super::gaml_parser::{
-    AltXdata, Basecurve, Coordinates, Experiment, Gaml, Peak, Peaktable, Trace, Units, Values,
-    Xdata, Ydata,
+    AltXdata, Basecurve, Coordinates, ElementLinks, Experiment, Gaml, Peak, Peaktable, Trace,
+    TraceLinks, Units, Values, Xdata, Ydata,
 };
 use crate::{
-    api::{Column, Node, Parameter, PointXy, Reader, Table, Value},
-    common::SfError,
-    utils::convert_path_to_node_indices,
+    api::{
+        Axis, Column, NdArray, Node, NodeLink, Parameter, ParseWarning, PointXy, Reader, Table,
+        Value,
+    },
+    common::{SfError, SfErrorKind},
+    utils::{check_data_range, convert_path_to_node_indices, extract_data_range, lock_input},
+};
+use std::{
+    collections::HashMap,
+    ops::{Deref, Range},
+    path::Path,
+    sync::{Arc, Mutex},
+    vec,
 };
-use std::{collections::HashMap, path::Path, vec};
 
 macro_rules! generate_map_xy_parameters_fn {
     ($xy_data_type:ty, $xy_type_name:literal, $fn_name:ident) => {
@@ -66,6 +75,9 @@ macro_rules! generate_map_xy_parameters_fn {
             if let Some(label) = &y_data.label {
                 parameters.push(Parameter::from_str_str("Ydata label", label));
             }
+            if let Some(linkid) = &y_data.linkid {
+                parameters.push(Parameter::from_str_str("Ydata linkid", linkid));
+            }
             parameters.extend(map_coordinates_attributes_to_parameters(coordinates));
             // elements
             for link in &x_data.links {
@@ -74,6 +86,9 @@ macro_rules! generate_map_xy_parameters_fn {
                     &link.linkref,
                 ));
             }
+            for link in &y_data.links {
+                parameters.push(Parameter::from_str_str("Ydata linkref", &link.linkref));
+            }
             parameters.extend(map_coordinates_linkrefs_to_parameters(coordinates));
             parameters.extend(map_gaml_parameters_with_prefix(
                 &format!("{} ", $xy_type_name),
@@ -99,24 +114,133 @@ macro_rules! generate_map_xy_parameters_fn {
 pub struct GamlReader {
     path: String,
     file: Gaml,
+    link_index: LinkIndex,
+    /// The experiment and trace indices and the last trace parsed on demand.
+    trace_cache: Mutex<Option<(usize, usize, Arc<Trace>)>>,
+}
+
+/// An element with a linkid or link elements, and the path of the node representing it.
+///
+/// Coordinates are represented by the trace node holding them, Xdata, altXdata, and Ydata by
+/// the xy data nodes holding their values.
+#[derive(Debug, PartialEq)]
+struct LinkableElement {
+    relation: &'static str,
+    path: String,
+    linkid: Option<String>,
+    linkrefs: Vec<String>,
+}
+
+/// The linkable elements of a file, indexed by node path, linkid, and linkref.
+#[derive(Debug, Default)]
+struct LinkIndex {
+    elements: Vec<LinkableElement>,
+    by_path: HashMap<String, Vec<usize>>,
+    by_linkid: HashMap<String, Vec<usize>>,
+    by_linkref: HashMap<String, Vec<usize>>,
+}
+
+impl LinkIndex {
+    fn new(elements: Vec<LinkableElement>) -> Self {
+        let mut index = Self::default();
+        for (i, element) in elements.iter().enumerate() {
+            index
+                .by_path
+                .entry(element.path.clone())
+                .or_default()
+                .push(i);
+            if let Some(linkid) = &element.linkid {
+                index.by_linkid.entry(linkid.clone()).or_default().push(i);
+            }
+            for linkref in &element.linkrefs {
+                let indices = index.by_linkref.entry(linkref.clone()).or_default();
+                if indices.last() != Some(&i) {
+                    indices.push(i);
+                }
+            }
+        }
+        index.elements = elements;
+        index
+    }
+
+    /// Provides the elements with the indices found for a key, in file order.
+    fn get<'a>(
+        &'a self,
+        map: &'a HashMap<String, Vec<usize>>,
+        key: &str,
+    ) -> impl Iterator<Item = &'a LinkableElement> {
+        map.get(key)
+            .into_iter()
+            .flatten()
+            .map(|&i| &self.elements[i])
+    }
+}
+
+/// An element either held by the parsed file or parsed on demand.
+enum Element<'a, T> {
+    Parsed(&'a T),
+    OnDemand(Arc<T>),
+}
+
+impl<T> Deref for Element<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        match self {
+            Element::Parsed(element) => element,
+            Element::OnDemand(element) => element,
+        }
+    }
 }
 
 impl Reader for GamlReader {
     fn read(&self, path: &str) -> Result<Node, SfError> {
+        self.read_node(path)
+            .map_err(|e| e.or_kind(SfErrorKind::CorruptData))
+    }
+
+    fn read_data_len(&self, path: &str) -> Result<usize, SfError> {
+        self.read_node_data_len(path)
+            .map_err(|e| e.or_kind(SfErrorKind::CorruptData))
+    }
+
+    fn read_data(&self, path: &str, range: Range<usize>) -> Result<Vec<PointXy>, SfError> {
+        self.read_node_data(path, range)
+            .map_err(|e| e.or_kind(SfErrorKind::CorruptData))
+    }
+
+    fn get_warnings(&self) -> &[ParseWarning] {
+        &self.file.warnings
+    }
+}
+
+impl GamlReader {
+    pub fn new(path: &str, file: Gaml) -> Self {
+        let link_index = LinkIndex::new(collect_linkable_elements(&file));
+        Self {
+            path: path.to_owned(),
+            file,
+            link_index,
+            trace_cache: Mutex::new(None),
+        }
+    }
+
+    fn read_node(&self, path: &str) -> Result<Node, SfError> {
         let path_indices = convert_path_to_node_indices(path)?;
         match &path_indices[..] {
             [] => Ok(Self::map_root(&self.path, &self.file)?), // "", "/"
             [exp_idx, tail @ ..] => {
-                let experiment =
-                    read_item_at_index(&self.file.experiments, *exp_idx, "experiment")?;
                 if tail.is_empty() {
-                    return Self::map_experiment(experiment, *exp_idx);
+                    return self.read_experiment_node(*exp_idx);
                 }
 
                 let (trace_idx, tail) = tail.split_first().unwrap();
-                let trace = read_item_at_index(&experiment.traces, *trace_idx, "trace")?;
+                let trace = self.read_trace(*exp_idx, *trace_idx)?;
+                let trace = &*trace;
                 if tail.is_empty() {
-                    return Self::map_trace(trace, *trace_idx);
+                    let mut node = Self::map_trace(trace, *trace_idx)?;
+                    node.links = self.map_links(&path_indices);
+                    return Ok(node);
                 }
 
                 let (xy_data_idx, tail) = tail.split_first().unwrap();
@@ -125,26 +249,21 @@ impl Reader for GamlReader {
                 let x_data = read_item_at_index(&trace.x_data, x_data_idx, "Xdata")?;
                 if tail.is_empty() {
                     let coordinates = trace.coordinates.as_slice();
-                    match alt_x_data_idx {
-                        None => {
-                            return Self::map_xy_data(
-                                x_data,
-                                (x_data_idx, y_data_idx),
-                                coordinates,
-                            );
-                        }
-                        Some(alt_x_idx) => {
-                            return Self::map_alt_xy_data(
-                                x_data,
-                                (x_data_idx, alt_x_idx, y_data_idx),
-                                coordinates,
-                            );
-                        }
-                    }
+                    let mut node = match alt_x_data_idx {
+                        None => Self::map_xy_data(x_data, (x_data_idx, y_data_idx), coordinates)?,
+                        Some(alt_x_idx) => Self::map_alt_xy_data(
+                            x_data,
+                            (x_data_idx, alt_x_idx, y_data_idx),
+                            coordinates,
+                        )?,
+                    };
+                    node.links = self.map_links(&path_indices);
+                    return Ok(node);
                 }
                 if alt_x_data_idx.is_some() {
                     // no children for altXdata
-                    return Err(SfError::new(&format!("Illegal node path: {}", path)));
+                    return Err(SfError::new(&format!("Illegal node path: {}", path))
+                        .with_kind(SfErrorKind::IllegalNodePath));
                 }
 
                 let (peaktable_idx, tail) = tail.split_first().unwrap();
@@ -161,29 +280,157 @@ impl Reader for GamlReader {
                     return Self::map_basecurve(basecurve, peak_index, peak.number);
                 }
 
-                Err(SfError::new(&format!("Illegal node path: {}", path)))
+                Err(SfError::new(&format!("Illegal node path: {}", path))
+                    .with_kind(SfErrorKind::IllegalNodePath))
             }
         }
     }
-}
 
-impl GamlReader {
-    pub fn new(path: &str, file: Gaml) -> Self {
-        Self {
-            path: path.to_owned(),
-            file,
+    fn read_experiment_node(&self, exp_idx: usize) -> Result<Node, SfError> {
+        match &self.file.index {
+            None => {
+                let experiment = read_item_at_index(&self.file.experiments, exp_idx, "experiment")?;
+                let trace_names = generate_child_node_names(&experiment.traces, &|trace, i| {
+                    Self::generate_trace_name(trace.name.as_deref(), i)
+                });
+                Self::map_experiment(experiment, exp_idx, trace_names)
+            }
+            Some(index) => {
+                let experiment = index.read_experiment(exp_idx)?;
+                let trace_names =
+                    generate_child_node_names(&index.experiments[exp_idx].traces, &|trace, i| {
+                        Self::generate_trace_name(trace.name.as_deref(), i)
+                    });
+                Self::map_experiment(&experiment, exp_idx, trace_names)
+            }
+        }
+    }
+
+    // the trace is only Send with the "sync" feature enabled
+    #[allow(clippy::arc_with_non_send_sync)]
+    fn read_trace(&self, exp_idx: usize, trace_idx: usize) -> Result<Element<'_, Trace>, SfError> {
+        match &self.file.index {
+            None => {
+                let experiment = read_item_at_index(&self.file.experiments, exp_idx, "experiment")?;
+                let trace = read_item_at_index(&experiment.traces, trace_idx, "trace")?;
+                Ok(Element::Parsed(trace))
+            }
+            Some(index) => {
+                // reading a node and its data typically reads the same trace repeatedly
+                let mut cache = lock_input(&self.trace_cache);
+                if let Some((cached_exp_idx, cached_trace_idx, trace)) = cache.as_ref()
+                    && *cached_exp_idx == exp_idx
+                    && *cached_trace_idx == trace_idx
+                {
+                    return Ok(Element::OnDemand(Arc::clone(trace)));
+                }
+                let trace = Arc::new(index.read_trace(exp_idx, trace_idx)?);
+                *cache = Some((exp_idx, trace_idx, Arc::clone(&trace)));
+                Ok(Element::OnDemand(trace))
+            }
+        }
+    }
+
+    /// Maps the links from and to the elements represented by a node to links to the nodes
+    /// representing the linked elements.
+    fn map_links(&self, path_indices: &[usize]) -> Vec<NodeLink> {
+        let path: String = path_indices.iter().map(|i| format!("/{i}")).collect();
+        let mut links = Vec::<NodeLink>::new();
+        let mut add_link = |link: NodeLink| {
+            if !links.contains(&link) {
+                links.push(link);
+            }
+        };
+        let index = &self.link_index;
+        for element in index.get(&index.by_path, &path) {
+            for linkref in &element.linkrefs {
+                for target in index.get(&index.by_linkid, linkref) {
+                    if target.path != path {
+                        add_link(NodeLink::new(target.relation, &target.path));
+                    }
+                }
+            }
+            if let Some(linkid) = &element.linkid {
+                for source in index.get(&index.by_linkref, linkid) {
+                    if source.path != path {
+                        add_link(NodeLink::new(source.relation, &source.path));
+                    }
+                }
+            }
+        }
+        links
+    }
+
+    fn read_node_data_len(&self, path: &str) -> Result<usize, SfError> {
+        let len = self.read_xy_values(path, &|x_values, y_values| {
+            Ok(x_values.get_len()?.min(y_values.get_len()?))
+        })?;
+        match len {
+            Some(len) => Ok(len),
+            None => Ok(self.read(path)?.data.len()),
+        }
+    }
+
+    fn read_node_data(&self, path: &str, range: Range<usize>) -> Result<Vec<PointXy>, SfError> {
+        let data = self.read_xy_values(path, &|x_values, y_values| {
+            check_data_range(&range, x_values.get_len()?.min(y_values.get_len()?))?;
+            let x_values = x_values.get_data_range(range.clone())?;
+            let y_values = y_values.get_data_range(range.clone())?;
+            let data = x_values
+                .into_iter()
+                .zip(y_values)
+                .map(|(x, y)| PointXy::new(x, y))
+                .collect();
+            Ok(data)
+        })?;
+        match data {
+            Some(data) => Ok(data),
+            None => extract_data_range(self.read(path)?.data, &range),
         }
     }
 
+    /// Finds the x and y values for a path to an xy data node and reads from them.
+    ///
+    /// Returns None for paths to other nodes.
+    fn read_xy_values<T>(
+        &self,
+        path: &str,
+        read_fn: &dyn Fn(&Values, &Values) -> Result<T, SfError>,
+    ) -> Result<Option<T>, SfError> {
+        let path_indices = convert_path_to_node_indices(path)?;
+        let [exp_idx, trace_idx, xy_data_idx] = path_indices[..] else {
+            return Ok(None);
+        };
+        let trace = self.read_trace(exp_idx, trace_idx)?;
+        let trace = &*trace;
+        let (x_data_idx, alt_x_data_idx, y_data_idx) = find_xy_indices(trace, xy_data_idx)?;
+        let x_data = read_item_at_index(&trace.x_data, x_data_idx, "Xdata")?;
+        let y_data = read_item_at_index(&x_data.y_data, y_data_idx, "Ydata")?;
+        let x_values = match alt_x_data_idx {
+            None => &x_data.values,
+            Some(alt_x_idx) => {
+                &read_item_at_index(&x_data.alt_x_data, alt_x_idx, "altXdata")?.values
+            }
+        };
+
+        read_fn(x_values, &y_data.values).map(Some)
+    }
+
     fn map_root(path: &str, gaml: &Gaml) -> Result<Node, SfError> {
         let path = Path::new(path);
         let name = path
             .file_name()
             .map_or("", |f| f.to_str().unwrap_or(""))
             .to_owned();
-        let parameters = Self::map_root_parameters(gaml);
-        let child_node_names =
-            generate_child_node_names(&gaml.experiments, &Self::generate_experiment_name);
+        let parameters = Self::map_root_parameters(gaml)?;
+        let child_node_names = match &gaml.index {
+            None => generate_child_node_names(&gaml.experiments, &|experiment, i| {
+                Self::generate_experiment_name(experiment.name.as_deref(), i)
+            }),
+            Some(index) => generate_child_node_names(&index.experiments, &|experiment, i| {
+                Self::generate_experiment_name(experiment.name.as_deref(), i)
+            }),
+        };
 
         Ok(Node {
             name,
@@ -191,11 +438,14 @@ impl GamlReader {
             data: vec![],
             metadata: vec![],
             table: None,
+            nd_array: None,
+            complex_data: None,
+            links: vec![],
             child_node_names,
         })
     }
 
-    fn map_root_parameters(gaml: &Gaml) -> Vec<Parameter> {
+    fn map_root_parameters(gaml: &Gaml) -> Result<Vec<Parameter>, SfError> {
         let mut parameters = vec![];
         parameters.push(Parameter::from_str_str("Version", gaml.version.to_string()));
         if let Some(name) = &gaml.name {
@@ -208,17 +458,23 @@ impl GamlReader {
                 &integrity.value,
             );
             parameters.push(param);
+            parameters.push(Parameter::from_str_str(
+                "Integrity status",
+                integrity.get_status()?.to_string(),
+            ));
         }
         parameters.extend(map_gaml_parameters(&gaml.parameters));
 
-        parameters
+        Ok(parameters)
     }
 
-    fn map_experiment(experiment: &Experiment, index: usize) -> Result<Node, SfError> {
-        let name = Self::generate_experiment_name(experiment, index);
+    fn map_experiment(
+        experiment: &Experiment,
+        index: usize,
+        child_node_names: Vec<String>,
+    ) -> Result<Node, SfError> {
+        let name = Self::generate_experiment_name(experiment.name.as_deref(), index);
         let parameters = Self::map_experiment_parameters(experiment);
-        let child_node_names =
-            generate_child_node_names(&experiment.traces, &Self::generate_trace_name);
 
         Ok(Node {
             name,
@@ -226,12 +482,15 @@ impl GamlReader {
             data: vec![],
             metadata: vec![],
             table: None,
+            nd_array: None,
+            complex_data: None,
+            links: vec![],
             child_node_names,
         })
     }
 
-    fn generate_experiment_name(experiment: &Experiment, index: usize) -> String {
-        match &experiment.name {
+    fn generate_experiment_name(name: Option<&str>, index: usize) -> String {
+        match name {
             None => format!("Experiment {index}"),
             Some(experiment_name) => format!("Experiment {index}, {experiment_name}"),
         }
@@ -251,8 +510,9 @@ impl GamlReader {
     }
 
     fn map_trace(trace: &Trace, index: usize) -> Result<Node, SfError> {
-        let name = Self::generate_trace_name(trace, index);
+        let name = Self::generate_trace_name(trace.name.as_deref(), index);
         let parameters = Self::map_trace_parameters(trace);
+        let nd_array = Self::map_trace_matrix(trace);
         let child_node_names = generate_xy_names(trace)?;
 
         Ok(Node {
@@ -261,12 +521,15 @@ impl GamlReader {
             data: vec![],
             metadata: vec![],
             table: None,
+            nd_array,
+            complex_data: None,
+            links: vec![],
             child_node_names,
         })
     }
 
-    fn generate_trace_name(trace: &Trace, index: usize) -> String {
-        match &trace.name {
+    fn generate_trace_name(name: Option<&str>, index: usize) -> String {
+        match name {
             None => format!("Trace {index}"),
             Some(trace_name) => format!("Trace {index}, {trace_name}"),
         }
@@ -286,6 +549,73 @@ impl GamlReader {
         parameters
     }
 
+    /// Maps the Xdata of a trace sharing a coordinate and x values to a matrix, e.g., the
+    /// spectra of LC-DAD data at different retention times.
+    ///
+    /// The matrix holds one row per Xdata with the values of its first Ydata. The row axis
+    /// holds the values of the first coordinates with one value per Xdata, the column axis
+    /// the shared x values. Returns None if there are less than two Xdata, the Xdata do not
+    /// share a coordinate and x values, or the values cannot be decoded.
+    fn map_trace_matrix(trace: &Trace) -> Option<NdArray> {
+        let num_rows = trace.x_data.len();
+        if num_rows < 2 {
+            return None;
+        }
+        let coordinates = trace
+            .coordinates
+            .iter()
+            .find(|co| co.values.get_len().is_ok_and(|len| len == num_rows))?;
+
+        // compare the lengths before decoding any values
+        let first_x_data = &trace.x_data[0];
+        let num_columns = first_x_data.values.get_len().ok()?;
+        let has_len = |values: &Values| values.get_len().is_ok_and(|len| len == num_columns);
+        // Xdata hold at least one Ydata
+        if !trace
+            .x_data
+            .iter()
+            .all(|x_data| has_len(&x_data.values) && has_len(&x_data.y_data[0].values))
+        {
+            return None;
+        }
+
+        let x_values = first_x_data.values.get_data().ok()?;
+        let mut values = Vec::<f64>::with_capacity(num_columns * num_rows);
+        for (index, x_data) in trace.x_data.iter().enumerate() {
+            if index > 0 && x_data.values.get_data().ok()? != x_values {
+                return None;
+            }
+            values.extend(x_data.y_data[0].values.get_data().ok()?);
+        }
+
+        let first_y_data = &first_x_data.y_data[0];
+        let is_shared = |label: &Option<String>, units: &Units| {
+            trace.x_data.iter().all(|x_data| {
+                let y_data = &x_data.y_data[0];
+                &y_data.label == label && &y_data.units == units
+            })
+        };
+        let (name, unit) = match is_shared(&first_y_data.label, &first_y_data.units) {
+            true => (
+                first_y_data.label.as_deref().unwrap_or("Ydata"),
+                map_units(&first_y_data.units),
+            ),
+            false => ("Ydata", None),
+        };
+        let row_axis = Axis::new(
+            coordinates.label.as_deref().unwrap_or("Coordinate"),
+            map_units(&coordinates.units),
+            coordinates.values.get_data().ok()?,
+        );
+        let column_axis = Axis::new(
+            first_x_data.label.as_deref().unwrap_or("Xdata"),
+            map_units(&first_x_data.units),
+            x_values,
+        );
+
+        NdArray::new(name, unit, vec![row_axis, column_axis], values).ok()
+    }
+
     fn map_xy_data(
         x_data: &Xdata,
         (x_index, y_index): (usize, usize),
@@ -324,6 +654,9 @@ impl GamlReader {
             data,
             metadata,
             table: None,
+            nd_array: None,
+            complex_data: None,
+            links: vec![],
             child_node_names,
         })
     }
@@ -374,6 +707,9 @@ impl GamlReader {
             data,
             metadata,
             table: None,
+            nd_array: None,
+            complex_data: None,
+            links: vec![],
             child_node_names: vec![],
         })
     }
@@ -399,6 +735,9 @@ impl GamlReader {
             data: vec![],
             metadata: vec![],
             table: Some(table),
+            nd_array: None,
+            complex_data: None,
+            links: vec![],
             child_node_names,
         })
     }
@@ -543,6 +882,9 @@ impl GamlReader {
             data,
             metadata: vec![],
             table: None,
+            nd_array: None,
+            complex_data: None,
+            links: vec![],
             child_node_names: vec![],
         })
     }
@@ -563,9 +905,74 @@ fn find_basecurve(
             return Ok((basecurve, peak, i));
         }
     }
-    Err(SfError::new(&format!(
-        "Illegal basecurve index: {basecurve_idx}"
-    )))?
+    Err(
+        SfError::new(&format!("Illegal basecurve index: {basecurve_idx}"))
+            .with_kind(SfErrorKind::IllegalNodePath),
+    )?
+}
+
+fn collect_linkable_elements(file: &Gaml) -> Vec<LinkableElement> {
+    let mut elements = vec![];
+    match &file.index {
+        None => {
+            for (exp_idx, experiment) in file.experiments.iter().enumerate() {
+                for (trace_idx, trace) in experiment.traces.iter().enumerate() {
+                    let trace_path = format!("/{exp_idx}/{trace_idx}");
+                    push_linkable_elements(&mut elements, &trace_path, &TraceLinks::from(trace));
+                }
+            }
+        }
+        Some(index) => {
+            for (exp_idx, experiment) in index.experiments.iter().enumerate() {
+                for (trace_idx, trace) in experiment.traces.iter().enumerate() {
+                    let trace_path = format!("/{exp_idx}/{trace_idx}");
+                    push_linkable_elements(&mut elements, &trace_path, &trace.links);
+                }
+            }
+        }
+    }
+    elements
+}
+
+fn push_linkable_elements(
+    elements: &mut Vec<LinkableElement>,
+    trace_path: &str,
+    trace_links: &TraceLinks,
+) {
+    let mut push_element = |relation: &'static str, path: &str, element: &ElementLinks| {
+        if element.linkid.is_some() || !element.links.is_empty() {
+            elements.push(LinkableElement {
+                relation,
+                path: path.to_owned(),
+                linkid: element.linkid.clone(),
+                linkrefs: element
+                    .links
+                    .iter()
+                    .map(|link| link.linkref.clone())
+                    .collect(),
+            });
+        }
+    };
+    for coordinates in &trace_links.coordinates {
+        push_element("coordinates", trace_path, coordinates);
+    }
+    // same order as in find_xy_indices()
+    let mut xy_data_idx = 0usize;
+    for x_data in &trace_links.x_data {
+        for y_data in &x_data.y_data {
+            let path = format!("{trace_path}/{xy_data_idx}");
+            push_element("Xdata", &path, &x_data.x_data);
+            push_element("Ydata", &path, y_data);
+            xy_data_idx += 1;
+        }
+        for alt_x_data in &x_data.alt_x_data {
+            for _y_data in &x_data.y_data {
+                let path = format!("{trace_path}/{xy_data_idx}");
+                push_element("altXdata", &path, alt_x_data);
+                xy_data_idx += 1;
+            }
+        }
+    }
 }
 
 fn find_xy_indices(
@@ -591,9 +998,10 @@ fn find_xy_indices(
             }
         }
     }
-    Err(SfError::new(&format!(
-        "Illegal xy data index: {xy_data_idx}"
-    )))
+    Err(
+        SfError::new(&format!("Illegal xy data index: {xy_data_idx}"))
+            .with_kind(SfErrorKind::IllegalNodePath),
+    )
 }
 
 fn generate_xy_name(
@@ -799,10 +1207,17 @@ fn read_item_at_index<'a, T>(
     index: usize,
     context: &str,
 ) -> Result<&'a T, SfError> {
-    slice.get(index).ok_or(SfError::new(&format!(
-        "Illegal {} index: {}",
-        context, index
-    )))
+    slice.get(index).ok_or(
+        SfError::new(&format!("Illegal {} index: {}", context, index))
+            .with_kind(SfErrorKind::IllegalNodePath),
+    )
+}
+
+fn map_units(units: &Units) -> Option<String> {
+    match units {
+        Units::Unknown => None,
+        units => Some(units.to_string()),
+    }
 }
 
 fn generate_xy_plot_hints(
@@ -838,18 +1253,20 @@ fn generate_xy_plot_hints(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::api::Parser;
     use crate::gaml::gaml_parser::{
-        AltXdata, Baseline, Byteorder, Format, Integrity, Parameter as RawParameter, Technique,
-        Valueorder, Values, Version, Ydata,
+        AltXdata, Baseline, Byteorder, Format, GamlParser, Integrity, IntegrityStatus,
+        Parameter as RawParameter, Technique, Valueorder, Values, Version, Ydata,
     };
+    use std::io::Cursor;
 
     fn create_values_f32(data: &[f32]) -> Values {
-        let bytes: Vec<u8> = data.iter().map(|v| v.to_le_bytes()).flatten().collect();
+        let bytes: Vec<u8> = data.iter().flat_map(|v| v.to_le_bytes()).collect();
         Values::create_values_with(bytes.as_slice(), Format::Float32, Byteorder::Intel)
     }
 
     fn create_values_f64(data: &[f64]) -> Values {
-        let bytes: Vec<u8> = data.iter().map(|v| v.to_le_bytes()).flatten().collect();
+        let bytes: Vec<u8> = data.iter().flat_map(|v| v.to_le_bytes()).collect();
         Values::create_values_with(bytes.as_slice(), Format::Float64, Byteorder::Intel)
     }
 
@@ -859,10 +1276,11 @@ mod tests {
         let gaml = Gaml {
             version: Version::Version1_20,
             name: Some("GAML name".into()),
-            integrity: Some(Integrity {
-                algorithm: "SHA1".into(),
-                value: "03cfd743661f07975fa2f1220c5194cbaff48451".into(),
-            }),
+            integrity: Some(Integrity::create_integrity_with(
+                "SHA1",
+                "03cfd743661f07975fa2f1220c5194cbaff48451",
+                IntegrityStatus::Invalid,
+            )),
             parameters: vec![RawParameter {
                 group: Some("param 0 group".into()),
                 name: "param 0 name".into(),
@@ -871,6 +1289,8 @@ mod tests {
                 value: Some("param 0 value".into()),
             }],
             experiments: vec![],
+            index: None,
+            warnings: vec![],
         };
         let reader = GamlReader::new(path, gaml);
 
@@ -885,6 +1305,7 @@ mod tests {
                         "Integrity (algorithm=SHA1)",
                         "03cfd743661f07975fa2f1220c5194cbaff48451"
                     ),
+                    Parameter::from_str_str("Integrity status", "invalid"),
                     Parameter::from_str_str(
                         "param 0 name (group=param 0 group, label=param 0 label, alias=param 0 alias)",
                         "param 0 value"
@@ -893,6 +1314,9 @@ mod tests {
                 data: vec![],
                 metadata: vec![],
                 table: None,
+                nd_array: None,
+                complex_data: None,
+                links: vec![],
                 child_node_names: vec![],
             },
             root_node
@@ -908,6 +1332,8 @@ mod tests {
             integrity: None,
             parameters: vec![],
             experiments: vec![],
+            index: None,
+            warnings: vec![],
         };
         let reader = GamlReader::new(path, gaml);
 
@@ -919,6 +1345,9 @@ mod tests {
                 data: vec![],
                 metadata: vec![],
                 table: None,
+                nd_array: None,
+                complex_data: None,
+                links: vec![],
                 child_node_names: vec![],
             },
             root_node
@@ -945,6 +1374,8 @@ mod tests {
                 }],
                 traces: vec![],
             }],
+            index: None,
+            warnings: vec![],
         };
         let reader = GamlReader::new(path, gaml);
 
@@ -967,6 +1398,9 @@ mod tests {
                 data: vec![],
                 metadata: vec![],
                 table: None,
+                nd_array: None,
+                complex_data: None,
+                links: vec![],
                 child_node_names: vec![]
             },
             exp_node
@@ -999,6 +1433,8 @@ mod tests {
                     x_data: vec![],
                 }],
             }],
+            index: None,
+            warnings: vec![],
         };
         let reader = GamlReader::new(path, gaml);
 
@@ -1019,6 +1455,9 @@ mod tests {
                 data: vec![],
                 metadata: vec![],
                 table: None,
+                nd_array: None,
+                complex_data: None,
+                links: vec![],
                 child_node_names: vec![]
             },
             trace_node
@@ -1060,6 +1499,8 @@ mod tests {
                         y_data: vec![Ydata {
                             units: Units::Absorbance,
                             label: Some("ydata label".into()),
+                            linkid: None,
+                            links: vec![],
                             parameters: vec![RawParameter {
                                 group: None,
                                 name: "param 0 name".into(),
@@ -1073,6 +1514,8 @@ mod tests {
                     }],
                 }],
             }],
+            index: None,
+            warnings: vec![],
         };
         let reader = GamlReader::new(path, gaml);
 
@@ -1184,6 +1627,8 @@ mod tests {
                         y_data: vec![Ydata {
                             units: Units::Absorbance,
                             label: Some("ydata label".into()),
+                            linkid: None,
+                            links: vec![],
                             parameters: vec![RawParameter {
                                 group: None,
                                 name: "param 0 name".into(),
@@ -1197,6 +1642,8 @@ mod tests {
                     }],
                 }],
             }],
+            index: None,
+            warnings: vec![],
         };
         let reader = GamlReader::new(path, gaml);
 
@@ -1340,6 +1787,8 @@ mod tests {
                         y_data: vec![Ydata {
                             units: Units::Absorbance,
                             label: None,
+                            linkid: None,
+                            links: vec![],
                             parameters: vec![],
                             values: create_values_f64(&[10.0, 20.0, 30.0]),
                             peaktables: vec![Peaktable {
@@ -1388,6 +1837,8 @@ mod tests {
                     }],
                 }],
             }],
+            index: None,
+            warnings: vec![],
         };
         let reader = GamlReader::new(path, gaml);
 
@@ -1477,6 +1928,8 @@ mod tests {
                         y_data: vec![Ydata {
                             units: Units::Absorbance,
                             label: None,
+                            linkid: None,
+                            links: vec![],
                             parameters: vec![],
                             values: create_values_f64(&[]),
                             peaktables: vec![Peaktable {
@@ -1518,6 +1971,8 @@ mod tests {
                     }],
                 }],
             }],
+            index: None,
+            warnings: vec![],
         };
         let reader = GamlReader::new(path, gaml);
 
@@ -1594,4 +2049,203 @@ mod tests {
         assert_eq!(&None, &basecurve_node.table);
         assert!(&basecurve_node.child_node_names.is_empty());
     }
+
+    #[test]
+    fn maps_links_in_both_directions() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<GAML version="1.20">
+  <experiment>
+    <trace technique="UNKNOWN">
+      <Xdata units="MINUTES" linkid="x0">
+        <values byteorder="INTEL" format="FLOAT32" numvalues="1">AACAPw==</values>
+        <Ydata units="ABSORBANCE" linkid="y0">
+          <link linkref="y1"/>
+          <link linkref="missing"/>
+          <values byteorder="INTEL" format="FLOAT32" numvalues="1">AABAQA==</values>
+        </Ydata>
+      </Xdata>
+    </trace>
+  </experiment>
+  <experiment>
+    <trace technique="UNKNOWN">
+      <Xdata units="MINUTES">
+        <link linkref="x0"/>
+        <values byteorder="INTEL" format="FLOAT32" numvalues="1">AACAPw==</values>
+        <Ydata units="ABSORBANCE" linkid="y1">
+          <values byteorder="INTEL" format="FLOAT32" numvalues="1">AABAQA==</values>
+        </Ydata>
+      </Xdata>
+    </trace>
+  </experiment>
+</GAML>"#;
+        let gaml = GamlParser::parse("links.gaml", Cursor::new(xml.as_bytes().to_vec())).unwrap();
+        assert_eq!(
+            Some("y0".to_owned()),
+            gaml.experiments[0].traces[0].x_data[0].y_data[0].linkid
+        );
+        let reader = GamlReader::new("links.gaml", gaml);
+
+        let y_parameters = reader.read("/0/0/0").unwrap().parameters;
+        assert!(y_parameters.contains(&Parameter::from_str_str("Ydata linkid", "y0")));
+        assert!(y_parameters.contains(&Parameter::from_str_str("Ydata linkref", "y1")));
+
+        // unresolved linkref is ignored
+        assert_eq!(
+            vec![
+                NodeLink::new("Xdata", "/1/0/0"),
+                NodeLink::new("Ydata", "/1/0/0"),
+            ],
+            reader.read("/0/0/0").unwrap().links
+        );
+        assert_eq!(
+            vec![
+                NodeLink::new("Xdata", "/0/0/0"),
+                NodeLink::new("Ydata", "/0/0/0"),
+            ],
+            reader.read("/1/0/0").unwrap().links
+        );
+        // traces without coordinates have no links
+        assert!(reader.read("/0/0").unwrap().links.is_empty());
+    }
+
+    #[test]
+    fn maps_xdata_sharing_coordinates_to_matrix() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<GAML version="1.20">
+  <experiment>
+    <trace technique="PDA">
+      <coordinates units="UNKNOWN">
+        <values byteorder="INTEL" format="FLOAT32" numvalues="2">AACAPwAAAEA=</values>
+      </coordinates>
+      <coordinates units="MINUTES" label="Retention time">
+        <values byteorder="INTEL" format="FLOAT32" numvalues="3">AACAPwAAwD8AAABA</values>
+      </coordinates>
+      <Xdata units="NANOMETERS" label="Wavelength">
+        <values byteorder="INTEL" format="FLOAT32" numvalues="3">AABIQwAAekMAAJZD</values>
+        <Ydata units="MILLIABSORBANCE" label="Absorbance">
+          <values byteorder="INTEL" format="FLOAT32" numvalues="3">AACAPwAAAEAAAEBA</values>
+        </Ydata>
+      </Xdata>
+      <Xdata units="NANOMETERS" label="Wavelength">
+        <values byteorder="INTEL" format="FLOAT32" numvalues="3">AABIQwAAekMAAJZD</values>
+        <Ydata units="MILLIABSORBANCE" label="Absorbance">
+          <values byteorder="INTEL" format="FLOAT32" numvalues="3">AACAQAAAoEAAAMBA</values>
+        </Ydata>
+      </Xdata>
+      <Xdata units="NANOMETERS" label="Wavelength">
+        <values byteorder="INTEL" format="FLOAT32" numvalues="3">AABIQwAAekMAAJZD</values>
+        <Ydata units="MILLIABSORBANCE" label="Absorbance">
+          <values byteorder="INTEL" format="FLOAT32" numvalues="3">AADgQAAAAEEAABBB</values>
+        </Ydata>
+      </Xdata>
+    </trace>
+    <trace technique="PDA">
+      <coordinates units="MINUTES" label="Retention time">
+        <values byteorder="INTEL" format="FLOAT32" numvalues="3">AACAPwAAwD8AAABA</values>
+      </coordinates>
+      <Xdata units="NANOMETERS" label="Wavelength">
+        <values byteorder="INTEL" format="FLOAT32" numvalues="3">AABIQwAAekMAAJZD</values>
+        <Ydata units="MILLIABSORBANCE" label="Absorbance">
+          <values byteorder="INTEL" format="FLOAT32" numvalues="3">AACAPwAAAEAAAEBA</values>
+        </Ydata>
+      </Xdata>
+      <Xdata units="NANOMETERS" label="Wavelength">
+        <values byteorder="INTEL" format="FLOAT32" numvalues="3">AABIQwAAekMAgJZD</values>
+        <Ydata units="MILLIABSORBANCE" label="Absorbance">
+          <values byteorder="INTEL" format="FLOAT32" numvalues="3">AACAQAAAoEAAAMBA</values>
+        </Ydata>
+      </Xdata>
+      <Xdata units="NANOMETERS" label="Wavelength">
+        <values byteorder="INTEL" format="FLOAT32" numvalues="3">AABIQwAAekMAAJZD</values>
+        <Ydata units="MILLIABSORBANCE" label="Absorbance">
+          <values byteorder="INTEL" format="FLOAT32" numvalues="3">AADgQAAAAEEAABBB</values>
+        </Ydata>
+      </Xdata>
+    </trace>
+    <trace technique="PDA">
+      <Xdata units="NANOMETERS" label="Wavelength">
+        <values byteorder="INTEL" format="FLOAT32" numvalues="3">AABIQwAAekMAAJZD</values>
+        <Ydata units="MILLIABSORBANCE" label="Absorbance">
+          <values byteorder="INTEL" format="FLOAT32" numvalues="3">AACAPwAAAEAAAEBA</values>
+        </Ydata>
+      </Xdata>
+      <Xdata units="NANOMETERS" label="Wavelength">
+        <values byteorder="INTEL" format="FLOAT32" numvalues="3">AABIQwAAekMAAJZD</values>
+        <Ydata units="MILLIABSORBANCE" label="Absorbance">
+          <values byteorder="INTEL" format="FLOAT32" numvalues="3">AACAQAAAoEAAAMBA</values>
+        </Ydata>
+      </Xdata>
+      <Xdata units="NANOMETERS" label="Wavelength">
+        <values byteorder="INTEL" format="FLOAT32" numvalues="3">AABIQwAAekMAAJZD</values>
+        <Ydata units="MILLIABSORBANCE" label="Absorbance">
+          <values byteorder="INTEL" format="FLOAT32" numvalues="3">AADgQAAAAEEAABBB</values>
+        </Ydata>
+      </Xdata>
+    </trace>
+    <trace technique="PDA">
+      <coordinates units="MINUTES" label="Retention time">
+        <values byteorder="INTEL" format="FLOAT32" numvalues="2">AACAPwAAwD8=</values>
+      </coordinates>
+      <Xdata units="NANOMETERS" label="Wavelength">
+        <values byteorder="INTEL" format="FLOAT32" numvalues="3">AABIQwAAekMAAJZD</values>
+        <Ydata units="MILLIABSORBANCE" label="Absorbance">
+          <values byteorder="INTEL" format="FLOAT32" numvalues="3">AACAPwAAAEAAAEBA</values>
+        </Ydata>
+      </Xdata>
+      <Xdata units="NANOMETERS" label="Wavelength">
+        <values byteorder="INTEL" format="FLOAT32" numvalues="3">AABIQwAAekMAAJZD</values>
+        <Ydata units="MILLIABSORBANCE" label="Absorbance">
+          <values byteorder="INTEL" format="FLOAT32" numvalues="3">!!!!!!!!!!!!!!!!</values>
+        </Ydata>
+      </Xdata>
+    </trace>
+  </experiment>
+</GAML>"#;
+        let gaml = GamlParser::parse("matrix.gaml", Cursor::new(xml.as_bytes().to_vec())).unwrap();
+        let reader = GamlReader::new("matrix.gaml", gaml);
+
+        let trace_node = reader.read("/0/0").unwrap();
+        let matrix = trace_node.nd_array.unwrap();
+        assert_eq!("Absorbance", matrix.name);
+        assert_eq!(Some("MILLIABSORBANCE".to_owned()), matrix.unit);
+        assert_eq!(
+            vec![
+                Axis::new(
+                    "Retention time",
+                    Some("MINUTES".to_owned()),
+                    vec![1.0, 1.5, 2.0]
+                ),
+                Axis::new(
+                    "Wavelength",
+                    Some("NANOMETERS".to_owned()),
+                    vec![200.0, 250.0, 300.0]
+                ),
+            ],
+            matrix.axes
+        );
+        assert_eq!(
+            vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0],
+            matrix.values
+        );
+        // chromatogram at 250 nm
+        assert_eq!(vec![2.0, 5.0, 8.0], matrix.select(1, 1).unwrap().values);
+        // rows remain available as xy data nodes
+        assert_eq!(3, trace_node.child_node_names.len());
+        assert_eq!(
+            vec![
+                PointXy::new(200.0, 4.0),
+                PointXy::new(250.0, 5.0),
+                PointXy::new(300.0, 6.0)
+            ],
+            reader.read("/0/0/1").unwrap().data
+        );
+
+        // differing x values
+        assert!(reader.read("/0/1").unwrap().nd_array.is_none());
+        // no coordinates
+        assert!(reader.read("/0/2").unwrap().nd_array.is_none());
+        // corrupt Ydata values
+        assert!(reader.read("/0/3").unwrap().nd_array.is_none());
+        assert!(reader.read("/0/3/1").is_err());
+    }
 }
@@ -19,22 +19,20 @@
 
 use super::{gaml_parser::GamlParser, gaml_reader::GamlReader};
 use crate::{
-    api::{Parser, Reader, Scanner, SeekRead},
+    api::{Confidence, ParseOptions, Reader, Scanner, SeekRead},
     common::SfError,
-    utils::is_recognized_extension,
-};
-use std::{
-    cmp,
-    error::Error,
-    io::{Read, Seek, SeekFrom},
+    utils::{determine_confidence, is_recognized_extension},
 };
+use std::{cmp, error::Error, io::SeekFrom};
 
 #[derive(Default)]
-pub struct GamlScanner {}
+pub struct GamlScanner {
+    options: ParseOptions,
+}
 
 impl GamlScanner {
     const ACCEPTED_EXTENSIONS: [&'static str; 1] = ["gaml"];
-    const MAGIC_BYTES: &'static [u8; 4] = b"GAML";
+    const MAGIC_BYTES: &'static [u8; 5] = b"<GAML";
     const NUM_START_BYTES: u64 = 128;
 }
 
@@ -43,10 +41,10 @@ impl GamlScanner {
         Self::default()
     }
 
-    fn read_start<T: Seek + Read + 'static>(
+    fn read_start<T: SeekRead + 'static>(
         &self,
         input: &mut T,
-    ) -> Result<Vec<u8>, Box<dyn Error>> {
+    ) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> {
         let len = input.seek(SeekFrom::End(0))?;
         input.seek(SeekFrom::Start(0))?;
         let len = cmp::min(len, Self::NUM_START_BYTES);
@@ -55,15 +53,9 @@ impl GamlScanner {
 
         Ok(buf)
     }
-}
 
-impl<T: Seek + Read + 'static> Scanner<T> for GamlScanner {
-    fn is_recognized(&self, path: &str, input: &mut T) -> bool {
-        if !is_recognized_extension(path, &Self::ACCEPTED_EXTENSIONS) {
-            return false;
-        };
-
-        // start of file contains magic bytes "GAML"?
+    /// Checks whether the start of the input contains the magic bytes "<GAML".
+    fn is_recognized_content<T: SeekRead + 'static>(&self, input: &mut T) -> bool {
         match self.read_start(input) {
             Err(_) => false,
             Ok(bytes) => {
@@ -74,10 +66,32 @@ impl<T: Seek + Read + 'static> Scanner<T> for GamlScanner {
             }
         }
     }
+}
+
+impl<T: SeekRead + 'static> Scanner<T> for GamlScanner {
+    fn is_recognized(&self, path: &str, input: &mut T) -> bool {
+        is_recognized_extension(path, &Self::ACCEPTED_EXTENSIONS)
+            && self.is_recognized_content(input)
+    }
+
+    fn get_confidence(&self, path: &str, input: &mut T) -> Option<Confidence> {
+        determine_confidence(
+            is_recognized_extension(path, &Self::ACCEPTED_EXTENSIONS),
+            self.is_recognized_content(input),
+        )
+    }
+
+    fn get_name(&self) -> &str {
+        "GAML"
+    }
+
+    fn set_parse_options(&mut self, options: &ParseOptions) {
+        self.options = options.clone();
+    }
 
     fn get_reader(&self, path: &str, input: T) -> Result<Box<dyn Reader>, SfError> {
         let input_seek_read: Box<dyn SeekRead> = Box::new(input);
-        let gaml = GamlParser::parse(path, input_seek_read)?;
+        let gaml = GamlParser::parse_with_options(path, input_seek_read, &self.options)?;
         Ok(Box::new(GamlReader::new(path, gaml)))
     }
 }
@@ -95,7 +109,7 @@ mod tests {
         let mut reader = Cursor::new(gaml);
         let scanner = GamlScanner::new();
 
-        assert_eq!(true, scanner.is_recognized(path, &mut reader));
+        assert!(scanner.is_recognized(path, &mut reader));
     }
 
     #[test]
@@ -106,7 +120,30 @@ mod tests {
         let mut reader = Cursor::new(gaml);
         let scanner = GamlScanner::new();
 
-        assert_eq!(true, scanner.is_recognized(path, &mut reader));
+        assert!(scanner.is_recognized(path, &mut reader));
+    }
+
+    #[test]
+    fn provides_confidence() {
+        let gaml = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n
+                            <GAML version=\"1.20\" name=\"Gaml test file\"></GAML>";
+        let scanner = GamlScanner::new();
+
+        let mut reader = Cursor::new(gaml);
+        let confidence = scanner.get_confidence("valid.gaml", &mut reader);
+        assert_eq!(Some(Confidence::High), confidence);
+
+        let mut reader = Cursor::new("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<a></a>");
+        let confidence = scanner.get_confidence("invalid.gaml", &mut reader);
+        assert_eq!(Some(Confidence::Low), confidence);
+
+        let mut reader = Cursor::new(gaml);
+        let confidence = scanner.get_confidence("valid.notgaml", &mut reader);
+        assert_eq!(Some(Confidence::Medium), confidence);
+
+        let mut reader = Cursor::new("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<a></a>");
+        let confidence = scanner.get_confidence("invalid.notgaml", &mut reader);
+        assert_eq!(None, confidence);
     }
 
     #[test]
@@ -117,7 +154,7 @@ mod tests {
         let mut reader = Cursor::new(gaml);
         let scanner = GamlScanner::new();
 
-        assert_eq!(false, scanner.is_recognized(path, &mut reader));
+        assert!(!scanner.is_recognized(path, &mut reader));
     }
 
     #[test]
@@ -128,7 +165,7 @@ mod tests {
         let mut reader = Cursor::new(gaml);
         let scanner = GamlScanner::new();
 
-        assert_eq!(false, scanner.is_recognized(path, &mut reader));
+        assert!(!scanner.is_recognized(path, &mut reader));
     }
 
     #[test]
@@ -17,6 +17,7 @@
 // IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
 // CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
+use super::jdx_parser::Abscissa;
 use super::jdx_utils::BinBufRead;
 use crate::common::SfError;
 use crate::{
@@ -38,12 +39,17 @@ enum TokenType {
     Missing,
 }
 
+/// Parses (X++(Y..Y)) data.
+///
+/// If a limit is given, decoding stops once this number of points is available.
+#[allow(clippy::too_many_arguments)]
 pub fn parse_xppyy_data<T: SeekBufRead>(
     label: &str,
     first_x: f64,
     last_x: f64,
     y_factor: f64,
     n_points: u64,
+    limit: Option<usize>,
     data_address: u64,
     reader: &mut T,
 ) -> Result<Vec<(f64, f64)>, SfError> {
@@ -51,10 +57,14 @@ pub fn parse_xppyy_data<T: SeekBufRead>(
     // remember stream position
     let pos = reader.stream_position()?;
     reader.seek(SeekFrom::Start(data_address))?;
-    let y_data = DataParser::read_xppyy_data(reader, n_points as usize)?;
+    let mut y_data = DataParser::read_xppyy_data(reader, n_points as usize, limit)?;
     // reset stream position
     reader.seek(SeekFrom::Start(pos))?;
-    if y_data.len() as u64 != n_points {
+    let is_complete = match limit {
+        None => y_data.len() as u64 == n_points,
+        Some(limit) => y_data.len() >= limit,
+    };
+    if !is_complete {
         return Err(SfError::new(&format!(
             "Mismatch between NPOINTS and actual number of points \
             in \"{}\". NPOINTS: {}, actual: {}",
@@ -64,33 +74,30 @@ pub fn parse_xppyy_data<T: SeekBufRead>(
         )));
     }
 
-    // prepare processing
-    let mut xy_data = Vec::<(f64, f64)>::with_capacity(y_data.len());
-    // cover special cases nPoints == 0 and nPoints == 1
-    if n_points == 0 {
-        return Ok(xy_data);
+    if let Some(limit) = limit {
+        y_data.truncate(limit);
     }
-    let (nominator, denominator) = if n_points == 1 {
-        (first_x, 1f64)
-    } else {
-        (last_x - first_x, (n_points - 1) as f64)
-    };
 
     // generate and return xy data
-    for (i, y_raw) in y_data.iter().enumerate() {
-        let x = first_x + nominator / denominator * i as f64;
-        let y = y_factor * y_raw;
-        xy_data.push((x, y));
-    }
+    let abscissa = Abscissa::new(first_x, last_x, n_points);
+    let xy_data = abscissa
+        .values()
+        .zip(y_data)
+        .map(|(x, y_raw)| (x, y_factor * y_raw))
+        .collect();
 
     Ok(xy_data)
 }
 
+/// Parses (XY..XY) data.
+///
+/// If a limit is given, decoding stops once this number of points is available.
 pub fn parse_xyxy_data<T: SeekBufRead>(
     label: &str,
     x_factor: f64,
     y_factor: f64,
     n_points: Option<u64>,
+    limit: Option<usize>,
     data_address: u64,
     reader: &mut T,
 ) -> Result<Vec<(f64, f64)>, SfError> {
@@ -98,12 +105,14 @@ pub fn parse_xyxy_data<T: SeekBufRead>(
     // remember stream position
     let pos = reader.stream_position()?;
     reader.seek(SeekFrom::Start(data_address))?;
-    let mut xy_data = DataParser::read_xyxy_data(reader)?;
+    let mut xy_data = DataParser::read_xyxy_data(reader, limit)?;
     // reset stream position
     reader.seek(SeekFrom::Start(pos))?;
-    if let Some(np) = n_points
-        && xy_data.len() as u64 != np
-    {
+    let is_complete = match limit {
+        None => n_points.is_none_or(|np| xy_data.len() as u64 == np),
+        Some(limit) => xy_data.len() >= limit,
+    };
+    if let (false, Some(np)) = (is_complete, n_points) {
         return Err(SfError::new(&format!(
             "Mismatch between NPOINTS and actual number of points \
                 in \"{}\". NPOINTS: {}, actual: {}",
@@ -125,12 +134,19 @@ pub fn parse_xyxy_data<T: SeekBufRead>(
 pub struct DataParser {}
 
 impl DataParser {
-    /// read (X++(Y..Y)) data
+    /// read (X++(Y..Y)) data, stopping once more values than the limit are read
+    ///
+    /// The values up to the limit are final then, as only the last value may be replaced
+    /// by a y check value at the start of the following line.
     pub fn read_xppyy_data<T: SeekBufRead>(
         reader: &mut T,
         expected_n_points: usize,
+        limit: Option<usize>,
     ) -> Result<Vec<f64>, SfError> {
-        let mut y_values = Vec::<f64>::with_capacity(expected_n_points);
+        let capacity = limit.map_or(expected_n_points, |limit| {
+            expected_n_points.min(limit.saturating_add(1))
+        });
+        let mut y_values = Vec::<f64>::with_capacity(capacity);
         let mut y_value_check = Option::<f64>::None;
         let mut pos = reader.stream_position()?;
         let mut buf = Vec::<u8>::with_capacity(128);
@@ -167,13 +183,19 @@ impl DataParser {
             } else {
                 y_value_check = line_y_values.last().copied();
             }
+            if limit.is_some_and(|limit| y_values.len() > limit) {
+                break;
+            }
         }
 
         Ok(y_values)
     }
 
-    /// read (XY..XY) data
-    pub fn read_xyxy_data<T: SeekBufRead>(reader: &mut T) -> Result<Vec<(f64, f64)>, SfError> {
+    /// read (XY..XY) data, stopping once the limit of xy pairs is reached
+    pub fn read_xyxy_data<T: SeekBufRead>(
+        reader: &mut T,
+        limit: Option<usize>,
+    ) -> Result<Vec<(f64, f64)>, SfError> {
         let mut xy_values = Vec::<(f64, f64)>::new();
         let mut pos = reader.stream_position()?;
         let mut buf = Vec::<u8>::with_capacity(128);
@@ -213,6 +235,12 @@ impl DataParser {
                 }
                 xy_values.push((*x, *y))
             }
+            if let Some(limit) = limit
+                && xy_values.len() >= limit
+            {
+                xy_values.truncate(limit);
+                break;
+            }
         }
 
         Ok(xy_values)
@@ -659,7 +687,7 @@ mod tests {
             ##END=";
         let mut reader = Cursor::new(input);
 
-        let actual = DataParser::read_xppyy_data(&mut reader, 53).unwrap();
+        let actual = DataParser::read_xppyy_data(&mut reader, 53, None).unwrap();
         let last_line = reader.read_line_iso_8859_1(&mut vec![]);
 
         assert_eq!(
@@ -674,6 +702,33 @@ mod tests {
         assert_eq!("##END=", last_line.unwrap().unwrap());
     }
 
+    #[test]
+    fn stops_reading_stream_beyond_limit() {
+        let input = b"599.860 0 0 0 0 2 4 4 4 7 5 4 4 5 5 7 10 11 11 6 5 7 6 9 9 7\r\n\
+            648.081 10 10 9 10 11 12 15 16 16 14 17 38 38 35 38 42 47 54\r\n\
+            682.799  59  66  75  78  88  96 104 110 121 128\r\n\
+            ##END=";
+        let mut reader = Cursor::new(input);
+
+        let actual = DataParser::read_xppyy_data(&mut reader, 53, Some(30)).unwrap();
+        let next_line = reader.read_line_iso_8859_1(&mut vec![]);
+
+        assert_eq!(43, actual.len());
+        assert_eq!(11.0, actual[29]);
+        assert!(next_line.unwrap().unwrap().starts_with("682.799"));
+
+        let input = b"1.0 10.0 2.0 20.0\r\n\
+            3.0 30.0 4.0 40.0\r\n\
+            ##END=";
+        let mut reader = Cursor::new(input);
+
+        let actual = DataParser::read_xyxy_data(&mut reader, Some(1)).unwrap();
+        let next_line = reader.read_line_iso_8859_1(&mut vec![]);
+
+        assert_eq!(vec![(1.0, 10.0)], actual);
+        assert_eq!("3.0 30.0 4.0 40.0", next_line.unwrap().unwrap());
+    }
+
     #[test]
     fn parsing_detects_failing_y_check() {
         let input = b"599.000+1jj\r\n\
@@ -681,7 +736,7 @@ mod tests {
                                 ##END=";
         let mut reader = Cursor::new(input);
 
-        let actual = DataParser::read_xppyy_data(&mut reader, 0);
+        let actual = DataParser::read_xppyy_data(&mut reader, 0, None);
 
         assert!(actual.is_err());
         assert!(
@@ -699,7 +754,7 @@ mod tests {
                                 ##END=";
         let mut reader = Cursor::new(input);
 
-        let actual = DataParser::read_xppyy_data(&mut reader, 53).unwrap();
+        let actual = DataParser::read_xppyy_data(&mut reader, 53, None).unwrap();
         let last_line = reader.read_line_iso_8859_1(&mut vec![]);
 
         assert_eq!(
@@ -0,0 +1,1159 @@
+// Copyright (c) 2025 Robert Schiwon
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use super::jdx_utils::normalize_label;
+use crate::{
+    api::{Exporter, NdArray, Node, Parameter, PointXy, Reader, Table},
+    common::SfError,
+    utils::convert_value_to_f64,
+};
+use std::io::{BufWriter, Write};
+
+/// The encoding of (X++(Y..Y)) data.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum JdxDataEncoding {
+    /// ASCII free format numeric values.
+    Affn,
+    /// Squeezed form (SQZ).
+    Sqz,
+    /// Difference form (SQZ and DIF).
+    Dif,
+    /// Difference form with duplicate suppression (SQZ, DIF, and DUP).
+    #[default]
+    DifDup,
+}
+
+/// Exports all nodes as JCAMP-DX.
+///
+/// A root node without child nodes is written as a single block. Otherwise, a LINK block
+/// holding the root node's parameters and one block per node in depth-first order is written.
+/// The root node is only written as a separate block if it holds data. The child nodes of a
+/// node holding a two dimensional array are not written as they are expected to represent
+/// the array's rows, e.g., NTUPLES pages.
+///
+/// A block holds:
+/// - The parameters as LDRs. Keys that are not JCAMP-DX labels are written as custom "##$"
+///   labels, labels describing the data are replaced by the ones written for the data.
+/// - Equidistant data as XYDATA in the chosen encoding, other data as XYPOINTS.
+/// - A peak table with x and y columns, or data plotted as sticks, as PEAK TABLE.
+/// - A two dimensional array as NTUPLES with one PAGE per row.
+pub struct JdxExporter<'a, R: Reader + ?Sized> {
+    reader: &'a R,
+    encoding: JdxDataEncoding,
+}
+
+/// The header LDR values of a block, inherited from the parent block if missing.
+#[derive(Clone)]
+struct BlockHeader {
+    data_type: String,
+    origin: String,
+    owner: String,
+}
+
+/// A row of a PEAK TABLE.
+struct Peak {
+    x: f64,
+    y: f64,
+    w: Option<f64>,
+    m: Option<String>,
+}
+
+impl<'a, R: Reader + ?Sized> JdxExporter<'a, R> {
+    const JCAMP_DX_VERSION: &'static str = "5.01";
+    const MAX_LINE_LEN: usize = 80;
+    /// Labels written by the exporter for describing the block and its data,
+    /// in normalized form.
+    const GENERATED_LABELS: [&'static str; 40] = [
+        "TITLE",
+        "JCAMPDX",
+        "DATATYPE",
+        "DATACLASS",
+        "BLOCKS",
+        "BLOCKID",
+        "ORIGIN",
+        "OWNER",
+        "END",
+        "XUNITS",
+        "YUNITS",
+        "FIRSTX",
+        "LASTX",
+        "DELTAX",
+        "MINX",
+        "MAXX",
+        "XFACTOR",
+        "FIRSTY",
+        "MINY",
+        "MAXY",
+        "YFACTOR",
+        "NPOINTS",
+        "XYDATA",
+        "XYPOINTS",
+        "PEAKTABLE",
+        "NTUPLES",
+        "VARNAME",
+        "SYMBOL",
+        "VARTYPE",
+        "VARFORM",
+        "VARDIM",
+        "UNITS",
+        "FIRST",
+        "LAST",
+        "MIN",
+        "MAX",
+        "FACTOR",
+        "PAGE",
+        "DATATABLE",
+        "ENDNTUPLES",
+    ];
+    /// Column keys of peak tables holding peak positions and intensities.
+    const PEAK_XY_KEYS: [(&'static str, &'static str); 3] = [
+        ("x", "y"),
+        ("peak_x_value", "peak_y_value"),
+        ("peak_retention_time", "peak_height"),
+    ];
+    /// Column keys of peak tables holding peak widths.
+    const PEAK_W_KEYS: [&'static str; 2] = ["w", "peak_width"];
+
+    /// Creates an exporter writing (X++(Y..Y)) data in DIFDUP form.
+    pub fn new(reader: &'a R) -> Self {
+        Self {
+            reader,
+            encoding: JdxDataEncoding::default(),
+        }
+    }
+
+    /// Sets the encoding of (X++(Y..Y)) data.
+    pub fn with_encoding(mut self, encoding: JdxDataEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    fn collect_nodes(
+        &self,
+        path: &str,
+        node: &Node,
+        header: &BlockHeader,
+        blocks: &mut Vec<(Node, BlockHeader)>,
+    ) -> Result<(), SfError> {
+        if Self::is_two_dimensional(node) {
+            // child nodes represent the array rows
+            return Ok(());
+        }
+        for i in 0..node.child_node_names.len() {
+            let child_path = format!("{}/{}", path, i);
+            let child = self.reader.read(&child_path)?;
+            let child_header = Self::map_header(&child, header);
+            // keep depth-first order with the parent preceding its children
+            let index = blocks.len();
+            self.collect_nodes(&child_path, &child, &child_header, &mut *blocks)?;
+            blocks.insert(index, (child, child_header));
+        }
+        Ok(())
+    }
+
+    fn map_header(node: &Node, parent: &BlockHeader) -> BlockHeader {
+        let mut header = parent.clone();
+        for parameter in &node.parameters {
+            if let Parameter::KeyValue(key, value) = parameter {
+                let value = value.to_string();
+                match normalize_label(key).as_str() {
+                    // the LINK type of a parent block does not apply to its child blocks
+                    "DATATYPE" if !value.eq_ignore_ascii_case("LINK") => header.data_type = value,
+                    "ORIGIN" => header.origin = value,
+                    "OWNER" => header.owner = value,
+                    _ => {}
+                }
+            }
+        }
+        header
+    }
+
+    /// Maps a parameter key to a label, prefixing keys that are no JCAMP-DX labels with "$".
+    ///
+    /// Returns None for labels written by the exporter.
+    fn map_label(key: &str) -> Option<String> {
+        let key: String = key
+            .chars()
+            .filter(|c| *c != '=' && !c.is_control())
+            .collect();
+        let key = key.trim();
+        if Self::GENERATED_LABELS.contains(&normalize_label(key).as_str()) {
+            return None;
+        }
+        let is_jdx_label = !key.is_empty()
+            && (key.starts_with(['$', '.'])
+                || key.chars().all(|c| {
+                    c.is_ascii_uppercase()
+                        || c.is_ascii_digit()
+                        || [' ', '-', '/', '_'].contains(&c)
+                }));
+        match is_jdx_label {
+            true => Some(key.to_owned()),
+            false => Some(format!("${}", key)),
+        }
+    }
+
+    fn is_two_dimensional(node: &Node) -> bool {
+        node.nd_array
+            .as_ref()
+            .is_some_and(|array| array.axes.len() == 2 && !array.values.is_empty())
+    }
+
+    fn is_sticks(node: &Node) -> bool {
+        node.metadata
+            .iter()
+            .any(|(key, value)| key == "plot.style" && value == "sticks")
+    }
+
+    fn has_data(node: &Node) -> bool {
+        !node.data.is_empty() || node.table.is_some() || node.nd_array.is_some()
+    }
+
+    fn find_metadata<'n>(node: &'n Node, key: &str) -> Option<&'n str> {
+        node.metadata
+            .iter()
+            .find(|(k, _v)| k == key)
+            .map(|(_k, v)| v.as_str())
+    }
+
+    fn map_peaks(node: &Node) -> Option<Vec<Peak>> {
+        if let Some(table) = &node.table
+            && let Some(peaks) = Self::map_table_peaks(table)
+        {
+            return Some(peaks);
+        }
+        if Self::is_sticks(node) && !node.data.is_empty() {
+            let peaks = node
+                .data
+                .iter()
+                .filter(|point| point.x.is_finite())
+                .map(|point| Peak {
+                    x: point.x,
+                    y: point.y,
+                    w: None,
+                    m: None,
+                })
+                .collect();
+            return Some(peaks);
+        }
+        None
+    }
+
+    /// Maps a table with peak position and intensity columns to peaks.
+    fn map_table_peaks(table: &Table) -> Option<Vec<Peak>> {
+        let has_key = |key: &str| table.column_names.iter().any(|c| c.key == key);
+        let (x_key, y_key) = Self::PEAK_XY_KEYS
+            .into_iter()
+            .find(|(x, y)| has_key(x) && has_key(y))?;
+        let w_key = Self::PEAK_W_KEYS.into_iter().find(|w| has_key(w));
+        let m_key = Some("m").filter(|m| has_key(m));
+
+        let peaks: Vec<Peak> = table
+            .rows
+            .iter()
+            .filter_map(|row| {
+                let x = row.get(x_key).and_then(convert_value_to_f64)?;
+                let y = row
+                    .get(y_key)
+                    .and_then(convert_value_to_f64)
+                    .unwrap_or(f64::NAN);
+                let w = w_key
+                    .and_then(|key| row.get(key))
+                    .and_then(convert_value_to_f64);
+                let m = m_key.and_then(|key| row.get(key)).map(|m| m.to_string());
+                x.is_finite().then_some(Peak { x, y, w, m })
+            })
+            .collect();
+        match peaks.is_empty() {
+            true => None,
+            false => Some(peaks),
+        }
+    }
+
+    fn is_equidistant(x_values: &[f64]) -> bool {
+        match x_values.len() {
+            0 => false,
+            1 => x_values[0].is_finite(),
+            n => {
+                let first = x_values[0];
+                let delta = (x_values[n - 1] - first) / (n - 1) as f64;
+                delta != 0.0
+                    && delta.is_finite()
+                    && x_values
+                        .iter()
+                        .enumerate()
+                        .all(|(i, x)| (x - (first + delta * i as f64)).abs() <= delta.abs() * 1e-6)
+            }
+        }
+    }
+
+    /// Determines the factor for scaling y values to integers for ASDF encoding.
+    ///
+    /// Integer values are not scaled, other values are scaled to nine significant digits.
+    fn y_factor(&self, y_values: &[f64]) -> f64 {
+        if self.encoding == JdxDataEncoding::Affn {
+            return 1.0;
+        }
+        let finite_values = || y_values.iter().filter(|y| y.is_finite());
+        let max = finite_values().fold(0f64, |max, y| max.max(y.abs()));
+        if max == 0.0 || (max < 1e15 && finite_values().all(|y| y.fract() == 0.0)) {
+            return 1.0;
+        }
+        let exponent = max.log10().floor() as i32 - 8;
+        // parse to obtain the closest representation of the power of ten
+        format!("1E{}", exponent).parse().unwrap_or(1.0)
+    }
+
+    fn write_block(
+        &self,
+        writer: &mut dyn Write,
+        node: &Node,
+        header: &BlockHeader,
+        block_id: Option<usize>,
+    ) -> Result<(), SfError> {
+        write_ldr(writer, "TITLE", &node.name)?;
+        write_ldr(writer, "JCAMP-DX", Self::JCAMP_DX_VERSION)?;
+        write_ldr(writer, "DATA TYPE", &header.data_type)?;
+        if let Some(id) = block_id {
+            write_ldr(writer, "BLOCK_ID", &id.to_string())?;
+        }
+        write_ldr(writer, "ORIGIN", &header.origin)?;
+        write_ldr(writer, "OWNER", &header.owner)?;
+        Self::write_parameters(writer, node)?;
+
+        let peaks = Self::map_peaks(node);
+        let data = match Self::is_sticks(node) {
+            true => &[][..],
+            false => &node.data[..],
+        };
+        if !data.is_empty() || peaks.is_some() {
+            let x_units = Self::find_metadata(node, "x.unit").unwrap_or("ARBITRARY UNITS");
+            let y_units = Self::find_metadata(node, "y.unit").unwrap_or("ARBITRARY UNITS");
+            write_ldr(writer, "XUNITS", x_units)?;
+            write_ldr(writer, "YUNITS", y_units)?;
+        }
+        if !data.is_empty() {
+            let x_values: Vec<f64> = data.iter().map(|p| p.x).collect();
+            match Self::is_equidistant(&x_values) {
+                true => self.write_xy_data(writer, data)?,
+                false => Self::write_xy_points(writer, data)?,
+            }
+        }
+        if let Some(peaks) = &peaks {
+            Self::write_peak_table(writer, peaks)?;
+        }
+        if Self::is_two_dimensional(node)
+            && let Some(array) = &node.nd_array
+        {
+            self.write_n_tuples(writer, array, &header.data_type)?;
+        }
+        write_ldr(writer, "END", "")
+    }
+
+    fn write_parameters(writer: &mut dyn Write, node: &Node) -> Result<(), SfError> {
+        for parameter in &node.parameters {
+            match parameter {
+                Parameter::KeyValue(key, value) => {
+                    if let Some(label) = Self::map_label(key) {
+                        write_ldr(writer, &label, &value.to_string())?;
+                    }
+                }
+                // LDR comment
+                Parameter::Value(value) => write_ldr(writer, "", &value.to_string())?,
+            }
+        }
+        Ok(())
+    }
+
+    fn write_xy_data(&self, writer: &mut dyn Write, data: &[PointXy]) -> Result<(), SfError> {
+        let n_points = data.len();
+        let first_x = data[0].x;
+        let last_x = data[n_points - 1].x;
+        let delta_x = match n_points {
+            1 => 0.0,
+            n => (last_x - first_x) / (n - 1) as f64,
+        };
+        let y_values: Vec<f64> = data.iter().map(|p| p.y).collect();
+        let y_factor = self.y_factor(&y_values);
+
+        write_ldr(writer, "FIRSTX", &format_value(first_x))?;
+        write_ldr(writer, "LASTX", &format_value(last_x))?;
+        if n_points > 1 {
+            write_ldr(writer, "DELTAX", &format_value(delta_x))?;
+        }
+        write_ldr(writer, "XFACTOR", "1")?;
+        write_ldr(writer, "YFACTOR", &format_value(y_factor))?;
+        if y_values[0].is_finite() {
+            let first_y = match self.encoding {
+                JdxDataEncoding::Affn => y_values[0],
+                _ => (y_values[0] / y_factor).round() * y_factor,
+            };
+            write_ldr(writer, "FIRSTY", &format_value(first_y))?;
+        }
+        write_ldr(writer, "NPOINTS", &n_points.to_string())?;
+        write_ldr(writer, "XYDATA", "(X++(Y..Y))")?;
+        let x_at = |i: usize| first_x + delta_x * i as f64;
+        for line in self.encode_xppyy(x_at, &y_values, y_factor) {
+            write_line(writer, &line)?;
+        }
+        Ok(())
+    }
+
+    fn write_xy_points(writer: &mut dyn Write, data: &[PointXy]) -> Result<(), SfError> {
+        let n_points = data.len();
+        write_ldr(writer, "FIRSTX", &format_value(data[0].x))?;
+        write_ldr(writer, "LASTX", &format_value(data[n_points - 1].x))?;
+        write_ldr(writer, "XFACTOR", "1")?;
+        write_ldr(writer, "YFACTOR", "1")?;
+        if data[0].y.is_finite() {
+            write_ldr(writer, "FIRSTY", &format_value(data[0].y))?;
+        }
+        write_ldr(writer, "NPOINTS", &n_points.to_string())?;
+        write_ldr(writer, "XYPOINTS", "(XY..XY)")?;
+        for line in encode_xyxy(data.iter().map(|p| (p.x, p.y)))? {
+            write_line(writer, &line)?;
+        }
+        Ok(())
+    }
+
+    fn write_peak_table(writer: &mut dyn Write, peaks: &[Peak]) -> Result<(), SfError> {
+        let has_w = peaks.iter().any(|peak| peak.w.is_some());
+        let has_m = !has_w && peaks.iter().any(|peak| peak.m.is_some());
+        let variable_list = match (has_w, has_m) {
+            (true, _) => "(XYW..XYW)",
+            (false, true) => "(XYM..XYM)",
+            (false, false) => "(XY..XY)",
+        };
+        // missing values are left blank
+        let format_optional = |v: f64| match v.is_finite() {
+            true => format_value(v),
+            false => String::new(),
+        };
+
+        write_ldr(writer, "PEAK TABLE", variable_list)?;
+        for peak in peaks {
+            let mut line = format!("{}, {}", format_value(peak.x), format_optional(peak.y));
+            if has_w {
+                line += ", ";
+                line += &format_optional(peak.w.unwrap_or(f64::NAN));
+            } else if has_m {
+                line += ", ";
+                line += &sanitize_list_item(peak.m.as_deref().unwrap_or_default());
+            }
+            write_line(writer, &line)?;
+        }
+        Ok(())
+    }
+
+    fn write_n_tuples(
+        &self,
+        writer: &mut dyn Write,
+        array: &NdArray,
+        data_type: &str,
+    ) -> Result<(), SfError> {
+        let (page_axis, x_axis) = (&array.axes[0], &array.axes[1]);
+        let n_points = x_axis.values.len();
+        let n_pages = page_axis.values.len();
+        let first_x = x_axis.values[0];
+        let last_x = x_axis.values[n_points - 1];
+        let delta_x = match n_points {
+            1 => 0.0,
+            n => (last_x - first_x) / (n - 1) as f64,
+        };
+        let is_equidistant = Self::is_equidistant(&x_axis.values);
+        let y_factor = match is_equidistant {
+            true => self.y_factor(&array.values),
+            false => 1.0,
+        };
+        let y_form = match is_equidistant && self.encoding != JdxDataEncoding::Affn {
+            true => "ASDF",
+            false => "AFFN",
+        };
+        let unit = |unit: &Option<String>| sanitize_list_item(unit.as_deref().unwrap_or_default());
+
+        write_ldr(writer, "NTUPLES", data_type)?;
+        write_ldr(
+            writer,
+            "VAR_NAME",
+            &format!(
+                "{}, {}, {}",
+                sanitize_list_item(&x_axis.name),
+                sanitize_list_item(&array.name),
+                sanitize_list_item(&page_axis.name)
+            ),
+        )?;
+        write_ldr(writer, "SYMBOL", "X, Y, P")?;
+        write_ldr(writer, "VAR_TYPE", "INDEPENDENT, DEPENDENT, PAGE")?;
+        write_ldr(writer, "VAR_FORM", &format!("AFFN, {}, AFFN", y_form))?;
+        write_ldr(
+            writer,
+            "VAR_DIM",
+            &format!("{}, {}, {}", n_points, n_points, n_pages),
+        )?;
+        write_ldr(
+            writer,
+            "UNITS",
+            &format!(
+                "{}, {}, {}",
+                unit(&x_axis.unit),
+                unit(&array.unit),
+                unit(&page_axis.unit)
+            ),
+        )?;
+        write_ldr(
+            writer,
+            "FIRST",
+            &format!(
+                "{}, , {}",
+                format_value(first_x),
+                format_value(page_axis.values[0])
+            ),
+        )?;
+        write_ldr(
+            writer,
+            "LAST",
+            &format!(
+                "{}, , {}",
+                format_value(last_x),
+                format_value(page_axis.values[n_pages - 1])
+            ),
+        )?;
+        write_ldr(
+            writer,
+            "FACTOR",
+            &format!("1, {}, 1", format_value(y_factor)),
+        )?;
+
+        for (page_value, y_values) in page_axis
+            .values
+            .iter()
+            .zip(array.values.chunks_exact(n_points))
+        {
+            write_ldr(writer, "PAGE", &format!("P={}", format_value(*page_value)))?;
+            write_ldr(writer, "NPOINTS", &n_points.to_string())?;
+            let lines = match is_equidistant {
+                true => {
+                    write_ldr(writer, "DATA TABLE", "(X++(Y..Y)), XYDATA")?;
+                    let x_at = |i: usize| first_x + delta_x * i as f64;
+                    self.encode_xppyy(x_at, y_values, y_factor)
+                }
+                false => {
+                    write_ldr(writer, "DATA TABLE", "(XY..XY), XYDATA")?;
+                    encode_xyxy(x_axis.values.iter().copied().zip(y_values.iter().copied()))?
+                }
+            };
+            for line in lines {
+                write_line(writer, &line)?;
+            }
+        }
+        write_ldr(writer, "END NTUPLES", data_type)
+    }
+
+    /// Encodes (X++(Y..Y)) data lines, each starting with the x value of its first y value.
+    fn encode_xppyy(
+        &self,
+        x_at: impl Fn(usize) -> f64,
+        y_values: &[f64],
+        y_factor: f64,
+    ) -> Vec<String> {
+        let (dif, dup) = match self.encoding {
+            JdxDataEncoding::Affn => {
+                return encode_affn_lines(x_at, y_values, Self::MAX_LINE_LEN);
+            }
+            JdxDataEncoding::Sqz => (false, false),
+            JdxDataEncoding::Dif => (true, false),
+            JdxDataEncoding::DifDup => (true, true),
+        };
+        let scaled_values: Vec<Option<i64>> = y_values
+            .iter()
+            .map(|y| y.is_finite().then(|| (y / y_factor).round() as i64))
+            .collect();
+
+        let mut lines = vec![];
+        let mut start = 0;
+        while start < scaled_values.len() {
+            let x = format_value(x_at(start));
+            let (line, end, is_dif_encoded) =
+                encode_asdf_line(x, &scaled_values, start, dif, dup, Self::MAX_LINE_LEN);
+            lines.push(line);
+            if !is_dif_encoded {
+                start = end;
+                continue;
+            }
+            // the last value of a DIF encoded line is repeated in the next line as y check
+            start = end - 1;
+            if end == scaled_values.len() {
+                let check_value = scaled_values[start].unwrap_or_default();
+                lines.push(format_value(x_at(start)) + &encode_sqz(check_value));
+                break;
+            }
+        }
+        lines
+    }
+}
+
+impl<R: Reader + ?Sized> Exporter for JdxExporter<'_, R> {
+    fn get_name(&self) -> &'static str {
+        "JCAMP-DX Exporter"
+    }
+
+    fn write(&mut self, writer: &mut dyn Write) -> Result<(), SfError> {
+        let mut writer = BufWriter::new(writer);
+        let root = self.reader.read("")?;
+        let default_header = BlockHeader {
+            data_type: "UNKNOWN".to_owned(),
+            origin: String::new(),
+            owner: String::new(),
+        };
+        let header = Self::map_header(&root, &default_header);
+
+        if root.child_node_names.is_empty() || Self::is_two_dimensional(&root) {
+            self.write_block(&mut writer, &root, &header, None)?;
+            writer.flush()?;
+            return Ok(());
+        }
+
+        let mut blocks = vec![];
+        self.collect_nodes("", &root, &header, &mut blocks)?;
+        let root_has_data = Self::has_data(&root);
+        let n_blocks = blocks.len() + usize::from(root_has_data);
+
+        write_ldr(&mut writer, "TITLE", &root.name)?;
+        write_ldr(&mut writer, "JCAMP-DX", Self::JCAMP_DX_VERSION)?;
+        write_ldr(&mut writer, "DATA TYPE", "LINK")?;
+        write_ldr(&mut writer, "BLOCKS", &n_blocks.to_string())?;
+        write_ldr(&mut writer, "ORIGIN", &header.origin)?;
+        write_ldr(&mut writer, "OWNER", &header.owner)?;
+        let mut block_id = 1;
+        if root_has_data {
+            self.write_block(&mut writer, &root, &header, Some(block_id))?;
+            block_id += 1;
+        } else {
+            Self::write_parameters(&mut writer, &root)?;
+        }
+        for (node, header) in &blocks {
+            self.write_block(&mut writer, node, header, Some(block_id))?;
+            block_id += 1;
+        }
+        write_ldr(&mut writer, "END", "")?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Writes an LDR, continuing multi-line values on subsequent lines.
+fn write_ldr(writer: &mut dyn Write, label: &str, value: &str) -> Result<(), SfError> {
+    let mut lines = value.lines();
+    match lines.next() {
+        Some(first_line) if !first_line.is_empty() => {
+            write_line(writer, &format!("##{}= {}", label, first_line))?
+        }
+        _ => write_line(writer, &format!("##{}=", label))?,
+    }
+    for line in lines {
+        write_line(writer, line)?;
+    }
+    Ok(())
+}
+
+/// Writes a line in ISO-8859-1 encoding, replacing characters outside its range with "?".
+fn write_line(writer: &mut dyn Write, line: &str) -> Result<(), SfError> {
+    let mut bytes: Vec<u8> = line
+        .chars()
+        .map(|c| u8::try_from(u32::from(c)).unwrap_or(b'?'))
+        .collect();
+    bytes.extend_from_slice(b"\r\n");
+    writer.write_all(&bytes)?;
+    Ok(())
+}
+
+fn format_value(value: f64) -> String {
+    match value.is_finite() {
+        true => value.to_string(),
+        false => "?".to_owned(),
+    }
+}
+
+/// Removes the separators of comma separated LDR values.
+fn sanitize_list_item(item: &str) -> String {
+    item.replace(',', " ").trim().to_owned()
+}
+
+/// Encodes (XY..XY) data in AFFN, one pair per line.
+fn encode_xyxy(data: impl Iterator<Item = (f64, f64)>) -> Result<Vec<String>, SfError> {
+    data.map(|(x, y)| {
+        if !x.is_finite() {
+            return Err(SfError::new(&format!(
+                "Illegal x value for JCAMP-DX export: {}",
+                x
+            )));
+        }
+        Ok(format!("{}, {}", format_value(x), format_value(y)))
+    })
+    .collect()
+}
+
+/// Encodes (X++(Y..Y)) data in AFFN.
+fn encode_affn_lines(x_at: impl Fn(usize) -> f64, y_values: &[f64], max_len: usize) -> Vec<String> {
+    let mut lines = vec![];
+    let mut index = 0;
+    while index < y_values.len() {
+        let start = index;
+        let mut line = format_value(x_at(start));
+        while index < y_values.len() {
+            let token = format_value(y_values[index]);
+            if index > start && line.len() + 1 + token.len() > max_len {
+                break;
+            }
+            line.push(' ');
+            line.push_str(&token);
+            index += 1;
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+/// Encodes one line of (X++(Y..Y)) data in ASDF, starting at the given index.
+///
+/// Returns the line, the index following the last encoded value,
+/// and whether the line ends with a DIF encoded value.
+fn encode_asdf_line(
+    mut line: String,
+    values: &[Option<i64>],
+    start: usize,
+    dif: bool,
+    dup: bool,
+    max_len: usize,
+) -> (String, usize, bool) {
+    let mut end = start;
+    let mut ends_dif_encoded = false;
+    // the pending token, whether it is a DIF token, and its number of repetitions
+    let mut pending: Option<(String, bool, usize)> = None;
+    let mut index = start;
+    loop {
+        let next = values.get(index).map(|value| {
+            let previous = match index {
+                i if i == start => None,
+                i => values[i - 1],
+            };
+            match (value, previous) {
+                (None, _) => ("?".to_owned(), false),
+                (Some(v), Some(p)) if dif => (encode_dif(v - p), true),
+                (Some(v), _) => (encode_sqz(*v), false),
+            }
+        });
+        if let (Some((token, _, count)), Some((next_token, _))) = (&mut pending, &next)
+            && dup
+            && token == next_token
+        {
+            *count += 1;
+            index += 1;
+            continue;
+        }
+        if let Some((token, is_dif, count)) = pending.take() {
+            let unit = match count {
+                1 => token,
+                n => token + &encode_dup(n),
+            };
+            // make sure that at least one value besides a y check value is encoded
+            if line.len() + unit.len() > max_len && end > start + 1 {
+                return (line, end, ends_dif_encoded);
+            }
+            line.push_str(&unit);
+            end += count;
+            ends_dif_encoded = is_dif;
+        }
+        match next {
+            None => return (line, end, ends_dif_encoded),
+            Some((token, is_dif)) => {
+                pending = Some((token, is_dif, 1));
+                index += 1;
+            }
+        }
+    }
+}
+
+/// Encodes a value as token with a leading pseudo-digit, e.g., SQZ "A23" for 123.
+fn encode_digits(value: u64, pseudo_digits: &[char; 10]) -> String {
+    let digits = value.to_string();
+    let first_digit = (digits.as_bytes()[0] - b'0') as usize;
+    format!("{}{}", pseudo_digits[first_digit], &digits[1..])
+}
+
+fn encode_sqz(value: i64) -> String {
+    match value >= 0 {
+        true => encode_digits(
+            value.unsigned_abs(),
+            &['@', 'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I'],
+        ),
+        false => encode_digits(
+            value.unsigned_abs(),
+            &['@', 'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i'],
+        ),
+    }
+}
+
+fn encode_dif(value: i64) -> String {
+    match value >= 0 {
+        true => encode_digits(
+            value.unsigned_abs(),
+            &['%', 'J', 'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R'],
+        ),
+        false => encode_digits(
+            value.unsigned_abs(),
+            &['%', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q', 'r'],
+        ),
+    }
+}
+
+fn encode_dup(count: usize) -> String {
+    encode_digits(
+        count as u64,
+        &['@', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z', 's'],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        api::{Axis, Column, ExportFormat, Parser, SeekBufRead, Value},
+        jdx::{jdx_parser::JdxParser, jdx_reader::JdxReader},
+    };
+    use std::{
+        collections::HashMap,
+        io::{BufReader, Cursor},
+    };
+
+    struct StubReader {}
+
+    impl StubReader {
+        fn equidistant_data() -> Vec<PointXy> {
+            (0..100)
+                .map(|i| {
+                    let y = match i {
+                        50 => f64::NAN,
+                        60..70 => 100.0,
+                        i => (i as f64 / 10.0).sin() * 1234.5,
+                    };
+                    PointXy::new(1000.0 - i as f64 * 0.5, y)
+                })
+                .collect()
+        }
+    }
+
+    impl Reader for StubReader {
+        fn read(&self, path: &str) -> Result<Node, SfError> {
+            let empty_node = |name: &str| Node {
+                name: name.to_owned(),
+                parameters: vec![],
+                data: vec![],
+                metadata: vec![],
+                table: None,
+                nd_array: None,
+                complex_data: None,
+                links: vec![],
+                child_node_names: vec![],
+            };
+            match path {
+                "" | "/" => Ok(Node {
+                    parameters: vec![
+                        Parameter::from_str_str("DATA TYPE", "INFRARED SPECTRUM"),
+                        Parameter::from_str_str("ORIGIN", "origin"),
+                        Parameter::from_str_str("Custom key", "multi\nline"),
+                        Parameter::from_f64(1.5),
+                    ],
+                    links: vec![],
+                    child_node_names: vec![
+                        "xydata".to_owned(),
+                        "xypoints".to_owned(),
+                        "peaks".to_owned(),
+                        "ntuples".to_owned(),
+                    ],
+                    ..empty_node("root")
+                }),
+                "/0" => Ok(Node {
+                    parameters: vec![
+                        Parameter::from_str_str("TITLE", "replaced by name"),
+                        Parameter::from_str_str("SPECTROMETER/DATA SYSTEM", "sys"),
+                    ],
+                    data: Self::equidistant_data(),
+                    metadata: vec![
+                        ("x.unit".to_owned(), "1/CM".to_owned()),
+                        ("y.unit".to_owned(), "ABSORBANCE".to_owned()),
+                    ],
+                    ..empty_node("xydata")
+                }),
+                "/1" => Ok(Node {
+                    data: vec![
+                        PointXy::new(1.0, 10.5),
+                        PointXy::new(2.0, f64::NAN),
+                        PointXy::new(4.0, -0.25),
+                    ],
+                    ..empty_node("xypoints")
+                }),
+                "/2" => Ok(Node {
+                    parameters: vec![Parameter::from_str_str("DATA TYPE", "MASS SPECTRUM")],
+                    metadata: vec![("plot.style".to_owned(), "sticks".to_owned())],
+                    data: vec![PointXy::new(1.0, 2.0), PointXy::new(3.0, 4.0)],
+                    table: Some(Table {
+                        column_names: vec![
+                            Column::new("x", "Peak Position"),
+                            Column::new("y", "Intensity"),
+                            Column::new("w", "Width"),
+                        ],
+                        rows: vec![
+                            HashMap::from([
+                                ("x".to_owned(), Value::F64(1.0)),
+                                ("y".to_owned(), Value::F64(2.0)),
+                                ("w".to_owned(), Value::F64(0.5)),
+                            ]),
+                            HashMap::from([
+                                ("x".to_owned(), Value::F64(3.0)),
+                                ("y".to_owned(), Value::F64(4.0)),
+                            ]),
+                        ],
+                    }),
+                    ..empty_node("peaks")
+                }),
+                "/3" => Ok(Node {
+                    nd_array: Some(NdArray::new(
+                        "SPECTRUM/REAL, SPECTRUM/IMAG",
+                        Some("ARBITRARY UNITS".to_owned()),
+                        vec![
+                            Axis::new("PAGE NUMBER", None, vec![1.0, 2.0]),
+                            Axis::new("FREQUENCY", Some("HZ".to_owned()), vec![0.1, 0.2, 0.3]),
+                        ],
+                        vec![1.0, 2.0, 3.0, 4.5, 5.5, -6.5],
+                    )?),
+                    // pages represented by the array, not expected to be read
+                    complex_data: None,
+                    links: vec![],
+                    child_node_names: vec!["page 1".to_owned(), "page 2".to_owned()],
+                    ..empty_node("ntuples")
+                }),
+                _ => Err(SfError::new(&format!("Illegal path: {}", path))),
+            }
+        }
+    }
+
+    fn export_and_read(reader: &impl Reader, encoding: JdxDataEncoding) -> JdxReader {
+        let mut export = vec![];
+        JdxExporter::new(reader)
+            .with_encoding(encoding)
+            .write(&mut export)
+            .unwrap();
+        let input: Box<dyn SeekBufRead> = Box::new(BufReader::new(Cursor::new(export)));
+        let file = JdxParser::parse("export.jdx", input).unwrap();
+        JdxReader::new("export.jdx", file)
+    }
+
+    fn assert_data_eq(expected: &[PointXy], actual: &[PointXy], tolerance: f64) {
+        assert_eq!(expected.len(), actual.len());
+        for (e, a) in expected.iter().zip(actual) {
+            assert!((e.x - a.x).abs() <= 1e-9, "{:?} != {:?}", e, a);
+            assert!(
+                (e.y.is_nan() && a.y.is_nan()) || (e.y - a.y).abs() <= tolerance,
+                "{:?} != {:?}",
+                e,
+                a
+            );
+        }
+    }
+
+    #[test]
+    fn encodes_asdf_pseudo_digits() {
+        assert_eq!("@", encode_sqz(0));
+        assert_eq!("A23", encode_sqz(123));
+        assert_eq!("d5", encode_sqz(-45));
+        assert_eq!("%", encode_dif(0));
+        assert_eq!("J2", encode_dif(12));
+        assert_eq!("l", encode_dif(-3));
+        assert_eq!("T", encode_dup(2));
+        assert_eq!("s", encode_dup(9));
+        assert_eq!("S2", encode_dup(12));
+    }
+
+    #[test]
+    fn encodes_xppyy_lines_with_y_checks() {
+        let reader = StubReader {};
+        let x_at = |i: usize| i as f64;
+        let y_values = [1.0, 2.0, 3.0, 4.0, 4.0, 4.0];
+
+        let dif_dup = JdxExporter::new(&reader);
+        assert_eq!(
+            vec!["0AJU%T", "5D"],
+            dif_dup.encode_xppyy(x_at, &y_values, 1.0)
+        );
+        let dif = JdxExporter::new(&reader).with_encoding(JdxDataEncoding::Dif);
+        assert_eq!(
+            vec!["0AJJJ%%", "5D"],
+            dif.encode_xppyy(x_at, &y_values, 1.0)
+        );
+        let sqz = JdxExporter::new(&reader).with_encoding(JdxDataEncoding::Sqz);
+        assert_eq!(vec!["0ABCDDD"], sqz.encode_xppyy(x_at, &y_values, 1.0));
+        let affn = JdxExporter::new(&reader).with_encoding(JdxDataEncoding::Affn);
+        assert_eq!(
+            vec!["0 1 2 3 4 4 4"],
+            affn.encode_xppyy(x_at, &y_values, 1.0)
+        );
+    }
+
+    #[test]
+    fn wraps_long_lines_repeating_dif_encoded_values() {
+        let reader = StubReader {};
+        let exporter = JdxExporter::new(&reader);
+        let y_values: Vec<f64> = (0..200).map(|i| (i * i) as f64).collect();
+        let lines = exporter.encode_xppyy(|i| i as f64, &y_values, 1.0);
+
+        assert!(lines.len() > 1);
+        assert!(lines.iter().all(|line| line.len() <= 80));
+        let mut start_index = 0;
+        for line in &lines[1..] {
+            // each line starts with the index of its first value as x value
+            // followed by the last value of the previous line
+            let x_len = line.chars().take_while(char::is_ascii_digit).count();
+            start_index = line[..x_len].parse::<usize>().unwrap();
+            let check_value = encode_sqz((start_index * start_index) as i64);
+            let tail = &line[x_len..];
+            assert!(tail.starts_with(&check_value));
+            assert!(!tail[check_value.len()..].starts_with(|c: char| c.is_ascii_digit()));
+        }
+        assert_eq!(199, start_index);
+    }
+
+    #[test]
+    fn exports_node_tree_as_link_block() {
+        let reader = StubReader {};
+
+        for encoding in [
+            JdxDataEncoding::Affn,
+            JdxDataEncoding::Sqz,
+            JdxDataEncoding::Dif,
+            JdxDataEncoding::DifDup,
+        ] {
+            let jdx_reader = export_and_read(&reader, encoding);
+
+            let root = jdx_reader.read("/").unwrap();
+            assert_eq!(4, root.child_node_names.len());
+            assert!(
+                root.parameters
+                    .contains(&Parameter::from_str_str("DATATYPE", "LINK"))
+            );
+            assert!(
+                root.parameters
+                    .contains(&Parameter::from_str_str("$CUSTOMKEY", "multi\nline"))
+            );
+
+            let xy_data = jdx_reader.read("/0").unwrap();
+            assert_eq!("xydata", xy_data.name);
+            assert!(
+                xy_data
+                    .parameters
+                    .contains(&Parameter::from_str_str("DATATYPE", "INFRARED SPECTRUM"))
+            );
+            assert!(
+                xy_data
+                    .parameters
+                    .contains(&Parameter::from_str_str("ORIGIN", "origin"))
+            );
+            assert!(
+                xy_data
+                    .parameters
+                    .contains(&Parameter::from_str_str("SPECTROMETERDATASYSTEM", "sys"))
+            );
+            assert!(
+                xy_data
+                    .metadata
+                    .contains(&("x.unit".to_owned(), "1/CM".to_owned()))
+            );
+            assert_data_eq(&StubReader::equidistant_data(), &xy_data.data, 1e-5);
+
+            let xy_points = jdx_reader.read("/1").unwrap();
+            assert_data_eq(&reader.read("/1").unwrap().data, &xy_points.data, 0.0);
+
+            let peaks = jdx_reader.read("/2").unwrap();
+            let table = peaks.table.unwrap();
+            assert_eq!(2, table.rows.len());
+            assert_eq!(Some(&Value::F64(0.5)), table.rows[0].get("w"));
+            assert_eq!(Some(&Value::F64(3.0)), table.rows[1].get("x"));
+            assert_eq!(Some(&Value::F64(4.0)), table.rows[1].get("y"));
+            assert_data_eq(&reader.read("/2").unwrap().data, &peaks.data, 0.0);
+
+            let n_tuples = jdx_reader.read("/3/0").unwrap();
+            assert_eq!("INFRARED SPECTRUM", n_tuples.name);
+            let expected_array = reader.read("/3").unwrap().nd_array.unwrap();
+            let array = n_tuples.nd_array.unwrap();
+            assert_eq!("SPECTRUM/REAL  SPECTRUM/IMAG", array.name);
+            assert_eq!(expected_array.unit, array.unit);
+            assert_eq!(expected_array.axes[0], array.axes[0]);
+            assert_eq!(expected_array.axes[1].name, array.axes[1].name);
+            assert_eq!(expected_array.axes[1].unit, array.axes[1].unit);
+            for (expected, actual) in expected_array.values.iter().zip(&array.values) {
+                assert!((expected - actual).abs() <= 1e-8);
+            }
+        }
+    }
+
+    #[test]
+    fn exports_single_node_as_single_block() {
+        struct SingleNodeReader {}
+        impl Reader for SingleNodeReader {
+            fn read(&self, _path: &str) -> Result<Node, SfError> {
+                Ok(Node {
+                    name: "single".to_owned(),
+                    parameters: vec![],
+                    data: vec![PointXy::new(1.0, 1e12), PointXy::new(2.0, -3.0)],
+                    metadata: vec![],
+                    table: None,
+                    nd_array: None,
+                    complex_data: None,
+                    links: vec![],
+                    child_node_names: vec![],
+                })
+            }
+        }
+
+        let reader = SingleNodeReader {};
+        let mut export = vec![];
+        reader.export(ExportFormat::Jdx, &mut export).unwrap();
+        let export = String::from_utf8(export).unwrap();
+
+        assert!(export.starts_with("##TITLE= single\r\n##JCAMP-DX= 5.01\r\n"));
+        assert!(!export.contains("LINK"));
+        assert!(export.contains("##XFACTOR= 1\r\n##YFACTOR= 1\r\n##FIRSTY= 1000000000000\r\n"));
+        assert!(export.contains("##XYDATA= (X++(Y..Y))\r\n1A000000000000j000000000003\r\n2c\r\n"));
+        assert!(export.ends_with("##END=\r\n"));
+    }
+
+    #[test]
+    fn export_fails_for_unreadable_node() {
+        struct FailingReader {}
+        impl Reader for FailingReader {
+            fn read(&self, _path: &str) -> Result<Node, SfError> {
+                Err(SfError::new("Error"))
+            }
+        }
+
+        let reader = FailingReader {};
+        let mut export = vec![];
+        assert!(reader.export(ExportFormat::Jdx, &mut export).is_err());
+    }
+}
@@ -0,0 +1,318 @@
+// Copyright (c) 2025 Robert Schiwon
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use super::jdx_parser::{BondType, Structure};
+use crate::{api::Exporter, common::SfError};
+use std::{
+    collections::HashMap,
+    io::{BufWriter, Write},
+};
+
+/// Exports a JCAMP-CS chemical structure as MDL Molfile (V2000).
+///
+/// Atoms are numbered in ATOMLIST order. Charges are written as "M  CHG" properties and
+/// quadruple bonds, which have no Molfile equivalent, as "any" bonds. XY_RASTER coordinates are
+/// scaled to a mean bond length of 1.5 Angstrom, XYZ coordinates are written as is. Atoms
+/// without coordinates are placed at the origin.
+pub struct MolfileExporter<'a> {
+    structure: &'a Structure,
+    title: String,
+}
+
+impl<'a> MolfileExporter<'a> {
+    /// The maximum number of atoms and bonds of the V2000 counts line.
+    const MAX_COUNT: usize = 999;
+    /// The maximum number of charges per "M  CHG" line.
+    const MAX_CHARGES_PER_LINE: usize = 8;
+    /// The mean bond length in Angstrom two dimensional raster coordinates are scaled to.
+    const BOND_LENGTH: f64 = 1.5;
+
+    /// Creates an exporter writing a Molfile with an empty title.
+    pub fn new(structure: &'a Structure) -> Self {
+        Self {
+            structure,
+            title: String::new(),
+        }
+    }
+
+    /// Sets the title written as first line, e.g., the JCAMP-DX block TITLE.
+    pub fn with_title(mut self, title: &str) -> Self {
+        self.title = title.lines().next().unwrap_or_default().to_owned();
+        self
+    }
+
+    fn map_bond_type(bond_type: BondType) -> u8 {
+        match bond_type {
+            BondType::Single => 1,
+            BondType::Double => 2,
+            BondType::Triple => 3,
+            BondType::Aromatic => 4,
+            BondType::Quadruple => 8,
+        }
+    }
+
+    fn write_header(&self, writer: &mut impl Write) -> Result<(), SfError> {
+        let dimensions = if self.structure.is_3d { "3D" } else { "2D" };
+        writeln!(writer, "{}", self.title)?;
+        writeln!(writer, "  sciforma          {}", dimensions)?;
+        writeln!(writer)?;
+        writeln!(
+            writer,
+            "{:>3}{:>3}  0  0  0  0  0  0  0  0999 V2000",
+            self.structure.atoms.len(),
+            self.structure.bonds.len()
+        )?;
+        Ok(())
+    }
+
+    /// Determines the factor scaling raster coordinates to the standard bond length.
+    ///
+    /// Returns 1 for three dimensional coordinates and if no bond has a length.
+    fn get_scale(&self) -> f64 {
+        if self.structure.is_3d {
+            return 1.0;
+        }
+        let coordinates: HashMap<u64, (f64, f64, f64)> = self
+            .structure
+            .atoms
+            .iter()
+            .filter_map(|atom| Some((atom.number, atom.coordinates?)))
+            .collect();
+        let lengths: Vec<f64> = self
+            .structure
+            .bonds
+            .iter()
+            .filter_map(|bond| {
+                let (x_1, y_1, _) = coordinates.get(&bond.atom_1)?;
+                let (x_2, y_2, _) = coordinates.get(&bond.atom_2)?;
+                Some((x_2 - x_1).hypot(y_2 - y_1))
+            })
+            .filter(|length| *length > 0.0)
+            .collect();
+        match lengths.is_empty() {
+            true => 1.0,
+            false => Self::BOND_LENGTH * lengths.len() as f64 / lengths.iter().sum::<f64>(),
+        }
+    }
+
+    fn write_atoms(&self, writer: &mut impl Write) -> Result<(), SfError> {
+        let scale = self.get_scale();
+        for atom in &self.structure.atoms {
+            let (x, y, z) = atom.coordinates.unwrap_or_default();
+            writeln!(
+                writer,
+                "{:>10.4}{:>10.4}{:>10.4} {:<3} 0  0  0  0  0  0  0  0  0  0  0  0",
+                x * scale,
+                y * scale,
+                z * scale,
+                atom.symbol
+            )?;
+        }
+        Ok(())
+    }
+
+    fn write_bonds(&self, writer: &mut impl Write) -> Result<(), SfError> {
+        let atom_indices: HashMap<u64, usize> = self
+            .structure
+            .atoms
+            .iter()
+            .enumerate()
+            .map(|(index, atom)| (atom.number, index + 1))
+            .collect();
+        let find_atom_index = |number: u64| {
+            atom_indices
+                .get(&number)
+                .copied()
+                .ok_or(SfError::new(&format!(
+                    "Bond references unknown atom number: {}",
+                    number
+                )))
+        };
+        for bond in &self.structure.bonds {
+            writeln!(
+                writer,
+                "{:>3}{:>3}{:>3}  0",
+                find_atom_index(bond.atom_1)?,
+                find_atom_index(bond.atom_2)?,
+                Self::map_bond_type(bond.bond_type)
+            )?;
+        }
+        Ok(())
+    }
+
+    fn write_charges(&self, writer: &mut impl Write) -> Result<(), SfError> {
+        let charges: Vec<(usize, i32)> = self
+            .structure
+            .atoms
+            .iter()
+            .enumerate()
+            .filter(|(_, atom)| atom.charge != 0)
+            .map(|(index, atom)| (index + 1, atom.charge))
+            .collect();
+        for chunk in charges.chunks(Self::MAX_CHARGES_PER_LINE) {
+            write!(writer, "M  CHG{:>3}", chunk.len())?;
+            for (index, charge) in chunk {
+                write!(writer, " {:>3} {:>3}", index, charge)?;
+            }
+            writeln!(writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl Exporter for MolfileExporter<'_> {
+    fn get_name(&self) -> &'static str {
+        "Molfile Exporter"
+    }
+
+    fn write(&mut self, writer: &mut dyn Write) -> Result<(), SfError> {
+        if self.structure.atoms.len() > Self::MAX_COUNT
+            || self.structure.bonds.len() > Self::MAX_COUNT
+        {
+            return Err(SfError::new(&format!(
+                "Too many atoms or bonds for Molfile: {} atoms, {} bonds",
+                self.structure.atoms.len(),
+                self.structure.bonds.len()
+            )));
+        }
+        let mut writer = BufWriter::new(writer);
+        self.write_header(&mut writer)?;
+        self.write_atoms(&mut writer)?;
+        self.write_bonds(&mut writer)?;
+        self.write_charges(&mut writer)?;
+        writeln!(writer, "M  END")?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jdx::jdx_parser::{StructureAtom, StructureBond};
+
+    fn create_atom(number: u64, symbol: &str, charge: i32, x: f64, y: f64) -> StructureAtom {
+        StructureAtom {
+            number,
+            symbol: symbol.to_owned(),
+            implicit_hydrogens: 0,
+            charge,
+            coordinates: Some((x, y, 0.0)),
+        }
+    }
+
+    #[test]
+    fn exports_structure_as_molfile() {
+        let structure = Structure {
+            molecular_formula: Some("C 2 H 3 O 2".to_owned()),
+            atoms: vec![
+                create_atom(11, "C", 0, 0.0, 0.0),
+                create_atom(12, "C", 0, 1.5, 0.0),
+                create_atom(13, "O", 0, 2.25, 1.299),
+                create_atom(14, "O", -1, 2.25, -1.299),
+            ],
+            bonds: vec![
+                StructureBond {
+                    atom_1: 11,
+                    atom_2: 12,
+                    bond_type: BondType::Single,
+                },
+                StructureBond {
+                    atom_1: 12,
+                    atom_2: 13,
+                    bond_type: BondType::Double,
+                },
+                StructureBond {
+                    atom_1: 12,
+                    atom_2: 14,
+                    bond_type: BondType::Quadruple,
+                },
+            ],
+            is_3d: false,
+        };
+        let mut exporter = MolfileExporter::new(&structure).with_title("Acetate\nsecond line");
+        let mut output = vec![];
+
+        exporter.write(&mut output).unwrap();
+
+        let expected = "Acetate\n\
+                        \x20 sciforma          2D\n\
+                        \n\
+                        \x20 4  3  0  0  0  0  0  0  0  0999 V2000\n\
+                        \x20   0.0000    0.0000    0.0000 C   0  0  0  0  0  0  0  0  0  0  0  0\n\
+                        \x20   1.5000    0.0000    0.0000 C   0  0  0  0  0  0  0  0  0  0  0  0\n\
+                        \x20   2.2500    1.2990    0.0000 O   0  0  0  0  0  0  0  0  0  0  0  0\n\
+                        \x20   2.2500   -1.2990    0.0000 O   0  0  0  0  0  0  0  0  0  0  0  0\n\
+                        \x20 1  2  1  0\n\
+                        \x20 2  3  2  0\n\
+                        \x20 2  4  8  0\n\
+                        M  CHG  1   4  -1\n\
+                        M  END\n";
+        assert_eq!(expected, String::from_utf8(output).unwrap());
+        assert_eq!("Molfile Exporter", exporter.get_name());
+    }
+
+    #[test]
+    fn scales_raster_coordinates_to_bond_length() {
+        let structure = Structure {
+            molecular_formula: None,
+            atoms: vec![
+                create_atom(1, "C", 0, 0.0, 0.0),
+                create_atom(2, "C", 0, 866.0, 500.0),
+                create_atom(3, "O", 0, 866.0, 1500.0),
+            ],
+            bonds: vec![
+                StructureBond {
+                    atom_1: 1,
+                    atom_2: 2,
+                    bond_type: BondType::Single,
+                },
+                StructureBond {
+                    atom_1: 2,
+                    atom_2: 3,
+                    bond_type: BondType::Double,
+                },
+            ],
+            is_3d: false,
+        };
+        let mut output = vec![];
+
+        MolfileExporter::new(&structure).write(&mut output).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("\n    1.2990    0.7500    0.0000 C "));
+        assert!(output.contains("\n    1.2990    2.2500    0.0000 O "));
+    }
+
+    #[test]
+    fn fails_exporting_bond_with_unknown_atom() {
+        let structure = Structure {
+            molecular_formula: None,
+            atoms: vec![create_atom(1, "C", 0, 0.0, 0.0)],
+            bonds: vec![StructureBond {
+                atom_1: 1,
+                atom_2: 2,
+                bond_type: BondType::Single,
+            }],
+            is_3d: true,
+        };
+        let mut exporter = MolfileExporter::new(&structure);
+        assert!(exporter.write(&mut vec![]).is_err());
+    }
+}
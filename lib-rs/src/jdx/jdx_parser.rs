@@ -20,23 +20,25 @@
 use super::jdx_data_parser::{parse_xppyy_data, parse_xyxy_data};
 use super::jdx_peak_assignments_parser::PeakAssignmentsParser;
 use super::jdx_peak_table_parser::PeakTableParser;
+use super::jdx_structure_parser::parse_structure;
 use super::jdx_utils::{
-    BinBufRead, find_and_parse_parameter, is_ldr_start, is_pure_comment, parse_element,
-    parse_ldr_start, parse_parameter, read_width_function, seek_and_read_sequence_data,
-    strip_line_comment, validate_input,
+    BinBufRead, find_and_parse_parameter, is_ldr_start, is_pure_comment, locate_error,
+    parse_element, parse_ldr_start, parse_parameter, read_width_function,
+    seek_and_read_sequence_data, strip_line_comment, validate_input,
 };
-use crate::api::{Parser, SeekBufRead};
-use crate::common::SfError;
+use crate::api::{ParseOptions, ParseWarning, Parser, SeekBufRead};
+use crate::common::{SfError, SfErrorKind, SfErrorLocation, WarningCollector};
 use crate::jdx::jdx_audit_trail_parser::AuditTrailParser;
 use crate::jdx::jdx_utils::{
     extract_var_list, find_ldr, is_bruker_specific_section_end, is_bruker_specific_section_start,
     parse_string_value, skip_pure_comments, skip_to_next_ldr,
 };
-use std::cell::RefCell;
+use crate::utils::{check_data_range, extract_data_range, lock_input};
 use std::collections::{HashMap, HashSet};
-use std::rc::Rc;
+use std::ops::Range;
 use std::str::FromStr;
 use std::sync::LazyLock;
+use std::sync::{Arc, Mutex};
 use std::vec;
 
 pub struct JdxParser {}
@@ -46,7 +48,23 @@ impl<T: SeekBufRead + 'static> Parser<T> for JdxParser {
     type E = SfError;
 
     fn parse(name: &str, input: T) -> Result<Self::R, Self::E> {
-        Self::R::new(name, input)
+        Self::parse_with_options(name, input, &ParseOptions::default())
+    }
+}
+
+impl JdxParser {
+    /// Parses JCAMP-DX, checking the data against the specification as set in the options.
+    pub fn parse_with_options<T: SeekBufRead + 'static>(
+        name: &str,
+        input: T,
+        options: &ParseOptions,
+    ) -> Result<JdxBlock<T>, SfError> {
+        let mut block =
+            JdxBlock::new(name, input).map_err(|e| e.or_kind(SfErrorKind::CorruptData))?;
+        let mut collector = WarningCollector::new(options);
+        block.check_conformance(&mut collector);
+        block.warnings = collector.into_warnings()?;
+        Ok(block)
     }
 }
 
@@ -104,24 +122,26 @@ pub struct JdxBlock<T: SeekBufRead> {
 
     /// Data contained in Bruker RELAX sections if available.
     pub bruker_relax_sections: Vec<BrukerRelaxSection>,
+
+    /// Deviations from the specification accepted while parsing.
+    ///
+    /// Only filled for the outermost block, including the deviations of nested blocks.
+    pub warnings: Vec<ParseWarning>,
 }
 
 impl<T: SeekBufRead> JdxBlock<T> {
     const BLOCK_START_LABEL: &'static str = "TITLE";
     const BLOCK_END_LABEL: &'static str = "END";
 
-    pub fn new(_name: &str, mut reader: T) -> Result<Self, SfError> {
-        let mut buf = Vec::<u8>::with_capacity(1024);
-        let line = reader.read_line_iso_8859_1(&mut buf)?;
-        let title = Self::parse_first_line(line.as_deref())?;
-        let reader_ref = Rc::new(RefCell::new(reader));
-        let (block, _next_line) = Self::parse_input(&title, reader_ref, &mut buf)?;
-        Ok(block)
+    pub fn new(_name: &str, reader: T) -> Result<Self, SfError> {
+        let reader_ref = Arc::new(Mutex::new(reader));
+        Self::parse_root(Arc::clone(&reader_ref))
+            .map_err(|e| locate_error(e, &mut *lock_input(&reader_ref)))
     }
 
     pub fn new_nested(
         title: &str,
-        reader_ref: Rc<RefCell<T>>,
+        reader_ref: Arc<Mutex<T>>,
         buf: &mut Vec<u8>,
     ) -> Result<(Self, Option<String>), SfError> {
         let (block, next_line) = Self::parse_input(title, reader_ref, buf)?;
@@ -132,6 +152,73 @@ impl<T: SeekBufRead> JdxBlock<T> {
         find_ldr(label, &self.ldrs)
     }
 
+    /// The BLOCK_ID identifying the block within its LINK block if available.
+    pub fn get_block_id(&self) -> Option<&str> {
+        self.get_ldr("BLOCKID")
+            .map(|ldr| ldr.value.trim())
+            .filter(|id| !id.is_empty())
+    }
+
+    /// Parses the CROSS REFERENCE to other blocks of the same LINK block.
+    ///
+    /// Each reference consists of an optional relation followed by a BLOCK_ID, e.g.,
+    /// "STRUCTURE: BLOCK_ID= 1". Multiple references are separated by commas or line breaks.
+    pub fn get_cross_references(&self) -> Vec<CrossReference> {
+        // relation in group 1 (optional), block ID in group 2
+        const CROSS_REFERENCE_REGEX_PATTERN: &str =
+            r"(?i)(?:([^:,;\n]+?)\s*:\s*)?BLOCK[_ ]?ID\s*=\s*([^\s,;]+)";
+        static CROSS_REFERENCE_REGEX: LazyLock<regex::Regex> =
+            LazyLock::new(|| regex::Regex::new(CROSS_REFERENCE_REGEX_PATTERN).unwrap());
+
+        let Some(ldr) = self.get_ldr("CROSSREFERENCE") else {
+            return vec![];
+        };
+        CROSS_REFERENCE_REGEX
+            .captures_iter(&ldr.value)
+            .map(|caps| CrossReference {
+                relation: caps
+                    .get(1)
+                    .map(|m| m.as_str().trim().to_owned())
+                    .filter(|relation| !relation.is_empty()),
+                block_id: caps[2].to_owned(),
+            })
+            .collect()
+    }
+
+    /// Whether the block holds a JCAMP-CS chemical structure, i.e., an ATOMLIST.
+    pub fn has_structure(&self) -> bool {
+        self.get_ldr("ATOMLIST").is_some()
+    }
+
+    /// Parses the JCAMP-CS chemical structure of the block from its ATOMLIST, BONDLIST,
+    /// CHARGE, XY_RASTER, XYZ, and MOLFORM LDRs.
+    ///
+    /// Returns None if the block does not hold an ATOMLIST.
+    pub fn get_structure(&self) -> Result<Option<Structure>, SfError> {
+        parse_structure(&self.ldrs)
+    }
+
+    /// Reports the deviations from the specification that are accepted when parsing.
+    fn check_conformance(&self, collector: &mut WarningCollector) {
+        if let Some(xy_data) = &self.xy_data {
+            xy_data.check_conformance(collector);
+        }
+        if let Some(audit_trail) = &self.audit_trail {
+            audit_trail.check_conformance(collector);
+        }
+        for block in &self.blocks {
+            block.check_conformance(collector);
+        }
+    }
+
+    fn parse_root(reader_ref: Arc<Mutex<T>>) -> Result<Self, SfError> {
+        let mut buf = Vec::<u8>::with_capacity(1024);
+        let line = lock_input(&reader_ref).read_line_iso_8859_1(&mut buf)?;
+        let title = Self::parse_first_line(line.as_deref())?;
+        let (block, _next_line) = Self::parse_input(&title, reader_ref, &mut buf)?;
+        Ok(block)
+    }
+
     fn parse_first_line(line_opt: Option<&str>) -> Result<String, SfError> {
         if line_opt.is_none() {
             return Err(SfError::new("Malformed block start. First line is empty."));
@@ -147,10 +234,10 @@ impl<T: SeekBufRead> JdxBlock<T> {
 
     fn parse_input(
         title: &str,
-        reader_ref: Rc<RefCell<T>>,
+        reader_ref: Arc<Mutex<T>>,
         buf: &mut Vec<u8>,
     ) -> Result<(Self, Option<String>), SfError> {
-        let mut reader = reader_ref.borrow_mut();
+        let mut reader = lock_input(&reader_ref);
 
         let mut ldrs = Vec::<StringLdr>::new();
         let mut ldr_comments = Vec::<String>::new();
@@ -193,38 +280,38 @@ impl<T: SeekBufRead> JdxBlock<T> {
                 Self::BLOCK_END_LABEL => break,
                 Self::BLOCK_START_LABEL => {
                     drop(reader);
-                    let (block, next) = JdxBlock::new_nested(&value, Rc::clone(&reader_ref), buf)?;
-                    reader = reader_ref.borrow_mut();
+                    let (block, next) = JdxBlock::new_nested(&value, Arc::clone(&reader_ref), buf)?;
+                    reader = lock_input(&reader_ref);
                     blocks.push(block);
                     next_line = next;
                 }
                 "XYDATA" => {
                     let builder =
-                        || XyData::new(&label, &value, &ldrs, next_line, Rc::clone(&reader_ref));
+                        || XyData::new(&label, &value, &ldrs, next_line, Arc::clone(&reader_ref));
                     (xy_data, reader, next_line) =
                         parse_element(&label, &title, &xy_data, builder, reader, &reader_ref)?;
                 }
                 "RADATA" => {
                     let builder =
-                        || RaData::new(&label, &value, &ldrs, next_line, Rc::clone(&reader_ref));
+                        || RaData::new(&label, &value, &ldrs, next_line, Arc::clone(&reader_ref));
                     (ra_data, reader, next_line) =
                         parse_element(&label, &title, &ra_data, builder, reader, &reader_ref)?;
                 }
                 "XYPOINTS" => {
                     let builder =
-                        || XyPoints::new(&label, &value, &ldrs, next_line, Rc::clone(&reader_ref));
+                        || XyPoints::new(&label, &value, &ldrs, next_line, Arc::clone(&reader_ref));
                     (xy_points, reader, next_line) =
                         parse_element(&label, &title, &xy_points, builder, reader, &reader_ref)?;
                 }
                 "PEAKTABLE" => {
                     let builder =
-                        || PeakTable::new(&label, &value, next_line, Rc::clone(&reader_ref));
+                        || PeakTable::new(&label, &value, next_line, Arc::clone(&reader_ref));
                     (peak_table, reader, next_line) =
                         parse_element(&label, &title, &peak_table, builder, reader, &reader_ref)?;
                 }
                 "PEAKASSIGNMENTS" => {
                     let builder =
-                        || PeakAssignments::new(&label, &value, next_line, Rc::clone(&reader_ref));
+                        || PeakAssignments::new(&label, &value, next_line, Arc::clone(&reader_ref));
                     (peak_assignments, reader, next_line) = parse_element(
                         &label,
                         &title,
@@ -236,13 +323,13 @@ impl<T: SeekBufRead> JdxBlock<T> {
                 }
                 "NTUPLES" => {
                     let builder =
-                        || NTuples::new(&label, &value, &ldrs, next_line, Rc::clone(&reader_ref));
+                        || NTuples::new(&label, &value, &ldrs, next_line, Arc::clone(&reader_ref));
                     (n_tuples, reader, next_line) =
                         parse_element(&label, &title, &n_tuples, builder, reader, &reader_ref)?;
                 }
                 "AUDITTRAIL" => {
                     let builder =
-                        || AuditTrail::new(&label, &value, next_line, Rc::clone(&reader_ref));
+                        || AuditTrail::new(&label, &value, next_line, Arc::clone(&reader_ref));
                     (audit_trail, reader, next_line) =
                         parse_element(&label, &title, &audit_trail, builder, reader, &reader_ref)?;
                 }
@@ -306,6 +393,7 @@ impl<T: SeekBufRead> JdxBlock<T> {
                 audit_trail,
                 bruker_specific_parameters,
                 bruker_relax_sections,
+                warnings: vec![],
             },
             next_line,
         ))
@@ -339,9 +427,9 @@ impl StringLdr {
 }
 
 /// A JCAMP-DX XYDATA record.
-#[derive(Debug, PartialEq)]
+#[derive(Debug)]
 pub struct XyData<T: SeekBufRead> {
-    reader_ref: Rc<RefCell<T>>,
+    reader_ref: Arc<Mutex<T>>,
     address: u64,
 
     label: String,
@@ -366,7 +454,7 @@ impl<T: SeekBufRead> XyData<T> {
         variable_list: &str,
         ldrs: &[StringLdr],
         next_line: Option<String>,
-        reader_ref: Rc<RefCell<T>>,
+        reader_ref: Arc<Mutex<T>>,
     ) -> Result<(XyData<T>, Option<String>), SfError> {
         let variable_list = extract_var_list(variable_list);
         validate_input(
@@ -375,7 +463,7 @@ impl<T: SeekBufRead> XyData<T> {
             Self::LABEL,
             Some(&Self::VARIABLE_LISTS),
         )?;
-        let mut reader = reader_ref.borrow_mut();
+        let mut reader = lock_input(&reader_ref);
         let address = reader.stream_position()?;
         let parameters = parse_xydata_parameters(ldrs, Self::LABEL)?;
         let next_line = skip_to_next_ldr(next_line, true, &mut *reader, &mut vec![])?;
@@ -393,10 +481,62 @@ impl<T: SeekBufRead> XyData<T> {
         ))
     }
 
+    /// Provides the number of data points as given by NPOINTS.
+    pub fn get_n_points(&self) -> u64 {
+        self.parameters.n_points
+    }
+
+    /// Provides the variable list, e.g., "(X++(Y..Y))".
+    pub fn get_variable_list(&self) -> &str {
+        &self.variable_list
+    }
+
+    /// Provides the spectral parameters.
+    pub fn get_parameters(&self) -> &XyParameters {
+        &self.parameters
+    }
+
+    /// Provides the abscissa as given by FIRSTX, LASTX, and NPOINTS.
+    ///
+    /// Returns None for the non-standard variable list "(XY..XY)"
+    /// as the x values are given explicitly in this case.
+    pub fn get_abscissa(&self) -> Option<Abscissa> {
+        match self.variable_list == Self::QUIRK_OO_VARIABLE_LIST {
+            true => None,
+            false => Some(self.parameters.get_abscissa()),
+        }
+    }
+
+    fn check_conformance(&self, collector: &mut WarningCollector) {
+        if self.variable_list == Self::QUIRK_OO_VARIABLE_LIST {
+            collector.report(
+                &format!(
+                    "Non-standard variable list for {}: {}",
+                    Self::LABEL,
+                    self.variable_list
+                ),
+                Some(SfErrorLocation::ByteOffset(self.address)),
+            );
+        }
+    }
+
     /// Provides the parsed xy data.
     ///
     /// Returns pairs of xy data. Invalid values ("?") will be represented by NaN.
     pub fn get_data(&self) -> Result<Vec<(f64, f64)>, SfError> {
+        self.parse_data(None)
+    }
+
+    /// Provides the parsed xy data in a range of points.
+    ///
+    /// Only the data up to the end of the range is decoded.
+    pub fn get_data_range(&self, range: &Range<usize>) -> Result<Vec<(f64, f64)>, SfError> {
+        check_data_range(range, self.parameters.n_points as usize)?;
+        extract_data_range(self.parse_data(Some(range.end))?, range)
+    }
+
+    fn parse_data(&self, limit: Option<usize>) -> Result<Vec<(f64, f64)>, SfError> {
+        let reader = &mut *lock_input(&self.reader_ref);
         let data = if self.variable_list == Self::QUIRK_OO_VARIABLE_LIST {
             // Ocean Optics quirk
             parse_xyxy_data(
@@ -404,9 +544,10 @@ impl<T: SeekBufRead> XyData<T> {
                 self.parameters.x_factor,
                 self.parameters.y_factor,
                 Some(self.parameters.n_points),
+                limit,
                 self.address,
-                &mut *self.reader_ref.borrow_mut(),
-            )?
+                reader,
+            )
         } else {
             parse_xppyy_data(
                 &self.label,
@@ -414,12 +555,13 @@ impl<T: SeekBufRead> XyData<T> {
                 self.parameters.last_x,
                 self.parameters.y_factor,
                 self.parameters.n_points,
+                limit,
                 self.address,
-                &mut *self.reader_ref.borrow_mut(),
-            )?
+                reader,
+            )
         };
 
-        Ok(data)
+        data.map_err(|e| locate_error(e, reader))
     }
 }
 
@@ -495,47 +637,61 @@ fn parse_xydata_parameters(ldrs: &[StringLdr], context: &str) -> Result<XyParame
     })
 }
 
-/// JCAMP-DX spectral parameters describing an XYDATA record.
-#[derive(Debug, PartialEq)]
+/// JCAMP-DX spectral parameters describing an XYDATA or XYPOINTS record.
+#[derive(Debug, PartialEq, Clone)]
 pub struct XyParameters {
     /// Abscissa units.
     ///
     /// Not required for parsing but for displaying.
-    x_units: String,
+    pub x_units: String,
     /// Ordinate units.
     ///
     /// Not required for parsing but for displaying.
-    y_units: String,
+    pub y_units: String,
     /// The factor by which to multiply raw x values to arrive at the actual value.
-    x_factor: f64,
+    pub x_factor: f64,
     /// The factor by which to multiply raw y values to arrive at the actual value.
-    y_factor: f64,
+    pub y_factor: f64,
     /// The number of xy pairs in this record.
-    n_points: u64,
+    pub n_points: u64,
     /// The first x value.
-    first_x: f64,
+    pub first_x: f64,
     /// The last x value.
-    last_x: f64,
+    pub last_x: f64,
     /// The first actual Y value (after scaling).
-    first_y: Option<f64>,
+    pub first_y: Option<f64>,
     /// Maximum X.
-    max_x: Option<f64>,
+    pub max_x: Option<f64>,
     /// Minimum X.
-    min_x: Option<f64>,
+    pub min_x: Option<f64>,
     /// Maximum Y.
-    max_y: Option<f64>,
+    pub max_y: Option<f64>,
     /// Minimum Y.
-    min_y: Option<f64>,
+    pub min_y: Option<f64>,
     /// The resolution of the data.
-    resolution: Option<String>,
+    pub resolution: Option<String>,
     /// The x distance between adjacent data points (if constant).
-    delta_x: Option<f64>,
+    pub delta_x: Option<f64>,
+}
+
+impl XyParameters {
+    /// Provides the abscissa of (X++(Y..Y)) data as given by FIRSTX, LASTX, and NPOINTS.
+    pub fn get_abscissa(&self) -> Abscissa {
+        Abscissa::new(self.first_x, self.last_x, self.n_points)
+    }
+
+    /// Checks whether DELTAX, if present, matches the spacing derived from FIRSTX, LASTX,
+    /// and NPOINTS.
+    pub fn is_delta_x_consistent(&self) -> bool {
+        self.delta_x
+            .is_none_or(|delta_x| self.get_abscissa().matches_spacing(delta_x))
+    }
 }
 
 /// A JCAMP-DX RADATA record.
-#[derive(Debug, PartialEq)]
+#[derive(Debug)]
 pub struct RaData<T: SeekBufRead> {
-    reader_ref: Rc<RefCell<T>>,
+    reader_ref: Arc<Mutex<T>>,
     address: u64,
 
     label: String,
@@ -552,7 +708,7 @@ impl<T: SeekBufRead> RaData<T> {
         variable_list: &str,
         ldrs: &[StringLdr],
         next_line: Option<String>,
-        reader_ref: Rc<RefCell<T>>,
+        reader_ref: Arc<Mutex<T>>,
     ) -> Result<(RaData<T>, Option<String>), SfError> {
         let variable_list = extract_var_list(variable_list);
         validate_input(
@@ -561,7 +717,7 @@ impl<T: SeekBufRead> RaData<T> {
             Self::LABEL,
             Some(&Self::VARIABLE_LISTS),
         )?;
-        let mut reader = reader_ref.borrow_mut();
+        let mut reader = lock_input(&reader_ref);
         let address = reader.stream_position()?;
         let parameters = Self::parse_parameters(ldrs)?;
         let next_line = skip_to_next_ldr(next_line, true, &mut *reader, &mut vec![])?;
@@ -651,68 +807,192 @@ impl<T: SeekBufRead> RaData<T> {
         })
     }
 
+    /// Provides the number of data points as given by NPOINTS.
+    pub fn get_n_points(&self) -> u64 {
+        self.parameters.n_points
+    }
+
+    /// Provides the variable list, e.g., "(R++(A..A))".
+    pub fn get_variable_list(&self) -> &str {
+        &self.variable_list
+    }
+
+    /// Provides the spectral parameters.
+    pub fn get_parameters(&self) -> &RaParameters {
+        &self.parameters
+    }
+
+    /// Provides the abscissa as given by FIRSTR, LASTR, and NPOINTS.
+    pub fn get_abscissa(&self) -> Abscissa {
+        self.parameters.get_abscissa()
+    }
+
     /// Provides the parsed xy data.
     ///
     /// Returns pairs of xy data. Invalid values ("?") will be represented by NaN.
     pub fn get_data(&self) -> Result<Vec<(f64, f64)>, SfError> {
+        self.parse_data(None)
+    }
+
+    /// Provides the parsed xy data in a range of points.
+    ///
+    /// Only the data up to the end of the range is decoded.
+    pub fn get_data_range(&self, range: &Range<usize>) -> Result<Vec<(f64, f64)>, SfError> {
+        check_data_range(range, self.parameters.n_points as usize)?;
+        extract_data_range(self.parse_data(Some(range.end))?, range)
+    }
+
+    fn parse_data(&self, limit: Option<usize>) -> Result<Vec<(f64, f64)>, SfError> {
+        let reader = &mut *lock_input(&self.reader_ref);
         let data = parse_xppyy_data(
             &self.label,
             self.parameters.first_r,
             self.parameters.last_r,
             self.parameters.a_factor,
             self.parameters.n_points,
+            limit,
             self.address,
-            &mut *self.reader_ref.borrow_mut(),
-        )?;
+            reader,
+        )
+        .map_err(|e| locate_error(e, reader))?;
 
         Ok(data)
     }
 }
 
 /// JCAMP-DX spectral parameters describing an RADATA record.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct RaParameters {
     /// Abscissa units.
     ///
     /// Not required for parsing but for displaying.
-    r_units: String,
+    pub r_units: String,
     /// Ordinate units.
     ///
     /// Not required for parsing but for displaying.
-    a_units: String,
+    pub a_units: String,
     /// The factor by which to multiply raw R values to arrive at the actual value.
-    r_factor: f64,
+    pub r_factor: f64,
     /// The factor by which to multiply raw A values to arrive at the actual value.
-    a_factor: f64,
+    pub a_factor: f64,
     /// The number of ra pairs in this record.
-    n_points: u64,
+    pub n_points: u64,
     /// The first R value.
-    first_r: f64,
+    pub first_r: f64,
     /// The last R value.
-    last_r: f64,
+    pub last_r: f64,
     /// The first actual A value (after scaling).
-    first_a: Option<f64>,
+    pub first_a: Option<f64>,
     // no MAXR, MINR according to standard
     /// Maximum A. Required, according to standard.
-    max_a: Option<f64>,
+    pub max_a: Option<f64>,
     /// Minimum A. Required, according to standard.
-    min_a: Option<f64>,
+    pub min_a: Option<f64>,
     /// The resolution of the data.
-    resolution: Option<String>,
+    pub resolution: Option<String>,
     /// The R distance between adjacent data points (if constant).
-    delta_r: Option<f64>,
+    pub delta_r: Option<f64>,
     /// The number of data points before zero path difference.
-    zdp: Option<f64>,
+    pub zdp: Option<f64>,
     /// Alias. Standard says type is AFFN, but gives "1/1" and "1/2" as examples.
-    alias: Option<String>,
+    pub alias: Option<String>,
     // In addition, XUNITS, YUNITS, FIRSTX, LASTX, DELTAX are given in examples
     // in the standard with not quite clear meaning.
 }
 
+impl RaParameters {
+    /// Provides the abscissa of (R++(A..A)) data as given by FIRSTR, LASTR, and NPOINTS.
+    pub fn get_abscissa(&self) -> Abscissa {
+        Abscissa::new(self.first_r, self.last_r, self.n_points)
+    }
+
+    /// Checks whether DELTAR, if present, matches the spacing derived from FIRSTR, LASTR,
+    /// and NPOINTS.
+    pub fn is_delta_r_consistent(&self) -> bool {
+        self.delta_r
+            .is_none_or(|delta_r| self.get_abscissa().matches_spacing(delta_r))
+    }
+}
+
+/// The abscissa of equidistant data, reconstructed from the first and last value
+/// and the number of points.
+///
+/// The values are calculated the same way as for the parsed data, so that
+/// they are identical to the x values of, e.g., [`XyData::get_data()`].
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Abscissa {
+    /// The first value.
+    pub first: f64,
+    /// The last value.
+    pub last: f64,
+    /// The number of values.
+    pub n_points: u64,
+}
+
+impl Abscissa {
+    /// The relative tolerance for matching a given spacing, e.g., DELTAX.
+    const SPACING_TOLERANCE: f64 = 1e-3;
+
+    pub fn new(first: f64, last: f64, n_points: u64) -> Self {
+        Self {
+            first,
+            last,
+            n_points,
+        }
+    }
+
+    /// Provides the distance between adjacent values.
+    ///
+    /// Returns None if there are fewer than two values.
+    pub fn get_spacing(&self) -> Option<f64> {
+        match self.n_points {
+            0 | 1 => None,
+            n => Some((self.last - self.first) / (n - 1) as f64),
+        }
+    }
+
+    /// Checks whether a spacing, e.g., as given by DELTAX, matches the derived
+    /// spacing within a relative tolerance of 0.1 %.
+    ///
+    /// Any spacing matches if there are fewer than two values.
+    pub fn matches_spacing(&self, spacing: f64) -> bool {
+        match self.get_spacing() {
+            None => true,
+            Some(derived) => (spacing - derived).abs() <= derived.abs() * Self::SPACING_TOLERANCE,
+        }
+    }
+
+    /// Checks whether another abscissa has the same number of values and its
+    /// first and last values deviate by no more than 0.1 % of the spacing.
+    pub fn matches(&self, other: &Abscissa) -> bool {
+        if self.n_points != other.n_points {
+            return false;
+        }
+        let tolerance = self
+            .get_spacing()
+            .map_or(0.0, |spacing| spacing.abs() * Self::SPACING_TOLERANCE);
+        (self.first - other.first).abs() <= tolerance && (self.last - other.last).abs() <= tolerance
+    }
+
+    /// Provides the value at an index.
+    pub fn get_value(&self, index: u64) -> f64 {
+        match self.get_spacing() {
+            None => self.first,
+            Some(spacing) => self.first + spacing * index as f64,
+        }
+    }
+
+    /// Provides an iterator over all values.
+    pub fn values(&self) -> impl Iterator<Item = f64> + use<> {
+        let abscissa = *self;
+        (0..self.n_points).map(move |i| abscissa.get_value(i))
+    }
+}
+
 /// A JCAMP-DX XYPOINTS record.
-#[derive(Debug, PartialEq)]
+#[derive(Debug)]
 pub struct XyPoints<T: SeekBufRead> {
-    reader_ref: Rc<RefCell<T>>,
+    reader_ref: Arc<Mutex<T>>,
     address: u64,
 
     label: String,
@@ -729,7 +1009,7 @@ impl<T: SeekBufRead> XyPoints<T> {
         variable_list: &str,
         ldrs: &[StringLdr],
         next_line: Option<String>,
-        reader_ref: Rc<RefCell<T>>,
+        reader_ref: Arc<Mutex<T>>,
     ) -> Result<(XyPoints<T>, Option<String>), SfError> {
         let variable_list = extract_var_list(variable_list);
         validate_input(
@@ -738,7 +1018,7 @@ impl<T: SeekBufRead> XyPoints<T> {
             Self::LABEL,
             Some(&Self::VARIABLE_LISTS),
         )?;
-        let mut reader = reader_ref.borrow_mut();
+        let mut reader = lock_input(&reader_ref);
         let address = reader.stream_position()?;
         // Even though XYPOINTS does not require all non optional spectral parameters for parsing, as per JCAMP-DX standard
         // there is no distinction between XYPOINTS and XYDATA as to what spectral parameters are required.
@@ -758,29 +1038,64 @@ impl<T: SeekBufRead> XyPoints<T> {
         ))
     }
 
+    /// Provides the number of data points as given by NPOINTS.
+    pub fn get_n_points(&self) -> u64 {
+        self.parameters.n_points
+    }
+
+    /// Provides the variable list, e.g., "(XY..XY)".
+    pub fn get_variable_list(&self) -> &str {
+        &self.variable_list
+    }
+
+    /// Provides the spectral parameters.
+    ///
+    /// As the x values are given explicitly, FIRSTX, LASTX, and DELTAX do not
+    /// necessarily describe equidistant data.
+    pub fn get_parameters(&self) -> &XyParameters {
+        &self.parameters
+    }
+
     /// Provides the parsed xy data.
     ///
     /// Returns pairs of xy data. Invalid values ("?") will be represented by NaN.
     pub fn get_data(&self) -> Result<Vec<(f64, f64)>, SfError> {
+        self.parse_data(None)
+    }
+
+    /// Provides the parsed xy data in a range of points.
+    ///
+    /// Only the data up to the end of the range is decoded.
+    pub fn get_data_range(&self, range: &Range<usize>) -> Result<Vec<(f64, f64)>, SfError> {
+        check_data_range(range, self.parameters.n_points as usize)?;
+        extract_data_range(self.parse_data(Some(range.end))?, range)
+    }
+
+    fn parse_data(&self, limit: Option<usize>) -> Result<Vec<(f64, f64)>, SfError> {
+        let reader = &mut *lock_input(&self.reader_ref);
         let data = parse_xyxy_data(
             &self.label,
             self.parameters.x_factor,
             self.parameters.y_factor,
             Some(self.parameters.n_points),
+            limit,
             self.address,
-            &mut *self.reader_ref.borrow_mut(),
-        )?;
+            reader,
+        )
+        .map_err(|e| locate_error(e, reader))?;
 
         Ok(data)
     }
 }
 
 /// A JCAMP-DX DATA TABLE record.
-#[derive(Debug, PartialEq)]
+#[derive(Debug)]
 pub struct PeakTable<T: SeekBufRead> {
-    reader_ref: Rc<RefCell<T>>,
+    reader_ref: Arc<Mutex<T>>,
     address: u64,
 
+    // only read in tests
+    #[allow(dead_code)]
     label: String,
     variable_list: String,
 }
@@ -793,7 +1108,7 @@ impl<T: SeekBufRead> PeakTable<T> {
         label: &str,
         variable_list: &str,
         next_line: Option<String>,
-        reader_ref: Rc<RefCell<T>>,
+        reader_ref: Arc<Mutex<T>>,
     ) -> Result<(PeakTable<T>, Option<String>), SfError> {
         let variable_list = extract_var_list(variable_list);
         validate_input(
@@ -802,7 +1117,7 @@ impl<T: SeekBufRead> PeakTable<T> {
             Self::LABEL,
             Some(&Self::VARIABLE_LISTS),
         )?;
-        let mut reader = reader_ref.borrow_mut();
+        let mut reader = lock_input(&reader_ref);
         let address = reader.stream_position()?;
         let next_line = skip_to_next_ldr(next_line, true, &mut *reader, &mut vec![])?;
         drop(reader);
@@ -819,17 +1134,18 @@ impl<T: SeekBufRead> PeakTable<T> {
     }
 
     pub fn get_width_function(&self) -> Result<Option<String>, SfError> {
-        read_width_function(&mut *self.reader_ref.borrow_mut(), self.address)
+        read_width_function(&mut *lock_input(&self.reader_ref), self.address)
     }
 
     /// Provides the parsed peak data.
     pub fn get_data(&self) -> Result<Vec<Peak>, SfError> {
-        let reader = &mut *self.reader_ref.borrow_mut();
+        let reader = &mut *lock_input(&self.reader_ref);
         let peaks: Vec<Peak> = seek_and_read_sequence_data::<T, PeakTableParser<T>>(
             &self.variable_list,
             self.address,
             reader,
-        )?;
+        )
+        .map_err(|e| locate_error(e, reader))?;
         Ok(peaks)
     }
 }
@@ -851,11 +1167,13 @@ pub struct Peak {
 }
 
 /// A JCAMP-DX PEAK ASSIGNMENTS record.
-#[derive(Debug, PartialEq)]
+#[derive(Debug)]
 pub struct PeakAssignments<T: SeekBufRead> {
-    reader_ref: Rc<RefCell<T>>,
+    reader_ref: Arc<Mutex<T>>,
     address: u64,
 
+    // only read in tests
+    #[allow(dead_code)]
     label: String,
     variable_list: String,
 }
@@ -868,7 +1186,7 @@ impl<T: SeekBufRead> PeakAssignments<T> {
         label: &str,
         variable_list: &str,
         next_line: Option<String>,
-        reader_ref: Rc<RefCell<T>>,
+        reader_ref: Arc<Mutex<T>>,
     ) -> Result<(PeakAssignments<T>, Option<String>), SfError> {
         let variable_list = extract_var_list(variable_list);
         validate_input(
@@ -877,7 +1195,7 @@ impl<T: SeekBufRead> PeakAssignments<T> {
             Self::LABEL,
             Some(&Self::VARIABLE_LISTS),
         )?;
-        let mut reader = reader_ref.borrow_mut();
+        let mut reader = lock_input(&reader_ref);
         let address = reader.stream_position()?;
         let next_line = skip_to_next_ldr(next_line, true, &mut *reader, &mut vec![])?;
         drop(reader);
@@ -894,17 +1212,19 @@ impl<T: SeekBufRead> PeakAssignments<T> {
     }
 
     pub fn get_width_function(&self) -> Result<Option<String>, SfError> {
-        read_width_function(&mut *self.reader_ref.borrow_mut(), self.address)
+        read_width_function(&mut *lock_input(&self.reader_ref), self.address)
     }
 
     /// Provides the parsed peak data.
     pub fn get_data(&self) -> Result<Vec<PeakAssignment>, SfError> {
-        let reader = &mut *self.reader_ref.borrow_mut();
-        let peaks: Vec<PeakAssignment> = seek_and_read_sequence_data::<T, PeakAssignmentsParser<T>>(
-            &self.variable_list,
-            self.address,
-            reader,
-        )?;
+        let reader = &mut *lock_input(&self.reader_ref);
+        let peaks: Vec<PeakAssignment> =
+            seek_and_read_sequence_data::<T, PeakAssignmentsParser<T>>(
+                &self.variable_list,
+                self.address,
+                reader,
+            )
+            .map_err(|e| locate_error(e, reader))?;
         Ok(peaks)
     }
 }
@@ -929,7 +1249,7 @@ pub struct PeakAssignment {
 }
 
 /// A JCAMP-DX NTUPLES record.
-#[derive(Debug, PartialEq)]
+#[derive(Debug)]
 pub struct NTuples<T: SeekBufRead> {
     /// The data form of the NTUPLES record (value of the
     /// first line of the LDR), e.g., "NMR FID" or "MASS SPECTRUM".
@@ -954,7 +1274,7 @@ impl<T: SeekBufRead> NTuples<T> {
         data_form: &str,
         block_ldrs: &[StringLdr],
         _next_line: Option<String>,
-        reader_ref: Rc<RefCell<T>>,
+        reader_ref: Arc<Mutex<T>>,
     ) -> Result<(Self, Option<String>), SfError> {
         validate_input(label, None, Self::LABEL, None)?;
         Self::parse(
@@ -964,13 +1284,89 @@ impl<T: SeekBufRead> NTuples<T> {
         )
     }
 
+    /// Finds pairs of pages holding the real ("R") and imaginary ("I") parts
+    /// of a complex signal, e.g., of an NMR FID.
+    ///
+    /// The n-th real page is paired with the n-th imaginary page if both share
+    /// the abscissa and their page variables are equal or enumerate pages,
+    /// e.g., "N=1" and "N=2".
+    ///
+    /// Returns the page indices as (real, imaginary) pairs.
+    pub fn get_complex_page_pairs(&self) -> Vec<(usize, usize)> {
+        let find_pages = |symbol: &str| -> Vec<usize> {
+            self.pages
+                .iter()
+                .enumerate()
+                .filter(|(_i, page)| {
+                    page.data_table
+                        .as_ref()
+                        .is_some_and(|data_table| data_table.attributes.1.symbol == symbol)
+                })
+                .map(|(i, _page)| i)
+                .collect()
+        };
+        let real_pages = find_pages("R");
+        let imaginary_pages = find_pages("I");
+        if real_pages.len() != imaginary_pages.len() {
+            return vec![];
+        }
+
+        real_pages
+            .into_iter()
+            .zip(imaginary_pages)
+            .filter(|(real, imaginary)| {
+                self.is_complex_page_pair(&self.pages[*real], &self.pages[*imaginary])
+            })
+            .collect()
+    }
+
+    fn is_complex_page_pair(&self, real: &Page<T>, imaginary: &Page<T>) -> bool {
+        let (Some(real_table), Some(imaginary_table)) = (&real.data_table, &imaginary.data_table)
+        else {
+            return false;
+        };
+        if real_table.attributes.0.symbol != imaginary_table.attributes.0.symbol
+            || real_table.get_n_points() != imaginary_table.get_n_points()
+        {
+            return false;
+        }
+        if let (Some(real_abscissa), Some(imaginary_abscissa)) =
+            (real_table.get_abscissa(), imaginary_table.get_abscissa())
+            && !real_abscissa.matches(&imaginary_abscissa)
+        {
+            return false;
+        }
+        if real.page_variables == imaginary.page_variables {
+            return true;
+        }
+
+        // differing page variables only pair if they enumerate pages
+        let page_symbol = |page: &Page<T>| {
+            page.page_variables
+                .split_once('=')
+                .map(|(symbol, _value)| symbol.trim().to_owned())
+        };
+        match (page_symbol(real), page_symbol(imaginary)) {
+            (Some(real_symbol), Some(imaginary_symbol)) if real_symbol == imaginary_symbol => {
+                self.attributes.iter().any(|attributes| {
+                    attributes.symbol == real_symbol
+                        && attributes
+                            .var_type
+                            .as_ref()
+                            .is_some_and(|var_type| var_type.eq_ignore_ascii_case("PAGE"))
+                })
+            }
+            _ => false,
+        }
+    }
+
     fn parse(
         block_ldrs: &[StringLdr],
         data_form: String,
-        reader_ref: Rc<RefCell<T>>,
+        reader_ref: Arc<Mutex<T>>,
     ) -> Result<(Self, Option<String>), SfError> {
         let mut buf = vec![];
-        let mut reader = reader_ref.borrow_mut();
+        let mut reader = lock_input(&reader_ref);
         // skip potential comment lines
         let next_line = reader.read_line_iso_8859_1(&mut buf)?;
         let next_line = skip_pure_comments(next_line, true, &mut *reader, &mut buf)?;
@@ -1018,11 +1414,11 @@ impl<T: SeekBufRead> NTuples<T> {
                 &attributes,
                 block_ldrs,
                 next_line,
-                Rc::clone(&reader_ref),
+                Arc::clone(&reader_ref),
             )?;
             pages.push(page);
             next_line = next;
-            reader = reader_ref.borrow_mut();
+            reader = lock_input(&reader_ref);
         }
         if next_line.is_none() {
             return Err(SfError::new(&format!(
@@ -1226,7 +1622,7 @@ impl<T: SeekBufRead> NTuples<T> {
 }
 
 /// A JCAMP-DX NTUPLES PAGE record.
-#[derive(Debug, PartialEq)]
+#[derive(Debug)]
 pub struct Page<T: SeekBufRead> {
     /// The page variables of the PAGE record (value of
     /// the first line of the LDR), e.g., "N=1" or "X=2.2, Y=3.3".
@@ -1251,7 +1647,7 @@ impl<T: SeekBufRead> Page<T> {
         attributes: &[NTuplesAttributes],
         block_ldrs: &[StringLdr],
         next_line: Option<String>,
-        reader_ref: Rc<RefCell<T>>,
+        reader_ref: Arc<Mutex<T>>,
     ) -> Result<(Self, Option<String>), SfError> {
         validate_input(label, None, Self::LABEL, None)?;
         Self::parse(page_var, attributes, block_ldrs, next_line, reader_ref)
@@ -1262,10 +1658,10 @@ impl<T: SeekBufRead> Page<T> {
         attributes: &[NTuplesAttributes],
         block_ldrs: &[StringLdr],
         next_line: Option<String>,
-        reader_ref: Rc<RefCell<T>>,
+        reader_ref: Arc<Mutex<T>>,
     ) -> Result<(Self, Option<String>), SfError> {
         let mut buf = vec![];
-        let mut reader = reader_ref.borrow_mut();
+        let mut reader = lock_input(&reader_ref);
 
         // skip potential comment lines
         let next_line = skip_pure_comments(next_line, false, &mut *reader, &mut buf)?;
@@ -1375,7 +1771,7 @@ impl<T: SeekBufRead> Page<T> {
 }
 
 /// A JCAMP-DX NTUPLES DATA TABLE record.
-#[derive(Debug, PartialEq)]
+#[derive(Debug)]
 pub struct DataTable<T: SeekBufRead> {
     /// The plot descriptor of the data table, e.g., "XYDATA" for
     /// "(X++(R..R)), XYDATA".
@@ -1388,7 +1784,7 @@ pub struct DataTable<T: SeekBufRead> {
     /// The record's variable list.
     pub variable_list: String,
 
-    reader_ref: Rc<RefCell<T>>,
+    reader_ref: Arc<Mutex<T>>,
     address: u64,
 }
 
@@ -1416,6 +1812,7 @@ static DATA_TABLE_Y_SYMBOLS: LazyLock<HashSet<&'static str>> =
 impl<T: SeekBufRead> DataTable<T> {
     const LABEL: &'static str = "DATATABLE";
     const PLOT_DESCRIPTORS: [&'static str; 4] = ["PROFILE", "XYDATA", "PEAKS", "CONTOUR"];
+    const XYXY_VARIABLE_LISTS: [&'static str; 3] = ["(XY..XY)", "(XR..XR)", "(XI..XI)"];
 
     #[allow(clippy::too_many_arguments)]
     fn new(
@@ -1426,7 +1823,7 @@ impl<T: SeekBufRead> DataTable<T> {
         block_ldrs: &[StringLdr],
         page_ldrs: &[StringLdr],
         next_line: Option<String>,
-        reader_ref: Rc<RefCell<T>>,
+        reader_ref: Arc<Mutex<T>>,
     ) -> Result<(Self, Option<String>), SfError> {
         // validate label and variable list
         validate_input(
@@ -1436,10 +1833,12 @@ impl<T: SeekBufRead> DataTable<T> {
             Some(&DATA_TABLE_VARIABLE_LISTS),
         )?;
         // validate plot descriptor if present
-        if plot_desc.is_some() && !Self::PLOT_DESCRIPTORS.contains(&plot_desc.unwrap()) {
+        if let Some(plot_desc) = plot_desc
+            && !Self::PLOT_DESCRIPTORS.contains(&plot_desc)
+        {
             return Err(SfError::new(&format!(
                 "Illegal plot descriptor in NTUPLES PAGE: {}",
-                plot_desc.unwrap()
+                plot_desc
             )));
         }
 
@@ -1449,9 +1848,53 @@ impl<T: SeekBufRead> DataTable<T> {
     }
 
     pub fn get_data(&self) -> Result<Vec<(f64, f64)>, SfError> {
-        let mut reader = self.reader_ref.borrow_mut();
+        let reader = &mut *lock_input(&self.reader_ref);
+        self.parse_data(reader, None)
+            .map_err(|e| locate_error(e, reader))
+    }
+
+    /// Provides the parsed xy data in a range of points.
+    ///
+    /// Only the data up to the end of the range is decoded if the number of points is known.
+    pub fn get_data_range(&self, range: &Range<usize>) -> Result<Vec<(f64, f64)>, SfError> {
+        let Some(n_points) = self.get_n_points() else {
+            return extract_data_range(self.get_data()?, range);
+        };
+        check_data_range(range, n_points as usize)?;
+        let reader = &mut *lock_input(&self.reader_ref);
+        let data = self
+            .parse_data(reader, Some(range.end))
+            .map_err(|e| locate_error(e, reader))?;
+        extract_data_range(data, range)
+    }
+
+    /// Provides the number of data points as given by the merged VAR_DIM or NPOINTS.
+    pub fn get_n_points(&self) -> Option<u64> {
+        self.attributes.1.var_dim
+    }
+
+    /// Checks whether the x values are given explicitly, e.g., for "(XY..XY)".
+    pub fn is_xyxy(&self) -> bool {
+        Self::XYXY_VARIABLE_LISTS.contains(&self.variable_list.as_str())
+    }
+
+    /// Provides the abscissa as given by the merged FIRST, LAST, and VAR_DIM
+    /// attributes for equidistant data, e.g., "(X++(Y..Y))".
+    ///
+    /// Returns None if the x values are given explicitly or any attribute is missing.
+    pub fn get_abscissa(&self) -> Option<Abscissa> {
+        if self.is_xyxy() {
+            return None;
+        }
+        Some(Abscissa::new(
+            self.attributes.0.first?,
+            self.attributes.0.last?,
+            self.attributes.1.var_dim?,
+        ))
+    }
 
-        if ["(XY..XY)", "(XR..XR)", "(XI..XI)"].contains(&self.variable_list.as_str()) {
+    fn parse_data(&self, reader: &mut T, limit: Option<usize>) -> Result<Vec<(f64, f64)>, SfError> {
+        if self.is_xyxy() {
             let x_factor = self.attributes.0.factor.unwrap_or(1.0);
             let y_factor = self.attributes.1.factor.unwrap_or(1.0);
             let n_points = self.attributes.1.var_dim;
@@ -1461,8 +1904,9 @@ impl<T: SeekBufRead> DataTable<T> {
                 x_factor,
                 y_factor,
                 n_points,
+                limit,
                 self.address,
-                &mut *reader,
+                reader,
             );
         }
 
@@ -1483,8 +1927,9 @@ impl<T: SeekBufRead> DataTable<T> {
             last_x,
             y_factor,
             n_points,
+            limit,
             self.address,
-            &mut *reader,
+            reader,
         )
     }
 
@@ -1495,7 +1940,7 @@ impl<T: SeekBufRead> DataTable<T> {
         block_ldrs: &[StringLdr],
         page_ldrs: &[StringLdr],
         next_line: Option<String>,
-        reader_ref: Rc<RefCell<T>>,
+        reader_ref: Arc<Mutex<T>>,
     ) -> Result<(Self, Option<String>), SfError> {
         let (x_symbol, y_symbol) = match DATA_TABLE_VAR_MAP.get(var_list) {
             Some((x, y)) => (*x, *y),
@@ -1521,7 +1966,7 @@ impl<T: SeekBufRead> DataTable<T> {
         Self::merge_page_first_ldr(&mut merged_x_vars, page_ldrs, x_col_index)?;
         Self::merge_page_first_ldr(&mut merged_y_vars, page_ldrs, y_col_index)?;
 
-        let mut reader = reader_ref.borrow_mut();
+        let mut reader = lock_input(&reader_ref);
         let address = reader.stream_position()?;
         let next_line = skip_to_next_ldr(next_line, true, &mut *reader, &mut vec![])?;
         drop(reader);
@@ -1698,7 +2143,7 @@ pub struct NTuplesAttributes {
 }
 
 /// A JCAMP-DX NTUPLES DATA TABLE record.
-#[derive(Debug, PartialEq)]
+#[derive(Debug)]
 pub struct AuditTrail<T: SeekBufRead> {
     /// The record's variable list. The value of the first line of the record
     /// representing the structure of the data, e.g., "(NUMBER, WHEN, WHO, WHERE, WHAT)".
@@ -1706,7 +2151,7 @@ pub struct AuditTrail<T: SeekBufRead> {
     /// The Bruker variable list if present.
     pub bruker_variable_list: Option<String>,
 
-    reader_ref: Rc<RefCell<T>>,
+    reader_ref: Arc<Mutex<T>>,
     address: u64,
 }
 
@@ -1725,7 +2170,7 @@ impl<T: SeekBufRead> AuditTrail<T> {
         label: &str,
         variable_list: &str,
         next_line: Option<String>,
-        reader_ref: Rc<RefCell<T>>,
+        reader_ref: Arc<Mutex<T>>,
     ) -> Result<(Self, Option<String>), SfError> {
         validate_input(
             label,
@@ -1735,7 +2180,7 @@ impl<T: SeekBufRead> AuditTrail<T> {
         )?;
 
         let mut buf = vec![];
-        let mut reader = reader_ref.borrow_mut();
+        let mut reader = lock_input(&reader_ref);
         let address = reader.stream_position()?;
 
         // Bruker quirk: check if overruling Bruker var list is present
@@ -1764,6 +2209,19 @@ impl<T: SeekBufRead> AuditTrail<T> {
         ))
     }
 
+    fn check_conformance(&self, collector: &mut WarningCollector) {
+        if let Some(bruker_variable_list) = &self.bruker_variable_list {
+            collector.report(
+                &format!(
+                    "Bruker variable list overruling variable list for {}: {}",
+                    Self::LABEL,
+                    bruker_variable_list
+                ),
+                Some(SfErrorLocation::ByteOffset(self.address)),
+            );
+        }
+    }
+
     pub fn get_data(&self) -> Result<Vec<AuditTrailEntry>, SfError> {
         let mut variable_list = self
             .bruker_variable_list
@@ -1777,13 +2235,14 @@ impl<T: SeekBufRead> AuditTrail<T> {
                 .unwrap_or_default();
         }
 
-        let reader = &mut *self.reader_ref.borrow_mut();
+        let reader = &mut *lock_input(&self.reader_ref);
         let audit_trail_entries: Vec<AuditTrailEntry> = seek_and_read_sequence_data::<
             T,
             AuditTrailParser<T>,
         >(
             variable_list, self.address, reader
-        )?;
+        )
+        .map_err(|e| locate_error(e, reader))?;
         Ok(audit_trail_entries)
     }
 
@@ -1846,6 +2305,70 @@ pub struct AuditTrailEntry {
     pub what: String,
 }
 
+/// A JCAMP-DX CROSS REFERENCE to another block.
+#[derive(Debug, PartialEq, Clone)]
+pub struct CrossReference {
+    /// The kind of the referenced block, e.g., "STRUCTURE" or "PEAK TABLE", if given.
+    pub relation: Option<String>,
+    /// The BLOCK_ID of the referenced block.
+    pub block_id: String,
+}
+
+/// A JCAMP-CS chemical structure.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Structure {
+    /// MOLFORM. The molecular formula, e.g., "C 6 H 6".
+    pub molecular_formula: Option<String>,
+    /// ATOMLIST. The atoms with charges and coordinates.
+    pub atoms: Vec<StructureAtom>,
+    /// BONDLIST. The bonds between atoms.
+    pub bonds: Vec<StructureBond>,
+    /// Whether the coordinates are three dimensional (XYZ) as opposed to two dimensional
+    /// (XY_RASTER).
+    pub is_3d: bool,
+}
+
+/// A JCAMP-CS atom, i.e. one item in an ATOMLIST.
+#[derive(Debug, PartialEq, Clone)]
+pub struct StructureAtom {
+    /// AN. Atom number.
+    pub number: u64,
+    /// AS. Atom symbol.
+    pub symbol: String,
+    /// NH. Number of implicit hydrogens.
+    pub implicit_hydrogens: u32,
+    /// The CHARGE of the atom.
+    pub charge: i32,
+    /// The XYZ or XY_RASTER coordinates of the atom, z being 0 for the latter.
+    pub coordinates: Option<(f64, f64, f64)>,
+}
+
+/// A JCAMP-CS bond, i.e. one item in a BONDLIST.
+#[derive(Debug, PartialEq, Clone)]
+pub struct StructureBond {
+    /// AN1. Number of the first atom.
+    pub atom_1: u64,
+    /// AN2. Number of the second atom.
+    pub atom_2: u64,
+    /// BT. Bond type.
+    pub bond_type: BondType,
+}
+
+/// A JCAMP-CS bond type.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum BondType {
+    /// "S"
+    Single,
+    /// "D"
+    Double,
+    /// "T"
+    Triple,
+    /// "Q"
+    Quadruple,
+    /// "A"
+    Aromatic,
+}
+
 /// A JCAMP-DX Bruker specific parameters section.
 ///
 /// This section starts with:
@@ -2083,6 +2606,7 @@ impl BrukerRelaxSection {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::SfErrorLocation;
     use std::io::{BufReader, Cursor};
 
     #[test]
@@ -2102,7 +2626,38 @@ mod tests {
         let cursor = Cursor::new(input);
         let buf_reader = BufReader::new(cursor);
         let buf_input: Box<dyn SeekBufRead> = Box::new(buf_reader);
-        assert!(JdxParser::parse(&path, buf_input).is_ok());
+        assert!(JdxParser::parse(path, buf_input).is_ok());
+    }
+
+    #[test]
+    fn parser_reports_quirks_as_warnings_or_errors() {
+        let input = b"##TITLE= Test Block\r\n\
+                                ##JCAMP-DX= 4.24\r\n\
+                                ##XUNITS= 1/CM\r\n\
+                                ##YUNITS= ABSORBANCE\r\n\
+                                ##FIRSTX= 450.0\r\n\
+                                ##LASTX= 461.0\r\n\
+                                ##XFACTOR= 1.0\r\n\
+                                ##YFACTOR= 1.0\r\n\
+                                ##NPOINTS= 2\r\n\
+                                ##XYDATA= (XY..XY)\r\n\
+                                450.0, 10.0; 461.0, 11.0\r\n\
+                                ##END=";
+        let path = "resources/quirk.jdx";
+
+        let block = JdxParser::parse(path, Cursor::new(input)).unwrap();
+        assert_eq!(1, block.warnings.len());
+        assert!(block.warnings[0].message.contains("(XY..XY)"));
+        assert!(matches!(
+            block.warnings[0].location,
+            Some(SfErrorLocation::ByteOffset(_))
+        ));
+        assert!(block.xy_data.unwrap().get_data().is_ok());
+
+        let error =
+            JdxParser::parse_with_options(path, Cursor::new(input), &ParseOptions::strict())
+                .unwrap_err();
+        assert_eq!(SfErrorKind::CorruptData, error.kind());
     }
 
     #[test]
@@ -2112,7 +2667,7 @@ mod tests {
         let cursor = Cursor::new(input);
         let buf_reader = BufReader::new(cursor);
         let buf_input: Box<dyn SeekBufRead> = Box::new(buf_reader);
-        assert!(JdxParser::parse(&path, buf_input).is_err());
+        assert!(JdxParser::parse(path, buf_input).is_err());
     }
 
     #[test]
@@ -2174,6 +2729,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn block_provides_block_id_and_cross_references() {
+        let input = b"##TITLE= Test\r\n\
+                           ##JCAMP-DX= 5.01\r\n\
+                           ##BLOCK_ID= 4\r\n\
+                           ##CROSS REFERENCE= STRUCTURE: BLOCK_ID= 1, PEAK TABLE: BLOCK_ID= 2\r\n\
+                           BLOCK ID= 3\r\n\
+                           ##END=\r\n";
+        let mut reader = Cursor::new(input);
+
+        let block = JdxBlock::new("test.jdx", &mut reader).unwrap();
+
+        assert_eq!(Some("4"), block.get_block_id());
+        assert_eq!(
+            vec![
+                CrossReference {
+                    relation: Some("STRUCTURE".to_owned()),
+                    block_id: "1".to_owned()
+                },
+                CrossReference {
+                    relation: Some("PEAK TABLE".to_owned()),
+                    block_id: "2".to_owned()
+                },
+                CrossReference {
+                    relation: None,
+                    block_id: "3".to_owned()
+                },
+            ],
+            block.get_cross_references()
+        );
+    }
+
     #[test]
     fn block_get_ldr_parses_ldr_comments() {
         let input = b"##TITLE= Test\r\n\
@@ -2225,6 +2812,26 @@ mod tests {
         assert!(error.to_string().contains("Malformed block start"));
     }
 
+    #[test]
+    fn block_parsing_error_provides_line() {
+        let input = b"##TITLE= Test Block\r\n\
+                                ##JCAMP-DX= 4.24\r\n\
+                                ##XYDATA= (X++(Y..Y))\r\n\
+                                450.0, 10.0\r\n\
+                                ##END=";
+        let mut reader = Cursor::new(input);
+
+        let error = JdxBlock::new("test.jdx", &mut reader).unwrap_err();
+
+        assert_eq!(
+            Some(&SfErrorLocation::Line {
+                line: 3,
+                byte_offset: 62
+            }),
+            error.location()
+        );
+    }
+
     #[test]
     fn block_parsing_fails_for_missing_end_ldr() {
         let input = b"##TITLE= Test Block\r\n\
@@ -2814,7 +3421,7 @@ mod tests {
                                  451.0, 11.0\r\n\
                                  452.0, 12.0\r\n\
                                  ##END=";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
         let (xy_data, next) = XyData::new(label, variables, ldrs, next_line, reader_ref).unwrap();
         assert_eq!("XYDATA", &xy_data.label);
@@ -2865,7 +3472,7 @@ mod tests {
                                  451.0, 11.0\r\n\
                                  452.0, 12.0\r\n\
                                  ##END=";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
         let (xy_data, next) = XyData::new(label, variables, ldrs, next_line, reader_ref).unwrap();
         assert_eq!("XYDATA", &xy_data.label);
@@ -2892,6 +3499,80 @@ mod tests {
         assert_eq!(Some("2.0".to_owned()), params.resolution);
     }
 
+    #[test]
+    fn xydata_provides_parameters_abscissa_and_spacing_check() {
+        let mut ldrs = vec![
+            StringLdr::new("XUNITS", "1/CM"),
+            StringLdr::new("YUNITS", "ABSORBANCE"),
+            StringLdr::new("FIRSTX", "450.0"),
+            StringLdr::new("LASTX", "452.0"),
+            StringLdr::new("XFACTOR", "1.0"),
+            StringLdr::new("YFACTOR", "1.0"),
+            StringLdr::new("NPOINTS", "3"),
+            StringLdr::new("DELTAX", "1.0"),
+        ];
+        let label = "XYDATA";
+        let variables = "(X++(Y..Y))";
+        let input = b"450.0, 10.0\r\n\
+                                 451.0, 11.0\r\n\
+                                 452.0, 12.0\r\n\
+                                 ##END=";
+
+        let next_line = Some(format!("##{label}= {variables}"));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
+        let (xy_data, _next) = XyData::new(label, variables, &ldrs, next_line, reader_ref).unwrap();
+
+        assert_eq!("(X++(Y..Y))", xy_data.get_variable_list());
+        let params = xy_data.get_parameters();
+        assert_eq!("1/CM", &params.x_units);
+        assert_eq!(3, params.n_points);
+        assert!(params.is_delta_x_consistent());
+        let abscissa = xy_data.get_abscissa().unwrap();
+        assert_eq!(Abscissa::new(450.0, 452.0, 3), abscissa);
+        assert_eq!(params.get_abscissa(), abscissa);
+        let xs = xy_data
+            .get_data()
+            .unwrap()
+            .into_iter()
+            .map(|(x, _)| x)
+            .collect::<Vec<_>>();
+        assert_eq!(xs, abscissa.values().collect::<Vec<_>>());
+
+        ldrs[7] = StringLdr::new("DELTAX", "2.0");
+        let next_line = Some(format!("##{label}= {variables}"));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
+        let (xy_data, _next) = XyData::new(label, variables, &ldrs, next_line, reader_ref).unwrap();
+        assert!(!xy_data.get_parameters().is_delta_x_consistent());
+    }
+
+    #[test]
+    fn abscissa_derives_spacing_and_values() {
+        let abscissa = Abscissa::new(10.0, 0.0, 5);
+        assert_eq!(Some(-2.5), abscissa.get_spacing());
+        assert_eq!(7.5, abscissa.get_value(1));
+        assert_eq!(
+            vec![10.0, 7.5, 5.0, 2.5, 0.0],
+            abscissa.values().collect::<Vec<_>>()
+        );
+        assert!(abscissa.matches_spacing(-2.5));
+        assert!(abscissa.matches_spacing(-2.501));
+        assert!(!abscissa.matches_spacing(-2.6));
+        assert!(!abscissa.matches_spacing(2.5));
+
+        let single = Abscissa::new(3.0, 3.0, 1);
+        assert_eq!(None, single.get_spacing());
+        assert_eq!(vec![3.0], single.values().collect::<Vec<_>>());
+        assert!(single.matches_spacing(1.0));
+
+        let empty = Abscissa::new(0.0, 0.0, 0);
+        assert_eq!(0, empty.values().count());
+
+        assert!(abscissa.matches(&Abscissa::new(10.001, 0.0, 5)));
+        assert!(!abscissa.matches(&Abscissa::new(10.01, 0.0, 5)));
+        assert!(!abscissa.matches(&Abscissa::new(10.0, 0.0, 4)));
+        assert!(single.matches(&Abscissa::new(3.0, 3.0, 1)));
+    }
+
     #[test]
     fn xydata_parses_accepts_blank_values_for_optional_parameters() {
         let ldrs = &[
@@ -2916,7 +3597,7 @@ mod tests {
                                  451.0, 11.0\r\n\
                                  452.0, 12.0\r\n\
                                  ##END=";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
         let (xy_data, next) = XyData::new(label, variables, ldrs, next_line, reader_ref).unwrap();
         assert_eq!("XYDATA", &xy_data.label);
@@ -2959,7 +3640,7 @@ mod tests {
         let next_line = Some(format!("##{label}= {variables}"));
         let input = b"450.0, 10.0\r\n\
                                  ##END=";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
         let (xy_data, next) = XyData::new(label, variables, ldrs, next_line, reader_ref).unwrap();
         assert_eq!("XYDATA", &xy_data.label);
@@ -2987,7 +3668,7 @@ mod tests {
         let next_line = Some(format!("##{label}= {variables}"));
         let input = b"450.0, 10.0\r\n\
                                  ##END=";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
         let (xy_data, next) = XyData::new(label, variables, ldrs, next_line, reader_ref).unwrap();
         assert_eq!("XYDATA", &xy_data.label);
@@ -3030,7 +3711,7 @@ mod tests {
         let next_line = Some(format!("##{label}= {variables}"));
         let input = b"450.0, 10.0\r\n\
                                  ##END=";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
         let (xy_data, next) = XyData::new(label, variables, ldrs, next_line, reader_ref).unwrap();
         assert_eq!("XYDATA", &xy_data.label);
@@ -3075,11 +3756,15 @@ mod tests {
                                  451.0, 11.0\r\n\
                                  452.0, 12.0\r\n\
                                  ##END=";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
         let (xy_data, _next) = XyData::new(label, variables, ldrs, next_line, reader_ref).unwrap();
         let error = xy_data.get_data().unwrap_err();
         assert!(error.to_string().contains("Mismatch") && error.to_string().contains("NPOINTS"));
+        assert!(matches!(
+            error.location(),
+            Some(SfErrorLocation::Line { .. })
+        ));
     }
 
     #[test]
@@ -3098,7 +3783,7 @@ mod tests {
         let next_line = Some(format!("##{label}= {variables}"));
         let input = b"450.0, 10.0\r\n\
                                  ##END=";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
         let error = XyData::new(label, variables, ldrs, next_line, reader_ref).unwrap_err();
         assert!(error.to_string().contains("Illegal variable list"));
@@ -3121,7 +3806,7 @@ mod tests {
         let input = b"##XYDATA= (X++(Y..Y))\r\n\
                                  450.0, 10.0\r\n\
                                  ##END=";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
         let error = XyData::new(label, variables, ldrs, next_line, reader_ref).unwrap_err();
         assert!(error.to_string().contains("Illegal label"));
@@ -3145,7 +3830,7 @@ mod tests {
         let input = b"1 A0JJA3\r\n\
                                  5 B0JJB3\r\n\
                                  ##END=";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
         let (xy_data, _next) = XyData::new(label, variables, ldrs, next_line, reader_ref).unwrap();
         let xy_vec = xy_data.get_data().unwrap();
@@ -3179,7 +3864,7 @@ mod tests {
         let variables = "(X++(Y..Y))";
         let next_line = Some(format!("##{label}= {variables}"));
         let input = b"##END=";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
         let (xy_data, _next) = XyData::new(label, variables, ldrs, next_line, reader_ref).unwrap();
         let xy_vec = xy_data.get_data().unwrap();
@@ -3203,7 +3888,7 @@ mod tests {
         let input = b"450.0, 10.0; 451.0, 11.0\r\n\
                                  460.0, 20.0; 461.0, 21.0\r\n\
                                  ##END=";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
         let (xy_data, _next) = XyData::new(label, variables, ldrs, next_line, reader_ref).unwrap();
         assert_eq!("(XY..XY)", &xy_data.variable_list);
@@ -3253,7 +3938,7 @@ mod tests {
                                  1, 11.0\r\n\
                                  2, 12.0\r\n\
                                  ##END=";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
         let (ra_data, next) = RaData::new(label, variables, ldrs, next_line, reader_ref).unwrap();
         assert_eq!("RADATA", &ra_data.label);
@@ -3306,7 +3991,7 @@ mod tests {
                                  1, 11.0\r\n\
                                  2, 12.0\r\n\
                                  ##END=";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
         let (ra_data, next) = RaData::new(label, variables, ldrs, next_line, reader_ref).unwrap();
         assert_eq!("RADATA", &ra_data.label);
@@ -3332,6 +4017,13 @@ mod tests {
         assert_eq!(Some(1.0), params.delta_r);
         assert_eq!(Some(1.0), params.zdp);
         assert_eq!(Some("1/2".to_owned()), params.alias);
+
+        assert!(params.is_delta_r_consistent());
+        assert_eq!(Abscissa::new(0.0, 2.0, 3), ra_data.get_abscissa());
+        assert_eq!(
+            ra_data.get_parameters().get_abscissa(),
+            ra_data.get_abscissa()
+        );
     }
 
     #[test]
@@ -3359,7 +4051,7 @@ mod tests {
                                  1, 11.0\r\n\
                                  2, 12.0\r\n\
                                  ##END=";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
         let (ra_data, next) = RaData::new(label, variables, ldrs, next_line, reader_ref).unwrap();
         assert_eq!("RADATA", &ra_data.label);
@@ -3405,7 +4097,7 @@ mod tests {
                                  1, 11.0\r\n\
                                  2, 12.0\r\n\
                                  ##END=";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
         let error = RaData::new(label, variables, ldrs, next_line, reader_ref).unwrap_err();
         assert!(error.to_string().contains("missing") && error.to_string().contains("NPOINTS"));
@@ -3428,7 +4120,7 @@ mod tests {
         let input = b"450.0, 10.0; 451.0, 11.0\r\n\
                                  460.0, ?; 461.0, 21.0\r\n\
                                  ##END=";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
         let (xy_points, next) =
             XyPoints::new(label, variables, ldrs, next_line, reader_ref).unwrap();
@@ -3476,7 +4168,7 @@ mod tests {
         let next_line = Some(format!("##{label}= {variables}"));
         let input = b"450.0, 10.0; 451.0, 11.0\r\n\
                                  ##END=";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
         let (xy_points, next) =
             XyPoints::new(label, variables, ldrs, next_line, reader_ref).unwrap();
@@ -3506,7 +4198,7 @@ mod tests {
         let next_line = Some(format!("##{label}= {variables}"));
         let input = b"450.0, 10.0; 451.0, 11.0\r\n\
                                  ##END=";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
         let (xy_points, next) =
             XyPoints::new(label, variables, ldrs, next_line, reader_ref).unwrap();
@@ -3537,7 +4229,7 @@ mod tests {
         let input = b"450.0, 10.0; ?, 11.0\r\n\
                                  460.0, 20.0; 461.0, 21.0\r\n\
                                  ##END=";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
         let (xy_points, _next) =
             XyPoints::new(label, variables, ldrs, next_line, reader_ref).unwrap();
@@ -3562,7 +4254,7 @@ mod tests {
         let input = b"450.0, 10.0; 451.0, 11.0\r\n\
                                  460.0, 20.0; 461.0, 21.0\r\n\
                                  ##END=";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
         let (xy_points, _next) =
             XyPoints::new(label, variables, ldrs, next_line, reader_ref).unwrap();
@@ -3587,7 +4279,7 @@ mod tests {
         let input = b"450.0, 10.0; 451.0, 11.0\r\n\
                                  460.0, 20.0; 461.0\r\n\
                                  ##END=";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
         let (xy_points, _next) =
             XyPoints::new(label, variables, ldrs, next_line, reader_ref).unwrap();
@@ -3607,9 +4299,9 @@ mod tests {
                                 \x20470.0, 12.0E2 480.0, 13.0\r\n\
                                 490.0, 14.0;  500.0, 15.0\r\n\
                                 ##END=";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
-        let (table, next) = PeakTable::new(label, &variables, next_line, reader_ref).unwrap();
+        let (table, next) = PeakTable::new(label, variables, next_line, reader_ref).unwrap();
 
         assert_eq!(label, table.label);
         assert_eq!(variables, table.variable_list);
@@ -3680,9 +4372,9 @@ mod tests {
                                 470.0, 12.0, 3.0 480.0, 13.0, 4.0\r\n\
                                 490.0, 14.0, 5.0; 500.0, 15.0, 6.0\r\n\
                                 ##END=";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
-        let (table, next) = PeakTable::new(label, &variables, next_line, reader_ref).unwrap();
+        let (table, next) = PeakTable::new(label, variables, next_line, reader_ref).unwrap();
 
         assert_eq!(label, table.label);
         assert_eq!(variables, table.variable_list);
@@ -3741,9 +4433,9 @@ mod tests {
         let next_line = Some(format!("##{label}= {variables}"));
         let input = b"450.0, 10.0, T\r\n\
                                 ##END=";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
-        let (table, next) = PeakTable::new(label, &variables, next_line, reader_ref).unwrap();
+        let (table, next) = PeakTable::new(label, variables, next_line, reader_ref).unwrap();
 
         assert_eq!(label, table.label);
         assert_eq!(variables, table.variable_list);
@@ -3770,9 +4462,9 @@ mod tests {
         let next_line = Some(format!("##{label}= {variables}"));
         let input = b"450.0, 10.0, 1.0\r\n\
                                 ##END=";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
-        let (table, _next) = PeakTable::new(label, &variables, next_line, reader_ref).unwrap();
+        let (table, _next) = PeakTable::new(label, variables, next_line, reader_ref).unwrap();
         let error = table.get_data().unwrap_err();
         assert!(error.to_string().contains("Illegal"));
     }
@@ -3784,9 +4476,9 @@ mod tests {
         let next_line = Some(format!("##{label}= {variables}"));
         let input = b"450.0, 10.0, 1.0, -1.0\r\n\
                                 ##END=";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
-        let (table, _next) = PeakTable::new(label, &variables, next_line, reader_ref).unwrap();
+        let (table, _next) = PeakTable::new(label, variables, next_line, reader_ref).unwrap();
         let error = table.get_data().unwrap_err();
         assert!(error.to_string().contains("Illegal"));
     }
@@ -3799,9 +4491,9 @@ mod tests {
         let input = b"450.0, 10.0\r\n\
                                  460.0\r\n\
                                  ##END=";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
-        let (table, _next) = PeakTable::new(label, &variables, next_line, reader_ref).unwrap();
+        let (table, _next) = PeakTable::new(label, variables, next_line, reader_ref).unwrap();
         let error = table.get_data().unwrap_err();
         assert!(error.to_string().contains("Illegal"));
     }
@@ -3813,9 +4505,9 @@ mod tests {
         let next_line = Some(format!("##{label}= {variables}"));
         let input = b"450.0,, 10.0\r\n\
                                  ##END=";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
-        let (table, _next) = PeakTable::new(label, &variables, next_line, reader_ref).unwrap();
+        let (table, _next) = PeakTable::new(label, variables, next_line, reader_ref).unwrap();
 
         let data = table.get_data().unwrap();
         assert_eq!(1, data.len());
@@ -3832,9 +4524,9 @@ mod tests {
         let next_line = Some(format!("##{label}= {variables}"));
         let input = b"450.0, 3.0, 10.0\r\n\
                                  ##END=";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
-        let error = PeakTable::new(label, &variables, next_line, reader_ref).unwrap_err();
+        let error = PeakTable::new(label, variables, next_line, reader_ref).unwrap_err();
         assert!(
             error.to_string().contains("Illegal") && error.to_string().contains("variable list")
         );
@@ -3847,9 +4539,9 @@ mod tests {
         let next_line = Some(format!("##{label}= {variables}"));
         let input = b"450.0, 10.0\r\n\
                                  ##END=";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
-        let (table, _next) = PeakTable::new(label, &variables, next_line, reader_ref).unwrap();
+        let (table, _next) = PeakTable::new(label, variables, next_line, reader_ref).unwrap();
         let error = table.get_data().unwrap_err();
         assert!(error.to_string().contains("Illegal"));
     }
@@ -3862,9 +4554,9 @@ mod tests {
         let input = b"$$ peak width kernel line 1\r\n\
                                  $$ peak width kernel line 2\r\n\
                                  ##END=";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
-        let (table, _next) = PeakTable::new(label, &variables, next_line, reader_ref).unwrap();
+        let (table, _next) = PeakTable::new(label, variables, next_line, reader_ref).unwrap();
 
         let width_function = table.get_width_function().unwrap();
         assert_eq!(
@@ -3892,10 +4584,10 @@ mod tests {
                                 , <peak\r\n\
                                 assignment 5>)\r\n\
                                 ##END=";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
         let (assignments, _next) =
-            PeakAssignments::new(label, &variables, next_line, reader_ref).unwrap();
+            PeakAssignments::new(label, variables, next_line, reader_ref).unwrap();
 
         let width_function = assignments.get_width_function().unwrap();
         assert_eq!(Some("peak width function".to_owned()), width_function);
@@ -3967,10 +4659,10 @@ mod tests {
                                 assignment 5>)\r\n\
                                 (6.0, 60.0, , <peak assignment 6>)\r\n\
                                 ##END=";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
         let (assignments, _next) =
-            PeakAssignments::new(label, &variables, next_line, reader_ref).unwrap();
+            PeakAssignments::new(label, variables, next_line, reader_ref).unwrap();
 
         let width_function = assignments.get_width_function().unwrap();
         assert_eq!(Some("peak width function".to_owned()), width_function);
@@ -4034,10 +4726,10 @@ mod tests {
         let next_line = Some(format!("##{label}= {variables}"));
         let input = b"(1.0, 10.0, D, <peak assignment 1>)\r\n\
                                 ##END=";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
         let (assignments, _next) =
-            PeakAssignments::new(label, &variables, next_line, reader_ref).unwrap();
+            PeakAssignments::new(label, variables, next_line, reader_ref).unwrap();
 
         let width_function = assignments.get_width_function().unwrap();
         assert_eq!(None, width_function);
@@ -4062,10 +4754,10 @@ mod tests {
         let next_line = Some(format!("##{label}= {variables}"));
         let input = b"(1.0, 10.0, D, 100.0, <peak assignment 1>)\r\n\
                                 ##END=";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
         let (assignments, _next) =
-            PeakAssignments::new(label, &variables, next_line, reader_ref).unwrap();
+            PeakAssignments::new(label, variables, next_line, reader_ref).unwrap();
 
         let width_function = assignments.get_width_function().unwrap();
         assert_eq!(None, width_function);
@@ -4090,10 +4782,10 @@ mod tests {
         let next_line = Some(format!("##{label}= {variables}"));
         let input = b"(1.0, 10.0, 100.0, <peak assignment 1>)\r\n\
                                 ##END=";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
         let (assignments, _next) =
-            PeakAssignments::new(label, &variables, next_line, reader_ref).unwrap();
+            PeakAssignments::new(label, variables, next_line, reader_ref).unwrap();
 
         let width_function = assignments.get_width_function().unwrap();
         assert_eq!(None, width_function);
@@ -4109,10 +4801,10 @@ mod tests {
         let next_line = Some(format!("##{label}= {variables}"));
         let input = b"(1.0, 10.0, 100.0, 1000.0, <peak assignment 1>)\r\n\
                                 ##END=";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
         let (assignments, _next) =
-            PeakAssignments::new(label, &variables, next_line, reader_ref).unwrap();
+            PeakAssignments::new(label, variables, next_line, reader_ref).unwrap();
 
         let width_function = assignments.get_width_function().unwrap();
         assert_eq!(None, width_function);
@@ -4129,10 +4821,10 @@ mod tests {
         // 10.0 could be Y or W
         let input = b"(1.0, 10.0, <peak assignment 1>)\r\n\
                                 ##END=";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
         let (assignments, _next) =
-            PeakAssignments::new(label, &variables, next_line, reader_ref).unwrap();
+            PeakAssignments::new(label, variables, next_line, reader_ref).unwrap();
 
         let width_function = assignments.get_width_function().unwrap();
         assert_eq!(None, width_function);
@@ -4149,10 +4841,10 @@ mod tests {
         // 10.0 could be Y or W
         let input = b"(1.0, 10.0, 2.0, <peak assignment 1>)\r\n\
                                 ##END=";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
         let (assignments, _next) =
-            PeakAssignments::new(label, &variables, next_line, reader_ref).unwrap();
+            PeakAssignments::new(label, variables, next_line, reader_ref).unwrap();
 
         let width_function = assignments.get_width_function().unwrap();
         assert_eq!(None, width_function);
@@ -4168,10 +4860,10 @@ mod tests {
         let next_line = Some(format!("##{label}= {variables}"));
         let input = b"1.0, 10.0, 100.0, <peak assignment 1>)\r\n\
                                 ##END=";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
         let (assignments, _next) =
-            PeakAssignments::new(label, &variables, next_line, reader_ref).unwrap();
+            PeakAssignments::new(label, variables, next_line, reader_ref).unwrap();
 
         let width_function = assignments.get_width_function().unwrap();
         assert_eq!(None, width_function);
@@ -4187,10 +4879,10 @@ mod tests {
         let next_line = Some(format!("##{label}= {variables}"));
         let input = b"(1.0, 10.0, 100.0, <peak assignment 1>\r\n\
                                 ##END=";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
         let (assignments, _next) =
-            PeakAssignments::new(label, &variables, next_line, reader_ref).unwrap();
+            PeakAssignments::new(label, variables, next_line, reader_ref).unwrap();
 
         let width_function = assignments.get_width_function().unwrap();
         assert_eq!(None, width_function);
@@ -4206,10 +4898,10 @@ mod tests {
         let next_line = Some(format!("##{label}= {variables}"));
         let input = b"(1.0, 10.0, peak assignment 1>)\r\n\
                                 ##END=";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
         let (assignments, _next) =
-            PeakAssignments::new(label, &variables, next_line, reader_ref).unwrap();
+            PeakAssignments::new(label, variables, next_line, reader_ref).unwrap();
 
         let width_function = assignments.get_width_function().unwrap();
         assert_eq!(None, width_function);
@@ -4225,10 +4917,10 @@ mod tests {
         let next_line = Some(format!("##{label}= {variables}"));
         let input = b"(1.0, 10.0, <peak assignment 1)\r\n\
                                 ##END=";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
         let (assignments, _next) =
-            PeakAssignments::new(label, &variables, next_line, reader_ref).unwrap();
+            PeakAssignments::new(label, variables, next_line, reader_ref).unwrap();
 
         let width_function = assignments.get_width_function().unwrap();
         assert_eq!(None, width_function);
@@ -4244,10 +4936,10 @@ mod tests {
         let next_line = Some(format!("##{label}= {variables}"));
         let input = b"(1.0 10.0; <peak assignment 1>)\r\n\
                                 ##END=";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
         let (assignments, _next) =
-            PeakAssignments::new(label, &variables, next_line, reader_ref).unwrap();
+            PeakAssignments::new(label, variables, next_line, reader_ref).unwrap();
 
         let width_function = assignments.get_width_function().unwrap();
         assert_eq!(None, width_function);
@@ -4263,9 +4955,9 @@ mod tests {
         let next_line = Some(format!("##{label}= {variables}"));
         let input = b"1.0, 10.0, <peak assignment 1>)\r\n\
                                 ##END=";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
-        let error = PeakAssignments::new(label, &variables, next_line, reader_ref).unwrap_err();
+        let error = PeakAssignments::new(label, variables, next_line, reader_ref).unwrap_err();
 
         assert!(
             error.to_string().contains("Illegal") && error.to_string().contains("variable list")
@@ -4279,10 +4971,10 @@ mod tests {
         let next_line = Some(format!("##{label}= {variables}"));
         let input = b"(1.0)\r\n\
                                 ##END=";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
         let (assignments, _next) =
-            PeakAssignments::new(label, &variables, next_line, reader_ref).unwrap();
+            PeakAssignments::new(label, variables, next_line, reader_ref).unwrap();
 
         let width_function = assignments.get_width_function().unwrap();
         assert_eq!(None, width_function);
@@ -4299,10 +4991,10 @@ mod tests {
         let input = b"(1.0, 10.0, <peak assignment 1>)\r\n\
                                  (1.0, 10.0, <peak assignment 1>\r\n\
                                  ##END=";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
         let (assignments, _next) =
-            PeakAssignments::new(label, &variables, next_line, reader_ref).unwrap();
+            PeakAssignments::new(label, variables, next_line, reader_ref).unwrap();
 
         let width_function = assignments.get_width_function().unwrap();
         assert_eq!(None, width_function);
@@ -4318,10 +5010,10 @@ mod tests {
         let next_line = Some(format!("##{label}= {variables}"));
         let input = b"$$ peak width function\r\n\
                                  ##END=";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
         let (assignments, _next) =
-            PeakAssignments::new(label, &variables, next_line, reader_ref).unwrap();
+            PeakAssignments::new(label, variables, next_line, reader_ref).unwrap();
 
         let width_function = assignments.get_width_function().unwrap();
         assert_eq!(Some("peak width function".to_owned()), width_function);
@@ -4357,11 +5049,11 @@ mod tests {
                                    2.0 +40+41\n\
                                    ##END NTUPLES= NMR SPECTRUM\n\
                                    ##END=\n";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
         let block_ldrs = Vec::<StringLdr>::new();
 
         let (ntuples, _next) =
-            NTuples::new(label, &variables, &block_ldrs, next_line, reader_ref).unwrap();
+            NTuples::new(label, variables, &block_ldrs, next_line, reader_ref).unwrap();
 
         assert_eq!(2, ntuples.pages.len());
         assert_eq!("NMR SPECTRUM", ntuples.data_form);
@@ -4438,6 +5130,12 @@ mod tests {
         assert_eq!(4, page_n1_data.len());
         assert_eq!((0.1, 50.0), page_n1_data[0]);
         assert_eq!((0.25, 105.0), page_n1_data[3]);
+        assert_eq!(Some(4), page_n1_data_table.get_n_points());
+        assert!(!page_n1_data_table.is_xyxy());
+        assert_eq!(
+            Some(Abscissa::new(0.1, 0.25, 4)),
+            page_n1_data_table.get_abscissa()
+        );
 
         let page_n2 = &ntuples.pages[1];
         assert_eq!("N=2", &page_n2.page_variables);
@@ -4455,6 +5153,8 @@ mod tests {
         assert_eq!(4, page_n2_data.len());
         assert_eq!((0.1, 300.0), page_n2_data[0]);
         assert_eq!((0.25, 410.0), page_n2_data[3]);
+
+        assert_eq!(vec![(0, 1)], ntuples.get_complex_page_pairs());
     }
 
     #[test]
@@ -4477,17 +5177,17 @@ mod tests {
                                    ##PAGE= N=2\n\
                                    ##END NTUPLES= NMR SPECTRUM\n\
                                    ##END=\n";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
         let block_ldrs = Vec::<StringLdr>::new();
 
         let (ntuples, next) =
-            NTuples::new(label, &variables, &block_ldrs, next_line, reader_ref).unwrap();
+            NTuples::new(label, variables, &block_ldrs, next_line, reader_ref).unwrap();
 
         assert_eq!(2, ntuples.pages.len());
         let page_n1 = &ntuples.pages[0];
-        assert_eq!(None, page_n1.data_table);
+        assert!(page_n1.data_table.is_none());
         let page_n2 = &ntuples.pages[1];
-        assert_eq!(None, page_n2.data_table);
+        assert!(page_n2.data_table.is_none());
 
         assert_eq!(Some("##END=".to_owned()), next);
     }
@@ -4519,11 +5219,11 @@ mod tests {
                                 2.0 +40+41\n\
                                 ##END NTUPLES= nD NMR FID\n\
                                 ##END=\n";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
         let block_ldrs = Vec::<StringLdr>::new();
 
         let (ntuples, _next) =
-            NTuples::new(label, &variables, &block_ldrs, next_line, reader_ref).unwrap();
+            NTuples::new(label, variables, &block_ldrs, next_line, reader_ref).unwrap();
 
         assert_eq!(10, ntuples.ldrs.len());
         assert_eq!(
@@ -4584,6 +5284,9 @@ mod tests {
         assert_eq!(4, page_t0_data.len());
         assert_eq!((1.0, 10.0), page_t0_data[0]);
         assert_eq!((2.5, 21.0), page_t0_data[3]);
+
+        // real and imaginary parts for different T1 values do not pair
+        assert!(ntuples.get_complex_page_pairs().is_empty());
     }
 
     #[test]
@@ -4608,11 +5311,11 @@ mod tests {
                                 2.0 +20+21\n\
                                 ##END NTUPLES= nD NMR SPECTRUM\n\
                                 ##END=\n";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
         let block_ldrs = Vec::<StringLdr>::new();
 
         let (ntuples, _next) =
-            NTuples::new(label, &variables, &block_ldrs, next_line, reader_ref).unwrap();
+            NTuples::new(label, variables, &block_ldrs, next_line, reader_ref).unwrap();
 
         assert_eq!(10, ntuples.ldrs.len());
         assert_eq!(
@@ -4703,11 +5406,11 @@ mod tests {
                                 330,  88.8;  340,  99.9                \n\
                                 ##END NTUPLES= MASS SPECTRUM\n\
                                 ##END=\n";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
         let block_ldrs = Vec::<StringLdr>::new();
 
         let (ntuples, _next) =
-            NTuples::new(label, &variables, &block_ldrs, next_line, reader_ref).unwrap();
+            NTuples::new(label, variables, &block_ldrs, next_line, reader_ref).unwrap();
 
         assert_eq!(3, ntuples.pages.len());
         assert_eq!("MASS SPECTRUM", ntuples.data_form);
@@ -4800,10 +5503,10 @@ mod tests {
                                 330,  88.8;  340,  99.9                \n\
                                 ##END NTUPLES= MASS SPECTRUM\n\
                                 ##END=\n";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
         let block_ldrs = Vec::<StringLdr>::new();
 
-        let ntuples_result = NTuples::new(label, &variables, &block_ldrs, next_line, reader_ref);
+        let ntuples_result = NTuples::new(label, variables, &block_ldrs, next_line, reader_ref);
 
         assert!(ntuples_result.is_ok());
         let ntuples = ntuples_result.unwrap().0;
@@ -4825,7 +5528,7 @@ mod tests {
                                 130,  80.0;  140,  90.0                \n\
                                 ##END NTUPLES= MASS SPECTRUM\n\
                                 ##END=\n";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
         let block_ldrs = vec![
             StringLdr::new("XUNITS", "XUNITS-TEST"),
             StringLdr::new("FIRSTX", "200.0"),
@@ -4843,7 +5546,7 @@ mod tests {
         ];
 
         let (ntuples, _next) =
-            NTuples::new(label, &variables, &block_ldrs, next_line, reader_ref).unwrap();
+            NTuples::new(label, variables, &block_ldrs, next_line, reader_ref).unwrap();
 
         assert_eq!(1, ntuples.pages.len());
         assert_eq!("MASS SPECTRUM", ntuples.data_form);
@@ -4915,14 +5618,14 @@ mod tests {
                                 130,  80.0;  140,  90.0                \n\
                                 ##END NTUPLES= MASS SPECTRUM\n\
                                 ##END=\n";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
         let block_ldrs = vec![
             // to be overridden by PAGE LDR
             StringLdr::new("NPOINTS", "10"),
         ];
 
         let (ntuples, _next) =
-            NTuples::new(label, &variables, &block_ldrs, next_line, reader_ref).unwrap();
+            NTuples::new(label, variables, &block_ldrs, next_line, reader_ref).unwrap();
 
         assert_eq!(1, ntuples.pages.len());
         let page_t5 = &ntuples.pages[0];
@@ -4977,10 +5680,10 @@ mod tests {
                                 ##PAGE= N=2\n\
                                 ##END NTUPLES= NMR SPECTRUM\n\
                                 ##END=\n";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
         let block_ldrs = Vec::<StringLdr>::new();
 
-        let ntuples_res = NTuples::new(label, &variables, &block_ldrs, next_line, reader_ref);
+        let ntuples_res = NTuples::new(label, variables, &block_ldrs, next_line, reader_ref);
 
         assert!(ntuples_res.is_err());
         assert!(ntuples_res.unwrap_err().to_string().contains("VAR_NAME"));
@@ -5005,10 +5708,10 @@ mod tests {
                                 ##PAGE= N=2\n\
                                 ##END NTUPLES= NMR SPECTRUM\n\
                                 ##END=\n";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
         let block_ldrs = Vec::<StringLdr>::new();
 
-        let ntuples_res = NTuples::new(label, &variables, &block_ldrs, next_line, reader_ref);
+        let ntuples_res = NTuples::new(label, variables, &block_ldrs, next_line, reader_ref);
 
         assert!(ntuples_res.is_err());
         assert!(ntuples_res.unwrap_err().to_string().contains("Duplicate"));
@@ -5033,11 +5736,11 @@ mod tests {
                                 ##PAGE= N=2\n\
                                 ##END NTUPLES= NMR SPECTRUM\n\
                                 ##END=\n";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
         let block_ldrs = Vec::<StringLdr>::new();
 
         let (ntuples, _next) =
-            NTuples::new(label, &variables, &block_ldrs, next_line, reader_ref).unwrap();
+            NTuples::new(label, variables, &block_ldrs, next_line, reader_ref).unwrap();
 
         assert_eq!(3, ntuples.attributes.len());
         let attributes_x = &ntuples.attributes[0];
@@ -5068,11 +5771,11 @@ mod tests {
                                 ##PAGE= N=2\n\
                                 ##END NTUPLES= NMR SPECTRUM\n\
                                 ##END=\n";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
         let block_ldrs = Vec::<StringLdr>::new();
 
         let (ntuples, _next) =
-            NTuples::new(label, &variables, &block_ldrs, next_line, reader_ref).unwrap();
+            NTuples::new(label, variables, &block_ldrs, next_line, reader_ref).unwrap();
 
         assert_eq!(3, ntuples.attributes.len());
         let attributes_x = &ntuples.attributes[0];
@@ -5098,10 +5801,10 @@ mod tests {
                                 ##VAR_FORM=        AFFN,             ASDF,          AFFN\n\
                                 ##VAR_DIM=            4,                4,             1\n\
                                 ##UNITS=             HZ,  ARBITRARY UNITS,              \n";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
         let block_ldrs = Vec::<StringLdr>::new();
 
-        let ntuples_res = NTuples::new(label, &variables, &block_ldrs, next_line, reader_ref);
+        let ntuples_res = NTuples::new(label, variables, &block_ldrs, next_line, reader_ref);
 
         assert!(ntuples_res.is_err());
         assert!(
@@ -5124,10 +5827,10 @@ mod tests {
                                 ##VAR_DIM=            4,                4,             1\n\
                                 ##UNITS=             HZ,  ARBITRARY UNITS,              \n\
                                 ##PAGE= N=1\n";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
         let block_ldrs = Vec::<StringLdr>::new();
 
-        let ntuples_res = NTuples::new(label, &variables, &block_ldrs, next_line, reader_ref);
+        let ntuples_res = NTuples::new(label, variables, &block_ldrs, next_line, reader_ref);
 
         assert!(ntuples_res.is_err());
         assert!(ntuples_res.unwrap_err().to_string().contains("Unexpected"));
@@ -5143,10 +5846,10 @@ mod tests {
                                 ##PAGE= N=1\n\
                                 ##DATA TABLE=                   $$ missing variable list\n\
                                 ##END NTUPLES= NMR SPECTRUM\n";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
         let block_ldrs = Vec::<StringLdr>::new();
 
-        let ntuples_res = NTuples::new(label, &variables, &block_ldrs, next_line, reader_ref);
+        let ntuples_res = NTuples::new(label, variables, &block_ldrs, next_line, reader_ref);
 
         assert!(ntuples_res.is_err());
         assert!(ntuples_res.unwrap_err().to_string().contains("Missing"));
@@ -5162,10 +5865,10 @@ mod tests {
                                 ##PAGE= N=1\n\
                                 ##DATA TABLE= a, b, c           $$ illegal variable list\n\
                                 ##END NTUPLES= NMR SPECTRUM\n";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
         let block_ldrs = Vec::<StringLdr>::new();
 
-        let ntuples_res = NTuples::new(label, &variables, &block_ldrs, next_line, reader_ref);
+        let ntuples_res = NTuples::new(label, variables, &block_ldrs, next_line, reader_ref);
 
         assert!(ntuples_res.is_err());
         assert!(ntuples_res.unwrap_err().to_string().contains("Unexpected"));
@@ -5183,10 +5886,10 @@ mod tests {
                                 \x20       line 2\n\
                                 \x20       line 3>)\n\
                                 ##END=\n";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
         let (audit_trail, _next) =
-            AuditTrail::new(label, &variables, next_line, reader_ref).unwrap();
+            AuditTrail::new(label, variables, next_line, reader_ref).unwrap();
         let entries = audit_trail.get_data().unwrap();
 
         assert_eq!(2, entries.len());
@@ -5220,10 +5923,10 @@ mod tests {
                                 \x20       line 2\n\
                                 \x20       line 3>)\n\
                                 ##END=\n";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
         let (audit_trail, _next) =
-            AuditTrail::new(label, &variables, next_line, reader_ref).unwrap();
+            AuditTrail::new(label, variables, next_line, reader_ref).unwrap();
         let entries = audit_trail.get_data().unwrap();
 
         assert_eq!(2, entries.len());
@@ -5257,10 +5960,10 @@ mod tests {
                                 \x20      line 2\n\
                                 \x20      line 3>)\n\
                                 ##END=\n";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
         let (audit_trail, _next) =
-            AuditTrail::new(label, &variables, next_line, reader_ref).unwrap();
+            AuditTrail::new(label, variables, next_line, reader_ref).unwrap();
         let entries = audit_trail.get_data().unwrap();
 
         assert_eq!(2, entries.len());
@@ -5315,10 +6018,10 @@ mod tests {
                                 $$ $$ hash MD5\n\
                                 $$ $$ 04 05 06 07 08 09 0A 0B 0C 0D 0E 0F 10 11 12 13\n\
                                 ##$RELAX= \n";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
         let (audit_trail, _next) =
-            AuditTrail::new(label, &variables, next_line, reader_ref).unwrap();
+            AuditTrail::new(label, variables, next_line, reader_ref).unwrap();
         let entries = audit_trail.get_data().unwrap();
 
         assert_eq!(3, entries.len());
@@ -5353,10 +6056,10 @@ mod tests {
         let input =
             b"(   1,<2022-09-01 09:10:11.123 -0200>,<testuser>,<location01>,<proc1>,<SW 1.3>,\n\
             ##END=\n";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
         let (audit_trail, _next) =
-            AuditTrail::new(label, &variables, next_line, reader_ref).unwrap();
+            AuditTrail::new(label, variables, next_line, reader_ref).unwrap();
         let error = audit_trail.get_data().unwrap_err();
 
         assert!(
@@ -5374,10 +6077,10 @@ mod tests {
         let input =
             b"(   1,<2022-09-01 09:10:11.123 -0200>,<testuser>,<location01>,<proc1>,<SW 1.3>,\n\
             \x20      <acquisition>)\n";
-        let reader_ref = Rc::new(RefCell::new(Cursor::new(input)));
+        let reader_ref = Arc::new(Mutex::new(Cursor::new(input)));
 
         let (audit_trail, _next) =
-            AuditTrail::new(label, &variables, next_line, reader_ref).unwrap();
+            AuditTrail::new(label, variables, next_line, reader_ref).unwrap();
         let error = audit_trail.get_data().unwrap_err();
 
         assert!(error.to_string().contains("end") && error.to_string().contains("parenthesis"));
@@ -5541,7 +6244,7 @@ mod tests {
         let mut reader = Cursor::new(input);
 
         let (bruker_relax_section, _next) =
-            BrukerRelaxSection::new(label, &value, next_line, &mut reader).unwrap();
+            BrukerRelaxSection::new(label, value, next_line, &mut reader).unwrap();
 
         assert_eq!("file_name_1", bruker_relax_section.name.unwrap());
         assert_eq!(
@@ -5569,7 +6272,7 @@ mod tests {
         let mut reader = Cursor::new(input);
 
         let (bruker_relax_section, _next) =
-            BrukerRelaxSection::new(label, &value, next_line, &mut reader).unwrap();
+            BrukerRelaxSection::new(label, value, next_line, &mut reader).unwrap();
 
         assert_eq!("file_name_2", bruker_relax_section.name.unwrap());
         assert_eq!(
@@ -5596,7 +6299,7 @@ mod tests {
         let mut reader = Cursor::new(input);
 
         let (bruker_relax_section, _next) =
-            BrukerRelaxSection::new(label, &value, next_line, &mut reader).unwrap();
+            BrukerRelaxSection::new(label, value, next_line, &mut reader).unwrap();
 
         assert!(bruker_relax_section.name.is_none());
         assert!(bruker_relax_section.content.is_none());
@@ -5610,7 +6313,7 @@ mod tests {
         let input = b"xxx\n";
         let mut reader = Cursor::new(input);
 
-        let error = BrukerRelaxSection::new(label, &value, next_line, &mut reader).unwrap_err();
+        let error = BrukerRelaxSection::new(label, value, next_line, &mut reader).unwrap_err();
 
         assert!(error.to_string().contains("Illegal"));
     }
@@ -5623,7 +6326,7 @@ mod tests {
         let input = b"";
         let mut reader = Cursor::new(input);
 
-        let error = BrukerRelaxSection::new(label, &value, next_line, &mut reader).unwrap_err();
+        let error = BrukerRelaxSection::new(label, value, next_line, &mut reader).unwrap_err();
 
         assert!(error.to_string().contains("Premature"));
     }
@@ -5636,7 +6339,7 @@ mod tests {
         let input = b"##SOME_LABEL= abc\n";
         let mut reader = Cursor::new(input);
 
-        let error = BrukerRelaxSection::new(label, &value, next_line, &mut reader).unwrap_err();
+        let error = BrukerRelaxSection::new(label, value, next_line, &mut reader).unwrap_err();
 
         assert!(
             error.to_string().contains("Illegal") && error.to_string().contains("not followed by")
@@ -113,37 +113,37 @@ impl<T: SeekBufRead> PeakAssignmentsParser<'_, T> {
 
         let x = parse_opt_str(x_opt.map(|m| m.as_str()), "x value in PEAK ASSIGNMENTS")?;
         let a = a_opt.unwrap().as_str().to_owned();
-        let (y, m, w) =
-            if Self::PEAK_ASSIGNMENTS_VARIABLE_LISTS[0] == self.variable_list && y_opt.is_some() {
-                let y = Self::parse_f64_token(y_opt.unwrap().as_str())?;
-                (Some(y), None, None)
-            } else if Self::PEAK_ASSIGNMENTS_VARIABLE_LISTS[1] == self.variable_list
-                && y_opt.is_some()
-                && wm_opt.is_some()
-            {
-                let y = Self::parse_f64_token(y_opt.unwrap().as_str())?;
-                let w = Self::parse_f64_token(wm_opt.unwrap().as_str())?;
-                (Some(y), None, Some(w))
-            } else if Self::PEAK_ASSIGNMENTS_VARIABLE_LISTS[2] == self.variable_list
-                && y_opt.is_some()
-                && wm_opt.is_some()
-            {
-                let y = Self::parse_f64_token(y_opt.unwrap().as_str())?;
-                #[allow(clippy::unnecessary_unwrap)]
-                let m = wm_opt.unwrap().as_str();
-                (Some(y), Some(m), None)
-            } else if Self::PEAK_ASSIGNMENTS_VARIABLE_LISTS[3] == self.variable_list
-                && y_opt.is_some()
-                && wm_opt.is_some()
-                && w_opt.is_some()
-            {
-                let y = Self::parse_f64_token(y_opt.unwrap().as_str())?;
-                let m = wm_opt.unwrap().as_str();
-                let w = Self::parse_f64_token(w_opt.unwrap().as_str())?;
-                (Some(y), Some(m), Some(w))
-            } else {
-                (None, None, None)
-            };
+        let (y, m, w) = if Self::PEAK_ASSIGNMENTS_VARIABLE_LISTS[0] == self.variable_list
+            && let Some(y) = y_opt
+        {
+            let y = Self::parse_f64_token(y.as_str())?;
+            (Some(y), None, None)
+        } else if Self::PEAK_ASSIGNMENTS_VARIABLE_LISTS[1] == self.variable_list
+            && let Some(y) = y_opt
+            && let Some(w) = wm_opt
+        {
+            let y = Self::parse_f64_token(y.as_str())?;
+            let w = Self::parse_f64_token(w.as_str())?;
+            (Some(y), None, Some(w))
+        } else if Self::PEAK_ASSIGNMENTS_VARIABLE_LISTS[2] == self.variable_list
+            && let Some(y) = y_opt
+            && let Some(m) = wm_opt
+        {
+            let y = Self::parse_f64_token(y.as_str())?;
+            let m = m.as_str();
+            (Some(y), Some(m), None)
+        } else if Self::PEAK_ASSIGNMENTS_VARIABLE_LISTS[3] == self.variable_list
+            && let Some(y) = y_opt
+            && let Some(m) = wm_opt
+            && let Some(w) = w_opt
+        {
+            let y = Self::parse_f64_token(y.as_str())?;
+            let m = m.as_str();
+            let w = Self::parse_f64_token(w.as_str())?;
+            (Some(y), Some(m), Some(w))
+        } else {
+            (None, None, None)
+        };
 
         Ok(PeakAssignment {
             x,
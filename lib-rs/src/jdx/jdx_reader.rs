@@ -17,26 +17,89 @@
 // IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
 // CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
-use super::jdx_parser::{
-    AuditTrail, BrukerRelaxSection, BrukerSpecificParameters, JdxBlock, NTuples, Page,
-    PeakAssignments, PeakTable,
+use super::{
+    jdx_molfile_exporter::MolfileExporter,
+    jdx_parser::{
+        Abscissa, AuditTrail, BondType, BrukerRelaxSection, BrukerSpecificParameters, DataTable,
+        JdxBlock, NTuples, NTuplesAttributes, Page, PeakAssignments, PeakTable, Structure,
+    },
 };
-use crate::common::SfError;
+use crate::common::{SfError, SfErrorKind};
 use crate::{
-    api::{Column, Node, Parameter, PointXy, Reader, SeekBufRead, Table, Value},
-    utils::convert_path_to_node_indices,
+    api::{
+        Axis, COMMON_EXPORT_FORMATS, Column, ComplexData, ExportFormat, Exporter, NdArray, Node,
+        NodeLink, Parameter, ParseWarning, PointXy, Reader, SeekBufRead, Table, Value,
+        export_data_set,
+    },
+    bruker::bruker_parameters::{BrukerParameter, map_parameters},
+    utils::{convert_path_to_node_indices, extract_data_range},
 };
-use std::{collections::HashMap, path::Path};
+use std::{borrow::Cow, collections::HashMap, io::Write, ops::Range, path::Path};
 
 pub struct JdxReader {
     path: String,
     file: JdxBlock<Box<dyn SeekBufRead>>,
 }
 
+/// The JCAMP-DX structure corresponding to a node.
+enum JdxNode<'a> {
+    /// A block and whether it is the root block.
+    Block(&'a JdxBlock<Box<dyn SeekBufRead>>, bool),
+    BrukerRelaxSection(&'a BrukerRelaxSection),
+    BrukerSpecificParameters(&'a BrukerSpecificParameters),
+    /// An NTUPLES record and whether it holds peak data.
+    NTuples(&'a NTuples<Box<dyn SeekBufRead>>, bool),
+    /// An NTUPLES record, the index of one of its pages, and whether it holds peak data.
+    NTuplesPage(&'a NTuples<Box<dyn SeekBufRead>>, usize, bool),
+    AuditTrail(&'a AuditTrail<Box<dyn SeekBufRead>>),
+    /// The atoms of the JCAMP-CS structure of a block.
+    AtomList(&'a JdxBlock<Box<dyn SeekBufRead>>),
+    /// The bonds of the JCAMP-CS structure of a block.
+    BondList(&'a JdxBlock<Box<dyn SeekBufRead>>),
+}
+
 impl Reader for JdxReader {
     fn read(&self, path: &str) -> Result<Node, SfError> {
-        let node_indices = convert_path_to_node_indices(path)?;
-        self.retrieve_node(&node_indices)
+        self.read_node(path)
+            .map_err(|e| e.or_kind(SfErrorKind::CorruptData))
+    }
+
+    fn read_data_len(&self, path: &str) -> Result<usize, SfError> {
+        self.read_node_data_len(path)
+            .map_err(|e| e.or_kind(SfErrorKind::CorruptData))
+    }
+
+    fn read_data(&self, path: &str, range: Range<usize>) -> Result<Vec<PointXy>, SfError> {
+        self.read_node_data(path, range)
+            .map_err(|e| e.or_kind(SfErrorKind::CorruptData))
+    }
+
+    fn get_warnings(&self) -> &[ParseWarning] {
+        &self.file.warnings
+    }
+
+    /// Provides the export formats for all readers and additionally MDL Molfile if a block
+    /// holds a chemical structure.
+    fn get_export_formats(&self) -> &'static [ExportFormat] {
+        const EXPORT_FORMATS_WITH_MOLFILE: [ExportFormat; 6] = [
+            ExportFormat::Json,
+            ExportFormat::Csv,
+            ExportFormat::Tsv,
+            ExportFormat::Jdx,
+            ExportFormat::Gaml,
+            ExportFormat::Molfile,
+        ];
+        match self.find_structure_block() {
+            Some(_) => &EXPORT_FORMATS_WITH_MOLFILE,
+            None => &COMMON_EXPORT_FORMATS,
+        }
+    }
+
+    fn export(&self, format: ExportFormat, writer: &mut dyn Write) -> Result<(), SfError> {
+        match format {
+            ExportFormat::Molfile => self.export_molfile(writer),
+            _ => export_data_set(self, format, writer),
+        }
     }
 }
 
@@ -48,6 +111,44 @@ impl JdxReader {
         }
     }
 
+    fn read_node(&self, path: &str) -> Result<Node, SfError> {
+        let node_indices = self.convert_path(path)?;
+        self.retrieve_node(&node_indices)
+    }
+
+    fn read_node_data_len(&self, path: &str) -> Result<usize, SfError> {
+        let node_indices = self.convert_path(path)?;
+        match self.find_node(&node_indices)? {
+            JdxNode::Block(block, _) => Self::read_block_data_len(block),
+            JdxNode::NTuplesPage(n_tuples, page_index, _) => {
+                match &n_tuples.pages[page_index].data_table {
+                    None => Ok(0),
+                    Some(data_table) => match data_table.attributes.1.var_dim {
+                        Some(n_points) => Ok(n_points as usize),
+                        None => Ok(data_table.get_data()?.len()),
+                    },
+                }
+            }
+            _ => Ok(0),
+        }
+    }
+
+    fn read_node_data(&self, path: &str, range: Range<usize>) -> Result<Vec<PointXy>, SfError> {
+        let node_indices = self.convert_path(path)?;
+        let raw_data = match self.find_node(&node_indices)? {
+            JdxNode::Block(block, _) => Self::read_block_raw_data_range(block, &range)?,
+            JdxNode::NTuplesPage(n_tuples, page_index, _) => {
+                match &n_tuples.pages[page_index].data_table {
+                    None => extract_data_range(vec![], &range)?,
+                    Some(data_table) => data_table.get_data_range(&range)?,
+                }
+            }
+            _ => extract_data_range(vec![], &range)?,
+        };
+
+        Ok(Self::map_xy_data(&raw_data))
+    }
+
     fn get_block_name(block: &JdxBlock<Box<dyn SeekBufRead>>) -> &str {
         block
             .get_ldr("TITLE")
@@ -55,6 +156,29 @@ impl JdxReader {
             .unwrap_or_default()
     }
 
+    /// The DATA TYPE of a block, "STRUCTURE" for JCAMP-CS blocks without DATA TYPE, and
+    /// "BLOCK" otherwise.
+    fn get_data_type(block: &JdxBlock<Box<dyn SeekBufRead>>) -> String {
+        match block.get_ldr("DATATYPE") {
+            Some(ldr) if !ldr.value.trim().is_empty() => ldr.value.trim().to_owned(),
+            _ if block.has_structure() => "STRUCTURE".to_owned(),
+            _ => "BLOCK".to_owned(),
+        }
+    }
+
+    /// The index of the ATOMLIST child node of a block holding a structure.
+    fn get_atom_list_index(block: &JdxBlock<Box<dyn SeekBufRead>>) -> usize {
+        block.bruker_relax_sections.len()
+            + block.bruker_specific_parameters.len()
+            + usize::from(block.n_tuples.is_some())
+            + usize::from(block.audit_trail.is_some())
+    }
+
+    /// The index of the first child node of a block representing a nested block.
+    fn get_child_blocks_start_index(block: &JdxBlock<Box<dyn SeekBufRead>>) -> usize {
+        Self::get_atom_list_index(block) + 2 * usize::from(block.has_structure())
+    }
+
     fn is_peak_data(block: &JdxBlock<Box<dyn SeekBufRead>>) -> bool {
         let data_type = block
             .get_ldr("DATATYPE")
@@ -65,6 +189,35 @@ impl JdxReader {
     }
 
     fn retrieve_node(&self, node_indices: &[usize]) -> Result<Node, SfError> {
+        match self.find_node(node_indices)? {
+            JdxNode::Block(block, is_root) => {
+                let mut block_node = Self::map_block(block)?;
+                block_node.links = self.map_block_links(node_indices, block)?;
+                if is_root {
+                    // replace block node name with file name for root node
+                    let path = Path::new(&self.path);
+                    let file_name = path.file_name().and_then(|f| f.to_str());
+                    if let Some(name) = file_name {
+                        block_node.name = name.to_owned();
+                    }
+                }
+                Ok(block_node)
+            }
+            JdxNode::BrukerRelaxSection(section) => Self::map_bruker_relax_section(section),
+            JdxNode::BrukerSpecificParameters(section) => {
+                Self::map_bruker_specific_parameters(section)
+            }
+            JdxNode::NTuples(n_tuples, is_peak_data) => Self::map_n_tuples(n_tuples, is_peak_data),
+            JdxNode::NTuplesPage(n_tuples, page_index, is_peak_data) => {
+                Self::map_n_tuples_page(n_tuples, page_index, is_peak_data)
+            }
+            JdxNode::AuditTrail(audit_trail) => Self::map_audit_trail(audit_trail),
+            JdxNode::AtomList(block) => Self::map_atom_list(block),
+            JdxNode::BondList(block) => Self::map_bond_list(block),
+        }
+    }
+
+    fn find_node(&self, node_indices: &[usize]) -> Result<JdxNode<'_>, SfError> {
         let generate_illegal_path_error =
             |node_index: usize, block: &JdxBlock<Box<dyn SeekBufRead>>| -> SfError {
                 let block_title = Self::get_block_name(block);
@@ -72,6 +225,7 @@ impl JdxReader {
                     "Illegal path for reading node. Block: \"{}\", child index: {}",
                     block_title, node_index
                 ))
+                .with_kind(SfErrorKind::IllegalNodePath)
             };
 
         let mut block = &self.file;
@@ -90,11 +244,9 @@ impl JdxReader {
             } else {
                 n_tuples_index
             };
-            let child_blocks_start_index = if block.audit_trail.is_some() {
-                audit_trail_index + 1
-            } else {
-                audit_trail_index
-            };
+            let atom_list_index = Self::get_atom_list_index(block);
+            let bond_list_index = atom_list_index + 1;
+            let child_blocks_start_index = Self::get_child_blocks_start_index(block);
 
             // node_index >= bruker_relax_start_index, always true
             if node_index < bruker_relax_end_index && !block.bruker_relax_sections.is_empty() {
@@ -103,9 +255,9 @@ impl JdxReader {
                     // not a leaf node
                     return Err(generate_illegal_path_error(node_index, block));
                 }
-                return Self::map_bruker_relax_section(
+                return Ok(JdxNode::BrukerRelaxSection(
                     &block.bruker_relax_sections[node_index - bruker_relax_start_index],
-                );
+                ));
             }
             if node_index >= bruker_params_start_index
                 && node_index < bruker_params_end_index
@@ -116,26 +268,43 @@ impl JdxReader {
                     // not a leaf node
                     return Err(generate_illegal_path_error(node_index, block));
                 }
-                return Self::map_bruker_specific_parameters(
+                return Ok(JdxNode::BrukerSpecificParameters(
                     &block.bruker_specific_parameters[node_index - bruker_params_start_index],
-                );
+                ));
             }
-            if node_index == n_tuples_index && block.n_tuples.is_some() {
+            if node_index == n_tuples_index
+                && let Some(n_tuples) = &block.n_tuples
+            {
                 // consider NTUPLES LDR as child node
                 let n_tuples_indices = &node_indices[(iteration_index + 1)..];
-                return Self::map_n_tuples(
-                    block.n_tuples.as_ref().unwrap(),
+                return Self::find_n_tuples_node(
+                    n_tuples,
                     n_tuples_indices,
                     Self::is_peak_data(block),
                 );
             }
-            if node_index == audit_trail_index && block.audit_trail.is_some() {
+            if node_index == audit_trail_index
+                && let Some(audit_trail) = &block.audit_trail
+            {
                 // consider AUDIT TRAIL LDR as child node
                 if iteration_index < node_indices.len() - 1 {
                     // not a leaf node
                     return Err(generate_illegal_path_error(node_index, block));
                 }
-                return Self::map_audit_trail(block.audit_trail.as_ref().unwrap());
+                return Ok(JdxNode::AuditTrail(audit_trail));
+            }
+            if (node_index == atom_list_index || node_index == bond_list_index)
+                && block.has_structure()
+            {
+                // consider JCAMP-CS ATOMLIST and BONDLIST LDRs as child nodes
+                if iteration_index < node_indices.len() - 1 {
+                    // not a leaf node
+                    return Err(generate_illegal_path_error(node_index, block));
+                }
+                return match node_index == atom_list_index {
+                    true => Ok(JdxNode::AtomList(block)),
+                    false => Ok(JdxNode::BondList(block)),
+                };
             }
             let child_block = block.blocks.get(node_index - child_blocks_start_index);
             match child_block {
@@ -147,17 +316,165 @@ impl JdxReader {
             }
         }
         // block is leaf node
-        let mut block_node = Self::map_block(block, Self::is_peak_data(block))?;
-        if iteration_index == 0 {
-            // root node
-            // replace block node name with file name for root node
-            let path = Path::new(&self.path);
-            let file_name = path.file_name().and_then(|f| f.to_str());
-            if let Some(name) = file_name {
-                block_node.name = name.to_owned();
+        Ok(JdxNode::Block(block, iteration_index == 0))
+    }
+
+    fn find_n_tuples_node<'a>(
+        n_tuples: &'a NTuples<Box<dyn SeekBufRead>>,
+        node_indices: &[usize],
+        is_peak_data: bool,
+    ) -> Result<JdxNode<'a>, SfError> {
+        if node_indices.is_empty() {
+            return Ok(JdxNode::NTuples(n_tuples, is_peak_data));
+        }
+
+        if node_indices.len() > 1 || node_indices[0] >= n_tuples.pages.len() {
+            let path = node_indices
+                .iter()
+                .map(|i| i.to_string())
+                .collect::<Vec<String>>()
+                .join(" ");
+            return Err(SfError::new(&format!(
+                "Illegal indices for reading NTUPLES node: {}",
+                path
+            ))
+            .with_kind(SfErrorKind::IllegalNodePath));
+        }
+
+        Ok(JdxNode::NTuplesPage(
+            n_tuples,
+            node_indices[0],
+            is_peak_data,
+        ))
+    }
+
+    /// Maps the CROSS REFERENCEs from and to a block to links to the referenced and referencing
+    /// blocks of the same LINK block.
+    ///
+    /// Peak assignments referencing a structure are also linked to its ATOMLIST.
+    fn map_block_links(
+        &self,
+        node_indices: &[usize],
+        block: &JdxBlock<Box<dyn SeekBufRead>>,
+    ) -> Result<Vec<NodeLink>, SfError> {
+        let Some((_, parent_indices)) = node_indices.split_last() else {
+            // root block
+            return Ok(vec![]);
+        };
+        let JdxNode::Block(parent, _) = self.find_node(parent_indices)? else {
+            return Ok(vec![]);
+        };
+        let start_index = Self::get_child_blocks_start_index(parent);
+        let map_sibling_path = |index: usize| -> String {
+            parent_indices
+                .iter()
+                .chain([start_index + index].iter())
+                .map(|i| format!("/{}", i))
+                .collect()
+        };
+
+        let mut links = Vec::<NodeLink>::new();
+        let mut add_link = |link: NodeLink| {
+            if !links.contains(&link) {
+                links.push(link);
+            }
+        };
+        for reference in block.get_cross_references() {
+            let target = parent.blocks.iter().enumerate().find(|(_, sibling)| {
+                !std::ptr::eq(*sibling, block)
+                    && sibling.get_block_id() == Some(reference.block_id.as_str())
+            });
+            if let Some((index, sibling)) = target {
+                let path = map_sibling_path(index);
+                let relation = reference
+                    .relation
+                    .unwrap_or_else(|| Self::get_data_type(sibling));
+                add_link(NodeLink::new(relation, &path));
+                if block.peak_assignments.is_some() && sibling.has_structure() {
+                    let atom_list_index = Self::get_atom_list_index(sibling);
+                    add_link(NodeLink::new(
+                        "ATOMLIST",
+                        format!("{}/{}", path, atom_list_index),
+                    ));
+                }
+            }
+        }
+        if let Some(block_id) = block.get_block_id() {
+            for (index, sibling) in parent.blocks.iter().enumerate() {
+                let is_referencing = !std::ptr::eq(sibling, block)
+                    && sibling
+                        .get_cross_references()
+                        .iter()
+                        .any(|reference| reference.block_id == block_id);
+                if is_referencing {
+                    add_link(NodeLink::new(
+                        Self::get_data_type(sibling),
+                        map_sibling_path(index),
+                    ));
+                }
+            }
+        }
+        Ok(links)
+    }
+
+    /// Finds the node path of a block by its path of BLOCK_IDs, e.g., "/2" for the block
+    /// with BLOCK_ID 2 in the root LINK block or "/3/1" for a block in a nested LINK block.
+    pub fn find_block_path(&self, block_id_path: &str) -> Result<String, SfError> {
+        let mut block = &self.file;
+        let mut path = String::new();
+        for block_id in block_id_path.split('/').map(str::trim) {
+            if block_id.is_empty() {
+                continue;
             }
+            let (index, child_block) = block
+                .blocks
+                .iter()
+                .enumerate()
+                .find(|(_, b)| b.get_block_id() == Some(block_id))
+                .ok_or(
+                    SfError::new(&format!(
+                        "Illegal BLOCK_ID path for reading node: {}",
+                        block_id_path
+                    ))
+                    .with_kind(SfErrorKind::IllegalNodePath),
+                )?;
+            path.push_str(&format!(
+                "/{}",
+                Self::get_child_blocks_start_index(block) + index
+            ));
+            block = child_block;
         }
-        Ok(block_node)
+        Ok(path)
+    }
+
+    /// Reads a block by its path of BLOCK_IDs, e.g., "/2".
+    pub fn read_by_block_id(&self, block_id_path: &str) -> Result<Node, SfError> {
+        self.read(&self.find_block_path(block_id_path)?)
+    }
+
+    /// Converts a node path to node indices.
+    ///
+    /// Leading segments of the form "#<BLOCK_ID>" are resolved to blocks by their
+    /// BLOCK_ID, e.g., "/#3/0" for the first child node of the block with BLOCK_ID 3
+    /// in the root LINK block.
+    fn convert_path(&self, path: &str) -> Result<Vec<usize>, SfError> {
+        let segments: Vec<&str> = path
+            .split('/')
+            .map(str::trim)
+            .filter(|segment| !segment.is_empty())
+            .collect();
+        let block_ids: Vec<&str> = segments
+            .iter()
+            .map_while(|segment| segment.strip_prefix('#'))
+            .collect();
+        if block_ids.is_empty() {
+            return convert_path_to_node_indices(path);
+        }
+        let mut resolved_path = self.find_block_path(&block_ids.join("/"))?;
+        for segment in &segments[block_ids.len()..] {
+            resolved_path.push_str(&format!("/{}", segment));
+        }
+        convert_path_to_node_indices(&resolved_path)
     }
 
     fn map_bruker_relax_section(section: &BrukerRelaxSection) -> Result<Node, SfError> {
@@ -177,69 +494,381 @@ impl JdxReader {
             data: vec![],
             metadata: vec![],
             table: None,
+            nd_array: None,
+            complex_data: None,
+            links: vec![],
             child_node_names: vec![],
         })
     }
 
     fn map_bruker_specific_parameters(section: &BrukerSpecificParameters) -> Result<Node, SfError> {
         let name = section.name.clone();
-        let mut parameters = vec![];
-        for ldr in &section.content {
-            parameters.push(Parameter::from_str_str(&ldr.label, &ldr.value));
-        }
+        let bruker_parameters: Vec<BrukerParameter> = section
+            .content
+            .iter()
+            .map(|ldr| BrukerParameter::parse(&ldr.label, &ldr.value))
+            .collect();
+        // typed values, arrays additionally as table
+        let (parameters, table) = map_parameters(&bruker_parameters);
 
         Ok(Node {
             name,
             parameters,
             data: vec![],
             metadata: vec![],
-            table: None,
+            table,
+            nd_array: None,
+            complex_data: None,
+            links: vec![],
             child_node_names: vec![],
         })
     }
 
     fn map_n_tuples(
         n_tuples: &NTuples<Box<dyn SeekBufRead>>,
-        node_indices: &[usize],
         is_peak_data: bool,
     ) -> Result<Node, SfError> {
-        if node_indices.is_empty() {
-            // map NTUPLES record
-            let name = n_tuples.data_form.to_owned();
+        // map NTUPLES record
+        let name = n_tuples.data_form.to_owned();
+
+        let mut parameters = vec![];
+        for ldr in &n_tuples.ldrs {
+            parameters.push(Parameter::from_str_str(&ldr.label, &ldr.value));
+        }
+
+        let mut child_node_names = Vec::<String>::new();
+        for page in &n_tuples.pages {
+            child_node_names.push(Self::map_n_tuples_page_name(page));
+        }
+
+        // peak data pages usually do not share x values,
+        // real and imaginary pages are represented as complex data instead
+        let complex_page_pairs = n_tuples.get_complex_page_pairs();
+        let nd_array = if is_peak_data || !complex_page_pairs.is_empty() {
+            None
+        } else if let Some(matrix) = Self::map_nd_nmr_matrix(n_tuples)? {
+            Some(matrix)
+        } else {
+            Self::map_n_tuples_array(n_tuples)?
+        };
+
+        // a single pair of real and imaginary pages, e.g., for an NMR FID
+        let complex_data = match complex_page_pairs.as_slice() {
+            [(real_index, imaginary_index)] if !is_peak_data => {
+                Self::map_complex_pages(n_tuples, *real_index, *imaginary_index, None)?
+            }
+            _ => None,
+        };
+
+        Ok(Node {
+            name,
+            parameters,
+            data: vec![],
+            metadata: vec![],
+            table: None,
+            nd_array,
+            complex_data,
+            links: vec![],
+            child_node_names,
+        })
+    }
+
+    /// Maps a pair of real and imaginary NTUPLES pages to a complex signal.
+    ///
+    /// The already decoded data of one of the pages may be passed with its page index
+    /// so that only the other page is decoded.
+    ///
+    /// Returns None if the pages hold different numbers of data points.
+    fn map_complex_pages(
+        n_tuples: &NTuples<Box<dyn SeekBufRead>>,
+        real_index: usize,
+        imaginary_index: usize,
+        decoded_page: Option<(usize, &[(f64, f64)])>,
+    ) -> Result<Option<ComplexData>, SfError> {
+        let (Some(real_table), Some(imaginary_table)) = (
+            &n_tuples.pages[real_index].data_table,
+            &n_tuples.pages[imaginary_index].data_table,
+        ) else {
+            return Ok(None);
+        };
+        let real_data = Self::get_page_data(real_table, real_index, decoded_page)?;
+        let imaginary_data = Self::get_page_data(imaginary_table, imaginary_index, decoded_page)?;
+        if real_data.len() != imaginary_data.len() {
+            return Ok(None);
+        }
+
+        let (x_attributes, real_attributes) = &real_table.attributes;
+        let imaginary_attributes = &imaginary_table.attributes.1;
+        let name =
+            Self::map_complex_name(&real_attributes.var_name, &imaginary_attributes.var_name);
+        let imaginary_unit = Self::map_attributes_unit(imaginary_attributes);
+        let unit = Self::map_attributes_unit(real_attributes).filter(|unit| {
+            imaginary_unit
+                .as_ref()
+                .is_some_and(|imaginary_unit| imaginary_unit == unit)
+        });
+        let axis = Axis::new(
+            &x_attributes.var_name,
+            Self::map_attributes_unit(x_attributes),
+            real_data.iter().map(|(x, _y)| *x).collect(),
+        );
 
-            let mut parameters = vec![];
-            for ldr in &n_tuples.ldrs {
-                parameters.push(Parameter::from_str_str(&ldr.label, &ldr.value));
+        let complex_data = ComplexData::new(
+            name,
+            unit,
+            axis,
+            real_data.iter().map(|(_x, y)| *y).collect(),
+            imaginary_data.iter().map(|(_x, y)| *y).collect(),
+        )?;
+        Ok(Some(complex_data))
+    }
+
+    /// Provides the data of a page, decoding it unless it is the already decoded page.
+    fn get_page_data<'a>(
+        data_table: &DataTable<Box<dyn SeekBufRead>>,
+        page_index: usize,
+        decoded_page: Option<(usize, &'a [(f64, f64)])>,
+    ) -> Result<Cow<'a, [(f64, f64)]>, SfError> {
+        match decoded_page {
+            Some((decoded_index, data)) if decoded_index == page_index => Ok(Cow::Borrowed(data)),
+            _ => Ok(Cow::Owned(data_table.get_data()?)),
+        }
+    }
+
+    /// Derives the name of a complex signal from the names of its parts,
+    /// e.g., "FID" from "FID/REAL" and "FID/IMAG".
+    fn map_complex_name(real_name: &str, imaginary_name: &str) -> String {
+        match (real_name.split_once('/'), imaginary_name.split_once('/')) {
+            (Some((real_prefix, _)), Some((imaginary_prefix, _)))
+                if real_prefix.trim() == imaginary_prefix.trim() =>
+            {
+                real_prefix.trim().to_owned()
             }
+            _ => format!("{}, {}", real_name, imaginary_name),
+        }
+    }
 
-            let mut child_node_names = Vec::<String>::new();
-            for page in &n_tuples.pages {
-                child_node_names.push(Self::map_n_tuples_page_name(page));
+    /// Maps the pages of an NTUPLES record to a two dimensional array
+    /// if all pages hold the same variables with the same units and x values.
+    fn map_n_tuples_array(
+        n_tuples: &NTuples<Box<dyn SeekBufRead>>,
+    ) -> Result<Option<NdArray>, SfError> {
+        let Some(data_tables) = n_tuples
+            .pages
+            .iter()
+            .map(|page| page.data_table.as_ref())
+            .collect::<Option<Vec<_>>>()
+        else {
+            return Ok(None);
+        };
+        let Some(first_data_table) = data_tables.first() else {
+            return Ok(None);
+        };
+        let (x_attributes, y_attributes) = &first_data_table.attributes;
+        // check the variables and abscissas before decoding any page
+        let abscissa = first_data_table.get_abscissa();
+        if data_tables.iter().any(|data_table| {
+            let (page_x_attributes, page_y_attributes) = &data_table.attributes;
+            page_x_attributes.symbol != x_attributes.symbol
+                || page_x_attributes.units != x_attributes.units
+                || page_y_attributes.symbol != y_attributes.symbol
+                || page_y_attributes.units != y_attributes.units
+                || data_table.get_n_points() != first_data_table.get_n_points()
+                || match (&abscissa, data_table.get_abscissa()) {
+                    (Some(abscissa), Some(other)) => !abscissa.matches(&other),
+                    (None, None) => false,
+                    _ => true,
+                }
+        }) {
+            return Ok(None);
+        }
+
+        let first_data = first_data_table.get_data()?;
+        let x_values: Vec<f64> = first_data.iter().map(|(x, _y)| *x).collect();
+        let mut values = Vec::<f64>::with_capacity(x_values.len() * data_tables.len());
+        values.extend(first_data.iter().map(|(_x, y)| *y));
+        for data_table in &data_tables[1..] {
+            let data = data_table.get_data()?;
+            if data.len() != x_values.len()
+                || data
+                    .iter()
+                    .zip(&x_values)
+                    .any(|((x, _y), shared_x)| x != shared_x)
+            {
+                return Ok(None);
             }
+            values.extend(data.iter().map(|(_x, y)| *y));
+        }
 
-            return Ok(Node {
-                name,
-                parameters,
-                data: vec![],
-                metadata: vec![],
-                table: None,
-                child_node_names,
-            });
+        let x_axis = Axis::new(&x_attributes.var_name, x_attributes.units.clone(), x_values);
+        let page_axis = Self::map_n_tuples_page_axis(n_tuples);
+
+        let mut y_names = Vec::<&str>::new();
+        for data_table in &data_tables {
+            let y_name = data_table.attributes.1.var_name.as_str();
+            if !y_names.contains(&y_name) {
+                y_names.push(y_name);
+            }
         }
 
-        if node_indices.len() > 1 || node_indices[0] > n_tuples.pages.len() {
-            let path = node_indices
-                .iter()
-                .map(|i| i.to_string())
-                .collect::<Vec<String>>()
-                .join(" ");
-            return Err(SfError::new(&format!(
-                "Illegal indices for reading NTUPLES node: {}",
-                path
-            )));
+        let array = NdArray::new(
+            y_names.join(", "),
+            y_attributes.units.clone(),
+            vec![page_axis, x_axis],
+            values,
+        )?;
+        Ok(Some(array))
+    }
+
+    /// Checks whether an NTUPLES record holds multidimensional NMR data,
+    /// e.g., "nD NMR SPECTRUM" or "nD NMR FID".
+    fn is_nd_nmr(n_tuples: &NTuples<Box<dyn SeekBufRead>>) -> bool {
+        n_tuples
+            .data_form
+            .trim()
+            .to_uppercase()
+            .starts_with("ND NMR")
+    }
+
+    /// Maps the pages of an nD NMR NTUPLES record, e.g., "(F2++(Y..Y))" pages
+    /// keyed by "F1=", to a two dimensional matrix with one row per page.
+    ///
+    /// Returns None if the record is not an nD NMR record, the pages hold
+    /// different variables, e.g., real and imaginary parts, or the rows do not
+    /// share the same abscissa.
+    fn map_nd_nmr_matrix(
+        n_tuples: &NTuples<Box<dyn SeekBufRead>>,
+    ) -> Result<Option<NdArray>, SfError> {
+        if !Self::is_nd_nmr(n_tuples) {
+            return Ok(None);
+        }
+        let Some(data_tables) = n_tuples
+            .pages
+            .iter()
+            .map(|page| page.data_table.as_ref())
+            .collect::<Option<Vec<_>>>()
+        else {
+            return Ok(None);
+        };
+        let Some(first_data_table) = data_tables.first() else {
+            return Ok(None);
+        };
+        let (column_attributes, value_attributes) = &first_data_table.attributes;
+        let Some(abscissa) = first_data_table.get_abscissa() else {
+            return Ok(None);
+        };
+        if data_tables.iter().any(|data_table| {
+            data_table.attributes.0.symbol != column_attributes.symbol
+                || data_table.attributes.1.symbol != value_attributes.symbol
+                || data_table
+                    .get_abscissa()
+                    .is_none_or(|other| !abscissa.matches(&other))
+        }) {
+            return Ok(None);
         }
 
-        Self::map_n_tuples_page(&n_tuples.pages[node_indices[0]], is_peak_data)
+        let n_columns = abscissa.n_points as usize;
+        let mut values = Vec::<f64>::with_capacity(n_columns * data_tables.len());
+        for data_table in &data_tables {
+            let data = data_table.get_data()?;
+            if data.len() != n_columns {
+                return Ok(None);
+            }
+            values.extend(data.iter().map(|(_x, y)| *y));
+        }
+
+        let row_axis = Self::map_page_variables_axis(n_tuples)
+            .or_else(|| Self::map_nd_nmr_row_axis(n_tuples, &column_attributes.symbol))
+            .unwrap_or_else(|| Self::map_page_index_axis(n_tuples));
+        let column_axis = Axis::new(
+            &column_attributes.var_name,
+            Self::map_attributes_unit(column_attributes),
+            abscissa.values().collect(),
+        );
+
+        let matrix = NdArray::new(
+            &value_attributes.var_name,
+            Self::map_attributes_unit(value_attributes),
+            vec![row_axis, column_axis],
+            values,
+        )?;
+        Ok(Some(matrix))
+    }
+
+    /// Maps the FIRST, LAST, and VAR_DIM attributes of the independent variable
+    /// other than the column variable, e.g., "F1", to the row axis.
+    ///
+    /// Returns None if no such variable exists or VAR_DIM does not match the
+    /// number of pages.
+    fn map_nd_nmr_row_axis(
+        n_tuples: &NTuples<Box<dyn SeekBufRead>>,
+        column_symbol: &str,
+    ) -> Option<Axis> {
+        let attributes = n_tuples.attributes.iter().find(|a| {
+            a.symbol != column_symbol
+                && a.var_type
+                    .as_ref()
+                    .is_some_and(|var_type| var_type.eq_ignore_ascii_case("INDEPENDENT"))
+        })?;
+        let n_points = attributes.var_dim?;
+        if n_points != n_tuples.pages.len() as u64 {
+            return None;
+        }
+        let abscissa = Abscissa::new(attributes.first?, attributes.last?, n_points);
+        Some(Axis::new(
+            &attributes.var_name,
+            Self::map_attributes_unit(attributes),
+            abscissa.values().collect(),
+        ))
+    }
+
+    fn map_attributes_unit(attributes: &NTuplesAttributes) -> Option<String> {
+        attributes
+            .units
+            .as_ref()
+            .filter(|unit| !unit.is_empty())
+            .cloned()
+    }
+
+    /// Maps the page variables, e.g., "N=1", to an axis.
+    ///
+    /// Falls back to page indices if the page variables are not numeric
+    /// or do not refer to the same variable.
+    fn map_n_tuples_page_axis(n_tuples: &NTuples<Box<dyn SeekBufRead>>) -> Axis {
+        Self::map_page_variables_axis(n_tuples)
+            .unwrap_or_else(|| Self::map_page_index_axis(n_tuples))
+    }
+
+    /// Maps the page variables, e.g., "N=1", to an axis if they are numeric
+    /// and refer to the same variable.
+    fn map_page_variables_axis(n_tuples: &NTuples<Box<dyn SeekBufRead>>) -> Option<Axis> {
+        let page_values = n_tuples
+            .pages
+            .iter()
+            .map(|page| {
+                let (symbol, value) = page.page_variables.split_once('=')?;
+                let value = value.trim().parse::<f64>().ok()?;
+                Some((symbol.trim(), value))
+            })
+            .collect::<Option<Vec<_>>>();
+
+        if let Some(page_values) = page_values
+            && let Some((symbol, _value)) = page_values.first()
+            && page_values.iter().all(|(s, _v)| s == symbol)
+        {
+            let attributes = n_tuples.attributes.iter().find(|a| &a.symbol == symbol);
+            let name = attributes.map_or(*symbol, |a| a.var_name.as_str());
+            let unit = attributes.and_then(Self::map_attributes_unit);
+            let values = page_values.into_iter().map(|(_s, v)| v).collect();
+            return Some(Axis::new(name, unit, values));
+        }
+
+        None
+    }
+
+    fn map_page_index_axis(n_tuples: &NTuples<Box<dyn SeekBufRead>>) -> Axis {
+        let values = (0..n_tuples.pages.len()).map(|i| i as f64).collect();
+        Axis::new("PAGE", None, values)
     }
 
     fn map_n_tuples_page_name(page: &Page<Box<dyn SeekBufRead>>) -> String {
@@ -252,9 +881,11 @@ impl JdxReader {
     }
 
     fn map_n_tuples_page(
-        page: &Page<Box<dyn SeekBufRead>>,
+        n_tuples: &NTuples<Box<dyn SeekBufRead>>,
+        page_index: usize,
         is_peak_data: bool,
     ) -> Result<Node, SfError> {
+        let page = &n_tuples.pages[page_index];
         let name = Self::map_n_tuples_page_name(page);
 
         let mut parameters = vec![];
@@ -262,33 +893,48 @@ impl JdxReader {
             parameters.push(Parameter::from_str_str(&ldr.label, &ldr.value));
         }
 
-        let mut data = vec![];
-        let table = match &page.data_table {
-            None => None,
-            Some(data_table) => {
-                if let Some(plot_desc) = &data_table.plot_descriptor {
-                    parameters.push(Parameter::from_str_str("Plot Descriptor", plot_desc));
-                }
-
-                let d = data_table.get_data()?;
-                data = Self::map_xy_data(&d);
-
-                if is_peak_data {
-                    Some(Self::map_data_as_peak_table(&d))
-                } else {
-                    None
-                }
+        let mut raw_data = vec![];
+        if let Some(data_table) = &page.data_table {
+            if let Some(plot_desc) = &data_table.plot_descriptor {
+                parameters.push(Parameter::from_str_str("Plot Descriptor", plot_desc));
             }
+            raw_data = data_table.get_data()?;
+        }
+        let data = Self::map_xy_data(&raw_data);
+        let table = match &page.data_table {
+            Some(_) if is_peak_data => Some(Self::map_data_as_peak_table(&raw_data)),
+            _ => None,
         };
 
         let metadata = Self::map_page_metadata(page, is_peak_data);
 
+        // the complex signal of a real or imaginary page and its counterpart
+        let complex_page_pair =
+            n_tuples
+                .get_complex_page_pairs()
+                .into_iter()
+                .find(|(real_index, imaginary_index)| {
+                    *real_index == page_index || *imaginary_index == page_index
+                });
+        let complex_data = match complex_page_pair {
+            Some((real_index, imaginary_index)) if !is_peak_data => Self::map_complex_pages(
+                n_tuples,
+                real_index,
+                imaginary_index,
+                Some((page_index, &raw_data)),
+            )?,
+            _ => None,
+        };
+
         Ok(Node {
             name,
             parameters,
             data,
             metadata,
             table,
+            nd_array: None,
+            complex_data,
+            links: vec![],
             child_node_names: vec![],
         })
     }
@@ -319,10 +965,7 @@ impl JdxReader {
         metadata
     }
 
-    fn map_block(
-        block: &JdxBlock<Box<dyn SeekBufRead>>,
-        is_peak_data: bool,
-    ) -> Result<Node, SfError> {
+    fn map_block(block: &JdxBlock<Box<dyn SeekBufRead>>) -> Result<Node, SfError> {
         let name = Self::get_block_name(block).to_owned();
 
         let mut parameters = Vec::<Parameter>::new();
@@ -333,17 +976,12 @@ impl JdxReader {
             ));
         }
 
-        let mut data = Self::map_block_data(block)?;
+        let data = Self::map_xy_data(&Self::read_block_raw_data(block)?);
 
         let table = if let Some(peak_assignments) = &block.peak_assignments {
             Some(Self::map_peak_assignments(peak_assignments)?)
         } else if let Some(peak_table) = &block.peak_table {
-            let table = Self::map_peak_table(peak_table)?;
-            if is_peak_data && data.is_empty() {
-                // for MS, map peak table as data if no other data is present
-                data = Self::map_peak_table_as_data(peak_table)?;
-            }
-            Some(table)
+            Some(Self::map_peak_table(peak_table)?)
         } else {
             None
         };
@@ -369,6 +1007,11 @@ impl JdxReader {
             // consider AUDIT TRAIL LDR as child node
             child_node_names.push("AUDITTRAIL".to_owned());
         }
+        if block.has_structure() {
+            // consider JCAMP-CS ATOMLIST and BONDLIST LDRs as child nodes
+            child_node_names.push("ATOMLIST".to_owned());
+            child_node_names.push("BONDLIST".to_owned());
+        }
         for block in &block.blocks {
             child_node_names.push(Self::get_block_name(block).to_owned());
         }
@@ -381,11 +1024,16 @@ impl JdxReader {
             data,
             metadata,
             table,
+            nd_array: None,
+            complex_data: None,
+            links: vec![],
             child_node_names,
         })
     }
 
-    fn map_block_data(block: &JdxBlock<Box<dyn SeekBufRead>>) -> Result<Vec<PointXy>, SfError> {
+    fn read_block_raw_data(
+        block: &JdxBlock<Box<dyn SeekBufRead>>,
+    ) -> Result<Vec<(f64, f64)>, SfError> {
         let raw_data = if let Some(xy_data) = &block.xy_data {
             xy_data.get_data()?
         } else if let Some(ra_data) = &block.ra_data {
@@ -396,7 +1044,53 @@ impl JdxReader {
             vec![]
         };
 
-        Ok(Self::map_xy_data(&raw_data))
+        if raw_data.is_empty()
+            && Self::is_peak_data(block)
+            && block.peak_assignments.is_none()
+            && let Some(peak_table) = &block.peak_table
+        {
+            // for MS, map peak table as data if no other data is present
+            return Self::read_peak_table_as_raw_data(peak_table);
+        }
+
+        Ok(raw_data)
+    }
+
+    /// Reads a range of a block's data, decoding XYDATA, RADATA, and XYPOINTS only up to
+    /// the end of the range.
+    fn read_block_raw_data_range(
+        block: &JdxBlock<Box<dyn SeekBufRead>>,
+        range: &Range<usize>,
+    ) -> Result<Vec<(f64, f64)>, SfError> {
+        match (&block.xy_data, &block.ra_data, &block.xy_points) {
+            (Some(xy_data), _, _) if xy_data.get_n_points() > 0 => xy_data.get_data_range(range),
+            (None, Some(ra_data), _) if ra_data.get_n_points() > 0 => ra_data.get_data_range(range),
+            (None, None, Some(xy_points)) if xy_points.get_n_points() > 0 => {
+                xy_points.get_data_range(range)
+            }
+            // possibly mapped peak table
+            _ => extract_data_range(Self::read_block_raw_data(block)?, range),
+        }
+    }
+
+    fn read_block_data_len(block: &JdxBlock<Box<dyn SeekBufRead>>) -> Result<usize, SfError> {
+        // NPOINTS is verified when reading the data, so it can be used to avoid parsing
+        let n_points = if let Some(xy_data) = &block.xy_data {
+            xy_data.get_n_points()
+        } else if let Some(ra_data) = &block.ra_data {
+            ra_data.get_n_points()
+        } else if let Some(xy_points) = &block.xy_points {
+            xy_points.get_n_points()
+        } else {
+            0
+        };
+
+        if n_points == 0 {
+            // possibly mapped peak table
+            return Ok(Self::read_block_raw_data(block)?.len());
+        }
+
+        Ok(n_points as usize)
     }
 
     fn map_xy_data(xy_data: &[(f64, f64)]) -> Vec<PointXy> {
@@ -489,13 +1183,13 @@ impl JdxReader {
         Ok(Table { column_names, rows })
     }
 
-    fn map_peak_table_as_data(
+    fn read_peak_table_as_raw_data(
         peak_table: &PeakTable<Box<dyn SeekBufRead>>,
-    ) -> Result<Vec<PointXy>, SfError> {
+    ) -> Result<Vec<(f64, f64)>, SfError> {
         let data = peak_table
             .get_data()?
             .iter()
-            .map(|peak| PointXy::new(peak.x, peak.y))
+            .map(|peak| (peak.x, peak.y))
             .collect();
         Ok(data)
     }
@@ -645,6 +1339,148 @@ impl JdxReader {
             data: vec![],
             metadata: vec![],
             table: Some(Table { column_names, rows }),
+            nd_array: None,
+            complex_data: None,
+            links: vec![],
+            child_node_names: vec![],
+        })
+    }
+
+    /// Finds the first block holding a chemical structure, a LINK block preceding its nested
+    /// blocks.
+    fn find_structure_block(&self) -> Option<&JdxBlock<Box<dyn SeekBufRead>>> {
+        fn find(block: &JdxBlock<Box<dyn SeekBufRead>>) -> Option<&JdxBlock<Box<dyn SeekBufRead>>> {
+            match block.has_structure() {
+                true => Some(block),
+                false => block.blocks.iter().find_map(find),
+            }
+        }
+        find(&self.file)
+    }
+
+    /// Exports the chemical structure of the first block holding one as MDL Molfile with the
+    /// block title as title.
+    fn export_molfile(&self, writer: &mut dyn Write) -> Result<(), SfError> {
+        let block = self.find_structure_block().ok_or(
+            SfError::new(&format!(
+                "No chemical structure found for Molfile export: {}",
+                self.path
+            ))
+            .with_kind(SfErrorKind::UnsupportedFormat),
+        )?;
+        let structure = Self::read_structure(block)?;
+        let title = block
+            .get_ldr("TITLE")
+            .map(|ldr| ldr.value.as_str())
+            .unwrap_or_default();
+        MolfileExporter::new(&structure)
+            .with_title(title)
+            .write(writer)
+    }
+
+    fn read_structure(block: &JdxBlock<Box<dyn SeekBufRead>>) -> Result<Structure, SfError> {
+        block.get_structure()?.ok_or(SfError::new(&format!(
+            "No structure found in block: {}",
+            Self::get_block_name(block)
+        )))
+    }
+
+    fn map_atom_list(block: &JdxBlock<Box<dyn SeekBufRead>>) -> Result<Node, SfError> {
+        let structure = Self::read_structure(block)?;
+        let has_coordinates = structure
+            .atoms
+            .iter()
+            .any(|atom| atom.coordinates.is_some());
+
+        let mut column_names = vec![
+            Column::new("number", "AN"),
+            Column::new("symbol", "AS"),
+            Column::new("implicit_hydrogens", "NH"),
+            Column::new("charge", "CHARGE"),
+        ];
+        if has_coordinates {
+            column_names.push(Column::new("x_coordinate", "X"));
+            column_names.push(Column::new("y_coordinate", "Y"));
+            if structure.is_3d {
+                column_names.push(Column::new("z_coordinate", "Z"));
+            }
+        }
+
+        let mut rows = vec![];
+        for atom in &structure.atoms {
+            let mut row = HashMap::<String, Value>::from([
+                ("number".to_owned(), Value::U64(atom.number)),
+                ("symbol".to_owned(), Value::String(atom.symbol.clone())),
+                (
+                    "implicit_hydrogens".to_owned(),
+                    Value::U64(atom.implicit_hydrogens.into()),
+                ),
+                ("charge".to_owned(), Value::I64(atom.charge.into())),
+            ]);
+            if let Some((x, y, z)) = atom.coordinates {
+                row.insert("x_coordinate".to_owned(), Value::F64(x));
+                row.insert("y_coordinate".to_owned(), Value::F64(y));
+                if structure.is_3d {
+                    row.insert("z_coordinate".to_owned(), Value::F64(z));
+                }
+            }
+            rows.push(row);
+        }
+
+        let mut parameters = vec![];
+        if let Some(molecular_formula) = &structure.molecular_formula {
+            parameters.push(Parameter::from_str_str("MOLFORM", molecular_formula));
+        }
+
+        Ok(Node {
+            name: "ATOMLIST".to_owned(),
+            parameters,
+            data: vec![],
+            metadata: vec![],
+            table: Some(Table { column_names, rows }),
+            nd_array: None,
+            complex_data: None,
+            links: vec![],
+            child_node_names: vec![],
+        })
+    }
+
+    fn map_bond_list(block: &JdxBlock<Box<dyn SeekBufRead>>) -> Result<Node, SfError> {
+        let structure = Self::read_structure(block)?;
+
+        let column_names = vec![
+            Column::new("atom_1", "AN1"),
+            Column::new("atom_2", "AN2"),
+            Column::new("bond_type", "BT"),
+        ];
+        let rows = structure
+            .bonds
+            .iter()
+            .map(|bond| {
+                let bond_type = match bond.bond_type {
+                    BondType::Single => "S",
+                    BondType::Double => "D",
+                    BondType::Triple => "T",
+                    BondType::Quadruple => "Q",
+                    BondType::Aromatic => "A",
+                };
+                HashMap::<String, Value>::from([
+                    ("atom_1".to_owned(), Value::U64(bond.atom_1)),
+                    ("atom_2".to_owned(), Value::U64(bond.atom_2)),
+                    ("bond_type".to_owned(), Value::String(bond_type.to_owned())),
+                ])
+            })
+            .collect();
+
+        Ok(Node {
+            name: "BONDLIST".to_owned(),
+            parameters: vec![],
+            data: vec![],
+            metadata: vec![],
+            table: Some(Table { column_names, rows }),
+            nd_array: None,
+            complex_data: None,
+            links: vec![],
             child_node_names: vec![],
         })
     }
@@ -820,8 +1656,8 @@ mod tests {
         let cursor = Cursor::new(input);
         let buf_reader = BufReader::new(cursor);
         let buf_input: Box<dyn SeekBufRead> = Box::new(buf_reader);
-        let file = JdxParser::parse(&path, buf_input).unwrap();
-        let reader = JdxReader::new(&path, file);
+        let file = JdxParser::parse(path, buf_input).unwrap();
+        let reader = JdxReader::new(path, file);
 
         let root_node = &reader.read("/").unwrap();
         assert_eq!("CompoundFile.jdx", root_node.name);
@@ -1311,8 +2147,8 @@ mod tests {
         let cursor = Cursor::new(input);
         let buf_reader = BufReader::new(cursor);
         let buf_input: Box<dyn SeekBufRead> = Box::new(buf_reader);
-        let file = JdxParser::parse(&path, buf_input).unwrap();
-        let reader = JdxReader::new(&path, file);
+        let file = JdxParser::parse(path, buf_input).unwrap();
+        let reader = JdxReader::new(path, file);
 
         let root_node = &reader.read("/").unwrap();
         assert_eq!("Bruker_specific_relax.jdx", root_node.name);
@@ -1371,13 +2207,14 @@ mod tests {
         assert_eq!(4, bruker_params_section.parameters.len());
         assert_eq!(
             vec![
-                Parameter::from_str_str("$DU", "<C:/>"),
-                Parameter::from_str_str("$NAME", "<Jul11-2023>"),
-                Parameter::from_str_str("$AQSEQ", "0"),
-                Parameter::from_str_str("$AQMOD", "3"),
+                Parameter::from_str_str("$DU", "C:/"),
+                Parameter::from_str_str("$NAME", "Jul11-2023"),
+                Parameter::from_str_i64("$AQSEQ", 0),
+                Parameter::from_str_i64("$AQMOD", 3),
             ],
             bruker_params_section.parameters
         );
+        assert!(bruker_params_section.table.is_none());
 
         let bruker_params_section_f1 = &reader.read("/3").unwrap();
         assert_eq!(
@@ -1389,17 +2226,30 @@ mod tests {
         assert_eq!(3, bruker_params_section_f1.parameters.len());
         assert_eq!(
             vec![
-                Parameter::from_str_str(
-                    "$AMP",
-                    "(0..31)\n\
-                    100 100 100 100 100 100 100 100 100 100 100 100 100 100 100 100 100 100 \n\
-                    100 100 100 100 100 100 100 100 100 100 100 100 100 100 "
-                ),
-                Parameter::from_str_str("$AQSEQ", "0"),
-                Parameter::from_str_str("$AQMOD", "2"),
+                Parameter::from_str_str("$AMP", ["100"; 32].join(" ")),
+                Parameter::from_str_i64("$AQSEQ", 0),
+                Parameter::from_str_i64("$AQMOD", 2),
             ],
             bruker_params_section_f1.parameters
         );
+        let amp_table = bruker_params_section_f1.table.as_ref().unwrap();
+        assert_eq!(
+            vec![
+                Column::new("parameter", "Parameter"),
+                Column::new("index", "Index"),
+                Column::new("value", "Value"),
+            ],
+            amp_table.column_names
+        );
+        assert_eq!(32, amp_table.rows.len());
+        assert_eq!(
+            HashMap::from([
+                ("parameter".to_owned(), Value::String("$AMP".to_owned())),
+                ("index".to_owned(), Value::U64(31)),
+                ("value".to_owned(), Value::I64(100)),
+            ]),
+            amp_table.rows[31]
+        );
 
         let error_bruker_relax_not_leaf = &reader.read("/0/0").unwrap_err();
         assert!(error_bruker_relax_not_leaf.to_string().contains("Illegal"));
@@ -1407,4 +2257,182 @@ mod tests {
         let error_bruker_params_not_leaf = &reader.read("/0/0").unwrap_err();
         assert!(error_bruker_params_not_leaf.to_string().contains("Illegal"));
     }
+
+    #[test]
+    fn maps_nd_nmr_spectrum_pages_to_matrix() {
+        let input = b"##TITLE= 2D NMR Spectrum\n\
+                                    ##JCAMP-DX= 6.00\n\
+                                    ##DATA TYPE= nD NMR SPECTRUM\n\
+                                    ##DATA CLASS= NTUPLES\n\
+                                    ##ORIGIN= Test\n\
+                                    ##OWNER= Test\n\
+                                    ##NTUPLES= nD NMR SPECTRUM\n\
+                                    ##VAR_NAME= FREQUENCY1, FREQUENCY2, SPECTRUM\n\
+                                    ##SYMBOL=   F1,         F2,         Y\n\
+                                    ##VAR_TYPE= INDEPENDENT, INDEPENDENT, DEPENDENT\n\
+                                    ##VAR_FORM= AFFN,       ASDF,       ASDF\n\
+                                    ##VAR_DIM=  3,          4,          4\n\
+                                    ##UNITS=    HZ,         HZ,         ARBITRARY UNITS\n\
+                                    ##FIRST=    30.0,       1.0,\n\
+                                    ##LAST=     10.0,       2.5,\n\
+                                    ##FACTOR=   1.0,        1.0,        2.0\n\
+                                    ##PAGE= F1=30.0\n\
+                                    ##DATA TABLE= (F2++(Y..Y)), PROFILE\n\
+                                    1.0 10 11\n\
+                                    2.0 12 13\n\
+                                    ##PAGE= F1=20.0\n\
+                                    ##FIRST= 20.0, 1.0001, 0\n\
+                                    ##DATA TABLE= (F2++(Y..Y)), PROFILE\n\
+                                    1.0 20 21\n\
+                                    2.0 22 23\n\
+                                    ##PAGE= F1=10.0\n\
+                                    ##DATA TABLE= (F2++(Y..Y)), PROFILE\n\
+                                    1.0 30 31\n\
+                                    2.0 32 33\n\
+                                    ##END NTUPLES= nD NMR SPECTRUM\n\
+                                    ##END=";
+        let path = "resources/nD_NMR.jdx";
+        let cursor = Cursor::new(input);
+        let buf_reader = BufReader::new(cursor);
+        let buf_input: Box<dyn SeekBufRead> = Box::new(buf_reader);
+        let file = JdxParser::parse(path, buf_input).unwrap();
+        let reader = JdxReader::new(path, file);
+
+        let n_tuples_node = reader.read("/0").unwrap();
+        assert_eq!("nD NMR SPECTRUM", n_tuples_node.name);
+        assert_eq!(
+            vec![
+                "F1=30.0 - SPECTRUM",
+                "F1=20.0 - SPECTRUM",
+                "F1=10.0 - SPECTRUM"
+            ],
+            n_tuples_node.child_node_names
+        );
+
+        let matrix = n_tuples_node.nd_array.unwrap();
+        assert_eq!("SPECTRUM", matrix.name);
+        assert_eq!(Some("ARBITRARY UNITS".to_owned()), matrix.unit);
+        assert_eq!(vec![3, 4], matrix.get_shape());
+        assert_eq!(
+            Axis::new("FREQUENCY1", Some("HZ".to_owned()), vec![30.0, 20.0, 10.0]),
+            matrix.axes[0]
+        );
+        assert_eq!(
+            Axis::new(
+                "FREQUENCY2",
+                Some("HZ".to_owned()),
+                vec![1.0, 1.5, 2.0, 2.5]
+            ),
+            matrix.axes[1]
+        );
+        assert_eq!(
+            vec![
+                20.0, 22.0, 24.0, 26.0, 40.0, 42.0, 44.0, 46.0, 60.0, 62.0, 64.0, 66.0
+            ],
+            matrix.values
+        );
+
+        // page level access remains available
+        let page_node = reader.read("/0/1").unwrap();
+        assert_eq!("F1=20.0 - SPECTRUM", page_node.name);
+        assert_eq!(4, page_node.data.len());
+        assert_eq!(40.0, page_node.data[0].y);
+        assert!(page_node.nd_array.is_none());
+    }
+
+    #[test]
+    fn maps_nd_nmr_row_axis_from_attributes_for_non_numeric_pages() {
+        let input = b"##TITLE= 2D NMR Spectrum\n\
+                                    ##JCAMP-DX= 6.00\n\
+                                    ##DATA TYPE= nD NMR SPECTRUM\n\
+                                    ##DATA CLASS= NTUPLES\n\
+                                    ##ORIGIN= Test\n\
+                                    ##OWNER= Test\n\
+                                    ##NTUPLES= nD NMR SPECTRUM\n\
+                                    ##VAR_NAME= FREQUENCY1, FREQUENCY2, SPECTRUM\n\
+                                    ##SYMBOL=   F1,         F2,         Y\n\
+                                    ##VAR_TYPE= INDEPENDENT, INDEPENDENT, DEPENDENT\n\
+                                    ##VAR_FORM= AFFN,       ASDF,       ASDF\n\
+                                    ##VAR_DIM=  2,          2,          2\n\
+                                    ##UNITS=    PPM,        PPM,\n\
+                                    ##FIRST=    8.0,        1.0,\n\
+                                    ##LAST=     4.0,        2.0,\n\
+                                    ##PAGE= F1=first\n\
+                                    ##DATA TABLE= (F2++(Y..Y)), PROFILE\n\
+                                    1.0 10 11\n\
+                                    ##PAGE= F1=second\n\
+                                    ##DATA TABLE= (F2++(Y..Y)), PROFILE\n\
+                                    1.0 20 21\n\
+                                    ##END NTUPLES= nD NMR SPECTRUM\n\
+                                    ##END=";
+        let path = "resources/nD_NMR.jdx";
+        let cursor = Cursor::new(input);
+        let buf_reader = BufReader::new(cursor);
+        let buf_input: Box<dyn SeekBufRead> = Box::new(buf_reader);
+        let file = JdxParser::parse(path, buf_input).unwrap();
+        let reader = JdxReader::new(path, file);
+
+        let matrix = reader.read("/0").unwrap().nd_array.unwrap();
+        assert_eq!(None, matrix.unit);
+        assert_eq!(
+            Axis::new("FREQUENCY1", Some("PPM".to_owned()), vec![8.0, 4.0]),
+            matrix.axes[0]
+        );
+        assert_eq!(vec![10.0, 11.0, 20.0, 21.0], matrix.values);
+    }
+
+    #[test]
+    fn reads_data_range_without_decoding_subsequent_lines() {
+        let input = b"##TITLE= Root LINK BLOCK\n\
+                                    ##JCAMP-DX= 4.24\n\
+                                    ##DATA TYPE= LINK\n\
+                                    ##BLOCKS= 2\n\
+                                    ##TITLE= Data XYDATA Block\n\
+                                    ##JCAMP-DX= 4.24\n\
+                                    ##DATA TYPE= INFRARED SPECTRUM\n\
+                                    ##XUNITS= 1/CM\n\
+                                    ##YUNITS= ABSORBANCE\n\
+                                    ##XFACTOR= 1.0\n\
+                                    ##YFACTOR= 1.0\n\
+                                    ##FIRSTX= 450\n\
+                                    ##LASTX= 453\n\
+                                    ##NPOINTS= 4\n\
+                                    ##XYDATA= (X++(Y..Y))\n\
+                                    +450+10+11+12\n\
+                                    +453 u13\n\
+                                    ##END=\n\
+                                    ##TITLE= Data XYPOINTS Block\n\
+                                    ##JCAMP-DX= 4.24\n\
+                                    ##DATA TYPE= INFRARED SPECTRUM\n\
+                                    ##XUNITS= 1/CM\n\
+                                    ##YUNITS= ABSORBANCE\n\
+                                    ##XFACTOR= 1.0\n\
+                                    ##YFACTOR= 1.0\n\
+                                    ##FIRSTX= 450\n\
+                                    ##LASTX= 453\n\
+                                    ##NPOINTS= 4\n\
+                                    ##XYPOINTS= (XY..XY)\n\
+                                    450, 10; 451, 11\n\
+                                    452, u12; 453, 13\n\
+                                    ##END=\n\
+                                    ##END=";
+        let path = "resources/corrupt_tail.jdx";
+        let cursor = Cursor::new(input);
+        let buf_reader = BufReader::new(cursor);
+        let buf_input: Box<dyn SeekBufRead> = Box::new(buf_reader);
+        let file = JdxParser::parse(path, buf_input).unwrap();
+        let reader = JdxReader::new(path, file);
+
+        for path in ["/0", "/1"] {
+            assert_eq!(
+                vec![PointXy::new(451.0, 11.0)],
+                reader.read_data(path, 1..2).unwrap()
+            );
+            assert_eq!(
+                SfErrorKind::IllegalDataRange,
+                reader.read_data(path, 0..5).unwrap_err().kind()
+            );
+            assert!(reader.read_data(path, 0..4).is_err());
+        }
+    }
 }
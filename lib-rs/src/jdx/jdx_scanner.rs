@@ -17,12 +17,12 @@
 // IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
 // CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
-use std::io::{BufReader, Read, Seek};
+use std::io::{BufReader, Read};
 
 use crate::{
-    api::{Parser, Reader, Scanner, SeekBufRead},
+    api::{Confidence, ParseOptions, Reader, Scanner, SeekBufRead, SeekRead},
     common::SfError,
-    utils::{from_iso_8859_1_cstr, is_recognized_extension},
+    utils::{determine_confidence, from_iso_8859_1_cstr, is_recognized_extension},
 };
 
 use super::{
@@ -32,7 +32,9 @@ use super::{
 };
 
 #[derive(Default)]
-pub struct JdxScanner {}
+pub struct JdxScanner {
+    options: ParseOptions,
+}
 
 impl JdxScanner {
     const ACCEPTED_EXTENSIONS: [&'static str; 3] = ["jdx", "dx", "jcm"];
@@ -44,15 +46,9 @@ impl JdxScanner {
     pub fn new() -> Self {
         Self::default()
     }
-}
 
-impl<T: Seek + Read + 'static> Scanner<T> for JdxScanner {
-    fn is_recognized(&self, path: &str, input: &mut T) -> bool {
-        if !is_recognized_extension(path, &Self::ACCEPTED_EXTENSIONS) {
-            return false;
-        }
-
-        // recognized extension => check start of content
+    /// Checks whether the content starts with a "##TITLE=" LDR.
+    fn is_recognized_content<T: SeekRead>(input: &mut T) -> bool {
         let mut buf = Vec::<u8>::with_capacity(Self::NUM_START_BYTES as usize);
         let mut chunk = input.take(Self::NUM_START_BYTES);
         match chunk.read_to_end(&mut buf) {
@@ -68,11 +64,34 @@ impl<T: Seek + Read + 'static> Scanner<T> for JdxScanner {
             }
         }
     }
+}
+
+impl<T: SeekRead + 'static> Scanner<T> for JdxScanner {
+    fn is_recognized(&self, path: &str, input: &mut T) -> bool {
+        // recognized extension => check start of content
+        is_recognized_extension(path, &Self::ACCEPTED_EXTENSIONS)
+            && Self::is_recognized_content(input)
+    }
+
+    fn get_confidence(&self, path: &str, input: &mut T) -> Option<Confidence> {
+        determine_confidence(
+            is_recognized_extension(path, &Self::ACCEPTED_EXTENSIONS),
+            Self::is_recognized_content(input),
+        )
+    }
+
+    fn get_name(&self) -> &str {
+        "JCAMP-DX"
+    }
+
+    fn set_parse_options(&mut self, options: &ParseOptions) {
+        self.options = options.clone();
+    }
 
     fn get_reader(&self, path: &str, input: T) -> Result<Box<dyn Reader>, SfError> {
         let buf_reader = BufReader::new(input);
         let buf_input: Box<dyn SeekBufRead> = Box::new(buf_reader);
-        let jdx_file = JdxParser::parse(path, buf_input)?;
+        let jdx_file = JdxParser::parse_with_options(path, buf_input, &self.options)?;
         let reader = JdxReader::new(path, jdx_file);
         Ok(Box::new(reader))
     }
@@ -116,4 +135,24 @@ mod tests {
         let scanner = JdxScanner::new();
         assert!(!scanner.is_recognized("name.jdx", &mut cursor));
     }
+
+    #[test]
+    fn scanner_provides_confidence() {
+        let valid = b"##TITLE= Data XYDATA (PAC) Block
+            ##JCAMP-DX= 4.24
+            ##END=";
+        let invalid = b"##NOTITLE= Data XYDATA (PAC) Block
+            ##JCAMP-DX= 4.24
+            ##END=";
+        let scanner = JdxScanner::new();
+
+        let confidence = scanner.get_confidence("name.jdx", &mut Cursor::new(valid));
+        assert_eq!(Some(Confidence::High), confidence);
+        let confidence = scanner.get_confidence("name.jdx", &mut Cursor::new(invalid));
+        assert_eq!(Some(Confidence::Low), confidence);
+        let confidence = scanner.get_confidence("name.txt", &mut Cursor::new(valid));
+        assert_eq!(Some(Confidence::Medium), confidence);
+        let confidence = scanner.get_confidence("name.txt", &mut Cursor::new(invalid));
+        assert_eq!(None, confidence);
+    }
 }
@@ -0,0 +1,277 @@
+// Copyright (c) 2025 Robert Schiwon
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use super::jdx_parser::{BondType, StringLdr, Structure, StructureAtom, StructureBond};
+use super::jdx_utils::{find_ldr, parse_str, strip_line_comment};
+use crate::common::SfError;
+use std::collections::HashMap;
+
+/// Parses the JCAMP-CS structure LDRs of a block.
+///
+/// Returns None if the block does not hold an ATOMLIST.
+pub fn parse_structure(ldrs: &[StringLdr]) -> Result<Option<Structure>, SfError> {
+    let atom_list = match find_ldr("ATOMLIST", ldrs) {
+        None => return Ok(None),
+        Some(ldr) => &ldr.value,
+    };
+
+    let mut atoms = vec![];
+    for tuple in split_tuples(atom_list, 3, "ATOMLIST")? {
+        atoms.push(StructureAtom {
+            number: parse_str(tuple[0], "AN in ATOMLIST")?,
+            symbol: tuple[1].to_owned(),
+            implicit_hydrogens: parse_str(tuple[2], "NH in ATOMLIST")?,
+            charge: 0,
+            coordinates: None,
+        });
+    }
+    let mut atom_indices = HashMap::<u64, usize>::new();
+    for (index, atom) in atoms.iter().enumerate() {
+        if atom_indices.insert(atom.number, index).is_some() {
+            return Err(SfError::new(&format!(
+                "Duplicate atom number in ATOMLIST: {}",
+                atom.number
+            )));
+        }
+    }
+    let find_atom_index = |number: u64, label: &str| -> Result<usize, SfError> {
+        atom_indices
+            .get(&number)
+            .copied()
+            .ok_or(SfError::new(&format!(
+                "Atom number not found in ATOMLIST for {}: {}",
+                label, number
+            )))
+    };
+
+    let mut bonds = vec![];
+    if let Some(ldr) = find_ldr("BONDLIST", ldrs) {
+        for tuple in split_tuples(&ldr.value, 3, "BONDLIST")? {
+            let bond = StructureBond {
+                atom_1: parse_str(tuple[0], "AN1 in BONDLIST")?,
+                atom_2: parse_str(tuple[1], "AN2 in BONDLIST")?,
+                bond_type: parse_bond_type(tuple[2])?,
+            };
+            find_atom_index(bond.atom_1, "BONDLIST")?;
+            find_atom_index(bond.atom_2, "BONDLIST")?;
+            bonds.push(bond);
+        }
+    }
+
+    if let Some(ldr) = find_ldr("CHARGE", ldrs) {
+        for tuple in split_tuples(&ldr.value, 2, "CHARGE")? {
+            let index = find_atom_index(parse_str(tuple[0], "AN in CHARGE")?, "CHARGE")?;
+            atoms[index].charge = parse_str(tuple[1], "CHARGE")?;
+        }
+    }
+
+    // prefer three dimensional coordinates over two dimensional raster coordinates
+    let mut is_3d = false;
+    if let Some(ldr) = find_ldr("XYZ", ldrs) {
+        let factors = parse_xyz_factors(find_ldr("XYZFACTOR", ldrs).map(|ldr| ldr.value.as_str()))?;
+        for tuple in split_tuples(&ldr.value, 4, "XYZ")? {
+            let index = find_atom_index(parse_str(tuple[0], "AN in XYZ")?, "XYZ")?;
+            let x: f64 = parse_str(tuple[1], "X in XYZ")?;
+            let y: f64 = parse_str(tuple[2], "Y in XYZ")?;
+            let z: f64 = parse_str(tuple[3], "Z in XYZ")?;
+            atoms[index].coordinates = Some((x * factors.0, y * factors.1, z * factors.2));
+        }
+        is_3d = true;
+    } else if let Some(ldr) = find_ldr("XYRASTER", ldrs) {
+        for tuple in split_tuples(&ldr.value, 3, "XY_RASTER")? {
+            let index = find_atom_index(parse_str(tuple[0], "AN in XY_RASTER")?, "XY_RASTER")?;
+            let x: f64 = parse_str(tuple[1], "X in XY_RASTER")?;
+            let y: f64 = parse_str(tuple[2], "Y in XY_RASTER")?;
+            atoms[index].coordinates = Some((x, y, 0.0));
+        }
+    }
+
+    Ok(Some(Structure {
+        molecular_formula: find_ldr("MOLFORM", ldrs).map(|ldr| ldr.value.trim().to_owned()),
+        atoms,
+        bonds,
+        is_3d,
+    }))
+}
+
+/// Splits the lines of an LDR value into tuples of a fixed number of values, ignoring
+/// "$$" comments such as the "$$ AN AS NH" variable list following the label.
+fn split_tuples<'v>(
+    value: &'v str,
+    tuple_size: usize,
+    label: &str,
+) -> Result<Vec<Vec<&'v str>>, SfError> {
+    let mut tuples = vec![];
+    for line in value.lines() {
+        let (content, _comment) = strip_line_comment(line, true, false);
+        if content.is_empty() {
+            continue;
+        }
+        let tokens: Vec<&str> = content
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|token| !token.is_empty())
+            .collect();
+        if !tokens.len().is_multiple_of(tuple_size) {
+            return Err(SfError::new(&format!(
+                "Illegal number of values in {}: {}",
+                label, content
+            )));
+        }
+        tuples.extend(tokens.chunks(tuple_size).map(|chunk| chunk.to_vec()));
+    }
+    Ok(tuples)
+}
+
+fn parse_bond_type(value: &str) -> Result<BondType, SfError> {
+    match value.to_ascii_uppercase().as_str() {
+        "S" => Ok(BondType::Single),
+        "D" => Ok(BondType::Double),
+        "T" => Ok(BondType::Triple),
+        "Q" => Ok(BondType::Quadruple),
+        "A" => Ok(BondType::Aromatic),
+        _ => Err(SfError::new(&format!(
+            "Illegal bond type in BONDLIST: {}",
+            value
+        ))),
+    }
+}
+
+/// Parses the XYZ_FACTOR as a single factor for all or one factor per coordinate.
+fn parse_xyz_factors(value: Option<&str>) -> Result<(f64, f64, f64), SfError> {
+    let value = match value {
+        None => return Ok((1.0, 1.0, 1.0)),
+        Some(v) => strip_line_comment(v, true, false).0,
+    };
+    let factors = value
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|token| !token.is_empty())
+        .map(|token| parse_str::<f64>(token, "XYZ_FACTOR"))
+        .collect::<Result<Vec<f64>, SfError>>()?;
+    match factors[..] {
+        [factor] => Ok((factor, factor, factor)),
+        [x, y, z] => Ok((x, y, z)),
+        _ => Err(SfError::new(&format!("Illegal XYZ_FACTOR: {}", value))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_ldrs(ldrs: &[(&str, &str)]) -> Vec<StringLdr> {
+        ldrs.iter()
+            .map(|(label, value)| StringLdr::new(*label, *value))
+            .collect()
+    }
+
+    #[test]
+    fn parses_atoms_bonds_charges_and_coordinates() {
+        let ldrs = create_ldrs(&[
+            ("TITLE", "Acetate"),
+            ("MOLFORM", "C 2 H 3 O 2"),
+            ("ATOMLIST", "$$ AN AS NH\n1 C 3\n2 C 0\n3 O 0\n4 O 0"),
+            ("BONDLIST", "$$ AN1 AN2 BT\n1 2 S\n2 3 D\n2 4 S"),
+            ("CHARGE", "$$ AN CHARGE\n4 -1"),
+            (
+                "XYRASTER",
+                "$$ AN X Y\n1 0 0 2 1000 0\n3 1500 866 4 1500 -866",
+            ),
+        ]);
+
+        let structure = parse_structure(&ldrs).unwrap().unwrap();
+
+        assert_eq!(Some("C 2 H 3 O 2"), structure.molecular_formula.as_deref());
+        assert!(!structure.is_3d);
+        assert_eq!(4, structure.atoms.len());
+        assert_eq!(
+            StructureAtom {
+                number: 1,
+                symbol: "C".to_owned(),
+                implicit_hydrogens: 3,
+                charge: 0,
+                coordinates: Some((0.0, 0.0, 0.0)),
+            },
+            structure.atoms[0]
+        );
+        assert_eq!(-1, structure.atoms[3].charge);
+        assert_eq!(Some((1500.0, -866.0, 0.0)), structure.atoms[3].coordinates);
+        assert_eq!(
+            vec![
+                StructureBond {
+                    atom_1: 1,
+                    atom_2: 2,
+                    bond_type: BondType::Single
+                },
+                StructureBond {
+                    atom_1: 2,
+                    atom_2: 3,
+                    bond_type: BondType::Double
+                },
+                StructureBond {
+                    atom_1: 2,
+                    atom_2: 4,
+                    bond_type: BondType::Single
+                },
+            ],
+            structure.bonds
+        );
+    }
+
+    #[test]
+    fn prefers_scaled_xyz_coordinates_over_raster() {
+        let ldrs = create_ldrs(&[
+            ("ATOMLIST", "1 C 4"),
+            ("XYRASTER", "1 10 20"),
+            ("XYZFACTOR", "0.001"),
+            ("XYZ", "$$ AN X Y Z\n1 1000 -2000 500"),
+        ]);
+
+        let structure = parse_structure(&ldrs).unwrap().unwrap();
+
+        assert!(structure.is_3d);
+        assert_eq!(None, structure.molecular_formula);
+        assert!(structure.bonds.is_empty());
+        assert_eq!(Some((1.0, -2.0, 0.5)), structure.atoms[0].coordinates);
+    }
+
+    #[test]
+    fn returns_none_without_atom_list() {
+        let ldrs = create_ldrs(&[("TITLE", "Spectrum"), ("MOLFORM", "C 6 H 6")]);
+        assert_eq!(None, parse_structure(&ldrs).unwrap());
+    }
+
+    #[test]
+    fn fails_for_illegal_structure() {
+        let illegal_inputs = [
+            vec![("ATOMLIST", "1 C 3\n2 C")],
+            vec![("ATOMLIST", "1 C 3\n1 C 3")],
+            vec![("ATOMLIST", "1 C 3\n2 C 3"), ("BONDLIST", "1 3 S")],
+            vec![("ATOMLIST", "1 C 3\n2 C 3"), ("BONDLIST", "1 2 X")],
+            vec![("ATOMLIST", "1 C 3"), ("CHARGE", "1 +a")],
+            vec![
+                ("ATOMLIST", "1 C 3"),
+                ("XYZFACTOR", "1 2"),
+                ("XYZ", "1 0 0 0"),
+            ],
+        ];
+        for ldrs in illegal_inputs {
+            assert!(parse_structure(&create_ldrs(&ldrs)).is_err());
+        }
+    }
+}
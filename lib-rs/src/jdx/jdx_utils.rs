@@ -18,15 +18,17 @@
 // CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
 use super::{JdxSequenceParser, jdx_parser::StringLdr};
-use crate::common::SfError;
-use crate::{api::SeekBufRead, utils::from_iso_8859_1_cstr};
+use crate::common::{SfError, SfErrorLocation};
+use crate::{
+    api::SeekBufRead,
+    utils::{from_iso_8859_1_cstr, lock_input},
+};
 use regex::Regex;
 use std::{
-    cell::{RefCell, RefMut},
-    io::{BufRead, SeekFrom},
-    rc::Rc,
+    io::{BufRead, Read, SeekFrom},
     str::FromStr,
     sync::LazyLock,
+    sync::{Arc, Mutex, MutexGuard},
 };
 
 pub trait BinBufRead: BufRead {
@@ -85,7 +87,7 @@ pub fn is_ldr_start(line: &str) -> bool {
     LDR_START_REGEX.is_match(line)
 }
 
-fn normalize_label(raw_label: &str) -> String {
+pub fn normalize_label(raw_label: &str) -> String {
     let mut label = String::with_capacity(raw_label.len());
     // normalize label
     for c in raw_label.chars() {
@@ -315,9 +317,9 @@ pub(crate) fn parse_element<'t, T: 't, E>(
     title: &str,
     o: &Option<E>,
     builder: impl FnOnce() -> Result<(E, Option<String>), SfError>,
-    reader: RefMut<'t, T>,
-    reader_ref: &'t Rc<RefCell<T>>,
-) -> Result<(Option<E>, RefMut<'t, T>, Option<String>), SfError> {
+    reader: MutexGuard<'t, T>,
+    reader_ref: &'t Arc<Mutex<T>>,
+) -> Result<(Option<E>, MutexGuard<'t, T>, Option<String>), SfError> {
     if o.is_some() {
         return Err(SfError::new(&format!(
             "Multiple \"{}\" LDRs found in block: {}",
@@ -326,10 +328,34 @@ pub(crate) fn parse_element<'t, T: 't, E>(
     }
     drop(reader);
     let (element, next_line) = builder()?;
-    let reader = reader_ref.borrow_mut();
+    let reader = lock_input(reader_ref);
     Ok((Some(element), reader, next_line))
 }
 
+/// Adds the line last read and the current position of the input to an error.
+///
+/// Lines are only counted once an error has occurred, so parsing is not slowed down.
+pub(crate) fn locate_error<T: SeekBufRead>(error: SfError, reader: &mut T) -> SfError {
+    error.or_location(|| {
+        let byte_offset = reader.stream_position().ok()?;
+        reader.seek(SeekFrom::Start(0)).ok()?;
+        // the line last read ends with the line break preceding the current position
+        let mut input = reader.by_ref().take(byte_offset.saturating_sub(1));
+        let mut line = 1u64;
+        loop {
+            let buf = input.fill_buf().ok()?;
+            if buf.is_empty() {
+                break;
+            }
+            line += buf.iter().filter(|&&b| b == b'\n').count() as u64;
+            let len = buf.len();
+            input.consume(len);
+        }
+        reader.seek(SeekFrom::Start(byte_offset)).ok()?;
+        Some(SfErrorLocation::Line { line, byte_offset })
+    })
+}
+
 pub(crate) fn read_width_function<T: SeekBufRead>(
     reader: &mut T,
     address: u64,
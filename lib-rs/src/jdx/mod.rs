@@ -19,16 +19,21 @@
 
 mod jdx_audit_trail_parser;
 mod jdx_data_parser;
+pub mod jdx_exporter;
+pub mod jdx_molfile_exporter;
 pub mod jdx_parser;
 mod jdx_peak_assignments_parser;
 mod jdx_peak_table_parser;
 pub mod jdx_reader;
 pub mod jdx_scanner;
+mod jdx_structure_parser;
 mod jdx_utils;
 
 use crate::api::SeekBufRead;
 use crate::common::SfError;
 
+pub(crate) use jdx_utils::normalize_label;
+
 trait JdxSequenceParser<'r, T: SeekBufRead>: Sized {
     type Item;
 
@@ -0,0 +1,1032 @@
+// Copyright (c) 2025 Robert Schiwon
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use crate::{
+    api::{Parser, SeekRead, Value},
+    common::{SfError, SfErrorKind},
+    utils::lock_input,
+};
+use std::{io::SeekFrom, path::Path, sync::Mutex};
+use strum::Display;
+
+pub struct JeolParser {}
+
+impl<T: SeekRead + 'static> Parser<T> for JeolParser {
+    type R = JdfFile;
+    type E = SfError;
+
+    fn parse(name: &str, input: T) -> Result<Self::R, Self::E> {
+        let input: Box<dyn SeekRead> = Box::new(input);
+        let file_name = Path::new(name)
+            .file_name()
+            .and_then(|file_name| file_name.to_str())
+            .unwrap_or(name);
+        JdfFile::new(file_name, input).map_err(|e| e.or_kind(SfErrorKind::CorruptData))
+    }
+}
+
+/// A JEOL Delta NMR file (JDF).
+pub struct JdfFile {
+    /// The file name.
+    pub name: String,
+    /// The file header.
+    pub header: JdfHeader,
+    /// The parameters from the parameter section.
+    pub parameters: Vec<JdfParameter>,
+
+    input: Mutex<Box<dyn SeekRead>>,
+    input_len: u64,
+}
+
+impl JdfFile {
+    pub(crate) const FILE_IDENTIFIER: &'static [u8; 8] = b"JEOL.NMR";
+    const SUBMATRIX_EDGE_2D: usize = 32;
+
+    fn new(name: &str, mut input: Box<dyn SeekRead>) -> Result<Self, SfError> {
+        let mut header_bytes = vec![0u8; JdfHeader::SIZE];
+        input.seek(SeekFrom::Start(0))?;
+        input.read_exact(&mut header_bytes).map_err(|e| {
+            SfError::from_source(e, "JDF file too short for file header.")
+                .with_kind(SfErrorKind::UnsupportedFormat)
+        })?;
+        let header = JdfHeader::parse(&header_bytes)?;
+
+        // check sizes from the header before allocating
+        let input_len = input.seek(SeekFrom::End(0))?;
+        Self::check_section(
+            "parameter",
+            header.param_start as u64,
+            header.param_length as u64,
+            input_len,
+        )?;
+        let mut parameter_bytes = vec![0u8; header.param_length as usize];
+        input.seek(SeekFrom::Start(header.param_start as u64))?;
+        input
+            .read_exact(&mut parameter_bytes)
+            .map_err(|e| SfError::from_source(e, "Error reading JDF parameter section."))?;
+        let parameters = JdfParameter::parse_section(&parameter_bytes, header.big_endian)?;
+
+        Ok(Self {
+            name: name.to_owned(),
+            header,
+            parameters,
+            input: Mutex::new(input),
+            input_len,
+        })
+    }
+
+    /// Checks that a section lies within the input.
+    fn check_section(name: &str, start: u64, len: u64, input_len: u64) -> Result<(), SfError> {
+        match start.checked_add(len) {
+            Some(end) if end <= input_len => Ok(()),
+            _ => Err(SfError::new(&format!(
+                "JDF {} section exceeds file length {}: {} bytes at {}",
+                name, input_len, len, start
+            ))
+            .with_kind(SfErrorKind::CorruptData)),
+        }
+    }
+
+    /// Finds a parameter by its name, ignoring case.
+    pub fn find_parameter(&self, name: &str) -> Option<&JdfParameter> {
+        self.parameters
+            .iter()
+            .find(|parameter| parameter.name.eq_ignore_ascii_case(name))
+    }
+
+    /// The number of data components, e.g., 2 for complex 1D data or 4 for 2D data that is
+    /// complex in both dimensions.
+    ///
+    /// The components are stored one after the other with the component index of the first
+    /// dimension changing fastest.
+    pub fn get_num_components(&self) -> usize {
+        self.header.data_axis_type[..self.header.get_num_dimensions()]
+            .iter()
+            .map(JdfAxisType::get_num_components)
+            .product()
+    }
+
+    /// Reads a data component with only the points between the data offset start and stop
+    /// of each dimension retained.
+    ///
+    /// 2D values are returned in row-major order with rows along the second and columns
+    /// along the first dimension.
+    pub fn read_component(&self, component: usize) -> Result<Vec<f64>, SfError> {
+        let header = &self.header;
+        if component >= self.get_num_components() {
+            return Err(SfError::new(&format!(
+                "Illegal JDF data component: {}",
+                component
+            )));
+        }
+        if !matches!(
+            header.data_format,
+            JdfDataFormat::OneD | JdfDataFormat::TwoD
+        ) {
+            return Err(SfError::new(&format!(
+                "Unsupported JDF data format: {}",
+                header.data_format
+            ))
+            .with_kind(SfErrorKind::UnsupportedFormat));
+        }
+        let ranges = (0..header.get_num_dimensions())
+            .map(|dim| header.get_valid_range(dim))
+            .collect::<Result<Vec<_>, _>>()?;
+        let points: Vec<usize> = header.data_points[..ranges.len()]
+            .iter()
+            .map(|&points| points as usize)
+            .collect();
+        let illegal_points = || {
+            SfError::new(&format!("Illegal JDF data points: {:?}", points))
+                .with_kind(SfErrorKind::CorruptData)
+        };
+        let component_len = points
+            .iter()
+            .try_fold(1usize, |len, &points| len.checked_mul(points))
+            .ok_or_else(illegal_points)?;
+        let offset = component
+            .checked_mul(component_len)
+            .ok_or_else(illegal_points)?;
+        let values = self.read_values(offset, component_len)?;
+
+        match (header.data_format, &ranges[..]) {
+            (JdfDataFormat::OneD, [(start, stop)]) => Ok(values[*start..=*stop].to_vec()),
+            (JdfDataFormat::TwoD, [(column_start, column_stop), (row_start, row_stop)]) => {
+                let edge = Self::SUBMATRIX_EDGE_2D;
+                let columns = points[0];
+                if !points.iter().all(|points| points.is_multiple_of(edge)) {
+                    return Err(SfError::new(&format!(
+                        "JDF data points not a multiple of the submatrix edge {}: {:?}",
+                        edge, points
+                    )));
+                }
+                // the submatrices and the values inside each submatrix are in row-major order
+                let submatrix_columns = columns / edge;
+                let mut matrix = vec![0.0; component_len];
+                for (i, value) in values.into_iter().enumerate() {
+                    let (submatrix, position) = (i / (edge * edge), i % (edge * edge));
+                    let row = submatrix / submatrix_columns * edge + position / edge;
+                    let column = submatrix % submatrix_columns * edge + position % edge;
+                    matrix[row * columns + column] = value;
+                }
+                Ok((*row_start..=*row_stop)
+                    .flat_map(|row| {
+                        &matrix[row * columns + column_start..=row * columns + column_stop]
+                    })
+                    .copied()
+                    .collect())
+            }
+            _ => Err(SfError::new(&format!(
+                "Inconsistent JDF data format {} and dimension number: {}",
+                header.data_format,
+                ranges.len()
+            ))),
+        }
+    }
+
+    fn read_values(&self, offset: usize, len: usize) -> Result<Vec<f64>, SfError> {
+        let header = &self.header;
+        let size = header.data_type.get_size() as u64;
+        let start = (offset as u64)
+            .checked_mul(size)
+            .and_then(|byte_offset| byte_offset.checked_add(header.data_start as u64));
+        let (Some(start), Some(byte_len)) = (start, (len as u64).checked_mul(size)) else {
+            return Err(SfError::new(&format!(
+                "Illegal JDF data range: {} values at {}",
+                len, offset
+            ))
+            .with_kind(SfErrorKind::CorruptData));
+        };
+        Self::check_section("data", start, byte_len, self.input_len)?;
+        let mut bytes = vec![0u8; byte_len as usize];
+        {
+            let mut input = lock_input(&self.input);
+            input.seek(SeekFrom::Start(start))?;
+            input
+                .read_exact(&mut bytes)
+                .map_err(|e| SfError::from_source(e, "Error reading JDF data section."))?;
+        }
+        let values = bytes
+            .chunks_exact(size as usize)
+            .map(|chunk| match (header.data_type, header.big_endian) {
+                (JdfDataType::F64, true) => f64::from_be_bytes(chunk.try_into().unwrap()),
+                (JdfDataType::F64, false) => f64::from_le_bytes(chunk.try_into().unwrap()),
+                (JdfDataType::F32, true) => f32::from_be_bytes(chunk.try_into().unwrap()) as f64,
+                (JdfDataType::F32, false) => f32::from_le_bytes(chunk.try_into().unwrap()) as f64,
+            })
+            .collect();
+        Ok(values)
+    }
+}
+
+/// The type of the data values.
+#[derive(Debug, Clone, Copy, PartialEq, Display)]
+pub enum JdfDataType {
+    /// 64 bit floating point numbers.
+    F64,
+    /// 32 bit floating point numbers.
+    F32,
+}
+
+impl JdfDataType {
+    fn get_size(&self) -> usize {
+        match self {
+            JdfDataType::F64 => 8,
+            JdfDataType::F32 => 4,
+        }
+    }
+}
+
+/// The layout of the data section.
+#[derive(Debug, Clone, Copy, PartialEq, Display)]
+pub enum JdfDataFormat {
+    #[strum(serialize = "One_D")]
+    OneD,
+    #[strum(serialize = "Two_D")]
+    TwoD,
+    #[strum(serialize = "Three_D")]
+    ThreeD,
+    #[strum(serialize = "Four_D")]
+    FourD,
+    #[strum(serialize = "Five_D")]
+    FiveD,
+    #[strum(serialize = "Six_D")]
+    SixD,
+    #[strum(serialize = "Seven_D")]
+    SevenD,
+    #[strum(serialize = "Eight_D")]
+    EightD,
+    #[strum(serialize = "Small_Two_D")]
+    SmallTwoD,
+    #[strum(serialize = "Small_Three_D")]
+    SmallThreeD,
+    #[strum(serialize = "Small_Four_D")]
+    SmallFourD,
+}
+
+impl JdfDataFormat {
+    fn from_code(code: u8) -> Option<Self> {
+        match code {
+            1 => Some(Self::OneD),
+            2 => Some(Self::TwoD),
+            3 => Some(Self::ThreeD),
+            4 => Some(Self::FourD),
+            5 => Some(Self::FiveD),
+            6 => Some(Self::SixD),
+            7 => Some(Self::SevenD),
+            8 => Some(Self::EightD),
+            12 => Some(Self::SmallTwoD),
+            13 => Some(Self::SmallThreeD),
+            14 => Some(Self::SmallFourD),
+            _ => None,
+        }
+    }
+}
+
+/// The type of a data axis.
+#[derive(Debug, Clone, Copy, PartialEq, Display)]
+pub enum JdfAxisType {
+    None,
+    Real,
+    #[strum(serialize = "TPPI")]
+    Tppi,
+    Complex,
+    #[strum(serialize = "Real_Complex")]
+    RealComplex,
+    Envelope,
+}
+
+impl JdfAxisType {
+    fn from_code(code: u8) -> Option<Self> {
+        match code {
+            0 => Some(Self::None),
+            1 => Some(Self::Real),
+            2 => Some(Self::Tppi),
+            3 => Some(Self::Complex),
+            4 => Some(Self::RealComplex),
+            5 => Some(Self::Envelope),
+            _ => None,
+        }
+    }
+
+    /// The number of data components along the axis, i.e., 2 for real and imaginary
+    /// components, 1 otherwise.
+    pub fn get_num_components(&self) -> usize {
+        match self {
+            JdfAxisType::Complex | JdfAxisType::RealComplex => 2,
+            _ => 1,
+        }
+    }
+}
+
+/// A unit made up of an SI prefix, a power, and a base unit.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct JdfUnit {
+    /// The SI prefix as power of 1/1000, e.g., -1 for kilo and 1 for milli.
+    pub prefix: i8,
+    /// The power of the unit, e.g., 1 for Hz.
+    pub power: u8,
+    /// The base unit code.
+    pub base: u8,
+}
+
+impl JdfUnit {
+    fn parse(bytes: &[u8]) -> Self {
+        // the prefix is a signed 4 bit number in the upper nibble
+        Self {
+            prefix: (bytes[0] as i8) >> 4,
+            power: bytes[0] & 0x0F,
+            base: bytes[1],
+        }
+    }
+
+    /// The name of the unit including the prefix, e.g., "HZ", "KHZ", "PPM", or "SECONDS".
+    pub fn get_name(&self) -> Option<String> {
+        let base = match self.base {
+            1 => "ABUNDANCE",
+            2 => "AMPERE",
+            4 => "CELSIUS",
+            6 => "DEGREES",
+            10 => "GRAMS",
+            13 => "HZ",
+            14 => "KELVIN",
+            16 => "LITERS",
+            19 => "METERS",
+            24 => "PERCENT",
+            25 => "POINTS",
+            26 => "PPM",
+            27 => "RADIANS",
+            28 => "SECONDS",
+            31 => "TESLA",
+            32 => "VOLTS",
+            35 => "DECIBELS",
+            _ => return None,
+        };
+        let prefix = match self.prefix {
+            0 => "",
+            -3 => "GIGA",
+            -2 => "MEGA",
+            -1 => "KILO",
+            1 => "MILLI",
+            2 => "MICRO",
+            3 => "NANO",
+            _ => return None,
+        };
+        let prefix = match (prefix, base) {
+            ("KILO", "HZ") => "K",
+            ("MEGA", "HZ") => "M",
+            ("GIGA", "HZ") => "G",
+            _ => prefix,
+        };
+        Some(format!("{}{}", prefix, base))
+    }
+}
+
+/// The JDF file header.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JdfHeader {
+    /// Whether the parameters and data are stored in big endian byte order.
+    pub big_endian: bool,
+    pub major_version: u8,
+    pub minor_version: u16,
+    /// The number of dimensions.
+    pub data_dimension_number: u8,
+    pub data_type: JdfDataType,
+    pub data_format: JdfDataFormat,
+    pub instrument: u8,
+    pub data_axis_type: [JdfAxisType; 8],
+    pub data_units: [JdfUnit; 8],
+    pub title: String,
+    /// The number of points per dimension, including padding.
+    pub data_points: [u32; 8],
+    /// The first valid point per dimension.
+    pub data_offset_start: [u32; 8],
+    /// The last valid point per dimension.
+    pub data_offset_stop: [u32; 8],
+    /// The axis value of the first valid point per dimension.
+    pub data_axis_start: [f64; 8],
+    /// The axis value of the last valid point per dimension.
+    pub data_axis_stop: [f64; 8],
+    /// The creation date as ISO 8601 date.
+    pub creation_time: String,
+    /// The revision date as ISO 8601 date.
+    pub revision_time: String,
+    pub node_name: String,
+    pub site: String,
+    pub author: String,
+    pub comment: String,
+    pub data_axis_titles: [String; 8],
+    /// The base frequency per dimension in MHz.
+    pub base_freq: [f64; 8],
+    pub zero_point: [f64; 8],
+    /// Whether the axis is displayed in reverse per dimension.
+    pub reversed: [bool; 8],
+    pub param_start: u32,
+    pub param_length: u32,
+    pub data_start: u32,
+    pub data_length: u64,
+}
+
+impl JdfHeader {
+    const SIZE: usize = 1296;
+
+    fn parse(bytes: &[u8]) -> Result<Self, SfError> {
+        if &bytes[0..8] != JdfFile::FILE_IDENTIFIER {
+            return Err(SfError::new("Missing JDF file identifier \"JEOL.NMR\".")
+                .with_kind(SfErrorKind::UnsupportedFormat));
+        }
+        let big_endian = match bytes[8] {
+            0 => true,
+            1 => false,
+            endian => {
+                return Err(SfError::new(&format!("Illegal JDF endianness: {}", endian)));
+            }
+        };
+        let data_type = match bytes[14] >> 6 {
+            0 => JdfDataType::F64,
+            1 => JdfDataType::F32,
+            data_type => {
+                return Err(
+                    SfError::new(&format!("Unsupported JDF data type: {}", data_type))
+                        .with_kind(SfErrorKind::UnsupportedFormat),
+                );
+            }
+        };
+        let data_format = JdfDataFormat::from_code(bytes[14] & 0x3F).ok_or(SfError::new(
+            &format!("Illegal JDF data format: {}", bytes[14] & 0x3F),
+        ))?;
+        let data_dimension_number = bytes[12];
+        if !(1..=8).contains(&data_dimension_number) {
+            return Err(SfError::new(&format!(
+                "Illegal JDF data dimension number: {}",
+                data_dimension_number
+            )));
+        }
+        let mut data_axis_type = [JdfAxisType::None; 8];
+        for (dim, axis_type) in data_axis_type.iter_mut().enumerate() {
+            *axis_type = JdfAxisType::from_code(bytes[24 + dim]).ok_or(SfError::new(&format!(
+                "Illegal JDF data axis type: {}",
+                bytes[24 + dim]
+            )))?;
+        }
+
+        Ok(Self {
+            big_endian,
+            major_version: bytes[9],
+            minor_version: read_u16(bytes, 10),
+            data_dimension_number,
+            data_type,
+            data_format,
+            instrument: bytes[15],
+            data_axis_type,
+            data_units: std::array::from_fn(|dim| JdfUnit::parse(&bytes[32 + dim * 2..])),
+            title: read_string(&bytes[48..172]),
+            data_points: std::array::from_fn(|dim| read_u32(bytes, 176 + dim * 4)),
+            data_offset_start: std::array::from_fn(|dim| read_u32(bytes, 208 + dim * 4)),
+            data_offset_stop: std::array::from_fn(|dim| read_u32(bytes, 240 + dim * 4)),
+            data_axis_start: std::array::from_fn(|dim| read_f64(bytes, 272 + dim * 8)),
+            data_axis_stop: std::array::from_fn(|dim| read_f64(bytes, 336 + dim * 8)),
+            creation_time: read_date(bytes, 400),
+            revision_time: read_date(bytes, 404),
+            node_name: read_string(&bytes[408..424]),
+            site: read_string(&bytes[424..552]),
+            author: read_string(&bytes[552..680]),
+            comment: read_string(&bytes[680..808]),
+            data_axis_titles: std::array::from_fn(|dim| {
+                read_string(&bytes[808 + dim * 32..840 + dim * 32])
+            }),
+            base_freq: std::array::from_fn(|dim| read_f64(bytes, 1064 + dim * 8)),
+            zero_point: std::array::from_fn(|dim| read_f64(bytes, 1128 + dim * 8)),
+            reversed: std::array::from_fn(|dim| bytes[1192 + dim] != 0),
+            param_start: read_u32(bytes, 1212),
+            param_length: read_u32(bytes, 1216),
+            data_start: read_u32(bytes, 1284),
+            data_length: u64::from_be_bytes(bytes[1288..1296].try_into().unwrap()),
+        })
+    }
+
+    /// The number of dimensions.
+    pub fn get_num_dimensions(&self) -> usize {
+        self.data_dimension_number as usize
+    }
+
+    /// The range of valid points of a dimension.
+    fn get_valid_range(&self, dim: usize) -> Result<(usize, usize), SfError> {
+        let (start, stop, points) = (
+            self.data_offset_start[dim] as usize,
+            self.data_offset_stop[dim] as usize,
+            self.data_points[dim] as usize,
+        );
+        match start <= stop && stop < points {
+            true => Ok((start, stop)),
+            false => Err(SfError::new(&format!(
+                "Illegal JDF data offsets for dimension {}: {}..{} for {} points",
+                dim + 1,
+                start,
+                stop,
+                points
+            ))),
+        }
+    }
+
+    /// The axis values of the valid points of a dimension, evenly spaced between axis start
+    /// and stop.
+    pub fn get_axis_values(&self, dim: usize) -> Result<Vec<f64>, SfError> {
+        let (start, stop) = self.get_valid_range(dim)?;
+        let (first, last) = (self.data_axis_start[dim], self.data_axis_stop[dim]);
+        let len = stop - start + 1;
+        let step = match len {
+            1 => 0.0,
+            _ => (last - first) / (len - 1) as f64,
+        };
+        Ok((0..len).map(|i| first + i as f64 * step).collect())
+    }
+}
+
+/// The type of a parameter value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JdfValue {
+    String(String),
+    Integer(i32),
+    Float(f64),
+    Complex(f64, f64),
+    Infinity(i32),
+}
+
+impl JdfValue {
+    /// Converts the value to a generic value. Complex values become strings of the form
+    /// "re, im", infinities strings of the form "+INF" or "-INF".
+    pub fn to_value(&self) -> Value {
+        match self {
+            JdfValue::String(v) => Value::String(v.clone()),
+            JdfValue::Integer(v) => Value::I64(*v as i64),
+            JdfValue::Float(v) => Value::F64(*v),
+            JdfValue::Complex(re, im) => Value::String(format!("{}, {}", re, im)),
+            JdfValue::Infinity(sign) => Value::String(
+                match sign {
+                    s if *s < 0 => "-INF",
+                    _ => "+INF",
+                }
+                .to_owned(),
+            ),
+        }
+    }
+}
+
+/// A parameter from the JDF parameter section.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JdfParameter {
+    pub name: String,
+    pub value: JdfValue,
+    /// The unit scaler.
+    pub unit_scaler: i16,
+    /// The first of the unit's components.
+    pub unit: JdfUnit,
+}
+
+impl JdfParameter {
+    const SECTION_HEADER_SIZE: usize = 16;
+    const SIZE: usize = 64;
+
+    fn parse_section(bytes: &[u8], big_endian: bool) -> Result<Vec<Self>, SfError> {
+        if bytes.is_empty() {
+            return Ok(vec![]);
+        }
+        if bytes.len() < Self::SECTION_HEADER_SIZE {
+            return Err(SfError::new(&format!(
+                "JDF parameter section too short: {} bytes",
+                bytes.len()
+            )));
+        }
+        let read_u32 = |offset: usize| {
+            let chunk: [u8; 4] = bytes[offset..offset + 4].try_into().unwrap();
+            match big_endian {
+                true => u32::from_be_bytes(chunk),
+                false => u32::from_le_bytes(chunk),
+            }
+        };
+        let (parameter_size, high_index) = (read_u32(0) as usize, read_u32(8) as usize);
+        if parameter_size != Self::SIZE {
+            return Err(SfError::new(&format!(
+                "Illegal JDF parameter size: {}",
+                parameter_size
+            )));
+        }
+        let num_parameters = high_index + 1;
+        let required_len = Self::SECTION_HEADER_SIZE + num_parameters * Self::SIZE;
+        if bytes.len() < required_len {
+            return Err(SfError::new(&format!(
+                "JDF parameter section too short for {} parameters: {} bytes, expected {}",
+                num_parameters,
+                bytes.len(),
+                required_len
+            )));
+        }
+        bytes[Self::SECTION_HEADER_SIZE..required_len]
+            .chunks_exact(Self::SIZE)
+            .map(|record| Self::parse(record, big_endian))
+            .collect()
+    }
+
+    fn parse(bytes: &[u8], big_endian: bool) -> Result<Self, SfError> {
+        macro_rules! read {
+            ($type:ty, $offset:expr) => {{
+                let chunk = bytes[$offset..$offset + size_of::<$type>()]
+                    .try_into()
+                    .unwrap();
+                match big_endian {
+                    true => <$type>::from_be_bytes(chunk),
+                    false => <$type>::from_le_bytes(chunk),
+                }
+            }};
+        }
+        let name = read_string(&bytes[36..64]);
+        let value = match read!(i32, 32) {
+            0 => JdfValue::String(read_string(&bytes[16..32])),
+            1 => JdfValue::Integer(read!(i32, 16)),
+            2 => JdfValue::Float(read!(f64, 16)),
+            3 => JdfValue::Complex(read!(f64, 16), read!(f64, 24)),
+            4 => JdfValue::Infinity(read!(i32, 16)),
+            value_type => {
+                return Err(SfError::new(&format!(
+                    "Illegal JDF value type for parameter \"{}\": {}",
+                    name, value_type
+                )));
+            }
+        };
+        Ok(Self {
+            name,
+            value,
+            unit_scaler: read!(i16, 4),
+            unit: JdfUnit::parse(&bytes[6..8]),
+        })
+    }
+}
+
+fn read_u16(bytes: &[u8], offset: usize) -> u16 {
+    u16::from_be_bytes(bytes[offset..offset + 2].try_into().unwrap())
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_be_bytes(bytes[offset..offset + 4].try_into().unwrap())
+}
+
+fn read_f64(bytes: &[u8], offset: usize) -> f64 {
+    f64::from_be_bytes(bytes[offset..offset + 8].try_into().unwrap())
+}
+
+/// Reads a NUL padded ASCII string, trimming blanks.
+fn read_string(bytes: &[u8]) -> String {
+    let len = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    bytes[..len]
+        .iter()
+        .map(|&b| b as char)
+        .collect::<String>()
+        .trim()
+        .to_owned()
+}
+
+/// Reads a date packed into 32 bits as 7 bits years since 1990, 4 bits month, and 5 bits day.
+fn read_date(bytes: &[u8], offset: usize) -> String {
+    let value = read_u32(bytes, offset);
+    format!(
+        "{:04}-{:02}-{:02}",
+        1990 + (value >> 25),
+        (value >> 21) & 0x0F,
+        (value >> 16) & 0x1F
+    )
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// A minimal JDF file for tests with the data given in file order.
+    pub(crate) struct TestJdf {
+        pub big_endian: bool,
+        pub data_format: u8,
+        pub axis_types: Vec<u8>,
+        pub units: Vec<[u8; 2]>,
+        pub points: Vec<u32>,
+        pub offsets: Vec<(u32, u32)>,
+        pub axis_ranges: Vec<(f64, f64)>,
+        pub base_freq: f64,
+        pub reversed: bool,
+        pub parameters: Vec<(&'static str, i32, [u8; 16])>,
+        pub data: Vec<f64>,
+    }
+
+    impl Default for TestJdf {
+        fn default() -> Self {
+            Self {
+                big_endian: true,
+                data_format: 1,
+                axis_types: vec![3],
+                // seconds
+                units: vec![[0, 28]],
+                points: vec![4],
+                offsets: vec![(0, 3)],
+                axis_ranges: vec![(0.0, 0.003)],
+                base_freq: 399.78,
+                reversed: false,
+                parameters: vec![],
+                data: vec![1.0, 2.0, 3.0, 4.0, -1.0, -2.0, -3.0, -4.0],
+            }
+        }
+    }
+
+    impl TestJdf {
+        pub(crate) fn to_bytes(&self) -> Vec<u8> {
+            let mut header = vec![0u8; JdfHeader::SIZE];
+            header[0..8].copy_from_slice(JdfFile::FILE_IDENTIFIER);
+            header[8] = match self.big_endian {
+                true => 0,
+                false => 1,
+            };
+            header[9] = 1;
+            header[12] = self.axis_types.len() as u8;
+            header[14] = self.data_format;
+            header[48..53].copy_from_slice(b"Title");
+            // 2025-03-14
+            let date: u32 = (35 << 25) | (3 << 21) | (14 << 16);
+            header[400..404].copy_from_slice(&date.to_be_bytes());
+            header[552..558].copy_from_slice(b"Author");
+            for dim in 0..self.axis_types.len() {
+                header[24 + dim] = self.axis_types[dim];
+                header[32 + dim * 2..34 + dim * 2].copy_from_slice(&self.units[dim]);
+                header[176 + dim * 4..180 + dim * 4]
+                    .copy_from_slice(&self.points[dim].to_be_bytes());
+                header[208 + dim * 4..212 + dim * 4]
+                    .copy_from_slice(&self.offsets[dim].0.to_be_bytes());
+                header[240 + dim * 4..244 + dim * 4]
+                    .copy_from_slice(&self.offsets[dim].1.to_be_bytes());
+                header[272 + dim * 8..280 + dim * 8]
+                    .copy_from_slice(&self.axis_ranges[dim].0.to_be_bytes());
+                header[336 + dim * 8..344 + dim * 8]
+                    .copy_from_slice(&self.axis_ranges[dim].1.to_be_bytes());
+                header[808 + dim * 32] = b'X' + dim as u8;
+                header[1064 + dim * 8..1072 + dim * 8]
+                    .copy_from_slice(&self.base_freq.to_be_bytes());
+                header[1192 + dim] = self.reversed as u8;
+            }
+
+            let u32_bytes = |value: u32| match self.big_endian {
+                true => value.to_be_bytes(),
+                false => value.to_le_bytes(),
+            };
+            let mut parameters = vec![];
+            if !self.parameters.is_empty() {
+                parameters.extend(u32_bytes(64));
+                parameters.extend(u32_bytes(0));
+                parameters.extend(u32_bytes(self.parameters.len() as u32 - 1));
+                parameters.extend(u32_bytes(16 + self.parameters.len() as u32 * 64));
+                for (name, value_type, value) in &self.parameters {
+                    let mut record = [0u8; 64];
+                    // Hz
+                    record[6..8].copy_from_slice(&[0, 13]);
+                    record[16..32].copy_from_slice(value);
+                    record[32..36].copy_from_slice(&u32_bytes(*value_type as u32));
+                    record[36..36 + name.len()].copy_from_slice(name.as_bytes());
+                    parameters.extend(record);
+                }
+            }
+
+            let param_start = JdfHeader::SIZE as u32;
+            let data_start = param_start + parameters.len() as u32;
+            header[1212..1216].copy_from_slice(&param_start.to_be_bytes());
+            header[1216..1220].copy_from_slice(&(parameters.len() as u32).to_be_bytes());
+            header[1284..1288].copy_from_slice(&data_start.to_be_bytes());
+            header[1288..1296].copy_from_slice(&(self.data.len() as u64 * 8).to_be_bytes());
+
+            let mut bytes = header;
+            bytes.extend(parameters);
+            for value in &self.data {
+                match self.big_endian {
+                    true => bytes.extend(value.to_be_bytes()),
+                    false => bytes.extend(value.to_le_bytes()),
+                }
+            }
+            bytes
+        }
+
+        pub(crate) fn parse(&self) -> Result<JdfFile, SfError> {
+            JeolParser::parse("test.jdf", Cursor::new(self.to_bytes()))
+        }
+    }
+
+    /// Lays out a row-major matrix in 32 x 32 submatrices.
+    pub(crate) fn to_submatrices(matrix: &[f64], columns: usize) -> Vec<f64> {
+        let rows = matrix.len() / columns;
+        let mut values = vec![];
+        for submatrix_row in 0..rows / 32 {
+            for submatrix_column in 0..columns / 32 {
+                for row in 0..32 {
+                    let start = (submatrix_row * 32 + row) * columns + submatrix_column * 32;
+                    values.extend(&matrix[start..start + 32]);
+                }
+            }
+        }
+        values
+    }
+
+    #[test]
+    fn parses_1d_complex_file() {
+        let mut value = [0u8; 16];
+        value[..3].copy_from_slice(b"1H ");
+        let mut scans = [0u8; 16];
+        scans[..4].copy_from_slice(&8i32.to_be_bytes());
+        let mut freq = [0u8; 16];
+        freq[..8].copy_from_slice(&399.78f64.to_be_bytes());
+        let jdf = TestJdf {
+            parameters: vec![
+                ("X_DOMAIN", 0, value),
+                ("SCANS", 1, scans),
+                ("X_FREQ", 2, freq),
+            ],
+            ..Default::default()
+        };
+
+        let file = jdf.parse().unwrap();
+        let header = &file.header;
+        assert!(header.big_endian);
+        assert_eq!(1, header.get_num_dimensions());
+        assert_eq!(JdfDataType::F64, header.data_type);
+        assert_eq!(JdfDataFormat::OneD, header.data_format);
+        assert_eq!(JdfAxisType::Complex, header.data_axis_type[0]);
+        assert_eq!(Some("SECONDS".to_owned()), header.data_units[0].get_name());
+        assert_eq!("Title", header.title);
+        assert_eq!("Author", header.author);
+        assert_eq!("2025-03-14", header.creation_time);
+        assert_eq!("X", header.data_axis_titles[0]);
+        assert_eq!(399.78, header.base_freq[0]);
+        assert_eq!(
+            vec![0.0, 0.001, 0.002, 0.003],
+            header
+                .get_axis_values(0)
+                .unwrap()
+                .iter()
+                .map(|v| (v * 1e6).round() / 1e6)
+                .collect::<Vec<_>>()
+        );
+
+        assert_eq!(3, file.parameters.len());
+        assert_eq!(
+            JdfValue::String("1H".to_owned()),
+            file.find_parameter("x_domain").unwrap().value
+        );
+        assert_eq!(
+            JdfValue::Integer(8),
+            file.find_parameter("SCANS").unwrap().value
+        );
+        let x_freq = file.find_parameter("X_FREQ").unwrap();
+        assert_eq!(JdfValue::Float(399.78), x_freq.value);
+        assert_eq!(Some("HZ".to_owned()), x_freq.unit.get_name());
+
+        assert_eq!(2, file.get_num_components());
+        assert_eq!(vec![1.0, 2.0, 3.0, 4.0], file.read_component(0).unwrap());
+        assert_eq!(
+            vec![-1.0, -2.0, -3.0, -4.0],
+            file.read_component(1).unwrap()
+        );
+        assert!(file.read_component(2).is_err());
+    }
+
+    #[test]
+    fn parses_little_endian_file_with_offsets() {
+        let mut scans = [0u8; 16];
+        scans[..4].copy_from_slice(&16i32.to_le_bytes());
+        let jdf = TestJdf {
+            big_endian: false,
+            axis_types: vec![1],
+            offsets: vec![(1, 2)],
+            axis_ranges: vec![(0.001, 0.002)],
+            parameters: vec![("SCANS", 1, scans)],
+            data: vec![1.0, 2.0, 3.0, 4.0],
+            ..Default::default()
+        };
+
+        let file = jdf.parse().unwrap();
+        assert!(!file.header.big_endian);
+        assert_eq!(JdfValue::Integer(16), file.parameters[0].value);
+        assert_eq!(1, file.get_num_components());
+        assert_eq!(vec![2.0, 3.0], file.read_component(0).unwrap());
+        assert_eq!(vec![0.001, 0.002], file.header.get_axis_values(0).unwrap());
+    }
+
+    #[test]
+    fn parses_2d_submatrices() {
+        let (columns, rows) = (64, 32);
+        let matrix: Vec<f64> = (0..rows * columns)
+            .map(|i| ((i / columns) * 100 + i % columns) as f64)
+            .collect();
+        let jdf = TestJdf {
+            data_format: 2,
+            axis_types: vec![1, 1],
+            units: vec![[0, 26], [0, 26]],
+            points: vec![columns as u32, rows as u32],
+            offsets: vec![(0, 49), (1, 30)],
+            axis_ranges: vec![(10.0, 0.0), (9.0, 1.0)],
+            data: to_submatrices(&matrix, columns),
+            ..Default::default()
+        };
+
+        let file = jdf.parse().unwrap();
+        assert_eq!(JdfDataFormat::TwoD, file.header.data_format);
+        assert_eq!(1, file.get_num_components());
+        let values = file.read_component(0).unwrap();
+        assert_eq!(30 * 50, values.len());
+        assert_eq!(100.0, values[0]);
+        assert_eq!(149.0, values[49]);
+        assert_eq!(200.0, values[50]);
+        assert_eq!(3049.0, values[30 * 50 - 1]);
+    }
+
+    #[test]
+    fn rejects_unsupported_data_format() {
+        let jdf = TestJdf {
+            data_format: 3,
+            axis_types: vec![1, 1, 1],
+            units: vec![[0, 28]; 3],
+            points: vec![32; 3],
+            offsets: vec![(0, 31); 3],
+            axis_ranges: vec![(0.0, 1.0); 3],
+            data: vec![],
+            ..Default::default()
+        };
+
+        let file = jdf.parse().unwrap();
+        let error = file.read_component(0).unwrap_err();
+        assert_eq!(SfErrorKind::UnsupportedFormat, error.kind());
+        assert!(error.to_string().contains("Three_D"));
+    }
+
+    #[test]
+    fn rejects_illegal_input() {
+        let error = JeolParser::parse("test.jdf", Cursor::new(b"JEOL.NMR".to_vec()))
+            .err()
+            .unwrap();
+        assert_eq!(SfErrorKind::UnsupportedFormat, error.kind());
+
+        let mut bytes = TestJdf::default().to_bytes();
+        bytes[0..8].copy_from_slice(b"NOT.JEOL");
+        let error = JeolParser::parse("test.jdf", Cursor::new(bytes))
+            .err()
+            .unwrap();
+        assert_eq!(SfErrorKind::UnsupportedFormat, error.kind());
+
+        let jdf = TestJdf {
+            offsets: vec![(0, 4)],
+            ..Default::default()
+        };
+        let error = jdf.parse().unwrap().read_component(0).unwrap_err();
+        assert!(error.to_string().contains("Illegal JDF data offsets"));
+    }
+
+    #[test]
+    fn rejects_sizes_exceeding_input() {
+        // parameter length
+        let mut bytes = TestJdf::default().to_bytes();
+        bytes[1216..1220].copy_from_slice(&u32::MAX.to_be_bytes());
+        let error = JeolParser::parse("test.jdf", Cursor::new(bytes))
+            .err()
+            .unwrap();
+        assert_eq!(SfErrorKind::CorruptData, error.kind());
+        assert!(error.to_string().contains("parameter section exceeds"));
+
+        let jdf = TestJdf {
+            points: vec![u32::MAX],
+            ..Default::default()
+        };
+        let error = jdf.parse().unwrap().read_component(1).unwrap_err();
+        assert_eq!(SfErrorKind::CorruptData, error.kind());
+
+        let jdf = TestJdf {
+            data_format: 2,
+            axis_types: vec![1, 1],
+            units: vec![[0, 28]; 2],
+            points: vec![u32::MAX, u32::MAX],
+            offsets: vec![(0, 31); 2],
+            axis_ranges: vec![(0.0, 1.0); 2],
+            ..Default::default()
+        };
+        let error = jdf.parse().unwrap().read_component(0).unwrap_err();
+        assert_eq!(SfErrorKind::CorruptData, error.kind());
+    }
+}
@@ -0,0 +1,430 @@
+// Copyright (c) 2025 Robert Schiwon
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use super::jeol_parser::{JdfDataFormat, JdfFile};
+use crate::{
+    api::{Axis, Column, ComplexData, NdArray, Node, Parameter, PointXy, Reader, Table, Value},
+    common::{SfError, SfErrorKind},
+    utils::convert_path_to_node_indices,
+};
+use std::collections::HashMap;
+
+pub struct JeolReader {
+    path: String,
+    file: JdfFile,
+}
+
+impl Reader for JeolReader {
+    fn read(&self, path: &str) -> Result<Node, SfError> {
+        self.read_node(path)
+            .map_err(|e| e.or_kind(SfErrorKind::CorruptData))
+    }
+}
+
+impl JeolReader {
+    pub fn new(path: &str, file: JdfFile) -> Self {
+        Self {
+            path: path.to_owned(),
+            file,
+        }
+    }
+
+    fn read_node(&self, path: &str) -> Result<Node, SfError> {
+        let node_indices = convert_path_to_node_indices(path)?;
+        let format = self.file.header.data_format;
+        match (&node_indices[..], format) {
+            ([], _) => Ok(self.map_root()),
+            ([0], JdfDataFormat::OneD) => self.map_1d(),
+            ([index], JdfDataFormat::TwoD) if *index < self.file.get_num_components() => {
+                self.map_2d(*index)
+            }
+            ([_], JdfDataFormat::OneD | JdfDataFormat::TwoD) => {
+                Err(SfError::new(&format!("Illegal node path: {}", path))
+                    .with_kind(SfErrorKind::IllegalNodePath))
+            }
+            ([_], _) => Err(SfError::new(&format!(
+                "Unsupported JDF data format for {}: {}",
+                self.path, format
+            ))
+            .with_kind(SfErrorKind::UnsupportedFormat)),
+            _ => Err(SfError::new(&format!("Illegal node path: {}", path))
+                .with_kind(SfErrorKind::IllegalNodePath)),
+        }
+    }
+
+    fn get_child_node_names(&self) -> Vec<String> {
+        match self.file.header.data_format {
+            JdfDataFormat::OneD => vec![self.get_1d_name().to_owned()],
+            JdfDataFormat::TwoD => (0..self.file.get_num_components())
+                .map(|component| self.get_2d_component_name(component))
+                .collect(),
+            // unsupported, but still listed to surface the error when read
+            _ => vec!["DATA".to_owned()],
+        }
+    }
+
+    fn get_1d_name(&self) -> &str {
+        match self.get_unit(0).as_deref() {
+            Some("SECONDS") => "FID",
+            _ => "SPECTRUM",
+        }
+    }
+
+    /// Names a 2D component by its real ("R") or imaginary ("I") part in each dimension,
+    /// starting with the first dimension, e.g., "RR", "IR", "RI", and "II" for data that is
+    /// complex in both dimensions.
+    fn get_2d_component_name(&self, component: usize) -> String {
+        let axis_types = &self.file.header.data_axis_type;
+        if self.file.get_num_components() == 1 {
+            return "REAL".to_owned();
+        }
+        let mut remainder = component;
+        axis_types[..2]
+            .iter()
+            .map(|axis_type| {
+                let num_components = axis_type.get_num_components();
+                let part = remainder % num_components;
+                remainder /= num_components;
+                match part {
+                    0 => 'R',
+                    _ => 'I',
+                }
+            })
+            .collect()
+    }
+
+    fn get_unit(&self, dim: usize) -> Option<String> {
+        self.file.header.data_units[dim].get_name()
+    }
+
+    fn get_axis_label(&self, dim: usize) -> String {
+        match self.get_unit(dim).as_deref() {
+            Some("SECONDS") => "Time".to_owned(),
+            Some("PPM") => "Chemical Shift".to_owned(),
+            _ => self.file.header.data_axis_titles[dim].clone(),
+        }
+    }
+
+    fn map_axis(&self, dim: usize) -> Result<Axis, SfError> {
+        let header = &self.file.header;
+        let title = &header.data_axis_titles[dim];
+        let name = match title.is_empty() {
+            true => format!("AXIS {}", dim + 1),
+            false => title.clone(),
+        };
+        Ok(Axis::new(
+            name,
+            self.get_unit(dim),
+            header.get_axis_values(dim)?,
+        ))
+    }
+
+    fn map_root(&self) -> Node {
+        let header = &self.file.header;
+        let mut parameters = vec![];
+        for (key, value) in [
+            ("title", &header.title),
+            ("comment", &header.comment),
+            ("author", &header.author),
+            ("site", &header.site),
+            ("node name", &header.node_name),
+        ] {
+            if !value.is_empty() {
+                parameters.push(Parameter::from_str_str(key, value));
+            }
+        }
+        parameters.push(Parameter::from_str_str(
+            "version",
+            format!("{}.{}", header.major_version, header.minor_version),
+        ));
+        parameters.push(Parameter::from_str_str(
+            "creation time",
+            &header.creation_time,
+        ));
+        parameters.push(Parameter::from_str_str(
+            "revision time",
+            &header.revision_time,
+        ));
+        parameters.push(Parameter::from_str_str(
+            "data format",
+            header.data_format.to_string(),
+        ));
+        parameters.push(Parameter::from_str_str(
+            "data type",
+            header.data_type.to_string(),
+        ));
+        for dim in 0..header.get_num_dimensions() {
+            let prefix = format!("dimension {}", dim + 1);
+            parameters.push(Parameter::from_str_str(
+                format!("{} axis type", prefix),
+                header.data_axis_type[dim].to_string(),
+            ));
+            parameters.push(Parameter::from_str_u64(
+                format!("{} points", prefix),
+                header.data_points[dim] as u64,
+            ));
+            parameters.push(Parameter::from_str_f64(
+                format!("{} base frequency", prefix),
+                header.base_freq[dim],
+            ));
+        }
+        for parameter in &self.file.parameters {
+            parameters.push(Parameter::KeyValue(
+                parameter.name.clone(),
+                parameter.value.to_value(),
+            ));
+        }
+
+        let rows: Vec<HashMap<String, Value>> = self
+            .file
+            .parameters
+            .iter()
+            .map(|parameter| {
+                let mut row = HashMap::from([
+                    ("name".to_owned(), Value::String(parameter.name.clone())),
+                    ("value".to_owned(), parameter.value.to_value()),
+                ]);
+                if let Some(unit) = parameter.unit.get_name() {
+                    row.insert("unit".to_owned(), Value::String(unit));
+                }
+                row
+            })
+            .collect();
+        let table = (!rows.is_empty()).then(|| Table {
+            column_names: vec![
+                Column::new("name", "Name"),
+                Column::new("value", "Value"),
+                Column::new("unit", "Unit"),
+            ],
+            rows,
+        });
+
+        Node {
+            name: self.file.name.clone(),
+            parameters,
+            data: vec![],
+            metadata: vec![],
+            table,
+            nd_array: None,
+            complex_data: None,
+            links: vec![],
+            child_node_names: self.get_child_node_names(),
+        }
+    }
+
+    fn map_1d(&self) -> Result<Node, SfError> {
+        let header = &self.file.header;
+        let axis = self.map_axis(0)?;
+        let real = self.file.read_component(0)?;
+        let data = axis
+            .values
+            .iter()
+            .zip(&real)
+            .map(|(x, y)| PointXy::new(*x, *y))
+            .collect();
+        let name = self.get_1d_name();
+        let complex_data = match self.file.get_num_components() {
+            2 => Some(ComplexData::new(
+                name,
+                None,
+                axis,
+                real,
+                self.file.read_component(1)?,
+            )?),
+            _ => None,
+        };
+
+        let mut metadata = vec![];
+        if let Some(unit) = self.get_unit(0) {
+            metadata.push(("x.unit".to_owned(), unit));
+        }
+        metadata.push(("x.label".to_owned(), self.get_axis_label(0)));
+        if header.reversed[0] {
+            metadata.push(("x.reverse".to_owned(), "true".to_owned()));
+        }
+
+        Ok(Node {
+            name: name.to_owned(),
+            parameters: vec![Parameter::from_str_f64(
+                ".OBSERVE FREQUENCY",
+                header.base_freq[0],
+            )],
+            data,
+            metadata,
+            table: None,
+            nd_array: None,
+            complex_data,
+            links: vec![],
+            child_node_names: vec![],
+        })
+    }
+
+    fn map_2d(&self, component: usize) -> Result<Node, SfError> {
+        let name = self.get_2d_component_name(component);
+        let values = self.file.read_component(component)?;
+        let nd_array = NdArray::new(
+            &name,
+            None,
+            vec![self.map_axis(1)?, self.map_axis(0)?],
+            values,
+        )?;
+
+        Ok(Node {
+            name,
+            parameters: vec![],
+            data: vec![],
+            metadata: vec![],
+            table: None,
+            nd_array: Some(nd_array),
+            complex_data: None,
+            links: vec![],
+            child_node_names: vec![],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jeol::jeol_parser::tests::{TestJdf, to_submatrices};
+
+    fn create_reader(jdf: TestJdf) -> JeolReader {
+        JeolReader::new("test.jdf", jdf.parse().unwrap())
+    }
+
+    #[test]
+    fn maps_1d_complex_fid() {
+        let mut scans = [0u8; 16];
+        scans[..4].copy_from_slice(&8i32.to_be_bytes());
+        let reader = create_reader(TestJdf {
+            parameters: vec![("SCANS", 1, scans)],
+            ..Default::default()
+        });
+
+        let root = reader.read("/").unwrap();
+        assert_eq!("test.jdf", root.name);
+        assert_eq!(vec!["FID"], root.child_node_names);
+        assert!(
+            root.parameters
+                .contains(&Parameter::from_str_str("title", "Title"))
+        );
+        assert!(
+            root.parameters
+                .contains(&Parameter::from_str_str("data format", "One_D"))
+        );
+        assert!(
+            root.parameters
+                .contains(&Parameter::from_str_str("dimension 1 axis type", "Complex"))
+        );
+        assert!(
+            root.parameters
+                .contains(&Parameter::from_str_i64("SCANS", 8))
+        );
+        let table = root.table.unwrap();
+        assert_eq!(1, table.rows.len());
+        assert_eq!(
+            Some(&Value::String("HZ".to_owned())),
+            table.rows[0].get("unit")
+        );
+
+        let fid = reader.read("/0").unwrap();
+        assert_eq!("FID", fid.name);
+        assert_eq!(4, fid.data.len());
+        assert_eq!(PointXy::new(0.0, 1.0), fid.data[0]);
+        let complex_data = fid.complex_data.unwrap();
+        assert_eq!(Some("SECONDS".to_owned()), complex_data.axis.unit);
+        assert_eq!(vec![-1.0, -2.0, -3.0, -4.0], complex_data.imaginary);
+        assert!(
+            fid.metadata
+                .contains(&("x.label".to_owned(), "Time".to_owned()))
+        );
+        assert!(
+            fid.parameters
+                .contains(&Parameter::from_str_f64(".OBSERVE FREQUENCY", 399.78))
+        );
+        assert_eq!(
+            SfErrorKind::IllegalNodePath,
+            reader.read("/1").unwrap_err().kind()
+        );
+    }
+
+    #[test]
+    fn maps_1d_real_spectrum() {
+        let reader = create_reader(TestJdf {
+            axis_types: vec![1],
+            units: vec![[0, 26]],
+            axis_ranges: vec![(3.0, 0.0)],
+            reversed: true,
+            data: vec![1.0, 2.0, 3.0, 4.0],
+            ..Default::default()
+        });
+
+        let spectrum = reader.read("/0").unwrap();
+        assert_eq!("SPECTRUM", spectrum.name);
+        assert_eq!(PointXy::new(3.0, 1.0), spectrum.data[0]);
+        assert_eq!(PointXy::new(0.0, 4.0), spectrum.data[3]);
+        assert!(spectrum.complex_data.is_none());
+        assert_eq!(
+            vec![
+                ("x.unit".to_owned(), "PPM".to_owned()),
+                ("x.label".to_owned(), "Chemical Shift".to_owned()),
+                ("x.reverse".to_owned(), "true".to_owned()),
+            ],
+            spectrum.metadata
+        );
+    }
+
+    #[test]
+    fn maps_2d_complex_components() {
+        let (columns, rows) = (32, 32);
+        let component: Vec<f64> = (0..rows * columns).map(|i| i as f64).collect();
+        let mut data = vec![];
+        for c in 0..2 {
+            let matrix: Vec<f64> = component.iter().map(|v| v + c as f64 * 10000.0).collect();
+            data.extend(to_submatrices(&matrix, columns));
+        }
+        let reader = create_reader(TestJdf {
+            data_format: 2,
+            axis_types: vec![3, 1],
+            units: vec![[0, 26], [0, 26]],
+            points: vec![32, 32],
+            offsets: vec![(0, 31), (0, 1)],
+            axis_ranges: vec![(10.0, 0.0), (5.0, 4.0)],
+            data,
+            ..Default::default()
+        });
+
+        let root = reader.read("/").unwrap();
+        assert_eq!(vec!["RR", "IR"], root.child_node_names);
+
+        let imaginary = reader.read("/1").unwrap();
+        assert_eq!("IR", imaginary.name);
+        let array = imaginary.nd_array.unwrap();
+        assert_eq!(vec![2, 32], array.get_shape());
+        assert_eq!(vec![5.0, 4.0], array.axes[0].values);
+        assert_eq!("Y", array.axes[0].name);
+        assert_eq!(Some("PPM".to_owned()), array.axes[1].unit);
+        assert_eq!(10000.0, array.values[0]);
+        assert_eq!(10063.0, array.values[63]);
+        assert_eq!(
+            SfErrorKind::IllegalNodePath,
+            reader.read("/2").unwrap_err().kind()
+        );
+    }
+}
@@ -0,0 +1,110 @@
+// Copyright (c) 2025 Robert Schiwon
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use super::{
+    jeol_parser::{JdfFile, JeolParser},
+    jeol_reader::JeolReader,
+};
+use crate::{
+    api::{Confidence, ParseOptions, Parser, Reader, Scanner, SeekRead},
+    common::SfError,
+    utils::{determine_confidence, is_recognized_extension},
+};
+use std::io::SeekFrom;
+
+#[derive(Default)]
+pub struct JeolScanner {
+    options: ParseOptions,
+}
+
+impl JeolScanner {
+    const ACCEPTED_EXTENSIONS: [&'static str; 1] = ["jdf"];
+}
+
+impl JeolScanner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Checks whether the input starts with the file identifier "JEOL.NMR".
+    fn is_recognized_content<T: SeekRead + 'static>(&self, input: &mut T) -> bool {
+        let mut buf = [0u8; 8];
+        let is_recognized = input
+            .seek(SeekFrom::Start(0))
+            .and_then(|_| input.read_exact(&mut buf))
+            .is_ok_and(|_| &buf == JdfFile::FILE_IDENTIFIER);
+        let _ = input.seek(SeekFrom::Start(0));
+        is_recognized
+    }
+}
+
+impl<T: SeekRead + 'static> Scanner<T> for JeolScanner {
+    fn is_recognized(&self, path: &str, input: &mut T) -> bool {
+        is_recognized_extension(path, &Self::ACCEPTED_EXTENSIONS)
+            && self.is_recognized_content(input)
+    }
+
+    fn get_confidence(&self, path: &str, input: &mut T) -> Option<Confidence> {
+        determine_confidence(
+            is_recognized_extension(path, &Self::ACCEPTED_EXTENSIONS),
+            self.is_recognized_content(input),
+        )
+    }
+
+    fn get_name(&self) -> &str {
+        "JEOL"
+    }
+
+    fn set_parse_options(&mut self, options: &ParseOptions) {
+        self.options = options.clone();
+    }
+
+    fn get_reader(&self, path: &str, input: T) -> Result<Box<dyn Reader>, SfError> {
+        let file = JeolParser::parse(path, input)?;
+        Ok(Box::new(JeolReader::new(path, file)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn provides_confidence() {
+        let scanner = JeolScanner::new();
+        let content = b"JEOL.NMR\x00\x01\x00\x00\x01".to_vec();
+
+        let mut input = Cursor::new(content.clone());
+        let confidence = scanner.get_confidence("valid.jdf", &mut input);
+        assert_eq!(Some(Confidence::High), confidence);
+
+        let mut input = Cursor::new(b"not a JDF file".to_vec());
+        let confidence = scanner.get_confidence("invalid.jdf", &mut input);
+        assert_eq!(Some(Confidence::Low), confidence);
+
+        let mut input = Cursor::new(content);
+        let confidence = scanner.get_confidence("valid.bin", &mut input);
+        assert_eq!(Some(Confidence::Medium), confidence);
+
+        let mut input = Cursor::new(b"JEOL".to_vec());
+        let confidence = scanner.get_confidence("invalid.bin", &mut input);
+        assert_eq!(None, confidence);
+    }
+}
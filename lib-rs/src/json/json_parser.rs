@@ -17,15 +17,21 @@
 // IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
 // CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
-use crate::common::SfError;
-use crate::{api::Parser, utils::convert_path_to_node_indices};
+use crate::common::{SfError, SfErrorKind};
+use crate::{
+    api::Parser,
+    utils::{check_data_range, convert_path_to_node_indices, lock_input},
+};
 use sciformats_serde_json::span::Span;
-use serde::Deserialize;
+use serde::{
+    Deserialize, Deserializer,
+    de::{IgnoredAny, SeqAccess, Visitor},
+};
 use std::{
-    cell::RefCell,
     collections::HashMap,
-    io::{Read, Seek},
-    rc::Rc,
+    io::{Read, Seek, Take},
+    ops::Range,
+    sync::{Arc, Mutex},
 };
 
 pub struct JsonParser {}
@@ -35,10 +41,15 @@ impl<T: Seek + Read> Parser<T> for JsonParser {
     type E = SfError;
 
     fn parse(_name: &str, input: T) -> Result<Self::R, Self::E> {
-        let rcrefcell = Rc::new(RefCell::new(input));
+        let rcrefcell = Arc::new(Mutex::new(input));
         let lazy_doc: JsonLazyDocument =
-            sciformats_serde_json::from_reader(&mut *rcrefcell.borrow_mut())
-                .map_err(|e| SfError::from_source(e, "Error deserializing JSON document."))?;
+            sciformats_serde_json::from_reader(&mut *lock_input(&rcrefcell)).map_err(|e| {
+                let kind = match e.is_io() {
+                    true => SfErrorKind::Io,
+                    false => SfErrorKind::CorruptData,
+                };
+                SfError::from_source(e, "Error deserializing JSON document.").with_kind(kind)
+            })?;
         let doc = JsonDocument {
             format: lazy_doc.format,
             version: lazy_doc.version,
@@ -53,33 +64,58 @@ pub struct JsonDocument<T: Seek + Read> {
     pub format: String,
     pub version: String,
     pub(super) nodes: JsonLazyNode,
-    pub(super) input: Rc<RefCell<T>>,
+    pub(super) input: Arc<Mutex<T>>,
 }
 
 impl<T: Seek + Read> JsonDocument<T> {
     pub fn get_node(&self, node_path: &str) -> Result<JsonNode, SfError> {
-        // Retrieve lazy node.
-        let indices = convert_path_to_node_indices(node_path)?;
-        let mut node = &self.nodes;
-        for index in indices {
-            node = node
-                .children
-                .get(index)
-                .ok_or(SfError::new(&format!("Illegal node path: {}", node_path)))?;
-        }
+        let node = self.find_lazy_node(node_path)?;
 
         // Deserialize data section.
-        let data_span = &node.data;
-        let mut input_borrow = self.input.borrow_mut();
-        input_borrow.seek(std::io::SeekFrom::Start(data_span.span.start))?;
-        let span_bytes = (&mut *input_borrow).take(data_span.span.end - data_span.span.start);
-        let mut nested_de = sciformats_serde_json::Deserializer::from_reader(span_bytes);
-        let data = Vec::<JsonDataItem>::deserialize(&mut nested_de).map_err(|e| {
-            SfError::new(&format!(
-                "Error deserializing JSON section: {}",
-                e.to_string()
-            ))
-        })?;
+        let mut input_borrow = lock_input(&self.input);
+        let mut nested_de = Self::create_data_deserializer(&mut *input_borrow, &node.data)?;
+        let data = Vec::<JsonDataItem>::deserialize(&mut nested_de)
+            .map_err(|e| SfError::new(&format!("Error deserializing JSON section: {}", e)))?;
+
+        // Deserialize nd array values section.
+        let nd_array = match &node.nd_array {
+            None => None,
+            Some(lazy_nd_array) => {
+                let mut nested_de =
+                    Self::create_data_deserializer(&mut *input_borrow, &lazy_nd_array.values)?;
+                let values = Vec::<f64>::deserialize(&mut nested_de).map_err(|e| {
+                    SfError::new(&format!("Error deserializing JSON section: {}", e))
+                })?;
+                Some(JsonNdArray {
+                    name: lazy_nd_array.name.clone(),
+                    unit: lazy_nd_array.unit.clone(),
+                    axes: lazy_nd_array.axes.clone(),
+                    values,
+                })
+            }
+        };
+
+        // Deserialize complex data values sections.
+        let complex_data = match &node.complex_data {
+            None => None,
+            Some(lazy_complex_data) => {
+                let mut deserialize_values = |span: &Span| -> Result<Vec<f64>, SfError> {
+                    let mut nested_de = Self::create_data_deserializer(&mut *input_borrow, span)?;
+                    Vec::<f64>::deserialize(&mut nested_de).map_err(|e| {
+                        SfError::new(&format!("Error deserializing JSON section: {}", e))
+                    })
+                };
+                let real = deserialize_values(&lazy_complex_data.real)?;
+                let imaginary = deserialize_values(&lazy_complex_data.imaginary)?;
+                Some(JsonComplexData {
+                    name: lazy_complex_data.name.clone(),
+                    unit: lazy_complex_data.unit.clone(),
+                    axis: lazy_complex_data.axis.clone(),
+                    real,
+                    imaginary,
+                })
+            }
+        };
 
         // Map child node names.
         let child_node_names: Vec<String> = node
@@ -96,11 +132,100 @@ impl<T: Seek + Read> JsonDocument<T> {
             data,
             metadata: node.metadata.clone(),
             table: node.table.clone(),
+            nd_array,
+            complex_data,
+            links: node.links.clone(),
             child_node_names,
         };
 
         Ok(node)
     }
+
+    /// Provides the number of data items of a node.
+    pub fn get_data_len(&self, node_path: &str) -> Result<usize, SfError> {
+        let (_data, len) = self.read_data_range(node_path, 0..0)?;
+        Ok(len)
+    }
+
+    /// Provides a range of the data items of a node.
+    ///
+    /// Data items outside the range are skipped without being deserialized.
+    pub fn get_data_range(
+        &self,
+        node_path: &str,
+        range: Range<usize>,
+    ) -> Result<Vec<JsonDataItem>, SfError> {
+        let (data, len) = self.read_data_range(node_path, range.clone())?;
+        check_data_range(&range, len)?;
+        Ok(data)
+    }
+
+    fn read_data_range(
+        &self,
+        node_path: &str,
+        range: Range<usize>,
+    ) -> Result<(Vec<JsonDataItem>, usize), SfError> {
+        let node = self.find_lazy_node(node_path)?;
+        let mut input_borrow = lock_input(&self.input);
+        let mut nested_de = Self::create_data_deserializer(&mut *input_borrow, &node.data)?;
+        nested_de
+            .deserialize_seq(JsonDataRangeVisitor { range })
+            .map_err(|e| SfError::new(&format!("Error deserializing JSON section: {}", e)))
+    }
+
+    fn find_lazy_node(&self, node_path: &str) -> Result<&JsonLazyNode, SfError> {
+        let indices = convert_path_to_node_indices(node_path)?;
+        let mut node = &self.nodes;
+        for index in indices {
+            node = node.children.get(index).ok_or(
+                SfError::new(&format!("Illegal node path: {}", node_path))
+                    .with_kind(SfErrorKind::IllegalNodePath),
+            )?;
+        }
+        Ok(node)
+    }
+
+    fn create_data_deserializer<'a>(
+        input: &'a mut T,
+        data_span: &Span,
+    ) -> Result<
+        sciformats_serde_json::Deserializer<sciformats_serde_json::de::IoRead<Take<&'a mut T>>>,
+        SfError,
+    > {
+        input.seek(std::io::SeekFrom::Start(data_span.span.start))?;
+        let span_bytes = input.take(data_span.span.end - data_span.span.start);
+        Ok(sciformats_serde_json::Deserializer::from_reader(span_bytes))
+    }
+}
+
+/// Deserializes the data items of a JSON array within a range and counts all items.
+struct JsonDataRangeVisitor {
+    range: Range<usize>,
+}
+
+impl<'de> Visitor<'de> for JsonDataRangeVisitor {
+    type Value = (Vec<JsonDataItem>, usize);
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("an array of data items")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut data = Vec::<JsonDataItem>::with_capacity(self.range.len());
+        let mut len = 0usize;
+        loop {
+            if self.range.contains(&len) {
+                match seq.next_element::<JsonDataItem>()? {
+                    None => break,
+                    Some(item) => data.push(item),
+                }
+            } else if seq.next_element::<IgnoredAny>()?.is_none() {
+                break;
+            }
+            len += 1;
+        }
+        Ok((data, len))
+    }
 }
 
 #[derive(Deserialize)]
@@ -119,6 +244,12 @@ pub struct JsonLazyNode {
     pub data: Span,
     pub metadata: Vec<JsonMetadataItem>,
     pub table: Option<JsonTable>,
+    #[serde(rename(deserialize = "ndArray"), default)]
+    pub nd_array: Option<JsonLazyNdArray>,
+    #[serde(rename(deserialize = "complexData"), default)]
+    pub complex_data: Option<JsonLazyComplexData>,
+    #[serde(default)]
+    pub links: Vec<JsonNodeLink>,
     pub children: Vec<JsonLazyNode>,
 }
 
@@ -128,9 +259,19 @@ pub struct JsonNode {
     pub data: Vec<JsonDataItem>,
     pub metadata: Vec<JsonMetadataItem>,
     pub table: Option<JsonTable>,
+    pub nd_array: Option<JsonNdArray>,
+    pub complex_data: Option<JsonComplexData>,
+    pub links: Vec<JsonNodeLink>,
     pub child_node_names: Vec<String>,
 }
 
+#[derive(Deserialize, PartialEq, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct JsonNodeLink {
+    pub relation: String,
+    pub path: String,
+}
+
 #[derive(Deserialize, PartialEq, Debug, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct JsonParameter {
@@ -176,6 +317,58 @@ pub struct JsonTableColumn {
     pub name: String,
 }
 
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct JsonLazyNdArray {
+    pub name: String,
+    #[serde(default)]
+    pub unit: Option<String>,
+    pub axes: Vec<JsonAxis>,
+    pub values: Span,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct JsonNdArray {
+    pub name: String,
+    pub unit: Option<String>,
+    pub axes: Vec<JsonAxis>,
+    pub values: Vec<f64>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct JsonLazyComplexData {
+    pub name: String,
+    #[serde(default)]
+    pub unit: Option<String>,
+    pub axis: JsonAxis,
+    pub real: Span,
+    pub imaginary: Span,
+    // derived from real and imaginary values, hence ignored
+    #[serde(default)]
+    pub magnitude: Option<IgnoredAny>,
+    #[serde(default)]
+    pub phase: Option<IgnoredAny>,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct JsonComplexData {
+    pub name: String,
+    pub unit: Option<String>,
+    pub axis: JsonAxis,
+    pub real: Vec<f64>,
+    pub imaginary: Vec<f64>,
+}
+
+#[derive(Deserialize, PartialEq, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct JsonAxis {
+    pub name: String,
+    #[serde(default)]
+    pub unit: Option<String>,
+    pub values: Vec<f64>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -413,4 +606,137 @@ mod tests {
         let result = JsonParser::parse("extra.json", Cursor::new(EXTRA_PARAM_JSON));
         assert!(result.is_err());
     }
+
+    #[test]
+    fn provides_data_ranges() {
+        const JSON: &str = r#"
+            {
+                "format": "sciformats",
+                "version": "0.1.0",
+                "nodes": {
+                    "name": "Root node",
+                    "parameters": [],
+                    "data": [
+                        {"x": 0, "y": 10},
+                        {"x": 1, "y": 11},
+                        {"x": 2, "y": 12}
+                    ],
+                    "metadata": [],
+                    "table": null,
+                    "children": []
+                }
+            }"#;
+        let doc = JsonParser::parse("data.json", Cursor::new(JSON)).unwrap();
+
+        assert_eq!(3, doc.get_data_len("/").unwrap());
+        assert_eq!(
+            vec![
+                JsonDataItem { x: 1.0, y: 11.0 },
+                JsonDataItem { x: 2.0, y: 12.0 }
+            ],
+            doc.get_data_range("/", 1..3).unwrap()
+        );
+        assert!(doc.get_data_range("/", 0..0).unwrap().is_empty());
+        assert!(doc.get_data_range("/", 2..4).is_err());
+        assert!(doc.get_data_range("/0", 0..1).is_err());
+    }
+
+    #[test]
+    fn parses_nd_arrays() {
+        const JSON: &str = r#"
+            {
+                "format": "sciformats",
+                "version": "0.1.0",
+                "nodes": {
+                    "name": "Root node",
+                    "parameters": [],
+                    "data": [],
+                    "metadata": [],
+                    "ndArray": {
+                        "name": "Intensity",
+                        "unit": "counts",
+                        "axes": [
+                            {"name": "Time", "values": [0, 1]},
+                            {"name": "m/z", "unit": "M/Z", "values": [10, 20, 30]}
+                        ],
+                        "values": [1, 2, 3, 4, 5, 6]
+                    },
+                    "children": [
+                        {
+                            "name": "Nested node",
+                            "parameters": [],
+                            "data": [],
+                            "metadata": [],
+                            "children": []
+                        }
+                    ]
+                }
+            }"#;
+        let doc = JsonParser::parse("nd_array.json", Cursor::new(JSON)).unwrap();
+
+        let root = doc.get_node("/").unwrap();
+        let nd_array = root.nd_array.unwrap();
+        assert_eq!("Intensity", nd_array.name);
+        assert_eq!(Some("counts".to_owned()), nd_array.unit);
+        assert_eq!(
+            vec![
+                JsonAxis {
+                    name: "Time".to_owned(),
+                    unit: None,
+                    values: vec![0.0, 1.0]
+                },
+                JsonAxis {
+                    name: "m/z".to_owned(),
+                    unit: Some("M/Z".to_owned()),
+                    values: vec![10.0, 20.0, 30.0]
+                },
+            ],
+            nd_array.axes
+        );
+        assert_eq!(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], nd_array.values);
+
+        let nested = doc.get_node("/0").unwrap();
+        assert!(nested.nd_array.is_none());
+    }
+
+    #[test]
+    fn parses_complex_data_ignoring_derived_values() {
+        const JSON: &str = r#"
+            {
+                "format": "sciformats",
+                "version": "0.1.0",
+                "nodes": {
+                    "name": "Root node",
+                    "parameters": [],
+                    "data": [],
+                    "metadata": [],
+                    "complexData": {
+                        "name": "FID",
+                        "unit": "ARBITRARY UNITS",
+                        "axis": {"name": "TIME", "unit": "SECONDS", "values": [0, 0.5]},
+                        "real": [3, 0],
+                        "imaginary": [4, 1],
+                        "magnitude": [5, 1],
+                        "phase": [0.9273, 1.5708]
+                    },
+                    "children": []
+                }
+            }"#;
+        let doc = JsonParser::parse("complex_data.json", Cursor::new(JSON)).unwrap();
+
+        let root = doc.get_node("/").unwrap();
+        let complex_data = root.complex_data.unwrap();
+        assert_eq!("FID", complex_data.name);
+        assert_eq!(Some("ARBITRARY UNITS".to_owned()), complex_data.unit);
+        assert_eq!(
+            JsonAxis {
+                name: "TIME".to_owned(),
+                unit: Some("SECONDS".to_owned()),
+                values: vec![0.0, 0.5]
+            },
+            complex_data.axis
+        );
+        assert_eq!(vec![3.0, 0.0], complex_data.real);
+        assert_eq!(vec![4.0, 1.0], complex_data.imaginary);
+    }
 }
@@ -18,22 +18,37 @@
 // CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
 use crate::{
-    api::{Column, Node, Parameter, PointXy, Reader, Table, Value},
-    common::SfError,
+    api::{
+        Axis, Column, ComplexData, NdArray, Node, NodeLink, Parameter, PointXy, Reader, SeekRead,
+        Table, Value,
+    },
+    common::{SfError, SfErrorKind},
     json::json_parser::{JsonDocument, JsonNode, JsonValue},
 };
-use std::io::{Read, Seek};
+use std::{
+    io::{Read, Seek},
+    ops::Range,
+};
 
 pub struct JsonReader<T: Seek + Read> {
     _path: String,
     file: JsonDocument<T>,
 }
 
-impl<T: Seek + Read> Reader for JsonReader<T> {
+impl<T: SeekRead> Reader for JsonReader<T> {
     fn read(&self, node_path: &str) -> Result<Node, SfError> {
-        let json_node = self.file.get_node(node_path)?;
-        let node = Self::map_node(json_node)?;
-        Ok(node)
+        self.read_node(node_path)
+            .map_err(|e| e.or_kind(SfErrorKind::CorruptData))
+    }
+
+    fn read_data_len(&self, node_path: &str) -> Result<usize, SfError> {
+        self.read_node_data_len(node_path)
+            .map_err(|e| e.or_kind(SfErrorKind::CorruptData))
+    }
+
+    fn read_data(&self, node_path: &str, range: Range<usize>) -> Result<Vec<PointXy>, SfError> {
+        self.read_node_data(node_path, range)
+            .map_err(|e| e.or_kind(SfErrorKind::CorruptData))
     }
 }
 
@@ -45,6 +60,30 @@ impl<T: Seek + Read> JsonReader<T> {
         }
     }
 
+    fn read_node(&self, node_path: &str) -> Result<Node, SfError> {
+        let json_node = self.file.get_node(node_path)?;
+        let node = Self::map_node(json_node)?;
+        Ok(node)
+    }
+
+    fn read_node_data_len(&self, node_path: &str) -> Result<usize, SfError> {
+        self.file.get_data_len(node_path)
+    }
+
+    fn read_node_data(
+        &self,
+        node_path: &str,
+        range: Range<usize>,
+    ) -> Result<Vec<PointXy>, SfError> {
+        let data = self
+            .file
+            .get_data_range(node_path, range)?
+            .iter()
+            .map(|json_data_point| PointXy::new(json_data_point.x, json_data_point.y))
+            .collect();
+        Ok(data)
+    }
+
     fn map_node(json_node: JsonNode) -> Result<Node, SfError> {
         // Map name
         let name = json_node.name.clone();
@@ -113,6 +152,46 @@ impl<T: Seek + Read> JsonReader<T> {
             None
         };
 
+        // Map nd array
+        let nd_array = match json_node.nd_array {
+            Some(json_nd_array) => {
+                let axes = json_nd_array
+                    .axes
+                    .into_iter()
+                    .map(|json_axis| Axis::new(json_axis.name, json_axis.unit, json_axis.values))
+                    .collect();
+                Some(NdArray::new(
+                    json_nd_array.name,
+                    json_nd_array.unit,
+                    axes,
+                    json_nd_array.values,
+                )?)
+            }
+            None => None,
+        };
+
+        // Map complex data
+        let complex_data = match json_node.complex_data {
+            Some(json_complex_data) => {
+                let json_axis = json_complex_data.axis;
+                Some(ComplexData::new(
+                    json_complex_data.name,
+                    json_complex_data.unit,
+                    Axis::new(json_axis.name, json_axis.unit, json_axis.values),
+                    json_complex_data.real,
+                    json_complex_data.imaginary,
+                )?)
+            }
+            None => None,
+        };
+
+        // Map links
+        let links = json_node
+            .links
+            .into_iter()
+            .map(|json_link| NodeLink::new(json_link.relation, json_link.path))
+            .collect();
+
         // Map child node names
         let child_node_names = json_node.child_node_names;
 
@@ -122,6 +201,9 @@ impl<T: Seek + Read> JsonReader<T> {
             data,
             metadata,
             table,
+            nd_array,
+            complex_data,
+            links,
             child_node_names,
         })
     }
@@ -134,12 +216,16 @@ mod tests {
         JsonLazyNode, JsonMetadataItem, JsonParameter, JsonTable, JsonTableColumn, JsonValue,
     };
     use sciformats_serde_json::span::Span;
-    use std::{cell::RefCell, collections::HashMap, io::Cursor, rc::Rc};
+    use std::{
+        collections::HashMap,
+        io::Cursor,
+        sync::{Arc, Mutex},
+    };
 
     fn create_sample_json_doc() -> JsonDocument<Cursor<String>> {
         let root_data = r#""data": [{"x": 0, "y": 10.1}, {"x": 1, "y": 1000.01}], "blank": []"#;
         let reader = Cursor::new(root_data.to_owned());
-        let reader_rc: Rc<RefCell<Cursor<String>>> = Rc::new(RefCell::new(reader));
+        let reader_rc: Arc<Mutex<Cursor<String>>> = Arc::new(Mutex::new(reader));
 
         let root_node = JsonLazyNode {
             name: "Root node".to_owned(),
@@ -194,6 +280,9 @@ mod tests {
                     HashMap::from([("col_key1".to_owned(), JsonValue::Number(123.456))]),
                 ],
             }),
+            nd_array: None,
+            complex_data: None,
+            links: vec![],
             children: vec![
                 JsonLazyNode {
                     name: "Nested node 0".to_owned(),
@@ -201,6 +290,9 @@ mod tests {
                     data: Span { span: 64..66 }, // []
                     metadata: vec![],
                     table: None,
+                    nd_array: None,
+                    complex_data: None,
+                    links: vec![],
                     children: vec![],
                 },
                 JsonLazyNode {
@@ -209,19 +301,20 @@ mod tests {
                     data: Span { span: 64..66 }, // []
                     metadata: vec![],
                     table: None,
+                    nd_array: None,
+                    complex_data: None,
+                    links: vec![],
                     children: vec![],
                 },
             ],
         };
 
-        let doc = JsonDocument {
+        JsonDocument {
             format: "sciformats".to_owned(),
             version: "0.1.0".to_owned(),
             nodes: root_node,
             input: reader_rc,
-        };
-
-        doc
+        }
     }
 
     #[test]
@@ -318,5 +411,6 @@ mod tests {
 
         let err = reader.read("/2").unwrap_err();
         assert_eq!(err.to_string(), "Illegal node path: /2");
+        assert_eq!(SfErrorKind::IllegalNodePath, err.kind());
     }
 }
@@ -18,10 +18,10 @@
 // CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
 use crate::{
-    api::{Parser, Reader, Scanner},
+    api::{Confidence, Parser, Reader, Scanner, SeekRead},
     common::SfError,
     json::{json_parser::JsonParser, json_reader::JsonReader},
-    utils::{from_iso_8859_1_cstr, is_recognized_extension},
+    utils::{determine_confidence, from_iso_8859_1_cstr, is_recognized_extension},
 };
 use std::io::{Read, Seek, SeekFrom};
 
@@ -50,26 +50,35 @@ impl JsonScanner {
             Ok(_) => Ok(from_iso_8859_1_cstr(&buf)),
         }
     }
-}
-
-impl<T: Seek + Read + 'static> Scanner<T> for JsonScanner {
-    fn is_recognized(&self, path: &str, input: &mut T) -> bool {
-        if !is_recognized_extension(path, &Self::ACCEPTED_EXTENSIONS) {
-            return false;
-        };
 
+    /// Checks whether the start of the content contains the expected export header.
+    fn is_recognized_content<T: Read + Seek>(input: &mut T) -> bool {
         let start = match Self::read_start(input) {
             Ok(s) => s,
             Err(_) => return false,
         };
 
-        for expected in Self::EXPECTED_STRINGS.iter() {
-            if !start.contains(expected) {
-                return false;
-            }
-        }
+        Self::EXPECTED_STRINGS
+            .iter()
+            .all(|expected| start.contains(expected))
+    }
+}
+
+impl<T: SeekRead + 'static> Scanner<T> for JsonScanner {
+    fn is_recognized(&self, path: &str, input: &mut T) -> bool {
+        is_recognized_extension(path, &Self::ACCEPTED_EXTENSIONS)
+            && Self::is_recognized_content(input)
+    }
 
-        true
+    fn get_confidence(&self, path: &str, input: &mut T) -> Option<Confidence> {
+        determine_confidence(
+            is_recognized_extension(path, &Self::ACCEPTED_EXTENSIONS),
+            Self::is_recognized_content(input),
+        )
+    }
+
+    fn get_name(&self) -> &str {
+        "JSON"
     }
 
     fn get_reader(&self, path: &str, input: T) -> Result<Box<dyn Reader>, SfError> {
@@ -146,7 +155,7 @@ mod tests {
         let mut input = Cursor::new(SINGLE_NODE_JSON);
         let scanner = JsonScanner::new();
 
-        assert_eq!(true, scanner.is_recognized(path, &mut input));
+        assert!(scanner.is_recognized(path, &mut input));
     }
 
     #[test]
@@ -169,6 +178,27 @@ mod tests {
         assert!(!scanner.is_recognized(path, &mut input));
     }
 
+    #[test]
+    fn provides_confidence() {
+        let scanner = JsonScanner::new();
+
+        let mut input = Cursor::new(SINGLE_NODE_JSON);
+        let confidence = scanner.get_confidence("example.json", &mut input);
+        assert_eq!(Some(Confidence::High), confidence);
+
+        let mut input = Cursor::new("{}");
+        let confidence = scanner.get_confidence("example.json", &mut input);
+        assert_eq!(Some(Confidence::Low), confidence);
+
+        let mut input = Cursor::new(SINGLE_NODE_JSON);
+        let confidence = scanner.get_confidence("example.txt", &mut input);
+        assert_eq!(Some(Confidence::Medium), confidence);
+
+        let mut input = Cursor::new("{}");
+        let confidence = scanner.get_confidence("example.txt", &mut input);
+        assert_eq!(None, confidence);
+    }
+
     #[test]
     fn provides_reader_for_valid_json() {
         let path = "example.json";
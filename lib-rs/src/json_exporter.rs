@@ -18,7 +18,10 @@
 // CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
 use crate::{
-    api::{Column, Exporter, Parameter, PointXy, Reader, Table, Value},
+    api::{
+        Axis, Column, ComplexData, Exporter, NdArray, NodeLink, Parameter, PointXy, Reader, Table,
+        Value,
+    },
     common::SfError,
 };
 use serde::{
@@ -126,6 +129,18 @@ impl<R: Reader + ?Sized> Serialize for NodeWrapper<'_, R> {
             // if table is some, serialize it
             serializer.serialize_field("table", table)?;
         };
+        if let Some(nd_array) = &node.nd_array {
+            // if nd_array is some, serialize it
+            serializer.serialize_field("ndArray", nd_array)?;
+        };
+        if let Some(complex_data) = &node.complex_data {
+            // if complex_data is some, serialize it
+            serializer.serialize_field("complexData", complex_data)?;
+        };
+        if !node.links.is_empty() {
+            // only serialize non empty links
+            serializer.serialize_field("links", &node.links)?;
+        };
         let mut child_paths = vec![];
         for (i, _name) in node.child_node_names.iter().enumerate() {
             let child_path = format!("{}/{}", self.path, i);
@@ -251,6 +266,68 @@ impl Serialize for Table {
     }
 }
 
+impl Serialize for NdArray {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut serializer = serializer.serialize_struct("ndArray", 4)?;
+        serializer.serialize_field("name", &self.name)?;
+        if let Some(unit) = &self.unit {
+            serializer.serialize_field("unit", unit)?;
+        }
+        serializer.serialize_field("axes", &self.axes)?;
+        serializer.serialize_field("values", &self.values)?;
+        serializer.end()
+    }
+}
+
+impl Serialize for ComplexData {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut serializer = serializer.serialize_struct("complexData", 7)?;
+        serializer.serialize_field("name", &self.name)?;
+        if let Some(unit) = &self.unit {
+            serializer.serialize_field("unit", unit)?;
+        }
+        serializer.serialize_field("axis", &self.axis)?;
+        serializer.serialize_field("real", &self.real)?;
+        serializer.serialize_field("imaginary", &self.imaginary)?;
+        serializer.serialize_field("magnitude", &self.get_magnitude())?;
+        serializer.serialize_field("phase", &self.get_phase())?;
+        serializer.end()
+    }
+}
+
+impl Serialize for NodeLink {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut serializer = serializer.serialize_struct("link", 2)?;
+        serializer.serialize_field("relation", &self.relation)?;
+        serializer.serialize_field("path", &self.path)?;
+        serializer.end()
+    }
+}
+
+impl Serialize for Axis {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut serializer = serializer.serialize_struct("axis", 3)?;
+        serializer.serialize_field("name", &self.name)?;
+        if let Some(unit) = &self.unit {
+            serializer.serialize_field("unit", unit)?;
+        }
+        serializer.serialize_field("values", &self.values)?;
+        serializer.end()
+    }
+}
+
 impl Serialize for Column {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -272,7 +349,7 @@ impl Serialize for Column {
 mod tests {
     use super::*;
     use crate::{
-        api::{self, Column, ExportFormat, Node, Parameter, Table},
+        api::{self, Axis, Column, ComplexData, ExportFormat, NdArray, Node, Parameter, Table},
         common::SfError,
     };
     use core::str;
@@ -320,6 +397,9 @@ mod tests {
                     ],
                 }),
                 // child_node_names: vec![],
+                nd_array: None,
+                complex_data: None,
+                links: vec![],
                 child_node_names: vec![
                     "child node name 0".to_owned(),
                     "child node name 1".to_owned(),
@@ -331,6 +411,20 @@ mod tests {
                 data: vec![],
                 metadata: vec![],
                 table: None,
+                nd_array: Some(
+                    NdArray::new(
+                        "values name",
+                        Some("values unit".to_owned()),
+                        vec![
+                            Axis::new("axis 0", None, vec![0.0, 1.0]),
+                            Axis::new("axis 1", Some("axis unit".to_owned()), vec![10.0, 20.0]),
+                        ],
+                        vec![1.0, 2.0, 3.0, 4.0],
+                    )
+                    .unwrap(),
+                ),
+                complex_data: None,
+                links: vec![],
                 child_node_names: vec![],
             };
             let child1 = Node {
@@ -339,6 +433,18 @@ mod tests {
                 data: vec![],
                 metadata: vec![],
                 table: None,
+                nd_array: None,
+                complex_data: Some(
+                    ComplexData::new(
+                        "complex name",
+                        None,
+                        Axis::new("axis", Some("axis unit".to_owned()), vec![0.0, 1.0]),
+                        vec![1.0, -1.0],
+                        vec![0.0, 0.0],
+                    )
+                    .unwrap(),
+                ),
+                links: vec![NodeLink::new("related", "/0")],
                 child_node_names: vec![],
             };
 
@@ -346,7 +452,7 @@ mod tests {
                 "" | "/" => Ok(root),
                 "/0" => Ok(child0),
                 "/1" => Ok(child1),
-                _ => Err(SfError::new(&format!("Illegal path: {}", path)).into()),
+                _ => Err(SfError::new(&format!("Illegal path: {}", path))),
             }
         }
     }
@@ -358,7 +464,7 @@ mod tests {
         assert_eq!(0, export.len());
 
         reader.export(ExportFormat::Json, &mut export).unwrap();
-        assert!(export.len() > 0);
+        assert!(!export.is_empty());
 
         // https://docs.rs/serde_json/latest/serde_json/fn.to_value.html#example
         let output_str = String::from_utf8(export).unwrap();
@@ -410,12 +516,30 @@ mod tests {
                         "name": "child node name 0",
                         "parameters": [], "data": [],
                         "metadata": [],
+                        "ndArray": {
+                            "name": "values name",
+                            "unit": "values unit",
+                            "axes": [
+                                {"name": "axis 0", "values": [0.0, 1.0]},
+                                {"name": "axis 1", "unit": "axis unit", "values": [10.0, 20.0]},
+                            ],
+                            "values": [1.0, 2.0, 3.0, 4.0],
+                        },
                         "children": [],
                     },
                     {
                         "name": "child node name 1",
                         "parameters": [], "data": [],
                         "metadata": [],
+                        "complexData": {
+                            "name": "complex name",
+                            "axis": {"name": "axis", "unit": "axis unit", "values": [0.0, 1.0]},
+                            "real": [1.0, -1.0],
+                            "imaginary": [0.0, 0.0],
+                            "magnitude": [1.0, 1.0],
+                            "phase": [0.0, std::f64::consts::PI],
+                        },
+                        "links": [{"relation": "related", "path": "/0"}],
                         "children": [],
                     },
                 ]
@@ -19,10 +19,16 @@
 
 pub mod andi;
 pub mod api;
+pub mod bruker;
 pub mod common;
+pub(crate) mod csv_exporter;
+pub(crate) mod directory_source;
 pub mod gaml;
 pub mod jdx;
+pub mod jeol;
 pub mod json;
 pub(crate) mod json_exporter;
+pub mod nmr_processing;
 pub(crate) mod utils;
+pub mod varian;
 pub(crate) mod xml_utils;
@@ -0,0 +1,896 @@
+// Copyright (c) 2025 Robert Schiwon
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use crate::{
+    api::{Axis, ComplexData, Node, Parameter, PointXy, Reader},
+    common::{SfError, SfErrorKind},
+    jdx::normalize_label,
+    utils::{convert_path_to_node_indices, convert_value_to_f64},
+};
+use std::f64::consts::{LN_2, PI};
+
+/// A window function applied to an FID before Fourier transformation.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Apodization {
+    /// No apodization.
+    #[default]
+    None,
+    /// Exponential multiplication with a line broadening in Hz.
+    Exponential { line_broadening: f64 },
+    /// Gaussian multiplication resulting in a Gaussian line shape with a width in Hz.
+    Gaussian { line_broadening: f64 },
+    /// Sine bell spanning the acquisition time, optionally squared.
+    ///
+    /// The offset shifts the start of the bell as fraction of π, e.g., 0 for
+    /// a sine bell and 0.5 for a cosine bell.
+    SineBell { offset: f64, squared: bool },
+}
+
+impl Apodization {
+    /// Provides the weight for a time relative to the start of the FID.
+    fn get_weight(&self, time: f64, acquisition_time: f64) -> f64 {
+        match *self {
+            Apodization::None => 1.0,
+            Apodization::Exponential { line_broadening } => (-PI * line_broadening * time).exp(),
+            Apodization::Gaussian { line_broadening } => {
+                let x = PI * line_broadening * time;
+                (-x * x / (4.0 * LN_2)).exp()
+            }
+            Apodization::SineBell { offset, squared } => {
+                let fraction = match acquisition_time > 0.0 {
+                    true => time / acquisition_time,
+                    false => 0.0,
+                };
+                let weight = (PI * offset + PI * (1.0 - offset) * fraction).sin();
+                match squared {
+                    true => weight * weight,
+                    false => weight,
+                }
+            }
+        }
+    }
+
+    fn get_description(&self) -> String {
+        match *self {
+            Apodization::None => "NONE".to_owned(),
+            Apodization::Exponential { line_broadening } => {
+                format!("EXPONENTIAL, LB={}", line_broadening)
+            }
+            Apodization::Gaussian { line_broadening } => {
+                format!("GAUSSIAN, GB={}", line_broadening)
+            }
+            Apodization::SineBell { offset, squared } => match squared {
+                true => format!("SQUARED SINE BELL, OFFSET={}", offset),
+                false => format!("SINE BELL, OFFSET={}", offset),
+            },
+        }
+    }
+}
+
+/// Options for processing an NMR FID to a spectrum.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct NmrProcessingOptions {
+    /// The digital filter group delay in points, overriding the acquisition parameters.
+    pub group_delay: Option<f64>,
+    /// The window function.
+    pub apodization: Apodization,
+    /// The minimum number of points after zero filling.
+    ///
+    /// The FID is always zero filled to the next power of two.
+    pub zero_fill_size: Option<usize>,
+    /// The zero-order phase correction in degrees.
+    pub phase_0: f64,
+    /// The first-order phase correction in degrees across the spectrum,
+    /// starting from the first (highest frequency) point.
+    pub phase_1: f64,
+}
+
+/// A chemical shift reference as given by ".SHIFT REFERENCE",
+/// e.g., "(INTERNAL, CDCl3, 1, 7.24)".
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShiftReference {
+    /// The data point number (starting at 1) of the spectrum without zero filling.
+    pub point: u64,
+    /// The chemical shift in ppm at that point.
+    pub shift: f64,
+}
+
+impl ShiftReference {
+    /// Parses a ".SHIFT REFERENCE" value.
+    pub fn parse(value: &str) -> Option<ShiftReference> {
+        let value = value.trim();
+        let value = value.strip_prefix('(').unwrap_or(value);
+        let value = value.strip_suffix(')').unwrap_or(value);
+        let segments: Vec<&str> = value.split(',').map(|s| s.trim()).collect();
+        match segments.as_slice() {
+            [_type, _compound, point, shift] => Some(ShiftReference {
+                point: point.parse().ok()?,
+                shift: shift.parse().ok()?,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Acquisition parameters required for processing an NMR FID.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct NmrAcquisitionParameters {
+    /// The observe frequency in MHz as given by ".OBSERVE FREQUENCY".
+    pub observe_frequency: Option<f64>,
+    /// The chemical shift reference as given by ".SHIFT REFERENCE" or, as the
+    /// shift of the first spectrum point, by Bruker's "$OFFSET".
+    pub shift_reference: Option<ShiftReference>,
+    /// The chemical shift in ppm at the carrier frequency as derived from Bruker's
+    /// "$O1" and "$SF" or Varian's "sfrq" and "reffrq".
+    pub carrier_shift: Option<f64>,
+    /// The digital filter group delay in points, e.g., as given by Bruker's "$GRPDLY".
+    pub group_delay: f64,
+}
+
+impl NmrAcquisitionParameters {
+    const OBSERVE_FREQUENCY_LABEL: &'static str = ".OBSERVEFREQUENCY";
+    const SHIFT_REFERENCE_LABEL: &'static str = ".SHIFTREFERENCE";
+    const GROUP_DELAY_LABEL: &'static str = "$GRPDLY";
+    const OFFSET_LABEL: &'static str = "$OFFSET";
+    const O1_LABEL: &'static str = "$O1";
+    const BF1_LABEL: &'static str = "$BF1";
+    const SF_LABEL: &'static str = "$SF";
+    const SFRQ_LABEL: &'static str = "SFRQ";
+    const REFFRQ_LABEL: &'static str = "REFFRQ";
+
+    /// Extracts the acquisition parameters from node parameters, e.g., of a
+    /// JCAMP-DX block. The first matching parameter takes precedence.
+    ///
+    /// The carrier shift is (BF1 * 10^6 + O1 - SF * 10^6) / SF for Bruker data,
+    /// with BF1 and SF defaulting to each other, and
+    /// (sfrq - reffrq) * 10^6 / reffrq for Varian data.
+    pub fn from_parameters<'a>(
+        parameters: impl IntoIterator<Item = &'a Parameter>,
+    ) -> NmrAcquisitionParameters {
+        let mut acquisition = NmrAcquisitionParameters::default();
+        let mut group_delay = None;
+        let mut offset = None;
+        let (mut o1, mut bf1, mut sf, mut sfrq, mut reffrq) = (None, None, None, None, None);
+        for parameter in parameters {
+            let Parameter::KeyValue(key, value) = parameter else {
+                continue;
+            };
+            match normalize_label(key).as_str() {
+                Self::OBSERVE_FREQUENCY_LABEL if acquisition.observe_frequency.is_none() => {
+                    acquisition.observe_frequency = convert_value_to_f64(value);
+                }
+                Self::SHIFT_REFERENCE_LABEL if acquisition.shift_reference.is_none() => {
+                    acquisition.shift_reference = ShiftReference::parse(&value.to_string());
+                }
+                Self::GROUP_DELAY_LABEL if group_delay.is_none() => {
+                    group_delay = convert_value_to_f64(value);
+                }
+                Self::OFFSET_LABEL if offset.is_none() => {
+                    offset = convert_value_to_f64(value);
+                }
+                Self::O1_LABEL if o1.is_none() => {
+                    o1 = convert_value_to_f64(value);
+                }
+                Self::BF1_LABEL if bf1.is_none() => {
+                    bf1 = convert_value_to_f64(value);
+                }
+                Self::SF_LABEL if sf.is_none() => {
+                    sf = convert_value_to_f64(value);
+                }
+                Self::SFRQ_LABEL if sfrq.is_none() => {
+                    sfrq = convert_value_to_f64(value);
+                }
+                Self::REFFRQ_LABEL if reffrq.is_none() => {
+                    reffrq = convert_value_to_f64(value);
+                }
+                _ => { /* noop */ }
+            }
+        }
+        // negative values, e.g., -1, indicate the absence of a digital filter
+        acquisition.group_delay = group_delay.filter(|g| *g > 0.0).unwrap_or(0.0);
+        if acquisition.shift_reference.is_none() {
+            acquisition.shift_reference = offset.map(|shift| ShiftReference { point: 1, shift });
+        }
+        let sf = sf.or(bf1).filter(|sf| *sf > 0.0);
+        let reffrq = reffrq.filter(|reffrq| *reffrq > 0.0);
+        acquisition.carrier_shift = match (o1, sf, sfrq, reffrq) {
+            (Some(o1), Some(sf), _, _) => Some(((bf1.unwrap_or(sf) - sf) * 1e6 + o1) / sf),
+            (_, _, Some(sfrq), Some(reffrq)) => Some((sfrq - reffrq) * 1e6 / reffrq),
+            _ => None,
+        };
+        acquisition
+    }
+}
+
+/// Processes NMR FIDs to spectra.
+///
+/// The processing consists of digital filter group delay removal, apodization,
+/// zero filling, Fourier transformation, and phase correction. The spectrum
+/// is ordered from high to low frequency with a ppm axis if the observe
+/// frequency and either a shift reference or the carrier shift are known and
+/// an axis of frequency offsets from the carrier in Hz otherwise.
+pub struct NmrProcessor {
+    options: NmrProcessingOptions,
+}
+
+impl NmrProcessor {
+    pub fn new(options: NmrProcessingOptions) -> Self {
+        Self { options }
+    }
+
+    /// Processes the FID of a node, e.g., a JCAMP-DX "NMR FID" NTUPLES node.
+    ///
+    /// The FID is taken from the node's complex data. The acquisition parameters
+    /// are taken from the node and its ancestors, the nearest taking precedence.
+    ///
+    /// The resulting spectrum node can be exported with a `NodeReader`.
+    pub fn process(&self, reader: &dyn Reader, path: &str) -> Result<Node, SfError> {
+        let node = reader.read(path)?;
+        let Some(fid) = &node.complex_data else {
+            return Err(SfError::new(&format!(
+                "No complex FID data found for node: {}",
+                path
+            )));
+        };
+
+        let node_indices = convert_path_to_node_indices(path)?;
+        let mut ancestors = vec![];
+        for depth in (0..node_indices.len()).rev() {
+            let ancestor_path = node_indices[..depth]
+                .iter()
+                .fold(String::new(), |path, index| format!("{}/{}", path, index));
+            ancestors.push(reader.read(&ancestor_path)?);
+        }
+        let parameters = node
+            .parameters
+            .iter()
+            .chain(ancestors.iter().flat_map(|ancestor| &ancestor.parameters));
+        let acquisition = NmrAcquisitionParameters::from_parameters(parameters);
+
+        self.process_fid(fid, &acquisition)
+    }
+
+    /// Processes an FID with the given acquisition parameters.
+    ///
+    /// The FID axis is expected to hold equidistant times in seconds.
+    pub fn process_fid(
+        &self,
+        fid: &ComplexData,
+        acquisition: &NmrAcquisitionParameters,
+    ) -> Result<Node, SfError> {
+        let times = &fid.axis.values;
+        let n_fid = times.len();
+        if n_fid < 2 {
+            return Err(SfError::new(&format!(
+                "Insufficient number of FID points for processing: {}",
+                n_fid
+            )));
+        }
+        let dwell_time = (times[n_fid - 1] - times[0]) / (n_fid - 1) as f64;
+        if !dwell_time.is_finite() || dwell_time <= 0.0 {
+            return Err(
+                SfError::new(&format!("Illegal FID dwell time: {}", dwell_time))
+                    .with_kind(SfErrorKind::CorruptData),
+            );
+        }
+        let group_delay = self.options.group_delay.unwrap_or(acquisition.group_delay);
+        if !(0.0..(n_fid - 1) as f64).contains(&group_delay) {
+            return Err(SfError::new(&format!(
+                "Illegal group delay for {} FID points: {}",
+                n_fid, group_delay
+            )));
+        }
+
+        // remove the points preceding the signal start, the fraction is corrected after FFT
+        let skip = group_delay.floor() as usize;
+        let fraction = group_delay - skip as f64;
+        let mut re = fid.real[skip..].to_vec();
+        let mut im = fid.imaginary[skip..].to_vec();
+
+        // apodization
+        let acquisition_time = (re.len() - 1) as f64 * dwell_time;
+        for (n, (re, im)) in re.iter_mut().zip(im.iter_mut()).enumerate() {
+            let weight = self
+                .options
+                .apodization
+                .get_weight(n as f64 * dwell_time, acquisition_time);
+            *re *= weight;
+            *im *= weight;
+        }
+
+        // zero filling
+        let size = self
+            .options
+            .zero_fill_size
+            .unwrap_or(0)
+            .max(re.len())
+            .next_power_of_two();
+        re.resize(size, 0.0);
+        im.resize(size, 0.0);
+
+        // FFT with zero frequency at the center
+        fft(&mut re, &mut im);
+        re.rotate_left(size / 2);
+        im.rotate_left(size / 2);
+
+        // fractional group delay as linear phase
+        if fraction != 0.0 {
+            for (i, (re, im)) in re.iter_mut().zip(im.iter_mut()).enumerate() {
+                let k = i as f64 - (size / 2) as f64;
+                rotate(re, im, 2.0 * PI * fraction * k / size as f64);
+            }
+        }
+
+        // order from high to low frequency
+        re.reverse();
+        im.reverse();
+
+        // phase correction
+        let phase_0 = self.options.phase_0.to_radians();
+        let phase_1 = self.options.phase_1.to_radians();
+        if phase_0 != 0.0 || phase_1 != 0.0 {
+            for (j, (re, im)) in re.iter_mut().zip(im.iter_mut()).enumerate() {
+                rotate(re, im, phase_0 + phase_1 * j as f64 / size as f64);
+            }
+        }
+
+        // frequency axis, offsets from the carrier in Hz
+        let spectral_width = 1.0 / dwell_time;
+        let get_offset = |j: f64| ((size / 2) as f64 - 1.0 - j) * spectral_width / size as f64;
+        let (axis_name, axis_unit, x_values): (&str, &str, Vec<f64>) =
+            match (acquisition.observe_frequency, acquisition.carrier_shift) {
+                (Some(observe_frequency), carrier_shift)
+                    if observe_frequency > 0.0
+                        && (acquisition.shift_reference.is_some() || carrier_shift.is_some()) =>
+                {
+                    let reference = match acquisition.shift_reference {
+                        None => carrier_shift.unwrap_or_default(),
+                        Some(ShiftReference { point, shift }) => {
+                            // the point refers to a spectrum with as many points as the FID
+                            let j = (point.max(1) - 1) as f64 * size as f64 / n_fid as f64;
+                            shift - get_offset(j) / observe_frequency
+                        }
+                    };
+                    let x_values = (0..size)
+                        .map(|j| get_offset(j as f64) / observe_frequency + reference)
+                        .collect();
+                    ("CHEMICAL SHIFT", "PPM", x_values)
+                }
+                _ => {
+                    let x_values = (0..size).map(|j| get_offset(j as f64)).collect();
+                    ("FREQUENCY OFFSET", "HZ", x_values)
+                }
+            };
+
+        let mut parameters = vec![
+            Parameter::from_str_f64("Group Delay", group_delay),
+            Parameter::from_str_str("Apodization", self.options.apodization.get_description()),
+            Parameter::from_str_u64("Size", size as u64),
+            Parameter::from_str_f64("Phase 0", self.options.phase_0),
+            Parameter::from_str_f64("Phase 1", self.options.phase_1),
+            Parameter::from_str_f64("Spectral Width", spectral_width),
+        ];
+        if let Some(observe_frequency) = acquisition.observe_frequency {
+            parameters.push(Parameter::from_str_f64(
+                "Observe Frequency",
+                observe_frequency,
+            ));
+        }
+
+        let data = x_values
+            .iter()
+            .zip(&re)
+            .map(|(x, y)| PointXy::new(*x, *y))
+            .collect();
+        let mut metadata = vec![
+            ("x.label".to_owned(), axis_name.to_owned()),
+            ("x.unit".to_owned(), axis_unit.to_owned()),
+        ];
+        if let Some(unit) = &fid.unit {
+            metadata.push(("y.unit".to_owned(), unit.to_owned()));
+        }
+        let complex_data = ComplexData::new(
+            &fid.name,
+            fid.unit.clone(),
+            Axis::new(axis_name, Some(axis_unit.to_owned()), x_values),
+            re,
+            im,
+        )?;
+
+        Ok(Node {
+            name: "NMR SPECTRUM".to_owned(),
+            parameters,
+            data,
+            metadata,
+            table: None,
+            nd_array: None,
+            complex_data: Some(complex_data),
+            links: vec![],
+            child_node_names: vec![],
+        })
+    }
+}
+
+/// Maps an FID of interleaved real and imaginary values to a node with complex
+/// data and a time axis, e.g., for Bruker and Varian raw data.
+///
+/// A trailing real value without imaginary counterpart is ignored.
+pub(crate) fn map_interleaved_fid(
+    name: &str,
+    values: &[f64],
+    dwell_time: f64,
+) -> Result<Node, SfError> {
+    let (real, imaginary): (Vec<f64>, Vec<f64>) = values
+        .chunks_exact(2)
+        .map(|pair| (pair[0], pair[1]))
+        .unzip();
+    let times: Vec<f64> = (0..real.len()).map(|i| i as f64 * dwell_time).collect();
+    let data = times
+        .iter()
+        .zip(&real)
+        .map(|(x, y)| PointXy::new(*x, *y))
+        .collect();
+    let complex_data = ComplexData::new(
+        "FID",
+        None,
+        Axis::new("TIME", Some("SECONDS".to_owned()), times),
+        real,
+        imaginary,
+    )?;
+
+    Ok(Node {
+        name: name.to_owned(),
+        parameters: vec![],
+        data,
+        metadata: vec![
+            ("x.unit".to_owned(), "SECONDS".to_owned()),
+            ("x.label".to_owned(), "Time".to_owned()),
+        ],
+        table: None,
+        nd_array: None,
+        complex_data: Some(complex_data),
+        links: vec![],
+        child_node_names: vec![],
+    })
+}
+
+/// Rotates a complex value by an angle in radians.
+fn rotate(re: &mut f64, im: &mut f64, angle: f64) {
+    let (sin, cos) = angle.sin_cos();
+    let (r, i) = (*re, *im);
+    *re = r * cos - i * sin;
+    *im = r * sin + i * cos;
+}
+
+/// Computes the discrete Fourier transform in place.
+///
+/// The length of the values needs to be a power of two.
+fn fft(re: &mut [f64], im: &mut [f64]) {
+    let n = re.len();
+    // bit reversal permutation
+    let mut j = 0usize;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+    // butterflies
+    let mut len = 2usize;
+    while len <= n {
+        let angle = -2.0 * PI / len as f64;
+        for start in (0..n).step_by(len) {
+            for k in 0..len / 2 {
+                let (w_im, w_re) = (angle * k as f64).sin_cos();
+                let a = start + k;
+                let b = a + len / 2;
+                let t_re = re[b] * w_re - im[b] * w_im;
+                let t_im = re[b] * w_im + im[b] * w_re;
+                re[b] = re[a] - t_re;
+                im[b] = im[a] - t_im;
+                re[a] += t_re;
+                im[a] += t_im;
+            }
+        }
+        len <<= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        api::{ExportFormat, Parser, SeekBufRead},
+        common::NodeReader,
+        jdx::{jdx_parser::JdxParser, jdx_reader::JdxReader},
+    };
+    use std::io::Cursor;
+
+    const EPSILON: f64 = 1e-9;
+
+    /// Creates an FID of a signal at an offset frequency in Hz.
+    fn create_fid(offset: f64, dwell_time: f64, n_points: usize, delay: f64) -> ComplexData {
+        let times: Vec<f64> = (0..n_points).map(|n| n as f64 * dwell_time).collect();
+        let phases: Vec<f64> = (0..n_points)
+            .map(|n| 2.0 * PI * offset * (n as f64 - delay) * dwell_time)
+            .collect();
+        ComplexData::new(
+            "FID",
+            Some("ARBITRARY UNITS".to_owned()),
+            Axis::new("TIME", Some("SECONDS".to_owned()), times),
+            phases.iter().map(|p| p.cos()).collect(),
+            phases.iter().map(|p| p.sin()).collect(),
+        )
+        .unwrap()
+    }
+
+    fn find_max_index(values: &[f64]) -> usize {
+        (0..values.len())
+            .max_by(|a, b| values[*a].total_cmp(&values[*b]))
+            .unwrap()
+    }
+
+    #[test]
+    fn fft_transforms_complex_exponential_to_single_bin() {
+        let n = 8;
+        let mut re: Vec<f64> = (0..n)
+            .map(|i| (2.0 * PI * 3.0 * i as f64 / n as f64).cos())
+            .collect();
+        let mut im: Vec<f64> = (0..n)
+            .map(|i| (2.0 * PI * 3.0 * i as f64 / n as f64).sin())
+            .collect();
+
+        fft(&mut re, &mut im);
+
+        for i in 0..n {
+            let expected = if i == 3 { n as f64 } else { 0.0 };
+            assert!((re[i] - expected).abs() < EPSILON);
+            assert!(im[i].abs() < EPSILON);
+        }
+    }
+
+    #[test]
+    fn processes_fid_to_spectrum_with_ppm_axis() {
+        // 1000 Hz spectral width, 64 points, signal at +125 Hz, i.e., 8 points from center
+        let fid = create_fid(125.0, 0.001, 64, 0.0);
+        let acquisition = NmrAcquisitionParameters {
+            observe_frequency: Some(100.0),
+            carrier_shift: Some(5.0),
+            ..Default::default()
+        };
+        let processor = NmrProcessor::new(NmrProcessingOptions::default());
+
+        let spectrum = processor.process_fid(&fid, &acquisition).unwrap();
+
+        assert_eq!("NMR SPECTRUM", spectrum.name);
+        assert_eq!(64, spectrum.data.len());
+        assert!(
+            spectrum
+                .metadata
+                .contains(&("x.unit".to_owned(), "PPM".to_owned()))
+        );
+        // descending frequencies
+        assert!(spectrum.data[0].x > spectrum.data[63].x);
+        let complex_data = spectrum.complex_data.unwrap();
+        let peak = find_max_index(&complex_data.real);
+        assert_eq!(23, peak);
+        assert!((6.25 - spectrum.data[peak].x).abs() < EPSILON);
+        assert!((64.0 - complex_data.real[peak]).abs() < EPSILON);
+        assert!(complex_data.imaginary[peak].abs() < EPSILON);
+        assert_eq!(complex_data.real[peak], spectrum.data[peak].y);
+    }
+
+    #[test]
+    fn processes_fid_to_spectrum_with_hz_axis_without_reference() {
+        let fid = create_fid(125.0, 0.001, 64, 0.0);
+        let processor = NmrProcessor::new(NmrProcessingOptions::default());
+        let unreferenced = NmrAcquisitionParameters {
+            observe_frequency: Some(100.0),
+            ..Default::default()
+        };
+
+        for acquisition in [NmrAcquisitionParameters::default(), unreferenced] {
+            let spectrum = processor.process_fid(&fid, &acquisition).unwrap();
+
+            assert!(
+                spectrum
+                    .metadata
+                    .contains(&("x.label".to_owned(), "FREQUENCY OFFSET".to_owned()))
+            );
+            assert!(
+                spectrum
+                    .metadata
+                    .contains(&("x.unit".to_owned(), "HZ".to_owned()))
+            );
+            assert!((500.0 - 15.625 - spectrum.data[0].x).abs() < EPSILON);
+            assert!((125.0 - spectrum.data[23].x).abs() < EPSILON);
+        }
+    }
+
+    #[test]
+    fn applies_shift_reference() {
+        let fid = create_fid(125.0, 0.001, 64, 0.0);
+        let acquisition = NmrAcquisitionParameters {
+            observe_frequency: Some(100.0),
+            shift_reference: ShiftReference::parse("(INTERNAL, TMS, 1, 10.0)"),
+            ..Default::default()
+        };
+        let processor = NmrProcessor::new(NmrProcessingOptions {
+            zero_fill_size: Some(128),
+            ..Default::default()
+        });
+
+        let spectrum = processor.process_fid(&fid, &acquisition).unwrap();
+
+        assert_eq!(128, spectrum.data.len());
+        assert!((10.0 - spectrum.data[0].x).abs() < EPSILON);
+        // 1000 Hz spectral width at 100 MHz spans 10 ppm
+        assert!((10.0 - 10.0 * 127.0 / 128.0 - spectrum.data[127].x).abs() < EPSILON);
+    }
+
+    #[test]
+    fn removes_integer_and_fractional_group_delay() {
+        for group_delay in [2.0, 2.5] {
+            let fid = create_fid(125.0, 0.001, 66, group_delay);
+            let processor = NmrProcessor::new(NmrProcessingOptions {
+                group_delay: Some(group_delay),
+                ..Default::default()
+            });
+
+            let spectrum = processor
+                .process_fid(&fid, &NmrAcquisitionParameters::default())
+                .unwrap();
+
+            assert_eq!(64, spectrum.data.len());
+            let complex_data = spectrum.complex_data.unwrap();
+            let magnitude = complex_data.get_magnitude();
+            let peak = find_max_index(&magnitude);
+            assert_eq!(23, peak);
+            // pure absorption, i.e., no residual phase at the peak
+            assert!(complex_data.real[peak] > 0.0);
+            assert!(complex_data.get_phase()[peak].abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn applies_zero_and_first_order_phase_correction() {
+        let fid = create_fid(125.0, 0.001, 64, 0.0);
+        let processor = NmrProcessor::new(NmrProcessingOptions {
+            phase_0: 90.0,
+            ..Default::default()
+        });
+
+        let complex_data = processor
+            .process_fid(&fid, &NmrAcquisitionParameters::default())
+            .unwrap()
+            .complex_data
+            .unwrap();
+
+        assert!(complex_data.real[23].abs() < EPSILON);
+        assert!((64.0 - complex_data.imaginary[23]).abs() < EPSILON);
+
+        let processor = NmrProcessor::new(NmrProcessingOptions {
+            phase_0: 45.0,
+            phase_1: -45.0 * 64.0 / 23.0,
+            ..Default::default()
+        });
+
+        let complex_data = processor
+            .process_fid(&fid, &NmrAcquisitionParameters::default())
+            .unwrap()
+            .complex_data
+            .unwrap();
+
+        assert!((64.0 - complex_data.real[23]).abs() < 1e-6);
+        assert!(complex_data.imaginary[23].abs() < 1e-6);
+    }
+
+    #[test]
+    fn applies_apodization() {
+        assert_eq!(1.0, Apodization::None.get_weight(0.5, 1.0));
+
+        let exponential = Apodization::Exponential {
+            line_broadening: 1.0,
+        };
+        assert_eq!(1.0, exponential.get_weight(0.0, 1.0));
+        assert!(((-PI).exp() - exponential.get_weight(1.0, 1.0)).abs() < EPSILON);
+
+        let gaussian = Apodization::Gaussian {
+            line_broadening: 1.0,
+        };
+        assert_eq!(1.0, gaussian.get_weight(0.0, 1.0));
+        assert!(gaussian.get_weight(1.0, 1.0) < gaussian.get_weight(0.5, 1.0));
+
+        let sine_bell = Apodization::SineBell {
+            offset: 0.0,
+            squared: false,
+        };
+        assert!(sine_bell.get_weight(0.0, 1.0).abs() < EPSILON);
+        assert!((1.0 - sine_bell.get_weight(0.5, 1.0)).abs() < EPSILON);
+        assert!(sine_bell.get_weight(1.0, 1.0).abs() < EPSILON);
+
+        let cosine_bell_squared = Apodization::SineBell {
+            offset: 0.5,
+            squared: true,
+        };
+        assert!((1.0 - cosine_bell_squared.get_weight(0.0, 1.0)).abs() < EPSILON);
+        assert!((0.5 - cosine_bell_squared.get_weight(0.5, 1.0)).abs() < EPSILON);
+
+        // apodization of the FID broadens the line and reduces the peak height
+        let fid = create_fid(125.0, 0.001, 64, 0.0);
+        let processor = NmrProcessor::new(NmrProcessingOptions {
+            apodization: exponential,
+            ..Default::default()
+        });
+        let spectrum = processor
+            .process_fid(&fid, &NmrAcquisitionParameters::default())
+            .unwrap();
+        assert!(spectrum.data[23].y < 64.0);
+        assert!(
+            spectrum
+                .parameters
+                .contains(&Parameter::from_str_str("Apodization", "EXPONENTIAL, LB=1"))
+        );
+    }
+
+    #[test]
+    fn fails_processing_with_illegal_group_delay() {
+        let fid = create_fid(125.0, 0.001, 8, 0.0);
+        let processor = NmrProcessor::new(NmrProcessingOptions {
+            group_delay: Some(8.0),
+            ..Default::default()
+        });
+        assert!(
+            processor
+                .process_fid(&fid, &NmrAcquisitionParameters::default())
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn extracts_acquisition_parameters() {
+        let parameters = [
+            Parameter::from_str_str(".OBSERVEFREQUENCY", "400.13"),
+            Parameter::from_str_str(".SHIFT REFERENCE", "(INTERNAL, CDCl3, 1, 12.5)"),
+            Parameter::from_str_str("$GRPDLY", "-1"),
+            Parameter::from_str_str(".OBSERVEFREQUENCY", "100.0"),
+        ];
+
+        let acquisition = NmrAcquisitionParameters::from_parameters(&parameters);
+
+        assert_eq!(Some(400.13), acquisition.observe_frequency);
+        assert_eq!(
+            Some(ShiftReference {
+                point: 1,
+                shift: 12.5
+            }),
+            acquisition.shift_reference
+        );
+        assert_eq!(0.0, acquisition.group_delay);
+        assert_eq!(None, acquisition.carrier_shift);
+        assert_eq!(None, ShiftReference::parse("(INTERNAL, CDCl3, 12.5)"));
+
+        let bruker_parameters = [
+            Parameter::from_str_f64("$BF1", 400.13),
+            Parameter::from_str_f64("$O1", 2400.78),
+            Parameter::from_str_f64("$SF", 400.13),
+            Parameter::from_str_f64("$OFFSET", 12.0),
+        ];
+
+        let acquisition = NmrAcquisitionParameters::from_parameters(&bruker_parameters);
+
+        assert!((6.0 - acquisition.carrier_shift.unwrap()).abs() < EPSILON);
+        assert_eq!(
+            Some(ShiftReference {
+                point: 1,
+                shift: 12.0
+            }),
+            acquisition.shift_reference
+        );
+
+        let varian_parameters = [
+            Parameter::from_str_f64("sfrq", 399.8023988),
+            Parameter::from_str_f64("reffrq", 399.8),
+        ];
+
+        let acquisition = NmrAcquisitionParameters::from_parameters(&varian_parameters);
+
+        assert!((6.0 - acquisition.carrier_shift.unwrap()).abs() < 1e-6);
+        assert_eq!(None, acquisition.shift_reference);
+    }
+
+    #[test]
+    fn maps_interleaved_fid_to_node_with_time_axis() {
+        let node = map_interleaved_fid("FID", &[1.0, 2.0, 3.0, 4.0, 5.0], 0.5).unwrap();
+
+        assert_eq!("FID", node.name);
+        assert_eq!(
+            vec![PointXy::new(0.0, 1.0), PointXy::new(0.5, 3.0)],
+            node.data
+        );
+        let complex_data = node.complex_data.unwrap();
+        assert_eq!(vec![0.0, 0.5], complex_data.axis.values);
+        assert_eq!(vec![1.0, 3.0], complex_data.real);
+        assert_eq!(vec![2.0, 4.0], complex_data.imaginary);
+    }
+
+    #[test]
+    fn processes_jdx_nmr_fid_and_exports_spectrum() {
+        // 1000 Hz spectral width, signal at +250 Hz
+        let input = b"##TITLE= NMR FID\n\
+                                    ##JCAMP-DX= 5.01\n\
+                                    ##DATA TYPE= NMR FID\n\
+                                    ##DATA CLASS= NTUPLES\n\
+                                    ##ORIGIN= Test\n\
+                                    ##OWNER= Test\n\
+                                    ##.OBSERVE FREQUENCY= 400.002\n\
+                                    ##$BF1= 400.0\n\
+                                    ##$O1= 2000.0\n\
+                                    ##$SF= 400.0\n\
+                                    ##$GRPDLY= 0\n\
+                                    ##NTUPLES= NMR FID\n\
+                                    ##VAR_NAME= TIME, FID/REAL, FID/IMAG, PAGE NUMBER\n\
+                                    ##SYMBOL= X, R, I, N\n\
+                                    ##VAR_TYPE= INDEPENDENT, DEPENDENT, DEPENDENT, PAGE\n\
+                                    ##VAR_FORM= AFFN, AFFN, AFFN, AFFN\n\
+                                    ##VAR_DIM= 8, 8, 8, 2\n\
+                                    ##UNITS= SECONDS, ARBITRARY UNITS, ARBITRARY UNITS,\n\
+                                    ##FIRST= 0.0, 1.0, 0.0, 1\n\
+                                    ##LAST= 0.007, 0.0, -1.0, 2\n\
+                                    ##FACTOR= 1.0, 1.0, 1.0, 1\n\
+                                    ##PAGE= N=1\n\
+                                    ##DATA TABLE= (X++(R..R)), XYDATA\n\
+                                    0.0 1 0 -1 0\n\
+                                    0.004 1 0 -1 0\n\
+                                    ##PAGE= N=2\n\
+                                    ##DATA TABLE= (X++(I..I)), XYDATA\n\
+                                    0.0 0 1 0 -1\n\
+                                    0.004 0 1 0 -1\n\
+                                    ##END NTUPLES= NMR FID\n\
+                                    ##END=";
+        let path = "nmr_fid.jdx";
+        let buf_input: Box<dyn SeekBufRead> = Box::new(Cursor::new(input));
+        let file = JdxParser::parse(path, buf_input).unwrap();
+        let reader = JdxReader::new(path, file);
+        let processor = NmrProcessor::new(NmrProcessingOptions::default());
+
+        let spectrum = processor.process(&reader, "/0").unwrap();
+
+        assert_eq!(8, spectrum.data.len());
+        // carrier at 5 ppm, offset of 250 Hz at 400 MHz
+        assert!((5.625 - spectrum.data[1].x).abs() < 1e-5);
+        assert!((8.0 - spectrum.data[1].y).abs() < EPSILON);
+        assert!(processor.process(&reader, "/").is_err());
+
+        let spectrum_reader = NodeReader::new(spectrum);
+        let mut export = vec![];
+        spectrum_reader
+            .export(ExportFormat::Json, &mut export)
+            .unwrap();
+        let export = String::from_utf8(export).unwrap();
+        assert!(export.contains("\"name\":\"NMR SPECTRUM\""));
+        assert!(export.contains("\"complexData\""));
+    }
+}
@@ -17,8 +17,15 @@
 // IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
 // CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
-use crate::common::SfError;
-use std::path::Path;
+use crate::{
+    api::{Confidence, Value},
+    common::{SfError, SfErrorKind},
+};
+use std::{
+    ops::Range,
+    path::Path,
+    sync::{Mutex, MutexGuard, PoisonError},
+};
 
 // -------------------------------------------------
 // Util functions
@@ -39,6 +46,21 @@ pub(crate) fn is_recognized_extension(path: &str, accepted_extensions: &[&str])
     }
 }
 
+/// Determine the confidence of recognizing a data set from its extension and content
+///
+/// A matching content outweighs a matching extension.
+pub(crate) fn determine_confidence(
+    is_recognized_extension: bool,
+    is_recognized_content: bool,
+) -> Option<Confidence> {
+    match (is_recognized_extension, is_recognized_content) {
+        (true, true) => Some(Confidence::High),
+        (false, true) => Some(Confidence::Medium),
+        (true, false) => Some(Confidence::Low),
+        (false, false) => None,
+    }
+}
+
 /// Convert path to indices
 ///
 /// The path segments are expected to be separated by forward slashes.
@@ -72,15 +94,62 @@ pub(crate) fn convert_path_to_node_indices(path: &str) -> Result<Vec<usize>, SfE
     let mut indices: Vec<usize> = vec![];
     for seg in path_segments {
         let idx_str = seg.split_once('-').map_or(seg, |p| p.0);
-        let idx = idx_str
-            .parse::<usize>()
-            .map_err(|e| SfError::from_source(e, "Error parsing path indices."))?;
+        let idx = idx_str.parse::<usize>().map_err(|e| {
+            SfError::from_source(e, "Error parsing path indices.")
+                .with_kind(SfErrorKind::IllegalNodePath)
+        })?;
         indices.push(idx);
     }
 
     Ok(indices)
 }
 
+/// Check that a range of data point indices lies within the available data points
+pub(crate) fn check_data_range(range: &Range<usize>, len: usize) -> Result<(), SfError> {
+    if range.start > range.end || range.end > len {
+        return Err(SfError::new(&format!(
+            "Illegal data range: {}..{}. Number of data points: {}",
+            range.start, range.end, len
+        ))
+        .with_kind(SfErrorKind::IllegalDataRange));
+    }
+    Ok(())
+}
+
+/// Extract a range of data points
+pub(crate) fn extract_data_range<T>(
+    mut data: Vec<T>,
+    range: &Range<usize>,
+) -> Result<Vec<T>, SfError> {
+    check_data_range(range, data.len())?;
+    data.truncate(range.end);
+    data.drain(..range.start);
+    Ok(data)
+}
+
+/// Convert a parameter value to f64, parsing strings
+pub(crate) fn convert_value_to_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::I32(v) => Some(f64::from(*v)),
+        Value::U32(v) => Some(f64::from(*v)),
+        Value::I64(v) => Some(*v as f64),
+        Value::U64(v) => Some(*v as f64),
+        Value::F32(v) => Some(f64::from(*v)),
+        Value::F64(v) => Some(*v),
+        Value::String(v) => v.trim().parse().ok(),
+        Value::Bool(_) => None,
+    }
+}
+
+/// Locks a shared input.
+///
+/// A poisoned lock is recovered as all reads of a shared input start by seeking
+/// to a known position, so a panic in another thread cannot leave it in a state
+/// that affects subsequent reads.
+pub(crate) fn lock_input<T: ?Sized>(input: &Mutex<T>) -> MutexGuard<'_, T> {
+    input.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
 /// Convert UTF-8 C string to String
 #[allow(dead_code)]
 pub(crate) fn convert_utf8_cstr_to_str(bytes: &[u8]) -> String {
@@ -0,0 +1,395 @@
+// Copyright (c) 2025 Robert Schiwon
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use crate::{
+    api::{Column, Parameter, Table, Value},
+    common::SfError,
+};
+use std::{collections::HashMap, fmt};
+
+/// A typed value of a Varian parameter.
+#[derive(Debug, Clone, PartialEq)]
+pub enum VarianValue {
+    /// An integral value of an integer parameter, e.g., "np".
+    I64(i64),
+    /// A real value, e.g., "sw".
+    F64(f64),
+    /// A string value without the enclosing quotes, e.g., "H1" for "\"H1\"".
+    String(String),
+}
+
+impl VarianValue {
+    /// The value as floating point number if numeric.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            VarianValue::I64(value) => Some(*value as f64),
+            VarianValue::F64(value) => Some(*value),
+            VarianValue::String(_) => None,
+        }
+    }
+
+    /// The value as string if a string.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            VarianValue::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Maps the value to a parameter value.
+    pub fn to_value(&self) -> Value {
+        match self {
+            VarianValue::I64(value) => Value::I64(*value),
+            VarianValue::F64(value) => Value::F64(*value),
+            VarianValue::String(value) => Value::String(value.clone()),
+        }
+    }
+}
+
+impl fmt::Display for VarianValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            VarianValue::I64(value) => write!(f, "{}", value),
+            VarianValue::F64(value) => write!(f, "{}", value),
+            VarianValue::String(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+/// A Varian parameter from a "procpar" file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VarianParameter {
+    /// The parameter name, e.g., "sfrq".
+    pub name: String,
+    /// The subtype, e.g., 1 for real, 2 for string, 4 for flag, 5 for frequency, 6 for pulse,
+    /// and 7 for integer.
+    pub subtype: u32,
+    /// Whether the parameter is active.
+    pub active: bool,
+    /// The values, more than one for arrayed parameters.
+    pub values: Vec<VarianValue>,
+    /// The allowed values if enumerated.
+    pub enumeration: Vec<VarianValue>,
+}
+
+impl VarianParameter {
+    const BASIC_TYPE_STRING: u32 = 2;
+    const SUBTYPE_INTEGER: u32 = 7;
+}
+
+/// Parses a "procpar" file.
+///
+/// Each parameter consists of a line with the name followed by ten attributes, e.g., subtype,
+/// basic type, and active flag, the number of values followed by the values, and the number
+/// of enumerated values followed by those values. String values are enclosed in double quotes
+/// and written one per line.
+pub fn parse_procpar(content: &str) -> Result<Vec<VarianParameter>, SfError> {
+    const NUM_ATTRIBUTES: usize = 10;
+    // active flag index among the attributes
+    const ACTIVE_INDEX: usize = 8;
+
+    let mut tokens = tokenize(content)?.into_iter().peekable();
+    let mut parameters = vec![];
+    while let Some(token) = tokens.next() {
+        let Token::Word(name) = token else {
+            return Err(SfError::new(&format!(
+                "Illegal procpar parameter name: {:?}",
+                token
+            )));
+        };
+        let mut attributes = vec![];
+        for _ in 0..NUM_ATTRIBUTES {
+            match tokens.next() {
+                Some(Token::Word(word)) => attributes.push(word),
+                _ => {
+                    return Err(SfError::new(&format!(
+                        "Missing procpar attributes for: {}",
+                        name
+                    )));
+                }
+            }
+        }
+        let parse_attribute = |index: usize| -> Result<u32, SfError> {
+            // some attributes, e.g., maximum value, are reals
+            attributes[index]
+                .parse::<f64>()
+                .map(|v| v as u32)
+                .map_err(|e| {
+                    SfError::from_source(
+                        e,
+                        format!(
+                            "Illegal procpar attribute for {}: {}",
+                            name, attributes[index]
+                        ),
+                    )
+                })
+        };
+        let subtype = parse_attribute(0)?;
+        let is_string = parse_attribute(1)? == VarianParameter::BASIC_TYPE_STRING;
+        let active = parse_attribute(ACTIVE_INDEX)? != 0;
+
+        let mut read_values = |kind: &str| -> Result<Vec<VarianValue>, SfError> {
+            let count = match tokens.next() {
+                Some(Token::Word(word)) => word.parse::<usize>().ok(),
+                _ => None,
+            }
+            .ok_or(SfError::new(&format!(
+                "Missing procpar {} count for: {}",
+                kind, name
+            )))?;
+            let mut values = Vec::with_capacity(count);
+            for _ in 0..count {
+                let value = match (tokens.next(), is_string) {
+                    (Some(Token::Quoted(value)), true) => VarianValue::String(value),
+                    (Some(Token::Word(word)), false) => parse_real(&word, subtype).ok_or(
+                        SfError::new(&format!("Illegal procpar value for {}: {}", name, word)),
+                    )?,
+                    (token, _) => {
+                        return Err(SfError::new(&format!(
+                            "Illegal procpar {} for {}: {:?}",
+                            kind, name, token
+                        )));
+                    }
+                };
+                values.push(value);
+            }
+            Ok(values)
+        };
+        let values = read_values("values")?;
+        let enumeration = read_values("enumeration")?;
+
+        parameters.push(VarianParameter {
+            name,
+            subtype,
+            active,
+            values,
+            enumeration,
+        });
+    }
+
+    Ok(parameters)
+}
+
+/// Finds a parameter by its name.
+pub fn find_parameter<'p>(
+    name: &str,
+    parameters: &'p [VarianParameter],
+) -> Option<&'p VarianParameter> {
+    parameters.iter().find(|parameter| parameter.name == name)
+}
+
+/// Finds the first value of a parameter by its name.
+pub fn find_value<'p>(name: &str, parameters: &'p [VarianParameter]) -> Option<&'p VarianValue> {
+    find_parameter(name, parameters).and_then(|parameter| parameter.values.first())
+}
+
+/// Maps Varian parameters to node parameters and a table holding the values of arrayed
+/// parameters.
+///
+/// Arrayed parameters are provided as parameters with their values joined by blanks and
+/// additionally as one table row per value with the parameter name, index starting at 1, and
+/// value. The table is None if there are no arrayed parameters.
+pub fn map_parameters(parameters: &[VarianParameter]) -> (Vec<Parameter>, Option<Table>) {
+    let mut node_parameters = vec![];
+    let mut rows = vec![];
+    for parameter in parameters {
+        let value = match parameter.values.as_slice() {
+            [value] => value.to_value(),
+            values => {
+                for (i, value) in values.iter().enumerate() {
+                    rows.push(HashMap::from([
+                        (
+                            "parameter".to_owned(),
+                            Value::String(parameter.name.clone()),
+                        ),
+                        ("index".to_owned(), Value::U64(i as u64 + 1)),
+                        ("value".to_owned(), value.to_value()),
+                    ]));
+                }
+                let values: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+                Value::String(values.join(" "))
+            }
+        };
+        node_parameters.push(Parameter::KeyValue(parameter.name.clone(), value));
+    }
+    let table = (!rows.is_empty()).then(|| Table {
+        column_names: vec![
+            Column::new("parameter", "Parameter"),
+            Column::new("index", "Index"),
+            Column::new("value", "Value"),
+        ],
+        rows,
+    });
+
+    (node_parameters, table)
+}
+
+fn parse_real(word: &str, subtype: u32) -> Option<VarianValue> {
+    let value = word.parse::<f64>().ok()?;
+    if subtype == VarianParameter::SUBTYPE_INTEGER
+        && value.fract() == 0.0
+        && value.abs() < i64::MAX as f64
+    {
+        return Some(VarianValue::I64(value as i64));
+    }
+    Some(VarianValue::F64(value))
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Word(String),
+    Quoted(String),
+}
+
+/// Splits content into blank separated words and double quoted strings, the latter possibly
+/// containing escaped quotes and backslashes.
+fn tokenize(content: &str) -> Result<Vec<Token>, SfError> {
+    let mut tokens = vec![];
+    let mut chars = content.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '"' {
+            chars.next();
+            let mut value = String::new();
+            loop {
+                match chars.next() {
+                    None => return Err(SfError::new("Unterminated string in procpar.")),
+                    Some('"') => break,
+                    Some('\\') => {
+                        if let Some(escaped) = chars.next() {
+                            value.push(escaped);
+                        }
+                    }
+                    Some(c) => value.push(c),
+                }
+            }
+            tokens.push(Token::Quoted(value));
+        } else {
+            let mut word = String::new();
+            while let Some(&c) = chars.peek()
+                && !c.is_whitespace()
+            {
+                word.push(c);
+                chars.next();
+            }
+            tokens.push(Token::Word(word));
+        }
+    }
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROCPAR: &str = "array 2 2 1023 0 0 2 1 0 1 64\n\
+                           1 \"d1\"\n\
+                           0\n\
+                           d1 3 1 8190 0 1e-07 2 1 0 1 64\n\
+                           3 0.5 1 2.5\n\
+                           0\n\
+                           np 7 1 1e+09 32 2 2 1 0 1 64\n\
+                           1 16384\n\
+                           0\n\
+                           tn 2 2 8 0 0 2 1 0 1 64\n\
+                           1 \"H1\"\n\
+                           0\n\
+                           comment 2 2 256 0 0 2 1 0 0 64\n\
+                           2 \"first \\\"line\\\"\"\n\
+                           \"second\"\n\
+                           0\n\
+                           dp 4 2 1 0 0 2 1 8 1 64\n\
+                           1 \"y\"\n\
+                           2 \"y\" \"n\"\n";
+
+    #[test]
+    fn parses_procpar() {
+        let parameters = parse_procpar(PROCPAR).unwrap();
+
+        assert_eq!(6, parameters.len());
+        assert_eq!(
+            VarianParameter {
+                name: "d1".to_owned(),
+                subtype: 3,
+                active: true,
+                values: vec![
+                    VarianValue::F64(0.5),
+                    VarianValue::F64(1.0),
+                    VarianValue::F64(2.5)
+                ],
+                enumeration: vec![],
+            },
+            parameters[1]
+        );
+        assert_eq!(
+            Some(&VarianValue::I64(16384)),
+            find_value("np", &parameters)
+        );
+        assert_eq!(Some("H1"), find_value("tn", &parameters).unwrap().as_str());
+        let comment = find_parameter("comment", &parameters).unwrap();
+        assert!(!comment.active);
+        assert_eq!(
+            vec![
+                VarianValue::String("first \"line\"".to_owned()),
+                VarianValue::String("second".to_owned())
+            ],
+            comment.values
+        );
+        assert_eq!(
+            vec![
+                VarianValue::String("y".to_owned()),
+                VarianValue::String("n".to_owned())
+            ],
+            find_parameter("dp", &parameters).unwrap().enumeration
+        );
+    }
+
+    #[test]
+    fn fails_parsing_malformed_procpar() {
+        assert!(parse_procpar("np 7 1 1e+09 32 2 2 1 0 1 64\n2 16384\n0\n").is_err());
+        assert!(parse_procpar("np 7 1 1e+09\n").is_err());
+        assert!(parse_procpar("tn 2 2 8 0 0 2 1 0 1 64\n1 \"H1\n0\n").is_err());
+        assert!(parse_procpar("tn 2 2 8 0 0 2 1 0 1 64\n1 H1\n0\n").is_err());
+    }
+
+    #[test]
+    fn maps_parameters_and_array_table() {
+        let parameters = parse_procpar(PROCPAR).unwrap();
+
+        let (node_parameters, table) = map_parameters(&parameters);
+
+        assert_eq!(6, node_parameters.len());
+        assert_eq!(
+            Parameter::from_str_str("d1", "0.5 1 2.5"),
+            node_parameters[1]
+        );
+        assert_eq!(Parameter::from_str_i64("np", 16384), node_parameters[2]);
+        let table = table.unwrap();
+        assert_eq!(5, table.rows.len());
+        assert_eq!(
+            HashMap::from([
+                ("parameter".to_owned(), Value::String("d1".to_owned())),
+                ("index".to_owned(), Value::U64(3)),
+                ("value".to_owned(), Value::F64(2.5)),
+            ]),
+            table.rows[2]
+        );
+    }
+}
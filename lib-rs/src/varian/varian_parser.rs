@@ -0,0 +1,660 @@
+// Copyright (c) 2025 Robert Schiwon
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use super::varian_parameters::{VarianParameter, find_parameter, parse_procpar};
+use crate::{
+    api::{ParseOptions, ParseWarning, Parser, SeekRead},
+    common::{SfError, SfErrorKind, WarningCollector},
+    directory_source::DirectorySource,
+};
+use std::path::{Path, PathBuf};
+
+pub struct VarianParser {}
+
+impl<T: SeekRead + 'static> Parser<T> for VarianParser {
+    type R = VarianDataSet;
+    type E = SfError;
+
+    /// Parses a ZIP archive holding a Varian ".fid" directory.
+    fn parse(name: &str, input: T) -> Result<Self::R, Self::E> {
+        Self::parse_with_options(name, input, &ParseOptions::default())
+    }
+}
+
+impl VarianParser {
+    /// Parses a ZIP archive holding a Varian ".fid" directory with the given options.
+    ///
+    /// In strict mode, an odd number of values per trace and data following the last block
+    /// of the "fid" file result in an error.
+    pub fn parse_with_options<T: SeekRead + 'static>(
+        name: &str,
+        input: T,
+        options: &ParseOptions,
+    ) -> Result<VarianDataSet, SfError> {
+        let input: Box<dyn SeekRead> = Box::new(input);
+        let source = DirectorySource::open_zip(input, VarianDataSet::PARAMETERS)?.ok_or(
+            SfError::new(&format!(
+                "No Varian data set (procpar) found in ZIP archive: {}",
+                name
+            ))
+            .with_kind(SfErrorKind::UnsupportedFormat),
+        )?;
+        let file_name = Path::new(name)
+            .file_name()
+            .and_then(|file_name| file_name.to_str())
+            .unwrap_or(name);
+        VarianDataSet::new(file_name, source, options)
+    }
+
+    /// Parses a Varian ".fid" directory in the file system.
+    ///
+    /// The path may also point to the "fid" or "procpar" file inside the directory.
+    pub fn parse_directory(path: &str, options: &ParseOptions) -> Result<VarianDataSet, SfError> {
+        let directory = find_fid_directory(Path::new(path)).ok_or(
+            SfError::new(&format!("No Varian fid directory found: {}", path))
+                .with_kind(SfErrorKind::UnsupportedFormat),
+        )?;
+        let name = directory
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or(path)
+            .to_owned();
+        VarianDataSet::new(&name, DirectorySource::Directory(directory), options)
+    }
+}
+
+/// Finds the ".fid" directory, i.e., the directory holding "fid" and "procpar", for a path
+/// pointing to the directory itself or to a file in it.
+pub(crate) fn find_fid_directory(path: &Path) -> Option<PathBuf> {
+    let directory = if path.is_dir() { path } else { path.parent()? };
+    (directory.join(VarianDataSet::DATA).is_file()
+        && directory.join(VarianDataSet::PARAMETERS).is_file())
+    .then(|| directory.to_path_buf())
+}
+
+/// The number format of the data in a "fid" file as indicated by the file header status.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VarianDataType {
+    /// 16 bit integers.
+    I16,
+    /// 32 bit integers.
+    I32,
+    /// 32 bit floating point numbers.
+    F32,
+}
+
+impl VarianDataType {
+    fn get_size(&self) -> usize {
+        match self {
+            VarianDataType::I16 => 2,
+            VarianDataType::I32 | VarianDataType::F32 => 4,
+        }
+    }
+}
+
+/// The header at the start of a "fid" file. All values are big endian.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileHeader {
+    /// The number of blocks.
+    pub nblocks: i32,
+    /// The number of traces per block.
+    pub ntraces: i32,
+    /// The number of values per trace, i.e., twice the number of complex points.
+    pub np: i32,
+    /// The number of bytes per value.
+    pub ebytes: i32,
+    /// The number of bytes per trace.
+    pub tbytes: i32,
+    /// The number of bytes per block, including the block headers.
+    pub bbytes: i32,
+    /// The software version.
+    pub vers_id: i16,
+    /// The status bits, e.g., indicating the data type.
+    pub status: i16,
+    /// The number of block headers per block.
+    pub nbheaders: i32,
+}
+
+impl FileHeader {
+    const SIZE: usize = 32;
+    const S_32: i16 = 0x4;
+    const S_FLOAT: i16 = 0x8;
+
+    fn parse(bytes: &[u8]) -> Result<Self, SfError> {
+        if bytes.len() < Self::SIZE {
+            return Err(SfError::new(&format!(
+                "Varian fid file too short for file header: {} bytes",
+                bytes.len()
+            )));
+        }
+        Ok(Self {
+            nblocks: read_i32(bytes, 0),
+            ntraces: read_i32(bytes, 4),
+            np: read_i32(bytes, 8),
+            ebytes: read_i32(bytes, 12),
+            tbytes: read_i32(bytes, 16),
+            bbytes: read_i32(bytes, 20),
+            vers_id: read_i16(bytes, 24),
+            status: read_i16(bytes, 26),
+            nbheaders: read_i32(bytes, 28),
+        })
+    }
+
+    /// The data type as indicated by the status bits.
+    pub fn get_data_type(&self) -> VarianDataType {
+        if self.status & Self::S_FLOAT != 0 {
+            VarianDataType::F32
+        } else if self.status & Self::S_32 != 0 {
+            VarianDataType::I32
+        } else {
+            VarianDataType::I16
+        }
+    }
+
+    /// Checks that the sizes are consistent with each other and the data type.
+    fn validate(&self) -> Result<(), SfError> {
+        let data_type = self.get_data_type();
+        let is_consistent = self.nblocks >= 0
+            && self.ntraces >= 0
+            && self.np >= 0
+            && self.nbheaders >= 0
+            && self.ebytes as usize == data_type.get_size()
+            && self.tbytes as i64 == self.np as i64 * self.ebytes as i64
+            && self.bbytes as i64
+                == self.ntraces as i64 * self.tbytes as i64
+                    + self.nbheaders as i64 * BlockHeader::SIZE as i64;
+        match is_consistent {
+            true => Ok(()),
+            false => Err(SfError::new(&format!(
+                "Inconsistent Varian fid file header: {:?}",
+                self
+            ))),
+        }
+    }
+}
+
+/// The header at the start of each block of a "fid" file. All values are big endian.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockHeader {
+    /// The scaling factor.
+    pub scale: i16,
+    /// The status bits.
+    pub status: i16,
+    /// The block index.
+    pub index: i16,
+    /// The mode bits.
+    pub mode: i16,
+    /// The number of completed transients.
+    pub ctcount: i32,
+    /// The left phase.
+    pub lpval: f32,
+    /// The right phase.
+    pub rpval: f32,
+    /// The level drift correction.
+    pub lvl: f32,
+    /// The tilt drift correction.
+    pub tlt: f32,
+}
+
+impl BlockHeader {
+    const SIZE: usize = 28;
+
+    fn parse(bytes: &[u8]) -> Self {
+        Self {
+            scale: read_i16(bytes, 0),
+            status: read_i16(bytes, 2),
+            index: read_i16(bytes, 4),
+            mode: read_i16(bytes, 6),
+            ctcount: read_i32(bytes, 8),
+            lpval: f32::from_bits(read_i32(bytes, 12) as u32),
+            rpval: f32::from_bits(read_i32(bytes, 16) as u32),
+            lvl: f32::from_bits(read_i32(bytes, 20) as u32),
+            tlt: f32::from_bits(read_i32(bytes, 24) as u32),
+        }
+    }
+}
+
+fn read_i16(bytes: &[u8], offset: usize) -> i16 {
+    i16::from_be_bytes(bytes[offset..offset + 2].try_into().unwrap())
+}
+
+fn read_i32(bytes: &[u8], offset: usize) -> i32 {
+    i32::from_be_bytes(bytes[offset..offset + 4].try_into().unwrap())
+}
+
+/// A Varian/Agilent VnmrJ data set, i.e., a ".fid" directory holding a "fid" file with the
+/// raw data and a "procpar" file with the parameters.
+///
+/// The blocks of the "fid" file are read on demand.
+pub struct VarianDataSet {
+    /// The name of the data set, e.g., the ".fid" directory or ZIP file name.
+    pub name: String,
+    /// The parameters from "procpar".
+    pub parameters: Vec<VarianParameter>,
+    /// The header of the "fid" file.
+    pub file_header: FileHeader,
+    /// The content of the "text" file if present.
+    pub text: Option<String>,
+    /// The deviations from the format accepted while parsing.
+    pub warnings: Vec<ParseWarning>,
+
+    source: DirectorySource,
+}
+
+impl VarianDataSet {
+    const DATA: &'static str = "fid";
+    const TEXT: &'static str = "text";
+    pub(crate) const PARAMETERS: &'static str = "procpar";
+
+    fn new(name: &str, source: DirectorySource, options: &ParseOptions) -> Result<Self, SfError> {
+        let missing_file = |file_name: &str| {
+            SfError::new(&format!(
+                "Missing \"{}\" in Varian data set: {}",
+                file_name, name
+            ))
+            .with_kind(SfErrorKind::CorruptData)
+        };
+        // procpar is ISO 8859-1 encoded
+        let procpar: String = source
+            .read_file(Self::PARAMETERS)?
+            .ok_or_else(|| missing_file(Self::PARAMETERS))?
+            .iter()
+            .map(|&b| b as char)
+            .collect();
+        let parameters =
+            parse_procpar(&procpar).map_err(|e| e.or_kind(SfErrorKind::CorruptData))?;
+        let data_len = source
+            .get_file_len(Self::DATA)?
+            .ok_or_else(|| missing_file(Self::DATA))?;
+        let header_bytes = source
+            .read_file_range(Self::DATA, 0..(FileHeader::SIZE as u64).min(data_len))?
+            .ok_or_else(|| missing_file(Self::DATA))?;
+        let file_header =
+            FileHeader::parse(&header_bytes).map_err(|e| e.or_kind(SfErrorKind::CorruptData))?;
+        file_header
+            .validate()
+            .map_err(|e| e.or_kind(SfErrorKind::CorruptData))?;
+        let required_len = (file_header.nblocks as u64)
+            .checked_mul(file_header.bbytes as u64)
+            .and_then(|len| len.checked_add(FileHeader::SIZE as u64));
+        if required_len.is_none_or(|required_len| data_len < required_len) {
+            return Err(SfError::new(&format!(
+                "Varian fid file too short for {} blocks of {} bytes: {} bytes",
+                file_header.nblocks, file_header.bbytes, data_len
+            ))
+            .with_kind(SfErrorKind::CorruptData));
+        }
+        if (file_header.nblocks as usize)
+            .checked_mul(file_header.ntraces as usize)
+            .is_none()
+        {
+            return Err(SfError::new(&format!(
+                "Illegal number of Varian traces: {} blocks of {} traces",
+                file_header.nblocks, file_header.ntraces
+            ))
+            .with_kind(SfErrorKind::CorruptData));
+        }
+
+        let mut collector = WarningCollector::new(options);
+        if file_header.np % 2 != 0 {
+            collector.report(
+                &format!(
+                    "Odd number of values per Varian trace \"np\": {}",
+                    file_header.np
+                ),
+                None,
+            );
+        }
+        if let Some(required_len) = required_len
+            && data_len > required_len
+        {
+            collector.report(
+                &format!(
+                    "Varian fid file with {} bytes following the last block",
+                    data_len - required_len
+                ),
+                None,
+            );
+        }
+        let warnings = collector.into_warnings()?;
+
+        let text = source
+            .read_file(Self::TEXT)?
+            .map(|bytes| bytes.iter().map(|&b| b as char).collect::<String>())
+            .map(|text| text.trim().to_owned())
+            .filter(|text| !text.is_empty());
+
+        Ok(Self {
+            name: name.to_owned(),
+            parameters,
+            file_header,
+            text,
+            warnings,
+            source,
+        })
+    }
+
+    /// The number of traces, i.e., the number of blocks times the number of traces per block.
+    pub fn get_num_traces(&self) -> usize {
+        self.file_header.nblocks as usize * self.file_header.ntraces as usize
+    }
+
+    /// The offset of a position within a block in the "fid" file.
+    ///
+    /// The file length has been checked to hold all blocks when parsing the file header.
+    fn get_block_offset(&self, block: usize, position: u64) -> Result<u64, SfError> {
+        (block as u64)
+            .checked_mul(self.file_header.bbytes as u64)
+            .and_then(|offset| offset.checked_add(FileHeader::SIZE as u64))
+            .and_then(|offset| offset.checked_add(position))
+            .ok_or(
+                SfError::new(&format!("Illegal Varian block offset: {}", block))
+                    .with_kind(SfErrorKind::CorruptData),
+            )
+    }
+
+    /// Reads a range of the "fid" file.
+    fn read_data(&self, offset: u64, len: u64) -> Result<Vec<u8>, SfError> {
+        self.source
+            .read_file_range(Self::DATA, offset..offset + len)?
+            .ok_or(
+                SfError::new(&format!("Missing \"{}\" in Varian data set", Self::DATA))
+                    .with_kind(SfErrorKind::CorruptData),
+            )
+    }
+
+    /// Reads the first header of a block.
+    ///
+    /// Returns None if the blocks have no headers.
+    pub fn read_block_header(&self, block: usize) -> Result<Option<BlockHeader>, SfError> {
+        if block >= self.file_header.nblocks as usize {
+            return Err(SfError::new(&format!(
+                "Illegal Varian block index: {}",
+                block
+            )));
+        }
+        if self.file_header.nbheaders == 0 {
+            return Ok(None);
+        }
+        let offset = self.get_block_offset(block, 0)?;
+        let bytes = self.read_data(offset, BlockHeader::SIZE as u64)?;
+        Ok(Some(BlockHeader::parse(&bytes)))
+    }
+
+    /// Reads a trace of interleaved real and imaginary values by its index, counting the
+    /// traces of all blocks.
+    pub fn read_trace(&self, index: usize) -> Result<Vec<f64>, SfError> {
+        let header = &self.file_header;
+        if index >= self.get_num_traces() {
+            return Err(SfError::new(&format!(
+                "Illegal Varian trace index: {}",
+                index
+            )));
+        }
+        let ntraces = header.ntraces as usize;
+        let (block, trace) = (index / ntraces, index % ntraces);
+        let position = header.nbheaders as u64 * BlockHeader::SIZE as u64
+            + trace as u64 * header.tbytes as u64;
+        let offset = self.get_block_offset(block, position)?;
+        let bytes = self.read_data(offset, header.tbytes as u64)?;
+        let data_type = header.get_data_type();
+        let values = bytes
+            .chunks_exact(data_type.get_size())
+            .map(|chunk| match data_type {
+                VarianDataType::I16 => i16::from_be_bytes(chunk.try_into().unwrap()) as f64,
+                VarianDataType::I32 => i32::from_be_bytes(chunk.try_into().unwrap()) as f64,
+                VarianDataType::F32 => f32::from_be_bytes(chunk.try_into().unwrap()) as f64,
+            })
+            .collect();
+        Ok(values)
+    }
+
+    /// Determines the values of the arrayed parameters for a block.
+    ///
+    /// The arrayed parameters are listed in the "array" parameter, e.g., "pw,d1" or "(pw,p1),d1"
+    /// with parameters in parentheses being arrayed jointly. The last listed parameter varies
+    /// fastest. The array is repeated for the blocks of further dimensions, e.g., the
+    /// increments of a 2D experiment.
+    pub fn get_arrayed_values(&self, block: usize) -> Vec<(&VarianParameter, usize)> {
+        let Some(array) = find_parameter("array", &self.parameters)
+            .and_then(|parameter| parameter.values.first())
+            .and_then(|value| value.as_str())
+        else {
+            return vec![];
+        };
+        let mut groups = vec![];
+        for group in split_array_groups(array) {
+            let parameters: Vec<&VarianParameter> = group
+                .iter()
+                .filter_map(|name| find_parameter(name, &self.parameters))
+                .collect();
+            let len = parameters.first().map_or(0, |p| p.values.len());
+            if len == 0 || parameters.iter().any(|p| p.values.len() != len) {
+                // inconsistent array, ignore
+                return vec![];
+            }
+            groups.push((parameters, len));
+        }
+        let total: usize = groups.iter().map(|(_, len)| len).product();
+        if total == 0 {
+            return vec![];
+        }
+
+        let mut remainder = block % total;
+        let mut indices = vec![0; groups.len()];
+        for (i, (_, len)) in groups.iter().enumerate().rev() {
+            indices[i] = remainder % len;
+            remainder /= len;
+        }
+        groups
+            .iter()
+            .zip(indices)
+            .flat_map(|((parameters, _), index)| parameters.iter().map(move |p| (*p, index)))
+            .collect()
+    }
+}
+
+/// Splits an "array" parameter value into groups of jointly arrayed parameter names, e.g.,
+/// "(pw,p1),d1" into [["pw", "p1"], ["d1"]].
+fn split_array_groups(array: &str) -> Vec<Vec<String>> {
+    let mut groups = vec![];
+    let mut group: Option<Vec<String>> = None;
+    for token in array.split(',').map(str::trim) {
+        let (start, name) = match token.strip_prefix('(') {
+            Some(name) => (true, name),
+            None => (false, token),
+        };
+        let (end, name) = match name.strip_suffix(')') {
+            Some(name) => (true, name),
+            None => (false, name),
+        };
+        let name = name.trim().to_owned();
+        match (&mut group, start, end) {
+            (None, true, false) => group = Some(vec![name]),
+            (Some(names), _, false) => names.push(name),
+            (Some(names), _, true) => {
+                names.push(name);
+                groups.push(group.take().unwrap());
+            }
+            (None, _, _) if !name.is_empty() => groups.push(vec![name]),
+            (None, _, _) => {}
+        }
+    }
+    if let Some(names) = group {
+        groups.push(names);
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::directory_source::create_zip;
+
+    const PROCPAR: &str = "array 2 2 1023 0 0 2 1 0 1 64\n\
+                           1 \"(pw,p1),d1\"\n\
+                           0\n\
+                           d1 3 1 8190 0 1e-07 2 1 0 1 64\n\
+                           3 0.5 1 2.5\n\
+                           0\n\
+                           p1 6 1 8190 0 0.0125 2 1 0 1 64\n\
+                           2 10 20\n\
+                           0\n\
+                           pw 6 1 8190 0 0.0125 2 1 0 1 64\n\
+                           2 5 6\n\
+                           0\n";
+
+    /// Creates a fid file with 16 bit integer traces of the given values.
+    fn create_fid(nblocks: i32, ntraces: i32, values: &[i16]) -> Vec<u8> {
+        let np = values.len() as i32 / (nblocks * ntraces);
+        let mut fid = vec![];
+        for value in [nblocks, ntraces, np, 2, np * 2, ntraces * np * 2 + 28] {
+            fid.extend(value.to_be_bytes());
+        }
+        fid.extend(0i16.to_be_bytes()); // vers_id
+        fid.extend(0x1i16.to_be_bytes()); // status, data
+        fid.extend(1i32.to_be_bytes()); // nbheaders
+        for (block, block_values) in values.chunks((ntraces * np) as usize).enumerate() {
+            let mut header = vec![0u8; 28];
+            header[4..6].copy_from_slice(&(block as i16 + 1).to_be_bytes());
+            header[8..12].copy_from_slice(&(8i32).to_be_bytes());
+            fid.extend(header);
+            for value in block_values {
+                fid.extend(value.to_be_bytes());
+            }
+        }
+        fid
+    }
+
+    #[test]
+    fn parses_headers_and_traces() {
+        let fid = create_fid(2, 2, &[1, -1, 2, -2, 3, -3, 4, -4]);
+        let zip = create_zip(&[("s.fid/procpar", PROCPAR.as_bytes()), ("s.fid/fid", &fid)]);
+
+        let data_set = VarianParser::parse("s.zip", zip).unwrap();
+
+        assert_eq!("s.zip", data_set.name);
+        assert_eq!(VarianDataType::I16, data_set.file_header.get_data_type());
+        assert_eq!(2, data_set.file_header.np);
+        assert_eq!(4, data_set.get_num_traces());
+        let block_header = data_set.read_block_header(1).unwrap().unwrap();
+        assert_eq!(2, block_header.index);
+        assert_eq!(8, block_header.ctcount);
+        assert!(data_set.read_block_header(2).is_err());
+        assert_eq!(vec![3.0, -3.0], data_set.read_trace(2).unwrap());
+        assert!(data_set.read_trace(4).is_err());
+    }
+
+    #[test]
+    fn determines_arrayed_values() {
+        let fid = create_fid(12, 1, &[0; 24]);
+        let zip = create_zip(&[("procpar", PROCPAR.as_bytes()), ("fid", &fid)]);
+        let data_set = VarianParser::parse("s.zip", zip).unwrap();
+
+        let map_values = |block: usize| -> Vec<(String, usize)> {
+            data_set
+                .get_arrayed_values(block)
+                .into_iter()
+                .map(|(parameter, index)| (parameter.name.clone(), index))
+                .collect()
+        };
+        assert_eq!(
+            vec![
+                ("pw".to_owned(), 0),
+                ("p1".to_owned(), 0),
+                ("d1".to_owned(), 0)
+            ],
+            map_values(0)
+        );
+        assert_eq!(
+            vec![
+                ("pw".to_owned(), 1),
+                ("p1".to_owned(), 1),
+                ("d1".to_owned(), 1)
+            ],
+            map_values(4)
+        );
+        // repeated for further dimensions
+        assert_eq!(map_values(2), map_values(8));
+    }
+
+    #[test]
+    fn splits_array_groups() {
+        assert_eq!(vec![vec!["d1"]], split_array_groups("d1"));
+        assert_eq!(
+            vec![vec!["pw", "p1"], vec!["d1"]],
+            split_array_groups("(pw, p1),d1")
+        );
+        assert!(split_array_groups("").is_empty());
+    }
+
+    #[test]
+    fn fails_parsing_inconsistent_fid() {
+        let mut fid = create_fid(1, 1, &[1, 2]);
+        // ebytes
+        fid[15] = 4;
+        let zip = create_zip(&[("procpar", PROCPAR.as_bytes()), ("fid", &fid)]);
+        let error = VarianParser::parse("s.zip", zip).err().unwrap();
+        assert_eq!(SfErrorKind::CorruptData, error.kind());
+
+        let fid = create_fid(1, 1, &[1, 2]);
+        let zip = create_zip(&[
+            ("procpar", PROCPAR.as_bytes()),
+            ("fid", &fid[..fid.len() - 1]),
+        ]);
+        let error = VarianParser::parse("s.zip", zip).err().unwrap();
+        assert!(error.to_string().contains("too short"));
+
+        // nblocks
+        let mut fid = create_fid(1, 1, &[1, 2]);
+        fid[0..4].copy_from_slice(&i32::MAX.to_be_bytes());
+        let zip = create_zip(&[("procpar", PROCPAR.as_bytes()), ("fid", &fid)]);
+        let error = VarianParser::parse("s.zip", zip).err().unwrap();
+        assert_eq!(SfErrorKind::CorruptData, error.kind());
+        assert!(error.to_string().contains("too short"));
+
+        let zip = create_zip(&[("procpar", PROCPAR.as_bytes())]);
+        let error = VarianParser::parse("s.zip", zip).err().unwrap();
+        assert!(error.to_string().contains("Missing \"fid\""));
+    }
+    #[test]
+    fn reports_deviations_in_lenient_mode_only() {
+        let mut fid = create_fid(1, 1, &[1, 2, 3]);
+        fid.extend([0u8; 2]);
+        let zip = || create_zip(&[("procpar", PROCPAR.as_bytes()), ("fid", &fid)]);
+
+        let data_set = VarianParser::parse("s.zip", zip()).unwrap();
+        assert_eq!(
+            vec![
+                "Odd number of values per Varian trace \"np\": 3",
+                "Varian fid file with 2 bytes following the last block",
+            ],
+            data_set
+                .warnings
+                .iter()
+                .map(|w| w.message.as_str())
+                .collect::<Vec<_>>()
+        );
+
+        let error = VarianParser::parse_with_options("s.zip", zip(), &ParseOptions::strict())
+            .err()
+            .unwrap();
+        assert_eq!(SfErrorKind::CorruptData, error.kind());
+    }
+}
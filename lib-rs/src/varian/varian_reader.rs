@@ -0,0 +1,206 @@
+// Copyright (c) 2025 Robert Schiwon
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use super::{
+    varian_parameters::{find_value, map_parameters},
+    varian_parser::VarianDataSet,
+};
+use crate::{
+    api::{Node, Parameter, ParseWarning, Reader},
+    common::{SfError, SfErrorKind},
+    nmr_processing::map_interleaved_fid,
+    utils::convert_path_to_node_indices,
+};
+
+/// A reader for Varian/Agilent VnmrJ ".fid" directories.
+///
+/// The root node holds the parameters from "procpar" and has one child node per trace of the
+/// "fid" file, e.g., one per arrayed experiment or 2D increment. Trace nodes hold the values
+/// of the arrayed parameters for their block.
+pub struct VarianReader {
+    path: String,
+    data_set: VarianDataSet,
+}
+
+impl Reader for VarianReader {
+    fn read(&self, path: &str) -> Result<Node, SfError> {
+        self.read_node(path)
+            .map_err(|e| e.or_kind(SfErrorKind::CorruptData))
+    }
+
+    fn get_warnings(&self) -> &[ParseWarning] {
+        &self.data_set.warnings
+    }
+}
+
+impl VarianReader {
+    pub fn new(path: &str, data_set: VarianDataSet) -> Self {
+        Self {
+            path: path.to_owned(),
+            data_set,
+        }
+    }
+
+    fn read_node(&self, path: &str) -> Result<Node, SfError> {
+        let node_indices = convert_path_to_node_indices(path)?;
+        match node_indices[..] {
+            [] => Ok(self.map_root()),
+            [index] if index < self.data_set.get_num_traces() => self.map_trace(index),
+            _ => Err(SfError::new(&format!("Illegal node path: {}", path))
+                .with_kind(SfErrorKind::IllegalNodePath)),
+        }
+    }
+
+    fn get_trace_name(&self, index: usize) -> String {
+        match self.data_set.get_num_traces() {
+            1 => "FID".to_owned(),
+            _ => format!("FID {}", index + 1),
+        }
+    }
+
+    fn map_root(&self) -> Node {
+        let (mut parameters, table) = map_parameters(&self.data_set.parameters);
+        if let Some(text) = &self.data_set.text {
+            parameters.insert(0, Parameter::from_str_str("text", text));
+        }
+
+        Node {
+            name: self.data_set.name.clone(),
+            parameters,
+            data: vec![],
+            metadata: vec![],
+            table,
+            nd_array: None,
+            complex_data: None,
+            links: vec![],
+            child_node_names: (0..self.data_set.get_num_traces())
+                .map(|index| self.get_trace_name(index))
+                .collect(),
+        }
+    }
+
+    /// Maps a trace of interleaved real and imaginary values to complex data with a time axis.
+    ///
+    /// The dwell time is derived from the spectral width "sw".
+    fn map_trace(&self, index: usize) -> Result<Node, SfError> {
+        let parameters = &self.data_set.parameters;
+        let dwell_time = find_value("sw", parameters)
+            .and_then(|v| v.as_f64())
+            .filter(|sw| *sw > 0.0)
+            .map(|sw| 1.0 / sw)
+            .ok_or(SfError::new(&format!(
+                "Missing or illegal spectral width \"sw\" for: {}",
+                self.path
+            )))?;
+        let trace = self.data_set.read_trace(index)?;
+        let mut node = map_interleaved_fid(&self.get_trace_name(index), &trace, dwell_time)?;
+
+        let block = index / self.data_set.file_header.ntraces as usize;
+        let mut node_parameters = vec![
+            Parameter::from_str_u64("block", block as u64 + 1),
+            Parameter::from_str_u64(
+                "trace",
+                (index % self.data_set.file_header.ntraces as usize) as u64 + 1,
+            ),
+        ];
+        if let Some(block_header) = self.data_set.read_block_header(block)? {
+            node_parameters.push(Parameter::from_str_i64(
+                "ctcount",
+                block_header.ctcount as i64,
+            ));
+        }
+        for (parameter, value_index) in self.data_set.get_arrayed_values(block) {
+            node_parameters.push(Parameter::KeyValue(
+                parameter.name.clone(),
+                parameter.values[value_index].to_value(),
+            ));
+        }
+        if let Some(sfrq) = find_value("sfrq", parameters).and_then(|v| v.as_f64()) {
+            node_parameters.push(Parameter::from_str_f64(".OBSERVE FREQUENCY", sfrq));
+        }
+        node.parameters = node_parameters;
+        Ok(node)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        api::{Parser, PointXy},
+        directory_source::create_zip,
+        varian::varian_parser::VarianParser,
+    };
+
+    fn create_reader(procpar: &str, fid_values: &[f32]) -> VarianReader {
+        let np = fid_values.len() as i32;
+        let mut fid = vec![];
+        for value in [1, 1, np, 4, np * 4, np * 4 + 28] {
+            fid.extend(value.to_be_bytes());
+        }
+        fid.extend(0i16.to_be_bytes());
+        // data, float
+        fid.extend(0x9i16.to_be_bytes());
+        fid.extend(1i32.to_be_bytes());
+        fid.extend([0u8; 28]);
+        for value in fid_values {
+            fid.extend(value.to_be_bytes());
+        }
+        let cursor = create_zip(&[("procpar", procpar.as_bytes()), ("fid", &fid)]);
+        let data_set = VarianParser::parse("test.zip", cursor).unwrap();
+        VarianReader::new("test.zip", data_set)
+    }
+
+    #[test]
+    fn maps_single_fid() {
+        let procpar = "sw 1 1 1e+06 0 0 2 1 0 1 64\n1 2000\n0\n\
+                       sfrq 5 1 1e+06 0 0 2 1 0 1 64\n1 399.8\n0\n";
+        let reader = create_reader(procpar, &[1.5, -0.5, 1.0, 0.25]);
+
+        let root = reader.read("/").unwrap();
+        assert_eq!("test.zip", root.name);
+        assert_eq!(vec!["FID"], root.child_node_names);
+        assert!(root.table.is_none());
+
+        let fid = reader.read("/0").unwrap();
+        assert_eq!("FID", fid.name);
+        assert_eq!(
+            vec![PointXy::new(0.0, 1.5), PointXy::new(0.0005, 1.0)],
+            fid.data
+        );
+        assert_eq!(vec![-0.5, 0.25], fid.complex_data.unwrap().imaginary);
+        assert!(
+            fid.parameters
+                .contains(&Parameter::from_str_f64(".OBSERVE FREQUENCY", 399.8))
+        );
+        assert_eq!(
+            SfErrorKind::IllegalNodePath,
+            reader.read("/1").unwrap_err().kind()
+        );
+    }
+
+    #[test]
+    fn fails_reading_fid_without_spectral_width() {
+        let reader = create_reader("", &[1.0, 2.0]);
+
+        let error = reader.read("/0").unwrap_err();
+        assert_eq!(SfErrorKind::CorruptData, error.kind());
+        assert!(error.to_string().contains("\"sw\""));
+    }
+}
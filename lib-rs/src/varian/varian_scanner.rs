@@ -0,0 +1,159 @@
+// Copyright (c) 2025 Robert Schiwon
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use super::{
+    varian_parser::{VarianDataSet, VarianParser, find_fid_directory},
+    varian_reader::VarianReader,
+};
+use crate::{
+    api::{Confidence, ParseOptions, Reader, Scanner, SeekRead},
+    common::SfError,
+    directory_source::DirectoryScanner,
+};
+
+/// A scanner for Varian/Agilent VnmrJ ".fid" directories.
+///
+/// Data sets are recognized as ZIP archives holding a ".fid" directory. With the
+/// `read_directories` parse option set, they are also recognized as paths in the file system
+/// pointing to a ".fid" directory or a file in it. For the latter, the input is ignored and the
+/// files are read from the directory.
+#[derive(Default)]
+pub struct VarianScanner {
+    options: ParseOptions,
+}
+
+impl VarianScanner {
+    const DIRECTORY_SCANNER: DirectoryScanner = DirectoryScanner {
+        marker_file: VarianDataSet::PARAMETERS,
+        find_directory: find_fid_directory,
+        read_directory: |path, options| {
+            let data_set = VarianParser::parse_directory(path, options)?;
+            Ok(Box::new(VarianReader::new(path, data_set)))
+        },
+        read_zip: |path, input, options| {
+            let data_set = VarianParser::parse_with_options(path, input, options)?;
+            Ok(Box::new(VarianReader::new(path, data_set)))
+        },
+    };
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<T: SeekRead + 'static> Scanner<T> for VarianScanner {
+    fn is_recognized(&self, path: &str, input: &mut T) -> bool {
+        Self::DIRECTORY_SCANNER.is_recognized(path, input, &self.options)
+    }
+
+    fn get_confidence(&self, path: &str, input: &mut T) -> Option<Confidence> {
+        Self::DIRECTORY_SCANNER.get_confidence(path, input, &self.options)
+    }
+
+    fn get_name(&self) -> &str {
+        "Varian"
+    }
+
+    fn set_parse_options(&mut self, options: &ParseOptions) {
+        self.options = options.clone();
+    }
+
+    fn get_reader(&self, path: &str, input: T) -> Result<Box<dyn Reader>, SfError> {
+        Self::DIRECTORY_SCANNER.get_reader(path, input, &self.options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::directory_source::create_zip;
+    use std::io::Cursor;
+
+    #[test]
+    fn provides_confidence() {
+        let scanner = VarianScanner::new();
+        let data_set = [("s.fid/procpar", b"".as_slice()), ("s.fid/fid", b"")];
+        let other = [("1/acqus", b"".as_slice())];
+
+        let mut input = create_zip(&data_set);
+        let confidence = scanner.get_confidence("valid.zip", &mut input);
+        assert_eq!(Some(Confidence::High), confidence);
+
+        let mut input = create_zip(&other);
+        let confidence = scanner.get_confidence("invalid.zip", &mut input);
+        assert_eq!(Some(Confidence::Low), confidence);
+
+        let mut input = create_zip(&data_set);
+        let confidence = scanner.get_confidence("valid.bin", &mut input);
+        assert_eq!(Some(Confidence::Medium), confidence);
+
+        let mut input = Cursor::new(b"not a ZIP archive".to_vec());
+        let confidence = scanner.get_confidence("invalid.bin", &mut input);
+        assert_eq!(None, confidence);
+    }
+
+    #[test]
+    fn rejects_path_outside_fid_directory() {
+        let scanner = VarianScanner::new();
+        let mut input = Cursor::new(b"not a ZIP archive".to_vec());
+
+        assert!(!scanner.is_recognized("/nonexistent/s.fid", &mut input));
+        assert!(!scanner.is_recognized("/nonexistent/s.fid/fid", &mut input));
+    }
+
+    #[test]
+    fn reads_fid_directory_only_if_enabled() {
+        let directory =
+            std::env::temp_dir().join(format!("sciformats_varian_scanner_{}", std::process::id()));
+        let fid_directory = directory.join("s.fid");
+        std::fs::create_dir_all(&fid_directory).unwrap();
+        std::fs::write(fid_directory.join("procpar"), "").unwrap();
+        // file header with 16 bit integer values and no blocks
+        let fid: Vec<u8> = [0i32, 0, 0, 2, 0, 0, 0, 0]
+            .iter()
+            .flat_map(|v| v.to_be_bytes())
+            .collect();
+        std::fs::write(fid_directory.join("fid"), fid).unwrap();
+        let path = fid_directory.join("procpar");
+        let path = path.to_str().unwrap();
+        let mut input = Cursor::new(b"not a ZIP archive".to_vec());
+
+        let scanner = VarianScanner::new();
+        let recognized_by_default = scanner.is_recognized(path, &mut input);
+        let confidence_by_default = scanner.get_confidence(path, &mut input);
+        let reader_by_default = scanner.get_reader(path, input.clone());
+        let scanner = VarianScanner {
+            options: ParseOptions {
+                read_directories: true,
+                ..Default::default()
+            },
+        };
+        let recognized = scanner.is_recognized(path, &mut input);
+        let confidence = scanner.get_confidence(path, &mut input);
+        let reader = scanner.get_reader(path, input);
+        std::fs::remove_dir_all(&directory).unwrap();
+
+        assert!(!recognized_by_default);
+        assert_eq!(None, confidence_by_default);
+        assert!(reader_by_default.is_err());
+        assert!(recognized);
+        assert_eq!(Some(Confidence::High), confidence);
+        assert!(reader.is_ok());
+    }
+}
@@ -17,14 +17,25 @@
 // IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
 // CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
-use crate::{api::SeekBufRead, common::SfError};
+use crate::{
+    api::SeekBufRead,
+    common::{SfError, SfErrorKind},
+    utils::lock_input,
+};
 use quick_xml::{
     Reader,
     escape::resolve_predefined_entity,
     events::{BytesStart, Event},
     name::QName,
 };
-use std::{cell::RefCell, collections::HashMap, error::Error, io::BufRead, rc::Rc, str, vec};
+use std::{
+    collections::HashMap,
+    error::Error,
+    io::BufRead,
+    str,
+    sync::{Arc, Mutex},
+    vec,
+};
 
 impl From<quick_xml::Error> for SfError {
     fn from(value: quick_xml::Error) -> Self {
@@ -49,16 +60,16 @@ impl<'buf> BufEvent<'buf> {
     }
 }
 
-type ElemConstructor<'f, 'buf, R, T, E> =
-    &'f dyn Fn(BufEvent<'buf>, &mut Reader<R>) -> Result<(T, BufEvent<'buf>), E>;
+type ElemConstructor<'f, 'buf, R, T> =
+    &'f dyn Fn(BufEvent<'buf>, &mut Reader<R>) -> Result<(T, BufEvent<'buf>), SfError>;
 
-type ElemConstructorRc<'f, 'buf, T, E> = &'f dyn Fn(
+type ElemConstructorRc<'f, 'buf, T> = &'f dyn Fn(
     BufEvent<'buf>,
-    Rc<RefCell<Reader<Box<dyn SeekBufRead>>>>,
-) -> Result<(T, BufEvent<'buf>), E>;
+    Arc<Mutex<Reader<Box<dyn SeekBufRead>>>>,
+) -> Result<(T, BufEvent<'buf>), SfError>;
 
-type ElemConstructorCore<'f, 'buf, Reader, T, E> =
-    &'f mut dyn Fn(BufEvent<'buf>, &mut Reader) -> Result<(T, BufEvent<'buf>), E>;
+type ElemConstructorCore<'f, 'buf, Reader, T> =
+    &'f mut dyn Fn(BufEvent<'buf>, &mut Reader) -> Result<(T, BufEvent<'buf>), SfError>;
 
 pub(super) enum XmlTagStart<'buf> {
     Start(HashMap<QName<'buf>, std::borrow::Cow<'buf, str>>),
@@ -76,7 +87,7 @@ impl XmlTagStart<'_> {
         }
     }
 
-    pub fn parse_req_attr<T, E: Error + 'static>(
+    pub fn parse_req_attr<T, E: Error + Send + Sync + 'static>(
         &self,
         name: &str,
         parse_fn: &dyn Fn(&str) -> Result<T, E>,
@@ -93,7 +104,7 @@ impl XmlTagStart<'_> {
                 .map(|value| value.clone().into_owned()),
         }
     }
-    pub fn parse_opt_attr<T, E: Error + 'static>(
+    pub fn parse_opt_attr<T, E: Error + Send + Sync + 'static>(
         &self,
         name: &str,
         parse_fn: &dyn Fn(&str) -> Result<T, E>,
@@ -104,7 +115,7 @@ impl XmlTagStart<'_> {
             .transpose()
     }
 
-    fn parse_attr_and_map_err<T, E: Error + 'static>(
+    fn parse_attr_and_map_err<T, E: Error + Send + Sync + 'static>(
         value: &str,
         name: &str,
         parse_fn: &dyn Fn(&str) -> Result<T, E>,
@@ -229,9 +240,9 @@ pub(super) fn next_non_whitespace<'buf, R: BufRead>(
 
 pub(super) fn next_non_whitespace_rc(
     next: BufEvent<'_>,
-    reader_ref: Rc<RefCell<Reader<Box<dyn SeekBufRead>>>>,
+    reader_ref: Arc<Mutex<Reader<Box<dyn SeekBufRead>>>>,
 ) -> Result<BufEvent<'_>, SfError> {
-    let mut reader = reader_ref.borrow_mut();
+    let mut reader = lock_input(&reader_ref);
     next_non_whitespace(next, &mut reader)
 }
 
@@ -336,93 +347,93 @@ pub(super) fn consume_end<'buf, R: BufRead>(
 
 pub(super) fn consume_end_rc<'buf>(
     tag_name: &[u8],
-    reader_ref: Rc<RefCell<Reader<Box<dyn SeekBufRead>>>>,
+    reader_ref: Arc<Mutex<Reader<Box<dyn SeekBufRead>>>>,
     next: BufEvent<'buf>,
 ) -> Result<BufEvent<'buf>, SfError> {
-    let mut reader = reader_ref.borrow_mut();
+    let mut reader = lock_input(&reader_ref);
     consume_end(tag_name, &mut reader, next)
 }
 
 #[allow(dead_code)]
-pub(super) fn read_req_elem<'buf, R: BufRead, T, E: Error + From<SfError>>(
+pub(super) fn read_req_elem<'buf, R: BufRead, T>(
     tag_name: &[u8],
     next: BufEvent<'buf>,
     reader: &mut Reader<R>,
-    constructor: ElemConstructor<'_, 'buf, R, T, E>,
-) -> Result<(T, BufEvent<'buf>), E> {
+    constructor: ElemConstructor<'_, 'buf, R, T>,
+) -> Result<(T, BufEvent<'buf>), SfError> {
     let next = next_non_whitespace(next, reader)?;
     read_req_elem_core(tag_name, next, &mut |e| constructor(e, reader))
 }
 
-pub(super) fn read_req_elem_rc<'buf, T, E: Error + From<SfError>>(
+pub(super) fn read_req_elem_rc<'buf, T>(
     tag_name: &[u8],
     next: BufEvent<'buf>,
-    reader_ref: Rc<RefCell<Reader<Box<dyn SeekBufRead>>>>,
-    constructor: ElemConstructorRc<'_, 'buf, T, E>,
-) -> Result<(T, BufEvent<'buf>), E> {
-    let next = next_non_whitespace_rc(next, Rc::clone(&reader_ref))?;
+    reader_ref: Arc<Mutex<Reader<Box<dyn SeekBufRead>>>>,
+    constructor: ElemConstructorRc<'_, 'buf, T>,
+) -> Result<(T, BufEvent<'buf>), SfError> {
+    let next = next_non_whitespace_rc(next, Arc::clone(&reader_ref))?;
     read_req_elem_core(tag_name, next, &mut |e| {
-        constructor(e, Rc::clone(&reader_ref))
+        constructor(e, Arc::clone(&reader_ref))
     })
 }
 
-pub(super) fn read_opt_elem<'buf, R: BufRead, T, E: Error + From<SfError>>(
+pub(super) fn read_opt_elem<'buf, R: BufRead, T>(
     tag_name: &[u8],
     next: BufEvent<'buf>,
     mut reader: &mut Reader<R>,
-    constructor: ElemConstructor<'_, 'buf, R, T, E>,
-) -> Result<(Option<T>, BufEvent<'buf>), E> {
+    constructor: ElemConstructor<'_, 'buf, R, T>,
+) -> Result<(Option<T>, BufEvent<'buf>), SfError> {
     read_opt_elem_core(
         tag_name,
         next,
         &mut reader,
-        &mut |e, r| next_non_whitespace(e, r).map_err(|e| e.into()),
+        &mut |e, r| next_non_whitespace(e, r),
         &mut |e, r| constructor(e, r),
     )
 }
 
-pub(super) fn read_opt_elem_rc<'buf, T, E: Error + From<SfError>>(
+pub(super) fn read_opt_elem_rc<'buf, T>(
     tag_name: &[u8],
     next: BufEvent<'buf>,
-    mut reader_ref: Rc<RefCell<Reader<Box<dyn SeekBufRead>>>>,
-    constructor: ElemConstructorRc<'_, 'buf, T, E>,
-) -> Result<(Option<T>, BufEvent<'buf>), E> {
+    mut reader_ref: Arc<Mutex<Reader<Box<dyn SeekBufRead>>>>,
+    constructor: ElemConstructorRc<'_, 'buf, T>,
+) -> Result<(Option<T>, BufEvent<'buf>), SfError> {
     read_opt_elem_core(
         tag_name,
         next,
         &mut reader_ref,
-        &mut |e, r| next_non_whitespace_rc(e, Rc::clone(r)).map_err(|e| e.into()),
-        &mut |e, r| constructor(e, Rc::clone(r)),
+        &mut |e, r| next_non_whitespace_rc(e, Arc::clone(r)),
+        &mut |e, r| constructor(e, Arc::clone(r)),
     )
 }
 
-pub(super) fn read_sequence<'buf, R: BufRead, T, E: Error + From<SfError>>(
+pub(super) fn read_sequence<'buf, R: BufRead, T>(
     tag_name: &[u8],
     next: BufEvent<'buf>,
     mut reader: &mut Reader<R>,
-    constructor: ElemConstructor<'_, 'buf, R, T, E>,
-) -> Result<(Vec<T>, BufEvent<'buf>), E> {
+    constructor: ElemConstructor<'_, 'buf, R, T>,
+) -> Result<(Vec<T>, BufEvent<'buf>), SfError> {
     read_sequence_core(
         tag_name,
         next,
         &mut reader,
-        &mut |e, r| next_non_whitespace(e, r).map_err(|e| e.into()),
+        &mut |e, r| next_non_whitespace(e, r),
         &mut |e, r| constructor(e, r),
     )
 }
 
-pub(super) fn read_sequence_rc<'buf, T, E: Error + From<SfError>>(
+pub(super) fn read_sequence_rc<'buf, T>(
     tag_name: &[u8],
     next: BufEvent<'buf>,
-    mut reader_ref: Rc<RefCell<Reader<Box<dyn SeekBufRead>>>>,
-    constructor: ElemConstructorRc<'_, 'buf, T, E>,
-) -> Result<(Vec<T>, BufEvent<'buf>), E> {
+    mut reader_ref: Arc<Mutex<Reader<Box<dyn SeekBufRead>>>>,
+    constructor: ElemConstructorRc<'_, 'buf, T>,
+) -> Result<(Vec<T>, BufEvent<'buf>), SfError> {
     read_sequence_core(
         tag_name,
         next,
         &mut reader_ref,
-        &mut |e, r| next_non_whitespace_rc(e, Rc::clone(r)).map_err(|e| e.into()),
-        &mut |e, r| constructor(e, Rc::clone(r)),
+        &mut |e, r| next_non_whitespace_rc(e, Arc::clone(r)),
+        &mut |e, r| constructor(e, Arc::clone(r)),
     )
 }
 
@@ -448,6 +459,8 @@ pub(super) fn read_req_elem_value_f64<'buf, R: BufRead>(
     let value_f64 = value.parse::<f64>().map_err(|e| {
         let tag = String::from_utf8_lossy(tag_name);
         SfError::from_source(e, format!("Illegal value for {}: {}", tag, value))
+            .with_kind(SfErrorKind::CorruptData)
+            .prepend_element_path(&tag)
     })?;
 
     Ok((value_f64, next))
@@ -469,6 +482,21 @@ fn check_matches_tag_name(tag: &[u8], bytes_start: &BytesStart<'_>) -> Result<()
     }
 }
 
+/// Provides the value of an optional attribute of a start or empty tag read without a
+/// [`BufEvent`].
+pub(super) fn get_opt_attr<R>(
+    bytes_start: &BytesStart<'_>,
+    name: &str,
+    reader: &Reader<R>,
+) -> Option<String> {
+    bytes_start
+        .try_get_attribute(name)
+        .ok()
+        .flatten()
+        .and_then(|attr| attr.decode_and_unescape_value(reader.decoder()).ok())
+        .map(|value| value.into_owned())
+}
+
 fn read_attributes<'buf, R>(
     bytes_start: &'buf BytesStart<'buf>,
     reader: &Reader<R>,
@@ -487,15 +515,16 @@ fn read_attributes<'buf, R>(
         .collect::<HashMap<_, _>>()
 }
 
-fn read_req_elem_core<'buf, T, E: Error + From<SfError>>(
+fn read_req_elem_core<'buf, T>(
     tag_name: &[u8],
     next: BufEvent<'buf>,
-    wrapped_constructor: &mut dyn FnMut(BufEvent<'buf>) -> Result<(T, BufEvent<'buf>), E>,
-) -> Result<(T, BufEvent<'buf>), E> {
+    wrapped_constructor: &mut dyn FnMut(BufEvent<'buf>) -> Result<(T, BufEvent<'buf>), SfError>,
+) -> Result<(T, BufEvent<'buf>), SfError> {
     match &next.event {
         Event::Start(bytes) => {
             if bytes.name().as_ref() == tag_name {
                 wrapped_constructor(next)
+                    .map_err(|e| e.prepend_element_path(&String::from_utf8_lossy(tag_name)))
             } else {
                 Err(SfError::new(&format!(
                     "Unexpected start tag: {:?}",
@@ -510,18 +539,19 @@ fn read_req_elem_core<'buf, T, E: Error + From<SfError>>(
     }
 }
 
-fn read_opt_elem_core<'buf, T, Reader, E: Error>(
+fn read_opt_elem_core<'buf, T, Reader>(
     tag_name: &[u8],
     next: BufEvent<'buf>,
     reader: &mut Reader,
-    next_non_ws: &mut dyn Fn(BufEvent<'buf>, &mut Reader) -> Result<BufEvent<'buf>, E>,
-    wrapped_constructor: ElemConstructorCore<'_, 'buf, Reader, T, E>,
-) -> Result<(Option<T>, BufEvent<'buf>), E> {
+    next_non_ws: &mut dyn Fn(BufEvent<'buf>, &mut Reader) -> Result<BufEvent<'buf>, SfError>,
+    wrapped_constructor: ElemConstructorCore<'_, 'buf, Reader, T>,
+) -> Result<(Option<T>, BufEvent<'buf>), SfError> {
     let next = next_non_ws(next, reader)?;
     match &next.event {
         Event::Start(bytes) => {
             if bytes.name().as_ref() == tag_name {
-                let (elem, next) = wrapped_constructor(next, reader)?;
+                let (elem, next) = wrapped_constructor(next, reader)
+                    .map_err(|e| e.prepend_element_path(&String::from_utf8_lossy(tag_name)))?;
                 Ok((Some(elem), next))
             } else {
                 Ok((None, next))
@@ -531,25 +561,24 @@ fn read_opt_elem_core<'buf, T, Reader, E: Error>(
     }
 }
 
-fn read_sequence_core<'buf, T, Reader, E: Error>(
+fn read_sequence_core<'buf, T, Reader>(
     tag_name: &[u8],
     next: BufEvent<'buf>,
     reader: &mut Reader,
-    next_non_ws: &mut dyn Fn(BufEvent<'buf>, &mut Reader) -> Result<BufEvent<'buf>, E>,
-    wrapped_constructor: ElemConstructorCore<'_, 'buf, Reader, T, E>,
-) -> Result<(Vec<T>, BufEvent<'buf>), E> {
+    next_non_ws: &mut dyn Fn(BufEvent<'buf>, &mut Reader) -> Result<BufEvent<'buf>, SfError>,
+    wrapped_constructor: ElemConstructorCore<'_, 'buf, Reader, T>,
+) -> Result<(Vec<T>, BufEvent<'buf>), SfError> {
     let mut next = next_non_ws(next, reader)?;
     let mut ret = vec![];
     loop {
         match &next.event {
-            Event::Start(bytes) | Event::Empty(bytes) => {
-                if bytes.name().as_ref() == tag_name {
-                    let res = wrapped_constructor(next, reader)?;
-                    ret.push(res.0);
-                    next = next_non_ws(res.1, reader)?;
-                } else {
-                    return Ok((ret, next));
-                }
+            Event::Start(bytes) | Event::Empty(bytes) if bytes.name().as_ref() == tag_name => {
+                let res = wrapped_constructor(next, reader).map_err(|e| {
+                    let tag = String::from_utf8_lossy(tag_name);
+                    e.prepend_element_path(&format!("{}[{}]", tag, ret.len()))
+                })?;
+                ret.push(res.0);
+                next = next_non_ws(res.1, reader)?;
             }
             _ => return Ok((ret, next)),
         }
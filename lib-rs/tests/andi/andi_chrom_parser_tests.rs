@@ -20,7 +20,8 @@
 use super::{ANDI_CHROM_QUIRKS, ANDI_CHROM_VALID, ANDI_NON_CDF_DUMMY, open_file};
 use sciformats::{
     andi::{AndiDatasetCompleteness, andi_chrom_parser::AndiChromParser},
-    api::Parser,
+    api::{ParseOptions, Parser},
+    common::{SfErrorKind, SfErrorLocation},
 };
 use std::str::FromStr;
 
@@ -82,7 +83,7 @@ fn andi_chrom_parse_valid_succeeds() {
     );
     let error_log = &admin_data.error_log;
     assert_eq!(2, error_log.len());
-    assert_eq!("error 1", error_log.get(0).unwrap());
+    assert_eq!("error 1", error_log.first().unwrap());
     assert_eq!("error 2", error_log.get(1).unwrap());
 
     let sample_description = &chrom.sample_description;
@@ -140,7 +141,7 @@ fn andi_chrom_parse_valid_succeeds() {
         ],
         raw_data.get_ordinate_values().unwrap()
     );
-    assert_eq!(true, raw_data.uniform_sampling_flag);
+    assert!(raw_data.uniform_sampling_flag);
     assert!(raw_data.get_raw_data_retention().unwrap().is_none());
     assert_eq!("1:2", raw_data.autosampler_position.as_ref().unwrap());
 
@@ -185,7 +186,7 @@ fn andi_chrom_parse_valid_succeeds() {
 
     let peaks = peak_processing_results.get_peaks().unwrap().unwrap();
 
-    let peak_0 = peaks.get(0).unwrap();
+    let peak_0 = peaks.first().unwrap();
     assert_eq_f32(10.111, peak_0.peak_retention_time.unwrap());
     assert_eq!("ref", peak_0.peak_name.as_ref().unwrap());
     assert_eq_f32(110.1111, peak_0.peak_amount.unwrap());
@@ -207,7 +208,7 @@ fn andi_chrom_parse_valid_succeeds() {
     assert!(peak_0.peak_asymmetry.is_none());
     assert!(peak_0.peak_efficiency.is_none());
     assert!(peak_0.mass_on_column.is_none());
-    assert_eq!(false, peak_0.manually_reintegrated_peaks);
+    assert!(!peak_0.manually_reintegrated_peaks);
     assert_eq!("seconds", peak_0.peak_retention_unit);
     assert_eq!("ppm", peak_0.peak_amount_unit.as_ref().unwrap());
     assert_eq!("au", peak_0.detector_unit.as_ref().unwrap());
@@ -236,7 +237,7 @@ fn andi_chrom_parse_valid_succeeds() {
     assert!(peak_2.peak_asymmetry.is_none());
     assert!(peak_2.peak_efficiency.is_none());
     assert!(peak_2.mass_on_column.is_none());
-    assert_eq!(false, peak_2.manually_reintegrated_peaks);
+    assert!(!peak_2.manually_reintegrated_peaks);
     assert_eq!("seconds", peak_2.peak_retention_unit);
     assert_eq!("ppm", peak_2.peak_amount_unit.as_ref().unwrap());
     assert_eq!("au", peak_2.detector_unit.as_ref().unwrap());
@@ -247,7 +248,7 @@ fn andi_chrom_parse_invalid_fails() {
     let (path, file) = open_file(ANDI_NON_CDF_DUMMY);
     let chrom = AndiChromParser::parse(&path, file);
 
-    assert!(chrom.is_err());
+    assert_eq!(SfErrorKind::CorruptData, chrom.unwrap_err().kind());
 }
 
 #[test]
@@ -263,6 +264,50 @@ fn andi_chrom_parse_quirks() {
     );
 }
 
+#[test]
+fn andi_chrom_parse_quirks_provides_warnings() {
+    let (path, file) = open_file(ANDI_CHROM_QUIRKS);
+    let chrom = AndiChromParser::parse(&path, file).unwrap();
+
+    let warnings = chrom.warnings;
+    assert_eq!(5, warnings.len());
+    assert!(warnings[0].message.contains("dataset_completeness"));
+    assert_eq!(None, warnings[0].location);
+    assert!(warnings[1].message.contains("uniform_sampling_flag"));
+    assert_eq!(
+        Some(SfErrorLocation::Variable("ordinate_values".to_owned())),
+        warnings[1].location
+    );
+    assert!(warnings[2].message.contains("detector_units"));
+    assert!(warnings[3].message.contains("retention_units"));
+    assert!(warnings[4].message.contains("peak_amount_units"));
+}
+
+#[test]
+fn andi_chrom_parse_valid_provides_no_warnings() {
+    let (path, file) = open_file(ANDI_CHROM_VALID);
+    let chrom = AndiChromParser::parse(&path, file).unwrap();
+
+    assert!(chrom.warnings.is_empty());
+}
+
+#[test]
+fn andi_chrom_parse_quirks_strictly_fails() {
+    let (path, file) = open_file(ANDI_CHROM_QUIRKS);
+    let error =
+        AndiChromParser::parse_with_options(&path, file, &ParseOptions::strict()).unwrap_err();
+
+    assert_eq!(SfErrorKind::CorruptData, error.kind());
+    assert!(error.to_string().contains("dataset_completeness"));
+    assert!(error.to_string().contains("peak_amount_units"));
+}
+
+#[test]
+fn andi_chrom_parse_valid_strictly_succeeds() {
+    let (path, file) = open_file(ANDI_CHROM_VALID);
+    assert!(AndiChromParser::parse_with_options(&path, file, &ParseOptions::strict()).is_ok());
+}
+
 #[test]
 fn andi_chrom_file_prints_debug_info() {
     let (path, file) = open_file(ANDI_CHROM_VALID);
@@ -18,10 +18,14 @@
 // CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
 use super::{ANDI_CHROM_QUIRKS, ANDI_CHROM_VALID, open_file};
+use crate::assert_read_data_matches_node_data;
 use sciformats::{
     andi::{andi_chrom_parser::AndiChromParser, andi_chrom_reader::AndiChromReader},
-    api::{Column, Parameter, Parser, Reader, Value},
+    api::{Column, ExportFormat, Parameter, Parser, Reader, SeekBufRead, Value},
+    common::SfErrorKind,
+    jdx::{jdx_parser::JdxParser, jdx_reader::JdxReader},
 };
+use std::io::Cursor;
 
 fn assert_eq_f64(left: f64, right: f64) {
     let max = left.max(right);
@@ -260,9 +264,9 @@ fn andi_chrom_read_valid_succeeds() {
         (80f64, 10000f64),
         (90f64, 10000f64),
     ];
-    for i in 0..10 {
-        assert_eq_f64(expect_data[i].0, raw_data.data[i].x);
-        assert_eq_f64(expect_data[i].1, raw_data.data[i].y);
+    for (i, (x, y)) in expect_data.iter().enumerate() {
+        assert_eq_f64(*x, raw_data.data[i].x);
+        assert_eq_f64(*y, raw_data.data[i].y);
     }
     assert_eq!(2, raw_data.metadata.len());
     assert_eq!(
@@ -424,6 +428,7 @@ fn andi_chrom_read_quirks() {
 
     let raw_data = reader.read("/3");
     assert!(raw_data.is_ok());
+    assert_eq!(5, reader.get_warnings().len());
 }
 
 #[test]
@@ -433,5 +438,48 @@ fn andi_chrom_read_illegal_node_path_fails() {
     let reader = AndiChromReader::new(&path, chrom);
 
     let illegal_path_data = reader.read("/5");
-    assert!(illegal_path_data.is_err());
+    assert_eq!(
+        SfErrorKind::IllegalNodePath,
+        illegal_path_data.unwrap_err().kind()
+    );
+}
+
+#[test]
+fn andi_chrom_read_data_range_succeeds() {
+    for file_name in [ANDI_CHROM_VALID, ANDI_CHROM_QUIRKS] {
+        let (path, file) = open_file(file_name);
+        let chrom = AndiChromParser::parse(&path, file).unwrap();
+        let reader = AndiChromReader::new(&path, chrom);
+
+        assert_read_data_matches_node_data(&reader, "/3");
+        assert_read_data_matches_node_data(&reader, "/4");
+    }
+}
+
+#[test]
+fn andi_chrom_jdx_export_roundtrip_succeeds() {
+    let (path, file) = open_file(ANDI_CHROM_VALID);
+    let chrom = AndiChromParser::parse(&path, file).unwrap();
+    let reader = AndiChromReader::new(&path, chrom);
+
+    let mut exported_content = vec![];
+    reader
+        .export(ExportFormat::Jdx, &mut exported_content)
+        .unwrap();
+    let export_input: Box<dyn SeekBufRead> = Box::new(Cursor::new(exported_content));
+    let export = JdxParser::parse("export.jdx", export_input).unwrap();
+    let export_reader = JdxReader::new("export.jdx", export);
+
+    let raw_data = reader.read("/3").unwrap();
+    let exported_raw_data = export_reader.read("/4").unwrap();
+    assert_eq!(raw_data.name, exported_raw_data.name);
+    assert_eq!(raw_data.data.len(), exported_raw_data.data.len());
+    for (point, exported_point) in raw_data.data.iter().zip(&exported_raw_data.data) {
+        assert_eq_f64(point.x, exported_point.x);
+        assert_eq_f64(point.y, exported_point.y);
+    }
+
+    let peaks = export_reader.read("/5").unwrap();
+    assert_eq!("Peak Processing Results", peaks.name);
+    assert_eq!(3, peaks.table.unwrap().rows.len());
 }
@@ -95,7 +95,7 @@ fn andi_ms_parse_centroid_file_succeeds() {
     assert_eq!(None, admin_data.pre_experiment_program_name);
     assert_eq!(None, admin_data.post_experiment_program_name);
     assert_eq!(1, admin_data.error_log.len());
-    assert_eq!("Dummy error 1", admin_data.error_log.get(0).unwrap());
+    assert_eq!("Dummy error 1", admin_data.error_log.first().unwrap());
     assert_eq!(1, admin_data.instrument_number.unwrap());
 
     let instrument_data = &ms.instrument_data;
@@ -205,8 +205,8 @@ fn andi_ms_parse_centroid_file_succeeds() {
 
     let raw_data_global = &ms.raw_data_global;
     assert_eq!(2, raw_data_global.scan_number);
-    assert_eq!(true, raw_data_global.has_masses);
-    assert_eq!(true, raw_data_global.has_times);
+    assert!(raw_data_global.has_masses);
+    assert!(raw_data_global.has_times);
     assert_eq!(1.0, raw_data_global.mass_axis_scale_factor);
     assert_eq!(1.0, raw_data_global.time_axis_scale_factor);
     assert_eq!(1.0, raw_data_global.intensity_axis_scale_factor);
@@ -249,7 +249,7 @@ fn andi_ms_parse_centroid_file_succeeds() {
     assert_eq!(None, raw_data_global.calibrated_mass_range_max);
     assert_eq!(None, raw_data_global.actual_run_time);
     assert_eq!(None, raw_data_global.actual_delay_time);
-    assert_eq!(true, raw_data_global.uniform_sampling_flag);
+    assert!(raw_data_global.uniform_sampling_flag);
     assert_eq!(None, raw_data_global.comments);
 
     let raw_data_scans = &ms.raw_data_scans;
@@ -408,7 +408,7 @@ fn andi_ms_parse_continuum_file_succeeds() {
     assert_eq!(None, admin_data.pre_experiment_program_name);
     assert_eq!(None, admin_data.post_experiment_program_name);
     assert_eq!(1, admin_data.error_log.len());
-    assert_eq!("Dummy error 1", admin_data.error_log.get(0).unwrap());
+    assert_eq!("Dummy error 1", admin_data.error_log.first().unwrap());
     assert_eq!(None, admin_data.instrument_number);
 
     let instrument_data = &ms.instrument_data;
@@ -470,8 +470,8 @@ fn andi_ms_parse_continuum_file_succeeds() {
 
     let raw_data_global = &ms.raw_data_global;
     assert_eq!(2, raw_data_global.scan_number);
-    assert_eq!(true, raw_data_global.has_masses);
-    assert_eq!(false, raw_data_global.has_times);
+    assert!(raw_data_global.has_masses);
+    assert!(!raw_data_global.has_times);
     assert_eq!(1.0, raw_data_global.mass_axis_scale_factor);
     assert_eq!(1.0, raw_data_global.time_axis_scale_factor);
     assert_eq!(1.0, raw_data_global.intensity_axis_scale_factor);
@@ -511,7 +511,7 @@ fn andi_ms_parse_continuum_file_succeeds() {
     assert_eq!(None, raw_data_global.calibrated_mass_range_max);
     assert_eq!(None, raw_data_global.actual_run_time);
     assert_eq!(None, raw_data_global.actual_delay_time);
-    assert_eq!(true, raw_data_global.uniform_sampling_flag);
+    assert!(raw_data_global.uniform_sampling_flag);
     assert_eq!(None, raw_data_global.comments);
 
     let raw_data_scans = &ms.raw_data_scans;
@@ -686,7 +686,7 @@ fn andi_ms_parse_library_file_succeeds() {
     assert_eq!(1, admin_data.error_log.len());
     assert_eq!(
         "                                                               ",
-        admin_data.error_log.get(0).unwrap()
+        admin_data.error_log.first().unwrap()
     );
     assert_eq!(None, admin_data.instrument_number);
 
@@ -749,8 +749,8 @@ fn andi_ms_parse_library_file_succeeds() {
 
     let raw_data_global = &ms.raw_data_global;
     assert_eq!(3, raw_data_global.scan_number);
-    assert_eq!(true, raw_data_global.has_masses);
-    assert_eq!(false, raw_data_global.has_times);
+    assert!(raw_data_global.has_masses);
+    assert!(!raw_data_global.has_times);
     assert_eq!(1.0, raw_data_global.mass_axis_scale_factor);
     assert_eq!(1.0, raw_data_global.time_axis_scale_factor);
     assert_eq!(1.0, raw_data_global.intensity_axis_scale_factor);
@@ -790,7 +790,7 @@ fn andi_ms_parse_library_file_succeeds() {
     assert_eq!(None, raw_data_global.calibrated_mass_range_max);
     assert_eq!(None, raw_data_global.actual_run_time);
     assert_eq!(None, raw_data_global.actual_delay_time);
-    assert_eq!(true, raw_data_global.uniform_sampling_flag);
+    assert!(raw_data_global.uniform_sampling_flag);
     assert_eq!(None, raw_data_global.comments);
 
     let raw_data_scans = &ms.raw_data_scans;
@@ -1058,7 +1058,7 @@ fn andi_ms_parse_sid_file_succeeds() {
     assert_eq!(None, admin_data.pre_experiment_program_name);
     assert_eq!(None, admin_data.post_experiment_program_name);
     assert_eq!(1, admin_data.error_log.len());
-    assert_eq!("Dummy error 1", admin_data.error_log.get(0).unwrap());
+    assert_eq!("Dummy error 1", admin_data.error_log.first().unwrap());
     assert_eq!(None, admin_data.instrument_number);
 
     let instrument_data = &ms.instrument_data;
@@ -1120,8 +1120,8 @@ fn andi_ms_parse_sid_file_succeeds() {
 
     let raw_data_global = &ms.raw_data_global;
     assert_eq!(1, raw_data_global.scan_number);
-    assert_eq!(true, raw_data_global.has_masses);
-    assert_eq!(false, raw_data_global.has_times);
+    assert!(raw_data_global.has_masses);
+    assert!(!raw_data_global.has_times);
     assert_eq!(1.0, raw_data_global.mass_axis_scale_factor);
     assert_eq!(1.0, raw_data_global.time_axis_scale_factor);
     assert_eq!(1.0, raw_data_global.intensity_axis_scale_factor);
@@ -1167,7 +1167,7 @@ fn andi_ms_parse_sid_file_succeeds() {
     assert_eq!(None, raw_data_global.calibrated_mass_range_max);
     assert_eq!(None, raw_data_global.actual_run_time);
     assert_eq!(None, raw_data_global.actual_delay_time);
-    assert_eq!(true, raw_data_global.uniform_sampling_flag);
+    assert!(raw_data_global.uniform_sampling_flag);
     assert_eq!(None, raw_data_global.comments);
 
     let raw_data_scans = &ms.raw_data_scans;
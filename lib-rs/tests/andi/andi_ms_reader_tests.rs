@@ -18,6 +18,7 @@
 // CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
 use super::{ANDI_MS_CENTROID, ANDI_MS_CONTINUUM, ANDI_MS_LIBRARY, ANDI_MS_SID, open_file};
+use crate::assert_read_data_matches_node_data;
 use sciformats::{
     andi::{
         andi_enums::{
@@ -1463,10 +1464,7 @@ fn andi_ms_library_read_succeeds() {
         library_data_0.parameters[1]
     );
     assert_eq!(
-        Parameter::from_str_str(
-            "Entry ID",
-            String::from_iter(std::iter::repeat(' ').take(31))
-        ),
+        Parameter::from_str_str("Entry ID", String::from_iter(std::iter::repeat_n(' ', 31))),
         library_data_0.parameters[2]
     );
     assert_eq!(
@@ -1476,15 +1474,12 @@ fn andi_ms_library_read_succeeds() {
     assert_eq!(
         Parameter::from_str_str(
             "Source Data File Reference",
-            String::from_iter(std::iter::repeat(' ').take(31))
+            String::from_iter(std::iter::repeat_n(' ', 31))
         ),
         library_data_0.parameters[4]
     );
     assert_eq!(
-        Parameter::from_str_str(
-            "CAS Name",
-            String::from_iter(std::iter::repeat(' ').take(254))
-        ),
+        Parameter::from_str_str("CAS Name", String::from_iter(std::iter::repeat_n(' ', 254))),
         library_data_0.parameters[5]
     );
     assert_eq!(
@@ -1498,14 +1493,14 @@ fn andi_ms_library_read_succeeds() {
     assert_eq!(
         Parameter::from_str_str(
             "Other Name 2",
-            String::from_iter(std::iter::repeat(' ').take(254))
+            String::from_iter(std::iter::repeat_n(' ', 254))
         ),
         library_data_0.parameters[8]
     );
     assert_eq!(
         Parameter::from_str_str(
             "Other Name 3",
-            String::from_iter(std::iter::repeat(' ').take(254))
+            String::from_iter(std::iter::repeat_n(' ', 254))
         ),
         library_data_0.parameters[9]
     );
@@ -1520,7 +1515,7 @@ fn andi_ms_library_read_succeeds() {
     assert_eq!(
         Parameter::from_str_str(
             "Wiswesser Notation",
-            String::from_iter(std::iter::repeat(' ').take(127))
+            String::from_iter(std::iter::repeat_n(' ', 127))
         ),
         library_data_0.parameters[12]
     );
@@ -1531,21 +1526,21 @@ fn andi_ms_library_read_succeeds() {
     assert_eq!(
         Parameter::from_str_str(
             "Other Structure Notation",
-            String::from_iter(std::iter::repeat(' ').take(127))
+            String::from_iter(std::iter::repeat_n(' ', 127))
         ),
         library_data_0.parameters[14]
     );
     assert_eq!(
         Parameter::from_str_str(
             "Retention Index Type",
-            String::from_iter(std::iter::repeat(' ').take(31))
+            String::from_iter(std::iter::repeat_n(' ', 31))
         ),
         library_data_0.parameters[15]
     );
     assert_eq!(
         Parameter::from_str_str(
             "Retention Reference Name",
-            String::from_iter(std::iter::repeat(' ').take(127))
+            String::from_iter(std::iter::repeat_n(' ', 127))
         ),
         library_data_0.parameters[16]
     );
@@ -1556,7 +1551,7 @@ fn andi_ms_library_read_succeeds() {
     assert_eq!(
         Parameter::from_str_str(
             "Other Information",
-            String::from_iter(std::iter::repeat(' ').take(254))
+            String::from_iter(std::iter::repeat_n(' ', 254))
         ),
         library_data_0.parameters[18]
     );
@@ -1577,10 +1572,7 @@ fn andi_ms_library_read_succeeds() {
         library_data_2.parameters[1]
     );
     assert_eq!(
-        Parameter::from_str_str(
-            "Entry ID",
-            String::from_iter(std::iter::repeat(' ').take(31))
-        ),
+        Parameter::from_str_str("Entry ID", String::from_iter(std::iter::repeat_n(' ', 31))),
         library_data_2.parameters[2]
     );
     assert_eq!(
@@ -1590,15 +1582,12 @@ fn andi_ms_library_read_succeeds() {
     assert_eq!(
         Parameter::from_str_str(
             "Source Data File Reference",
-            String::from_iter(std::iter::repeat(' ').take(31))
+            String::from_iter(std::iter::repeat_n(' ', 31))
         ),
         library_data_2.parameters[4]
     );
     assert_eq!(
-        Parameter::from_str_str(
-            "CAS Name",
-            String::from_iter(std::iter::repeat(' ').take(254))
-        ),
+        Parameter::from_str_str("CAS Name", String::from_iter(std::iter::repeat_n(' ', 254))),
         library_data_2.parameters[5]
     );
     assert_eq!(
@@ -1612,14 +1601,14 @@ fn andi_ms_library_read_succeeds() {
     assert_eq!(
         Parameter::from_str_str(
             "Other Name 2",
-            String::from_iter(std::iter::repeat(' ').take(254))
+            String::from_iter(std::iter::repeat_n(' ', 254))
         ),
         library_data_2.parameters[8]
     );
     assert_eq!(
         Parameter::from_str_str(
             "Other Name 3",
-            String::from_iter(std::iter::repeat(' ').take(254))
+            String::from_iter(std::iter::repeat_n(' ', 254))
         ),
         library_data_2.parameters[9]
     );
@@ -1634,7 +1623,7 @@ fn andi_ms_library_read_succeeds() {
     assert_eq!(
         Parameter::from_str_str(
             "Wiswesser Notation",
-            String::from_iter(std::iter::repeat(' ').take(127))
+            String::from_iter(std::iter::repeat_n(' ', 127))
         ),
         library_data_2.parameters[12]
     );
@@ -1645,21 +1634,21 @@ fn andi_ms_library_read_succeeds() {
     assert_eq!(
         Parameter::from_str_str(
             "Other Structure Notation",
-            String::from_iter(std::iter::repeat(' ').take(127))
+            String::from_iter(std::iter::repeat_n(' ', 127))
         ),
         library_data_2.parameters[14]
     );
     assert_eq!(
         Parameter::from_str_str(
             "Retention Index Type",
-            String::from_iter(std::iter::repeat(' ').take(31))
+            String::from_iter(std::iter::repeat_n(' ', 31))
         ),
         library_data_2.parameters[15]
     );
     assert_eq!(
         Parameter::from_str_str(
             "Retention Reference Name",
-            String::from_iter(std::iter::repeat(' ').take(127))
+            String::from_iter(std::iter::repeat_n(' ', 127))
         ),
         library_data_2.parameters[16]
     );
@@ -1670,7 +1659,7 @@ fn andi_ms_library_read_succeeds() {
     assert_eq!(
         Parameter::from_str_str(
             "Other Information",
-            String::from_iter(std::iter::repeat(' ').take(254))
+            String::from_iter(std::iter::repeat_n(' ', 254))
         ),
         library_data_2.parameters[18]
     );
@@ -2050,10 +2039,10 @@ fn andi_ms_sid_read_succeeds() {
             assert_eq!("Resolution", k);
             match v {
                 Value::F64(value) => assert!(value.is_nan()),
-                _ => assert!(false),
+                _ => panic!(),
             }
         }
-        _ => assert!(false),
+        _ => panic!(),
     }
     assert_eq!(
         vec![PointXy::new(20.0f64, 100f64), PointXy::new(21.0f64, 200f64),],
@@ -2116,11 +2105,11 @@ fn andi_ms_sid_read_succeeds() {
     assert_eq!(Value::F64(20f64), table_row_0["mass"]);
     match table_row_0["sampling_time"] {
         Value::F64(value) => assert!(value.is_nan()),
-        _ => assert!(false),
+        _ => panic!(),
     }
     match table_row_0["delay_time"] {
         Value::F64(value) => assert!(value.is_nan()),
-        _ => assert!(false),
+        _ => panic!(),
     }
     let table_row_1 = &table.rows[1];
     assert_eq!(3, table_row_1.len());
@@ -2139,3 +2128,50 @@ fn andi_ms_read_illegal_node_path_fails() {
     let illegal_path_data = reader.read("/7");
     assert!(illegal_path_data.is_err());
 }
+
+#[test]
+fn andi_ms_read_data_range_succeeds() {
+    for (file_name, paths) in [
+        (ANDI_MS_CENTROID, vec!["/5/0", "/5/0/0"]),
+        (ANDI_MS_CONTINUUM, vec!["/5/0"]),
+        (ANDI_MS_LIBRARY, vec!["/5/0", "/5/2", "/5/0/0"]),
+        (ANDI_MS_SID, vec!["/5/0", "/6/0"]),
+    ] {
+        let (path, file) = open_file(file_name);
+        let ms = AndiMsParser::parse(&path, file).unwrap();
+        let reader = AndiMsReader::new(&path, ms);
+
+        for path in paths {
+            assert_read_data_matches_node_data(&reader, path);
+        }
+    }
+}
+
+#[test]
+fn andi_ms_read_raw_data_scans_array_succeeds() {
+    let (path, file) = open_file(ANDI_MS_CONTINUUM);
+    let ms = AndiMsParser::parse(&path, file).unwrap();
+    let reader = AndiMsReader::new(&path, ms);
+
+    let nd_array = reader.read("/5").unwrap().nd_array.unwrap();
+    assert_eq!("Intensity", nd_array.name);
+    assert_eq!(Some("Arbitrary Intensity Units".to_owned()), nd_array.unit);
+    assert_eq!(vec![2, 10], nd_array.get_shape());
+    assert_eq!("Scan Acquisition Time", nd_array.axes[0].name);
+    assert_eq!(vec![0.1, 0.2], nd_array.axes[0].values);
+    assert_eq!("Mass", nd_array.axes[1].name);
+    assert_eq!(Some("M/Z".to_owned()), nd_array.axes[1].unit);
+    assert_eq!(35.0, nd_array.axes[1].values[0]);
+    assert_eq!(Some(1000.0), nd_array.get(&[0, 5]));
+    assert_eq!(Some(2000.0), nd_array.get(&[1, 5]));
+}
+
+#[test]
+fn andi_ms_read_raw_data_scans_array_requires_shared_axis() {
+    // scans of centroided data have different m/z values
+    let (path, file) = open_file(ANDI_MS_CENTROID);
+    let ms = AndiMsParser::parse(&path, file).unwrap();
+    let reader = AndiMsReader::new(&path, ms);
+
+    assert!(reader.read("/5").unwrap().nd_array.is_none());
+}
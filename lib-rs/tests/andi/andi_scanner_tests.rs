@@ -18,7 +18,11 @@
 // CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
 use super::{ANDI_CHROM_VALID, ANDI_MS_CENTROID, ANDI_NON_ANDI_CDF, ANDI_NON_CDF_DUMMY, open_file};
-use sciformats::{andi::andi_scanner::AndiScanner, api::Scanner};
+use sciformats::{
+    andi::andi_scanner::AndiScanner,
+    api::{Confidence, Scanner},
+    common::SfErrorKind,
+};
 use std::io::{Cursor, Seek};
 
 #[test]
@@ -61,7 +65,7 @@ fn andi_scanner_rejects_no_extension_file() {
     let scanner = AndiScanner::new();
     let mut cursor = Cursor::new(vec![0x43u8, 0x44u8, 0x46u8]);
     let path = "no_extension_file_name";
-    assert!(!scanner.is_recognized(&path, &mut cursor));
+    assert!(!scanner.is_recognized(path, &mut cursor));
 }
 
 #[test]
@@ -69,7 +73,7 @@ fn andi_scanner_rejects_unrecognized_extension_file() {
     let scanner = AndiScanner::new();
     let mut cursor = Cursor::new(vec![0x43u8, 0x44u8, 0x46u8]);
     let path = "unrecognized_extension_file_name.abc";
-    assert!(!scanner.is_recognized(&path, &mut cursor));
+    assert!(!scanner.is_recognized(path, &mut cursor));
 }
 
 #[test]
@@ -77,7 +81,7 @@ fn andi_scanner_rejects_too_short_file() {
     let scanner = AndiScanner::new();
     let mut cursor = Cursor::new(vec![0x43u8, 0x44u8]);
     let path = "file_name.cdf";
-    assert!(!scanner.is_recognized(&path, &mut cursor));
+    assert!(!scanner.is_recognized(path, &mut cursor));
 }
 
 #[test]
@@ -86,5 +90,34 @@ fn andi_scanner_recognizes_non_andi_cdf_file_but_fails_reading() {
     let (path, mut file) = open_file(ANDI_NON_ANDI_CDF);
     assert!(scanner.is_recognized(&path, &mut file));
     let _ = file.seek(std::io::SeekFrom::Start(0));
-    assert!(scanner.get_reader(&path, file).is_err());
+    let error = scanner.get_reader(&path, file).err().unwrap();
+    assert_eq!(SfErrorKind::UnsupportedFormat, error.kind());
+}
+
+#[test]
+fn andi_scanner_provides_confidence() {
+    let scanner = AndiScanner::new();
+    let (path, mut file) = open_file(ANDI_CHROM_VALID);
+    assert_eq!(
+        Some(Confidence::High),
+        scanner.get_confidence(&path, &mut file)
+    );
+
+    let (path, mut file) = open_file(ANDI_NON_CDF_DUMMY);
+    assert_eq!(
+        Some(Confidence::Low),
+        scanner.get_confidence(&path, &mut file)
+    );
+
+    let mut cursor = Cursor::new(vec![0x43u8, 0x44u8, 0x46u8, 0x01u8]);
+    assert_eq!(
+        Some(Confidence::Medium),
+        scanner.get_confidence("no_extension_file_name", &mut cursor)
+    );
+
+    let mut cursor = Cursor::new(vec![0x43u8, 0x44u8, 0x46u8, 0x03u8]);
+    assert_eq!(
+        None,
+        scanner.get_confidence("no_extension_file_name", &mut cursor)
+    );
 }
@@ -0,0 +1,126 @@
+// Copyright (c) 2025 Robert Schiwon
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use super::{BRUKER_ZIP_FILE, open_file};
+#[cfg(feature = "sync")]
+use crate::assert_concurrent_reads_match_nodes;
+use crate::assert_read_data_matches_node_data;
+use sciformats::{
+    api::{Parameter, Parser, PointXy, Reader, Scanner, Value},
+    bruker::{bruker_parser::BrukerParser, bruker_reader::BrukerReader},
+    common::{ScannerRepository, SfErrorKind, SharedSeekRead},
+};
+
+#[test]
+fn bruker_read_zip_succeeds() {
+    let (path, file) = open_file(BRUKER_ZIP_FILE);
+    let data_set = BrukerParser::parse(&path, file).unwrap();
+    let reader = BrukerReader::new(&path, data_set);
+
+    let root = reader.read("/").unwrap();
+    assert_eq!(BRUKER_ZIP_FILE, root.name);
+    assert_eq!(vec!["fid", "pdata/1"], root.child_node_names);
+    assert!(
+        root.parameters
+            .contains(&Parameter::from_str_str("$NUC1", "1H"))
+    );
+    assert!(root.parameters.contains(&Parameter::from_str_i64("$TD", 8)));
+    assert!(
+        root.parameters
+            .contains(&Parameter::from_str_str("$P", "10 12.5 0 0"))
+    );
+
+    let fid = reader.read("/0").unwrap();
+    assert_eq!("fid", fid.name);
+    assert_eq!(
+        vec![Parameter::from_str_f64(".OBSERVE FREQUENCY", 400.13)],
+        fid.parameters
+    );
+    assert_eq!(
+        vec![
+            PointXy::new(0.0, 200.0),
+            PointXy::new(0.001, 100.0),
+            PointXy::new(0.002, 50.0),
+            PointXy::new(0.003, 24.0),
+        ],
+        fid.data
+    );
+    let complex_data = fid.complex_data.unwrap();
+    assert_eq!(vec![-20.0, -10.0, -4.0, -2.0], complex_data.imaginary);
+    assert_eq!(Some("SECONDS".to_owned()), complex_data.axis.unit);
+    assert_read_data_matches_node_data(&reader, "/0");
+
+    let spectrum = reader.read("/1").unwrap();
+    assert_eq!("pdata/1", spectrum.name);
+    assert_eq!(
+        Parameter::from_str_str("title", "Test spectrum"),
+        spectrum.parameters[0]
+    );
+    assert_eq!(
+        vec![
+            PointXy::new(10.0, 1.0),
+            PointXy::new(7.5, 2.0),
+            PointXy::new(5.0, 100.0),
+            PointXy::new(2.5, 3.0),
+        ],
+        spectrum.data
+    );
+    assert!(
+        spectrum
+            .metadata
+            .contains(&("x.unit".to_owned(), "PPM".to_owned()))
+    );
+    assert_eq!(
+        vec![4.0, -2.0, 0.0, 1.0],
+        spectrum.complex_data.unwrap().imaginary
+    );
+    assert_read_data_matches_node_data(&reader, "/1");
+
+    assert_eq!(
+        SfErrorKind::IllegalNodePath,
+        reader.read("/2").unwrap_err().kind()
+    );
+}
+
+#[test]
+fn bruker_zip_is_recognized_by_scanner_repository() {
+    let repo = ScannerRepository::init_all();
+    let (path, file) = open_file(BRUKER_ZIP_FILE);
+    let mut input = SharedSeekRead::new(file);
+    assert!(repo.is_recognized(&path, &mut input));
+
+    let reader = repo.get_reader(&path, input).unwrap();
+    let root = reader.read("/").unwrap();
+    assert_eq!(
+        Some(&Parameter::KeyValue("$SW_h".to_owned(), Value::I64(1000))),
+        root.parameters
+            .iter()
+            .find(|p| matches!(p, Parameter::KeyValue(key, _) if key == "$SW_h"))
+    );
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn bruker_reader_supports_concurrent_reads() {
+    let (path, file) = open_file(BRUKER_ZIP_FILE);
+    let data_set = BrukerParser::parse(&path, file).unwrap();
+    let reader = BrukerReader::new(&path, data_set);
+
+    assert_concurrent_reads_match_nodes(&reader);
+}
@@ -18,16 +18,20 @@
 // CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
 use super::open_files;
+#[cfg(feature = "sync")]
+use crate::assert_concurrent_reads_match_nodes;
 use sciformats::{
-    api::{Scanner, SeekRead},
-    common::{BufSeekRead, ScannerRepository},
+    api::{Confidence, ParseOptions, Scanner},
+    common::{BufSeekRead, ScannerCandidate, ScannerRepository, SfErrorKind, SharedSeekRead},
 };
 
 open_files!(
     "../andi/resources/",
     (
         (ANDI_CHROM_VALID_FILE_PATH, "andi_chrom_valid.cdf"),
+        (ANDI_CHROM_QUIRKS_FILE_PATH, "andi_chrom_quirks.cdf"),
         (ANDI_INVALID_FILE_PATH, "dummy.cdf"),
+        (ANDI_NON_ANDI_FILE_PATH, "non_andi.cdf"),
     )
 );
 
@@ -35,7 +39,7 @@ open_files!(
 fn scanner_repository_recognizes_valid_file() {
     let repo = ScannerRepository::init_all();
     let (path, file) = open_file(ANDI_CHROM_VALID_FILE_PATH);
-    let mut input: Box<dyn SeekRead> = Box::new(file);
+    let mut input = SharedSeekRead::new(file);
     assert!(repo.is_recognized(&path, &mut input));
 }
 
@@ -43,7 +47,7 @@ fn scanner_repository_recognizes_valid_file() {
 fn scanner_repository_rejects_invalid_file() {
     let repo = ScannerRepository::init_all();
     let (path, file) = open_file(ANDI_INVALID_FILE_PATH);
-    let mut input: Box<dyn SeekRead> = Box::new(file);
+    let mut input = SharedSeekRead::new(file);
     assert!(!repo.is_recognized(&path, &mut input));
 }
 
@@ -51,7 +55,7 @@ fn scanner_repository_rejects_invalid_file() {
 fn scanner_repository_returns_reader_for_valid_file() {
     let repo = ScannerRepository::init_all();
     let (path, file) = open_file(ANDI_CHROM_VALID_FILE_PATH);
-    let input: Box<dyn SeekRead> = Box::new(file);
+    let input = SharedSeekRead::new(file);
     assert!(repo.get_reader(&path, input).is_ok());
 }
 
@@ -59,16 +63,84 @@ fn scanner_repository_returns_reader_for_valid_file() {
 fn scanner_repository_returns_error_for_valid_file() {
     let repo = ScannerRepository::init_all();
     let (path, file) = open_file(ANDI_INVALID_FILE_PATH);
-    let input: Box<dyn SeekRead> = Box::new(file);
+    let input = SharedSeekRead::new(file);
     assert!(repo.get_reader(&path, input).is_err());
 }
 
+#[test]
+fn scanner_repository_recognizes_valid_file_by_content() {
+    let mut repo = ScannerRepository::init_all();
+    let (_, file) = open_file(ANDI_CHROM_VALID_FILE_PATH);
+    let mut input = SharedSeekRead::new(file);
+    assert!(repo.is_recognized("upload.bin", &mut input));
+    assert!(repo.get_reader("upload.bin", input).is_ok());
+
+    repo.set_strict_extensions(true);
+    let (_, file) = open_file(ANDI_CHROM_VALID_FILE_PATH);
+    let mut input = SharedSeekRead::new(file);
+    assert!(!repo.is_recognized("upload.bin", &mut input));
+}
+
+#[test]
+fn scanner_repository_applies_parse_options() {
+    let mut repo = ScannerRepository::init_all();
+    let (path, file) = open_file(ANDI_CHROM_QUIRKS_FILE_PATH);
+    let input = SharedSeekRead::new(file);
+    let reader = repo.get_reader(&path, input).unwrap();
+    assert!(!reader.get_warnings().is_empty());
+
+    repo.set_parse_options(&ParseOptions::strict());
+    let (path, file) = open_file(ANDI_CHROM_QUIRKS_FILE_PATH);
+    let input = SharedSeekRead::new(file);
+    let error = repo.get_reader(&path, input).err().unwrap();
+    assert_eq!(SfErrorKind::CorruptData, error.kind());
+
+    let (path, file) = open_file(ANDI_CHROM_VALID_FILE_PATH);
+    let input = SharedSeekRead::new(file);
+    assert!(repo.get_reader(&path, input).is_ok());
+}
+
+#[test]
+fn scanner_repository_reports_failing_scanners() {
+    let repo = ScannerRepository::init_all();
+    let (path, file) = open_file(ANDI_NON_ANDI_FILE_PATH);
+    let input = SharedSeekRead::new(file);
+    let error = repo.get_reader(&path, input).err().unwrap();
+    assert!(error.to_string().contains("AnDI: "));
+    assert_eq!(SfErrorKind::UnsupportedFormat, error.kind());
+}
+
+#[test]
+fn scanner_repository_lists_candidates() {
+    let repo = ScannerRepository::init_all();
+    let (path, file) = open_file(ANDI_INVALID_FILE_PATH);
+    let mut input = SharedSeekRead::new(file);
+    let candidates = repo.get_candidates(&path, &mut input).unwrap();
+    assert_eq!(
+        vec![ScannerCandidate {
+            name: "AnDI".to_owned(),
+            confidence: Confidence::Low,
+        }],
+        candidates
+    );
+}
+
 #[test]
 fn buf_seek_read_allows_valid_file_reading() {
     let repo = ScannerRepository::init_all();
     let (path, file) = open_file(ANDI_CHROM_VALID_FILE_PATH);
     let buf_seek_read = BufSeekRead::new(file);
-    let input: Box<dyn SeekRead> = Box::new(buf_seek_read);
+    let input = SharedSeekRead::new(buf_seek_read);
     let reader = repo.get_reader(&path, input).unwrap();
     assert!(reader.read("/").is_ok());
 }
+
+#[cfg(feature = "sync")]
+#[test]
+fn readers_support_concurrent_reads() {
+    let repo = ScannerRepository::init_all();
+    let (path, file) = open_file(ANDI_CHROM_VALID_FILE_PATH);
+    let input = SharedSeekRead::new(file);
+    let reader = repo.get_reader(&path, input).unwrap();
+    assert_concurrent_reads_match_nodes(reader.as_ref());
+}
@@ -21,7 +21,7 @@ use super::{GAML_SAMPLE_FILE, open_file};
 use sciformats::{
     api::Parser,
     gaml::gaml_parser::{
-        Byteorder, Experiment, Format, GamlParser, Integrity, Link, Parameter, Peaktable,
+        Byteorder, Experiment, Format, GamlParser, IntegrityStatus, Link, Parameter, Peaktable,
         Technique, Trace, Units, Valueorder, Version, Xdata,
     },
 };
@@ -33,13 +33,10 @@ fn gaml_parse_valid_succeeds() {
 
     assert_eq!(Version::Version1_20, gaml.version);
     assert_eq!(Some("Gaml Test File".to_owned()), gaml.name);
-    assert_eq!(
-        Some(Integrity {
-            algorithm: "SHA1".to_owned(),
-            value: "03cfd743661f07975fa2f1220c5194cbaff48451".to_owned()
-        }),
-        gaml.integrity
-    );
+    let integrity = gaml.integrity.unwrap();
+    assert_eq!("SHA1", integrity.algorithm);
+    assert_eq!("03cfd743661f07975fa2f1220c5194cbaff48451", integrity.value);
+    assert_eq!(IntegrityStatus::Invalid, integrity.get_status().unwrap());
 
     let gaml_parameters = gaml.parameters;
     assert_eq!(3, gaml_parameters.len());
@@ -17,12 +17,20 @@
 // IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
 // CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
-use super::{GAML_SAMPLE_FILE, open_file};
+use super::{GAML_INTEGRITY_FILE, GAML_SAMPLE_FILE, open_file};
+#[cfg(feature = "sync")]
+use crate::assert_concurrent_reads_match_nodes;
+use crate::assert_read_data_matches_node_data;
 use sciformats::{
-    api::{Column, Parameter, Parser, PointXy, Reader, Table, Value},
+    api::{
+        Column, ExportFormat, NodeLink, Parameter, ParseOptions, Parser, PointXy, Reader, Table,
+        Value,
+    },
+    common::SfErrorKind,
     gaml::{gaml_parser::GamlParser, gaml_reader::GamlReader},
+    json::{json_parser::JsonParser, json_reader::JsonReader},
 };
-use std::collections::HashMap;
+use std::{collections::HashMap, io::Cursor};
 
 #[test]
 fn gaml_parse_valid_succeeds() {
@@ -34,7 +42,7 @@ fn gaml_parse_valid_succeeds() {
 
     assert_eq!(GAML_SAMPLE_FILE, &root.name);
     let root_parameters = &root.parameters;
-    assert_eq!(6, root_parameters.len());
+    assert_eq!(7, root_parameters.len());
 
     assert_eq!(
         Parameter::from_str_str("Version", "1.20"),
@@ -51,26 +59,30 @@ fn gaml_parse_valid_succeeds() {
         ),
         root_parameters[2]
     );
+    assert_eq!(
+        Parameter::from_str_str("Integrity status", "invalid"),
+        root_parameters[3]
+    );
     assert_eq!(
         Parameter::from_str_str(
             "GAML parameter name 0 (group=GAML parameter group 0, label=GAML parameter label 0, alias=GAML parameter alias 0)",
             "GAML parameter value 0"
         ),
-        root_parameters[3]
+        root_parameters[4]
     );
     assert_eq!(
         Parameter::from_str_str(
             "GAML parameter name 1 (group=GAML parameter group 0, label=GAML parameter label 1, alias=GAML parameter alias 1)",
             "GAML parameter value 1"
         ),
-        root_parameters[4]
+        root_parameters[5]
     );
     assert_eq!(
         Parameter::from_str_str(
             "GAML parameter name 2 (group=GAML parameter group 1, label=GAML parameter label 2, alias=GAML parameter alias 2)",
             "GAML parameter value 2"
         ),
-        root_parameters[5]
+        root_parameters[6]
     );
 
     assert_eq!(Vec::<PointXy>::new(), root.data);
@@ -939,3 +951,214 @@ fn parse_peaktables0000_succeeds(reader: &GamlReader) {
     );
     assert!(basecurve0.child_node_names.is_empty());
 }
+
+#[test]
+fn gaml_read_data_range_succeeds() {
+    let (path, file) = open_file(GAML_SAMPLE_FILE);
+    let gaml = GamlParser::parse(&path, file).unwrap();
+    let reader = GamlReader::new(&path, gaml);
+
+    for path in [
+        "/0/0/0",
+        "/0/0/1",
+        "/1/0/0",
+        "/1/0/1",
+        "/1/1/0",
+        "/0/0/0/0",
+        "/0/0/0/0/0",
+    ] {
+        assert_read_data_matches_node_data(&reader, path);
+    }
+}
+
+#[test]
+fn gaml_read_valid_integrity_succeeds() {
+    let (path, file) = open_file(GAML_INTEGRITY_FILE);
+    let gaml = GamlParser::parse(&path, file).unwrap();
+    let reader = GamlReader::new(&path, gaml);
+
+    let root = reader.read("/").unwrap();
+    assert_eq!(
+        vec![
+            Parameter::from_str_str("Version", "1.20"),
+            Parameter::from_str_str("Name", "Integrity Test File"),
+            Parameter::from_str_str(
+                "Integrity (algorithm=SHA-256)",
+                "9ef60dc28f6ffd1e7d088737bd9a25d8e4950344caecd773e914a377a105b2bb"
+            ),
+            Parameter::from_str_str("Integrity status", "valid"),
+            Parameter::from_str_str("Operator", "Analyst"),
+        ],
+        root.parameters
+    );
+}
+
+#[test]
+fn gaml_strict_integrity_rejects_mismatching_checksum() {
+    let options = ParseOptions {
+        strict_integrity: true,
+        ..Default::default()
+    };
+
+    let (path, file) = open_file(GAML_INTEGRITY_FILE);
+    assert!(GamlParser::parse_with_options(&path, file, &options).is_ok());
+
+    let (path, file) = open_file(GAML_SAMPLE_FILE);
+    let error = GamlParser::parse_with_options(&path, file, &options)
+        .err()
+        .unwrap();
+    assert_eq!(SfErrorKind::CorruptData, error.kind());
+    assert!(error.to_string().contains("does not match"));
+}
+
+#[test]
+fn gaml_gaml_export_roundtrip_succeeds() {
+    let (path, file) = open_file(GAML_SAMPLE_FILE);
+    let gaml = GamlParser::parse(&path, file).unwrap();
+    let gaml_reader = GamlReader::new(&path, gaml);
+
+    let mut exported_content = vec![];
+    gaml_reader
+        .export(ExportFormat::Gaml, &mut exported_content)
+        .unwrap();
+    let export = GamlParser::parse("export.gaml", Cursor::new(exported_content)).unwrap();
+    let export_reader = GamlReader::new("export.gaml", export);
+
+    // one experiment per xy data node
+    let mut xy_paths = vec![];
+    let root = gaml_reader.read("/").unwrap();
+    for e in 0..root.child_node_names.len() {
+        let experiment = gaml_reader.read(&format!("/{e}")).unwrap();
+        for t in 0..experiment.child_node_names.len() {
+            let trace = gaml_reader.read(&format!("/{e}/{t}")).unwrap();
+            for xy in 0..trace.child_node_names.len() {
+                xy_paths.push(format!("/{e}/{t}/{xy}"));
+            }
+        }
+    }
+    let export_root = export_reader.read("/").unwrap();
+    assert_eq!(root.parameters, export_root.parameters[2..]);
+    assert_eq!(xy_paths.len(), export_root.child_node_names.len());
+
+    for (i, original_path) in xy_paths.iter().enumerate() {
+        let original = gaml_reader.read(original_path).unwrap();
+        let exported = export_reader.read(&format!("/{i}/0/0")).unwrap();
+        assert_eq!(original.data, exported.data);
+        assert_eq!(original.metadata, exported.metadata);
+        assert_eq!(
+            original.child_node_names.len(),
+            exported.child_node_names.len()
+        );
+        for p in 0..original.child_node_names.len() {
+            let original_peaks = gaml_reader
+                .read(&format!("{original_path}/{p}"))
+                .unwrap()
+                .table;
+            let exported_peaks = export_reader.read(&format!("/{i}/0/0/{p}")).unwrap().table;
+            assert_eq!(original_peaks, exported_peaks);
+        }
+    }
+}
+
+#[test]
+fn gaml_read_links_succeeds() {
+    let (path, file) = open_file(GAML_SAMPLE_FILE);
+    let gaml = GamlParser::parse(&path, file).unwrap();
+    let reader = GamlReader::new(&path, gaml);
+
+    assert!(reader.read("/").unwrap().links.is_empty());
+    assert!(reader.read("/0").unwrap().links.is_empty());
+    // coordinates of trace 0/0 linking to Xdata and altXdata
+    assert_eq!(
+        vec![
+            NodeLink::new("Xdata", "/0/0/0"),
+            NodeLink::new("altXdata", "/0/0/1"),
+        ],
+        reader.read("/0/0").unwrap().links
+    );
+    assert_eq!(
+        vec![NodeLink::new("coordinates", "/0/0")],
+        reader.read("/0/0/0").unwrap().links
+    );
+    assert_eq!(
+        vec![NodeLink::new("coordinates", "/0/0")],
+        reader.read("/0/0/1").unwrap().links
+    );
+    // Xdata with two Ydata represented by two nodes
+    assert_eq!(
+        vec![
+            NodeLink::new("Xdata", "/1/0/0"),
+            NodeLink::new("Xdata", "/1/0/1"),
+        ],
+        reader.read("/1/0").unwrap().links
+    );
+    assert_eq!(
+        vec![NodeLink::new("coordinates", "/1/0")],
+        reader.read("/1/0/1").unwrap().links
+    );
+    // linkid without references
+    assert!(reader.read("/1/1/0").unwrap().links.is_empty());
+
+    let mut exported_content = vec![];
+    reader
+        .export(ExportFormat::Json, &mut exported_content)
+        .unwrap();
+    let doc = JsonParser::parse("export.json", Cursor::new(exported_content)).unwrap();
+    let json_reader = JsonReader::new("export.json", doc);
+    for path in ["/0/0", "/0/0/0", "/0/0/1", "/1/0", "/1/0/1"] {
+        assert_eq!(
+            reader.read(path).unwrap().links,
+            json_reader.read(path).unwrap().links
+        );
+    }
+}
+
+#[test]
+fn gaml_read_on_demand_matches_full_parsing() {
+    let options = ParseOptions {
+        on_demand: true,
+        ..Default::default()
+    };
+    let (path, file) = open_file(GAML_SAMPLE_FILE);
+    let gaml = GamlParser::parse_with_options(&path, file, &options).unwrap();
+    assert!(gaml.experiments.is_empty());
+    let on_demand_reader = GamlReader::new(&path, gaml);
+    let (path, file) = open_file(GAML_SAMPLE_FILE);
+    let gaml = GamlParser::parse(&path, file).unwrap();
+    let reader = GamlReader::new(&path, gaml);
+
+    parse_experiments_succeeds(&on_demand_reader);
+    let mut paths = vec!["/".to_owned()];
+    while let Some(path) = paths.pop() {
+        let node = reader.read(&path).unwrap();
+        assert_eq!(node, on_demand_reader.read(&path).unwrap());
+        assert_eq!(
+            reader.read_data_len(&path).unwrap(),
+            on_demand_reader.read_data_len(&path).unwrap()
+        );
+        assert_read_data_matches_node_data(&on_demand_reader, &path);
+        let parent = path.trim_end_matches('/');
+        paths.extend((0..node.child_node_names.len()).map(|i| format!("{parent}/{i}")));
+    }
+    // alternate between traces to replace the cached trace
+    for path in ["/0/0/0", "/1/0/0", "/0/0/1", "/0/0/0"] {
+        assert_eq!(
+            reader.read(path).unwrap(),
+            on_demand_reader.read(path).unwrap()
+        );
+    }
+    assert_eq!(
+        SfErrorKind::IllegalNodePath,
+        on_demand_reader.read("/0/2").err().unwrap().kind()
+    );
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn gaml_reader_supports_concurrent_reads() {
+    let (path, file) = open_file(GAML_SAMPLE_FILE);
+    let gaml = GamlParser::parse(&path, file).unwrap();
+    let reader = GamlReader::new(&path, gaml);
+
+    assert_concurrent_reads_match_nodes(&reader);
+}
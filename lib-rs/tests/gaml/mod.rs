@@ -22,4 +22,10 @@ mod gaml_reader_tests;
 
 use super::open_files;
 
-open_files!("resources/", ((GAML_SAMPLE_FILE, "sample_file.gaml"),));
+open_files!(
+    "resources/",
+    (
+        (GAML_SAMPLE_FILE, "sample_file.gaml"),
+        (GAML_INTEGRITY_FILE, "integrity_file.gaml"),
+    )
+);
@@ -18,10 +18,15 @@
 // CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
 mod andi;
+mod bruker;
 mod common;
 mod gaml;
 mod jdx;
+mod jeol;
 mod json;
+mod varian;
+
+use sciformats::{api::Reader, common::SfErrorKind};
 
 /// Provides access to test resources. For non WASM this happens by opening them from the
 /// filesystem, for WASM they are embedded into the binary.
@@ -90,3 +95,48 @@ macro_rules! open_files {
   };
 }
 pub(crate) use open_files;
+
+/// Checks that reading the data of a node by range yields the same points as reading the whole node.
+pub(crate) fn assert_read_data_matches_node_data(reader: &dyn Reader, path: &str) {
+    let data = reader.read(path).unwrap().data;
+    let len = reader.read_data_len(path).unwrap();
+    assert_eq!(data.len(), len);
+    assert_eq!(data, reader.read_data(path, 0..len).unwrap());
+    if len > 1 {
+        assert_eq!(
+            &data[1..len - 1],
+            &reader.read_data(path, 1..len - 1).unwrap()[..]
+        );
+    }
+    assert_eq!(
+        SfErrorKind::IllegalDataRange,
+        reader.read_data(path, 0..len + 1).unwrap_err().kind()
+    );
+}
+
+/// Checks that reading all nodes from multiple threads yields the same nodes as reading them
+/// sequentially.
+#[cfg(feature = "sync")]
+pub(crate) fn assert_concurrent_reads_match_nodes(reader: &dyn Reader) {
+    // breadth-first traversal of all node paths
+    let mut node_paths = vec![String::new()];
+    let mut expected = vec![];
+    while let Some(path) = node_paths.get(expected.len()) {
+        let node = reader.read(path).unwrap();
+        let child_paths: Vec<String> = (0..node.child_node_names.len())
+            .map(|i| format!("{path}/{i}"))
+            .collect();
+        node_paths.extend(child_paths);
+        expected.push(node);
+    }
+
+    std::thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| {
+                for (node_path, expected_node) in node_paths.iter().zip(&expected).rev() {
+                    assert_eq!(expected_node, &reader.read(node_path).unwrap());
+                }
+            });
+        }
+    });
+}
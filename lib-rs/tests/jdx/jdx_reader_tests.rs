@@ -17,12 +17,15 @@
 // IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
 // CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
-use super::{COMPOUND_FILE, open_file};
+use super::{COMPOUND_FILE, STACKED_N_TUPLES_FILE, STRUCTURE_LINK_FILE, open_file};
+use crate::assert_read_data_matches_node_data;
 use sciformats::{
-    api::{Parameter, Parser, Reader, SeekBufRead},
-    jdx::{jdx_parser::JdxParser, jdx_reader::JdxReader},
+    api::{ExportFormat, Exporter, NodeLink, Parameter, Parser, Reader, SeekBufRead, Value},
+    common::SfErrorKind,
+    jdx::{jdx_molfile_exporter::MolfileExporter, jdx_parser::JdxParser, jdx_reader::JdxReader},
+    json::{json_parser::JsonParser, json_reader::JsonReader},
 };
-use std::io::BufReader;
+use std::io::{BufReader, Cursor};
 
 #[test]
 fn jdx_read_valid_succeeds() {
@@ -45,3 +48,362 @@ fn jdx_read_valid_succeeds() {
     let audit_trail = &reader.read("/8/0").unwrap();
     assert!(audit_trail.table.is_some());
 }
+
+#[test]
+fn jdx_read_data_range_succeeds() {
+    let (path, file) = open_file(COMPOUND_FILE);
+    let buf_reader = BufReader::new(file);
+    let buf_input: Box<dyn SeekBufRead> = Box::new(buf_reader);
+    let parser = JdxParser::parse(&path, buf_input).unwrap();
+    let reader = JdxReader::new(&path, parser);
+
+    for path in [
+        "/0", "/1", "/2", "/3/0/0", "/3/0/1", "/4", "/6", "/7/0/0", "/8/0",
+    ] {
+        assert_read_data_matches_node_data(&reader, path);
+    }
+    assert_eq!(2, reader.read_data("/0", 0..2).unwrap().len());
+    assert!(reader.read_data("/0", 1..3).is_err());
+    assert_eq!(
+        SfErrorKind::IllegalNodePath,
+        reader.read("/0/0").unwrap_err().kind()
+    );
+}
+
+#[test]
+fn jdx_read_n_tuples_array_succeeds() {
+    let (path, file) = open_file(STACKED_N_TUPLES_FILE);
+    let buf_reader = BufReader::new(file);
+    let buf_input: Box<dyn SeekBufRead> = Box::new(buf_reader);
+    let parser = JdxParser::parse(&path, buf_input).unwrap();
+    let reader = JdxReader::new(&path, parser);
+
+    let n_tuples_node = reader.read("/0").unwrap();
+    let nd_array = n_tuples_node.nd_array.unwrap();
+    assert_eq!("ABSORBANCE", nd_array.name);
+    assert_eq!(Some("ABSORBANCE".to_owned()), nd_array.unit);
+    assert_eq!(vec![2, 4], nd_array.get_shape());
+    assert_eq!("TIME", nd_array.axes[0].name);
+    assert_eq!(Some("SECONDS".to_owned()), nd_array.axes[0].unit);
+    assert_eq!(vec![0.0, 60.0], nd_array.axes[0].values);
+    assert_eq!("WAVELENGTH", nd_array.axes[1].name);
+    assert_eq!(Some("NANOMETERS".to_owned()), nd_array.axes[1].unit);
+    assert_eq!(vec![200.0, 210.0, 220.0, 230.0], nd_array.axes[1].values);
+    assert_eq!(Some(0.1), nd_array.get(&[0, 0]));
+    assert_eq!(Some(0.13), nd_array.get(&[0, 3]));
+    assert_eq!(Some(0.2), nd_array.get(&[1, 0]));
+    assert_eq!(Some(0.23), nd_array.get(&[1, 3]));
+
+    // pages hold no n-dimensional data
+    assert!(reader.read("/0/0").unwrap().nd_array.is_none());
+}
+
+#[test]
+fn jdx_does_not_stack_n_tuples_pages_of_different_variables() {
+    let (path, file) = open_file(COMPOUND_FILE);
+    let buf_reader = BufReader::new(file);
+    let buf_input: Box<dyn SeekBufRead> = Box::new(buf_reader);
+    let parser = JdxParser::parse(&path, buf_input).unwrap();
+    let reader = JdxReader::new(&path, parser);
+
+    // real and imaginary pages
+    assert!(reader.read("/3/0").unwrap().nd_array.is_none());
+}
+
+#[test]
+fn jdx_read_n_tuples_complex_data_succeeds() {
+    let (path, file) = open_file(COMPOUND_FILE);
+    let buf_reader = BufReader::new(file);
+    let buf_input: Box<dyn SeekBufRead> = Box::new(buf_reader);
+    let parser = JdxParser::parse(&path, buf_input).unwrap();
+    let reader = JdxReader::new(&path, parser);
+
+    let complex_data = reader.read("/3/0").unwrap().complex_data.unwrap();
+    assert_eq!("SPECTRUM", complex_data.name);
+    assert_eq!(Some("ARBITRARY UNITS".to_owned()), complex_data.unit);
+    assert_eq!("FREQUENCY", complex_data.axis.name);
+    assert_eq!(Some("HZ".to_owned()), complex_data.axis.unit);
+    assert_eq!(4, complex_data.axis.values.len());
+    assert_eq!(50.0, complex_data.real[0]);
+    assert_eq!(410.0, complex_data.imaginary[3]);
+    let magnitude = complex_data.get_magnitude();
+    assert_eq!(50.0f64.hypot(300.0), magnitude[0]);
+    let phase = complex_data.get_phase();
+    assert_eq!(300.0f64.atan2(50.0), phase[0]);
+
+    // both real and imaginary pages provide the complex signal
+    assert_eq!(
+        Some(&complex_data),
+        reader.read("/3/0/0").unwrap().complex_data.as_ref()
+    );
+    assert_eq!(
+        Some(&complex_data),
+        reader.read("/3/0/1").unwrap().complex_data.as_ref()
+    );
+    // real and imaginary pages are provided as complex data instead of an array
+    assert!(reader.read("/3/0").unwrap().nd_array.is_none());
+}
+
+#[test]
+fn jdx_n_tuples_complex_data_json_roundtrip_succeeds() {
+    let (path, file) = open_file(COMPOUND_FILE);
+    let buf_reader = BufReader::new(file);
+    let buf_input: Box<dyn SeekBufRead> = Box::new(buf_reader);
+    let parser = JdxParser::parse(&path, buf_input).unwrap();
+    let jdx_reader = JdxReader::new(&path, parser);
+
+    let mut exported_content = vec![];
+    let mut writer = Cursor::new(&mut exported_content);
+    jdx_reader.export(ExportFormat::Json, &mut writer).unwrap();
+    let doc = JsonParser::parse("export.json", Cursor::new(exported_content)).unwrap();
+    let json_reader = JsonReader::new("export.json", doc);
+
+    let jdx_node = jdx_reader.read("/3/0").unwrap();
+    let json_node = json_reader.read("/3/0").unwrap();
+    assert!(jdx_node.complex_data.is_some());
+    assert_eq!(jdx_node.complex_data, json_node.complex_data);
+    assert!(json_node.nd_array.is_none());
+}
+
+#[test]
+fn jdx_jdx_export_roundtrip_succeeds() {
+    let (path, file) = open_file(COMPOUND_FILE);
+    let buf_reader = BufReader::new(file);
+    let buf_input: Box<dyn SeekBufRead> = Box::new(buf_reader);
+    let parser = JdxParser::parse(&path, buf_input).unwrap();
+    let jdx_reader = JdxReader::new(&path, parser);
+
+    let mut exported_content = vec![];
+    jdx_reader
+        .export(ExportFormat::Jdx, &mut exported_content)
+        .unwrap();
+    let export_input: Box<dyn SeekBufRead> = Box::new(Cursor::new(exported_content));
+    let export = JdxParser::parse("export.jdx", export_input).unwrap();
+    let export_reader = JdxReader::new("export.jdx", export);
+
+    // XYDATA, RADATA, XYPOINTS, NTUPLES pages, PEAK TABLE
+    for (original_path, export_path) in [
+        ("/0", "/0"),
+        ("/1", "/1"),
+        ("/2", "/2"),
+        ("/3/0/0", "/5"),
+        ("/3/0/1", "/6"),
+        ("/4", "/7"),
+    ] {
+        let original = jdx_reader.read(original_path).unwrap();
+        let exported = export_reader.read(export_path).unwrap();
+        assert_eq!(original.name, exported.name);
+        assert_eq!(original.data, exported.data);
+    }
+}
+
+#[test]
+fn jdx_n_tuples_array_json_roundtrip_succeeds() {
+    let (path, file) = open_file(STACKED_N_TUPLES_FILE);
+    let buf_reader = BufReader::new(file);
+    let buf_input: Box<dyn SeekBufRead> = Box::new(buf_reader);
+    let parser = JdxParser::parse(&path, buf_input).unwrap();
+    let jdx_reader = JdxReader::new(&path, parser);
+
+    let mut exported_content = vec![];
+    let mut writer = Cursor::new(&mut exported_content);
+    jdx_reader.export(ExportFormat::Json, &mut writer).unwrap();
+    let doc = JsonParser::parse("export.json", Cursor::new(exported_content)).unwrap();
+    let json_reader = JsonReader::new("export.json", doc);
+
+    let jdx_nd_array = jdx_reader.read("/0").unwrap().nd_array;
+    assert!(jdx_nd_array.is_some());
+    assert_eq!(jdx_nd_array, json_reader.read("/0").unwrap().nd_array);
+}
+
+#[test]
+fn jdx_n_tuples_array_jdx_export_roundtrip_succeeds() {
+    let (path, file) = open_file(STACKED_N_TUPLES_FILE);
+    let buf_reader = BufReader::new(file);
+    let buf_input: Box<dyn SeekBufRead> = Box::new(buf_reader);
+    let parser = JdxParser::parse(&path, buf_input).unwrap();
+    let jdx_reader = JdxReader::new(&path, parser);
+
+    let mut exported_content = vec![];
+    jdx_reader
+        .export(ExportFormat::Jdx, &mut exported_content)
+        .unwrap();
+    let export_input: Box<dyn SeekBufRead> = Box::new(Cursor::new(exported_content));
+    let export = JdxParser::parse("export.jdx", export_input).unwrap();
+    let export_reader = JdxReader::new("export.jdx", export);
+
+    let original = jdx_reader.read("/0").unwrap().nd_array.unwrap();
+    let exported = export_reader.read("/0/0").unwrap().nd_array.unwrap();
+    assert_eq!(original.get_shape(), exported.get_shape());
+    // y values are scaled by YFACTOR
+    for (original_value, exported_value) in original.values.iter().zip(&exported.values) {
+        assert!((original_value - exported_value).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn jdx_read_structure_succeeds() {
+    let (path, file) = open_file(STRUCTURE_LINK_FILE);
+    let buf_reader = BufReader::new(file);
+    let buf_input: Box<dyn SeekBufRead> = Box::new(buf_reader);
+    let parser = JdxParser::parse(&path, buf_input).unwrap();
+    let structure = parser.blocks[0].get_structure().unwrap().unwrap();
+    assert!(parser.blocks[1].get_structure().unwrap().is_none());
+    let reader = JdxReader::new(&path, parser);
+
+    let structure_node = reader.read("/0").unwrap();
+    assert_eq!(
+        vec!["ATOMLIST", "BONDLIST"],
+        structure_node.child_node_names
+    );
+    let atom_list = reader.read("/0/0").unwrap();
+    assert!(
+        atom_list
+            .parameters
+            .contains(&Parameter::from_str_str("MOLFORM", "C 2 H 4 O 2"))
+    );
+    let atoms = atom_list.table.unwrap();
+    assert_eq!(4, atoms.rows.len());
+    assert_eq!(
+        Some(&Value::String("O".to_owned())),
+        atoms.rows[3].get("symbol")
+    );
+    assert_eq!(Some(&Value::F64(1732.0)), atoms.rows[3].get("x_coordinate"));
+    let bonds = reader.read("/0/1").unwrap().table.unwrap();
+    assert_eq!(3, bonds.rows.len());
+    assert_eq!(
+        Some(&Value::String("D".to_owned())),
+        bonds.rows[1].get("bond_type")
+    );
+    assert_eq!(
+        SfErrorKind::IllegalNodePath,
+        reader.read("/0/2").unwrap_err().kind()
+    );
+    assert!(reader.read("/1").unwrap().child_node_names.is_empty());
+
+    let mut molfile = vec![];
+    MolfileExporter::new(&structure)
+        .with_title("Acetic acid")
+        .write(&mut molfile)
+        .unwrap();
+    let molfile = String::from_utf8(molfile).unwrap();
+    assert!(molfile.starts_with("Acetic acid\n"));
+    assert!(molfile.contains("  4  3  0  0  0  0  0  0  0  0999 V2000\n"));
+    assert!(molfile.contains("  2  3  2  0\n"));
+    assert!(molfile.ends_with("M  END\n"));
+}
+
+#[test]
+fn jdx_molfile_export_succeeds() {
+    let (path, file) = open_file(STRUCTURE_LINK_FILE);
+    let buf_reader = BufReader::new(file);
+    let buf_input: Box<dyn SeekBufRead> = Box::new(buf_reader);
+    let parser = JdxParser::parse(&path, buf_input).unwrap();
+    let reader: Box<dyn Reader> = Box::new(JdxReader::new(&path, parser));
+
+    assert!(reader.get_export_formats().contains(&ExportFormat::Molfile));
+    let mut molfile = vec![];
+    reader.export(ExportFormat::Molfile, &mut molfile).unwrap();
+    let molfile = String::from_utf8(molfile).unwrap();
+    assert!(molfile.starts_with("Acetic acid structure\n"));
+    // XY_RASTER bond lengths of 1000 scaled to 1.5 Angstrom
+    assert!(molfile.contains("\n    1.2990    0.7500    0.0000 C "));
+    assert!(molfile.contains("\n    2.5980    0.0000    0.0000 O "));
+    assert!(molfile.ends_with("M  END\n"));
+}
+
+#[test]
+fn jdx_molfile_export_without_structure_fails() {
+    let (path, file) = open_file(COMPOUND_FILE);
+    let buf_reader = BufReader::new(file);
+    let buf_input: Box<dyn SeekBufRead> = Box::new(buf_reader);
+    let parser = JdxParser::parse(&path, buf_input).unwrap();
+    let reader = JdxReader::new(&path, parser);
+
+    assert!(!reader.get_export_formats().contains(&ExportFormat::Molfile));
+    let error = reader
+        .export(ExportFormat::Molfile, &mut vec![])
+        .unwrap_err();
+    assert_eq!(SfErrorKind::UnsupportedFormat, error.kind());
+}
+
+#[test]
+fn jdx_read_by_block_id_path_succeeds() {
+    let (path, file) = open_file(STRUCTURE_LINK_FILE);
+    let buf_reader = BufReader::new(file);
+    let buf_input: Box<dyn SeekBufRead> = Box::new(buf_reader);
+    let parser = JdxParser::parse(&path, buf_input).unwrap();
+    let reader: Box<dyn Reader> = Box::new(JdxReader::new(&path, parser));
+
+    assert_eq!("Acetic acid IR spectrum", reader.read("/#2").unwrap().name);
+    assert_eq!(
+        reader.read_data_len("/1").unwrap(),
+        reader.read_data_len("/#2").unwrap()
+    );
+    assert_eq!(
+        reader.read_data("/1", 0..2).unwrap(),
+        reader.read_data("/#2", 0..2).unwrap()
+    );
+    // child node of a block by BLOCK_ID
+    assert_eq!("ATOMLIST", reader.read("/#1/0").unwrap().name);
+    assert_eq!(
+        SfErrorKind::IllegalNodePath,
+        reader.read("/#4").unwrap_err().kind()
+    );
+}
+
+#[test]
+fn jdx_read_cross_references_succeeds() {
+    let (path, file) = open_file(STRUCTURE_LINK_FILE);
+    let buf_reader = BufReader::new(file);
+    let buf_input: Box<dyn SeekBufRead> = Box::new(buf_reader);
+    let parser = JdxParser::parse(&path, buf_input).unwrap();
+    let reader = JdxReader::new(&path, parser);
+
+    assert!(reader.read("").unwrap().links.is_empty());
+    assert_eq!(
+        vec![
+            NodeLink::new("STRUCTURE", "/0"),
+            NodeLink::new("ATOMLIST", "/0/0"),
+            NodeLink::new("SPECTRUM", "/1"),
+        ],
+        reader.read("/2").unwrap().links
+    );
+    // the peak table for the spectrum
+    assert_eq!(
+        vec![
+            NodeLink::new("STRUCTURE", "/0"),
+            NodeLink::new("INFRARED PEAK ASSIGNMENTS", "/2")
+        ],
+        reader.read("/1").unwrap().links
+    );
+    // the structure for the assignments
+    assert_eq!(
+        vec![
+            NodeLink::new("INFRARED SPECTRUM", "/1"),
+            NodeLink::new("INFRARED PEAK ASSIGNMENTS", "/2"),
+        ],
+        reader.read("/0").unwrap().links
+    );
+
+    assert_eq!("/2", reader.find_block_path("/3").unwrap());
+    assert_eq!(
+        "Acetic acid IR spectrum",
+        reader.read_by_block_id("2").unwrap().name
+    );
+    assert_eq!(
+        SfErrorKind::IllegalNodePath,
+        reader.read_by_block_id("/4").unwrap_err().kind()
+    );
+
+    let mut exported_content = vec![];
+    reader
+        .export(ExportFormat::Json, &mut exported_content)
+        .unwrap();
+    let doc = JsonParser::parse("export.json", Cursor::new(exported_content)).unwrap();
+    let json_reader = JsonReader::new("export.json", doc);
+    assert_eq!(
+        reader.read("/2").unwrap().links,
+        json_reader.read("/2").unwrap().links
+    );
+}
@@ -21,4 +21,11 @@ mod jdx_reader_tests;
 
 use super::open_files;
 
-open_files!("resources/", ((COMPOUND_FILE, "CompoundFile.jdx"),));
+open_files!(
+    "resources/",
+    (
+        (COMPOUND_FILE, "CompoundFile.jdx"),
+        (STRUCTURE_LINK_FILE, "StructureLink.jdx"),
+        (STACKED_N_TUPLES_FILE, "StackedNTuples.jdx"),
+    )
+);
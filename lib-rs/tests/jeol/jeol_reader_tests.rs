@@ -0,0 +1,103 @@
+// Copyright (c) 2025 Robert Schiwon
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use super::{JEOL_FID_FILE, open_file};
+use crate::assert_read_data_matches_node_data;
+use sciformats::{
+    api::{Parameter, Parser, PointXy, Reader, Scanner, Value},
+    common::{ScannerRepository, SfErrorKind, SharedSeekRead},
+    jeol::{jeol_parser::JeolParser, jeol_reader::JeolReader, jeol_scanner::JeolScanner},
+};
+
+#[test]
+fn jeol_read_1d_fid_succeeds() {
+    let (path, file) = open_file(JEOL_FID_FILE);
+    let jdf = JeolParser::parse(&path, file).unwrap();
+    let reader = JeolReader::new(&path, jdf);
+
+    let root = reader.read("/").unwrap();
+    assert_eq!(JEOL_FID_FILE, root.name);
+    assert_eq!(vec!["FID"], root.child_node_names);
+    assert_eq!(
+        Parameter::from_str_str("title", "Ethylbenzene proton"),
+        root.parameters[0]
+    );
+    assert!(
+        root.parameters
+            .contains(&Parameter::from_str_str("creation time", "2025-03-14"))
+    );
+    assert!(
+        root.parameters
+            .contains(&Parameter::from_str_str("X_DOMAIN", "Proton"))
+    );
+    assert!(
+        root.parameters
+            .contains(&Parameter::from_str_i64("SCANS", 16))
+    );
+    assert!(
+        root.parameters
+            .contains(&Parameter::from_str_f64("RELAXATION_DELAY", 4.0))
+    );
+    let table = root.table.unwrap();
+    assert_eq!(5, table.rows.len());
+    assert_eq!(
+        Some(&Value::String("PPM".to_owned())),
+        table.rows[3].get("unit")
+    );
+
+    let fid = reader.read("/0").unwrap();
+    assert_eq!("FID", fid.name);
+    assert_eq!(8, fid.data.len());
+    assert_eq!(PointXy::new(0.0, 1000.0), fid.data[0]);
+    assert_eq!(1000.0 / 128.0, fid.data[7].y);
+    assert!((fid.data[7].x - 0.0007).abs() < 1e-12);
+    assert_eq!(
+        vec![
+            ("x.unit".to_owned(), "SECONDS".to_owned()),
+            ("x.label".to_owned(), "Time".to_owned()),
+        ],
+        fid.metadata
+    );
+    assert_eq!(
+        vec![Parameter::from_str_f64(".OBSERVE FREQUENCY", 399.78219838)],
+        fid.parameters
+    );
+    assert_eq!(-100.0, fid.complex_data.unwrap().imaginary[0]);
+    assert_read_data_matches_node_data(&reader, "/0");
+
+    assert_eq!(
+        SfErrorKind::IllegalNodePath,
+        reader.read("/1").unwrap_err().kind()
+    );
+}
+
+#[test]
+fn jeol_jdf_is_recognized_by_scanner_repository() {
+    let scanner = JeolScanner::new();
+    let (path, mut file) = open_file(JEOL_FID_FILE);
+    assert!(scanner.is_recognized(&path, &mut file));
+
+    let repo = ScannerRepository::init_all();
+    let (path, file) = open_file(JEOL_FID_FILE);
+    let mut input = SharedSeekRead::new(file);
+    assert!(repo.is_recognized(&path, &mut input));
+
+    let reader = repo.get_reader(&path, input).unwrap();
+    assert_eq!(vec!["FID"], reader.read("/").unwrap().child_node_names);
+}
@@ -18,8 +18,9 @@
 // CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
 use super::{EXAMPLE, open_file};
+use crate::assert_read_data_matches_node_data;
 use sciformats::{
-    api::{ExportFormat, Parser, Reader},
+    api::{ExportFormat, Parser, PointXy, Reader},
     json::{json_parser::JsonParser, json_reader::JsonReader},
 };
 use sciformats_serde_json::Value;
@@ -47,3 +48,33 @@ fn json_roundtrip_succeeds() {
 
     assert_eq!(original_json, exported_json);
 }
+
+#[test]
+fn json_read_data_range_succeeds() {
+    let (path, file) = open_file(EXAMPLE);
+    let doc = JsonParser::parse(&path, file).unwrap();
+    let reader = JsonReader::new(&path, doc);
+
+    for path in ["/", "/0", "/1"] {
+        assert_read_data_matches_node_data(&reader, path);
+    }
+    let data = reader.read_data("/", 1..2).unwrap();
+    assert_eq!(vec![PointXy::new(1.0, 1000.01)], data);
+}
+
+#[test]
+fn json_read_nd_array_succeeds() {
+    let (path, file) = open_file(EXAMPLE);
+    let doc = JsonParser::parse(&path, file).unwrap();
+    let reader = JsonReader::new(&path, doc);
+
+    assert!(reader.read("/0").unwrap().nd_array.is_none());
+
+    let nd_array = reader.read("/1").unwrap().nd_array.unwrap();
+    assert_eq!("Intensity", nd_array.name);
+    assert_eq!(Some("arbitrary unit".to_owned()), nd_array.unit);
+    assert_eq!(vec![2, 3], nd_array.get_shape());
+    assert_eq!(Some("s".to_owned()), nd_array.axes[0].unit);
+    assert_eq!(None, nd_array.axes[1].unit);
+    assert_eq!(Some(6.0), nd_array.get(&[1, 2]));
+}
@@ -0,0 +1,108 @@
+// Copyright (c) 2025 Robert Schiwon
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use super::{VARIAN_ARRAYED_FILE, open_file};
+#[cfg(feature = "sync")]
+use crate::assert_concurrent_reads_match_nodes;
+use crate::assert_read_data_matches_node_data;
+use sciformats::{
+    api::{Parameter, Parser, PointXy, Reader, Scanner, Value},
+    common::{ScannerRepository, SfErrorKind, SharedSeekRead},
+    varian::{varian_parser::VarianParser, varian_reader::VarianReader},
+};
+
+#[test]
+fn varian_read_arrayed_zip_succeeds() {
+    let (path, file) = open_file(VARIAN_ARRAYED_FILE);
+    let data_set = VarianParser::parse(&path, file).unwrap();
+    let reader = VarianReader::new(&path, data_set);
+
+    let root = reader.read("/").unwrap();
+    assert_eq!(VARIAN_ARRAYED_FILE, root.name);
+    assert_eq!(vec!["FID 1", "FID 2"], root.child_node_names);
+    assert_eq!(
+        Parameter::from_str_str("text", "Arrayed d1 test"),
+        root.parameters[0]
+    );
+    assert!(root.parameters.contains(&Parameter::from_str_i64("np", 8)));
+    assert!(
+        root.parameters
+            .contains(&Parameter::from_str_str("seqfil", "s2pul"))
+    );
+    assert!(
+        root.parameters
+            .contains(&Parameter::from_str_str("d1", "1 2"))
+    );
+    let table = root.table.unwrap();
+    assert_eq!(2, table.rows.len());
+    assert_eq!(Some(&Value::F64(2.0)), table.rows[1].get("value"));
+
+    let fid_2 = reader.read("/1").unwrap();
+    assert_eq!("FID 2", fid_2.name);
+    assert_eq!(
+        vec![
+            Parameter::from_str_u64("block", 2),
+            Parameter::from_str_u64("trace", 1),
+            Parameter::from_str_i64("ctcount", 4),
+            Parameter::from_str_f64("d1", 2.0),
+            Parameter::from_str_f64(".OBSERVE FREQUENCY", 399.7897),
+        ],
+        fid_2.parameters
+    );
+    assert_eq!(
+        vec![
+            PointXy::new(0.0, 2000.0),
+            PointXy::new(0.00025, 1000.0),
+            PointXy::new(0.0005, 500.0),
+            PointXy::new(0.00075, 250.0),
+        ],
+        fid_2.data
+    );
+    assert_eq!(
+        vec![-200.0, -100.0, -50.0, -25.0],
+        fid_2.complex_data.unwrap().imaginary
+    );
+    assert_read_data_matches_node_data(&reader, "/1");
+
+    assert_eq!(
+        SfErrorKind::IllegalNodePath,
+        reader.read("/2").unwrap_err().kind()
+    );
+}
+
+#[test]
+fn varian_zip_is_recognized_by_scanner_repository() {
+    let repo = ScannerRepository::init_all();
+    let (path, file) = open_file(VARIAN_ARRAYED_FILE);
+    let mut input = SharedSeekRead::new(file);
+    assert!(repo.is_recognized(&path, &mut input));
+
+    let reader = repo.get_reader(&path, input).unwrap();
+    assert_eq!(2, reader.read("/").unwrap().child_node_names.len());
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn varian_reader_supports_concurrent_reads() {
+    let (path, file) = open_file(VARIAN_ARRAYED_FILE);
+    let data_set = VarianParser::parse(&path, file).unwrap();
+    let reader = VarianReader::new(&path, data_set);
+
+    assert_concurrent_reads_match_nodes(&reader);
+}